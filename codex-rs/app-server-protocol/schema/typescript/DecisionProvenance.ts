@@ -0,0 +1,23 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AskForApproval } from "./AskForApproval";
+
+/**
+ * What decided whether a command runs without asking, needs approval, or is
+ * rejected.
+ */
+export type DecisionProvenance = { "type": "known_safe_command" } | { "type": "dangerous_command" } | { "type": "exec_policy_rule", matched_prefix: Array<string>, justification?: string, 
+/**
+ * Rules file that declared the rule. Unset for rules that did not
+ * come from a file, such as those from requirements.
+ */
+file?: string, 
+/**
+ * 1-based line of the `prefix_rule(...)` call in `file`.
+ */
+line?: number, } | { "type": "session_approval" } | { "type": "sandbox_denial_retry" } | { "type": "approval_policy", approval_policy: AskForApproval, } | { "type": "tool_config", tool: string, 
+/**
+ * True for `approval = "always"`, false for `approval = "never"`.
+ */
+always_ask: boolean, };
@@ -1,6 +1,7 @@
 // GENERATED CODE! DO NOT MODIFY BY HAND!
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DecisionProvenance } from "./DecisionProvenance";
 import type { ExecPolicyAmendment } from "./ExecPolicyAmendment";
 import type { ParsedCommand } from "./ParsedCommand";
 
@@ -29,4 +30,8 @@ reason: string | null,
 /**
  * Proposed execpolicy amendment that can be applied to allow future runs.
  */
-proposed_execpolicy_amendment?: ExecPolicyAmendment, parsed_cmd: Array<ParsedCommand>, };
+proposed_execpolicy_amendment?: ExecPolicyAmendment, parsed_cmd: Array<ParsedCommand>, 
+/**
+ * What caused this approval request.
+ */
+provenance?: DecisionProvenance, };
@@ -1,6 +1,7 @@
 // GENERATED CODE! DO NOT MODIFY BY HAND!
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DecisionProvenance } from "./DecisionProvenance";
 import type { ExecCommandSource } from "./ExecCommandSource";
 import type { ParsedCommand } from "./ParsedCommand";
 
@@ -56,4 +57,8 @@ duration: string,
 /**
  * Formatted output from the command, as seen by the model.
  */
-formatted_output: string, };
+formatted_output: string, 
+/**
+ * What allowed the command to run, when it went through approval.
+ */
+provenance?: DecisionProvenance, };
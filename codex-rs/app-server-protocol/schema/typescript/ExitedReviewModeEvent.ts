@@ -3,4 +3,8 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { ReviewOutputEvent } from "./ReviewOutputEvent";
 
-export type ExitedReviewModeEvent = { review_output: ReviewOutputEvent | null, };
+export type ExitedReviewModeEvent = { review_output: ReviewOutputEvent | null, 
+/**
+ * Ghost commit capturing the working tree the review looked at.
+ */
+snapshot_sha?: string, };
@@ -0,0 +1,9 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Provider failure that moves a session to the next entry of its profile's
+ * failover chain.
+ */
+export type FailoverTrigger = "rate_limit" | "server_error" | "stream_idle_timeout" | "context_window_exceeded";
@@ -9,4 +9,28 @@ export type ParsedCommand = { "type": "read", cmd: string, name: string,
  * be resolved against the `cwd`` that will be used to run the command
  * to derive the absolute path.
  */
-path: string, } | { "type": "list_files", cmd: string, path: string | null, } | { "type": "search", cmd: string, query: string | null, path: string | null, } | { "type": "unknown", cmd: string, };
+path: string, } | { "type": "list_files", cmd: string, path: string | null, } | { "type": "search", cmd: string, query: string | null, path: string | null, } | { "type": "write", cmd: string, 
+/**
+ * (Best effort) Paths created or modified by the command, including
+ * output redirection targets. Relative paths resolve against the `cwd`.
+ */
+paths: Array<string>, } | { "type": "delete", cmd: string, 
+/**
+ * (Best effort) Paths removed by the command. Relative paths resolve
+ * against the `cwd`.
+ */
+paths: Array<string>, } | { "type": "install", cmd: string, 
+/**
+ * Packages named on the command line. Empty when installing from a
+ * manifest or lockfile.
+ */
+packages: Array<string>, } | { "type": "build", cmd: string, } | { "type": "test", cmd: string, } | { "type": "git", cmd: string, 
+/**
+ * The git subcommand, e.g. `commit` or `status`. `git grep` and
+ * `git ls-files` are summarized as `search` and `list_files` instead.
+ */
+subcommand: string, } | { "type": "fetch", cmd: string, 
+/**
+ * The URL being requested, when one appears on the command line.
+ */
+url: string | null, } | { "type": "unknown", cmd: string, };
@@ -0,0 +1,12 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FailoverTrigger } from "./FailoverTrigger";
+
+/**
+ * Recorded whenever a session changes provider (and possibly model) through
+ * its failover chain. `trigger` is `None` when the session returns to an
+ * earlier entry whose cool-down has expired; `cooldown_secs` is how long
+ * `from_provider` is skipped after a failure.
+ */
+export type ProviderFailoverEvent = { from_provider: string, from_model: string, to_provider: string, to_model: string, trigger: FailoverTrigger | null, cooldown_secs: bigint | null, };
@@ -0,0 +1,8 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Decision a user recorded for a single review finding.
+ */
+export type ReviewFindingStatus = "accepted" | "dismissed";
@@ -1,9 +1,22 @@
 // GENERATED CODE! DO NOT MODIFY BY HAND!
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ReviewFinding } from "./ReviewFinding";
 
 export type ReviewTarget = { "type": "uncommittedChanges" } | { "type": "baseBranch", branch: string, } | { "type": "commit", sha: string, 
 /**
  * Optional human-readable label (e.g., commit subject) for UIs.
  */
-title: string | null, } | { "type": "custom", instructions: string, };
+title: string | null, } | { "type": "custom", instructions: string, } | { "type": "commitRange", base: string, head: string, } | { "type": "patch", diff: string, 
+/**
+ * Where the patch came from (e.g., a file name), for UIs.
+ */
+label: string | null, } | { "type": "incremental", 
+/**
+ * Snapshot of the working tree taken when the earlier review ran.
+ */
+sinceSha: string, 
+/**
+ * Findings already reported by the earlier review(s).
+ */
+previousFindings: Array<ReviewFinding>, };
@@ -0,0 +1,17 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type SnapshotFileStat = { 
+/**
+ * Path relative to the session cwd.
+ */
+path: string, 
+/**
+ * Lines added, or `None` for binary files.
+ */
+added: bigint | null, 
+/**
+ * Lines removed, or `None` for binary files.
+ */
+removed: bigint | null, };
@@ -0,0 +1,14 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SnapshotTimelineEntry } from "./SnapshotTimelineEntry";
+
+export type SnapshotTimelineEvent = { 
+/**
+ * Snapshots in the order they were captured, oldest first.
+ */
+entries: Array<SnapshotTimelineEntry>, 
+/**
+ * Whether `Op::Redo` has a replaced working tree state to reapply.
+ */
+redo_available: boolean, };
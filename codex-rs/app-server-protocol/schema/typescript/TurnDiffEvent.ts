@@ -2,4 +2,12 @@
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type TurnDiffEvent = { unified_diff: string, };
+export type TurnDiffEvent = { 
+/**
+ * Changes made by the agent during the turn.
+ */
+unified_diff: string, 
+/**
+ * Changes made outside Codex to files the agent touched during the turn.
+ */
+external_unified_diff?: string, };
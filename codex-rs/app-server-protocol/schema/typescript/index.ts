@@ -50,6 +50,7 @@ export type { ConversationGitInfo } from "./ConversationGitInfo";
 export type { ConversationSummary } from "./ConversationSummary";
 export type { CreditsSnapshot } from "./CreditsSnapshot";
 export type { CustomPrompt } from "./CustomPrompt";
+export type { DecisionProvenance } from "./DecisionProvenance";
 export type { DeprecationNoticeEvent } from "./DeprecationNoticeEvent";
 export type { DynamicToolCallRequest } from "./DynamicToolCallRequest";
 export type { ElicitationRequestEvent } from "./ElicitationRequestEvent";
@@ -67,6 +68,7 @@ export type { ExecOneOffCommandResponse } from "./ExecOneOffCommandResponse";
 export type { ExecOutputStream } from "./ExecOutputStream";
 export type { ExecPolicyAmendment } from "./ExecPolicyAmendment";
 export type { ExitedReviewModeEvent } from "./ExitedReviewModeEvent";
+export type { FailoverTrigger } from "./FailoverTrigger";
 export type { FileChange } from "./FileChange";
 export type { ForcedLoginMethod } from "./ForcedLoginMethod";
 export type { ForkConversationParams } from "./ForkConversationParams";
@@ -135,6 +137,7 @@ export type { PlanItemArg } from "./PlanItemArg";
 export type { PlanType } from "./PlanType";
 export type { PreparedContextResponseEvent } from "./PreparedContextResponseEvent";
 export type { Profile } from "./Profile";
+export type { ProviderFailoverEvent } from "./ProviderFailoverEvent";
 export type { RateLimitSnapshot } from "./RateLimitSnapshot";
 export type { RateLimitWindow } from "./RateLimitWindow";
 export type { RawResponseItemEvent } from "./RawResponseItemEvent";
@@ -162,6 +165,8 @@ export type { ResumeConversationResponse } from "./ResumeConversationResponse";
 export type { ReviewCodeLocation } from "./ReviewCodeLocation";
 export type { ReviewDecision } from "./ReviewDecision";
 export type { ReviewFinding } from "./ReviewFinding";
+export type { ReviewFindingStatus } from "./ReviewFindingStatus";
+export type { ReviewFindingStatusEvent } from "./ReviewFindingStatusEvent";
 export type { ReviewLineRange } from "./ReviewLineRange";
 export type { ReviewOutputEvent } from "./ReviewOutputEvent";
 export type { ReviewRequest } from "./ReviewRequest";
@@ -189,6 +194,9 @@ export type { SkillMetadata } from "./SkillMetadata";
 export type { SkillScope } from "./SkillScope";
 export type { SkillToolDependency } from "./SkillToolDependency";
 export type { SkillsListEntry } from "./SkillsListEntry";
+export type { SnapshotFileStat } from "./SnapshotFileStat";
+export type { SnapshotTimelineEntry } from "./SnapshotTimelineEntry";
+export type { SnapshotTimelineEvent } from "./SnapshotTimelineEvent";
 export type { StepStatus } from "./StepStatus";
 export type { StreamErrorEvent } from "./StreamErrorEvent";
 export type { SubAgentSource } from "./SubAgentSource";
@@ -2,4 +2,4 @@
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type CommandAction = { "type": "read", command: string, name: string, path: string, } | { "type": "listFiles", command: string, path: string | null, } | { "type": "search", command: string, query: string | null, path: string | null, } | { "type": "unknown", command: string, };
+export type CommandAction = { "type": "read", command: string, name: string, path: string, } | { "type": "listFiles", command: string, path: string | null, } | { "type": "search", command: string, query: string | null, path: string | null, } | { "type": "write", command: string, paths: Array<string>, } | { "type": "delete", command: string, paths: Array<string>, } | { "type": "install", command: string, packages: Array<string>, } | { "type": "build", command: string, } | { "type": "test", command: string, } | { "type": "git", command: string, subcommand: string, } | { "type": "fetch", command: string, url: string | null, } | { "type": "unknown", command: string, };
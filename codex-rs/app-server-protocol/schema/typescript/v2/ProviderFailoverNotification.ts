@@ -0,0 +1,11 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ProviderFailoverTrigger } from "./ProviderFailoverTrigger";
+
+/**
+ * Notification that the thread moved to another provider (and possibly model) on its
+ * failover chain. `trigger` is null when it returns to an earlier entry whose cool-down
+ * has expired; `cooldownSecs` is how long `fromProvider` is skipped after a failure.
+ */
+export type ProviderFailoverNotification = { threadId: string, turnId: string, fromProvider: string, fromModel: string, toProvider: string, toModel: string, trigger: ProviderFailoverTrigger | null, cooldownSecs: bigint | null, };
@@ -6,4 +6,8 @@ export type ReviewTarget = { "type": "uncommittedChanges" } | { "type": "baseBra
 /**
  * Optional human-readable label (e.g., commit subject) for UIs.
  */
-title: string | null, } | { "type": "custom", instructions: string, };
+title: string | null, } | { "type": "custom", instructions: string, } | { "type": "commitRange", base: string, head: string, } | { "type": "patch", diff: string, 
+/**
+ * Where the patch came from (e.g., a file name), for UIs.
+ */
+label: string | null, } | { "type": "incremental", sinceThreadId: string, };
@@ -0,0 +1,23 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThreadSnapshotFileChange } from "./ThreadSnapshotFileChange";
+
+export type ThreadSnapshot = { 
+/**
+ * Identifier to pass to `thread/snapshot/restore`.
+ */
+id: string, 
+/**
+ * 1-based position of the turn that captured the snapshot.
+ */
+turn: number, 
+/**
+ * The user message that started the turn, if it is still in the thread's history.
+ */
+summary: string | null, 
+/**
+ * Files changed between this snapshot and the next one, or the current working tree
+ * for the latest snapshot.
+ */
+files: Array<ThreadSnapshotFileChange>, };
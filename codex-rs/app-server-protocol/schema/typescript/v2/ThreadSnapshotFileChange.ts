@@ -0,0 +1,17 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ThreadSnapshotFileChange = { 
+/**
+ * Path relative to the thread's working directory.
+ */
+path: string, 
+/**
+ * Lines added, or null for binary files.
+ */
+added: number | null, 
+/**
+ * Lines removed, or null for binary files.
+ */
+removed: number | null, };
@@ -0,0 +1,14 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThreadSnapshot } from "./ThreadSnapshot";
+
+export type ThreadSnapshotListResponse = { 
+/**
+ * Snapshots of the working tree captured at the start of each turn, oldest first.
+ */
+snapshots: Array<ThreadSnapshot>, 
+/**
+ * Whether `thread/snapshot/redo` has a replaced working tree state to reapply.
+ */
+redoAvailable: boolean, };
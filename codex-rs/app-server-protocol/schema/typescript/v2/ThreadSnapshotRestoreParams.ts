@@ -0,0 +1,17 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ThreadSnapshotRestoreParams = { threadId: string, 
+/**
+ * A snapshot id returned by `thread/snapshot/list`.
+ */
+snapshotId: string, 
+/**
+ * Files to restore, relative to the thread's working directory. Every file is
+ * restored when omitted or empty.
+ *
+ * The thread's history is left unchanged. The replaced working tree state can be
+ * brought back with `thread/snapshot/redo`.
+ */
+paths?: Array<string> | null, };
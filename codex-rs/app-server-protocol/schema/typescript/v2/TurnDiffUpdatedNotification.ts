@@ -6,4 +6,8 @@
  * Notification that the turn-level unified diff has changed.
  * Contains the latest aggregated diff across all file changes in the turn.
  */
-export type TurnDiffUpdatedNotification = { threadId: string, turnId: string, diff: string, };
+export type TurnDiffUpdatedNotification = { threadId: string, turnId: string, diff: string, 
+/**
+ * Unified diff of the edits made outside Codex to files changed in the turn, if any.
+ */
+externalDiff?: string, };
@@ -98,6 +98,8 @@ export type { PatchApplyStatus } from "./PatchApplyStatus";
 export type { PatchChangeKind } from "./PatchChangeKind";
 export type { PlanDeltaNotification } from "./PlanDeltaNotification";
 export type { ProfileV2 } from "./ProfileV2";
+export type { ProviderFailoverNotification } from "./ProviderFailoverNotification";
+export type { ProviderFailoverTrigger } from "./ProviderFailoverTrigger";
 export type { RateLimitSnapshot } from "./RateLimitSnapshot";
 export type { RateLimitWindow } from "./RateLimitWindow";
 export type { RawResponseItemCompletedNotification } from "./RawResponseItemCompletedNotification";
@@ -157,6 +159,14 @@ export type { ThreadRollbackParams } from "./ThreadRollbackParams";
 export type { ThreadRollbackResponse } from "./ThreadRollbackResponse";
 export type { ThreadSetNameParams } from "./ThreadSetNameParams";
 export type { ThreadSetNameResponse } from "./ThreadSetNameResponse";
+export type { ThreadSnapshot } from "./ThreadSnapshot";
+export type { ThreadSnapshotFileChange } from "./ThreadSnapshotFileChange";
+export type { ThreadSnapshotListParams } from "./ThreadSnapshotListParams";
+export type { ThreadSnapshotListResponse } from "./ThreadSnapshotListResponse";
+export type { ThreadSnapshotRedoParams } from "./ThreadSnapshotRedoParams";
+export type { ThreadSnapshotRedoResponse } from "./ThreadSnapshotRedoResponse";
+export type { ThreadSnapshotRestoreParams } from "./ThreadSnapshotRestoreParams";
+export type { ThreadSnapshotRestoreResponse } from "./ThreadSnapshotRestoreResponse";
 export type { ThreadSortKey } from "./ThreadSortKey";
 export type { ThreadSourceKind } from "./ThreadSourceKind";
 export type { ThreadStartParams } from "./ThreadStartParams";
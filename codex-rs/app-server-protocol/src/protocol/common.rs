@@ -223,6 +223,18 @@ client_request_definitions! {
         params: v2::ThreadRollbackParams,
         response: v2::ThreadRollbackResponse,
     },
+    ThreadSnapshotList => "thread/snapshot/list" {
+        params: v2::ThreadSnapshotListParams,
+        response: v2::ThreadSnapshotListResponse,
+    },
+    ThreadSnapshotRestore => "thread/snapshot/restore" {
+        params: v2::ThreadSnapshotRestoreParams,
+        response: v2::ThreadSnapshotRestoreResponse,
+    },
+    ThreadSnapshotRedo => "thread/snapshot/redo" {
+        params: v2::ThreadSnapshotRedoParams,
+        response: v2::ThreadSnapshotRedoResponse,
+    },
     ThreadList => "thread/list" {
         params: v2::ThreadListParams,
         response: v2::ThreadListResponse,
@@ -712,6 +724,7 @@ server_notification_definitions! {
     TurnCompleted => "turn/completed" (v2::TurnCompletedNotification),
     TurnDiffUpdated => "turn/diff/updated" (v2::TurnDiffUpdatedNotification),
     TurnPlanUpdated => "turn/plan/updated" (v2::TurnPlanUpdatedNotification),
+    ProviderFailover => "thread/providerFailover" (v2::ProviderFailoverNotification),
     ItemStarted => "item/started" (v2::ItemStartedNotification),
     ItemCompleted => "item/completed" (v2::ItemCompletedNotification),
     /// This event is internal-only. Used by Codex Cloud.
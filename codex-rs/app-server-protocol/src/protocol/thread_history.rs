@@ -1,21 +1,36 @@
+use crate::protocol::v2::CommandExecutionStatus;
+use crate::protocol::v2::FileUpdateChange;
+use crate::protocol::v2::PatchApplyStatus;
+use crate::protocol::v2::PatchChangeKind;
 use crate::protocol::v2::ThreadItem;
 use crate::protocol::v2::Turn;
 use crate::protocol::v2::TurnError;
 use crate::protocol::v2::TurnStatus;
 use crate::protocol::v2::UserInput;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::AgentReasoningEvent;
 use codex_protocol::protocol::AgentReasoningRawContentEvent;
 use codex_protocol::protocol::CompactedItem;
 use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::ExitedReviewModeEvent;
 use codex_protocol::protocol::ItemCompletedEvent;
+use codex_protocol::protocol::ReviewOutputEvent;
+use codex_protocol::protocol::ReviewRequest;
 use codex_protocol::protocol::RolloutItem;
 use codex_protocol::protocol::ThreadRolledBackEvent;
 use codex_protocol::protocol::TurnAbortedEvent;
 use codex_protocol::protocol::TurnCompleteEvent;
 use codex_protocol::protocol::TurnStartedEvent;
 use codex_protocol::protocol::UserMessageEvent;
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::path::PathBuf;
 use uuid::Uuid;
 
+/// Renders a structured review result as plain text for transcript items.
+pub type ReviewOutputRenderer = fn(&ReviewOutputEvent) -> String;
+
 /// Convert persisted [`RolloutItem`] entries into a sequence of [`Turn`] values.
 ///
 /// When available, this uses `TurnContext.turn_id` as the canonical turn id so
@@ -28,10 +43,32 @@ pub fn build_turns_from_rollout_items(items: &[RolloutItem]) -> Vec<Turn> {
     builder.finish()
 }
 
+/// Like [`build_turns_from_rollout_items`], but also maps persisted tool calls
+/// (shell commands and `apply_patch` edits) and review mode boundaries into
+/// turn items, so the result reads as a complete transcript of the thread.
+///
+/// Review results are rendered with `render_review` because the canonical
+/// formatting lives outside of this crate.
+pub fn build_transcript_turns_from_rollout_items(
+    items: &[RolloutItem],
+    render_review: ReviewOutputRenderer,
+) -> Vec<Turn> {
+    let mut builder = ThreadHistoryBuilder::new();
+    builder.render_review = Some(render_review);
+    for item in items {
+        builder.handle_rollout_item(item);
+    }
+    builder.finish()
+}
+
 struct ThreadHistoryBuilder {
     turns: Vec<Turn>,
     current_turn: Option<PendingTurn>,
     next_item_index: i64,
+    /// Set when building a full transcript; enables tool call and review items.
+    render_review: Option<ReviewOutputRenderer>,
+    /// Maps tool `call_id`s to the id of the item created for the call.
+    tool_call_items: HashMap<String, String>,
 }
 
 impl ThreadHistoryBuilder {
@@ -40,6 +77,8 @@ impl ThreadHistoryBuilder {
             turns: Vec::new(),
             current_turn: None,
             next_item_index: 1,
+            render_review: None,
+            tool_call_items: HashMap::new(),
         }
     }
 
@@ -60,8 +99,8 @@ impl ThreadHistoryBuilder {
             }
             EventMsg::ItemCompleted(payload) => self.handle_item_completed(payload),
             EventMsg::TokenCount(_) => {}
-            EventMsg::EnteredReviewMode(_) => {}
-            EventMsg::ExitedReviewMode(_) => {}
+            EventMsg::EnteredReviewMode(payload) => self.handle_entered_review_mode(payload),
+            EventMsg::ExitedReviewMode(payload) => self.handle_exited_review_mode(payload),
             EventMsg::ThreadRolledBack(payload) => self.handle_thread_rollback(payload),
             EventMsg::UndoCompleted(_) => {}
             EventMsg::TurnAborted(payload) => self.handle_turn_aborted(payload),
@@ -75,12 +114,166 @@ impl ThreadHistoryBuilder {
         match item {
             RolloutItem::EventMsg(event) => self.handle_event(event),
             RolloutItem::Compacted(payload) => self.handle_compacted(payload),
-            RolloutItem::TurnContext(_)
-            | RolloutItem::SessionMeta(_)
-            | RolloutItem::ResponseItem(_) => {}
+            RolloutItem::ResponseItem(item) => {
+                if self.render_review.is_some() {
+                    self.handle_response_item(item);
+                }
+            }
+            RolloutItem::TurnContext(_) | RolloutItem::SessionMeta(_) => {}
+        }
+    }
+
+    /// Maps persisted tool calls and their outputs into transcript items.
+    /// Only used when building a full transcript.
+    fn handle_response_item(&mut self, item: &ResponseItem) {
+        match item {
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => {
+                if name == "apply_patch" {
+                    let input = serde_json::from_str::<JsonValue>(arguments)
+                        .ok()
+                        .and_then(|args| args.get("input")?.as_str().map(str::to_string))
+                        .unwrap_or_default();
+                    self.push_file_change(call_id, &input);
+                } else if let Some((command, cwd)) = parse_shell_function_call(name, arguments) {
+                    self.push_command_execution(call_id, command, cwd);
+                }
+            }
+            ResponseItem::LocalShellCall {
+                call_id: Some(call_id),
+                action: LocalShellAction::Exec(action),
+                ..
+            } => {
+                let cwd = action
+                    .working_directory
+                    .as_deref()
+                    .map(PathBuf::from)
+                    .unwrap_or_default();
+                self.push_command_execution(call_id, join_command(&action.command), cwd);
+            }
+            ResponseItem::CustomToolCall {
+                name,
+                input,
+                call_id,
+                ..
+            } if name == "apply_patch" => self.push_file_change(call_id, input),
+            ResponseItem::FunctionCallOutput { call_id, output } => {
+                let text = output.body.to_text().unwrap_or_default();
+                self.complete_tool_call(call_id, &text);
+            }
+            ResponseItem::CustomToolCallOutput { call_id, output } => {
+                self.complete_tool_call(call_id, output);
+            }
+            _ => {}
+        }
+    }
+
+    fn push_command_execution(&mut self, call_id: &str, command: String, cwd: PathBuf) {
+        let id = self.next_item_id();
+        self.tool_call_items.insert(call_id.to_string(), id.clone());
+        self.ensure_turn().items.push(ThreadItem::CommandExecution {
+            id,
+            command,
+            cwd,
+            process_id: None,
+            status: CommandExecutionStatus::InProgress,
+            command_actions: Vec::new(),
+            aggregated_output: None,
+            exit_code: None,
+            duration_ms: None,
+        });
+    }
+
+    fn push_file_change(&mut self, call_id: &str, patch: &str) {
+        let changes = split_patch_into_changes(patch);
+        if changes.is_empty() {
+            return;
+        }
+        let id = self.next_item_id();
+        self.tool_call_items.insert(call_id.to_string(), id.clone());
+        self.ensure_turn().items.push(ThreadItem::FileChange {
+            id,
+            changes,
+            status: PatchApplyStatus::InProgress,
+        });
+    }
+
+    fn complete_tool_call(&mut self, call_id: &str, output: &str) {
+        let Some(item_id) = self.tool_call_items.remove(call_id) else {
+            return;
+        };
+        let Some(item) = self.current_turn.as_mut().and_then(|turn| {
+            turn.items
+                .iter_mut()
+                .rev()
+                .find(|item| item.id() == item_id)
+        }) else {
+            return;
+        };
+        let parsed = ParsedToolOutput::parse(output);
+        match item {
+            ThreadItem::CommandExecution {
+                status,
+                aggregated_output,
+                exit_code,
+                duration_ms,
+                ..
+            } => {
+                *status = match parsed.exit_code {
+                    Some(code) if code != 0 => CommandExecutionStatus::Failed,
+                    _ => CommandExecutionStatus::Completed,
+                };
+                *aggregated_output = Some(parsed.output);
+                *exit_code = parsed.exit_code;
+                *duration_ms = parsed.duration_ms;
+            }
+            ThreadItem::FileChange { status, .. } => {
+                let succeeded = match parsed.exit_code {
+                    Some(code) => code == 0,
+                    None => parsed.output.contains("Success."),
+                };
+                *status = if succeeded {
+                    PatchApplyStatus::Completed
+                } else {
+                    PatchApplyStatus::Failed
+                };
+            }
+            _ => {}
         }
     }
 
+    fn handle_entered_review_mode(&mut self, payload: &ReviewRequest) {
+        if self.render_review.is_none() {
+            return;
+        }
+        let review = payload
+            .user_facing_hint
+            .clone()
+            .unwrap_or_else(|| "code review".to_string());
+        let id = self.next_item_id();
+        self.ensure_turn()
+            .items
+            .push(ThreadItem::EnteredReviewMode { id, review });
+    }
+
+    fn handle_exited_review_mode(&mut self, payload: &ExitedReviewModeEvent) {
+        let Some(render_review) = self.render_review else {
+            return;
+        };
+        let Some(output) = payload.review_output.as_ref() else {
+            return;
+        };
+        let review = render_review(output);
+        let id = self.next_item_id();
+        self.ensure_turn()
+            .items
+            .push(ThreadItem::ExitedReviewMode { id, review });
+    }
+
     fn handle_user_message(&mut self, payload: &UserMessageEvent) {
         // User messages should stay in explicitly opened turns. For backward
         // compatibility with older streams that did not open turns explicitly,
@@ -305,9 +498,160 @@ impl From<PendingTurn> for Turn {
     }
 }
 
+/// Extracts the command line and working directory from a shell-style
+/// function call, covering the `shell`, `shell_command` and `exec_command`
+/// tool shapes.
+fn parse_shell_function_call(name: &str, arguments: &str) -> Option<(String, PathBuf)> {
+    let args = serde_json::from_str::<JsonValue>(arguments).ok()?;
+    let command = match name {
+        "shell" | "container.exec" => {
+            let argv = args
+                .get("command")?
+                .as_array()?
+                .iter()
+                .filter_map(JsonValue::as_str)
+                .map(str::to_string)
+                .collect::<Vec<_>>();
+            join_command(&argv)
+        }
+        "shell_command" => args.get("command")?.as_str()?.to_string(),
+        "exec_command" => args.get("cmd")?.as_str()?.to_string(),
+        _ => return None,
+    };
+    let cwd = args
+        .get("workdir")
+        .and_then(JsonValue::as_str)
+        .map(PathBuf::from)
+        .unwrap_or_default();
+    Some((command, cwd))
+}
+
+/// Joins an argv into a single display string, unwrapping `bash -lc <script>`.
+fn join_command(argv: &[String]) -> String {
+    if let [_, flag, script] = argv
+        && matches!(flag.as_str(), "-c" | "-lc")
+    {
+        return script.clone();
+    }
+    argv.iter()
+        .map(|arg| {
+            if arg.is_empty() || arg.chars().any(char::is_whitespace) {
+                format!("'{}'", arg.replace('\'', "'\\''"))
+            } else {
+                arg.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Splits an `apply_patch` envelope into one change per file section.
+fn split_patch_into_changes(patch: &str) -> Vec<FileUpdateChange> {
+    let mut changes: Vec<FileUpdateChange> = Vec::new();
+    for line in patch.lines() {
+        if let Some(path) = line.strip_prefix("*** Add File: ") {
+            changes.push(FileUpdateChange {
+                path: path.trim().to_string(),
+                kind: PatchChangeKind::Add,
+                diff: String::new(),
+            });
+        } else if let Some(path) = line.strip_prefix("*** Delete File: ") {
+            changes.push(FileUpdateChange {
+                path: path.trim().to_string(),
+                kind: PatchChangeKind::Delete,
+                diff: String::new(),
+            });
+        } else if let Some(path) = line.strip_prefix("*** Update File: ") {
+            changes.push(FileUpdateChange {
+                path: path.trim().to_string(),
+                kind: PatchChangeKind::Update { move_path: None },
+                diff: String::new(),
+            });
+        } else if let Some(dest) = line.strip_prefix("*** Move to: ") {
+            if let Some(FileUpdateChange {
+                kind: PatchChangeKind::Update { move_path },
+                ..
+            }) = changes.last_mut()
+            {
+                *move_path = Some(PathBuf::from(dest.trim()));
+            }
+        } else if line.starts_with("*** Begin Patch") || line.starts_with("*** End Patch") {
+            continue;
+        } else if let Some(change) = changes.last_mut() {
+            change.diff.push_str(line);
+            change.diff.push('\n');
+        }
+    }
+    changes
+}
+
+/// Best-effort parse of the text returned to the model for a tool call.
+struct ParsedToolOutput {
+    output: String,
+    exit_code: Option<i32>,
+    duration_ms: Option<i64>,
+}
+
+impl ParsedToolOutput {
+    fn parse(text: &str) -> Self {
+        // Structured shell output: {"output": "...", "metadata": {"exit_code": 0, ...}}.
+        if let Ok(json) = serde_json::from_str::<JsonValue>(text)
+            && let Some(output) = json.get("output").and_then(JsonValue::as_str)
+        {
+            let metadata = json.get("metadata");
+            return Self {
+                output: output.to_string(),
+                exit_code: metadata
+                    .and_then(|m| m.get("exit_code"))
+                    .and_then(JsonValue::as_i64)
+                    .and_then(|code| i32::try_from(code).ok()),
+                duration_ms: metadata
+                    .and_then(|m| m.get("duration_seconds"))
+                    .and_then(JsonValue::as_f64)
+                    .map(|secs| (secs * 1000.0) as i64),
+            };
+        }
+
+        // Freeform output: header lines followed by `Output:` and the body.
+        let mut exit_code = None;
+        let mut duration_ms = None;
+        let mut lines = text.lines();
+        let mut consumed_header = false;
+        for line in lines.by_ref() {
+            if line == "Output:" {
+                consumed_header = true;
+                break;
+            }
+            if let Some(code) = line
+                .strip_prefix("Exit code: ")
+                .or_else(|| line.strip_prefix("Process exited with code "))
+            {
+                exit_code = code.trim().parse().ok();
+            } else if let Some(wall_time) = line.strip_prefix("Wall time: ") {
+                duration_ms = wall_time
+                    .trim_end_matches(" seconds")
+                    .parse::<f64>()
+                    .ok()
+                    .map(|secs| (secs * 1000.0) as i64);
+            }
+        }
+        let output = if consumed_header {
+            lines.collect::<Vec<_>>().join("\n")
+        } else {
+            text.to_string()
+        };
+        Self {
+            output,
+            exit_code,
+            duration_ms,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use codex_protocol::models::FunctionCallOutputPayload;
     use codex_protocol::protocol::AgentMessageEvent;
     use codex_protocol::protocol::AgentReasoningEvent;
     use codex_protocol::protocol::AgentReasoningRawContentEvent;
@@ -735,4 +1079,117 @@ mod tests {
             }]
         );
     }
+
+    fn render_review_for_test(output: &ReviewOutputEvent) -> String {
+        format!("review: {}", output.overall_explanation)
+    }
+
+    fn transcript_tool_call_items() -> Vec<RolloutItem> {
+        vec![
+            RolloutItem::EventMsg(EventMsg::TurnStarted(TurnStartedEvent {
+                turn_id: "turn-tools".into(),
+                model_context_window: None,
+                collaboration_mode_kind: Default::default(),
+            })),
+            RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+                message: "Fix the build".into(),
+                images: None,
+                text_elements: Vec::new(),
+                local_images: Vec::new(),
+            })),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                id: None,
+                name: "shell_command".into(),
+                arguments: r#"{"command":"cargo build","workdir":"/repo"}"#.into(),
+                call_id: "call-1".into(),
+            }),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCallOutput {
+                call_id: "call-1".into(),
+                output: FunctionCallOutputPayload::from_text(
+                    "Exit code: 101\nWall time: 1.5 seconds\nOutput:\nerror[E0425]".into(),
+                ),
+            }),
+            RolloutItem::ResponseItem(ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "call-2".into(),
+                name: "apply_patch".into(),
+                input: "*** Begin Patch\n*** Update File: src/lib.rs\n@@\n-old\n+new\n*** End Patch"
+                    .into(),
+            }),
+            RolloutItem::ResponseItem(ResponseItem::CustomToolCallOutput {
+                call_id: "call-2".into(),
+                output: "Exit code: 0\nWall time: 0 seconds\nOutput:\nSuccess. Updated the following files:\nM src/lib.rs\n"
+                    .into(),
+            }),
+            RolloutItem::EventMsg(EventMsg::ExitedReviewMode(ExitedReviewModeEvent {
+                review_output: Some(ReviewOutputEvent {
+                    overall_explanation: "Looks good".into(),
+                    ..Default::default()
+                }),
+                snapshot_sha: None,
+            })),
+            RolloutItem::EventMsg(EventMsg::TurnComplete(TurnCompleteEvent {
+                turn_id: "turn-tools".into(),
+                last_agent_message: None,
+            })),
+        ]
+    }
+
+    #[test]
+    fn thread_history_ignores_tool_calls() {
+        let turns = build_turns_from_rollout_items(&transcript_tool_call_items());
+        assert_eq!(turns.len(), 1);
+        assert_eq!(turns[0].items.len(), 1);
+    }
+
+    #[test]
+    fn transcript_includes_commands_patches_and_reviews() {
+        let turns = build_transcript_turns_from_rollout_items(
+            &transcript_tool_call_items(),
+            render_review_for_test,
+        );
+        assert_eq!(turns.len(), 1);
+        assert_eq!(
+            turns[0].items[1..].to_vec(),
+            vec![
+                ThreadItem::CommandExecution {
+                    id: "item-2".into(),
+                    command: "cargo build".into(),
+                    cwd: PathBuf::from("/repo"),
+                    process_id: None,
+                    status: CommandExecutionStatus::Failed,
+                    command_actions: Vec::new(),
+                    aggregated_output: Some("error[E0425]".into()),
+                    exit_code: Some(101),
+                    duration_ms: Some(1500),
+                },
+                ThreadItem::FileChange {
+                    id: "item-3".into(),
+                    changes: vec![FileUpdateChange {
+                        path: "src/lib.rs".into(),
+                        kind: PatchChangeKind::Update { move_path: None },
+                        diff: "@@\n-old\n+new\n".into(),
+                    }],
+                    status: PatchApplyStatus::Completed,
+                },
+                ThreadItem::ExitedReviewMode {
+                    id: "item-4".into(),
+                    review: "review: Looks good".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn join_command_unwraps_shell_scripts() {
+        assert_eq!(
+            join_command(&["bash".into(), "-lc".into(), "ls -la".into()]),
+            "ls -la"
+        );
+        assert_eq!(
+            join_command(&["rg".into(), "foo bar".into(), "src".into()]),
+            "rg 'foo bar' src"
+        );
+    }
 }
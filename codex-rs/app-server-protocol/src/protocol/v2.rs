@@ -40,6 +40,8 @@ use codex_protocol::protocol::SkillInterface as CoreSkillInterface;
 use codex_protocol::protocol::SkillMetadata as CoreSkillMetadata;
 use codex_protocol::protocol::SkillScope as CoreSkillScope;
 use codex_protocol::protocol::SkillToolDependency as CoreSkillToolDependency;
+use codex_protocol::protocol::SnapshotFileStat as CoreSnapshotFileStat;
+use codex_protocol::protocol::SnapshotTimelineEntry as CoreSnapshotTimelineEntry;
 use codex_protocol::protocol::SubAgentSource as CoreSubAgentSource;
 use codex_protocol::protocol::TokenUsage as CoreTokenUsage;
 use codex_protocol::protocol::TokenUsageInfo as CoreTokenUsageInfo;
@@ -218,6 +220,15 @@ v2_enum_from_core!(
     }
 );
 
+v2_enum_from_core!(
+    pub enum ProviderFailoverTrigger from codex_protocol::protocol::FailoverTrigger {
+        RateLimit,
+        ServerError,
+        StreamIdleTimeout,
+        ContextWindowExceeded
+    }
+);
+
 v2_enum_from_core!(
     pub enum McpAuthStatus from codex_protocol::protocol::McpAuthStatus {
         Unsupported,
@@ -616,6 +627,11 @@ pub enum CommandExecutionApprovalDecision {
     },
     /// User denied the command. The agent will continue the turn.
     Decline,
+    /// User denied the command, and wants future commands that start with the proposed
+    /// execpolicy amendment's prefix to be refused without prompting in this repository.
+    DeclineWithExecpolicyAmendment {
+        execpolicy_amendment: ExecPolicyAmendment,
+    },
     /// User denied the command. The turn will also be immediately interrupted.
     Cancel,
 }
@@ -836,6 +852,32 @@ pub enum CommandAction {
         query: Option<String>,
         path: Option<String>,
     },
+    Write {
+        command: String,
+        paths: Vec<String>,
+    },
+    Delete {
+        command: String,
+        paths: Vec<String>,
+    },
+    Install {
+        command: String,
+        packages: Vec<String>,
+    },
+    Build {
+        command: String,
+    },
+    Test {
+        command: String,
+    },
+    Git {
+        command: String,
+        subcommand: String,
+    },
+    Fetch {
+        command: String,
+        url: Option<String>,
+    },
     Unknown {
         command: String,
     },
@@ -909,6 +951,25 @@ impl CommandAction {
                 query,
                 path,
             } => CoreParsedCommand::Search { cmd, query, path },
+            CommandAction::Write {
+                command: cmd,
+                paths,
+            } => CoreParsedCommand::Write { cmd, paths },
+            CommandAction::Delete {
+                command: cmd,
+                paths,
+            } => CoreParsedCommand::Delete { cmd, paths },
+            CommandAction::Install {
+                command: cmd,
+                packages,
+            } => CoreParsedCommand::Install { cmd, packages },
+            CommandAction::Build { command: cmd } => CoreParsedCommand::Build { cmd },
+            CommandAction::Test { command: cmd } => CoreParsedCommand::Test { cmd },
+            CommandAction::Git {
+                command: cmd,
+                subcommand,
+            } => CoreParsedCommand::Git { cmd, subcommand },
+            CommandAction::Fetch { command: cmd, url } => CoreParsedCommand::Fetch { cmd, url },
             CommandAction::Unknown { command: cmd } => CoreParsedCommand::Unknown { cmd },
         }
     }
@@ -930,6 +991,25 @@ impl From<CoreParsedCommand> for CommandAction {
                 query,
                 path,
             },
+            CoreParsedCommand::Write { cmd, paths } => CommandAction::Write {
+                command: cmd,
+                paths,
+            },
+            CoreParsedCommand::Delete { cmd, paths } => CommandAction::Delete {
+                command: cmd,
+                paths,
+            },
+            CoreParsedCommand::Install { cmd, packages } => CommandAction::Install {
+                command: cmd,
+                packages,
+            },
+            CoreParsedCommand::Build { cmd } => CommandAction::Build { command: cmd },
+            CoreParsedCommand::Test { cmd } => CommandAction::Test { command: cmd },
+            CoreParsedCommand::Git { cmd, subcommand } => CommandAction::Git {
+                command: cmd,
+                subcommand,
+            },
+            CoreParsedCommand::Fetch { cmd, url } => CommandAction::Fetch { command: cmd, url },
             CoreParsedCommand::Unknown { cmd } => CommandAction::Unknown { command: cmd },
         }
     }
@@ -1658,6 +1738,108 @@ pub struct ThreadRollbackResponse {
     pub thread: Thread,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSnapshotListParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSnapshotListResponse {
+    /// Snapshots of the working tree captured at the start of each turn, oldest first.
+    pub snapshots: Vec<ThreadSnapshot>,
+    /// Whether `thread/snapshot/redo` has a replaced working tree state to reapply.
+    pub redo_available: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSnapshot {
+    /// Identifier to pass to `thread/snapshot/restore`.
+    pub id: String,
+    /// 1-based position of the turn that captured the snapshot.
+    pub turn: u32,
+    /// The user message that started the turn, if it is still in the thread's history.
+    pub summary: Option<String>,
+    /// Files changed between this snapshot and the next one, or the current working tree
+    /// for the latest snapshot.
+    pub files: Vec<ThreadSnapshotFileChange>,
+}
+
+impl From<CoreSnapshotTimelineEntry> for ThreadSnapshot {
+    fn from(value: CoreSnapshotTimelineEntry) -> Self {
+        Self {
+            id: value.snapshot_id,
+            turn: value.turn,
+            summary: value.summary,
+            files: value.files.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSnapshotFileChange {
+    /// Path relative to the thread's working directory.
+    pub path: PathBuf,
+    /// Lines added, or null for binary files.
+    #[ts(type = "number | null")]
+    pub added: Option<i64>,
+    /// Lines removed, or null for binary files.
+    #[ts(type = "number | null")]
+    pub removed: Option<i64>,
+}
+
+impl From<CoreSnapshotFileStat> for ThreadSnapshotFileChange {
+    fn from(value: CoreSnapshotFileStat) -> Self {
+        Self {
+            path: value.path,
+            added: value.added.and_then(|added| i64::try_from(added).ok()),
+            removed: value
+                .removed
+                .and_then(|removed| i64::try_from(removed).ok()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSnapshotRestoreParams {
+    pub thread_id: String,
+    /// A snapshot id returned by `thread/snapshot/list`.
+    pub snapshot_id: String,
+    /// Files to restore, relative to the thread's working directory. Every file is
+    /// restored when omitted or empty.
+    ///
+    /// The thread's history is left unchanged. The replaced working tree state can be
+    /// brought back with `thread/snapshot/redo`.
+    #[ts(optional = nullable)]
+    pub paths: Option<Vec<PathBuf>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSnapshotRestoreResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSnapshotRedoParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSnapshotRedoResponse {}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -2255,6 +2437,26 @@ pub enum ReviewTarget {
     #[serde(rename_all = "camelCase")]
     #[ts(rename_all = "camelCase")]
     Custom { instructions: String },
+
+    /// Review the combined changes of a commit range (`git diff base..head`).
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    CommitRange { base: String, head: String },
+
+    /// Review a patch that is not necessarily applied to the working tree.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    Patch {
+        diff: String,
+        /// Where the patch came from (e.g., a file name), for UIs.
+        label: Option<String>,
+    },
+
+    /// Review only what changed since the review in an earlier thread, without repeating
+    /// the findings it already reported.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    Incremental { since_thread_id: String },
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -2537,6 +2739,26 @@ pub enum ThreadItem {
     ContextCompaction { id: String },
 }
 
+impl ThreadItem {
+    pub fn id(&self) -> &str {
+        match self {
+            ThreadItem::UserMessage { id, .. }
+            | ThreadItem::AgentMessage { id, .. }
+            | ThreadItem::Plan { id, .. }
+            | ThreadItem::Reasoning { id, .. }
+            | ThreadItem::CommandExecution { id, .. }
+            | ThreadItem::FileChange { id, .. }
+            | ThreadItem::McpToolCall { id, .. }
+            | ThreadItem::CollabAgentToolCall { id, .. }
+            | ThreadItem::WebSearch { id, .. }
+            | ThreadItem::ImageView { id, .. }
+            | ThreadItem::EnteredReviewMode { id, .. }
+            | ThreadItem::ExitedReviewMode { id, .. }
+            | ThreadItem::ContextCompaction { id } => id,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(tag = "type", rename_all = "camelCase")]
 #[ts(tag = "type", rename_all = "camelCase")]
@@ -2804,6 +3026,27 @@ pub struct TurnDiffUpdatedNotification {
     pub thread_id: String,
     pub turn_id: String,
     pub diff: String,
+    /// Unified diff of the edits made outside Codex to files changed in the turn, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub external_diff: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+/// Notification that the thread moved to another provider (and possibly model) on its
+/// failover chain. `trigger` is null when it returns to an earlier entry whose cool-down
+/// has expired; `cooldownSecs` is how long `fromProvider` is skipped after a failure.
+pub struct ProviderFailoverNotification {
+    pub thread_id: String,
+    pub turn_id: String,
+    pub from_provider: String,
+    pub from_model: String,
+    pub to_provider: String,
+    pub to_model: String,
+    pub trigger: Option<ProviderFailoverTrigger>,
+    pub cooldown_secs: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -5,6 +5,7 @@ use crate::codex_message_processor::summary_to_thread;
 use crate::error_code::INTERNAL_ERROR_CODE;
 use crate::error_code::INVALID_REQUEST_ERROR_CODE;
 use crate::outgoing_message::ThreadScopedOutgoingMessageSender;
+use crate::thread_state::PendingSnapshotRestore;
 use crate::thread_state::ThreadState;
 use crate::thread_state::TurnSummary;
 use codex_app_server_protocol::AccountRateLimitsUpdatedNotification;
@@ -43,6 +44,7 @@ use codex_app_server_protocol::McpToolCallStatus;
 use codex_app_server_protocol::PatchApplyStatus;
 use codex_app_server_protocol::PatchChangeKind as V2PatchChangeKind;
 use codex_app_server_protocol::PlanDeltaNotification;
+use codex_app_server_protocol::ProviderFailoverNotification;
 use codex_app_server_protocol::RawResponseItemCompletedNotification;
 use codex_app_server_protocol::ReasoningSummaryPartAddedNotification;
 use codex_app_server_protocol::ReasoningSummaryTextDeltaNotification;
@@ -53,6 +55,10 @@ use codex_app_server_protocol::TerminalInteractionNotification;
 use codex_app_server_protocol::ThreadItem;
 use codex_app_server_protocol::ThreadNameUpdatedNotification;
 use codex_app_server_protocol::ThreadRollbackResponse;
+use codex_app_server_protocol::ThreadSnapshot;
+use codex_app_server_protocol::ThreadSnapshotListResponse;
+use codex_app_server_protocol::ThreadSnapshotRedoResponse;
+use codex_app_server_protocol::ThreadSnapshotRestoreResponse;
 use codex_app_server_protocol::ThreadTokenUsage;
 use codex_app_server_protocol::ThreadTokenUsageUpdatedNotification;
 use codex_app_server_protocol::ToolRequestUserInputOption;
@@ -205,6 +211,7 @@ pub(crate) async fn apply_bespoke_event_handling(
             reason,
             proposed_execpolicy_amendment,
             parsed_cmd,
+            provenance: _,
         }) => match api_version {
             ApiVersion::V1 => {
                 let params = ExecCommandApprovalParams {
@@ -1125,6 +1132,56 @@ pub(crate) async fn apply_bespoke_event_handling(
                 outgoing.send_response(request_id, response).await;
             }
         }
+        EventMsg::SnapshotTimeline(timeline_event) => {
+            let pending = {
+                let mut state = thread_state.lock().await;
+                state.pending_snapshot_list.take()
+            };
+
+            if let Some(request_id) = pending {
+                let response = ThreadSnapshotListResponse {
+                    snapshots: timeline_event
+                        .entries
+                        .into_iter()
+                        .map(ThreadSnapshot::from)
+                        .collect(),
+                    redo_available: timeline_event.redo_available,
+                };
+                outgoing.send_response(request_id, response).await;
+            }
+        }
+        EventMsg::UndoCompleted(undo_event) => {
+            let pending = {
+                let mut state = thread_state.lock().await;
+                state.pending_snapshot_restore.take()
+            };
+
+            match pending {
+                Some(pending) if !undo_event.success => {
+                    let error = JSONRPCErrorError {
+                        code: INVALID_REQUEST_ERROR_CODE,
+                        message: undo_event
+                            .message
+                            .unwrap_or_else(|| "failed to restore snapshot".to_string()),
+                        data: None,
+                    };
+                    outgoing
+                        .send_error(pending.request_id().clone(), error)
+                        .await;
+                }
+                Some(PendingSnapshotRestore::Restore(request_id)) => {
+                    outgoing
+                        .send_response(request_id, ThreadSnapshotRestoreResponse {})
+                        .await;
+                }
+                Some(PendingSnapshotRestore::Redo(request_id)) => {
+                    outgoing
+                        .send_response(request_id, ThreadSnapshotRedoResponse {})
+                        .await;
+                }
+                None => {}
+            }
+        }
         EventMsg::ThreadNameUpdated(thread_name_event) => {
             if let ApiVersion::V2 = api_version {
                 let notification = ThreadNameUpdatedNotification {
@@ -1136,6 +1193,23 @@ pub(crate) async fn apply_bespoke_event_handling(
                     .await;
             }
         }
+        EventMsg::ProviderFailover(failover_event) => {
+            if let ApiVersion::V2 = api_version {
+                let notification = ProviderFailoverNotification {
+                    thread_id: conversation_id.to_string(),
+                    turn_id: event_turn_id.clone(),
+                    from_provider: failover_event.from_provider,
+                    from_model: failover_event.from_model,
+                    to_provider: failover_event.to_provider,
+                    to_model: failover_event.to_model,
+                    trigger: failover_event.trigger.map(Into::into),
+                    cooldown_secs: failover_event.cooldown_secs,
+                };
+                outgoing
+                    .send_server_notification(ServerNotification::ProviderFailover(notification))
+                    .await;
+            }
+        }
         EventMsg::TurnDiff(turn_diff_event) => {
             handle_turn_diff(
                 conversation_id,
@@ -1173,6 +1247,7 @@ async fn handle_turn_diff(
             thread_id: conversation_id.to_string(),
             turn_id: event_turn_id.to_string(),
             diff: turn_diff_event.unified_diff,
+            external_diff: turn_diff_event.external_unified_diff,
         };
         outgoing
             .send_server_notification(ServerNotification::TurnDiffUpdated(notification))
@@ -1725,6 +1800,14 @@ async fn on_command_execution_request_approval_response(
                     ReviewDecision::Denied,
                     Some(CommandExecutionStatus::Declined),
                 ),
+                CommandExecutionApprovalDecision::DeclineWithExecpolicyAmendment {
+                    execpolicy_amendment,
+                } => (
+                    ReviewDecision::DeniedExecpolicyAmendment {
+                        proposed_execpolicy_amendment: execpolicy_amendment.into_core(),
+                    },
+                    Some(CommandExecutionStatus::Declined),
+                ),
                 CommandExecutionApprovalDecision::Cancel => (
                     ReviewDecision::Abort,
                     Some(CommandExecutionStatus::Declined),
@@ -2558,6 +2641,7 @@ mod tests {
             "turn-1",
             TurnDiffEvent {
                 unified_diff: unified_diff.clone(),
+                external_unified_diff: None,
             },
             ApiVersion::V2,
             &outgoing,
@@ -2591,6 +2675,7 @@ mod tests {
             "turn-1",
             TurnDiffEvent {
                 unified_diff: "diff".to_string(),
+                external_unified_diff: None,
             },
             ApiVersion::V1,
             &outgoing,
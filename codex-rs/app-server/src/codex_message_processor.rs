@@ -130,6 +130,9 @@ use codex_app_server_protocol::ThreadResumeResponse;
 use codex_app_server_protocol::ThreadRollbackParams;
 use codex_app_server_protocol::ThreadSetNameParams;
 use codex_app_server_protocol::ThreadSetNameResponse;
+use codex_app_server_protocol::ThreadSnapshotListParams;
+use codex_app_server_protocol::ThreadSnapshotRedoParams;
+use codex_app_server_protocol::ThreadSnapshotRestoreParams;
 use codex_app_server_protocol::ThreadSortKey;
 use codex_app_server_protocol::ThreadSourceKind;
 use codex_app_server_protocol::ThreadStartParams;
@@ -252,6 +255,7 @@ use uuid::Uuid;
 
 use crate::filters::compute_source_filters;
 use crate::filters::source_kind_matches;
+use crate::thread_state::PendingSnapshotRestore;
 use crate::thread_state::ThreadState;
 use crate::thread_state::ThreadStateManager;
 
@@ -407,8 +411,10 @@ impl CodexMessageProcessor {
         collaboration_mode
     }
 
-    fn review_request_from_target(
+    async fn review_request_from_target(
+        &self,
         target: ApiReviewTarget,
+        cwd: &Path,
     ) -> Result<(ReviewRequest, String), JSONRPCErrorError> {
         fn invalid_request(message: String) -> JSONRPCErrorError {
             JSONRPCErrorError {
@@ -448,6 +454,28 @@ impl CodexMessageProcessor {
                     instructions: trimmed,
                 }
             }
+            ApiReviewTarget::CommitRange { base, head } => {
+                let base = base.trim().to_string();
+                let head = head.trim().to_string();
+                if base.is_empty() || head.is_empty() {
+                    return Err(invalid_request(
+                        "base and head must not be empty".to_string(),
+                    ));
+                }
+                ApiReviewTarget::CommitRange { base, head }
+            }
+            ApiReviewTarget::Patch { diff, label } => {
+                if diff.trim().is_empty() {
+                    return Err(invalid_request("diff must not be empty".to_string()));
+                }
+                let label = label
+                    .map(|l| l.trim().to_string())
+                    .filter(|l| !l.is_empty());
+                ApiReviewTarget::Patch { diff, label }
+            }
+            ApiReviewTarget::Incremental { since_thread_id } => ApiReviewTarget::Incremental {
+                since_thread_id: since_thread_id.trim().to_string(),
+            },
         };
 
         let core_target = match cleaned_target {
@@ -455,8 +483,32 @@ impl CodexMessageProcessor {
             ApiReviewTarget::BaseBranch { branch } => CoreReviewTarget::BaseBranch { branch },
             ApiReviewTarget::Commit { sha, title } => CoreReviewTarget::Commit { sha, title },
             ApiReviewTarget::Custom { instructions } => CoreReviewTarget::Custom { instructions },
+            ApiReviewTarget::CommitRange { base, head } => {
+                CoreReviewTarget::CommitRange { base, head }
+            }
+            ApiReviewTarget::Patch { diff, label } => CoreReviewTarget::Patch { diff, label },
+            ApiReviewTarget::Incremental { since_thread_id } => {
+                let path = find_thread_path_by_id_str(&self.config.codex_home, &since_thread_id)
+                    .await
+                    .map_err(|err| invalid_request(format!("failed to locate thread: {err}")))?
+                    .ok_or_else(|| {
+                        invalid_request(format!("thread not found: {since_thread_id}"))
+                    })?;
+                codex_core::review_prompts::incremental_review_target(&path)
+                    .await
+                    .map_err(|err| invalid_request(err.to_string()))?
+            }
         };
 
+        if matches!(
+            core_target,
+            CoreReviewTarget::CommitRange { .. } | CoreReviewTarget::Patch { .. }
+        ) {
+            // Reject unknown refs and oversized patches now instead of failing the turn.
+            codex_core::review_prompts::review_prompt(&core_target, cwd)
+                .map_err(|err| invalid_request(err.to_string()))?;
+        }
+
         let hint = codex_core::review_prompts::user_facing_hint(&core_target);
         let review_request = ReviewRequest {
             target: core_target,
@@ -516,6 +568,18 @@ impl CodexMessageProcessor {
                 self.thread_rollback(to_connection_request_id(request_id), params)
                     .await;
             }
+            ClientRequest::ThreadSnapshotList { request_id, params } => {
+                self.thread_snapshot_list(to_connection_request_id(request_id), params)
+                    .await;
+            }
+            ClientRequest::ThreadSnapshotRestore { request_id, params } => {
+                self.thread_snapshot_restore(to_connection_request_id(request_id), params)
+                    .await;
+            }
+            ClientRequest::ThreadSnapshotRedo { request_id, params } => {
+                self.thread_snapshot_redo(to_connection_request_id(request_id), params)
+                    .await;
+            }
             ClientRequest::ThreadList { request_id, params } => {
                 self.thread_list(to_connection_request_id(request_id), params)
                     .await;
@@ -2342,6 +2406,128 @@ impl CodexMessageProcessor {
         }
     }
 
+    async fn thread_snapshot_list(
+        &self,
+        request_id: ConnectionRequestId,
+        params: ThreadSnapshotListParams,
+    ) {
+        let ThreadSnapshotListParams { thread_id } = params;
+
+        let (thread_id, thread) = match self.load_thread(&thread_id).await {
+            Ok(v) => v,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        let list_already_in_progress = {
+            let thread_state = self.thread_state_manager.thread_state(thread_id);
+            let mut thread_state = thread_state.lock().await;
+            if thread_state.pending_snapshot_list.is_some() {
+                true
+            } else {
+                thread_state.pending_snapshot_list = Some(request_id.clone());
+                false
+            }
+        };
+        if list_already_in_progress {
+            self.send_invalid_request_error(
+                request_id,
+                "snapshot list already in progress for this thread".to_string(),
+            )
+            .await;
+            return;
+        }
+
+        if let Err(err) = thread.submit(Op::ListSnapshots).await {
+            // No SnapshotTimeline event will arrive if an error occurs.
+            let thread_state = self.thread_state_manager.thread_state(thread_id);
+            thread_state.lock().await.pending_snapshot_list = None;
+
+            self.send_internal_error(request_id, format!("failed to list snapshots: {err}"))
+                .await;
+        }
+    }
+
+    async fn thread_snapshot_restore(
+        &self,
+        request_id: ConnectionRequestId,
+        params: ThreadSnapshotRestoreParams,
+    ) {
+        let ThreadSnapshotRestoreParams {
+            thread_id,
+            snapshot_id,
+            paths,
+        } = params;
+        let op = Op::RestoreSnapshot {
+            snapshot_id,
+            paths: paths.unwrap_or_default(),
+        };
+        self.start_snapshot_restore(&thread_id, PendingSnapshotRestore::Restore(request_id), op)
+            .await;
+    }
+
+    async fn thread_snapshot_redo(
+        &self,
+        request_id: ConnectionRequestId,
+        params: ThreadSnapshotRedoParams,
+    ) {
+        let ThreadSnapshotRedoParams { thread_id } = params;
+        self.start_snapshot_restore(
+            &thread_id,
+            PendingSnapshotRestore::Redo(request_id),
+            Op::Redo,
+        )
+        .await;
+    }
+
+    /// Submits a restore or redo and leaves the request pending until core reports the
+    /// outcome with an `UndoCompleted` event.
+    async fn start_snapshot_restore(
+        &self,
+        thread_id: &str,
+        pending: PendingSnapshotRestore,
+        op: Op,
+    ) {
+        let request_id = pending.request_id().clone();
+        let (thread_id, thread) = match self.load_thread(thread_id).await {
+            Ok(v) => v,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        let restore_already_in_progress = {
+            let thread_state = self.thread_state_manager.thread_state(thread_id);
+            let mut thread_state = thread_state.lock().await;
+            if thread_state.pending_snapshot_restore.is_some() {
+                true
+            } else {
+                thread_state.pending_snapshot_restore = Some(pending);
+                false
+            }
+        };
+        if restore_already_in_progress {
+            self.send_invalid_request_error(
+                request_id,
+                "snapshot restore already in progress for this thread".to_string(),
+            )
+            .await;
+            return;
+        }
+
+        if let Err(err) = thread.submit(op).await {
+            // No UndoCompleted event will arrive if an error occurs.
+            let thread_state = self.thread_state_manager.thread_state(thread_id);
+            thread_state.lock().await.pending_snapshot_restore = None;
+
+            self.send_internal_error(request_id, format!("failed to restore snapshot: {err}"))
+                .await;
+        }
+    }
+
     async fn thread_compact_start(
         &self,
         request_id: ConnectionRequestId,
@@ -5228,13 +5414,15 @@ impl CodexMessageProcessor {
             }
         };
 
-        let (review_request, display_text) = match Self::review_request_from_target(target) {
-            Ok(value) => value,
-            Err(err) => {
-                self.outgoing.send_error(request_id, err).await;
-                return;
-            }
-        };
+        let cwd = parent_thread.config_snapshot().await.cwd;
+        let (review_request, display_text) =
+            match self.review_request_from_target(target, &cwd).await {
+                Ok(value) => value,
+                Err(err) => {
+                    self.outgoing.send_error(request_id, err).await;
+                    return;
+                }
+            };
 
         let delivery = delivery.unwrap_or(ApiReviewDelivery::Inline).to_core();
         match delivery {
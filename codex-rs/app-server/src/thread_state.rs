@@ -23,10 +23,27 @@ pub(crate) struct TurnSummary {
     pub(crate) last_error: Option<TurnError>,
 }
 
+/// A `thread/snapshot/restore` or `thread/snapshot/redo` request waiting for core to report
+/// whether the working tree was restored.
+pub(crate) enum PendingSnapshotRestore {
+    Restore(ConnectionRequestId),
+    Redo(ConnectionRequestId),
+}
+
+impl PendingSnapshotRestore {
+    pub(crate) fn request_id(&self) -> &ConnectionRequestId {
+        match self {
+            Self::Restore(request_id) | Self::Redo(request_id) => request_id,
+        }
+    }
+}
+
 #[derive(Default)]
 pub(crate) struct ThreadState {
     pub(crate) pending_interrupts: PendingInterruptQueue,
     pub(crate) pending_rollbacks: Option<ConnectionRequestId>,
+    pub(crate) pending_snapshot_list: Option<ConnectionRequestId>,
+    pub(crate) pending_snapshot_restore: Option<PendingSnapshotRestore>,
     pub(crate) turn_summary: TurnSummary,
     pub(crate) cancel_tx: Option<oneshot::Sender<()>>,
     pub(crate) experimental_raw_events: bool,
@@ -59,6 +59,9 @@ use codex_app_server_protocol::ThreadLoadedListParams;
 use codex_app_server_protocol::ThreadReadParams;
 use codex_app_server_protocol::ThreadResumeParams;
 use codex_app_server_protocol::ThreadRollbackParams;
+use codex_app_server_protocol::ThreadSnapshotListParams;
+use codex_app_server_protocol::ThreadSnapshotRedoParams;
+use codex_app_server_protocol::ThreadSnapshotRestoreParams;
 use codex_app_server_protocol::ThreadStartParams;
 use codex_app_server_protocol::ThreadUnarchiveParams;
 use codex_app_server_protocol::TurnInterruptParams;
@@ -443,6 +446,33 @@ impl McpProcess {
         self.send_request("thread/rollback", params).await
     }
 
+    /// Send a `thread/snapshot/list` JSON-RPC request.
+    pub async fn send_thread_snapshot_list_request(
+        &mut self,
+        params: ThreadSnapshotListParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("thread/snapshot/list", params).await
+    }
+
+    /// Send a `thread/snapshot/restore` JSON-RPC request.
+    pub async fn send_thread_snapshot_restore_request(
+        &mut self,
+        params: ThreadSnapshotRestoreParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("thread/snapshot/restore", params).await
+    }
+
+    /// Send a `thread/snapshot/redo` JSON-RPC request.
+    pub async fn send_thread_snapshot_redo_request(
+        &mut self,
+        params: ThreadSnapshotRedoParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("thread/snapshot/redo", params).await
+    }
+
     /// Send a `thread/list` JSON-RPC request.
     pub async fn send_thread_list_request(
         &mut self,
@@ -573,6 +573,7 @@ fn append_rollout_turn_context(path: &Path, timestamp: &str, model: &str) -> std
             developer_instructions: None,
             final_output_json_schema: None,
             truncation_policy: None,
+            model_route: None,
         }),
     };
     let serialized = serde_json::to_string(&line).map_err(std::io::Error::other)?;
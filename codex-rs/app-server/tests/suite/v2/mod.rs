@@ -23,6 +23,7 @@ mod thread_loaded_list;
 mod thread_read;
 mod thread_resume;
 mod thread_rollback;
+mod thread_snapshot;
 mod thread_start;
 mod thread_unarchive;
 mod turn_interrupt;
@@ -0,0 +1,116 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::create_mock_responses_server_sequence_unchecked;
+use app_test_support::to_response;
+use codex_app_server_protocol::JSONRPCError;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ThreadSnapshotListParams;
+use codex_app_server_protocol::ThreadSnapshotListResponse;
+use codex_app_server_protocol::ThreadSnapshotRedoParams;
+use codex_app_server_protocol::ThreadSnapshotRestoreParams;
+use codex_app_server_protocol::ThreadStartParams;
+use codex_app_server_protocol::ThreadStartResponse;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[tokio::test]
+async fn thread_snapshot_requests_report_missing_snapshots() -> Result<()> {
+    let server = create_mock_responses_server_sequence_unchecked(Vec::new()).await;
+
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri())?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let start_id = mcp
+        .send_thread_start_request(ThreadStartParams {
+            model: Some("mock-model".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    let start_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(start_id)),
+    )
+    .await??;
+    let ThreadStartResponse { thread, .. } = to_response::<ThreadStartResponse>(start_resp)?;
+
+    // No turn has run, so there is nothing on the timeline yet.
+    let list_id = mcp
+        .send_thread_snapshot_list_request(ThreadSnapshotListParams {
+            thread_id: thread.id.clone(),
+        })
+        .await?;
+    let list_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(list_id)),
+    )
+    .await??;
+    let list = to_response::<ThreadSnapshotListResponse>(list_resp)?;
+    assert_eq!(
+        list,
+        ThreadSnapshotListResponse {
+            snapshots: Vec::new(),
+            redo_available: false,
+        }
+    );
+
+    let restore_id = mcp
+        .send_thread_snapshot_restore_request(ThreadSnapshotRestoreParams {
+            thread_id: thread.id.clone(),
+            snapshot_id: "missing".to_string(),
+            paths: None,
+        })
+        .await?;
+    let restore_err: JSONRPCError = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(restore_id)),
+    )
+    .await??;
+    assert_eq!(
+        restore_err.error.message,
+        "Snapshot missing is not part of this session."
+    );
+
+    let redo_id = mcp
+        .send_thread_snapshot_redo_request(ThreadSnapshotRedoParams {
+            thread_id: thread.id,
+        })
+        .await?;
+    let redo_err: JSONRPCError = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(redo_id)),
+    )
+    .await??;
+    assert_eq!(redo_err.error.message, "Nothing to redo.");
+
+    Ok(())
+}
+
+fn create_config_toml(codex_home: &std::path::Path, server_uri: &str) -> std::io::Result<()> {
+    let config_toml = codex_home.join("config.toml");
+    std::fs::write(
+        config_toml,
+        format!(
+            r#"
+model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "read-only"
+
+model_provider = "mock_provider"
+
+[model_providers.mock_provider]
+name = "Mock provider for test"
+base_url = "{server_uri}/v1"
+wire_api = "responses"
+request_max_retries = 0
+stream_max_retries = 0
+"#
+        ),
+    )
+}
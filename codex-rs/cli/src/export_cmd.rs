@@ -0,0 +1,717 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use clap::ValueEnum;
+use codex_app_server_protocol::CommandExecutionStatus;
+use codex_app_server_protocol::PatchApplyStatus;
+use codex_app_server_protocol::PatchChangeKind;
+use codex_app_server_protocol::ThreadItem;
+use codex_app_server_protocol::Turn;
+use codex_app_server_protocol::TurnStatus;
+use codex_app_server_protocol::UserInput;
+use codex_app_server_protocol::build_transcript_turns_from_rollout_items;
+use codex_core::RolloutRecorder;
+use codex_core::config::find_codex_home;
+use codex_core::find_archived_thread_path_by_id_str;
+use codex_core::find_thread_name_by_id;
+use codex_core::find_thread_path_by_id_str;
+use codex_core::find_thread_path_by_name_str;
+use codex_core::review_format::render_review_output_text;
+use codex_core::secret_redaction::redact_secrets;
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::SessionMeta;
+use serde_json::Value as JsonValue;
+use serde_json::json;
+
+/// Command output longer than this many lines is truncated in Markdown and
+/// HTML transcripts. JSON exports always keep the full output.
+const MAX_RENDERED_OUTPUT_LINES: usize = 200;
+
+/// Export a recorded session as a Markdown, HTML or JSON transcript.
+#[derive(Debug, clap::Parser)]
+pub struct ExportCommand {
+    /// Thread id (UUID) or thread name to export.
+    #[arg(value_name = "THREAD")]
+    pub thread: String,
+
+    /// Transcript format.
+    #[arg(long = "format", value_enum, default_value_t = ExportFormat::Md)]
+    pub format: ExportFormat,
+
+    /// Write the transcript to this file instead of stdout.
+    #[arg(short = 'o', long = "output", value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Replace likely secrets, the session working directory and the home
+    /// directory with placeholders.
+    #[arg(long = "redact", default_value_t = false)]
+    pub redact: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    Md,
+    Html,
+    Json,
+}
+
+/// A session transcript assembled from a rollout file.
+#[derive(Debug, Clone, PartialEq)]
+struct Transcript {
+    thread_id: ThreadId,
+    thread_name: Option<String>,
+    cwd: Option<PathBuf>,
+    started_at: Option<String>,
+    turns: Vec<Turn>,
+}
+
+pub async fn run_export(cmd: ExportCommand) -> Result<()> {
+    let ExportCommand {
+        thread,
+        format,
+        output,
+        redact,
+    } = cmd;
+
+    let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+    let rollout_path = resolve_rollout_path(&codex_home, &thread)
+        .await?
+        .ok_or_else(|| anyhow!("no recorded session found for `{thread}`"))?;
+
+    let history = RolloutRecorder::get_rollout_history(&rollout_path)
+        .await
+        .with_context(|| format!("failed to read rollout {}", rollout_path.display()))?;
+    let items = history.get_rollout_items();
+    let session_meta = items.iter().find_map(|item| match item {
+        RolloutItem::SessionMeta(meta_line) => Some(meta_line.meta.clone()),
+        _ => None,
+    });
+    let Some(session_meta) = session_meta else {
+        anyhow::bail!(
+            "rollout {} is missing session metadata",
+            rollout_path.display()
+        );
+    };
+    let thread_name = find_thread_name_by_id(&codex_home, &session_meta.id)
+        .await
+        .unwrap_or_default();
+
+    let mut transcript = build_transcript(session_meta, thread_name, &items);
+    if redact {
+        let home = dirs::home_dir();
+        transcript = redact_transcript(transcript, home.as_deref())?;
+    }
+
+    let rendered = match format {
+        ExportFormat::Md => render_markdown(&transcript),
+        ExportFormat::Html => render_html(&transcript),
+        ExportFormat::Json => serde_json::to_string_pretty(&transcript_to_json(&transcript))?,
+    };
+
+    match output {
+        Some(path) => std::fs::write(&path, rendered)
+            .with_context(|| format!("failed to write {}", path.display()))?,
+        None => println!("{rendered}"),
+    }
+    Ok(())
+}
+
+/// Resolve a thread id or name to a rollout path, including archived sessions.
+async fn resolve_rollout_path(codex_home: &Path, thread: &str) -> Result<Option<PathBuf>> {
+    if ThreadId::from_string(thread).is_ok() {
+        if let Some(path) = find_thread_path_by_id_str(codex_home, thread).await? {
+            return Ok(Some(path));
+        }
+        return Ok(find_archived_thread_path_by_id_str(codex_home, thread).await?);
+    }
+    Ok(find_thread_path_by_name_str(codex_home, thread).await?)
+}
+
+fn build_transcript(
+    session_meta: SessionMeta,
+    thread_name: Option<String>,
+    items: &[RolloutItem],
+) -> Transcript {
+    let started_at = (!session_meta.timestamp.is_empty()).then_some(session_meta.timestamp);
+    let cwd = (!session_meta.cwd.as_os_str().is_empty()).then_some(session_meta.cwd);
+    Transcript {
+        thread_id: session_meta.id,
+        thread_name,
+        cwd,
+        started_at,
+        turns: build_transcript_turns_from_rollout_items(items, render_review_output_text),
+    }
+}
+
+/// Redact secrets and absolute paths from every string in the transcript.
+///
+/// Turns are round-tripped through JSON so that new item fields are covered
+/// without having to update this function.
+fn redact_transcript(transcript: Transcript, home: Option<&Path>) -> Result<Transcript> {
+    let mut path_prefixes = Vec::new();
+    if let Some(cwd) = transcript.cwd.as_deref() {
+        path_prefixes.push((cwd.display().to_string(), "<cwd>"));
+    }
+    if let Some(home) = home {
+        path_prefixes.push((home.display().to_string(), "~"));
+    }
+    // Replace the most specific prefix first so the cwd wins over home.
+    path_prefixes.retain(|(prefix, _)| prefix.len() > 1);
+    path_prefixes.sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+
+    let redact = |text: &str| {
+        let mut text = redact_secrets(text);
+        for (prefix, placeholder) in &path_prefixes {
+            text = replace_path_prefix(&text, prefix, placeholder);
+        }
+        text
+    };
+
+    let mut turns = serde_json::to_value(&transcript.turns)?;
+    redact_json_strings(&mut turns, &redact);
+    Ok(Transcript {
+        thread_name: transcript.thread_name.as_deref().map(redact),
+        cwd: transcript.cwd.as_ref().map(|_| PathBuf::from("<cwd>")),
+        turns: serde_json::from_value(turns)?,
+        ..transcript
+    })
+}
+
+/// Replace `prefix` with `placeholder` wherever it is a whole path or the
+/// start of one, so `/work` does not turn `/workspace` into `<cwd>space`. A
+/// match counts when a path separator, the end of the text, or a character
+/// that cannot continue a file name (whitespace, quotes, a sentence-ending
+/// period, ...) follows it.
+fn replace_path_prefix(text: &str, prefix: &str, placeholder: &str) -> String {
+    let is_name_char = |c: char| c.is_alphanumeric() || matches!(c, '-' | '_');
+    let mut out = String::with_capacity(text.len());
+    let mut copied = 0;
+    for (start, _) in text.match_indices(prefix) {
+        let end = start + prefix.len();
+        let mut rest = text[end..].chars();
+        let continues_name = match (rest.next(), rest.next()) {
+            (Some('.'), Some(after)) => is_name_char(after) || after == '.',
+            (Some(next), _) => is_name_char(next),
+            (None, _) => false,
+        };
+        if continues_name {
+            continue;
+        }
+        out.push_str(&text[copied..start]);
+        out.push_str(placeholder);
+        copied = end;
+    }
+    out.push_str(&text[copied..]);
+    out
+}
+
+fn redact_json_strings(value: &mut JsonValue, redact: &impl Fn(&str) -> String) {
+    match value {
+        JsonValue::String(text) => *text = redact(text),
+        JsonValue::Array(values) => {
+            for value in values {
+                redact_json_strings(value, redact);
+            }
+        }
+        JsonValue::Object(map) => {
+            for value in map.values_mut() {
+                redact_json_strings(value, redact);
+            }
+        }
+        JsonValue::Null | JsonValue::Bool(_) | JsonValue::Number(_) => {}
+    }
+}
+
+fn transcript_to_json(transcript: &Transcript) -> JsonValue {
+    json!({
+        "threadId": transcript.thread_id.to_string(),
+        "threadName": transcript.thread_name,
+        "cwd": transcript.cwd,
+        "startedAt": transcript.started_at,
+        "turns": transcript.turns,
+    })
+}
+
+fn transcript_title(transcript: &Transcript) -> String {
+    transcript
+        .thread_name
+        .clone()
+        .unwrap_or_else(|| format!("Codex session {}", transcript.thread_id))
+}
+
+fn user_input_text(content: &[UserInput]) -> String {
+    content
+        .iter()
+        .map(|input| match input {
+            UserInput::Text { text, .. } => text.clone(),
+            UserInput::Image { url } => format!("[image: {url}]"),
+            UserInput::LocalImage { path } => format!("[image: {}]", path.display()),
+            UserInput::Skill { name, .. } => format!("[skill: {name}]"),
+            UserInput::Mention { name, .. } => format!("[mention: {name}]"),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn command_summary(status: &CommandExecutionStatus, exit_code: Option<i32>) -> String {
+    match (status, exit_code) {
+        (_, Some(code)) => format!("exit {code}"),
+        (CommandExecutionStatus::InProgress, None) => "no output recorded".to_string(),
+        (CommandExecutionStatus::Completed, None) => "completed".to_string(),
+        (CommandExecutionStatus::Failed, None) => "failed".to_string(),
+        (CommandExecutionStatus::Declined, None) => "declined".to_string(),
+    }
+}
+
+fn patch_status_label(status: &PatchApplyStatus) -> &'static str {
+    match status {
+        PatchApplyStatus::InProgress => "pending",
+        PatchApplyStatus::Completed => "applied",
+        PatchApplyStatus::Failed => "failed",
+        PatchApplyStatus::Declined => "declined",
+    }
+}
+
+fn change_label(path: &str, kind: &PatchChangeKind) -> String {
+    match kind {
+        PatchChangeKind::Add => format!("added {path}"),
+        PatchChangeKind::Delete => format!("deleted {path}"),
+        PatchChangeKind::Update {
+            move_path: Some(dest),
+        } => format!("moved {path} → {}", dest.display()),
+        PatchChangeKind::Update { move_path: None } => format!("updated {path}"),
+    }
+}
+
+/// Keep at most [`MAX_RENDERED_OUTPUT_LINES`] lines of command output.
+fn truncate_output(output: &str) -> String {
+    let total = output.lines().count();
+    if total <= MAX_RENDERED_OUTPUT_LINES {
+        return output.trim_end().to_string();
+    }
+    let mut kept = output
+        .lines()
+        .take(MAX_RENDERED_OUTPUT_LINES)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let omitted = total - MAX_RENDERED_OUTPUT_LINES;
+    kept.push_str(&format!("\n… {omitted} more lines"));
+    kept
+}
+
+/// Pick a backtick fence that is longer than any run of backticks in `text`.
+fn code_fence(text: &str) -> String {
+    let mut longest = 0;
+    let mut current = 0;
+    for ch in text.chars() {
+        if ch == '`' {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    "`".repeat(longest.max(2) + 1)
+}
+
+fn push_fenced(out: &mut String, lang: &str, text: &str) {
+    let fence = code_fence(text);
+    out.push_str(&format!("{fence}{lang}\n{}\n{fence}\n\n", text.trim_end()));
+}
+
+fn render_markdown(transcript: &Transcript) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("# {}\n\n", transcript_title(transcript)));
+    out.push_str(&format!("- Thread: `{}`\n", transcript.thread_id));
+    if let Some(cwd) = &transcript.cwd {
+        out.push_str(&format!("- Working directory: `{}`\n", cwd.display()));
+    }
+    if let Some(started_at) = &transcript.started_at {
+        out.push_str(&format!("- Started: {started_at}\n"));
+    }
+    out.push('\n');
+
+    for (index, turn) in transcript.turns.iter().enumerate() {
+        out.push_str(&format!("## Turn {}", index + 1));
+        match turn.status {
+            TurnStatus::Interrupted => out.push_str(" (interrupted)"),
+            TurnStatus::Failed => out.push_str(" (failed)"),
+            TurnStatus::Completed | TurnStatus::InProgress => {}
+        }
+        out.push_str("\n\n");
+        for item in &turn.items {
+            render_markdown_item(&mut out, item);
+        }
+    }
+    out.trim_end().to_string()
+}
+
+fn render_markdown_item(out: &mut String, item: &ThreadItem) {
+    match item {
+        ThreadItem::UserMessage { content, .. } => {
+            out.push_str("**User**\n\n");
+            for line in user_input_text(content).lines() {
+                out.push_str(&format!("> {line}\n"));
+            }
+            out.push('\n');
+        }
+        ThreadItem::AgentMessage { text, .. } => {
+            out.push_str("**Codex**\n\n");
+            out.push_str(text.trim_end());
+            out.push_str("\n\n");
+        }
+        ThreadItem::Plan { text, .. } => {
+            out.push_str("**Plan**\n\n");
+            out.push_str(text.trim_end());
+            out.push_str("\n\n");
+        }
+        ThreadItem::CommandExecution {
+            command,
+            status,
+            aggregated_output,
+            exit_code,
+            ..
+        } => {
+            let summary = command_summary(status, *exit_code);
+            out.push_str(&format!(
+                "<details>\n<summary><code>$ {}</code> ({summary})</summary>\n\n",
+                html_escape(command)
+            ));
+            if let Some(output) = aggregated_output.as_deref()
+                && !output.trim().is_empty()
+            {
+                push_fenced(out, "text", &truncate_output(output));
+            }
+            out.push_str("</details>\n\n");
+        }
+        ThreadItem::FileChange {
+            changes, status, ..
+        } => {
+            out.push_str(&format!(
+                "**Edited files** ({})\n\n",
+                patch_status_label(status)
+            ));
+            for change in changes {
+                out.push_str(&format!("- {}\n", change_label(&change.path, &change.kind)));
+            }
+            out.push('\n');
+            for change in changes.iter().filter(|change| !change.diff.is_empty()) {
+                push_fenced(out, "diff", &change.diff);
+            }
+        }
+        ThreadItem::EnteredReviewMode { review, .. } => {
+            out.push_str(&format!("**Review started:** {review}\n\n"));
+        }
+        ThreadItem::ExitedReviewMode { review, .. } => {
+            out.push_str("**Review findings**\n\n");
+            out.push_str(review.trim_end());
+            out.push_str("\n\n");
+        }
+        ThreadItem::Reasoning { .. }
+        | ThreadItem::McpToolCall { .. }
+        | ThreadItem::CollabAgentToolCall { .. }
+        | ThreadItem::WebSearch { .. }
+        | ThreadItem::ImageView { .. }
+        | ThreadItem::ContextCompaction { .. } => {}
+    }
+}
+
+fn html_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+const HTML_STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:960px;margin:2em auto;padding:0 1em;line-height:1.5}\
+pre{background:#f6f8fa;padding:.75em;overflow-x:auto}\
+.user{border-left:4px solid #0969da;padding-left:.75em}\
+.diff-add{color:#1a7f37}.diff-del{color:#cf222e}\
+summary{cursor:pointer}";
+
+fn render_html(transcript: &Transcript) -> String {
+    let title = html_escape(&transcript_title(transcript));
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str(&format!(
+        "<title>{title}</title>\n<style>{HTML_STYLE}</style>\n"
+    ));
+    out.push_str("</head>\n<body>\n");
+    out.push_str(&format!("<h1>{title}</h1>\n<ul>\n"));
+    out.push_str(&format!(
+        "<li>Thread: <code>{}</code></li>\n",
+        transcript.thread_id
+    ));
+    if let Some(cwd) = &transcript.cwd {
+        out.push_str(&format!(
+            "<li>Working directory: <code>{}</code></li>\n",
+            html_escape(&cwd.display().to_string())
+        ));
+    }
+    if let Some(started_at) = &transcript.started_at {
+        out.push_str(&format!("<li>Started: {}</li>\n", html_escape(started_at)));
+    }
+    out.push_str("</ul>\n");
+
+    for (index, turn) in transcript.turns.iter().enumerate() {
+        out.push_str(&format!("<h2>Turn {}", index + 1));
+        match turn.status {
+            TurnStatus::Interrupted => out.push_str(" (interrupted)"),
+            TurnStatus::Failed => out.push_str(" (failed)"),
+            TurnStatus::Completed | TurnStatus::InProgress => {}
+        }
+        out.push_str("</h2>\n");
+        for item in &turn.items {
+            render_html_item(&mut out, item);
+        }
+    }
+    out.push_str("</body>\n</html>");
+    out
+}
+
+fn render_html_item(out: &mut String, item: &ThreadItem) {
+    match item {
+        ThreadItem::UserMessage { content, .. } => {
+            out.push_str(&format!(
+                "<div class=\"user\"><strong>User</strong><pre>{}</pre></div>\n",
+                html_escape(&user_input_text(content))
+            ));
+        }
+        ThreadItem::AgentMessage { text, .. } => {
+            out.push_str(&format!(
+                "<div><strong>Codex</strong><pre>{}</pre></div>\n",
+                html_escape(text.trim_end())
+            ));
+        }
+        ThreadItem::Plan { text, .. } => {
+            out.push_str(&format!(
+                "<div><strong>Plan</strong><pre>{}</pre></div>\n",
+                html_escape(text.trim_end())
+            ));
+        }
+        ThreadItem::CommandExecution {
+            command,
+            status,
+            aggregated_output,
+            exit_code,
+            ..
+        } => {
+            let summary = command_summary(status, *exit_code);
+            out.push_str(&format!(
+                "<details><summary><code>$ {}</code> ({summary})</summary>",
+                html_escape(command)
+            ));
+            if let Some(output) = aggregated_output.as_deref()
+                && !output.trim().is_empty()
+            {
+                out.push_str(&format!(
+                    "<pre>{}</pre>",
+                    html_escape(&truncate_output(output))
+                ));
+            }
+            out.push_str("</details>\n");
+        }
+        ThreadItem::FileChange {
+            changes, status, ..
+        } => {
+            out.push_str(&format!(
+                "<div><strong>Edited files</strong> ({})<ul>",
+                patch_status_label(status)
+            ));
+            for change in changes {
+                out.push_str(&format!(
+                    "<li>{}</li>",
+                    html_escape(&change_label(&change.path, &change.kind))
+                ));
+            }
+            out.push_str("</ul>");
+            for change in changes.iter().filter(|change| !change.diff.is_empty()) {
+                out.push_str("<pre>");
+                for line in change.diff.lines() {
+                    let escaped = html_escape(line);
+                    if line.starts_with('+') {
+                        out.push_str(&format!("<span class=\"diff-add\">{escaped}</span>\n"));
+                    } else if line.starts_with('-') {
+                        out.push_str(&format!("<span class=\"diff-del\">{escaped}</span>\n"));
+                    } else {
+                        out.push_str(&format!("{escaped}\n"));
+                    }
+                }
+                out.push_str("</pre>");
+            }
+            out.push_str("</div>\n");
+        }
+        ThreadItem::EnteredReviewMode { review, .. } => {
+            out.push_str(&format!(
+                "<p><strong>Review started:</strong> {}</p>\n",
+                html_escape(review)
+            ));
+        }
+        ThreadItem::ExitedReviewMode { review, .. } => {
+            out.push_str(&format!(
+                "<div><strong>Review findings</strong><pre>{}</pre></div>\n",
+                html_escape(review.trim_end())
+            ));
+        }
+        ThreadItem::Reasoning { .. }
+        | ThreadItem::McpToolCall { .. }
+        | ThreadItem::CollabAgentToolCall { .. }
+        | ThreadItem::WebSearch { .. }
+        | ThreadItem::ImageView { .. }
+        | ThreadItem::ContextCompaction { .. } => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_app_server_protocol::FileUpdateChange;
+    use pretty_assertions::assert_eq;
+
+    fn sample_transcript() -> Transcript {
+        Transcript {
+            thread_id: ThreadId::from_string("67e55044-10b1-426f-9247-bb680e5fe0c8")
+                .expect("thread id"),
+            thread_name: Some("fix build".to_string()),
+            cwd: Some(PathBuf::from("/work/repo")),
+            started_at: Some("2025-01-01T00:00:00.000Z".to_string()),
+            turns: vec![Turn {
+                id: "turn-1".to_string(),
+                status: TurnStatus::Completed,
+                error: None,
+                items: vec![
+                    ThreadItem::UserMessage {
+                        id: "item-1".to_string(),
+                        content: vec![UserInput::Text {
+                            text: "Use key sk-abcdefghijklmnopqrstuvwxyz123456".to_string(),
+                            text_elements: Vec::new(),
+                        }],
+                    },
+                    ThreadItem::CommandExecution {
+                        id: "item-2".to_string(),
+                        command: "cat /work/repo/src/lib.rs".to_string(),
+                        cwd: PathBuf::from("/work/repo"),
+                        process_id: None,
+                        status: CommandExecutionStatus::Completed,
+                        command_actions: Vec::new(),
+                        aggregated_output: Some("fn main() {}".to_string()),
+                        exit_code: Some(0),
+                        duration_ms: None,
+                    },
+                    ThreadItem::FileChange {
+                        id: "item-3".to_string(),
+                        changes: vec![FileUpdateChange {
+                            path: "src/lib.rs".to_string(),
+                            kind: PatchChangeKind::Update { move_path: None },
+                            diff: "@@\n-old\n+new\n".to_string(),
+                        }],
+                        status: PatchApplyStatus::Completed,
+                    },
+                    ThreadItem::AgentMessage {
+                        id: "item-4".to_string(),
+                        text: "Done.".to_string(),
+                    },
+                ],
+            }],
+        }
+    }
+
+    #[test]
+    fn renders_markdown_transcript() {
+        let rendered = render_markdown(&sample_transcript());
+        assert_eq!(
+            rendered,
+            "# fix build
+
+- Thread: `67e55044-10b1-426f-9247-bb680e5fe0c8`
+- Working directory: `/work/repo`
+- Started: 2025-01-01T00:00:00.000Z
+
+## Turn 1
+
+**User**
+
+> Use key sk-abcdefghijklmnopqrstuvwxyz123456
+
+<details>
+<summary><code>$ cat /work/repo/src/lib.rs</code> (exit 0)</summary>
+
+```text
+fn main() {}
+```
+
+</details>
+
+**Edited files** (applied)
+
+- updated src/lib.rs
+
+```diff
+@@
+-old
++new
+```
+
+**Codex**
+
+Done."
+        );
+    }
+
+    #[test]
+    fn redacts_secrets_and_paths() {
+        let transcript =
+            redact_transcript(sample_transcript(), Some(Path::new("/work"))).expect("redact");
+        let rendered = render_markdown(&transcript);
+        assert!(rendered.contains("> Use key [REDACTED_SECRET]"));
+        assert!(rendered.contains("$ cat &lt;cwd&gt;/src/lib.rs"));
+        assert!(!rendered.contains("/work"));
+    }
+
+    #[test]
+    fn redacts_paths_only_at_file_name_boundaries() {
+        assert_eq!(
+            replace_path_prefix(
+                "/work /work/a /workspace/b /work.bak \"/work\" in /work.",
+                "/work",
+                "~"
+            ),
+            "~ ~/a /workspace/b /work.bak \"~\" in ~."
+        );
+    }
+
+    #[test]
+    fn html_shows_turn_status() {
+        let mut transcript = sample_transcript();
+        transcript.turns[0].status = TurnStatus::Interrupted;
+        let rendered = render_html(&transcript);
+        assert!(rendered.contains("<h2>Turn 1 (interrupted)</h2>"));
+    }
+
+    #[test]
+    fn html_escapes_user_content() {
+        let mut transcript = sample_transcript();
+        transcript.thread_name = Some("<script>".to_string());
+        let rendered = render_html(&transcript);
+        assert!(rendered.contains("<title>&lt;script&gt;</title>"));
+        assert!(rendered.contains("<span class=\"diff-add\">+new</span>"));
+    }
+
+    #[test]
+    fn code_fence_outgrows_embedded_backticks() {
+        assert_eq!(code_fence("plain"), "```");
+        assert_eq!(code_fence("has ``` fence"), "````");
+    }
+}
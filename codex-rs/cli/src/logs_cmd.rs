@@ -0,0 +1,355 @@
+use std::io::IsTerminal;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use chrono::DateTime;
+use chrono::SecondsFormat;
+use chrono::Utc;
+use codex_core::config::Config;
+use codex_core::state_db;
+use codex_state::LogQuery;
+use codex_state::LogRow;
+use codex_state::StateRuntime;
+use codex_utils_cli::CliConfigOverrides;
+use owo_colors::OwoColorize;
+use serde_json::json;
+
+/// Query logs recorded in the state database.
+#[derive(Debug, clap::Parser)]
+pub struct LogsCommand {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub action: Option<LogsSubcommand>,
+
+    /// Only show logs recorded for this thread id. Repeat to include several threads.
+    #[arg(long = "thread", value_name = "THREAD_ID")]
+    pub threads: Vec<String>,
+
+    /// Include logs that are not tied to a thread (combined with `--thread`).
+    #[arg(long = "threadless", default_value_t = false)]
+    pub threadless: bool,
+
+    /// Only show logs at this level (case-insensitive).
+    #[arg(long = "level", value_name = "LEVEL")]
+    pub level: Option<String>,
+
+    /// Substring match on the module path. Repeat to include several modules.
+    #[arg(long = "module", value_name = "MODULE")]
+    pub modules: Vec<String>,
+
+    /// Substring match on the source file. Repeat to include several files.
+    #[arg(long = "file", value_name = "FILE")]
+    pub files: Vec<String>,
+
+    /// Only show logs recorded at or after this time: RFC3339, unix seconds,
+    /// or an age such as `30s`, `10m`, `2h` or `1d`.
+    #[arg(long = "since", value_name = "TIME")]
+    pub since: Option<String>,
+
+    /// Only show logs recorded at or before this time (same formats as `--since`).
+    #[arg(long = "until", value_name = "TIME")]
+    pub until: Option<String>,
+
+    /// Number of the most recent matching entries to show.
+    #[arg(short = 'n', long = "limit", default_value_t = 200)]
+    pub limit: usize,
+
+    /// Keep running and print new entries as they are recorded.
+    #[arg(short = 'f', long = "follow", default_value_t = false)]
+    pub follow: bool,
+
+    /// Poll interval for `--follow`, in milliseconds.
+    #[arg(long = "poll-ms", default_value_t = 500, requires = "follow")]
+    pub poll_ms: u64,
+
+    /// Print one JSON object per entry.
+    #[arg(long = "json", default_value_t = false)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum LogsSubcommand {
+    /// Apply the `[logs]` retention limits now and compact the database file.
+    Prune(PruneArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct PruneArgs {
+    /// Only delete entries; leave the freed space in the database file.
+    #[arg(long = "no-vacuum", default_value_t = false)]
+    pub no_vacuum: bool,
+}
+
+pub async fn run_logs(cmd: LogsCommand) -> Result<()> {
+    let overrides = cmd
+        .config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(overrides)
+        .await
+        .context("failed to load configuration")?;
+    let db_path = codex_state::state_db_path(&config.codex_home);
+    let Some(runtime) =
+        state_db::open_if_present(&config.codex_home, &config.model_provider_id).await
+    else {
+        anyhow::bail!("no state database found at {}", db_path.display());
+    };
+
+    match cmd.action {
+        Some(LogsSubcommand::Prune(args)) => prune_logs(&runtime, &config, args).await,
+        None => print_logs(&runtime, cmd).await,
+    }
+}
+
+async fn prune_logs(runtime: &StateRuntime, config: &Config, args: PruneArgs) -> Result<()> {
+    let deleted_rows = runtime
+        .prune_logs(&config.log_retention)
+        .await
+        .context("failed to prune logs")?;
+    println!("Deleted {deleted_rows} log entries.");
+    if !args.no_vacuum
+        && runtime
+            .vacuum_if_sparse()
+            .await
+            .context("failed to vacuum the state database")?
+    {
+        println!("Compacted the state database.");
+    }
+    Ok(())
+}
+
+async fn print_logs(runtime: &StateRuntime, cmd: LogsCommand) -> Result<()> {
+    let now = Utc::now().timestamp();
+    let query = LogQuery {
+        level_upper: cmd.level.as_ref().map(|level| level.to_ascii_uppercase()),
+        from_ts: cmd
+            .since
+            .as_deref()
+            .map(|value| parse_time(value, now))
+            .transpose()
+            .context("failed to parse --since")?,
+        to_ts: cmd
+            .until
+            .as_deref()
+            .map(|value| parse_time(value, now))
+            .transpose()
+            .context("failed to parse --until")?,
+        module_like: non_empty(cmd.modules),
+        file_like: non_empty(cmd.files),
+        thread_ids: non_empty(cmd.threads),
+        include_threadless: cmd.threadless,
+        ..Default::default()
+    };
+    let style = if cmd.json {
+        OutputStyle::Json
+    } else if std::io::stdout().is_terminal() {
+        OutputStyle::Color
+    } else {
+        OutputStyle::Plain
+    };
+
+    let mut last_id = 0;
+    if cmd.limit > 0 {
+        let mut rows = runtime
+            .query_logs(&LogQuery {
+                limit: Some(cmd.limit),
+                descending: true,
+                ..query.clone()
+            })
+            .await
+            .context("failed to query logs")?;
+        rows.reverse();
+        for row in &rows {
+            last_id = last_id.max(row.id);
+            println!("{}", format_row(row, style));
+        }
+    }
+    if !cmd.follow {
+        return Ok(());
+    }
+    if last_id == 0 {
+        last_id = runtime
+            .max_log_id(&query)
+            .await
+            .context("failed to query logs")?;
+    }
+
+    let poll_interval = Duration::from_millis(cmd.poll_ms.max(1));
+    loop {
+        tokio::time::sleep(poll_interval).await;
+        let rows = runtime
+            .query_logs(&LogQuery {
+                after_id: Some(last_id),
+                ..query.clone()
+            })
+            .await
+            .context("failed to query logs")?;
+        for row in &rows {
+            last_id = last_id.max(row.id);
+            println!("{}", format_row(row, style));
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputStyle {
+    Plain,
+    Color,
+    Json,
+}
+
+fn non_empty(values: Vec<String>) -> Vec<String> {
+    values
+        .into_iter()
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+/// Parse a `--since`/`--until` value into unix seconds, resolving ages
+/// against `now`.
+pub(crate) fn parse_time(value: &str, now: i64) -> Result<i64> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<i64>() {
+        return Ok(secs);
+    }
+    if let Some(age) = parse_age(value) {
+        return Ok(now.saturating_sub(age));
+    }
+    let dt = DateTime::parse_from_rfc3339(value).with_context(|| {
+        format!("expected RFC3339, unix seconds or an age like `10m`, got `{value}`")
+    })?;
+    Ok(dt.timestamp())
+}
+
+/// Age in seconds for values such as `30s`, `10m`, `2h`, `1d` or `1w`.
+fn parse_age(value: &str) -> Option<i64> {
+    let unit = value.chars().last()?;
+    let multiplier = match unit {
+        's' => 1,
+        'm' => 60,
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        'w' => 7 * 24 * 60 * 60,
+        _ => return None,
+    };
+    let amount = value[..value.len() - unit.len_utf8()].parse::<i64>().ok()?;
+    amount.checked_mul(multiplier)
+}
+
+fn format_timestamp(ts: i64, ts_nanos: i64) -> String {
+    let nanos = u32::try_from(ts_nanos).unwrap_or(0);
+    match DateTime::<Utc>::from_timestamp(ts, nanos) {
+        Some(dt) => dt.to_rfc3339_opts(SecondsFormat::Millis, true),
+        None => format!("{ts}.{ts_nanos:09}Z"),
+    }
+}
+
+fn format_row(row: &LogRow, style: OutputStyle) -> String {
+    let timestamp = format_timestamp(row.ts, row.ts_nanos);
+    let message = row.message.as_deref().unwrap_or("");
+    match style {
+        OutputStyle::Json => json!({
+            "id": row.id,
+            "timestamp": timestamp,
+            "level": row.level,
+            "target": row.target,
+            "thread_id": row.thread_id,
+            "module_path": row.module_path,
+            "file": row.file,
+            "line": row.line,
+            "message": row.message,
+        })
+        .to_string(),
+        OutputStyle::Plain => {
+            let thread_id = row.thread_id.as_deref().unwrap_or("-");
+            format!(
+                "{timestamp} {:<5} [{thread_id}] {} - {message}",
+                row.level, row.target
+            )
+        }
+        OutputStyle::Color => {
+            let thread_id = row.thread_id.as_deref().unwrap_or("-");
+            format!(
+                "{} {} [{}] {} - {message}",
+                timestamp.dimmed(),
+                colored_level(&row.level),
+                thread_id.blue().dimmed(),
+                row.target.dimmed(),
+            )
+        }
+    }
+}
+
+fn colored_level(level: &str) -> String {
+    let padded = format!("{level:<5}");
+    match level.to_ascii_uppercase().as_str() {
+        "ERROR" => padded.red().bold().to_string(),
+        "WARN" => padded.yellow().bold().to_string(),
+        "INFO" => padded.green().bold().to_string(),
+        "DEBUG" => padded.blue().bold().to_string(),
+        "TRACE" => padded.magenta().bold().to_string(),
+        _ => padded.bold().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_time_accepts_unix_rfc3339_and_ages() {
+        let now = 1_700_000_000;
+        assert_eq!(parse_time("1699990000", now).ok(), Some(1_699_990_000));
+        assert_eq!(
+            parse_time("2023-11-14T22:13:20Z", now).ok(),
+            Some(1_700_000_000)
+        );
+        assert_eq!(parse_time("90s", now).ok(), Some(now - 90));
+        assert_eq!(parse_time("10m", now).ok(), Some(now - 600));
+        assert_eq!(parse_time("2h", now).ok(), Some(now - 7_200));
+        assert_eq!(parse_time("1d", now).ok(), Some(now - 86_400));
+        assert!(parse_time("yesterday", now).is_err());
+        assert!(parse_time("m", now).is_err());
+    }
+
+    #[test]
+    fn json_rows_include_source_location() {
+        let row = LogRow {
+            id: 7,
+            ts: 1_700_000_000,
+            ts_nanos: 5_000_000,
+            level: "WARN".to_string(),
+            target: "codex_core::codex".to_string(),
+            message: Some("stream idle".to_string()),
+            thread_id: None,
+            module_path: Some("codex_core::codex".to_string()),
+            file: Some("core/src/codex.rs".to_string()),
+            line: Some(42),
+        };
+
+        let value: serde_json::Value =
+            serde_json::from_str(&format_row(&row, OutputStyle::Json)).unwrap_or_default();
+        assert_eq!(
+            value,
+            json!({
+                "id": 7,
+                "timestamp": "2023-11-14T22:13:20.005Z",
+                "level": "WARN",
+                "target": "codex_core::codex",
+                "thread_id": null,
+                "module_path": "codex_core::codex",
+                "file": "core/src/codex.rs",
+                "line": 42,
+                "message": "stream idle",
+            })
+        );
+        assert_eq!(
+            format_row(&row, OutputStyle::Plain),
+            "2023-11-14T22:13:20.005Z WARN  [-] codex_core::codex - stream idle"
+        );
+    }
+}
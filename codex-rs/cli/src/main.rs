@@ -18,6 +18,7 @@ use codex_cli::login::run_logout;
 use codex_cloud_tasks::Cli as CloudTasksCli;
 use codex_exec::Cli as ExecCli;
 use codex_exec::Command as ExecCommand;
+use codex_exec::OutputFormat as ExecOutputFormat;
 use codex_exec::ReviewArgs;
 use codex_execpolicy::ExecPolicyCheckCommand;
 use codex_responses_api_proxy::Args as ResponsesApiProxyArgs;
@@ -35,11 +36,19 @@ use supports_color::Stream;
 mod app_cmd;
 #[cfg(target_os = "macos")]
 mod desktop_app;
+mod export_cmd;
+mod logs_cmd;
 mod mcp_cmd;
+mod secrets_cmd;
+mod usage_cmd;
 #[cfg(not(windows))]
 mod wsl_paths;
 
+use crate::export_cmd::ExportCommand;
+use crate::logs_cmd::LogsCommand;
 use crate::mcp_cmd::McpCli;
+use crate::secrets_cmd::SecretsCommand;
+use crate::usage_cmd::UsageCommand;
 
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
@@ -85,7 +94,7 @@ enum Subcommand {
     Exec(ExecCli),
 
     /// Run a code review non-interactively.
-    Review(ReviewArgs),
+    Review(ReviewCommand),
 
     /// Manage login.
     Login(LoginCommand),
@@ -129,6 +138,18 @@ enum Subcommand {
     /// Fork a previous interactive session (picker by default; use --last to fork the most recent).
     Fork(ForkCommand),
 
+    /// Export a recorded session as a Markdown, HTML or JSON transcript.
+    Export(ExportCommand),
+
+    /// Query logs recorded in the state database.
+    Logs(LogsCommand),
+
+    /// Show token usage per day or week, by model, project or thread.
+    Usage(UsageCommand),
+
+    /// Store secrets that config can reference as `secret:NAME`.
+    Secrets(SecretsCommand),
+
     /// [EXPERIMENTAL] Browse tasks from Codex Cloud and apply changes locally.
     #[clap(name = "cloud", alias = "cloud-tasks")]
     Cloud(CloudTasksCli),
@@ -201,6 +222,16 @@ struct ResumeCommand {
     config_overrides: TuiCli,
 }
 
+#[derive(Debug, Parser)]
+struct ReviewCommand {
+    /// Print the findings as a report instead of text (`sarif`).
+    #[arg(long = "format", value_name = "FORMAT", value_enum)]
+    format: Option<ExecOutputFormat>,
+
+    #[clap(flatten)]
+    args: ReviewArgs,
+}
+
 #[derive(Debug, Parser)]
 struct ForkCommand {
     /// Conversation/session id (UUID). When provided, forks this session.
@@ -590,9 +621,10 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             );
             codex_exec::run_main(exec_cli, codex_linux_sandbox_exe).await?;
         }
-        Some(Subcommand::Review(review_args)) => {
+        Some(Subcommand::Review(ReviewCommand { format, args })) => {
             let mut exec_cli = ExecCli::try_parse_from(["codex", "exec"])?;
-            exec_cli.command = Some(ExecCommand::Review(review_args));
+            exec_cli.command = Some(ExecCommand::Review(args));
+            exec_cli.format = format;
             prepend_config_flags(
                 &mut exec_cli.config_overrides,
                 root_config_overrides.clone(),
@@ -675,6 +707,22 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             let exit_info = run_interactive_tui(interactive, codex_linux_sandbox_exe).await?;
             handle_app_exit(exit_info)?;
         }
+        Some(Subcommand::Export(export_cli)) => {
+            export_cmd::run_export(export_cli).await?;
+        }
+        Some(Subcommand::Logs(mut logs_cli)) => {
+            prepend_config_flags(
+                &mut logs_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            logs_cmd::run_logs(logs_cli).await?;
+        }
+        Some(Subcommand::Usage(usage_cli)) => {
+            usage_cmd::run_usage(usage_cli).await?;
+        }
+        Some(Subcommand::Secrets(secrets_cli)) => {
+            secrets_cmd::run_secrets(secrets_cli)?;
+        }
         Some(Subcommand::Login(mut login_cli)) => {
             prepend_config_flags(
                 &mut login_cli.config_overrides,
@@ -0,0 +1,194 @@
+use std::io::IsTerminal;
+use std::io::Read;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_core::config::find_codex_home;
+use codex_secrets::SecretListEntry;
+use codex_secrets::SecretName;
+use codex_secrets::SecretScope;
+use codex_secrets::SecretsBackendKind;
+use codex_secrets::SecretsManager;
+use codex_secrets::environment_id_from_cwd;
+
+/// Manage secrets that config values can reference as `secret:NAME`.
+#[derive(Debug, clap::Parser)]
+pub struct SecretsCommand {
+    #[command(subcommand)]
+    pub subcommand: SecretsSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum SecretsSubcommand {
+    /// Store a secret. The value is read from stdin.
+    Set(SetArgs),
+
+    /// List stored secret names. Values are never printed.
+    List(ListArgs),
+
+    /// Delete a stored secret.
+    #[clap(visible_alias = "remove")]
+    Rm(RmArgs),
+}
+
+#[derive(Debug, clap::Args)]
+pub struct ScopeArgs {
+    /// Use the secret scope of one environment instead of the global scope.
+    /// Without a value, the environment is the project containing the
+    /// current directory.
+    #[arg(
+        long = "env",
+        value_name = "ENV_ID",
+        num_args = 0..=1,
+        default_missing_value = ""
+    )]
+    pub env: Option<String>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct SetArgs {
+    /// Secret name (A-Z, 0-9 and _).
+    pub name: String,
+
+    #[command(flatten)]
+    pub scope: ScopeArgs,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ListArgs {
+    #[command(flatten)]
+    pub scope: ScopeArgs,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct RmArgs {
+    /// Secret name (A-Z, 0-9 and _).
+    pub name: String,
+
+    #[command(flatten)]
+    pub scope: ScopeArgs,
+}
+
+impl ScopeArgs {
+    fn resolve(&self) -> Result<SecretScope> {
+        match self.env.as_deref() {
+            None => Ok(SecretScope::Global),
+            Some("") => {
+                let cwd = std::env::current_dir().context("failed to read current directory")?;
+                SecretScope::environment(environment_id_from_cwd(&cwd))
+            }
+            Some(environment_id) => SecretScope::environment(environment_id),
+        }
+    }
+}
+
+pub fn run_secrets(cmd: SecretsCommand) -> Result<()> {
+    let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+    let manager = SecretsManager::new(codex_home, SecretsBackendKind::Local);
+    match cmd.subcommand {
+        SecretsSubcommand::Set(args) => {
+            let name = SecretName::new(&args.name)?;
+            let scope = args.scope.resolve()?;
+            let value = read_secret_value(&name)?;
+            anyhow::ensure!(!value.is_empty(), "secret value must not be empty");
+            manager.set(&scope, &name, &value)?;
+            println!("Stored secret {name} ({}).", scope_label(&scope));
+        }
+        SecretsSubcommand::List(args) => {
+            let scope = args.scope.env.is_some().then(|| args.scope.resolve());
+            let entries = manager.list(scope.transpose()?.as_ref())?;
+            print!("{}", format_entries(entries));
+        }
+        SecretsSubcommand::Rm(args) => {
+            let name = SecretName::new(&args.name)?;
+            let scope = args.scope.resolve()?;
+            if !manager.delete(&scope, &name)? {
+                anyhow::bail!("no secret named {name} ({})", scope_label(&scope));
+            }
+            println!("Removed secret {name} ({}).", scope_label(&scope));
+        }
+    }
+    Ok(())
+}
+
+/// Read the value from stdin so it never lands in shell history. Interactive
+/// input is a single line typed without echo; piped input is taken whole,
+/// minus the trailing newline.
+fn read_secret_value(name: &SecretName) -> Result<String> {
+    let stdin = std::io::stdin();
+    let value = if stdin.is_terminal() {
+        rpassword::prompt_password(format!("Value for {name}: "))
+            .context("failed to read secret value")?
+    } else {
+        let mut value = String::new();
+        stdin.lock().read_to_string(&mut value)?;
+        value
+    };
+    Ok(value.trim_end_matches(['\r', '\n']).to_string())
+}
+
+fn scope_label(scope: &SecretScope) -> String {
+    match scope {
+        SecretScope::Global => "global".to_string(),
+        SecretScope::Environment(environment_id) => format!("env {environment_id}"),
+    }
+}
+
+fn format_entries(mut entries: Vec<SecretListEntry>) -> String {
+    if entries.is_empty() {
+        return "No secrets stored.\n".to_string();
+    }
+    entries.sort_by_key(|entry| {
+        (
+            entry.scope != SecretScope::Global,
+            scope_label(&entry.scope),
+            entry.name.clone(),
+        )
+    });
+    let width = entries
+        .iter()
+        .map(|entry| entry.name.as_str().len())
+        .chain(std::iter::once("NAME".len()))
+        .max()
+        .unwrap_or_default();
+    let mut out = format!("{:<width$}  SCOPE\n", "NAME");
+    for entry in entries {
+        out.push_str(&format!(
+            "{:<width$}  {}\n",
+            entry.name.as_str(),
+            scope_label(&entry.scope)
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn lists_global_secrets_before_environment_secrets() -> Result<()> {
+        let entries = vec![
+            SecretListEntry {
+                scope: SecretScope::environment("webapp")?,
+                name: SecretName::new("NPM_TOKEN")?,
+            },
+            SecretListEntry {
+                scope: SecretScope::Global,
+                name: SecretName::new("OPENAI_PROXY_KEY")?,
+            },
+        ];
+        assert_eq!(
+            format_entries(entries),
+            [
+                "NAME              SCOPE",
+                "OPENAI_PROXY_KEY  global",
+                "NPM_TOKEN         env webapp",
+                "",
+            ]
+            .join("\n")
+        );
+        Ok(())
+    }
+}
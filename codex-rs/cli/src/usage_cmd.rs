@@ -0,0 +1,274 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use chrono::DateTime;
+use chrono::Utc;
+use clap::ValueEnum;
+use codex_core::config::find_codex_home;
+use codex_core::git_info::resolve_root_git_project_for_trust;
+use codex_state::StateRuntime;
+use codex_state::UsageBucket;
+use codex_state::UsageGroupBy;
+use codex_state::UsagePeriod;
+use codex_state::UsageQuery;
+use serde_json::json;
+
+use crate::logs_cmd::parse_time;
+
+/// Show token usage recorded in the state database.
+#[derive(Debug, clap::Parser)]
+pub struct UsageCommand {
+    /// Bucket usage by UTC day or by week (starting Monday).
+    #[arg(long = "period", value_enum, default_value_t = PeriodArg::Day)]
+    pub period: PeriodArg,
+
+    /// Break usage down by model, project or thread.
+    #[arg(long = "by", value_enum, default_value_t = GroupByArg::Model)]
+    pub group_by: GroupByArg,
+
+    /// Only count usage at or after this time: RFC3339, unix seconds, or an
+    /// age such as `2d`. Defaults to the last 7 days, or 4 weeks with
+    /// `--period week`.
+    #[arg(long = "since", value_name = "TIME")]
+    pub since: Option<String>,
+
+    /// Only count usage at or before this time (same formats as `--since`).
+    #[arg(long = "until", value_name = "TIME")]
+    pub until: Option<String>,
+
+    /// Only count usage in the project containing this directory.
+    #[arg(long = "repo", value_name = "DIR")]
+    pub repo: Option<PathBuf>,
+
+    /// Only count usage of this model.
+    #[arg(long = "model", value_name = "MODEL")]
+    pub model: Option<String>,
+
+    /// Only count usage of this thread.
+    #[arg(long = "thread", value_name = "THREAD_ID")]
+    pub thread: Option<String>,
+
+    /// Print one JSON object per row.
+    #[arg(long = "json", default_value_t = false)]
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum PeriodArg {
+    Day,
+    Week,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GroupByArg {
+    Model,
+    Repo,
+    Thread,
+}
+
+pub async fn run_usage(cmd: UsageCommand) -> Result<()> {
+    let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+    let db_path = codex_state::state_db_path(&codex_home);
+    if !tokio::fs::try_exists(&db_path).await.unwrap_or(false) {
+        anyhow::bail!("no state database found at {}", db_path.display());
+    }
+    let runtime = StateRuntime::init(codex_home, "usage".to_string(), None)
+        .await
+        .context("failed to open the state database")?;
+
+    let (period, default_since) = match cmd.period {
+        PeriodArg::Day => (UsagePeriod::Day, "7d"),
+        PeriodArg::Week => (UsagePeriod::Week, "4w"),
+    };
+    let group_by = match cmd.group_by {
+        GroupByArg::Model => UsageGroupBy::Model,
+        GroupByArg::Repo => UsageGroupBy::Repo,
+        GroupByArg::Thread => UsageGroupBy::Thread,
+    };
+    let now = Utc::now().timestamp();
+    let since = parse_time(cmd.since.as_deref().unwrap_or(default_since), now)
+        .context("failed to parse --since")?;
+    let repo = cmd
+        .repo
+        .map(|dir| {
+            let dir = std::fs::canonicalize(&dir)
+                .with_context(|| format!("failed to resolve {}", dir.display()))?;
+            let root = resolve_root_git_project_for_trust(&dir).unwrap_or(dir);
+            Ok::<_, anyhow::Error>(root.to_string_lossy().into_owned())
+        })
+        .transpose()?;
+    let query = UsageQuery {
+        // Start at the beginning of the first period so it is not reported
+        // as partial.
+        from_ts: Some(period.start_of(since)),
+        to_ts: cmd
+            .until
+            .as_deref()
+            .map(|value| parse_time(value, now))
+            .transpose()
+            .context("failed to parse --until")?,
+        repo,
+        model: cmd.model,
+        thread_id: cmd.thread,
+        period,
+        group_by,
+    };
+    let buckets = runtime
+        .usage_breakdown(&query)
+        .await
+        .context("failed to query token usage")?;
+
+    if cmd.json {
+        for bucket in &buckets {
+            println!("{}", bucket_json(bucket, period, group_by));
+        }
+    } else {
+        print!("{}", format_table(&buckets, period, group_by));
+    }
+    Ok(())
+}
+
+fn period_label(period_start: i64) -> String {
+    DateTime::<Utc>::from_timestamp(period_start, 0)
+        .map(|dt| dt.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| period_start.to_string())
+}
+
+fn key_name(group_by: UsageGroupBy) -> &'static str {
+    match group_by {
+        UsageGroupBy::Model => "model",
+        UsageGroupBy::Repo => "repo",
+        UsageGroupBy::Thread => "thread",
+    }
+}
+
+fn bucket_json(bucket: &UsageBucket, period: UsagePeriod, group_by: UsageGroupBy) -> String {
+    let period_name = match period {
+        UsagePeriod::Day => "day",
+        UsagePeriod::Week => "week",
+    };
+    json!({
+        period_name: period_label(bucket.period_start),
+        key_name(group_by): bucket.key,
+        "turns": bucket.turns,
+        "input_tokens": bucket.input_tokens,
+        "cached_input_tokens": bucket.cached_input_tokens,
+        "output_tokens": bucket.output_tokens,
+        "reasoning_output_tokens": bucket.reasoning_output_tokens,
+        "total_tokens": bucket.total_tokens,
+    })
+    .to_string()
+}
+
+fn format_table(buckets: &[UsageBucket], period: UsagePeriod, group_by: UsageGroupBy) -> String {
+    if buckets.is_empty() {
+        return "No token usage recorded for this range.\n".to_string();
+    }
+    let period_header = match period {
+        UsagePeriod::Day => "DAY",
+        UsagePeriod::Week => "WEEK OF",
+    };
+    let key_header = key_name(group_by).to_ascii_uppercase();
+    // Usage recorded without a key for the grouping (e.g. no model) shows as "-".
+    let display_key = |bucket: &UsageBucket| -> String {
+        if bucket.key.is_empty() {
+            "-".to_string()
+        } else {
+            bucket.key.clone()
+        }
+    };
+    let key_width = buckets
+        .iter()
+        .map(|bucket| display_key(bucket).len())
+        .chain(std::iter::once(key_header.len()))
+        .max()
+        .unwrap_or_default();
+
+    let mut out = format!(
+        "{period_header:<10}  {key_header:<key_width$}  {:>6}  {:>12}  {:>12}  {:>12}  {:>12}\n",
+        "TURNS", "INPUT", "CACHED", "OUTPUT", "TOTAL"
+    );
+    let mut total = UsageBucket::default();
+    for bucket in buckets {
+        out.push_str(&format!(
+            "{:<10}  {:<key_width$}  {:>6}  {:>12}  {:>12}  {:>12}  {:>12}\n",
+            period_label(bucket.period_start),
+            display_key(bucket),
+            bucket.turns,
+            bucket.input_tokens,
+            bucket.cached_input_tokens,
+            bucket.output_tokens,
+            bucket.total_tokens,
+        ));
+        total.turns += bucket.turns;
+        total.input_tokens += bucket.input_tokens;
+        total.cached_input_tokens += bucket.cached_input_tokens;
+        total.output_tokens += bucket.output_tokens;
+        total.total_tokens += bucket.total_tokens;
+    }
+    out.push_str(&format!(
+        "{:<10}  {:<key_width$}  {:>6}  {:>12}  {:>12}  {:>12}  {:>12}\n",
+        "total",
+        "",
+        total.turns,
+        total.input_tokens,
+        total.cached_input_tokens,
+        total.output_tokens,
+        total.total_tokens,
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn table_lists_buckets_and_totals() {
+        let bucket = |period_start, key: &str, turns, total| UsageBucket {
+            period_start,
+            key: key.to_string(),
+            turns,
+            input_tokens: total - 10,
+            cached_input_tokens: 0,
+            output_tokens: 10,
+            reasoning_output_tokens: 0,
+            total_tokens: total,
+        };
+        let buckets = vec![
+            bucket(1_704_067_200, "/src/app", 3, 1_000),
+            bucket(1_704_067_200, "", 1, 50),
+        ];
+        assert_eq!(
+            format_table(&buckets, UsagePeriod::Week, UsageGroupBy::Repo),
+            [
+                "WEEK OF     REPO       TURNS         INPUT        CACHED        OUTPUT         TOTAL",
+                "2024-01-01  /src/app       3           990             0            10          1000",
+                "2024-01-01  -              1            40             0            10            50",
+                "total                      4          1030             0            20          1050",
+                "",
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&bucket_json(
+                &buckets[0],
+                UsagePeriod::Week,
+                UsageGroupBy::Repo
+            ))
+            .unwrap_or_default(),
+            json!({
+                "week": "2024-01-01",
+                "repo": "/src/app",
+                "turns": 3,
+                "input_tokens": 990,
+                "cached_input_tokens": 0,
+                "output_tokens": 10,
+                "reasoning_output_tokens": 0,
+                "total_tokens": 1000,
+            })
+        );
+    }
+}
@@ -0,0 +1,850 @@
+//! A [`CloudBackend`] that runs tasks on this machine.
+//!
+//! Every task lives in a directory under the backend root:
+//!
+//! ```text
+//! tasks/<task id>/task.json             prompt, repository and base commit
+//! tasks/<task id>/attempts/<n>/worktree  detached git worktree the attempt edits
+//! tasks/<task id>/attempts/<n>/events.jsonl   `codex exec --json` output
+//! tasks/<task id>/attempts/<n>/pid            process id of the running job
+//! tasks/<task id>/attempts/<n>/exit_code      written when the job exits
+//! tasks/<task id>/attempts/<n>/diff.patch     the attempt's changes once it ended
+//! ```
+//!
+//! Attempts run as `codex exec` jobs inside their own worktree, so best-of-N
+//! attempts never see each other's edits. Jobs are either started right away
+//! in the background or left queued for [`LocalClient::run_next_queued`],
+//! which lets a long-running worker process drain the queue directory.
+//!
+//! A job whose process is gone without an exit code (for example because the
+//! process that started it exited first) counts as failed. Once a job has
+//! ended its diff is saved next to it and the worktree is removed.
+
+use crate::ApplyOutcome;
+use crate::ApplyStatus;
+use crate::AttemptStatus;
+use crate::CloudBackend;
+use crate::CloudTaskError;
+use crate::CreatedTask;
+use crate::DiffSummary;
+use crate::Result;
+use crate::TaskId;
+use crate::TaskListPage;
+use crate::TaskStatus;
+use crate::TaskSummary;
+use crate::TurnAttempt;
+use crate::api::TaskText;
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+const TASKS_DIR: &str = "tasks";
+const ATTEMPTS_DIR: &str = "attempts";
+const TASK_FILE: &str = "task.json";
+const WORKTREE_DIR: &str = "worktree";
+const EVENTS_FILE: &str = "events.jsonl";
+const STDERR_FILE: &str = "stderr.log";
+const LAST_MESSAGE_FILE: &str = "last_message.md";
+const CLAIM_FILE: &str = "claimed";
+const PID_FILE: &str = "pid";
+const EXIT_CODE_FILE: &str = "exit_code";
+const DIFF_FILE: &str = "diff.patch";
+const DIFF_INDEX_FILE: &str = "diff.index";
+
+#[derive(Clone, Debug)]
+pub struct LocalClient {
+    root: PathBuf,
+    codex_exe: PathBuf,
+    queue_only: bool,
+}
+
+impl LocalClient {
+    /// Stores tasks under `root`, which is created on demand.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            codex_exe: PathBuf::from("codex"),
+            queue_only: false,
+        }
+    }
+
+    /// Binary used to run attempts (`codex` on `PATH` by default).
+    pub fn with_codex_exe(mut self, exe: impl Into<PathBuf>) -> Self {
+        self.codex_exe = exe.into();
+        self
+    }
+
+    /// Leave new attempts queued for [`Self::run_next_queued`] instead of
+    /// starting them right away.
+    pub fn with_queue_only(mut self, queue_only: bool) -> Self {
+        self.queue_only = queue_only;
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Claims the oldest queued attempt and runs it to completion. Returns the
+    /// task it belonged to, or `None` when nothing is queued.
+    pub fn run_next_queued(&self) -> Result<Option<TaskId>> {
+        let mut tasks = self.load_tasks()?;
+        tasks.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        for task in tasks {
+            for attempt in 1..=task.attempts {
+                let dir = self.attempt_dir(&task.id, attempt);
+                if !claim_attempt(&dir)? {
+                    continue;
+                }
+                let mut child = self
+                    .job_command(&task, &dir)?
+                    .spawn()
+                    .map_err(|e| io_error("failed to run codex exec", e))?;
+                record_pid(&dir, child.id())?;
+                let exit_code = child
+                    .wait()
+                    .map_err(|e| io_error("failed to run codex exec", e))?
+                    .code()
+                    .unwrap_or(-1);
+                fs::write(dir.join(EXIT_CODE_FILE), exit_code.to_string())
+                    .map_err(|e| io_error("failed to record exit code", e))?;
+                self.finish_attempt(&task, attempt)?;
+                return Ok(Some(TaskId(task.id)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Runs blocking filesystem and git work off the async executor.
+    async fn blocking<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(LocalClient) -> Result<T> + Send + 'static,
+    {
+        let client = self.clone();
+        tokio::task::spawn_blocking(move || f(client))
+            .await
+            .map_err(|e| CloudTaskError::Msg(format!("local task worker failed: {e}")))?
+    }
+
+    fn tasks_dir(&self) -> PathBuf {
+        self.root.join(TASKS_DIR)
+    }
+
+    fn task_dir(&self, id: &str) -> PathBuf {
+        self.tasks_dir().join(id)
+    }
+
+    fn attempt_dir(&self, id: &str, attempt: usize) -> PathBuf {
+        self.task_dir(id)
+            .join(ATTEMPTS_DIR)
+            .join(attempt.to_string())
+    }
+
+    fn load_task(&self, id: &str) -> Result<TaskRecord> {
+        let path = self.task_dir(id).join(TASK_FILE);
+        let contents = fs::read_to_string(&path)
+            .map_err(|_| CloudTaskError::Msg(format!("Task {id} not found (local)")))?;
+        serde_json::from_str(&contents)
+            .map_err(|e| CloudTaskError::Msg(format!("invalid {}: {e}", path.display())))
+    }
+
+    fn save_task(&self, task: &TaskRecord) -> Result<()> {
+        let json = serde_json::to_vec_pretty(task)
+            .map_err(|e| CloudTaskError::Msg(format!("failed to serialize task: {e}")))?;
+        fs::write(self.task_dir(&task.id).join(TASK_FILE), json)
+            .map_err(|e| io_error("failed to write task", e))
+    }
+
+    fn load_tasks(&self) -> Result<Vec<TaskRecord>> {
+        let entries = match fs::read_dir(self.tasks_dir()) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(io_error("failed to list tasks", e)),
+        };
+        let mut tasks = Vec::new();
+        for entry in entries.flatten() {
+            if let Some(id) = entry.file_name().to_str()
+                && let Ok(task) = self.load_task(id)
+            {
+                tasks.push(task);
+            }
+        }
+        Ok(tasks)
+    }
+
+    fn attempt(&self, task: &TaskRecord, attempt: usize) -> Result<TurnAttempt> {
+        let dir = self.attempt_dir(&task.id, attempt);
+        let status = attempt_status(&dir);
+        Ok(TurnAttempt {
+            turn_id: attempt_turn_id(&task.id, attempt),
+            attempt_placement: Some(attempt as i64 - 1),
+            created_at: Some(task.created_at),
+            status,
+            diff: self.attempt_diff(task, attempt)?,
+            messages: attempt_messages(&dir),
+        })
+    }
+
+    /// Changes the attempt made relative to the task's base commit, including
+    /// untracked files. Once the job has ended the diff is cached and the
+    /// worktree removed.
+    fn attempt_diff(&self, task: &TaskRecord, attempt: usize) -> Result<Option<String>> {
+        let dir = self.attempt_dir(&task.id, attempt);
+        let cached = dir.join(DIFF_FILE);
+        if let Ok(diff) = fs::read_to_string(&cached) {
+            return Ok(non_empty(diff));
+        }
+        let worktree = dir.join(WORKTREE_DIR);
+        if !worktree.is_dir() {
+            return Ok(None);
+        }
+
+        // Check before diffing so a job that ends meanwhile is diffed again.
+        let ended = job_ended(&dir);
+        let diff = match worktree_diff(&dir, &worktree, &task.base_commit) {
+            Ok(diff) => diff,
+            // Another reader may have cached the diff and removed the worktree.
+            Err(err) => return fs::read_to_string(&cached).map(non_empty).map_err(|_| err),
+        };
+
+        if ended && write_atomically(&cached, &diff).is_ok() {
+            remove_worktree(&task.repo, &worktree);
+        }
+        Ok(non_empty(diff))
+    }
+
+    /// Saves the diff of an attempt whose job has exited and drops its worktree.
+    fn finish_attempt(&self, task: &TaskRecord, attempt: usize) -> Result<()> {
+        self.attempt_diff(task, attempt).map(|_| ())
+    }
+
+    fn summary(&self, task: &TaskRecord) -> Result<TaskSummary> {
+        let statuses: Vec<AttemptStatus> = (1..=task.attempts)
+            .map(|attempt| attempt_status(&self.attempt_dir(&task.id, attempt)))
+            .collect();
+        let summary = match self.attempt_diff(task, 1)? {
+            Some(diff) => diff_summary(&diff),
+            None => DiffSummary::default(),
+        };
+        let updated_at = (1..=task.attempts)
+            .filter_map(|attempt| {
+                let events = self.attempt_dir(&task.id, attempt).join(EVENTS_FILE);
+                fs::metadata(events).and_then(|m| m.modified()).ok()
+            })
+            .map(DateTime::<Utc>::from)
+            .max()
+            .unwrap_or(task.created_at);
+        Ok(TaskSummary {
+            id: TaskId(task.id.clone()),
+            title: task.title.clone(),
+            status: task_status(task.applied, &statuses),
+            updated_at,
+            environment_id: Some(task.environment_id.clone()),
+            environment_label: task
+                .repo
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned()),
+            summary,
+            is_review: task.qa_mode,
+            attempt_total: Some(task.attempts),
+        })
+    }
+
+    /// `codex exec` invocation for one attempt, with its output wired into
+    /// the attempt directory.
+    fn job_command(&self, task: &TaskRecord, dir: &Path) -> Result<Command> {
+        let events = File::create(dir.join(EVENTS_FILE))
+            .map_err(|e| io_error("failed to create events log", e))?;
+        let stderr = File::create(dir.join(STDERR_FILE))
+            .map_err(|e| io_error("failed to create stderr log", e))?;
+        let worktree = dir.join(WORKTREE_DIR);
+        let mut cmd = Command::new(&self.codex_exe);
+        cmd.arg("exec").arg("--json").arg("--cd").arg(&worktree);
+        if task.qa_mode {
+            cmd.args(["--sandbox", "read-only"]);
+        } else {
+            cmd.arg("--full-auto");
+        }
+        cmd.arg("--output-last-message")
+            .arg(dir.join(LAST_MESSAGE_FILE))
+            .arg("--")
+            .arg(&task.prompt)
+            .current_dir(&worktree)
+            .stdin(Stdio::null())
+            .stdout(events)
+            .stderr(stderr);
+        Ok(cmd)
+    }
+
+    fn spawn_attempt(&self, task: &TaskRecord, attempt: usize) -> Result<()> {
+        let dir = self.attempt_dir(&task.id, attempt);
+        if !claim_attempt(&dir)? {
+            return Ok(());
+        }
+        let mut child = self
+            .job_command(task, &dir)?
+            .spawn()
+            .map_err(|e| io_error("failed to start codex exec", e))?;
+        record_pid(&dir, child.id())?;
+        // Reap the job and record how it ended; the task status is derived
+        // from the files it leaves behind. If this process exits first, the
+        // recorded pid lets readers notice that the job is gone.
+        let client = self.clone();
+        let task = task.clone();
+        std::thread::spawn(move || {
+            let code = child.wait().ok().and_then(|s| s.code()).unwrap_or(-1);
+            if fs::write(dir.join(EXIT_CODE_FILE), code.to_string()).is_ok() {
+                let _ = client.finish_attempt(&task, attempt);
+            }
+        });
+        Ok(())
+    }
+
+    /// Applies the task's diff to the repository the task was created from.
+    fn apply(
+        &self,
+        id: TaskId,
+        diff_override: Option<String>,
+        preflight: bool,
+    ) -> Result<ApplyOutcome> {
+        let id = id.0;
+        let mut task = self.load_task(&id)?;
+        let diff = match diff_override {
+            Some(diff) => diff,
+            None => self
+                .attempt_diff(&task, 1)?
+                .ok_or_else(|| CloudTaskError::Msg(format!("No diff available for task {id}")))?,
+        };
+
+        let req = codex_git::ApplyGitRequest {
+            cwd: task.repo.clone(),
+            diff,
+            revert: false,
+            preflight,
+        };
+        let r = codex_git::apply_git_patch(&req)
+            .map_err(|e| CloudTaskError::Io(format!("git apply failed to run: {e}")))?;
+        let status = if r.exit_code == 0 {
+            ApplyStatus::Success
+        } else if !r.applied_paths.is_empty() || !r.conflicted_paths.is_empty() {
+            ApplyStatus::Partial
+        } else {
+            ApplyStatus::Error
+        };
+        let applied = matches!(status, ApplyStatus::Success) && !preflight;
+        if applied {
+            task.applied = true;
+            self.save_task(&task)?;
+        }
+
+        let mode = if preflight { "Preflight" } else { "Apply" };
+        let message = match status {
+            ApplyStatus::Success if preflight => {
+                format!("Preflight passed for task {id} (applies cleanly)")
+            }
+            ApplyStatus::Success => format!(
+                "Applied task {id} locally ({} files)",
+                r.applied_paths.len()
+            ),
+            ApplyStatus::Partial | ApplyStatus::Error => format!(
+                "{mode} failed for task {id} (applied={}, skipped={}, conflicts={})",
+                r.applied_paths.len(),
+                r.skipped_paths.len(),
+                r.conflicted_paths.len()
+            ),
+        };
+        Ok(ApplyOutcome {
+            applied,
+            status,
+            message,
+            skipped_paths: r.skipped_paths,
+            conflict_paths: r.conflicted_paths,
+        })
+    }
+
+    fn create(
+        &self,
+        env_id: &str,
+        prompt: &str,
+        git_ref: &str,
+        qa_mode: bool,
+        best_of_n: usize,
+    ) -> Result<CreatedTask> {
+        // Local environments are identified by their repository root.
+        let start = Some(PathBuf::from(env_id))
+            .filter(|path| path.is_dir())
+            .map_or_else(std::env::current_dir, Ok)
+            .map_err(|e| io_error("failed to resolve the working directory", e))?;
+        let repo = PathBuf::from(git_line(&start, &["rev-parse", "--show-toplevel"])?);
+        let base_commit = git_line(&repo, &["rev-parse", &format!("{git_ref}^{{commit}}")])?;
+
+        let created_at = Utc::now();
+        let id = format!("local-{}", created_at.format("%Y%m%d-%H%M%S-%3f"));
+        let task_dir = self.task_dir(&id);
+        fs::create_dir_all(self.tasks_dir()).map_err(|e| io_error("failed to create root", e))?;
+        fs::create_dir(&task_dir).map_err(|e| io_error("failed to create task", e))?;
+
+        let task = TaskRecord {
+            id: id.clone(),
+            title: task_title(prompt),
+            prompt: prompt.to_string(),
+            environment_id: env_id.to_string(),
+            repo,
+            git_ref: git_ref.to_string(),
+            base_commit,
+            created_at,
+            qa_mode,
+            attempts: best_of_n.max(1),
+            applied: false,
+        };
+        self.save_task(&task)?;
+        for attempt in 1..=task.attempts {
+            let dir = self.attempt_dir(&id, attempt);
+            fs::create_dir_all(&dir).map_err(|e| io_error("failed to create attempt", e))?;
+            let worktree = dir.join(WORKTREE_DIR);
+            git_output(
+                &task.repo,
+                &[
+                    "worktree",
+                    "add",
+                    "--detach",
+                    &worktree.to_string_lossy(),
+                    &task.base_commit,
+                ],
+                None,
+            )
+            .map_err(|e| CloudTaskError::Msg(format!("failed to create worktree: {e}")))?;
+            if !self.queue_only {
+                self.spawn_attempt(&task, attempt)?;
+            }
+        }
+        Ok(CreatedTask { id: TaskId(id) })
+    }
+}
+
+#[async_trait::async_trait]
+impl CloudBackend for LocalClient {
+    async fn list_tasks(
+        &self,
+        env: Option<&str>,
+        limit: Option<i64>,
+        cursor: Option<&str>,
+    ) -> Result<TaskListPage> {
+        let env = env.map(str::to_string);
+        let cursor = cursor.map(str::to_string);
+        self.blocking(move |client| {
+            let mut tasks = client.load_tasks()?;
+            tasks.retain(|task| env.as_ref().is_none_or(|env| task.environment_id == *env));
+            tasks.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+            let offset = cursor.and_then(|c| c.parse::<usize>().ok()).unwrap_or(0);
+            let limit = limit
+                .and_then(|limit| usize::try_from(limit).ok())
+                .unwrap_or(tasks.len());
+            let next = offset.saturating_add(limit);
+            let cursor = (next < tasks.len()).then(|| next.to_string());
+            let tasks = tasks
+                .iter()
+                .skip(offset)
+                .take(limit)
+                .map(|task| client.summary(task))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(TaskListPage { tasks, cursor })
+        })
+        .await
+    }
+
+    async fn get_task_summary(&self, id: TaskId) -> Result<TaskSummary> {
+        self.blocking(move |client| client.summary(&client.load_task(&id.0)?))
+            .await
+    }
+
+    async fn get_task_diff(&self, id: TaskId) -> Result<Option<String>> {
+        self.blocking(move |client| client.attempt_diff(&client.load_task(&id.0)?, 1))
+            .await
+    }
+
+    async fn get_task_messages(&self, id: TaskId) -> Result<Vec<String>> {
+        self.blocking(move |client| {
+            let task = client.load_task(&id.0)?;
+            Ok(attempt_messages(&client.attempt_dir(&task.id, 1)))
+        })
+        .await
+    }
+
+    async fn get_task_text(&self, id: TaskId) -> Result<TaskText> {
+        self.blocking(move |client| {
+            let task = client.load_task(&id.0)?;
+            let dir = client.attempt_dir(&task.id, 1);
+            Ok(TaskText {
+                prompt: Some(task.prompt.clone()),
+                messages: attempt_messages(&dir),
+                turn_id: Some(attempt_turn_id(&task.id, 1)),
+                sibling_turn_ids: (2..=task.attempts)
+                    .map(|attempt| attempt_turn_id(&task.id, attempt))
+                    .collect(),
+                attempt_placement: Some(0),
+                attempt_status: attempt_status(&dir),
+            })
+        })
+        .await
+    }
+
+    async fn list_sibling_attempts(
+        &self,
+        task: TaskId,
+        turn_id: String,
+    ) -> Result<Vec<TurnAttempt>> {
+        self.blocking(move |client| {
+            let task = client.load_task(&task.0)?;
+            (1..=task.attempts)
+                .filter(|attempt| attempt_turn_id(&task.id, *attempt) != turn_id)
+                .map(|attempt| client.attempt(&task, attempt))
+                .collect()
+        })
+        .await
+    }
+
+    async fn apply_task_preflight(
+        &self,
+        id: TaskId,
+        diff_override: Option<String>,
+    ) -> Result<ApplyOutcome> {
+        self.blocking(move |client| client.apply(id, diff_override, true))
+            .await
+    }
+
+    async fn apply_task(&self, id: TaskId, diff_override: Option<String>) -> Result<ApplyOutcome> {
+        self.blocking(move |client| client.apply(id, diff_override, false))
+            .await
+    }
+
+    async fn create_task(
+        &self,
+        env_id: &str,
+        prompt: &str,
+        git_ref: &str,
+        qa_mode: bool,
+        best_of_n: usize,
+    ) -> Result<CreatedTask> {
+        let env_id = env_id.to_string();
+        let prompt = prompt.to_string();
+        let git_ref = git_ref.to_string();
+        self.blocking(move |client| client.create(&env_id, &prompt, &git_ref, qa_mode, best_of_n))
+            .await
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct TaskRecord {
+    id: String,
+    title: String,
+    prompt: String,
+    environment_id: String,
+    repo: PathBuf,
+    git_ref: String,
+    base_commit: String,
+    created_at: DateTime<Utc>,
+    qa_mode: bool,
+    attempts: usize,
+    #[serde(default)]
+    applied: bool,
+}
+
+fn attempt_turn_id(task_id: &str, attempt: usize) -> String {
+    format!("{task_id}-attempt-{attempt}")
+}
+
+fn task_title(prompt: &str) -> String {
+    const MAX_CHARS: usize = 80;
+    let first_line = prompt.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+    let first_line = first_line.trim();
+    if first_line.chars().count() > MAX_CHARS {
+        let truncated: String = first_line.chars().take(MAX_CHARS - 1).collect();
+        format!("{truncated}…")
+    } else {
+        first_line.to_string()
+    }
+}
+
+/// Marks an attempt as taken. Returns false when another process claimed it.
+fn claim_attempt(dir: &Path) -> Result<bool> {
+    match OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(dir.join(CLAIM_FILE))
+    {
+        Ok(mut file) => {
+            let _ = writeln!(file, "{}", std::process::id());
+            Ok(true)
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(io_error("failed to claim attempt", e)),
+    }
+}
+
+fn attempt_status(dir: &Path) -> AttemptStatus {
+    let events = fs::read_to_string(dir.join(EVENTS_FILE)).ok();
+    let from_events = events.as_deref().and_then(status_from_events);
+    if let Ok(code) = fs::read_to_string(dir.join(EXIT_CODE_FILE)) {
+        return if code.trim() == "0" && from_events != Some(AttemptStatus::Failed) {
+            AttemptStatus::Completed
+        } else {
+            AttemptStatus::Failed
+        };
+    }
+    if let Some(status) = from_events {
+        status
+    } else if !dir.join(CLAIM_FILE).exists() {
+        AttemptStatus::Pending
+    } else if job_ended(dir) {
+        AttemptStatus::Failed
+    } else {
+        AttemptStatus::InProgress
+    }
+}
+
+/// Diff of `worktree` against `base_commit`, staged into a throwaway index so
+/// the worktree's own index is untouched.
+fn worktree_diff(dir: &Path, worktree: &Path, base_commit: &str) -> Result<String> {
+    static NEXT_INDEX: AtomicUsize = AtomicUsize::new(0);
+    let index = dir.join(format!(
+        "{DIFF_INDEX_FILE}.{}.{}",
+        std::process::id(),
+        NEXT_INDEX.fetch_add(1, Ordering::Relaxed)
+    ));
+    let git_with_index = |args: &[&str]| {
+        git_output(worktree, args, Some(&index))
+            .map_err(|e| CloudTaskError::Msg(format!("failed to diff attempt: {e}")))
+    };
+    let diff = git_with_index(&["read-tree", base_commit])
+        .and_then(|_| git_with_index(&["add", "-A"]))
+        .and_then(|_| git_with_index(&["diff", "--cached", "--binary", base_commit]));
+    let _ = fs::remove_file(&index);
+    diff
+}
+
+fn write_atomically(path: &Path, contents: &str) -> std::io::Result<()> {
+    let tmp = path.with_extension(format!("tmp.{}", std::process::id()));
+    fs::write(&tmp, contents)?;
+    fs::rename(&tmp, path)
+}
+
+fn record_pid(dir: &Path, pid: u32) -> Result<()> {
+    fs::write(dir.join(PID_FILE), pid.to_string())
+        .map_err(|e| io_error("failed to record job pid", e))
+}
+
+/// Whether the attempt's job has exited: it recorded an exit code, or the
+/// process that was running it no longer exists.
+fn job_ended(dir: &Path) -> bool {
+    if dir.join(EXIT_CODE_FILE).exists() {
+        return true;
+    }
+    // Before the job has a pid of its own, the claiming process stands in.
+    let pid = [PID_FILE, CLAIM_FILE].iter().find_map(|file| {
+        fs::read_to_string(dir.join(file))
+            .ok()
+            .and_then(|pid| pid.trim().parse::<u32>().ok())
+    });
+    pid.is_some_and(|pid| !pid_is_alive(pid))
+}
+
+#[cfg(unix)]
+fn pid_is_alive(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    if pid <= 0 {
+        return false;
+    }
+    let res = unsafe { libc::kill(pid, 0) };
+    res == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(not(unix))]
+fn pid_is_alive(_pid: u32) -> bool {
+    true
+}
+
+/// Drops an attempt's worktree once its diff is saved.
+fn remove_worktree(repo: &Path, worktree: &Path) {
+    let worktree = worktree.to_string_lossy();
+    if git_output(repo, &["worktree", "remove", "--force", &worktree], None).is_err() {
+        let _ = fs::remove_dir_all(worktree.as_ref());
+        let _ = git_output(repo, &["worktree", "prune"], None);
+    }
+}
+
+/// Outcome reported by the last terminal event of a `codex exec --json` log.
+fn status_from_events(events: &str) -> Option<AttemptStatus> {
+    events
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter_map(|event| match event.get("type").and_then(Value::as_str)? {
+            "turn.completed" => Some(AttemptStatus::Completed),
+            "turn.failed" | "error" | "budget.exceeded" => Some(AttemptStatus::Failed),
+            _ => None,
+        })
+        .last()
+}
+
+fn messages_from_events(events: &str) -> Vec<String> {
+    events
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter(|event| event.get("type").and_then(Value::as_str) == Some("item.completed"))
+        .filter_map(|event| {
+            let item = event.get("item")?;
+            if item.get("type").and_then(Value::as_str) != Some("agent_message") {
+                return None;
+            }
+            item.get("text").and_then(Value::as_str).map(str::to_string)
+        })
+        .collect()
+}
+
+fn attempt_messages(dir: &Path) -> Vec<String> {
+    let messages = fs::read_to_string(dir.join(EVENTS_FILE))
+        .map(|events| messages_from_events(&events))
+        .unwrap_or_default();
+    if !messages.is_empty() {
+        return messages;
+    }
+    fs::read_to_string(dir.join(LAST_MESSAGE_FILE))
+        .ok()
+        .and_then(non_empty)
+        .into_iter()
+        .collect()
+}
+
+fn task_status(applied: bool, attempts: &[AttemptStatus]) -> TaskStatus {
+    if applied {
+        TaskStatus::Applied
+    } else if attempts
+        .iter()
+        .any(|s| matches!(s, AttemptStatus::Pending | AttemptStatus::InProgress))
+    {
+        TaskStatus::Pending
+    } else if attempts.contains(&AttemptStatus::Completed) {
+        TaskStatus::Ready
+    } else {
+        TaskStatus::Error
+    }
+}
+
+fn diff_summary(diff: &str) -> DiffSummary {
+    let mut summary = DiffSummary::default();
+    for line in diff.lines() {
+        if line.starts_with("diff --git ") {
+            summary.files_changed += 1;
+        } else if line.starts_with("+++") || line.starts_with("---") {
+            continue;
+        } else if line.starts_with('+') {
+            summary.lines_added += 1;
+        } else if line.starts_with('-') {
+            summary.lines_removed += 1;
+        }
+    }
+    summary
+}
+
+fn non_empty(text: String) -> Option<String> {
+    (!text.trim().is_empty()).then_some(text)
+}
+
+fn io_error(context: &str, err: std::io::Error) -> CloudTaskError {
+    CloudTaskError::Io(format!("{context}: {err}"))
+}
+
+fn git_output(cwd: &Path, args: &[&str], index: Option<&Path>) -> std::io::Result<String> {
+    let mut cmd = Command::new("git");
+    cmd.args(args).current_dir(cwd);
+    if let Some(index) = index {
+        cmd.env("GIT_INDEX_FILE", index);
+    }
+    let output = cmd.output()?;
+    if !output.status.success() {
+        return Err(std::io::Error::other(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn git_line(cwd: &Path, args: &[&str]) -> Result<String> {
+    git_output(cwd, args, None)
+        .map(|out| out.trim().to_string())
+        .map_err(|e| CloudTaskError::Msg(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const EVENTS: &str = concat!(
+        "{\"type\":\"thread.started\",\"thread_id\":\"t\"}\n",
+        "{\"type\":\"turn.started\"}\n",
+        "{\"type\":\"item.completed\",\"item\":{\"id\":\"0\",\"type\":\"reasoning\",\"text\":\"hm\"}}\n",
+        "{\"type\":\"item.completed\",\"item\":{\"id\":\"1\",\"type\":\"agent_message\",\"text\":\"Done.\"}}\n",
+    );
+
+    #[test]
+    fn exec_events_drive_attempt_status_and_messages() {
+        assert_eq!(status_from_events(EVENTS), None);
+        assert_eq!(messages_from_events(EVENTS), vec!["Done.".to_string()]);
+
+        let completed = format!("{EVENTS}{{\"type\":\"turn.completed\",\"usage\":{{}}}}\n");
+        assert_eq!(
+            status_from_events(&completed),
+            Some(AttemptStatus::Completed)
+        );
+        let failed =
+            format!("{EVENTS}{{\"type\":\"turn.failed\",\"error\":{{\"message\":\"x\"}}}}\n");
+        assert_eq!(status_from_events(&failed), Some(AttemptStatus::Failed));
+    }
+
+    #[test]
+    fn task_status_waits_for_every_attempt() {
+        use AttemptStatus::*;
+        assert_eq!(
+            task_status(false, &[Completed, InProgress]),
+            TaskStatus::Pending
+        );
+        assert_eq!(task_status(false, &[Failed, Completed]), TaskStatus::Ready);
+        assert_eq!(task_status(false, &[Failed, Failed]), TaskStatus::Error);
+        assert_eq!(task_status(true, &[Completed]), TaskStatus::Applied);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn attempt_whose_job_is_gone_counts_as_failed() {
+        let dir = tempfile::tempdir().expect("attempt dir");
+        assert_eq!(attempt_status(dir.path()), AttemptStatus::Pending);
+
+        assert!(claim_attempt(dir.path()).expect("claim"));
+        assert_eq!(attempt_status(dir.path()), AttemptStatus::InProgress);
+
+        let mut exited = Command::new("true").spawn().expect("spawn true");
+        exited.wait().expect("wait for true");
+        record_pid(dir.path(), exited.id()).expect("record pid");
+        assert_eq!(attempt_status(dir.path()), AttemptStatus::Failed);
+        assert!(job_ended(dir.path()));
+    }
+}
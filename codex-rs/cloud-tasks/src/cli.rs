@@ -24,6 +24,8 @@ pub enum Command {
     Apply(ApplyCommand),
     /// Show the unified diff for a Codex Cloud task.
     Diff(DiffCommand),
+    /// Run queued local tasks (requires `CODEX_CLOUD_TASKS_MODE=local`).
+    Worker(WorkerCommand),
 }
 
 #[derive(Debug, Args)]
@@ -118,3 +120,14 @@ pub struct DiffCommand {
     #[arg(long = "attempt", value_parser = parse_attempts, value_name = "N")]
     pub attempt: Option<usize>,
 }
+
+#[derive(Debug, Args)]
+pub struct WorkerCommand {
+    /// Seconds to wait before checking the queue again when it is empty.
+    #[arg(long = "poll-secs", default_value_t = 5, value_name = "SECS")]
+    pub poll_secs: u64,
+
+    /// Exit once the queue is empty instead of waiting for new tasks.
+    #[arg(long = "once", default_value_t = false)]
+    pub once: bool,
+}
@@ -26,6 +26,15 @@ pub async fn autodetect_environment_id(
     headers: &HeaderMap,
     desired_label: Option<String>,
 ) -> anyhow::Result<AutodetectSelection> {
+    if crate::util::local_mode() {
+        let row = local_environment()
+            .ok_or_else(|| anyhow::anyhow!("local mode requires a git repository"))?;
+        return Ok(AutodetectSelection {
+            id: row.id,
+            label: row.label,
+        });
+    }
+
     // 1) Try repo-specific environments based on local git origins (GitHub only, like VSCode)
     let origins = get_git_origins();
     crate::append_error_log(format!("env: git origins: {origins:?}"));
@@ -167,6 +176,26 @@ async fn get_json<T: serde::de::DeserializeOwned>(
     Ok(parsed)
 }
 
+/// In local mode the only environment is the repository containing the
+/// working directory, identified by its root path.
+fn local_environment() -> Option<crate::app::EnvironmentRow> {
+    let out = std::process::Command::new("git")
+        .args(["rev-parse", "--show-toplevel"])
+        .output()
+        .ok()
+        .filter(|out| out.status.success())?;
+    let root = String::from_utf8_lossy(&out.stdout).trim().to_string();
+    let label = std::path::Path::new(&root)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned());
+    Some(crate::app::EnvironmentRow {
+        id: root.clone(),
+        label,
+        is_pinned: true,
+        repo_hints: Some(root),
+    })
+}
+
 fn get_git_origins() -> Vec<String> {
     // Prefer: git config --get-regexp remote\..*\.url
     let out = std::process::Command::new("git")
@@ -256,6 +285,10 @@ pub async fn list_environments(
     base_url: &str,
     headers: &HeaderMap,
 ) -> anyhow::Result<Vec<crate::app::EnvironmentRow>> {
+    if crate::util::local_mode() {
+        return Ok(local_environment().into_iter().collect());
+    }
+
     let mut map: HashMap<String, crate::app::EnvironmentRow> = HashMap::new();
 
     // 1) By-repo lookup for each parsed GitHub origin
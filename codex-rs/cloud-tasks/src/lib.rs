@@ -47,6 +47,14 @@ async fn init_backend(user_agent_suffix: &str) -> anyhow::Result<BackendContext>
 
     set_user_agent_suffix(user_agent_suffix);
 
+    if util::local_mode() {
+        let backend = local_backend()?;
+        return Ok(BackendContext {
+            base_url: format!("file://{}", backend.root().display()),
+            backend: Arc::new(backend),
+        });
+    }
+
     if use_mock {
         return Ok(BackendContext {
             backend: Arc::new(codex_cloud_tasks_client::MockClient),
@@ -107,6 +115,20 @@ async fn init_backend(user_agent_suffix: &str) -> anyhow::Result<BackendContext>
     })
 }
 
+/// Backend for `CODEX_CLOUD_TASKS_MODE=local`. Attempts run as `codex exec`
+/// jobs started by this binary, or only get queued for `codex cloud worker`
+/// when `CODEX_CLOUD_TASKS_LOCAL_QUEUE` is set.
+fn local_backend() -> anyhow::Result<codex_cloud_tasks_client::LocalClient> {
+    let root = util::local_tasks_root()?;
+    let queue_only = std::env::var("CODEX_CLOUD_TASKS_LOCAL_QUEUE")
+        .is_ok_and(|value| !value.is_empty() && value != "0");
+    let mut backend = codex_cloud_tasks_client::LocalClient::new(root).with_queue_only(queue_only);
+    if let Ok(exe) = std::env::current_exe() {
+        backend = backend.with_codex_exe(exe);
+    }
+    Ok(backend)
+}
+
 #[async_trait::async_trait]
 trait GitInfoProvider {
     async fn default_branch_name(&self, path: &std::path::Path) -> Option<String>;
@@ -728,6 +750,25 @@ fn spawn_apply(
 
 // (no standalone patch summarizer needed – UI displays raw diffs)
 
+async fn run_worker_command(args: crate::cli::WorkerCommand) -> anyhow::Result<()> {
+    if !util::local_mode() {
+        return Err(anyhow!(
+            "`codex cloud worker` runs local tasks; set CODEX_CLOUD_TASKS_MODE=local"
+        ));
+    }
+    let backend = local_backend()?;
+    let poll_interval = Duration::from_secs(args.poll_secs.max(1));
+    loop {
+        let worker = backend.clone();
+        let ran = tokio::task::spawn_blocking(move || worker.run_next_queued()).await??;
+        match ran {
+            Some(task_id) => println!("finished attempt of {}", task_id.0),
+            None if args.once => return Ok(()),
+            None => tokio::time::sleep(poll_interval).await,
+        }
+    }
+}
+
 /// Entry point for the `codex cloud` subcommand.
 pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()> {
     if let Some(command) = cli.command {
@@ -737,6 +778,7 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
             crate::cli::Command::List(args) => run_list_command(args).await,
             crate::cli::Command::Apply(args) => run_apply_command(args).await,
             crate::cli::Command::Diff(args) => run_diff_command(args).await,
+            crate::cli::Command::Worker(args) => run_worker_command(args).await,
         };
     }
     let Cli { .. } = cli;
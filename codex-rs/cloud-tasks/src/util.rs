@@ -3,6 +3,7 @@ use chrono::DateTime;
 use chrono::Local;
 use chrono::Utc;
 use reqwest::header::HeaderMap;
+use std::path::PathBuf;
 
 use codex_core::config::Config;
 use codex_login::AuthManager;
@@ -25,6 +26,26 @@ pub fn append_error_log(message: impl AsRef<str>) {
     }
 }
 
+/// True when `CODEX_CLOUD_TASKS_MODE=local` runs tasks on this machine
+/// instead of Codex Cloud.
+pub fn local_mode() -> bool {
+    matches!(
+        std::env::var("CODEX_CLOUD_TASKS_MODE").ok().as_deref(),
+        Some("local") | Some("LOCAL")
+    )
+}
+
+/// Directory holding local tasks: `CODEX_CLOUD_TASKS_LOCAL_DIR`, or
+/// `$CODEX_HOME/cloud-tasks`.
+pub fn local_tasks_root() -> anyhow::Result<PathBuf> {
+    if let Ok(dir) = std::env::var("CODEX_CLOUD_TASKS_LOCAL_DIR")
+        && !dir.is_empty()
+    {
+        return Ok(PathBuf::from(dir));
+    }
+    Ok(codex_core::config::find_codex_home()?.join("cloud-tasks"))
+}
+
 /// Normalize the configured base URL to a canonical form used by the backend client.
 /// - trims trailing '/'
 /// - appends '/backend-api' for ChatGPT hosts when missing
@@ -108,6 +129,9 @@ pub async fn build_chatgpt_headers() -> HeaderMap {
 /// Construct a browser-friendly task URL for the given backend base URL.
 pub fn task_url(base_url: &str, task_id: &str) -> String {
     let normalized = normalize_base_url(base_url);
+    if normalized.starts_with("file://") {
+        return format!("{normalized}/tasks/{task_id}");
+    }
     if let Some(root) = normalized.strip_suffix("/backend-api") {
         return format!("{root}/codex/tasks/{task_id}");
     }
@@ -0,0 +1,100 @@
+#![cfg(unix)]
+
+use codex_cloud_tasks_client::AttemptStatus;
+use codex_cloud_tasks_client::CloudBackend;
+use codex_cloud_tasks_client::LocalClient;
+use codex_cloud_tasks_client::TaskStatus;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::Command;
+
+fn git(cwd: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .current_dir(cwd)
+        .status()
+        .expect("run git");
+    assert!(status.success(), "git {args:?} failed");
+}
+
+/// Stand-in for `codex exec`: edits the worktree and prints exec events.
+const FAKE_CODEX: &str = r#"#!/bin/sh
+echo hello > greeting.txt
+echo '{"type":"turn.started"}'
+echo '{"type":"item.completed","item":{"id":"1","type":"agent_message","text":"Added a greeting."}}'
+echo '{"type":"turn.completed","usage":{"input_tokens":1,"cached_input_tokens":0,"output_tokens":1}}'
+"#;
+
+#[tokio::test]
+async fn queued_local_attempts_run_in_worktrees() {
+    let repo = tempfile::tempdir().expect("repo dir");
+    git(repo.path(), &["init", "-q"]);
+    std::fs::write(repo.path().join("README.md"), "readme\n").expect("write readme");
+    git(repo.path(), &["add", "."]);
+    git(repo.path(), &["commit", "-q", "-m", "init"]);
+
+    let home = tempfile::tempdir().expect("root dir");
+    let codex = home.path().join("fake-codex");
+    std::fs::write(&codex, FAKE_CODEX).expect("write script");
+    std::fs::set_permissions(&codex, std::fs::Permissions::from_mode(0o755)).expect("chmod");
+
+    let client = LocalClient::new(home.path().join("cloud-tasks"))
+        .with_codex_exe(&codex)
+        .with_queue_only(true);
+    let env = repo.path().to_string_lossy().to_string();
+    let created = client
+        .create_task(&env, "Add a greeting", "HEAD", false, 2)
+        .await
+        .expect("create task");
+
+    let summary = client.get_task_summary(created.id.clone()).await.unwrap();
+    assert_eq!(summary.status, TaskStatus::Pending);
+    assert_eq!(summary.attempt_total, Some(2));
+
+    assert_eq!(client.run_next_queued().unwrap(), Some(created.id.clone()));
+    assert_eq!(client.run_next_queued().unwrap(), Some(created.id.clone()));
+    assert_eq!(client.run_next_queued().unwrap(), None);
+
+    let page = client.list_tasks(Some(&env), None, None).await.unwrap();
+    assert_eq!(page.tasks.len(), 1);
+    assert_eq!(page.tasks[0].status, TaskStatus::Ready);
+    assert_eq!(page.tasks[0].summary.files_changed, 1);
+
+    let diff = client.get_task_diff(created.id.clone()).await.unwrap();
+    assert!(diff.is_some_and(|diff| diff.contains("+hello")));
+    assert_eq!(
+        client.get_task_messages(created.id.clone()).await.unwrap(),
+        vec!["Added a greeting.".to_string()]
+    );
+
+    let text = client.get_task_text(created.id.clone()).await.unwrap();
+    let turn_id = text.turn_id.expect("turn id");
+    let siblings = client
+        .list_sibling_attempts(created.id.clone(), turn_id)
+        .await
+        .unwrap();
+    assert_eq!(siblings.len(), 1);
+    assert_eq!(siblings[0].status, AttemptStatus::Completed);
+    assert_eq!(siblings[0].attempt_placement, Some(1));
+
+    // The original checkout is untouched until the diff is applied.
+    assert!(!repo.path().join("greeting.txt").exists());
+
+    // Finished attempts keep their diff but not their worktree.
+    let worktrees = Command::new("git")
+        .args(["worktree", "list", "--porcelain"])
+        .current_dir(repo.path())
+        .output()
+        .expect("list worktrees");
+    let worktrees = String::from_utf8_lossy(&worktrees.stdout);
+    assert_eq!(worktrees.matches("worktree ").count(), 1, "{worktrees}");
+
+    // Applying targets the task's repository, not the current directory.
+    let outcome = client.apply_task(created.id.clone(), None).await.unwrap();
+    assert!(outcome.applied, "{}", outcome.message);
+    assert_eq!(
+        std::fs::read_to_string(repo.path().join("greeting.txt")).unwrap(),
+        "hello\n"
+    );
+}
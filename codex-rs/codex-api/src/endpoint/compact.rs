@@ -3,6 +3,7 @@ use crate::common::CompactionInput;
 use crate::endpoint::session::EndpointSession;
 use crate::error::ApiError;
 use crate::provider::Provider;
+use crate::recording::StreamRecorder;
 use codex_client::HttpTransport;
 use codex_client::RequestTelemetry;
 use codex_protocol::models::ResponseItem;
@@ -14,21 +15,29 @@ use std::sync::Arc;
 
 pub struct CompactClient<T: HttpTransport, A: AuthProvider> {
     session: EndpointSession<T, A>,
+    recorder: Option<StreamRecorder>,
 }
 
 impl<T: HttpTransport, A: AuthProvider> CompactClient<T, A> {
     pub fn new(transport: T, provider: Provider, auth: A) -> Self {
         Self {
             session: EndpointSession::new(transport, provider, auth),
+            recorder: None,
         }
     }
 
     pub fn with_telemetry(self, request: Option<Arc<dyn RequestTelemetry>>) -> Self {
         Self {
             session: self.session.with_request_telemetry(request),
+            recorder: self.recorder,
         }
     }
 
+    /// Records every compaction response into `recorder`.
+    pub fn with_recorder(self, recorder: Option<StreamRecorder>) -> Self {
+        Self { recorder, ..self }
+    }
+
     fn path() -> &'static str {
         "responses/compact"
     }
@@ -38,12 +47,18 @@ impl<T: HttpTransport, A: AuthProvider> CompactClient<T, A> {
         body: serde_json::Value,
         extra_headers: HeaderMap,
     ) -> Result<Vec<ResponseItem>, ApiError> {
+        let recorded_body = self.recorder.as_ref().map(|_| body.clone());
         let resp = self
             .session
             .execute(Method::POST, Self::path(), extra_headers, Some(body))
             .await?;
         let parsed: CompactHistoryResponse =
             serde_json::from_slice(&resp.body).map_err(|e| ApiError::Stream(e.to_string()))?;
+        if let Some(recorder) = self.recorder.as_ref()
+            && let Some(body) = recorded_body
+        {
+            recorder.try_record_compaction(&body, &resp.body);
+        }
         Ok(parsed.output)
     }
 
@@ -59,8 +74,8 @@ impl<T: HttpTransport, A: AuthProvider> CompactClient<T, A> {
 }
 
 #[derive(Debug, Deserialize)]
-struct CompactHistoryResponse {
-    output: Vec<ResponseItem>,
+pub(crate) struct CompactHistoryResponse {
+    pub(crate) output: Vec<ResponseItem>,
 }
 
 #[cfg(test)]
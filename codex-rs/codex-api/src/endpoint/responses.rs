@@ -4,6 +4,7 @@ use crate::common::ResponsesApiRequest;
 use crate::endpoint::session::EndpointSession;
 use crate::error::ApiError;
 use crate::provider::Provider;
+use crate::recording::StreamRecorder;
 use crate::requests::headers::build_conversation_headers;
 use crate::requests::headers::insert_header;
 use crate::requests::headers::subagent_header;
@@ -25,6 +26,7 @@ use std::sync::OnceLock;
 pub struct ResponsesClient<T: HttpTransport, A: AuthProvider> {
     session: EndpointSession<T, A>,
     sse_telemetry: Option<Arc<dyn SseTelemetry>>,
+    recorder: Option<StreamRecorder>,
 }
 
 #[derive(Default)]
@@ -41,6 +43,7 @@ impl<T: HttpTransport, A: AuthProvider> ResponsesClient<T, A> {
         Self {
             session: EndpointSession::new(transport, provider, auth),
             sse_telemetry: None,
+            recorder: None,
         }
     }
 
@@ -52,9 +55,15 @@ impl<T: HttpTransport, A: AuthProvider> ResponsesClient<T, A> {
         Self {
             session: self.session.with_request_telemetry(request),
             sse_telemetry: sse,
+            recorder: self.recorder,
         }
     }
 
+    /// Records the raw response streams into `recorder`.
+    pub fn with_recorder(self, recorder: Option<StreamRecorder>) -> Self {
+        Self { recorder, ..self }
+    }
+
     pub async fn stream_request(
         &self,
         request: ResponsesApiRequest,
@@ -99,7 +108,10 @@ impl<T: HttpTransport, A: AuthProvider> ResponsesClient<T, A> {
             Compression::Zstd => RequestCompression::Zstd,
         };
 
-        let stream_response = self
+        // Only responses that were actually streamed are recorded, so a replay
+        // does not see requests that failed before streaming started.
+        let recorded_body = self.recorder.as_ref().map(|_| body.clone());
+        let mut stream_response = self
             .session
             .stream_with(
                 Method::POST,
@@ -115,6 +127,12 @@ impl<T: HttpTransport, A: AuthProvider> ResponsesClient<T, A> {
                 },
             )
             .await?;
+        if let Some(recorder) = self.recorder.as_ref()
+            && let Some(body) = recorded_body
+            && let Some(recording) = recorder.try_begin(&body)
+        {
+            stream_response.bytes = recording.tee(stream_response.bytes);
+        }
 
         Ok(spawn_response_stream(
             stream_response,
@@ -2,10 +2,12 @@ use crate::auth::AuthProvider;
 use crate::auth::add_auth_headers_to_header_map;
 use crate::common::ResponseEvent;
 use crate::common::ResponseStream;
+use crate::common::ResponsesApiRequest;
 use crate::common::ResponsesWsRequest;
 use crate::error::ApiError;
 use crate::provider::Provider;
 use crate::rate_limits::parse_rate_limit_event;
+use crate::recording::StreamRecorder;
 use crate::sse::responses::ResponsesStreamEvent;
 use crate::sse::responses::process_responses_event;
 use crate::telemetry::WebsocketTelemetry;
@@ -171,6 +173,7 @@ pub struct ResponsesWebsocketConnection {
     server_reasoning_included: bool,
     models_etag: Option<String>,
     telemetry: Option<Arc<dyn WebsocketTelemetry>>,
+    recorder: Option<StreamRecorder>,
 }
 
 impl ResponsesWebsocketConnection {
@@ -180,6 +183,7 @@ impl ResponsesWebsocketConnection {
         server_reasoning_included: bool,
         models_etag: Option<String>,
         telemetry: Option<Arc<dyn WebsocketTelemetry>>,
+        recorder: Option<StreamRecorder>,
     ) -> Self {
         Self {
             stream: Arc::new(Mutex::new(Some(stream))),
@@ -187,6 +191,7 @@ impl ResponsesWebsocketConnection {
             server_reasoning_included,
             models_etag,
             telemetry,
+            recorder,
         }
     }
 
@@ -194,9 +199,13 @@ impl ResponsesWebsocketConnection {
         self.stream.lock().await.is_none()
     }
 
+    /// Streams the response to `request`. `full_request` is the request that
+    /// `request` may only append to; it is what gets recorded, so that
+    /// recordings do not depend on the previous response.
     pub async fn stream_request(
         &self,
         request: ResponsesWsRequest,
+        full_request: &ResponsesApiRequest,
     ) -> Result<ResponseStream, ApiError> {
         let (tx_event, rx_event) =
             mpsc::channel::<std::result::Result<ResponseEvent, ApiError>>(1600);
@@ -208,6 +217,15 @@ impl ResponsesWebsocketConnection {
         let request_body = serde_json::to_value(&request).map_err(|err| {
             ApiError::Stream(format!("failed to encode websocket request: {err}"))
         })?;
+        let recording = match self.recorder.as_ref() {
+            Some(recorder) => {
+                let full_request = serde_json::to_value(full_request).map_err(|err| {
+                    ApiError::Stream(format!("failed to encode websocket request: {err}"))
+                })?;
+                Some((recorder.clone(), full_request))
+            }
+            None => None,
+        };
 
         tokio::spawn(async move {
             if let Some(etag) = models_etag {
@@ -234,6 +252,7 @@ impl ResponsesWebsocketConnection {
                 request_body,
                 idle_timeout,
                 telemetry,
+                recording,
             )
             .await
             {
@@ -250,11 +269,21 @@ impl ResponsesWebsocketConnection {
 pub struct ResponsesWebsocketClient<A: AuthProvider> {
     provider: Provider,
     auth: A,
+    recorder: Option<StreamRecorder>,
 }
 
 impl<A: AuthProvider> ResponsesWebsocketClient<A> {
     pub fn new(provider: Provider, auth: A) -> Self {
-        Self { provider, auth }
+        Self {
+            provider,
+            auth,
+            recorder: None,
+        }
+    }
+
+    /// Records the frames of every response streamed over the connection.
+    pub fn with_recorder(self, recorder: Option<StreamRecorder>) -> Self {
+        Self { recorder, ..self }
     }
 
     pub async fn connect(
@@ -281,6 +310,7 @@ impl<A: AuthProvider> ResponsesWebsocketClient<A> {
             server_reasoning_included,
             models_etag,
             telemetry,
+            self.recorder.clone(),
         ))
     }
 }
@@ -468,6 +498,7 @@ async fn run_websocket_response_stream(
     request_body: Value,
     idle_timeout: Duration,
     telemetry: Option<Arc<dyn WebsocketTelemetry>>,
+    recording: Option<(StreamRecorder, Value)>,
 ) -> Result<(), ApiError> {
     let request_text = match serde_json::to_string(&request_body) {
         Ok(text) => text,
@@ -490,12 +521,13 @@ async fn run_websocket_response_stream(
     }
 
     result?;
+    let recording = recording.and_then(|(recorder, request)| recorder.try_begin(&request));
 
     loop {
         let poll_start = Instant::now();
         let response = tokio::time::timeout(idle_timeout, ws_stream.next())
             .await
-            .map_err(|_| ApiError::Stream("idle timeout waiting for websocket".into()));
+            .map_err(|_| ApiError::IdleTimeout("idle timeout waiting for websocket".into()));
         if let Some(t) = telemetry.as_ref() {
             t.on_ws_event(&response, poll_start.elapsed());
         }
@@ -517,6 +549,9 @@ async fn run_websocket_response_stream(
         match message {
             Message::Text(text) => {
                 trace!("websocket event: {text}");
+                if let Some(recording) = recording.as_ref() {
+                    recording.record_frame(&text);
+                }
                 if let Some(wrapped_error) = parse_wrapped_websocket_error_event(&text)
                     && let Some(error) = map_wrapped_websocket_error_event(wrapped_error)
                 {
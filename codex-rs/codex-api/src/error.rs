@@ -12,6 +12,9 @@ pub enum ApiError {
     Api { status: StatusCode, message: String },
     #[error("stream error: {0}")]
     Stream(String),
+    /// The stream stayed silent for longer than the provider's idle timeout.
+    #[error("stream error: {0}")]
+    IdleTimeout(String),
     #[error("context window exceeded")]
     ContextWindowExceeded,
     #[error("quota exceeded")]
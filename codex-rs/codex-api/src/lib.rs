@@ -4,6 +4,7 @@ pub mod endpoint;
 pub mod error;
 pub mod provider;
 pub mod rate_limits;
+pub mod recording;
 pub mod requests;
 pub mod sse;
 pub mod telemetry;
@@ -36,6 +37,8 @@ pub use crate::endpoint::responses_websocket::ResponsesWebsocketConnection;
 pub use crate::error::ApiError;
 pub use crate::provider::Provider;
 pub use crate::provider::is_azure_responses_wire_base_url;
+pub use crate::recording::StreamRecorder;
+pub use crate::recording::StreamReplay;
 pub use crate::sse::stream_from_fixture;
 pub use crate::telemetry::SseTelemetry;
 pub use crate::telemetry::WebsocketTelemetry;
@@ -0,0 +1,495 @@
+//! Recording and replay of raw Responses streams.
+//!
+//! A recording is a directory with one pair of files per model request:
+//! `NNNN.request.json` holds the request body and `NNNN.sse` the response
+//! stream exactly as received. WebSocket frames are written as SSE `data:`
+//! lines, so both transports produce files in the format of the `.sse`
+//! fixtures used by the test suites and replay through the same parser.
+//! Remote compaction responses are stored as `NNNN.compact.json`.
+//!
+//! A replay serves each request the recorded response whose request was made
+//! at the same point of the conversation, so sessions that interleave
+//! requests differently (sub-agents, retries) still get the right responses.
+
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::endpoint::compact::CompactHistoryResponse;
+use crate::error::ApiError;
+use crate::sse::process_sse;
+use codex_client::ByteStream;
+use codex_client::TransportError;
+use codex_protocol::models::ResponseItem;
+use futures::TryStreamExt;
+use serde_json::Value;
+use std::fs::File;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_util::io::ReaderStream;
+use tracing::warn;
+
+const REQUEST_SUFFIX: &str = ".request.json";
+const STREAM_SUFFIX: &str = ".sse";
+const COMPACTION_SUFFIX: &str = ".compact.json";
+
+/// Writes every response stream of a session into a recording directory.
+///
+/// Several recorders may write into the same directory (a session and its
+/// sub-agents); each request claims the next free index.
+#[derive(Debug, Clone)]
+pub struct StreamRecorder {
+    dir: PathBuf,
+    next_index: Arc<AtomicUsize>,
+}
+
+impl StreamRecorder {
+    /// Records into `dir`, continuing after any streams already recorded there
+    /// (for example when a session is resumed).
+    pub fn new(dir: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        let next_index = recorded_indices(&dir)?.last().map_or(1, |last| last + 1);
+        Ok(Self {
+            dir,
+            next_index: Arc::new(AtomicUsize::new(next_index)),
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Starts recording the response to `request`.
+    pub fn begin(&self, request: &Value) -> std::io::Result<StreamRecording> {
+        let index = self.write_request(request)?;
+        let file = File::create(self.dir.join(format!("{index:04}{STREAM_SUFFIX}")))?;
+        Ok(StreamRecording {
+            file: Arc::new(Mutex::new(file)),
+        })
+    }
+
+    /// Like [`Self::begin`], but logs failures instead of returning them so
+    /// that recording never interrupts a turn.
+    pub fn try_begin(&self, request: &Value) -> Option<StreamRecording> {
+        match self.begin(request) {
+            Ok(recording) => Some(recording),
+            Err(err) => {
+                warn!(
+                    "failed to record model stream in {}: {err}",
+                    self.dir.display()
+                );
+                None
+            }
+        }
+    }
+
+    /// Records a remote compaction request and the response body it got.
+    pub fn record_compaction(&self, request: &Value, response: &[u8]) -> std::io::Result<()> {
+        let index = self.write_request(request)?;
+        std::fs::write(
+            self.dir.join(format!("{index:04}{COMPACTION_SUFFIX}")),
+            response,
+        )
+    }
+
+    /// Like [`Self::record_compaction`], but logs failures.
+    pub fn try_record_compaction(&self, request: &Value, response: &[u8]) {
+        if let Err(err) = self.record_compaction(request, response) {
+            warn!(
+                "failed to record compaction in {}: {err}",
+                self.dir.display()
+            );
+        }
+    }
+
+    /// Writes `request` under the next index no other recorder has taken.
+    fn write_request(&self, request: &Value) -> std::io::Result<usize> {
+        let request_json = serde_json::to_vec_pretty(request).map_err(std::io::Error::other)?;
+        loop {
+            let index = self.next_index.fetch_add(1, Ordering::Relaxed);
+            let path = self.dir.join(format!("{index:04}{REQUEST_SUFFIX}"));
+            match File::create_new(path) {
+                Ok(mut file) => {
+                    file.write_all(&request_json)?;
+                    return Ok(index);
+                }
+                Err(err) if err.kind() == ErrorKind::AlreadyExists => {}
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+/// The response stream of a single request being recorded.
+#[derive(Debug, Clone)]
+pub struct StreamRecording {
+    file: Arc<Mutex<File>>,
+}
+
+impl StreamRecording {
+    /// Appends raw SSE bytes.
+    pub fn record_bytes(&self, bytes: &[u8]) {
+        let mut file = self.file.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Err(err) = file.write_all(bytes) {
+            warn!("failed to record model stream: {err}");
+        }
+    }
+
+    /// Appends a WebSocket text frame as an SSE event.
+    pub fn record_frame(&self, text: &str) {
+        let data = text.replace('\n', "\ndata: ");
+        self.record_bytes(format!("data: {data}\n\n").as_bytes());
+    }
+
+    /// Wraps an SSE byte stream so that every chunk is recorded as it is read.
+    pub fn tee(self, stream: ByteStream) -> ByteStream {
+        Box::pin(stream.inspect_ok(move |bytes| self.record_bytes(bytes)))
+    }
+}
+
+/// Where in a conversation a request was made.
+///
+/// Thread ids, paths and tool output differ between a recorded session and
+/// its replay, so requests are matched on the last user message and the tool
+/// calls made since. Call ids come from the recorded responses, so they repeat
+/// exactly on replay.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RequestKey {
+    last_user_message: String,
+    calls: Vec<String>,
+}
+
+impl RequestKey {
+    fn from_input(input: &Value) -> Self {
+        let mut key = Self {
+            last_user_message: String::new(),
+            calls: Vec::new(),
+        };
+        for item in input.as_array().into_iter().flatten() {
+            let item_type = item.get("type").and_then(Value::as_str).unwrap_or_default();
+            if item_type == "message" && item.get("role").and_then(Value::as_str) == Some("user") {
+                key.last_user_message = message_text(item);
+                key.calls.clear();
+            } else if !item_type.ends_with("_output")
+                && let Some(call_id) = item.get("call_id").and_then(Value::as_str)
+            {
+                key.calls.push(call_id.to_string());
+            }
+        }
+        key
+    }
+
+    fn from_items(input: &[ResponseItem]) -> Result<Self, ApiError> {
+        let input = serde_json::to_value(input)
+            .map_err(|err| ApiError::Stream(format!("failed to encode replayed request: {err}")))?;
+        Ok(Self::from_input(&input))
+    }
+}
+
+fn message_text(message: &Value) -> String {
+    message
+        .get("content")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|content| content.get("text").and_then(Value::as_str))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResponseKind {
+    Stream,
+    Compaction,
+}
+
+#[derive(Debug)]
+struct RecordedExchange {
+    key: RequestKey,
+    kind: ResponseKind,
+    response: PathBuf,
+}
+
+/// Serves the responses of a recording directory to the requests they answer.
+#[derive(Debug)]
+pub struct StreamReplay {
+    dir: PathBuf,
+    exchanges: Vec<RecordedExchange>,
+    served: Mutex<Vec<bool>>,
+}
+
+impl StreamReplay {
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, ApiError> {
+        let dir = dir.into();
+        let read_error = |err: std::io::Error| {
+            ApiError::Stream(format!("failed to read recording {}: {err}", dir.display()))
+        };
+        let mut exchanges = Vec::new();
+        for index in recorded_indices(&dir).map_err(read_error)? {
+            let (kind, response) = match (
+                dir.join(format!("{index:04}{STREAM_SUFFIX}")),
+                dir.join(format!("{index:04}{COMPACTION_SUFFIX}")),
+            ) {
+                (stream, _) if stream.exists() => (ResponseKind::Stream, stream),
+                (_, compaction) if compaction.exists() => (ResponseKind::Compaction, compaction),
+                // The session ended before the response arrived.
+                _ => continue,
+            };
+            let request_path = dir.join(format!("{index:04}{REQUEST_SUFFIX}"));
+            let request: Value =
+                std::fs::read(&request_path)
+                    .map_err(read_error)
+                    .and_then(|bytes| {
+                        serde_json::from_slice(&bytes).map_err(|err| {
+                            ApiError::Stream(format!("invalid {}: {err}", request_path.display()))
+                        })
+                    })?;
+            exchanges.push(RecordedExchange {
+                key: RequestKey::from_input(&request["input"]),
+                kind,
+                response,
+            });
+        }
+        if exchanges.is_empty() {
+            return Err(ApiError::Stream(format!(
+                "no recorded responses found in {}",
+                dir.display()
+            )));
+        }
+        let served = Mutex::new(vec![false; exchanges.len()]);
+        Ok(Self {
+            dir,
+            exchanges,
+            served,
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Number of recorded responses.
+    pub fn len(&self) -> usize {
+        self.exchanges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.exchanges.is_empty()
+    }
+
+    /// Streams the recorded response to a Responses request with `input`.
+    pub fn next_stream(
+        &self,
+        input: &[ResponseItem],
+        idle_timeout: Duration,
+    ) -> Result<ResponseStream, ApiError> {
+        let path = self.claim(ResponseKind::Stream, input)?;
+        let content = std::fs::read(path)
+            .map_err(|err| ApiError::Stream(format!("failed to read {}: {err}", path.display())))?;
+
+        let reader = std::io::Cursor::new(content);
+        let stream =
+            ReaderStream::new(reader).map_err(|err| TransportError::Network(err.to_string()));
+        let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+        tokio::spawn(process_sse(Box::pin(stream), tx_event, idle_timeout, None));
+        Ok(ResponseStream { rx_event })
+    }
+
+    /// Returns the recorded compacted history for a compaction request with `input`.
+    pub fn compaction(&self, input: &[ResponseItem]) -> Result<Vec<ResponseItem>, ApiError> {
+        let path = self.claim(ResponseKind::Compaction, input)?;
+        let content = std::fs::read(path)
+            .map_err(|err| ApiError::Stream(format!("failed to read {}: {err}", path.display())))?;
+        let parsed: CompactHistoryResponse = serde_json::from_slice(&content)
+            .map_err(|err| ApiError::Stream(format!("invalid {}: {err}", path.display())))?;
+        Ok(parsed.output)
+    }
+
+    /// Takes the first response not served yet whose request matches `input`.
+    fn claim(&self, kind: ResponseKind, input: &[ResponseItem]) -> Result<&Path, ApiError> {
+        let key = RequestKey::from_items(input)?;
+        let mut served = self.served.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some(index) = self
+            .exchanges
+            .iter()
+            .zip(served.iter())
+            .position(|(exchange, served)| !served && exchange.kind == kind && exchange.key == key)
+        else {
+            // Not retryable: asking again cannot produce a recorded response.
+            return Err(ApiError::InvalidRequest {
+                message: format!(
+                    "recording {} has no response for the request after {:?} with {} tool call(s)",
+                    self.dir.display(),
+                    key.last_user_message,
+                    key.calls.len()
+                ),
+            });
+        };
+        served[index] = true;
+        Ok(&self.exchanges[index].response)
+    }
+}
+
+/// Indices of the requests recorded in `dir`, in request order.
+fn recorded_indices(dir: &Path) -> std::io::Result<Vec<usize>> {
+    let mut indices = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let name = entry?.file_name();
+        let index = name
+            .to_str()
+            .and_then(|name| name.strip_suffix(REQUEST_SUFFIX))
+            .filter(|stem| !stem.is_empty() && stem.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|stem| stem.parse().ok());
+        if let Some(index) = index {
+            indices.push(index);
+        }
+    }
+    indices.sort_unstable();
+    Ok(indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use codex_protocol::models::ContentItem;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    async fn collect_events(mut stream: ResponseStream) -> Vec<String> {
+        let mut events = Vec::new();
+        while let Some(event) = stream.rx_event.recv().await {
+            match event {
+                Ok(ResponseEvent::OutputItemDone(item)) => {
+                    events.push(format!("item:{}", serde_json::json!(item)["type"]));
+                }
+                Ok(ResponseEvent::Completed { response_id, .. }) => {
+                    events.push(format!("completed:{response_id}"));
+                }
+                Ok(_) => {}
+                Err(err) => events.push(format!("error:{err}")),
+            }
+        }
+        events
+    }
+
+    fn user_message(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+            end_turn: None,
+            phase: None,
+        }
+    }
+
+    fn function_call(call_id: &str) -> Vec<ResponseItem> {
+        vec![
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: "{}".to_string(),
+                call_id: call_id.to_string(),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: call_id.to_string(),
+                output: FunctionCallOutputPayload::from_text("output differs on replay".into()),
+            },
+        ]
+    }
+
+    fn request(input: &[ResponseItem]) -> Value {
+        json!({ "model": "gpt-test", "input": input })
+    }
+
+    #[tokio::test]
+    async fn recorded_responses_replay_by_request() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let recorder = StreamRecorder::new(dir.path())?;
+
+        let first = vec![user_message("first")];
+        let sse = concat!(
+            "event: response.output_item.done\n",
+            "data: {\"type\":\"response.output_item.done\",\"item\":{\"type\":\"message\",",
+            "\"role\":\"assistant\",\"content\":[{\"type\":\"output_text\",\"text\":\"hi\"}]}}\n\n",
+            "event: response.completed\n",
+            "data: {\"type\":\"response.completed\",\"response\":{\"id\":\"resp-1\"}}\n\n",
+        );
+        let (head, tail) = sse.split_at(40);
+        let chunks: ByteStream = Box::pin(futures::stream::iter(vec![
+            Ok(Bytes::from(head.to_string())),
+            Ok(Bytes::from(tail.to_string())),
+        ]));
+        let recording = recorder.begin(&request(&first))?;
+        let passed_through: Vec<Bytes> = recording.tee(chunks).try_collect().await?;
+        assert_eq!(passed_through.concat(), sse.as_bytes());
+
+        // A sub-agent recording into the same directory takes the next index.
+        let sub_agent = StreamRecorder::new(dir.path())?;
+        let sub_agent_input = vec![user_message("sub-agent task")];
+        let recording = sub_agent.begin(&request(&sub_agent_input))?;
+        recording.record_frame(r#"{"type":"response.completed","response":{"id":"resp-sub"}}"#);
+
+        let mut second = first.clone();
+        second.extend(function_call("call-1"));
+        let recording = recorder.begin(&request(&second))?;
+        recording.record_frame(r#"{"type":"response.completed","response":{"id":"resp-2"}}"#);
+
+        let compacted = vec![user_message("summary")];
+        recorder.record_compaction(
+            &request(&second),
+            serde_json::to_vec(&json!({ "output": compacted }))?.as_slice(),
+        )?;
+
+        let stored: Value =
+            serde_json::from_slice(&std::fs::read(dir.path().join("0002.request.json"))?)?;
+        assert_eq!(stored, request(&sub_agent_input));
+
+        let replay = StreamReplay::open(dir.path())?;
+        assert_eq!(replay.len(), 4);
+        let idle_timeout = Duration::from_secs(5);
+
+        // Requests are served by what they ask for, not by position.
+        let mut replayed_second = vec![user_message("first")];
+        replayed_second.extend(function_call("call-1"));
+        assert_eq!(
+            collect_events(replay.next_stream(&replayed_second, idle_timeout)?).await,
+            vec!["completed:resp-2".to_string()]
+        );
+        assert_eq!(replay.compaction(&replayed_second)?, compacted);
+        assert_eq!(
+            collect_events(replay.next_stream(&first, idle_timeout)?).await,
+            vec![
+                "item:\"message\"".to_string(),
+                "completed:resp-1".to_string()
+            ]
+        );
+        assert_eq!(
+            collect_events(replay.next_stream(&sub_agent_input, idle_timeout)?).await,
+            vec!["completed:resp-sub".to_string()]
+        );
+        assert!(replay.next_stream(&first, idle_timeout).is_err());
+        assert!(
+            replay
+                .next_stream(&[user_message("unrecorded")], idle_timeout)
+                .is_err()
+        );
+
+        // Resuming a recording appends after the existing requests.
+        let recorder = StreamRecorder::new(dir.path())?;
+        recorder.begin(&request(&first))?;
+        assert!(dir.path().join("0005.sse").exists());
+        Ok(())
+    }
+}
@@ -362,7 +362,9 @@ pub async fn process_sse(
             }
             Err(_) => {
                 let _ = tx_event
-                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .send(Err(ApiError::IdleTimeout(
+                        "idle timeout waiting for SSE".into(),
+                    )))
                     .await;
                 return;
             }
@@ -23,6 +23,7 @@ pub(crate) fn map_api_error(err: ApiError) -> CodexErr {
         ApiError::UsageNotIncluded => CodexErr::UsageNotIncluded,
         ApiError::Retryable { message, delay } => CodexErr::Stream(message, delay),
         ApiError::Stream(msg) => CodexErr::Stream(msg, None),
+        ApiError::IdleTimeout(msg) => CodexErr::StreamIdleTimeout(msg),
         ApiError::ServerOverloaded => CodexErr::ServerOverloaded,
         ApiError::Api { status, message } => CodexErr::UnexpectedStatus(UnexpectedResponseError {
             status,
@@ -131,6 +132,15 @@ mod tests {
         assert!(matches!(err, CodexErr::ServerOverloaded));
     }
 
+    #[test]
+    fn map_api_error_keeps_idle_timeouts_apart_from_other_stream_errors() {
+        let err = map_api_error(ApiError::IdleTimeout(
+            "idle timeout waiting for SSE".to_string(),
+        ));
+        assert!(matches!(err, CodexErr::StreamIdleTimeout(_)));
+        assert!(err.is_retryable());
+    }
+
     #[test]
     fn map_api_error_maps_server_overloaded_from_503_body() {
         let body = serde_json::json!({
@@ -6,6 +6,7 @@ use crate::safety::assess_patch_safety;
 use crate::tools::sandboxing::ExecApprovalRequirement;
 use codex_apply_patch::ApplyPatchAction;
 use codex_apply_patch::ApplyPatchFileChange;
+use codex_protocol::approvals::DecisionProvenance;
 use std::collections::HashMap;
 use std::path::PathBuf;
 
@@ -53,6 +54,9 @@ pub(crate) async fn apply_patch(
             exec_approval_requirement: ExecApprovalRequirement::Skip {
                 bypass_sandbox: false,
                 proposed_execpolicy_amendment: None,
+                provenance: DecisionProvenance::ApprovalPolicy {
+                    approval_policy: turn_context.approval_policy,
+                },
             },
         }),
         SafetyCheck::AskUser => {
@@ -65,6 +69,9 @@ pub(crate) async fn apply_patch(
                 exec_approval_requirement: ExecApprovalRequirement::NeedsApproval {
                     reason: None,
                     proposed_execpolicy_amendment: None,
+                    provenance: DecisionProvenance::ApprovalPolicy {
+                        approval_policy: turn_context.approval_policy,
+                    },
                 },
             })
         }
@@ -51,6 +51,8 @@ use codex_api::ResponsesOptions as ApiResponsesOptions;
 use codex_api::ResponsesWebsocketClient as ApiWebSocketResponsesClient;
 use codex_api::ResponsesWebsocketConnection as ApiWebSocketConnection;
 use codex_api::SseTelemetry;
+use codex_api::StreamRecorder;
+use codex_api::StreamReplay;
 use codex_api::TransportError;
 use codex_api::WebsocketTelemetry;
 use codex_api::build_conversation_headers;
@@ -152,6 +154,18 @@ struct CurrentClientSetup {
 #[derive(Debug, Clone)]
 pub struct ModelClient {
     state: Arc<ModelClientState>,
+    stream_capture: Option<StreamCapture>,
+}
+
+/// Where a session's raw model response streams are recorded to or replayed
+/// from (see `codex_api::recording`).
+#[derive(Debug, Clone)]
+pub(crate) enum StreamCapture {
+    Record(StreamRecorder),
+    /// Serves recorded responses instead of calling the provider. Replays
+    /// always use the HTTP path, since the recording already holds complete
+    /// streams.
+    Replay(Arc<StreamReplay>),
 }
 
 /// A turn-scoped streaming session created from a [`ModelClient`].
@@ -230,6 +244,30 @@ impl ModelClient {
                 disable_websockets: AtomicBool::new(false),
                 prepared_context_snapshot: RwLock::new(None),
             }),
+            stream_capture: None,
+        }
+    }
+
+    /// Returns a client that records its response streams or replays them
+    /// from a recording.
+    pub(crate) fn with_stream_capture(self, stream_capture: Option<StreamCapture>) -> Self {
+        Self {
+            stream_capture,
+            ..self
+        }
+    }
+
+    fn stream_recorder(&self) -> Option<StreamRecorder> {
+        match self.stream_capture.as_ref() {
+            Some(StreamCapture::Record(recorder)) => Some(recorder.clone()),
+            Some(StreamCapture::Replay(_)) | None => None,
+        }
+    }
+
+    fn stream_replay(&self) -> Option<&StreamReplay> {
+        match self.stream_capture.as_ref() {
+            Some(StreamCapture::Replay(replay)) => Some(replay),
+            Some(StreamCapture::Record(_)) | None => None,
         }
     }
 
@@ -247,6 +285,26 @@ impl ModelClient {
         }
     }
 
+    /// Returns a client that talks to `provider` but otherwise shares this client's session
+    /// settings. Used when a failover chain moves the session to another provider; transport
+    /// fallback and prepared-context state start fresh because they are provider specific.
+    pub(crate) fn with_provider(&self, provider: ModelProviderInfo) -> Self {
+        let state = &self.state;
+        Self::new(
+            state.auth_manager.clone(),
+            state.conversation_id,
+            provider,
+            state.session_source.clone(),
+            state.model_verbosity,
+            state.enable_responses_websockets,
+            state.enable_responses_websockets_v2,
+            state.enable_request_compression,
+            state.include_timing_metrics,
+            state.beta_features_header.clone(),
+        )
+        .with_stream_capture(self.stream_capture.clone())
+    }
+
     pub(crate) async fn set_prepared_context_snapshot(&self, snapshot_json: String) {
         let mut guard = self.state.prepared_context_snapshot.write().await;
         *guard = Some(snapshot_json);
@@ -259,7 +317,8 @@ impl ModelClient {
     /// Compacts the current conversation history using the Compact endpoint.
     ///
     /// This is a unary call (no streaming) that returns a new list of
-    /// `ResponseItem`s representing the compacted transcript.
+    /// `ResponseItem`s representing the compacted transcript. Replayed sessions
+    /// get the recorded transcript instead.
     ///
     /// The model selection and telemetry context are passed explicitly to keep `ModelClient`
     /// session-scoped.
@@ -272,12 +331,16 @@ impl ModelClient {
         if prompt.input.is_empty() {
             return Ok(Vec::new());
         }
+        if let Some(replay) = self.stream_replay() {
+            return replay.compaction(&prompt.input).map_err(map_api_error);
+        }
         let client_setup = self.current_client_setup().await?;
         let transport = ReqwestTransport::new(build_reqwest_client());
         let request_telemetry = Self::build_request_telemetry(otel_manager);
         let client =
             ApiCompactClient::new(transport, client_setup.api_provider, client_setup.api_auth)
-                .with_telemetry(Some(request_telemetry));
+                .with_telemetry(Some(request_telemetry))
+                .with_recorder(self.stream_recorder());
 
         let instructions = prompt.base_instructions.text.clone();
         let payload = ApiCompactionInput {
@@ -370,9 +433,10 @@ impl ModelClient {
 
     /// Returns whether websocket transport has been permanently disabled for this session.
     ///
-    /// Once set by fallback activation, subsequent turns must stay on HTTP transport.
+    /// Once set by fallback activation, subsequent turns must stay on HTTP transport. Sessions
+    /// replaying a recording never use websockets.
     fn websockets_disabled(&self) -> bool {
-        self.state.disable_websockets.load(Ordering::Relaxed)
+        self.state.disable_websockets.load(Ordering::Relaxed) || self.stream_replay().is_some()
     }
 
     /// Returns auth + provider configuration resolved from the current session auth state.
@@ -411,6 +475,7 @@ impl ModelClient {
         let headers = self.build_websocket_headers(turn_state.as_ref(), turn_metadata_header);
         let websocket_telemetry = ModelClientSession::build_websocket_telemetry(otel_manager);
         ApiWebSocketResponsesClient::new(api_provider, api_auth)
+            .with_recorder(self.stream_recorder())
             .connect(
                 headers,
                 crate::default_client::default_headers(),
@@ -725,7 +790,7 @@ impl ModelClientSession {
 
     /// Streams a turn via the OpenAI Responses API.
     ///
-    /// Handles SSE fixtures, reasoning summaries, verbosity, and the
+    /// Handles SSE fixtures, recorded stream replays, reasoning summaries, verbosity, and the
     /// `text` controls used for output schemas.
     #[allow(clippy::too_many_arguments)]
     async fn stream_responses_api(
@@ -747,6 +812,16 @@ impl ModelClientSession {
             let (stream, _last_request_rx) = map_response_stream(stream, otel_manager.clone());
             return Ok(stream);
         }
+        if let Some(replay) = self.client.stream_replay() {
+            let stream = replay
+                .next_stream(
+                    &prompt.get_formatted_input(),
+                    self.client.state.provider.stream_idle_timeout(),
+                )
+                .map_err(map_api_error)?;
+            let (stream, _last_request_rx) = map_response_stream(stream, otel_manager.clone());
+            return Ok(stream);
+        }
 
         let auth_manager = self.client.state.auth_manager.clone();
         let mut auth_recovery = auth_manager
@@ -775,7 +850,8 @@ impl ModelClientSession {
                 client_setup.api_provider,
                 client_setup.api_auth,
             )
-            .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
+            .with_telemetry(Some(request_telemetry), Some(sse_telemetry))
+            .with_recorder(self.client.stream_recorder());
             let stream_result = client.stream_request(request, options).await;
 
             match stream_result {
@@ -863,7 +939,7 @@ impl ModelClientSession {
                         "websocket connection is unavailable".to_string(),
                     ))
                 })?
-                .stream_request(ws_request)
+                .stream_request(ws_request, &request)
                 .await
                 .map_err(map_api_error)?;
             self.websocket_last_request = Some(request);
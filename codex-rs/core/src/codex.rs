@@ -5,6 +5,7 @@ use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicU64;
+use std::time::Instant;
 
 use crate::AuthManager;
 use crate::CodexAuth;
@@ -36,12 +37,16 @@ use crate::stream_events_utils::handle_non_tool_response_item;
 use crate::stream_events_utils::handle_output_item_done;
 use crate::stream_events_utils::last_assistant_message_from_item;
 use crate::terminal;
+use crate::tokenizer::Tokenizer;
+use crate::tokenizer::tokenizer_for_model;
 use crate::truncate::TruncationPolicy;
 use crate::turn_metadata::build_turn_metadata_header;
 use crate::turn_metadata::resolve_turn_metadata_header_with_timeout;
 use crate::util::error_or_panic;
 use async_channel::Receiver;
 use async_channel::Sender;
+use codex_api::StreamRecorder;
+use codex_api::StreamReplay;
 use codex_hooks::HookEvent;
 use codex_hooks::HookEventAfterAgent;
 use codex_hooks::HookPayload;
@@ -49,6 +54,7 @@ use codex_hooks::Hooks;
 use codex_hooks::HooksConfig;
 use codex_network_proxy::NetworkProxy;
 use codex_protocol::ThreadId;
+use codex_protocol::approvals::DecisionProvenance;
 use codex_protocol::approvals::ExecPolicyAmendment;
 use codex_protocol::config_types::ModeKind;
 use codex_protocol::config_types::Settings;
@@ -95,6 +101,7 @@ use tokio::sync::RwLock;
 use tokio::sync::oneshot;
 use tokio::sync::watch;
 use tokio_util::sync::CancellationToken;
+use tokio_util::task::AbortOnDropHandle;
 use tracing::Instrument;
 use tracing::debug;
 use tracing::error;
@@ -110,6 +117,7 @@ use uuid::Uuid;
 use crate::ModelProviderInfo;
 use crate::client::ModelClient;
 use crate::client::ModelClientSession;
+use crate::client::StreamCapture;
 use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
 use crate::codex_thread::ThreadConfigSnapshot;
@@ -120,7 +128,11 @@ use crate::config::ConstraintResult;
 use crate::config::GhostSnapshotConfig;
 use crate::config::StartedNetworkProxy;
 use crate::config::resolve_web_search_mode_for_turn;
+use crate::config::types::FailoverTarget;
 use crate::config::types::McpServerConfig;
+use crate::config::types::ModelRoute;
+use crate::config::types::ModelRouteTask;
+use crate::config::types::RememberedApprovalsScope;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::context_manager::ContextManager;
 use crate::context_manager::TotalTokenUsageBreakdown;
@@ -137,6 +149,8 @@ pub enum SteerInputError {
     EmptyInput,
 }
 use crate::exec_policy::ExecPolicyUpdateError;
+use crate::external_edits::AgentActivity;
+use crate::external_edits::ExternalEdits;
 use crate::feedback_tags;
 use crate::file_watcher::FileWatcher;
 use crate::file_watcher::FileWatcherEvent;
@@ -155,6 +169,10 @@ use crate::mentions::build_connector_slug_counts;
 use crate::mentions::build_skill_name_counts;
 use crate::mentions::collect_explicit_app_ids;
 use crate::mentions::collect_tool_mentions_from_messages;
+use crate::model_routing::RouteRequest;
+use crate::model_routing::drop_turn_reasoning;
+use crate::model_routing::last_response_tool;
+use crate::model_routing::select_route;
 use crate::project_doc::get_user_instructions;
 use crate::proposed_plan_parser::ProposedPlanParser;
 use crate::proposed_plan_parser::ProposedPlanSegment;
@@ -169,14 +187,17 @@ use crate::protocol::ErrorEvent;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecApprovalRequestEvent;
+use crate::protocol::FailoverTrigger;
 use crate::protocol::McpServerRefreshConfig;
 use crate::protocol::Op;
 use crate::protocol::PlanDeltaEvent;
+use crate::protocol::ProviderFailoverEvent;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::ReasoningContentDeltaEvent;
 use crate::protocol::ReasoningRawContentDeltaEvent;
 use crate::protocol::RequestUserInputEvent;
 use crate::protocol::ReviewDecision;
+use crate::protocol::ReviewFinding;
 use crate::protocol::SandboxPolicy;
 use crate::protocol::SessionConfiguredEvent;
 use crate::protocol::SessionNetworkProxyRuntime;
@@ -192,10 +213,18 @@ use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
 use crate::protocol::TurnDiffEvent;
 use crate::protocol::WarningEvent;
+use crate::provider_failover::FailoverState;
+use crate::provider_failover::failover_trigger;
+use crate::remembered_approvals::ApprovalSubject;
+use crate::remembered_approvals::RememberedDecision;
+use crate::remembered_approvals::RepositoryApprovals;
+use crate::remembered_approvals::repository_key;
+use crate::review_prompts::dismissed_findings_from_items;
 use crate::rollout::RolloutRecorder;
 use crate::rollout::RolloutRecorderParams;
 use crate::rollout::map_session_init_error;
 use crate::rollout::metadata;
+use crate::session_budget;
 use crate::shell;
 use crate::shell_snapshot::ShellSnapshot;
 use crate::skills::SkillError;
@@ -211,6 +240,7 @@ use crate::skills::injection::app_id_from_path;
 use crate::skills::injection::tool_kind_for_path;
 use crate::skills::resolve_skill_dependencies_for_turn;
 use crate::state::ActiveTurn;
+use crate::state::RedoSnapshot;
 use crate::state::SessionServices;
 use crate::state::SessionState;
 use crate::state_db;
@@ -219,10 +249,12 @@ use crate::tasks::RegularTask;
 use crate::tasks::ReviewTask;
 use crate::tasks::SessionTask;
 use crate::tasks::SessionTaskContext;
+use crate::token_budget;
 use crate::tools::ToolRouter;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::js_repl::JsReplHandle;
 use crate::tools::parallel::ToolCallRuntime;
+use crate::tools::py_repl::PyReplHandle;
 use crate::tools::sandboxing::ApprovalStore;
 use crate::tools::spec::ToolsConfig;
 use crate::tools::spec::ToolsConfigParams;
@@ -270,6 +302,8 @@ pub struct CodexSpawnOk {
 
 pub(crate) const INITIAL_SUBMIT_ID: &str = "";
 pub(crate) const SUBMISSION_CHANNEL_CAPACITY: usize = 64;
+/// Directory under `CODEX_HOME` holding recorded model response streams.
+pub(crate) const MODEL_STREAM_RECORDINGS_SUBDIR: &str = "recordings";
 
 impl Codex {
     /// Spawn a new [`Codex`] and initialize the session.
@@ -280,6 +314,7 @@ impl Codex {
         models_manager: Arc<ModelsManager>,
         skills_manager: Arc<SkillsManager>,
         file_watcher: Arc<FileWatcher>,
+        agent_activity: Arc<AgentActivity>,
         conversation_history: InitialHistory,
         session_source: SessionSource,
         agent_control: AgentControl,
@@ -415,6 +450,7 @@ impl Codex {
             session_source_clone,
             skills_manager,
             file_watcher,
+            agent_activity,
             agent_control,
         )
         .instrument(session_init_span)
@@ -513,6 +549,7 @@ pub(crate) struct Session {
     pub(crate) active_turn: Mutex<Option<ActiveTurn>>,
     pub(crate) services: SessionServices,
     js_repl: Arc<JsReplHandle>,
+    py_repl: Arc<PyReplHandle>,
     next_internal_sub_id: AtomicU64,
 }
 
@@ -552,8 +589,12 @@ pub(crate) struct TurnContext {
     pub(crate) codex_linux_sandbox_exe: Option<PathBuf>,
     pub(crate) tool_call_gate: Arc<ReadinessFlag>,
     pub(crate) truncation_policy: TruncationPolicy,
+    pub(crate) tokenizer: &'static dyn Tokenizer,
     pub(crate) js_repl: Arc<JsReplHandle>,
+    pub(crate) py_repl: Arc<PyReplHandle>,
     pub(crate) dynamic_tools: Vec<DynamicToolSpec>,
+    /// `[[model_routes]]` rule that selected this context's model, if any.
+    pub(crate) model_route: Option<String>,
     turn_metadata_header: OnceCell<Option<String>>,
 }
 impl TurnContext {
@@ -600,7 +641,8 @@ impl TurnContext {
             features: &features,
             web_search_mode: self.tools_config.web_search_mode,
             plugins: Some(&config.plugins),
-        });
+        })
+        .with_config_dynamic_tools(&config.dynamic_tools);
 
         Self {
             sub_id: self.sub_id.clone(),
@@ -633,12 +675,65 @@ impl TurnContext {
             codex_linux_sandbox_exe: self.codex_linux_sandbox_exe.clone(),
             tool_call_gate: Arc::new(ReadinessFlag::new()),
             truncation_policy,
+            tokenizer: tokenizer_for_model(&model_info.slug),
             js_repl: Arc::clone(&self.js_repl),
+            py_repl: Arc::clone(&self.py_repl),
             dynamic_tools: self.dynamic_tools.clone(),
+            model_route: self.model_route.clone(),
             turn_metadata_header: self.turn_metadata_header.clone(),
         }
     }
 
+    /// Derive a context that samples from a failover chain entry instead of the
+    /// session's own provider. Entries without a model keep the current one.
+    pub(crate) async fn with_failover_target(
+        &self,
+        target: &FailoverTarget,
+        models_manager: &ModelsManager,
+    ) -> Self {
+        let model = target
+            .model
+            .clone()
+            .unwrap_or_else(|| self.collaboration_mode.model().to_string());
+        let mut turn_context = self.with_model(model, models_manager).await;
+        let mut config = (*turn_context.config).clone();
+        config.model_provider_id = target.provider_id.clone();
+        config.model_provider = target.provider.clone();
+        turn_context.config = Arc::new(config);
+        turn_context.provider = target.provider.clone();
+        turn_context
+    }
+
+    /// Derive a context that uses the model and reasoning effort chosen by a
+    /// `[[model_routes]]` rule; `label` is recorded with the turn context.
+    pub(crate) async fn with_model_route(
+        &self,
+        label: String,
+        route: &ModelRoute,
+        models_manager: &ModelsManager,
+    ) -> Self {
+        let model = route
+            .model
+            .clone()
+            .unwrap_or_else(|| self.collaboration_mode.model().to_string());
+        let mut turn_context = self.with_model(model, models_manager).await;
+        // Routed requests belong to the same turn, so tool calls must wait on
+        // the same ghost snapshot readiness.
+        turn_context.tool_call_gate = Arc::clone(&self.tool_call_gate);
+        if let Some(effort) = route.model_reasoning_effort {
+            let mut config = (*turn_context.config).clone();
+            config.model_reasoning_effort = Some(effort);
+            turn_context.config = Arc::new(config);
+            turn_context.reasoning_effort = Some(effort);
+            turn_context.collaboration_mode =
+                turn_context
+                    .collaboration_mode
+                    .with_updates(None, Some(Some(effort)), None);
+        }
+        turn_context.model_route = Some(label);
+        turn_context
+    }
+
     pub(crate) fn resolve_path(&self, path: Option<String>) -> PathBuf {
         path.as_ref()
             .map(PathBuf::from)
@@ -829,6 +924,44 @@ impl Session {
         }
     }
 
+    /// Chooses whether the session records its model response streams or replays them.
+    ///
+    /// Recordings live in `$CODEX_HOME/recordings/<thread id>` of the root session; sub-agent
+    /// threads record into their root's directory and replay from the same recording, which
+    /// serves every request the response recorded for it.
+    fn build_stream_capture(
+        config: &Config,
+        conversation_id: ThreadId,
+    ) -> anyhow::Result<Option<StreamCapture>> {
+        if let Some(dir) = config.model_stream_replay.as_ref() {
+            let replay = StreamReplay::open(dir.clone())
+                .map_err(|err| anyhow::anyhow!("failed to open replay: {err}"))?;
+            return Ok(Some(StreamCapture::Replay(Arc::new(replay))));
+        }
+        if !config.record_model_streams {
+            return Ok(None);
+        }
+        let dir = config
+            .model_stream_recording_dir
+            .clone()
+            .unwrap_or_else(|| {
+                config
+                    .codex_home
+                    .join(MODEL_STREAM_RECORDINGS_SUBDIR)
+                    .join(conversation_id.to_string())
+            });
+        match StreamRecorder::new(&dir) {
+            Ok(recorder) => Ok(Some(StreamCapture::Record(recorder))),
+            Err(err) => {
+                warn!(
+                    "failed to create model stream recording {}: {err}",
+                    dir.display()
+                );
+                Ok(None)
+            }
+        }
+    }
+
     /// Don't expand the number of mutated arguments on config. We are in the process of getting rid of it.
     pub(crate) fn build_per_turn_config(session_configuration: &SessionConfiguration) -> Config {
         // todo(aibrahim): store this state somewhere else so we don't need to mut config
@@ -879,6 +1012,25 @@ impl Session {
                         };
                         sess.send_event_raw(event).await;
                     }
+                    Ok(FileWatcherEvent::WorkspaceChanged { paths }) => {
+                        let Some(sess) = weak_sess.upgrade() else {
+                            break;
+                        };
+                        let cwd = {
+                            let state = sess.state.lock().await;
+                            state.session_configuration.cwd.clone()
+                        };
+                        let external = sess.services.external_edits.record(paths, &cwd);
+                        if external.is_empty() {
+                            continue;
+                        }
+                        if let Some(tracker) = sess.services.external_edits.turn_diff_tracker() {
+                            let mut tracker = tracker.lock().await;
+                            for path in &external {
+                                tracker.on_external_change(path);
+                            }
+                        }
+                    }
                     Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
                     Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
                 }
@@ -897,6 +1049,7 @@ impl Session {
         network: Option<NetworkProxy>,
         sub_id: String,
         js_repl: Arc<JsReplHandle>,
+        py_repl: Arc<PyReplHandle>,
     ) -> TurnContext {
         let reasoning_effort = session_configuration.collaboration_mode.reasoning_effort();
         let reasoning_summary = session_configuration.model_reasoning_summary;
@@ -915,7 +1068,8 @@ impl Session {
             features: &per_turn_config.features,
             web_search_mode: Some(per_turn_config.web_search_mode.value()),
             plugins: Some(&per_turn_config.plugins),
-        });
+        })
+        .with_config_dynamic_tools(&per_turn_config.dynamic_tools);
 
         let cwd = session_configuration.cwd.clone();
         TurnContext {
@@ -946,8 +1100,11 @@ impl Session {
             codex_linux_sandbox_exe: per_turn_config.codex_linux_sandbox_exe.clone(),
             tool_call_gate: Arc::new(ReadinessFlag::new()),
             truncation_policy: model_info.truncation_policy.into(),
+            tokenizer: tokenizer_for_model(&model_info.slug),
             js_repl,
+            py_repl,
             dynamic_tools: session_configuration.dynamic_tools.clone(),
+            model_route: None,
             turn_metadata_header: OnceCell::new(),
         }
     }
@@ -965,6 +1122,7 @@ impl Session {
         session_source: SessionSource,
         skills_manager: Arc<SkillsManager>,
         file_watcher: Arc<FileWatcher>,
+        agent_activity: Arc<AgentActivity>,
         agent_control: AgentControl,
     ) -> anyhow::Result<Arc<Self>> {
         debug!(
@@ -1159,7 +1317,29 @@ impl Session {
                 }
             };
         session_configuration.thread_name = thread_name.clone();
+        let stream_capture = Self::build_stream_capture(config.as_ref(), conversation_id)?;
+        // Sub-agents are spawned from turn configs, so this is how they find the recording.
+        if let Some(StreamCapture::Record(recorder)) = stream_capture.as_ref()
+            && config.model_stream_recording_dir.is_none()
+        {
+            let mut inherited = (*session_configuration.original_config_do_not_use).clone();
+            inherited.model_stream_recording_dir = Some(recorder.dir().to_path_buf());
+            session_configuration.original_config_do_not_use = Arc::new(inherited);
+        }
         let mut state = SessionState::new(session_configuration.clone());
+        state.provider_failover = config.failover.clone().map(FailoverState::new);
+        let mut remembered_approvals = RepositoryApprovals::new(
+            &config.codex_home,
+            repository_key(&session_configuration.cwd).await,
+        );
+        if let Err(err) = remembered_approvals.reload().await {
+            warn!("failed to load remembered approvals: {err}");
+        }
+        if let Err(err) =
+            exec_policy.set_remembered_prefixes(&remembered_approvals.command_prefixes())
+        {
+            warn!("failed to apply remembered command approvals: {err}");
+        }
         let network_proxy =
             match config.network.as_ref() {
                 Some(spec) => Some(spec.start_proxy().await.map_err(|err| {
@@ -1196,8 +1376,10 @@ impl Session {
             otel_manager,
             models_manager: Arc::clone(&models_manager),
             tool_approvals: Mutex::new(ApprovalStore::default()),
+            remembered_approvals: RwLock::new(remembered_approvals),
             skills_manager,
             file_watcher,
+            external_edits: Arc::new(ExternalEdits::new(agent_activity)),
             agent_control,
             network_proxy,
             state_db: state_db_ctx.clone(),
@@ -1213,12 +1395,17 @@ impl Session {
                 config.features.enabled(Feature::EnableRequestCompression),
                 config.features.enabled(Feature::RuntimeMetrics),
                 Self::build_model_client_beta_features_header(config.as_ref()),
-            ),
+            )
+            .with_stream_capture(stream_capture),
         };
         let js_repl = Arc::new(JsReplHandle::with_node_path(
             config.js_repl_node_path.clone(),
             config.codex_home.clone(),
         ));
+        let py_repl = Arc::new(PyReplHandle::with_python_path(
+            config.py_repl_python_path.clone(),
+            config.codex_home.clone(),
+        ));
 
         let prewarm_model_info = models_manager
             .get_model_info(session_configuration.collaboration_mode.model(), &config)
@@ -1247,6 +1434,7 @@ impl Session {
             active_turn: Mutex::new(None),
             services,
             js_repl,
+            py_repl,
             next_internal_sub_id: AtomicU64::new(0),
         });
 
@@ -1381,14 +1569,17 @@ impl Session {
         format!("auto-compact-{id}")
     }
 
-    pub(crate) async fn get_total_token_usage(&self) -> i64 {
+    pub(crate) async fn get_total_token_usage(&self, tokenizer: &dyn Tokenizer) -> i64 {
         let state = self.state.lock().await;
-        state.get_total_token_usage(state.server_reasoning_included())
+        state.get_total_token_usage(state.server_reasoning_included(), tokenizer)
     }
 
-    pub(crate) async fn get_total_token_usage_breakdown(&self) -> TotalTokenUsageBreakdown {
+    pub(crate) async fn get_total_token_usage_breakdown(
+        &self,
+        tokenizer: &dyn Tokenizer,
+    ) -> TotalTokenUsageBreakdown {
         let state = self.state.lock().await;
-        state.history.get_total_token_usage_breakdown()
+        state.history.get_total_token_usage_breakdown(tokenizer)
     }
 
     pub(crate) async fn get_estimated_token_count(
@@ -1479,6 +1670,10 @@ impl Session {
                     let mut state = self.state.lock().await;
                     state.set_token_info(Some(info));
                 }
+                {
+                    let mut state = self.state.lock().await;
+                    state.dismissed_review_findings = dismissed_findings_from_items(&rollout_items);
+                }
 
                 // Defer seeding the session's initial context until the first turn starts so
                 // turn/start overrides can be merged before we write to the rollout.
@@ -1504,6 +1699,10 @@ impl Session {
                     let mut state = self.state.lock().await;
                     state.set_token_info(Some(info));
                 }
+                {
+                    let mut state = self.state.lock().await;
+                    state.dismissed_review_findings = dismissed_findings_from_items(&rollout_items);
+                }
 
                 // If persisting, persist all rollout items as-is (recorder filters)
                 if !rollout_items.is_empty() {
@@ -1555,6 +1754,50 @@ impl Session {
         state.set_previous_model(previous_model);
     }
 
+    pub(crate) async fn dismissed_review_findings(&self) -> Vec<ReviewFinding> {
+        let state = self.state.lock().await;
+        state.dismissed_review_findings.clone()
+    }
+
+    /// The client for the provider `turn_context` talks to. After a provider failover this
+    /// is the active fallback's client rather than the session's own.
+    pub(crate) async fn model_client_for_turn(&self, turn_context: &TurnContext) -> ModelClient {
+        let state = self.state.lock().await;
+        let failover_target = state
+            .provider_failover
+            .as_ref()
+            .and_then(|failover| failover.target(failover.active()));
+        match failover_target {
+            Some(target) if target.provider == turn_context.provider => self
+                .services
+                .model_client
+                .with_provider(target.provider.clone()),
+            _ => self.services.model_client.clone(),
+        }
+    }
+
+    pub(crate) async fn push_redo_snapshot(&self, snapshot: RedoSnapshot) {
+        let mut state = self.state.lock().await;
+        state.redo_snapshots.push(snapshot);
+    }
+
+    pub(crate) async fn pop_redo_snapshot(&self) -> Option<RedoSnapshot> {
+        let mut state = self.state.lock().await;
+        state.redo_snapshots.pop()
+    }
+
+    pub(crate) async fn redo_available(&self) -> bool {
+        let state = self.state.lock().await;
+        !state.redo_snapshots.is_empty()
+    }
+
+    /// Drops the redo stack; called when a new turn captures a snapshot, after which the
+    /// replaced states no longer follow from the current one.
+    pub(crate) async fn clear_redo_snapshots(&self) {
+        let mut state = self.state.lock().await;
+        state.redo_snapshots.clear();
+    }
+
     fn maybe_refresh_shell_snapshot_for_cwd(
         &self,
         previous_cwd: &Path,
@@ -1579,6 +1822,38 @@ impl Session {
         );
     }
 
+    /// Start watching a new per-turn cwd so edits made there are still reported.
+    fn maybe_watch_workspace_for_cwd(&self, previous_cwd: &Path, next_cwd: &Path) {
+        if previous_cwd == next_cwd || !self.features.enabled(Feature::ExternalEditTracking) {
+            return;
+        }
+        self.services
+            .file_watcher
+            .register_workspace_root(next_cwd.to_path_buf());
+    }
+
+    /// Switch remembered approvals to the repository containing a new per-turn cwd, so
+    /// decisions saved for one checkout never apply in another.
+    async fn maybe_reload_remembered_approvals_for_cwd(
+        &self,
+        previous_cwd: &Path,
+        next_cwd: &Path,
+    ) {
+        if previous_cwd == next_cwd {
+            return;
+        }
+        let repository = repository_key(next_cwd).await;
+        let mut approvals = self.services.remembered_approvals.write().await;
+        if approvals.repository() == repository {
+            return;
+        }
+        approvals.set_repository(repository);
+        if let Err(err) = approvals.reload().await {
+            warn!("failed to load remembered approvals: {err}");
+        }
+        self.apply_remembered_prefixes(&approvals);
+    }
+
     pub(crate) async fn update_settings(
         &self,
         updates: SessionSettingsUpdate,
@@ -1594,6 +1869,9 @@ impl Session {
                 drop(state);
 
                 self.maybe_refresh_shell_snapshot_for_cwd(&previous_cwd, &next_cwd, &codex_home);
+                self.maybe_watch_workspace_for_cwd(&previous_cwd, &next_cwd);
+                self.maybe_reload_remembered_approvals_for_cwd(&previous_cwd, &next_cwd)
+                    .await;
 
                 Ok(())
             }
@@ -1640,6 +1918,9 @@ impl Session {
             &session_configuration.cwd,
             &codex_home,
         );
+        self.maybe_watch_workspace_for_cwd(&previous_cwd, &session_configuration.cwd);
+        self.maybe_reload_remembered_approvals_for_cwd(&previous_cwd, &session_configuration.cwd)
+            .await;
 
         Ok(self
             .new_turn_from_configuration(
@@ -1702,6 +1983,7 @@ impl Session {
                 .map(StartedNetworkProxy::proxy),
             sub_id,
             Arc::clone(&self.js_repl),
+            Arc::clone(&self.py_repl),
         );
 
         if let Some(final_schema) = final_output_json_schema {
@@ -1977,28 +2259,73 @@ impl Session {
         .await;
     }
 
-    /// Adds an execpolicy amendment to both the in-memory and on-disk policies so future
-    /// commands can use the newly approved prefix.
+    /// Saves an execpolicy amendment so future commands can use the newly approved prefix.
+    /// It is remembered for the current repository unless `[remembered_approvals]` asks for
+    /// the global rules file.
     pub(crate) async fn persist_execpolicy_amendment(
         &self,
         amendment: &ExecPolicyAmendment,
     ) -> Result<(), ExecPolicyUpdateError> {
-        let codex_home = self
-            .state
-            .lock()
-            .await
-            .session_configuration
-            .codex_home()
-            .clone();
+        let config = self.get_config().await;
+        match config.remembered_approvals.scope {
+            RememberedApprovalsScope::Repository => {
+                self.remember_approval(
+                    ApprovalSubject::CommandPrefix {
+                        prefix: amendment.command.clone(),
+                    },
+                    RememberedDecision::Allow,
+                )
+                .await
+                .map_err(|source| ExecPolicyUpdateError::RememberApproval { source })?;
+            }
+            RememberedApprovalsScope::Global => {
+                self.services
+                    .exec_policy
+                    .append_amendment_and_update(&config.codex_home, amendment)
+                    .await?;
+            }
+        }
 
-        self.services
-            .exec_policy
-            .append_amendment_and_update(&codex_home, amendment)
-            .await?;
+        Ok(())
+    }
+
+    /// Saves `decision` for `subject` for the current repository and applies it to the rest
+    /// of the session.
+    pub(crate) async fn remember_approval(
+        &self,
+        subject: ApprovalSubject,
+        decision: RememberedDecision,
+    ) -> std::io::Result<()> {
+        let expires_at = self
+            .get_config()
+            .await
+            .remembered_approvals
+            .expire_after_days
+            .map(|days| chrono::Utc::now() + chrono::Duration::days(i64::from(days)));
+        let mut approvals = self.services.remembered_approvals.write().await;
+        approvals.remember(subject, decision, expires_at).await?;
+        self.apply_remembered_prefixes(&approvals);
+        Ok(())
+    }
 
+    /// Re-reads the approvals remembered for this repository.
+    pub(crate) async fn reload_remembered_approvals(&self) -> std::io::Result<()> {
+        let mut approvals = self.services.remembered_approvals.write().await;
+        approvals.reload().await?;
+        self.apply_remembered_prefixes(&approvals);
         Ok(())
     }
 
+    fn apply_remembered_prefixes(&self, approvals: &RepositoryApprovals) {
+        if let Err(err) = self
+            .services
+            .exec_policy
+            .set_remembered_prefixes(&approvals.command_prefixes())
+        {
+            warn!("failed to apply remembered command approvals: {err}");
+        }
+    }
+
     async fn turn_context_for_sub_id(&self, sub_id: &str) -> Option<Arc<TurnContext>> {
         let active = self.active_turn.lock().await;
         active
@@ -2062,6 +2389,7 @@ impl Session {
         cwd: PathBuf,
         reason: Option<String>,
         proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
+        provenance: Option<DecisionProvenance>,
     ) -> ReviewDecision {
         // Add the tx_approve callback to the map before sending the request.
         let (tx_approve, rx_approve) = oneshot::channel();
@@ -2089,6 +2417,7 @@ impl Session {
             reason,
             proposed_execpolicy_amendment,
             parsed_cmd,
+            provenance,
         });
         self.send_event(turn_context, event).await;
         rx_approve.await.unwrap_or_default()
@@ -2267,6 +2596,7 @@ impl Session {
                     history.record_items(
                         std::iter::once(response_item),
                         turn_context.truncation_policy,
+                        turn_context.tokenizer,
                     );
                 }
                 RolloutItem::Compacted(compacted) => {
@@ -2278,6 +2608,7 @@ impl Session {
                             self.build_initial_context(turn_context).await,
                             &user_messages,
                             &compacted.message,
+                            turn_context.tokenizer,
                         );
                         history.replace(rebuilt);
                     }
@@ -2307,7 +2638,11 @@ impl Session {
         turn_context: &TurnContext,
     ) {
         let mut state = self.state.lock().await;
-        state.record_items(items.iter(), turn_context.truncation_policy);
+        state.record_items(
+            items.iter(),
+            turn_context.truncation_policy,
+            turn_context.tokenizer,
+        );
     }
 
     pub(crate) async fn record_model_warning(&self, message: impl Into<String>, ctx: &TurnContext) {
@@ -2484,17 +2819,106 @@ impl Session {
             if let Some(token_usage) = token_usage {
                 state
                     .update_token_info_from_usage(token_usage, turn_context.model_context_window());
+                state.tokens_used = state
+                    .tokens_used
+                    .saturating_add(u64::try_from(token_usage.total_tokens).unwrap_or(0));
             }
         }
+        if let Some(token_usage) = token_usage
+            && let Some(state_db) = self.state_db()
+        {
+            token_budget::record_response_usage(
+                state_db,
+                token_budget::ResponseUsage {
+                    thread_id: self.conversation_id.to_string(),
+                    turn_id: &turn_context.sub_id,
+                    model: &turn_context.model_info.slug,
+                    provider: &turn_context.config.model_provider_id,
+                    cwd: &turn_context.cwd,
+                    usage: token_usage,
+                },
+            );
+        }
         self.send_token_count_event(turn_context).await;
     }
 
+    /// Warn about, or refuse, a turn in a project that has used up its
+    /// `token_budget`. Returns `false` when the turn must not run.
+    async fn enforce_token_budget(&self, turn_context: &TurnContext) -> bool {
+        let Some(budget) = turn_context.config.active_project.token_budget.as_ref() else {
+            return true;
+        };
+        let Some(state_db) = self.state_db() else {
+            return true;
+        };
+        let status = match token_budget::check_budget(&state_db, budget, &turn_context.cwd).await {
+            Ok(status) => status,
+            Err(err) => {
+                warn!("failed to check token budget: {err}");
+                return true;
+            }
+        };
+        let Some(message) = status.message(budget) else {
+            return true;
+        };
+        if matches!(status, token_budget::BudgetStatus::HardLimitReached { .. }) {
+            let event = EventMsg::Error(ErrorEvent {
+                message,
+                codex_error_info: Some(CodexErrorInfo::UsageLimitExceeded),
+            });
+            self.send_event(turn_context, event).await;
+            return false;
+        }
+        self.send_event(turn_context, EventMsg::Warning(WarningEvent { message }))
+            .await;
+        true
+    }
+
+    /// Refuse to sample the model once the session has reached a limit of its
+    /// `session_budget`. Returns `false` when sampling must not happen.
+    async fn enforce_session_budget(&self, turn_context: &TurnContext) -> bool {
+        let (tokens_used, elapsed) = {
+            let state = self.state.lock().await;
+            (state.tokens_used, state.started_at.elapsed())
+        };
+        let Some(message) = session_budget::exceeded_message(
+            &turn_context.config.session_budget,
+            tokens_used,
+            elapsed,
+        ) else {
+            return true;
+        };
+        let event = EventMsg::Error(ErrorEvent {
+            message,
+            codex_error_info: Some(CodexErrorInfo::UsageLimitExceeded),
+        });
+        self.send_event(turn_context, event).await;
+        false
+    }
+
+    /// Cancel `cancellation_token` when the session's wall-clock budget runs
+    /// out. The timer stops when the returned handle is dropped.
+    async fn cancel_at_session_deadline(
+        &self,
+        turn_context: &TurnContext,
+        cancellation_token: &CancellationToken,
+    ) -> Option<AbortOnDropHandle<()>> {
+        let started_at = self.state.lock().await.started_at;
+        let deadline = session_budget::deadline(&turn_context.config.session_budget, started_at)?;
+        let cancellation_token = cancellation_token.clone();
+        Some(AbortOnDropHandle::new(tokio::spawn(async move {
+            tokio::time::sleep_until(deadline.into()).await;
+            cancellation_token.cancel();
+        })))
+    }
+
     pub(crate) async fn recompute_token_usage(&self, turn_context: &TurnContext) {
         let history = self.clone_history().await;
         let base_instructions = self.get_base_instructions().await;
-        let Some(estimated_total_tokens) =
-            history.estimate_token_count_with_base_instructions(&base_instructions)
-        else {
+        let Some(estimated_total_tokens) = history.estimate_token_count_with_base_instructions(
+            &base_instructions,
+            turn_context.tokenizer,
+        ) else {
             return;
         };
         {
@@ -2949,6 +3373,9 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::CleanBackgroundTerminals => {
                 handlers::clean_background_terminals(&sess).await;
             }
+            Op::ReloadRememberedApprovals => {
+                handlers::reload_remembered_approvals(&sess, sub.id.clone()).await;
+            }
             Op::OverrideTurnContext {
                 cwd,
                 approval_policy,
@@ -3047,6 +3474,15 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::Undo => {
                 handlers::undo(&sess, sub.id.clone()).await;
             }
+            Op::ListSnapshots => {
+                handlers::list_snapshots(&sess, sub.id.clone()).await;
+            }
+            Op::RestoreSnapshot { snapshot_id, paths } => {
+                handlers::restore_snapshot(&sess, sub.id.clone(), snapshot_id, paths).await;
+            }
+            Op::Redo => {
+                handlers::redo(&sess, sub.id.clone()).await;
+            }
             Op::Compact => {
                 handlers::compact(&sess, sub.id.clone()).await;
             }
@@ -3086,6 +3522,9 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::Review { review_request } => {
                 handlers::review(&sess, &config, sub.id.clone(), review_request).await;
             }
+            Op::SetReviewFindingStatus { finding, status } => {
+                handlers::set_review_finding_status(&sess, sub.id.clone(), finding, status).await;
+            }
             _ => {} // Ignore unknown ops; enum is non_exhaustive to allow extensions.
         }
     }
@@ -3105,6 +3544,10 @@ mod handlers {
     use crate::mcp::auth::compute_auth_statuses;
     use crate::mcp::collect_mcp_snapshot_from_manager;
     use crate::mcp::effective_mcp_servers;
+    use crate::remembered_approvals::ApprovalSubject;
+    use crate::remembered_approvals::RememberedDecision;
+    use crate::review_prompts::append_dismissed_findings;
+    use crate::review_prompts::record_finding_status;
     use crate::review_prompts::resolve_review_request;
     use crate::rollout::session_index;
     use crate::tasks::CompactTask;
@@ -3112,6 +3555,7 @@ mod handlers {
     use crate::tasks::UserShellCommandMode;
     use crate::tasks::UserShellCommandTask;
     use crate::tasks::execute_user_shell_command;
+    use crate::tasks::snapshot_timeline;
     use codex_protocol::custom_prompts::CustomPrompt;
     use codex_protocol::protocol::CodexErrorInfo;
     use codex_protocol::protocol::ErrorEvent;
@@ -3125,6 +3569,9 @@ mod handlers {
     use codex_protocol::protocol::RemoteSkillDownloadedEvent;
     use codex_protocol::protocol::RemoteSkillSummary;
     use codex_protocol::protocol::ReviewDecision;
+    use codex_protocol::protocol::ReviewFinding;
+    use codex_protocol::protocol::ReviewFindingStatus;
+    use codex_protocol::protocol::ReviewFindingStatusEvent;
     use codex_protocol::protocol::ReviewRequest;
     use codex_protocol::protocol::SkillsListEntry;
     use codex_protocol::protocol::ThreadNameUpdatedEvent;
@@ -3155,6 +3602,18 @@ mod handlers {
         sess.close_unified_exec_processes().await;
     }
 
+    pub async fn reload_remembered_approvals(sess: &Arc<Session>, sub_id: String) {
+        if let Err(err) = sess.reload_remembered_approvals().await {
+            let message = format!("Failed to reload remembered approvals: {err}");
+            warn!("{message}");
+            sess.send_event_raw(Event {
+                id: sub_id,
+                msg: EventMsg::Warning(WarningEvent { message }),
+            })
+            .await;
+        }
+    }
+
     pub async fn override_turn_context(
         sess: &Session,
         sub_id: String,
@@ -3361,6 +3820,26 @@ mod handlers {
                 }
             }
         }
+        if let ReviewDecision::DeniedExecpolicyAmendment {
+            proposed_execpolicy_amendment,
+        } = &decision
+            && let Err(err) = sess
+                .remember_approval(
+                    ApprovalSubject::CommandPrefix {
+                        prefix: proposed_execpolicy_amendment.command.clone(),
+                    },
+                    RememberedDecision::Deny,
+                )
+                .await
+        {
+            let message = format!("Failed to remember denied command prefix: {err}");
+            tracing::warn!("{message}");
+            sess.send_event_raw(Event {
+                id: event_turn_id.clone(),
+                msg: EventMsg::Warning(WarningEvent { message }),
+            })
+            .await;
+        }
         match decision {
             ReviewDecision::Abort => {
                 sess.interrupt_task().await;
@@ -3601,6 +4080,39 @@ mod handlers {
             .await;
     }
 
+    /// Computing diff stats snapshots the working tree, so the timeline is built off the
+    /// submission loop.
+    pub async fn list_snapshots(sess: &Arc<Session>, sub_id: String) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        let sess = Arc::clone(sess);
+        tokio::spawn(async move {
+            let timeline = snapshot_timeline(&sess, &turn_context).await;
+            sess.send_event(&turn_context, EventMsg::SnapshotTimeline(timeline))
+                .await;
+        });
+    }
+
+    pub async fn restore_snapshot(
+        sess: &Arc<Session>,
+        sub_id: String,
+        snapshot_id: String,
+        paths: Vec<PathBuf>,
+    ) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        sess.spawn_task(
+            turn_context,
+            Vec::new(),
+            UndoTask::restore_snapshot(snapshot_id, paths),
+        )
+        .await;
+    }
+
+    pub async fn redo(sess: &Arc<Session>, sub_id: String) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        sess.spawn_task(turn_context, Vec::new(), UndoTask::redo())
+            .await;
+    }
+
     pub async fn compact(sess: &Arc<Session>, sub_id: String) {
         let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
 
@@ -3789,6 +4301,25 @@ mod handlers {
         .await;
     }
 
+    /// Records the user's decision on a review finding and persists it to the rollout so it
+    /// survives resume.
+    pub async fn set_review_finding_status(
+        sess: &Arc<Session>,
+        sub_id: String,
+        finding: ReviewFinding,
+        status: ReviewFindingStatus,
+    ) {
+        {
+            let mut state = sess.state.lock().await;
+            record_finding_status(&mut state.dismissed_review_findings, &finding, status);
+        }
+        sess.send_event_raw(Event {
+            id: sub_id,
+            msg: EventMsg::ReviewFindingStatus(ReviewFindingStatusEvent { finding, status }),
+        })
+        .await;
+    }
+
     pub async fn shutdown(sess: &Arc<Session>, sub_id: String) -> bool {
         sess.abort_all_tasks(TurnAbortReason::Interrupted).await;
         sess.services
@@ -3845,7 +4376,9 @@ mod handlers {
         let turn_context = sess.new_default_turn_with_sub_id(sub_id.clone()).await;
         sess.refresh_mcp_servers_if_requested(&turn_context).await;
         match resolve_review_request(review_request, turn_context.cwd.as_path()) {
-            Ok(resolved) => {
+            Ok(mut resolved) => {
+                let dismissed = sess.dismissed_review_findings().await;
+                append_dismissed_findings(&mut resolved.prompt, &dismissed);
                 spawn_review_thread(
                     Arc::clone(sess),
                     Arc::clone(config),
@@ -3959,8 +4492,11 @@ async fn spawn_review_thread(
         codex_linux_sandbox_exe: parent_turn_context.codex_linux_sandbox_exe.clone(),
         tool_call_gate: Arc::new(ReadinessFlag::new()),
         js_repl: Arc::clone(&sess.js_repl),
+        py_repl: Arc::clone(&sess.py_repl),
         dynamic_tools: parent_turn_context.dynamic_tools.clone(),
+        model_route: None,
         truncation_policy: model_info.truncation_policy.into(),
+        tokenizer: tokenizer_for_model(&model_info.slug),
         turn_metadata_header: parent_turn_context.turn_metadata_header.clone(),
     };
 
@@ -4061,8 +4597,22 @@ pub(crate) async fn run_turn(
         return None;
     }
 
+    // Failover switches are always derived from the turn's original context so
+    // that returning to the primary provider restores it exactly.
+    let base_turn_context = route_regular_turn(&sess, turn_context, &input).await;
+    let mut turn_context = Arc::clone(&base_turn_context);
+    let mut prewarmed_client_session = prewarmed_client_session;
+    let mut model_client = sess.services.model_client.clone();
+    if let Some((failover_turn_context, failover_client)) =
+        resolve_provider_failover(&sess, &base_turn_context, None).await
+    {
+        turn_context = failover_turn_context;
+        model_client = failover_client;
+        prewarmed_client_session = None;
+    }
+
     let model_info = turn_context.model_info.clone();
-    let auto_compact_limit = model_info.auto_compact_token_limit().unwrap_or(i64::MAX);
+    let mut auto_compact_limit = model_info.auto_compact_token_limit().unwrap_or(i64::MAX);
 
     let event = EventMsg::TurnStarted(TurnStartedEvent {
         turn_id: turn_context.sub_id.clone(),
@@ -4070,6 +4620,11 @@ pub(crate) async fn run_turn(
         collaboration_mode_kind: turn_context.collaboration_mode.mode,
     });
     sess.send_event(&turn_context, event).await;
+    if !sess.enforce_token_budget(&turn_context).await
+        || !sess.enforce_session_budget(&turn_context).await
+    {
+        return None;
+    }
     if run_pre_sampling_compact(&sess, &turn_context)
         .await
         .is_err()
@@ -4148,6 +4703,15 @@ pub(crate) async fn run_turn(
             .await;
     }
 
+    if let Some(external_edits) = sess
+        .services
+        .external_edits
+        .take_context_item(&turn_context.cwd)
+    {
+        sess.record_conversation_items(&turn_context, std::slice::from_ref(&external_edits))
+            .await;
+    }
+
     let initial_input_for_turn: ResponseInputItem = ResponseInputItem::from(input.clone());
     let response_item: ResponseItem = initial_input_for_turn.clone().into();
     sess.record_user_prompt_and_emit_turn_item(turn_context.as_ref(), &input, response_item)
@@ -4164,14 +4728,23 @@ pub(crate) async fn run_turn(
     // Although from the perspective of codex.rs, TurnDiffTracker has the lifecycle of a Task which contains
     // many turns, from the perspective of the user, it is a single turn.
     let turn_diff_tracker = Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new()));
+    sess.services
+        .external_edits
+        .set_turn_diff_tracker(&turn_diff_tracker);
 
     let turn_metadata_header = turn_context.resolve_turn_metadata_header().await;
     // `ModelClientSession` is turn-scoped and caches WebSocket + sticky routing state, so we reuse
     // one instance across retries within this turn.
-    let mut client_session =
-        prewarmed_client_session.unwrap_or_else(|| sess.services.model_client.new_session());
+    let mut client_session = prewarmed_client_session.unwrap_or_else(|| model_client.new_session());
+    // Context of the previous sampling request, to notice when a route switches models.
+    let mut last_sampling_context: Option<Arc<TurnContext>> = None;
+    let mut turn_switched_models = false;
 
     loop {
+        if !sess.enforce_session_budget(&turn_context).await {
+            break;
+        }
+
         // Note that pending_input would be something like a message the user
         // submitted through the UI while the model was running. Though the UI
         // may support this, the model might not.
@@ -4203,11 +4776,33 @@ pub(crate) async fn run_turn(
         }
 
         // Construct the input that we will send to the model.
-        let sampling_request_input: Vec<ResponseItem> = {
-            sess.clone_history()
-                .await
-                .for_prompt(&turn_context.model_info.input_modalities)
-        };
+        let mut history = sess.clone_history().await;
+        let sampling_turn_context =
+            route_follow_up(&sess, &turn_context, history.raw_items()).await;
+        let previous_sampling_context = last_sampling_context
+            .replace(Arc::clone(&sampling_turn_context))
+            .unwrap_or_else(|| Arc::clone(&turn_context));
+        if previous_sampling_context.model_info.slug != sampling_turn_context.model_info.slug {
+            turn_switched_models = true;
+            let total_usage_tokens = sess.get_total_token_usage(turn_context.tokenizer).await;
+            match maybe_compact_for_model_switch(
+                &sess,
+                &previous_sampling_context,
+                &sampling_turn_context,
+                total_usage_tokens,
+            )
+            .await
+            {
+                Ok(true) => history = sess.clone_history().await,
+                Ok(false) => {}
+                Err(_) => return None,
+            }
+        }
+        let mut sampling_request_input: Vec<ResponseItem> =
+            history.for_prompt(&sampling_turn_context.model_info.input_modalities);
+        if turn_switched_models {
+            drop_turn_reasoning(&mut sampling_request_input);
+        }
 
         let sampling_request_input_messages = sampling_request_input
             .iter()
@@ -4217,16 +4812,20 @@ pub(crate) async fn run_turn(
             })
             .map(|user_message| user_message.message())
             .collect::<Vec<String>>();
+        let sampling_cancellation_token = cancellation_token.child_token();
+        let _session_deadline = sess
+            .cancel_at_session_deadline(&turn_context, &sampling_cancellation_token)
+            .await;
         match run_sampling_request(
             Arc::clone(&sess),
-            Arc::clone(&turn_context),
+            sampling_turn_context,
             Arc::clone(&turn_diff_tracker),
             &mut client_session,
             turn_metadata_header.as_deref(),
             sampling_request_input,
             &explicitly_enabled_connectors,
             skills_outcome.as_ref(),
-            cancellation_token.child_token(),
+            sampling_cancellation_token,
         )
         .await
         {
@@ -4235,7 +4834,7 @@ pub(crate) async fn run_turn(
                     needs_follow_up,
                     last_agent_message: sampling_request_last_agent_message,
                 } = sampling_request_output;
-                let total_usage_tokens = sess.get_total_token_usage().await;
+                let total_usage_tokens = sess.get_total_token_usage(turn_context.tokenizer).await;
                 let token_limit_reached = total_usage_tokens >= auto_compact_limit;
 
                 let estimated_token_count =
@@ -4281,7 +4880,11 @@ pub(crate) async fn run_turn(
                 continue;
             }
             Err(CodexErr::TurnAborted) => {
-                // Aborted turn is reported via a different event.
+                // Aborted turn is reported via a different event, unless the
+                // session's time budget cut the request short.
+                if !cancellation_token.is_cancelled() {
+                    sess.enforce_session_budget(&turn_context).await;
+                }
                 break;
             }
             Err(CodexErr::InvalidImageRequest()) => {
@@ -4301,6 +4904,19 @@ pub(crate) async fn run_turn(
                 break;
             }
             Err(e) => {
+                if let Some(trigger) = failover_trigger(&e)
+                    && let Some((failover_turn_context, failover_client)) =
+                        resolve_provider_failover(&sess, &base_turn_context, Some(trigger)).await
+                {
+                    info!("Turn error, failing over to the next provider: {e:#}");
+                    turn_context = failover_turn_context;
+                    auto_compact_limit = turn_context
+                        .model_info
+                        .auto_compact_token_limit()
+                        .unwrap_or(i64::MAX);
+                    client_session = failover_client.new_session();
+                    continue;
+                }
                 info!("Turn error: {e:#}");
                 let event = EventMsg::Error(e.to_error_event(None));
                 sess.send_event(&turn_context, event).await;
@@ -4313,18 +4929,95 @@ pub(crate) async fn run_turn(
     last_agent_message
 }
 
+/// Move the session along its provider failover chain, if one is configured.
+///
+/// With a `trigger`, the active entry failed and is put into its cool-down;
+/// without one, this is the start of a turn and the earliest entry that is not
+/// cooling down is selected. Returns the context and client for the active
+/// entry, or `None` when the session should keep using its own provider (or,
+/// after a failure, when no entry is left to try). Every switch is reported as
+/// a `ProviderFailover` event.
+async fn resolve_provider_failover(
+    sess: &Arc<Session>,
+    base_turn_context: &Arc<TurnContext>,
+    trigger: Option<FailoverTrigger>,
+) -> Option<(Arc<TurnContext>, ModelClient)> {
+    let now = Instant::now();
+    let (switch, from, to) = {
+        let mut state = sess.state.lock().await;
+        let failover = state.provider_failover.as_mut()?;
+        let switch = match trigger {
+            Some(trigger) => Some(failover.fail_over(trigger, now)?),
+            None => failover.select_for_turn(now),
+        };
+        let active = failover.active();
+        if switch.is_none() && active == 0 {
+            return None;
+        }
+        let from = switch.and_then(|switch| failover.target(switch.from).cloned());
+        (switch, from, failover.target(active).cloned())
+    };
+
+    let (turn_context, client) = match to.as_ref() {
+        Some(target) => (
+            Arc::new(
+                base_turn_context
+                    .with_failover_target(target, &sess.services.models_manager)
+                    .await,
+            ),
+            sess.services
+                .model_client
+                .with_provider(target.provider.clone()),
+        ),
+        None => (
+            Arc::clone(base_turn_context),
+            sess.services.model_client.clone(),
+        ),
+    };
+
+    if let Some(switch) = switch {
+        let describe = |target: Option<&FailoverTarget>| match target {
+            Some(target) => (
+                target.provider_id.clone(),
+                target
+                    .model
+                    .clone()
+                    .unwrap_or_else(|| base_turn_context.collaboration_mode.model().to_string()),
+            ),
+            None => (
+                base_turn_context.config.model_provider_id.clone(),
+                base_turn_context.collaboration_mode.model().to_string(),
+            ),
+        };
+        let (from_provider, from_model) = describe(from.as_ref());
+        let (to_provider, to_model) = describe(to.as_ref());
+        let event = EventMsg::ProviderFailover(ProviderFailoverEvent {
+            from_provider,
+            from_model,
+            to_provider,
+            to_model,
+            trigger,
+            cooldown_secs: switch.cooldown.map(|cooldown| cooldown.as_secs()),
+        });
+        sess.send_event(&turn_context, event).await;
+    }
+
+    Some((turn_context, client))
+}
+
 async fn run_pre_sampling_compact(
     sess: &Arc<Session>,
     turn_context: &Arc<TurnContext>,
 ) -> CodexResult<()> {
-    let total_usage_tokens_before_compaction = sess.get_total_token_usage().await;
+    let total_usage_tokens_before_compaction =
+        sess.get_total_token_usage(turn_context.tokenizer).await;
     maybe_run_previous_model_inline_compact(
         sess,
         turn_context,
         total_usage_tokens_before_compaction,
     )
     .await?;
-    let total_usage_tokens = sess.get_total_token_usage().await;
+    let total_usage_tokens = sess.get_total_token_usage(turn_context.tokenizer).await;
     let auto_compact_limit = turn_context
         .model_info
         .auto_compact_token_limit()
@@ -4349,35 +5042,145 @@ async fn maybe_run_previous_model_inline_compact(
             .with_model(previous_model, &sess.services.models_manager)
             .await,
     );
+    maybe_compact_for_model_switch(
+        sess,
+        &previous_turn_context,
+        turn_context,
+        total_usage_tokens,
+    )
+    .await?;
+    Ok(())
+}
 
-    let Some(old_context_window) = previous_turn_context.model_context_window() else {
-        return Ok(());
+/// Compact with the model of `from` before switching to the model of `to`,
+/// when the history is past `to`'s auto-compact limit and `from` has the larger
+/// context window to summarize it in. Returns whether it compacted.
+async fn maybe_compact_for_model_switch(
+    sess: &Arc<Session>,
+    from: &Arc<TurnContext>,
+    to: &Arc<TurnContext>,
+    total_usage_tokens: i64,
+) -> CodexResult<bool> {
+    let Some(old_context_window) = from.model_context_window() else {
+        return Ok(false);
     };
-    let Some(new_context_window) = turn_context.model_context_window() else {
-        return Ok(());
+    let Some(new_context_window) = to.model_context_window() else {
+        return Ok(false);
     };
-    let new_auto_compact_limit = turn_context
-        .model_info
-        .auto_compact_token_limit()
-        .unwrap_or(i64::MAX);
+    let new_auto_compact_limit = to.model_info.auto_compact_token_limit().unwrap_or(i64::MAX);
     let should_run = total_usage_tokens > new_auto_compact_limit
-        && previous_turn_context.model_info.slug != turn_context.model_info.slug
+        && from.model_info.slug != to.model_info.slug
         && old_context_window > new_context_window;
     if should_run {
-        run_auto_compact(sess, &previous_turn_context).await?;
+        run_auto_compact(sess, from).await?;
     }
-    Ok(())
+    Ok(should_run)
 }
 
 async fn run_auto_compact(sess: &Arc<Session>, turn_context: &Arc<TurnContext>) -> CodexResult<()> {
+    let turn_context = route_compact_turn(sess, Arc::clone(turn_context)).await;
     if should_use_remote_compact_task(&turn_context.provider) {
-        run_inline_remote_auto_compact_task(Arc::clone(sess), Arc::clone(turn_context)).await?;
+        run_inline_remote_auto_compact_task(Arc::clone(sess), turn_context).await?;
     } else {
-        run_inline_auto_compact_task(Arc::clone(sess), Arc::clone(turn_context)).await?;
+        run_inline_auto_compact_task(Arc::clone(sess), turn_context).await?;
     }
     Ok(())
 }
 
+/// Apply the first `[[model_routes]]` rule matching `request` to
+/// `turn_context`, or return `None` when no rule matches.
+async fn route_turn_context(
+    sess: &Session,
+    turn_context: &Arc<TurnContext>,
+    request: &RouteRequest<'_>,
+) -> Option<Arc<TurnContext>> {
+    let (label, route) = select_route(&turn_context.config.model_routes, request)?;
+    info!(
+        route = %label,
+        task = ?request.task,
+        model = ?route.model,
+        effort = ?route.model_reasoning_effort,
+        "model route selected"
+    );
+    Some(Arc::new(
+        turn_context
+            .with_model_route(label, route, &sess.services.models_manager)
+            .await,
+    ))
+}
+
+/// Route a regular turn. The prompt is sized as the current history plus the
+/// new user input.
+async fn route_regular_turn(
+    sess: &Session,
+    turn_context: Arc<TurnContext>,
+    input: &[UserInput],
+) -> Arc<TurnContext> {
+    if turn_context.config.model_routes.is_empty() {
+        return turn_context;
+    }
+    let input_tokens = input
+        .iter()
+        .map(|item| match item {
+            UserInput::Text { text, .. } => turn_context.tokenizer.count_tokens(text),
+            _ => 0,
+        })
+        .fold(0usize, usize::saturating_add);
+    let prompt_tokens = sess
+        .get_estimated_token_count(&turn_context)
+        .await
+        .map(|tokens| tokens.saturating_add(i64::try_from(input_tokens).unwrap_or(i64::MAX)));
+    let routed = route_turn_context(
+        sess,
+        &turn_context,
+        &RouteRequest::for_turn(&turn_context, prompt_tokens),
+    )
+    .await;
+    routed.unwrap_or(turn_context)
+}
+
+/// Route a compaction summary request made on behalf of `turn_context`.
+pub(crate) async fn route_compact_turn(
+    sess: &Session,
+    turn_context: Arc<TurnContext>,
+) -> Arc<TurnContext> {
+    if turn_context.config.model_routes.is_empty() {
+        return turn_context;
+    }
+    let prompt_tokens = sess.get_estimated_token_count(&turn_context).await;
+    let request = RouteRequest {
+        task: ModelRouteTask::Compact,
+        ..RouteRequest::for_turn(&turn_context, prompt_tokens)
+    };
+    let routed = route_turn_context(sess, &turn_context, &request).await;
+    routed.unwrap_or(turn_context)
+}
+
+/// Route a follow-up request within a turn through an `after_tool` rule when
+/// the previous response only called that tool. Other requests keep the
+/// turn's context. The turn loop compacts and drops this turn's reasoning
+/// when the routed model differs from the one that sampled last.
+async fn route_follow_up(
+    sess: &Session,
+    turn_context: &Arc<TurnContext>,
+    history: &[ResponseItem],
+) -> Arc<TurnContext> {
+    if turn_context.config.model_routes.is_empty() {
+        return Arc::clone(turn_context);
+    }
+    let Some(tool) = last_response_tool(history) else {
+        return Arc::clone(turn_context);
+    };
+    let prompt_tokens = sess.get_estimated_token_count(turn_context).await;
+    let request = RouteRequest {
+        after_tool: Some(tool),
+        ..RouteRequest::for_turn(turn_context, prompt_tokens)
+    };
+    route_turn_context(sess, turn_context, &request)
+        .await
+        .unwrap_or_else(|| Arc::clone(turn_context))
+}
+
 fn filter_connectors_for_input(
     connectors: Vec<connectors::AppInfo>,
     input: &[ResponseItem],
@@ -4656,8 +5459,25 @@ async fn built_tools(
         mcp_tools = filter_codex_apps_mcp_tools(mcp_tools, connectors);
     }
 
+    // Tools from large OpenAPI sources stay hidden until `search_tool_bm25`
+    // selects them.
+    let mut tools_config = None;
+    if turn_context
+        .tools_config
+        .config_dynamic_tools
+        .iter()
+        .any(|tool| tool.deferred)
+    {
+        let selected_tools = sess.get_mcp_tool_selection().await.unwrap_or_default();
+        let mut filtered = turn_context.tools_config.clone();
+        filtered
+            .config_dynamic_tools
+            .retain(|tool| !tool.deferred || selected_tools.contains(&tool.name));
+        tools_config = Some(filtered);
+    }
+
     Ok(Arc::new(ToolRouter::from_config(
-        &turn_context.tools_config,
+        tools_config.as_ref().unwrap_or(&turn_context.tools_config),
         Some(
             mcp_tools
                 .into_iter()
@@ -5102,6 +5922,7 @@ async fn try_run_sampling_request(
         developer_instructions: turn_context.developer_instructions.clone(),
         final_output_json_schema: turn_context.final_output_json_schema.clone(),
         truncation_policy: Some(turn_context.truncation_policy.into()),
+        model_route: turn_context.model_route.clone(),
     });
 
     feedback_tags!(
@@ -5355,12 +6176,18 @@ async fn try_run_sampling_request(
     drain_in_flight(&mut in_flight, sess.clone(), turn_context.clone()).await?;
 
     if should_emit_turn_diff {
-        let unified_diff = {
+        let (unified_diff, external_unified_diff) = {
             let mut tracker = turn_diff_tracker.lock().await;
-            tracker.get_unified_diff()
+            (
+                tracker.get_unified_diff(),
+                tracker.get_external_unified_diff(),
+            )
         };
         if let Ok(Some(unified_diff)) = unified_diff {
-            let msg = EventMsg::TurnDiff(TurnDiffEvent { unified_diff });
+            let msg = EventMsg::TurnDiff(TurnDiffEvent {
+                unified_diff,
+                external_unified_diff,
+            });
             sess.clone().send_event(&turn_context, msg).await;
         }
     }
@@ -5776,6 +6603,7 @@ mod tests {
             developer_instructions: None,
             final_output_json_schema: None,
             truncation_policy: Some(turn_context.truncation_policy.into()),
+            model_route: None,
         })];
 
         session
@@ -5915,7 +6743,10 @@ mod tests {
             text: override_instructions,
         };
         let expected_tokens = history
-            .estimate_token_count_with_base_instructions(&session_base_instructions)
+            .estimate_token_count_with_base_instructions(
+                &session_base_instructions,
+                turn_context.tokenizer,
+            )
             .expect("estimate with session base instructions");
         let model_estimated_tokens = history
             .estimate_token_count(&turn_context)
@@ -5994,6 +6825,7 @@ mod tests {
             developer_instructions: None,
             final_output_json_schema: None,
             truncation_policy: Some(turn_context.truncation_policy.into()),
+            model_route: None,
         })];
 
         session
@@ -6365,7 +7197,11 @@ mod tests {
         };
         let (_, turn_context) = make_session_and_context().await;
 
-        let out = format_exec_output_str(&exec, turn_context.truncation_policy);
+        let out = format_exec_output_str(
+            &exec,
+            turn_context.truncation_policy,
+            turn_context.tokenizer,
+        );
 
         assert_eq!(
             out,
@@ -6770,8 +7606,13 @@ enabled = ["collab-prompts-test"]
             otel_manager: otel_manager.clone(),
             models_manager: Arc::clone(&models_manager),
             tool_approvals: Mutex::new(ApprovalStore::default()),
+            remembered_approvals: RwLock::new(RepositoryApprovals::new(
+                &config.codex_home,
+                config.cwd.to_string_lossy().into_owned(),
+            )),
             skills_manager,
             file_watcher,
+            external_edits: Arc::new(ExternalEdits::default()),
             agent_control,
             network_proxy: None,
             state_db: None,
@@ -6794,6 +7635,10 @@ enabled = ["collab-prompts-test"]
             config.js_repl_node_path.clone(),
             config.codex_home.clone(),
         ));
+        let py_repl = Arc::new(PyReplHandle::with_python_path(
+            config.py_repl_python_path.clone(),
+            config.codex_home.clone(),
+        ));
 
         let turn_context = Session::make_turn_context(
             Some(Arc::clone(&auth_manager)),
@@ -6805,6 +7650,7 @@ enabled = ["collab-prompts-test"]
             None,
             "turn_id".to_string(),
             Arc::clone(&js_repl),
+            Arc::clone(&py_repl),
         );
 
         let session = Session {
@@ -6817,6 +7663,7 @@ enabled = ["collab-prompts-test"]
             active_turn: Mutex::new(None),
             services,
             js_repl,
+            py_repl,
             next_internal_sub_id: AtomicU64::new(0),
         };
 
@@ -6915,8 +7762,13 @@ enabled = ["collab-prompts-test"]
             otel_manager: otel_manager.clone(),
             models_manager: Arc::clone(&models_manager),
             tool_approvals: Mutex::new(ApprovalStore::default()),
+            remembered_approvals: RwLock::new(RepositoryApprovals::new(
+                &config.codex_home,
+                config.cwd.to_string_lossy().into_owned(),
+            )),
             skills_manager,
             file_watcher,
+            external_edits: Arc::new(ExternalEdits::default()),
             agent_control,
             network_proxy: None,
             state_db: None,
@@ -6939,6 +7791,10 @@ enabled = ["collab-prompts-test"]
             config.js_repl_node_path.clone(),
             config.codex_home.clone(),
         ));
+        let py_repl = Arc::new(PyReplHandle::with_python_path(
+            config.py_repl_python_path.clone(),
+            config.codex_home.clone(),
+        ));
 
         let turn_context = Arc::new(Session::make_turn_context(
             Some(Arc::clone(&auth_manager)),
@@ -6950,6 +7806,7 @@ enabled = ["collab-prompts-test"]
             None,
             "turn_id".to_string(),
             Arc::clone(&js_repl),
+            Arc::clone(&py_repl),
         ));
 
         let session = Arc::new(Session {
@@ -6962,6 +7819,7 @@ enabled = ["collab-prompts-test"]
             active_turn: Mutex::new(None),
             services,
             js_repl,
+            py_repl,
             next_internal_sub_id: AtomicU64::new(0),
         });
 
@@ -7507,6 +8365,7 @@ enabled = ["collab-prompts-test"]
         live_history.record_items(
             initial_context.iter(),
             reconstruction_turn.truncation_policy,
+            reconstruction_turn.tokenizer,
         );
 
         let user1 = ResponseItem::Message {
@@ -7521,6 +8380,7 @@ enabled = ["collab-prompts-test"]
         live_history.record_items(
             std::iter::once(&user1),
             reconstruction_turn.truncation_policy,
+            reconstruction_turn.tokenizer,
         );
         rollout_items.push(RolloutItem::ResponseItem(user1.clone()));
 
@@ -7536,6 +8396,7 @@ enabled = ["collab-prompts-test"]
         live_history.record_items(
             std::iter::once(&assistant1),
             reconstruction_turn.truncation_policy,
+            reconstruction_turn.tokenizer,
         );
         rollout_items.push(RolloutItem::ResponseItem(assistant1.clone()));
 
@@ -7544,8 +8405,12 @@ enabled = ["collab-prompts-test"]
             .clone()
             .for_prompt(&reconstruction_turn.model_info.input_modalities);
         let user_messages1 = collect_user_messages(&snapshot1);
-        let rebuilt1 =
-            compact::build_compacted_history(initial_context.clone(), &user_messages1, summary1);
+        let rebuilt1 = compact::build_compacted_history(
+            initial_context.clone(),
+            &user_messages1,
+            summary1,
+            reconstruction_turn.tokenizer,
+        );
         live_history.replace(rebuilt1);
         rollout_items.push(RolloutItem::Compacted(CompactedItem {
             message: summary1.to_string(),
@@ -7564,6 +8429,7 @@ enabled = ["collab-prompts-test"]
         live_history.record_items(
             std::iter::once(&user2),
             reconstruction_turn.truncation_policy,
+            reconstruction_turn.tokenizer,
         );
         rollout_items.push(RolloutItem::ResponseItem(user2.clone()));
 
@@ -7579,6 +8445,7 @@ enabled = ["collab-prompts-test"]
         live_history.record_items(
             std::iter::once(&assistant2),
             reconstruction_turn.truncation_policy,
+            reconstruction_turn.tokenizer,
         );
         rollout_items.push(RolloutItem::ResponseItem(assistant2.clone()));
 
@@ -7587,8 +8454,12 @@ enabled = ["collab-prompts-test"]
             .clone()
             .for_prompt(&reconstruction_turn.model_info.input_modalities);
         let user_messages2 = collect_user_messages(&snapshot2);
-        let rebuilt2 =
-            compact::build_compacted_history(initial_context.clone(), &user_messages2, summary2);
+        let rebuilt2 = compact::build_compacted_history(
+            initial_context.clone(),
+            &user_messages2,
+            summary2,
+            reconstruction_turn.tokenizer,
+        );
         live_history.replace(rebuilt2);
         rollout_items.push(RolloutItem::Compacted(CompactedItem {
             message: summary2.to_string(),
@@ -7607,6 +8478,7 @@ enabled = ["collab-prompts-test"]
         live_history.record_items(
             std::iter::once(&user3),
             reconstruction_turn.truncation_policy,
+            reconstruction_turn.tokenizer,
         );
         rollout_items.push(RolloutItem::ResponseItem(user3));
 
@@ -7622,6 +8494,7 @@ enabled = ["collab-prompts-test"]
         live_history.record_items(
             std::iter::once(&assistant3),
             reconstruction_turn.truncation_policy,
+            reconstruction_turn.tokenizer,
         );
         rollout_items.push(RolloutItem::ResponseItem(assistant3));
 
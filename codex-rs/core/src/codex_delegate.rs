@@ -54,6 +54,7 @@ pub(crate) async fn run_codex_thread_interactive(
         models_manager,
         Arc::clone(&parent_session.services.skills_manager),
         Arc::clone(&parent_session.services.file_watcher),
+        parent_session.services.external_edits.agent_activity(),
         initial_history.unwrap_or(InitialHistory::New),
         SessionSource::SubAgent(SubAgentSource::Review),
         parent_session.services.agent_control.clone(),
@@ -316,6 +317,7 @@ async fn handle_exec_approval(
         cwd,
         reason,
         proposed_execpolicy_amendment,
+        provenance,
         ..
     } = event;
     let approval_id = call_id.clone();
@@ -327,6 +329,7 @@ async fn handle_exec_approval(
         cwd,
         reason,
         proposed_execpolicy_amendment,
+        provenance,
     );
     let decision =
         await_approval_with_cancel(approval_fut, parent_session, &approval_id, cancel_token).await;
@@ -14,8 +14,8 @@ use crate::protocol::EventMsg;
 use crate::protocol::TurnContextItem;
 use crate::protocol::TurnStartedEvent;
 use crate::protocol::WarningEvent;
+use crate::tokenizer::Tokenizer;
 use crate::truncate::TruncationPolicy;
-use crate::truncate::approx_token_count;
 use crate::truncate::truncate_text;
 use crate::util::backoff;
 use codex_protocol::items::ContextCompactionItem;
@@ -79,6 +79,7 @@ async fn run_compact_task_inner(
     history.record_items(
         &[initial_input_for_turn.into()],
         turn_context.truncation_policy,
+        turn_context.tokenizer,
     );
 
     let mut truncated_count = 0usize;
@@ -86,7 +87,10 @@ async fn run_compact_task_inner(
     let max_retries = turn_context.provider.stream_max_retries();
     let mut retries = 0;
     let turn_metadata_header = turn_context.resolve_turn_metadata_header().await;
-    let mut client_session = sess.services.model_client.new_session();
+    let mut client_session = sess
+        .model_client_for_turn(&turn_context)
+        .await
+        .new_session();
     // Reuse one client session so turn-scoped state (sticky routing, websocket append tracking)
     // survives retries within this compact turn.
 
@@ -109,6 +113,7 @@ async fn run_compact_task_inner(
         developer_instructions: turn_context.developer_instructions.clone(),
         final_output_json_schema: turn_context.final_output_json_schema.clone(),
         truncation_policy: Some(turn_context.truncation_policy.into()),
+        model_route: turn_context.model_route.clone(),
     });
     sess.persist_rollout_items(&[rollout_item]).await;
 
@@ -193,7 +198,12 @@ async fn run_compact_task_inner(
     let user_messages = collect_user_messages(history_items);
 
     let initial_context = sess.build_initial_context(turn_context.as_ref()).await;
-    let mut new_history = build_compacted_history(initial_context, &user_messages, &summary_text);
+    let mut new_history = build_compacted_history(
+        initial_context,
+        &user_messages,
+        &summary_text,
+        turn_context.tokenizer,
+    );
     let ghost_snapshots: Vec<ResponseItem> = history_items
         .iter()
         .filter(|item| matches!(item, ResponseItem::GhostSnapshot { .. }))
@@ -310,12 +320,14 @@ pub(crate) fn build_compacted_history(
     initial_context: Vec<ResponseItem>,
     user_messages: &[String],
     summary_text: &str,
+    tokenizer: &dyn Tokenizer,
 ) -> Vec<ResponseItem> {
     build_compacted_history_with_limit(
         initial_context,
         user_messages,
         summary_text,
         COMPACT_USER_MESSAGE_MAX_TOKENS,
+        tokenizer,
     )
 }
 
@@ -324,6 +336,7 @@ fn build_compacted_history_with_limit(
     user_messages: &[String],
     summary_text: &str,
     max_tokens: usize,
+    tokenizer: &dyn Tokenizer,
 ) -> Vec<ResponseItem> {
     let mut selected_messages: Vec<String> = Vec::new();
     if max_tokens > 0 {
@@ -332,12 +345,13 @@ fn build_compacted_history_with_limit(
             if remaining == 0 {
                 break;
             }
-            let tokens = approx_token_count(message);
+            let tokens = tokenizer.count_tokens(message);
             if tokens <= remaining {
                 selected_messages.push(message.clone());
                 remaining = remaining.saturating_sub(tokens);
             } else {
-                let truncated = truncate_text(message, TruncationPolicy::Tokens(remaining));
+                let truncated =
+                    truncate_text(message, TruncationPolicy::Tokens(remaining), tokenizer);
                 selected_messages.push(truncated);
                 break;
             }
@@ -425,6 +439,7 @@ async fn drain_to_completed(
 mod tests {
 
     use super::*;
+    use crate::tokenizer::HeuristicTokenizer;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -539,6 +554,7 @@ do things
             std::slice::from_ref(&big),
             "SUMMARY",
             max_tokens,
+            &HeuristicTokenizer,
         );
         assert_eq!(history.len(), 2);
 
@@ -576,7 +592,12 @@ do things
         let user_messages = vec!["first user message".to_string()];
         let summary_text = "summary text";
 
-        let history = build_compacted_history(initial_context, &user_messages, summary_text);
+        let history = build_compacted_history(
+            initial_context,
+            &user_messages,
+            summary_text,
+            &HeuristicTokenizer,
+        );
         assert!(
             !history.is_empty(),
             "expected compacted history to include summary"
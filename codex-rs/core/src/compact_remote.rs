@@ -97,15 +97,17 @@ async fn run_remote_compact_task_inner_impl(
     };
 
     let mut new_history = sess
-        .services
-        .model_client
+        .model_client_for_turn(&turn_context)
+        .await
         .compact_conversation_history(
             &prompt,
             &turn_context.model_info,
             &turn_context.otel_manager,
         )
         .or_else(|err| async {
-            let total_usage_breakdown = sess.get_total_token_usage_breakdown().await;
+            let total_usage_breakdown = sess
+                .get_total_token_usage_breakdown(turn_context.tokenizer)
+                .await;
             let compact_request_log_data =
                 build_compact_request_log_data(&prompt.input, &prompt.base_instructions.text);
             log_remote_compact_failure(
@@ -191,7 +193,7 @@ fn trim_function_call_history_to_fit_context_window(
     };
 
     while history
-        .estimate_token_count_with_base_instructions(base_instructions)
+        .estimate_token_count_with_base_instructions(base_instructions, turn_context.tokenizer)
         .is_some_and(|estimated_tokens| estimated_tokens > context_window)
     {
         let Some(last_item) = history.raw_items().last() else {
@@ -0,0 +1,263 @@
+//! Tools declared in config and served by a local command or an HTTP
+//! endpoint rather than by an app-server client.
+//!
+//! Definitions come from `tools/*.toml` next to every enabled config layer
+//! (`$CODEX_HOME/tools`, a trusted project's `.codex/tools`, ...) and from
+//! `[dynamic_tools.<name>]` tables, which take precedence. Project tools run
+//! commands from the repository, so they are only loaded for trusted
+//! projects.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_app_server_protocol::ConfigLayerSource;
+use serde_json::Value as JsonValue;
+use serde_json::json;
+
+use crate::config::types::ConfigDynamicTool;
+use crate::config::types::DynamicToolBackend;
+use crate::config::types::DynamicToolToml;
+use crate::config_loader::ConfigLayerStack;
+use crate::config_loader::ConfigLayerStackOrdering;
+
+pub(crate) const TOOLS_DIR_NAME: &str = "tools";
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+/// Limit the Responses API places on function names.
+const MAX_TOOL_NAME_LEN: usize = 64;
+
+/// Invalid definitions are skipped and reported through `startup_warnings`.
+pub(crate) fn load_dynamic_tools(
+    config_layer_stack: &ConfigLayerStack,
+    configured: HashMap<String, DynamicToolToml>,
+    project_trusted: bool,
+    startup_warnings: &mut Vec<String>,
+) -> Vec<ConfigDynamicTool> {
+    let mut declared = BTreeMap::new();
+    for layer in
+        config_layer_stack.get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst, false)
+    {
+        if matches!(layer.name, ConfigLayerSource::Project { .. }) && !project_trusted {
+            continue;
+        }
+        let Some(config_folder) = layer.config_folder() else {
+            continue;
+        };
+        let tools_dir = config_folder.as_path().join(TOOLS_DIR_NAME);
+        for (name, path, tool) in read_tools_dir(&tools_dir) {
+            match tool {
+                Ok(tool) => {
+                    declared.insert(name, tool);
+                }
+                Err(err) => startup_warnings.push(format!(
+                    "Ignoring tool definition {}: {err}",
+                    path.display()
+                )),
+            }
+        }
+    }
+    declared.extend(configured);
+
+    declared
+        .into_iter()
+        .filter_map(|(name, tool)| match resolve_dynamic_tool(&name, tool) {
+            Ok(tool) => Some(tool),
+            Err(err) => {
+                startup_warnings.push(format!("Ignoring tool `{name}`: {err}"));
+                None
+            }
+        })
+        .collect()
+}
+
+fn read_tools_dir(dir: &Path) -> Vec<(String, PathBuf, Result<DynamicToolToml, String>)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_str()?.to_string();
+            let tool = std::fs::read_to_string(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|contents| {
+                    toml::from_str::<DynamicToolToml>(&contents).map_err(|err| err.to_string())
+                });
+            Some((name, path, tool))
+        })
+        .collect()
+}
+
+pub(crate) fn resolve_dynamic_tool(
+    name: &str,
+    tool: DynamicToolToml,
+) -> Result<ConfigDynamicTool, String> {
+    if name.is_empty()
+        || name.len() > MAX_TOOL_NAME_LEN
+        || !name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-')
+    {
+        return Err(format!(
+            "names must be 1-{MAX_TOOL_NAME_LEN} characters of A-Z, a-z, 0-9, _ or -"
+        ));
+    }
+
+    let DynamicToolToml {
+        description,
+        command,
+        url,
+        http_headers,
+        input_schema,
+        timeout_ms,
+        approval,
+    } = tool;
+    let backend = match (command, url) {
+        (Some(command), None) => {
+            if command.first().is_none_or(String::is_empty) {
+                return Err("`command` must name an executable".to_string());
+            }
+            if http_headers.is_some() {
+                return Err("`http_headers` is only valid with `url`".to_string());
+            }
+            DynamicToolBackend::Command { command }
+        }
+        (None, Some(url)) => DynamicToolBackend::Http {
+            url,
+            http_headers: http_headers.unwrap_or_default(),
+        },
+        (Some(_), Some(_)) | (None, None) => {
+            return Err("exactly one of `command` and `url` must be set".to_string());
+        }
+    };
+
+    let input_schema =
+        input_schema.unwrap_or_else(|| json!({ "type": "object", "properties": {} }));
+    if !matches!(input_schema, JsonValue::Object(_)) {
+        return Err("`input_schema` must be a JSON Schema object".to_string());
+    }
+
+    Ok(ConfigDynamicTool {
+        name: name.to_string(),
+        description,
+        backend,
+        input_schema,
+        timeout: timeout_ms.map_or(DEFAULT_TIMEOUT, Duration::from_millis),
+        approval: approval.unwrap_or_default(),
+        deferred: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::types::DynamicToolApproval;
+    use crate::config_loader::ConfigLayerEntry;
+    use crate::config_loader::ConfigRequirements;
+    use crate::config_loader::ConfigRequirementsToml;
+    use codex_utils_absolute_path::AbsolutePathBuf;
+    use pretty_assertions::assert_eq;
+    use toml::Value as TomlValue;
+
+    fn command_tool(description: &str, command: &str) -> DynamicToolToml {
+        DynamicToolToml {
+            description: description.to_string(),
+            command: Some(vec![command.to_string()]),
+            url: None,
+            http_headers: None,
+            input_schema: None,
+            timeout_ms: None,
+            approval: None,
+        }
+    }
+
+    #[test]
+    fn config_tables_override_tool_files() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let project = tmp.path().join("repo/.codex");
+        std::fs::create_dir_all(project.join(TOOLS_DIR_NAME))?;
+        std::fs::write(
+            project.join(TOOLS_DIR_NAME).join("lint.toml"),
+            "description = \"Lint files\"\ncommand = [\"./lint\"]\napproval = \"never\"\n",
+        )?;
+        std::fs::write(
+            project.join(TOOLS_DIR_NAME).join("deploy.toml"),
+            "description = \"Deploy\"\n",
+        )?;
+        std::fs::write(
+            project.join(TOOLS_DIR_NAME).join("status.toml"),
+            "description = \"From file\"\nurl = \"http://localhost/status\"\n",
+        )?;
+        let stack = ConfigLayerStack::new(
+            vec![ConfigLayerEntry::new(
+                ConfigLayerSource::Project {
+                    dot_codex_folder: AbsolutePathBuf::from_absolute_path(&project)?,
+                },
+                TomlValue::Table(toml::map::Map::new()),
+            )],
+            ConfigRequirements::default(),
+            ConfigRequirementsToml::default(),
+        )?;
+
+        let mut warnings = Vec::new();
+        let tools = load_dynamic_tools(
+            &stack,
+            HashMap::from([("status".to_string(), command_tool("From config", "status"))]),
+            true,
+            &mut warnings,
+        );
+
+        assert_eq!(
+            tools,
+            vec![
+                ConfigDynamicTool {
+                    name: "lint".to_string(),
+                    description: "Lint files".to_string(),
+                    backend: DynamicToolBackend::Command {
+                        command: vec!["./lint".to_string()],
+                    },
+                    input_schema: json!({ "type": "object", "properties": {} }),
+                    timeout: DEFAULT_TIMEOUT,
+                    approval: DynamicToolApproval::Never,
+                    deferred: false,
+                },
+                ConfigDynamicTool {
+                    name: "status".to_string(),
+                    description: "From config".to_string(),
+                    backend: DynamicToolBackend::Command {
+                        command: vec!["status".to_string()],
+                    },
+                    input_schema: json!({ "type": "object", "properties": {} }),
+                    timeout: DEFAULT_TIMEOUT,
+                    approval: DynamicToolApproval::Auto,
+                    deferred: false,
+                },
+            ]
+        );
+        assert_eq!(
+            warnings,
+            vec!["Ignoring tool `deploy`: exactly one of `command` and `url` must be set"]
+        );
+
+        let untrusted = load_dynamic_tools(&stack, HashMap::new(), false, &mut Vec::new());
+        assert_eq!(untrusted, Vec::new());
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_names_the_api_would_refuse() {
+        assert_eq!(
+            resolve_dynamic_tool("run tests", command_tool("Run", "make")),
+            Err("names must be 1-64 characters of A-Z, a-z, 0-9, _ or -".to_string())
+        );
+    }
+}
@@ -1,22 +1,37 @@
 use crate::auth::AuthCredentialsStoreMode;
+use crate::config::dynamic_tools::load_dynamic_tools;
 use crate::config::edit::ConfigEdit;
 use crate::config::edit::ConfigEditsBuilder;
+use crate::config::openapi_tools::load_openapi_tools;
+use crate::config::secret_refs::SecretReferenceResolver;
 use crate::config::types::AppsConfigToml;
+use crate::config::types::ConfigDynamicTool;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
+use crate::config::types::DynamicToolToml;
+use crate::config::types::FailoverConfig;
+use crate::config::types::FailoverTarget;
+use crate::config::types::FailoverToml;
+use crate::config::types::FailoverTrigger;
 use crate::config::types::History;
+use crate::config::types::LogsConfigToml;
 use crate::config::types::McpServerConfig;
 use crate::config::types::McpServerDisabledReason;
 use crate::config::types::McpServerTransportConfig;
+use crate::config::types::ModelRoute;
 use crate::config::types::Notice;
 use crate::config::types::NotificationMethod;
 use crate::config::types::Notifications;
+use crate::config::types::OpenApiSourceToml;
 use crate::config::types::OtelConfig;
 use crate::config::types::OtelConfigToml;
 use crate::config::types::OtelExporterKind;
+use crate::config::types::RememberedApprovals;
 use crate::config::types::SandboxWorkspaceWrite;
+use crate::config::types::SessionBudget;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyToml;
 use crate::config::types::SkillsConfig;
+use crate::config::types::TokenBudget;
 use crate::config::types::Tui;
 use crate::config::types::UriBasedFileOpener;
 use crate::config::types::WindowsSandboxModeToml;
@@ -63,6 +78,7 @@ use codex_protocol::config_types::WebSearchMode;
 use codex_protocol::config_types::WindowsSandboxLevel;
 use codex_protocol::openai_models::ReasoningEffort;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
+use codex_state::LogRetention;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use codex_utils_absolute_path::AbsolutePathBufGuard;
 use schemars::JsonSchema;
@@ -74,6 +90,7 @@ use std::collections::HashMap;
 use std::io::ErrorKind;
 use std::path::Path;
 use std::path::PathBuf;
+use std::time::Duration;
 #[cfg(test)]
 use tempfile::tempdir;
 
@@ -81,10 +98,13 @@ use crate::config::profile::ConfigProfile;
 use toml::Value as TomlValue;
 use toml_edit::DocumentMut;
 
+mod dynamic_tools;
 pub mod edit;
 mod network_proxy_spec;
+mod openapi_tools;
 pub mod profile;
 pub mod schema;
+mod secret_refs;
 pub mod service;
 pub mod types;
 pub use codex_config::Constrained;
@@ -238,6 +258,12 @@ pub struct Config {
     /// Info needed to make an API request to the model.
     pub model_provider: ModelProviderInfo,
 
+    /// Fallback providers declared by the active profile.
+    pub failover: Option<FailoverConfig>,
+
+    /// Rules that pick the model and reasoning effort per turn by task kind.
+    pub model_routes: Vec<ModelRoute>,
+
     /// Optionally specify the personality of the model
     pub personality: Option<Personality>,
 
@@ -356,6 +382,11 @@ pub struct Config {
     /// Definition for MCP servers that Codex can reach out to for tool calls.
     pub mcp_servers: Constrained<HashMap<String, McpServerConfig>>,
 
+    /// Tools served by a local command or an HTTP endpoint, from
+    /// `[dynamic_tools]`, `tools/*.toml` next to each config layer and the
+    /// operations of `[openapi]` sources.
+    pub dynamic_tools: Vec<ConfigDynamicTool>,
+
     /// Preferred store for MCP OAuth credentials.
     /// keyring: Use an OS-specific keyring service.
     ///          Credentials stored in the keyring will only be readable by Codex unless the user explicitly grants access via OS-level keyring access.
@@ -392,12 +423,33 @@ pub struct Config {
     /// Directory where Codex writes log files (defaults to `$CODEX_HOME/log`).
     pub log_dir: PathBuf,
 
+    /// Retention applied to the log store in the state database.
+    pub log_retention: LogRetention,
+
     /// Settings that govern if and what will be written to `~/.codex/history.jsonl`.
     pub history: History,
 
+    /// Settings for approvals remembered per repository.
+    pub remembered_approvals: RememberedApprovals,
+
     /// When true, session is not persisted on disk. Default to `false`
     pub ephemeral: bool,
 
+    /// Record the raw model response streams of each session under
+    /// `$CODEX_HOME/recordings/<thread id>`.
+    pub record_model_streams: bool,
+
+    /// Recording that sub-agent sessions write into, set by the session that
+    /// spawned them so a whole session tree records into one directory.
+    pub model_stream_recording_dir: Option<PathBuf>,
+
+    /// Serve model responses from this recording instead of the provider
+    /// (`codex exec --replay`).
+    pub model_stream_replay: Option<PathBuf>,
+
+    /// Token and wall-clock ceilings enforced for each session.
+    pub session_budget: SessionBudget,
+
     /// Optional URI-based file opener. If set, citations to files in the model
     /// output will be hyperlinked using the specified URI scheme.
     pub file_opener: UriBasedFileOpener,
@@ -413,6 +465,9 @@ pub struct Config {
     /// Optional absolute path to the Node runtime used by `js_repl`.
     pub js_repl_node_path: Option<PathBuf>,
 
+    /// Optional absolute path to the Python interpreter used by `py_repl`.
+    pub py_repl_python_path: Option<PathBuf>,
+
     /// Value to use for `reasoning.effort` when making a request using the
     /// Responses API.
     pub model_reasoning_effort: Option<ReasoningEffort>,
@@ -970,6 +1025,10 @@ pub struct ConfigToml {
     /// Token usage threshold triggering auto-compaction of conversation history.
     pub model_auto_compact_token_limit: Option<i64>,
 
+    /// Rules that pick the model and reasoning effort per turn by task kind.
+    /// The first matching `[[model_routes]]` entry wins.
+    pub model_routes: Option<Vec<ModelRoute>>,
+
     /// Default approval policy for executing commands.
     pub approval_policy: Option<AskForApproval>,
 
@@ -1035,6 +1094,14 @@ pub struct ConfigToml {
     #[schemars(schema_with = "crate::config::schema::mcp_servers_schema")]
     pub mcp_servers: HashMap<String, McpServerConfig>,
 
+    /// Tools backed by a local command or an HTTP endpoint, keyed by tool
+    /// name. Entries override `tools/<name>.toml` files.
+    pub dynamic_tools: Option<HashMap<String, DynamicToolToml>>,
+
+    /// OpenAPI 3 documents whose operations are exposed as tools, keyed by a
+    /// source name that prefixes the tool names.
+    pub openapi: Option<HashMap<String, OpenApiSourceToml>>,
+
     /// Preferred backend for storing MCP OAuth credentials.
     /// keyring: Use an OS-specific keyring service.
     ///          https://github.com/openai/codex/blob/main/codex-rs/rmcp-client/src/oauth.rs#L2
@@ -1063,6 +1130,17 @@ pub struct ConfigToml {
     /// Optional absolute path to the Node runtime used by `js_repl`.
     pub js_repl_node_path: Option<AbsolutePathBuf>,
 
+    /// Optional absolute path to the Python interpreter used by `py_repl`.
+    pub py_repl_python_path: Option<AbsolutePathBuf>,
+
+    /// Record the raw model response streams of each session under
+    /// `$CODEX_HOME/recordings/<thread id>` so they can be replayed with
+    /// `codex exec --replay`.
+    pub record_model_streams: Option<bool>,
+
+    /// Token and wall-clock ceilings enforced for each session.
+    pub session_budget: Option<SessionBudget>,
+
     /// Profile to use from the `profiles` map.
     pub profile: Option<String>,
 
@@ -1074,10 +1152,16 @@ pub struct ConfigToml {
     #[serde(default)]
     pub history: Option<History>,
 
+    /// Settings for approvals remembered per repository.
+    pub remembered_approvals: Option<RememberedApprovals>,
+
     /// Directory where Codex writes log files, for example `codex-tui.log`.
     /// Defaults to `$CODEX_HOME/log`.
     pub log_dir: Option<AbsolutePathBuf>,
 
+    /// Retention for the log store in the state database (`codex logs`).
+    pub logs: Option<LogsConfigToml>,
+
     /// Optional URI-based file opener. If set, citations to files in the model
     /// output will be hyperlinked using the specified URI scheme.
     pub file_opener: Option<UriBasedFileOpener>,
@@ -1215,6 +1299,9 @@ impl From<ConfigToml> for UserSavedConfig {
 #[schemars(deny_unknown_fields)]
 pub struct ProjectConfig {
     pub trust_level: Option<TrustLevel>,
+
+    /// Limits on the tokens turns in this project may use.
+    pub token_budget: Option<TokenBudget>,
 }
 
 impl ProjectConfig {
@@ -1408,6 +1495,7 @@ pub struct ConfigOverrides {
     pub config_profile: Option<String>,
     pub codex_linux_sandbox_exe: Option<PathBuf>,
     pub js_repl_node_path: Option<PathBuf>,
+    pub py_repl_python_path: Option<PathBuf>,
     pub base_instructions: Option<String>,
     pub developer_instructions: Option<String>,
     pub personality: Option<Personality>,
@@ -1416,6 +1504,10 @@ pub struct ConfigOverrides {
     pub show_raw_agent_reasoning: Option<bool>,
     pub tools_web_search_request: Option<bool>,
     pub ephemeral: Option<bool>,
+    /// Recording to serve model responses from instead of the provider.
+    pub model_stream_replay: Option<PathBuf>,
+    /// Session budget limits that take precedence over `[session_budget]`.
+    pub session_budget: SessionBudget,
     /// Additional directories that should be treated as writable roots for this session.
     pub additional_writable_roots: Vec<PathBuf>,
 }
@@ -1448,6 +1540,69 @@ pub fn resolve_oss_provider(
     }
 }
 
+/// Map `[logs]` onto the state database's retention policy. A limit of `0`
+/// disables that limit.
+fn resolve_log_retention(logs: LogsConfigToml) -> LogRetention {
+    let max_age_days = logs
+        .retention_days
+        .unwrap_or(LogRetention::DEFAULT_MAX_AGE_DAYS);
+    let max_rows = logs.max_rows.unwrap_or(LogRetention::DEFAULT_MAX_ROWS);
+    LogRetention {
+        max_age_days: (max_age_days > 0).then_some(max_age_days),
+        max_rows: (max_rows > 0).then_some(max_rows),
+    }
+}
+
+/// Default time a failed failover entry is skipped before it is tried again.
+const DEFAULT_FAILOVER_COOLDOWN: Duration = Duration::from_secs(300);
+
+/// Resolve a profile's failover chain against the known model providers.
+fn resolve_failover(
+    failover: FailoverToml,
+    model_providers: &HashMap<String, ModelProviderInfo>,
+) -> std::io::Result<FailoverConfig> {
+    let cooldown = failover
+        .cooldown_secs
+        .map_or(DEFAULT_FAILOVER_COOLDOWN, Duration::from_secs);
+    let chain = failover
+        .chain
+        .into_iter()
+        .map(|target| {
+            let provider = model_providers
+                .get(&target.model_provider)
+                .ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::NotFound,
+                        format!(
+                            "Model provider `{}` in failover chain not found",
+                            target.model_provider
+                        ),
+                    )
+                })?
+                .clone();
+            Ok(FailoverTarget {
+                provider_id: target.model_provider,
+                provider,
+                model: target.model,
+                cooldown: target.cooldown_secs.map_or(cooldown, Duration::from_secs),
+            })
+        })
+        .collect::<std::io::Result<Vec<_>>>()?;
+    let triggers = failover.triggers.unwrap_or_else(|| {
+        vec![
+            FailoverTrigger::RateLimit,
+            FailoverTrigger::ServerError,
+            FailoverTrigger::StreamIdleTimeout,
+            FailoverTrigger::ContextWindowExceeded,
+        ]
+    });
+    Ok(FailoverConfig {
+        chain,
+        triggers,
+        cooldown,
+    })
+}
+
 /// Resolve the web search mode from explicit config and feature flags.
 fn resolve_web_search_mode(
     config_toml: &ConfigToml,
@@ -1535,6 +1690,7 @@ impl Config {
             config_profile: config_profile_key,
             codex_linux_sandbox_exe,
             js_repl_node_path: js_repl_node_path_override,
+            py_repl_python_path: py_repl_python_path_override,
             base_instructions,
             developer_instructions,
             personality,
@@ -1543,6 +1699,8 @@ impl Config {
             show_raw_agent_reasoning,
             tools_web_search_request: override_tools_web_search_request,
             ephemeral,
+            model_stream_replay,
+            session_budget: session_budget_override,
             additional_writable_roots,
         } = overrides;
 
@@ -1595,7 +1753,10 @@ impl Config {
             .collect::<Result<Vec<_>, _>>()?;
         let active_project = cfg
             .get_active_project(&resolved_cwd)
-            .unwrap_or(ProjectConfig { trust_level: None });
+            .unwrap_or(ProjectConfig {
+                trust_level: None,
+                token_budget: None,
+            });
         let sandbox_mode_was_explicit = sandbox_mode.is_some()
             || config_profile.sandbox_mode.is_some()
             || cfg.sandbox_mode.is_some();
@@ -1655,6 +1816,9 @@ impl Config {
         for (key, provider) in cfg.model_providers.into_iter() {
             model_providers.entry(key).or_insert(provider);
         }
+        let secret_resolver = SecretReferenceResolver::new(&codex_home, &resolved_cwd);
+        let mut unresolved_providers =
+            secret_resolver.resolve_model_providers(&mut model_providers)?;
 
         let model_provider_id = model_provider
             .or(config_profile.model_provider)
@@ -1671,8 +1835,29 @@ impl Config {
                 std::io::Error::new(std::io::ErrorKind::NotFound, message)
             })?
             .clone();
+        let failover = config_profile
+            .failover
+            .map(|failover| resolve_failover(failover, &model_providers))
+            .transpose()?;
+        // Only the providers this session can use need their secrets.
+        let used_provider_ids = std::iter::once(&model_provider_id).chain(
+            failover
+                .iter()
+                .flat_map(|failover| failover.chain.iter().map(|target| &target.provider_id)),
+        );
+        for provider_id in used_provider_ids {
+            if let Some(missing) = unresolved_providers.remove(provider_id) {
+                return Err(missing.into());
+            }
+        }
+        for (provider_id, missing) in unresolved_providers {
+            tracing::warn!("model provider `{provider_id}` is unusable: {missing}");
+        }
 
-        let shell_environment_policy = cfg.shell_environment_policy.into();
+        let mut shell_environment_policy: ShellEnvironmentPolicy =
+            cfg.shell_environment_policy.into();
+        secret_resolver
+            .resolve_shell_environment(&mut shell_environment_policy, &mut startup_warnings)?;
 
         let history = cfg.history.unwrap_or_default();
 
@@ -1771,6 +1956,9 @@ impl Config {
         let js_repl_node_path = js_repl_node_path_override
             .or(config_profile.js_repl_node_path.map(Into::into))
             .or(cfg.js_repl_node_path.map(Into::into));
+        let py_repl_python_path = py_repl_python_path_override
+            .or(config_profile.py_repl_python_path.map(Into::into))
+            .or(cfg.py_repl_python_path.map(Into::into));
 
         let review_model = override_review_model.or(cfg.review_model);
 
@@ -1834,9 +2022,22 @@ impl Config {
             &mut startup_warnings,
         )?;
 
-        let mcp_servers = constrain_mcp_servers(cfg.mcp_servers.clone(), mcp_servers.as_ref())
+        let mut configured_mcp_servers = cfg.mcp_servers.clone();
+        secret_resolver.resolve_mcp_servers(&mut configured_mcp_servers, &mut startup_warnings)?;
+        let mcp_servers = constrain_mcp_servers(configured_mcp_servers, mcp_servers.as_ref())
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{e}")))?;
 
+        let mut dynamic_tools = load_dynamic_tools(
+            &config_layer_stack,
+            cfg.dynamic_tools.clone().unwrap_or_default(),
+            active_project.is_trusted(),
+            &mut startup_warnings,
+        );
+        secret_resolver.resolve_dynamic_tools(&mut dynamic_tools, &mut startup_warnings)?;
+        let mut openapi_sources = cfg.openapi.clone().unwrap_or_default();
+        secret_resolver.resolve_openapi_sources(&mut openapi_sources, &mut startup_warnings)?;
+        dynamic_tools.extend(load_openapi_tools(&openapi_sources, &mut startup_warnings));
+
         let network = match network_requirements {
             Some(Sourced { value, source }) => {
                 let network = NetworkProxySpec::from_constraints(&config_layer_stack, value)
@@ -1851,6 +2052,16 @@ impl Config {
             None => None,
         };
 
+        let configured_session_budget = cfg.session_budget.unwrap_or_default();
+        let session_budget = SessionBudget {
+            max_tokens: session_budget_override
+                .max_tokens
+                .or(configured_session_budget.max_tokens),
+            timeout_secs: session_budget_override
+                .timeout_secs
+                .or(configured_session_budget.timeout_secs),
+        };
+
         let mut config = Self {
             model,
             review_model,
@@ -1858,6 +2069,11 @@ impl Config {
             model_auto_compact_token_limit: cfg.model_auto_compact_token_limit,
             model_provider_id,
             model_provider,
+            failover,
+            model_routes: config_profile
+                .model_routes
+                .or(cfg.model_routes)
+                .unwrap_or_default(),
             cwd: resolved_cwd,
             startup_warnings,
             approval_policy: constrained_approval_policy.value,
@@ -1880,6 +2096,7 @@ impl Config {
             // is important in code to differentiate the mode from the store implementation.
             cli_auth_credentials_store_mode: cfg.cli_auth_credentials_store.unwrap_or_default(),
             mcp_servers,
+            dynamic_tools,
             // The config.toml omits "_mode" because it's a config file. However, "_mode"
             // is important in code to differentiate the mode from the store implementation.
             mcp_oauth_credentials_store_mode: cfg.mcp_oauth_credentials_store.unwrap_or_default(),
@@ -1905,10 +2122,17 @@ impl Config {
             log_dir,
             config_layer_stack,
             history,
+            remembered_approvals: cfg.remembered_approvals.unwrap_or_default(),
+            log_retention: resolve_log_retention(cfg.logs.unwrap_or_default()),
             ephemeral: ephemeral.unwrap_or_default(),
+            record_model_streams: cfg.record_model_streams.unwrap_or(false),
+            model_stream_recording_dir: None,
+            model_stream_replay,
+            session_budget,
             file_opener: cfg.file_opener.unwrap_or(UriBasedFileOpener::VsCode),
             codex_linux_sandbox_exe,
             js_repl_node_path,
+            py_repl_python_path,
 
             hide_agent_reasoning: cfg.hide_agent_reasoning.unwrap_or(false),
             show_raw_agent_reasoning: cfg
@@ -2184,6 +2408,7 @@ mod tests {
     use crate::config::types::FeedbackConfigToml;
     use crate::config::types::HistoryPersistence;
     use crate::config::types::McpServerTransportConfig;
+    use crate::config::types::ModelRouteTask;
     use crate::config::types::NotificationMethod;
     use crate::config::types::Notifications;
     use crate::config_loader::RequirementSource;
@@ -2795,6 +3020,75 @@ trust_level = "trusted"
         Ok(())
     }
 
+    #[test]
+    fn profile_model_routes_replace_base_routes() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg: ConfigToml = toml::from_str(
+            r#"
+profile = "cheap"
+
+[[model_routes]]
+task = "review"
+model = "gpt-strong"
+
+[[profiles.cheap.model_routes]]
+name = "compact-mini"
+task = "compact"
+model = "gpt-mini"
+model_reasoning_effort = "low"
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.model_routes,
+            vec![ModelRoute {
+                name: Some("compact-mini".to_string()),
+                task: Some(ModelRouteTask::Compact),
+                model: Some("gpt-mini".to_string()),
+                model_reasoning_effort: Some(ReasoningEffort::Low),
+                ..Default::default()
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn logs_retention_zero_disables_limits() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg: ConfigToml = toml::from_str(
+            r#"
+[logs]
+retention_days = 0
+max_rows = 5000
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.log_retention,
+            LogRetention {
+                max_age_days: None,
+                max_rows: Some(5000),
+            }
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn project_profile_overrides_user_profile() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
@@ -4225,6 +4519,8 @@ model_verbosity = "high"
                 model_auto_compact_token_limit: None,
                 model_provider_id: "openai".to_string(),
                 model_provider: fixture.openai_provider.clone(),
+                failover: None,
+                model_routes: Vec::new(),
                 approval_policy: Constrained::allow_any(AskForApproval::Never),
                 sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
                 enforce_residency: Constrained::allow_any(None),
@@ -4236,6 +4532,7 @@ model_verbosity = "high"
                 cwd: fixture.cwd(),
                 cli_auth_credentials_store_mode: Default::default(),
                 mcp_servers: Constrained::allow_any(HashMap::new()),
+                dynamic_tools: Vec::new(),
                 mcp_oauth_credentials_store_mode: Default::default(),
                 mcp_oauth_callback_port: None,
                 model_providers: fixture.model_provider_map.clone(),
@@ -4248,10 +4545,17 @@ model_verbosity = "high"
                 config_layer_stack: Default::default(),
                 startup_warnings: Vec::new(),
                 history: History::default(),
+                remembered_approvals: RememberedApprovals::default(),
+                log_retention: LogRetention::default(),
                 ephemeral: false,
+                record_model_streams: false,
+                model_stream_recording_dir: None,
+                model_stream_replay: None,
+                session_budget: SessionBudget::default(),
                 file_opener: UriBasedFileOpener::VsCode,
                 codex_linux_sandbox_exe: None,
                 js_repl_node_path: None,
+                py_repl_python_path: None,
                 hide_agent_reasoning: false,
                 show_raw_agent_reasoning: false,
                 model_reasoning_effort: Some(ReasoningEffort::High),
@@ -4276,7 +4580,10 @@ model_verbosity = "high"
                 features: Features::with_defaults(),
                 suppress_unstable_features_warning: false,
                 active_profile: Some("o3".to_string()),
-                active_project: ProjectConfig { trust_level: None },
+                active_project: ProjectConfig {
+                    trust_level: None,
+                    token_budget: None,
+                },
                 windows_sandbox_mode: None,
                 windows_wsl_setup_acknowledged: false,
                 notices: Default::default(),
@@ -4336,6 +4643,8 @@ model_verbosity = "high"
             model_auto_compact_token_limit: None,
             model_provider_id: "openai-custom".to_string(),
             model_provider: fixture.openai_custom_provider.clone(),
+            failover: None,
+            model_routes: Vec::new(),
             approval_policy: Constrained::allow_any(AskForApproval::UnlessTrusted),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
             enforce_residency: Constrained::allow_any(None),
@@ -4347,6 +4656,7 @@ model_verbosity = "high"
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: Constrained::allow_any(HashMap::new()),
+            dynamic_tools: Vec::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
             mcp_oauth_callback_port: None,
             model_providers: fixture.model_provider_map.clone(),
@@ -4359,10 +4669,17 @@ model_verbosity = "high"
             config_layer_stack: Default::default(),
             startup_warnings: Vec::new(),
             history: History::default(),
+            remembered_approvals: RememberedApprovals::default(),
+            log_retention: LogRetention::default(),
             ephemeral: false,
+            record_model_streams: false,
+            model_stream_recording_dir: None,
+            model_stream_replay: None,
+            session_budget: SessionBudget::default(),
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
             js_repl_node_path: None,
+            py_repl_python_path: None,
             hide_agent_reasoning: false,
             show_raw_agent_reasoning: false,
             model_reasoning_effort: None,
@@ -4387,7 +4704,10 @@ model_verbosity = "high"
             features: Features::with_defaults(),
             suppress_unstable_features_warning: false,
             active_profile: Some("gpt3".to_string()),
-            active_project: ProjectConfig { trust_level: None },
+            active_project: ProjectConfig {
+                trust_level: None,
+                token_budget: None,
+            },
             windows_sandbox_mode: None,
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
@@ -4445,6 +4765,8 @@ model_verbosity = "high"
             model_auto_compact_token_limit: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
+            failover: None,
+            model_routes: Vec::new(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
             enforce_residency: Constrained::allow_any(None),
@@ -4456,6 +4778,7 @@ model_verbosity = "high"
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: Constrained::allow_any(HashMap::new()),
+            dynamic_tools: Vec::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
             mcp_oauth_callback_port: None,
             model_providers: fixture.model_provider_map.clone(),
@@ -4468,10 +4791,17 @@ model_verbosity = "high"
             config_layer_stack: Default::default(),
             startup_warnings: Vec::new(),
             history: History::default(),
+            remembered_approvals: RememberedApprovals::default(),
+            log_retention: LogRetention::default(),
             ephemeral: false,
+            record_model_streams: false,
+            model_stream_recording_dir: None,
+            model_stream_replay: None,
+            session_budget: SessionBudget::default(),
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
             js_repl_node_path: None,
+            py_repl_python_path: None,
             hide_agent_reasoning: false,
             show_raw_agent_reasoning: false,
             model_reasoning_effort: None,
@@ -4496,7 +4826,10 @@ model_verbosity = "high"
             features: Features::with_defaults(),
             suppress_unstable_features_warning: false,
             active_profile: Some("zdr".to_string()),
-            active_project: ProjectConfig { trust_level: None },
+            active_project: ProjectConfig {
+                trust_level: None,
+                token_budget: None,
+            },
             windows_sandbox_mode: None,
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
@@ -4540,6 +4873,8 @@ model_verbosity = "high"
             model_auto_compact_token_limit: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
+            failover: None,
+            model_routes: Vec::new(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
             enforce_residency: Constrained::allow_any(None),
@@ -4551,6 +4886,7 @@ model_verbosity = "high"
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: Constrained::allow_any(HashMap::new()),
+            dynamic_tools: Vec::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
             mcp_oauth_callback_port: None,
             model_providers: fixture.model_provider_map.clone(),
@@ -4563,10 +4899,17 @@ model_verbosity = "high"
             config_layer_stack: Default::default(),
             startup_warnings: Vec::new(),
             history: History::default(),
+            remembered_approvals: RememberedApprovals::default(),
+            log_retention: LogRetention::default(),
             ephemeral: false,
+            record_model_streams: false,
+            model_stream_recording_dir: None,
+            model_stream_replay: None,
+            session_budget: SessionBudget::default(),
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
             js_repl_node_path: None,
+            py_repl_python_path: None,
             hide_agent_reasoning: false,
             show_raw_agent_reasoning: false,
             model_reasoning_effort: Some(ReasoningEffort::High),
@@ -4591,7 +4934,10 @@ model_verbosity = "high"
             features: Features::with_defaults(),
             suppress_unstable_features_warning: false,
             active_profile: Some("gpt5".to_string()),
-            active_project: ProjectConfig { trust_level: None },
+            active_project: ProjectConfig {
+                trust_level: None,
+                token_budget: None,
+            },
             windows_sandbox_mode: None,
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
@@ -4919,6 +5265,7 @@ trust_level = "untrusted"
                 project_key,
                 ProjectConfig {
                     trust_level: Some(TrustLevel::Trusted),
+                    token_budget: None,
                 },
             )])),
             ..Default::default()
@@ -4959,6 +5306,7 @@ trust_level = "untrusted"
                 project_key,
                 ProjectConfig {
                     trust_level: Some(TrustLevel::Trusted),
+                    token_budget: None,
                 },
             )])),
             ..Default::default()
@@ -5112,6 +5460,7 @@ mcp_oauth_callback_port = 5678
                     test_path.to_string_lossy().to_string(),
                     ProjectConfig {
                         trust_level: Some(TrustLevel::Untrusted),
+                        token_budget: None,
                     },
                 )])),
                 ..Default::default()
@@ -0,0 +1,666 @@
+//! Tools generated from OpenAPI 3 documents declared in `[openapi.<name>]`.
+//!
+//! Every selected operation becomes one function tool named
+//! `<source>__<operationId>`. Its parameters and JSON request body are
+//! flattened into a single argument object whose schema is checked against
+//! the `JsonSchema` subset the Responses API tools use. Sources with more
+//! operations than [`MAX_LISTED_OPERATIONS`] are deferred: their tools stay
+//! hidden until `search_tool_bm25` selects them.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use serde_json::Map;
+use serde_json::Value as JsonValue;
+use serde_json::json;
+
+use crate::config::types::ConfigDynamicTool;
+use crate::config::types::DynamicToolBackend;
+use crate::config::types::OPENAPI_BODY_ARGUMENT;
+use crate::config::types::OpenApiOperation;
+use crate::config::types::OpenApiParameter;
+use crate::config::types::OpenApiParameterLocation;
+use crate::config::types::OpenApiSourceToml;
+use crate::tools::spec::parse_tool_input_schema;
+
+/// Sources with more operations than this are only reachable through
+/// `search_tool_bm25`.
+pub(crate) const MAX_LISTED_OPERATIONS: usize = 16;
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+/// Limit the Responses API places on function names.
+const MAX_TOOL_NAME_LEN: usize = 64;
+/// Depth at which recursive `$ref`s are cut off and replaced with a bare
+/// object schema.
+const MAX_REF_DEPTH: usize = 8;
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Unreadable documents and operations that cannot be expressed as tools
+/// are skipped and reported through `startup_warnings`.
+pub(crate) fn load_openapi_tools(
+    sources: &HashMap<String, OpenApiSourceToml>,
+    startup_warnings: &mut Vec<String>,
+) -> Vec<ConfigDynamicTool> {
+    let sources: BTreeMap<_, _> = sources.iter().collect();
+    let mut tools = Vec::new();
+    for (source_name, source) in sources {
+        let document = match read_document(source.spec.as_path()) {
+            Ok(document) => document,
+            Err(err) => {
+                startup_warnings.push(format!(
+                    "Ignoring OpenAPI source `{source_name}` ({}): {err}",
+                    source.spec.display()
+                ));
+                continue;
+            }
+        };
+        match openapi_tools(source_name, source, &document, startup_warnings) {
+            Ok(source_tools) => tools.extend(source_tools),
+            Err(err) => {
+                startup_warnings.push(format!("Ignoring OpenAPI source `{source_name}`: {err}"))
+            }
+        }
+    }
+    tools
+}
+
+fn read_document(path: &Path) -> Result<JsonValue, String> {
+    let contents = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+    if path.extension().is_some_and(|ext| ext == "json") {
+        serde_json::from_str(&contents).map_err(|err| err.to_string())
+    } else {
+        serde_yaml::from_str(&contents).map_err(|err| err.to_string())
+    }
+}
+
+fn openapi_tools(
+    source_name: &str,
+    source: &OpenApiSourceToml,
+    document: &JsonValue,
+    startup_warnings: &mut Vec<String>,
+) -> Result<Vec<ConfigDynamicTool>, String> {
+    if !document
+        .get("openapi")
+        .and_then(JsonValue::as_str)
+        .is_some_and(|version| version.starts_with("3."))
+    {
+        return Err("only OpenAPI 3 documents are supported".to_string());
+    }
+    let base_url = source
+        .base_url
+        .clone()
+        .or_else(|| {
+            document
+                .pointer("/servers/0/url")
+                .and_then(JsonValue::as_str)
+                .map(str::to_string)
+        })
+        .ok_or_else(|| "set `base_url`; the document lists no servers".to_string())?;
+    if url::Url::parse(&base_url).is_err() {
+        return Err(format!(
+            "`{base_url}` is not an absolute URL; set `base_url`"
+        ));
+    }
+
+    let selected: Option<BTreeSet<&str>> = source
+        .operations
+        .as_ref()
+        .map(|operations| operations.iter().map(String::as_str).collect());
+    let mut found = BTreeSet::new();
+    let mut tools = Vec::new();
+    let paths = document
+        .get("paths")
+        .and_then(JsonValue::as_object)
+        .into_iter()
+        .flatten();
+    for (path, path_item) in paths {
+        let path_item = resolve_ref(document, path_item);
+        for method in METHODS {
+            let Some(operation) = path_item.get(method) else {
+                continue;
+            };
+            let operation_id = operation
+                .get("operationId")
+                .and_then(JsonValue::as_str)
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("{method}_{path}"));
+            if let Some(selected) = &selected
+                && !selected.contains(operation_id.as_str())
+            {
+                continue;
+            }
+            found.insert(operation_id.clone());
+            let operation = OperationContext {
+                source_name,
+                source,
+                document,
+                base_url: &base_url,
+                method,
+                path,
+                path_item,
+                operation,
+                operation_id: &operation_id,
+            };
+            match operation.to_tool() {
+                Ok(tool) => tools.push(tool),
+                Err(err) => startup_warnings.push(format!(
+                    "Ignoring OpenAPI operation `{operation_id}` from `{source_name}`: {err}"
+                )),
+            }
+        }
+    }
+
+    if let Some(selected) = &selected {
+        for missing in selected.iter().filter(|id| !found.contains(**id)) {
+            startup_warnings.push(format!(
+                "OpenAPI source `{source_name}` has no operation `{missing}`"
+            ));
+        }
+    }
+    if tools.len() > MAX_LISTED_OPERATIONS {
+        for tool in &mut tools {
+            tool.deferred = true;
+        }
+    }
+    Ok(tools)
+}
+
+struct OperationContext<'a> {
+    source_name: &'a str,
+    source: &'a OpenApiSourceToml,
+    document: &'a JsonValue,
+    base_url: &'a str,
+    method: &'a str,
+    path: &'a str,
+    path_item: &'a JsonValue,
+    operation: &'a JsonValue,
+    operation_id: &'a str,
+}
+
+impl OperationContext<'_> {
+    fn to_tool(&self) -> Result<ConfigDynamicTool, String> {
+        let name = tool_name(self.source_name, self.operation_id);
+        if name.len() > MAX_TOOL_NAME_LEN {
+            return Err(format!(
+                "tool name `{name}` is longer than {MAX_TOOL_NAME_LEN} characters"
+            ));
+        }
+
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        let mut parameters = Vec::new();
+        for parameter in self.parameters() {
+            let name = parameter
+                .get("name")
+                .and_then(JsonValue::as_str)
+                .ok_or("a parameter has no `name`")?;
+            let location = match parameter.get("in").and_then(JsonValue::as_str) {
+                Some("path") => OpenApiParameterLocation::Path,
+                Some("query") => OpenApiParameterLocation::Query,
+                Some("header") => OpenApiParameterLocation::Header,
+                Some("cookie") if !is_required(parameter) => continue,
+                other => {
+                    return Err(format!(
+                        "parameter `{name}` is in {}, which is not supported",
+                        other.unwrap_or("an unspecified location")
+                    ));
+                }
+            };
+            let mut schema = parameter
+                .get("schema")
+                .map(|schema| inline_schema(self.document, schema, 0))
+                .unwrap_or_else(|| json!({ "type": "string" }));
+            if let (Some(description), JsonValue::Object(schema)) =
+                (parameter.get("description"), &mut schema)
+            {
+                schema
+                    .entry("description")
+                    .or_insert_with(|| description.clone());
+            }
+            if location == OpenApiParameterLocation::Path || is_required(parameter) {
+                required.push(JsonValue::String(name.to_string()));
+            }
+            properties.insert(name.to_string(), schema);
+            parameters.push(OpenApiParameter {
+                name: name.to_string(),
+                location,
+            });
+        }
+
+        let has_body = match self.body_schema()? {
+            Some((schema, body_required)) => {
+                if properties.contains_key(OPENAPI_BODY_ARGUMENT) {
+                    return Err(format!(
+                        "a parameter named `{OPENAPI_BODY_ARGUMENT}` clashes with the request body"
+                    ));
+                }
+                if body_required {
+                    required.push(JsonValue::String(OPENAPI_BODY_ARGUMENT.to_string()));
+                }
+                properties.insert(OPENAPI_BODY_ARGUMENT.to_string(), schema);
+                true
+            }
+            None => false,
+        };
+
+        let mut input_schema = json!({
+            "type": "object",
+            "properties": properties,
+            "additionalProperties": false,
+        });
+        if !required.is_empty() {
+            input_schema["required"] = JsonValue::Array(required);
+        }
+        parse_tool_input_schema(&input_schema).map_err(|err| err.to_string())?;
+
+        let method = self.method.to_ascii_uppercase();
+        let summary = ["summary", "description"]
+            .into_iter()
+            .find_map(|key| self.operation.get(key).and_then(JsonValue::as_str))
+            .map(str::trim)
+            .filter(|summary| !summary.is_empty());
+        let description = match summary {
+            Some(summary) => format!("{summary}\n\n{method} {}", self.path),
+            None => format!("{method} {}", self.path),
+        };
+
+        Ok(ConfigDynamicTool {
+            name,
+            description,
+            backend: DynamicToolBackend::OpenApi(OpenApiOperation {
+                source: self.source_name.to_string(),
+                method,
+                base_url: self.base_url.to_string(),
+                path: self.path.to_string(),
+                parameters,
+                has_body,
+                http_headers: self.source.http_headers.clone().unwrap_or_default(),
+            }),
+            input_schema,
+            timeout: self
+                .source
+                .timeout_ms
+                .map_or(DEFAULT_TIMEOUT, Duration::from_millis),
+            approval: self.source.approval.unwrap_or_default(),
+            deferred: false,
+        })
+    }
+
+    /// Path-level parameters overridden by operation-level ones with the same
+    /// name and location.
+    fn parameters(&self) -> Vec<&JsonValue> {
+        let mut merged: Vec<&JsonValue> = Vec::new();
+        for holder in [self.path_item, self.operation] {
+            let Some(parameters) = holder.get("parameters").and_then(JsonValue::as_array) else {
+                continue;
+            };
+            for parameter in parameters {
+                let parameter = resolve_ref(self.document, parameter);
+                merged.retain(|existing| {
+                    existing.get("name") != parameter.get("name")
+                        || existing.get("in") != parameter.get("in")
+                });
+                merged.push(parameter);
+            }
+        }
+        merged
+    }
+
+    /// Schema of a JSON request body and whether it is required.
+    fn body_schema(&self) -> Result<Option<(JsonValue, bool)>, String> {
+        let Some(request_body) = self.operation.get("requestBody") else {
+            return Ok(None);
+        };
+        let request_body = resolve_ref(self.document, request_body);
+        let body_required = is_required(request_body);
+        let json_media = request_body
+            .get("content")
+            .and_then(JsonValue::as_object)
+            .and_then(|content| {
+                content.get_key_value("application/json").or_else(|| {
+                    content
+                        .iter()
+                        .find(|(media_type, _)| media_type.ends_with("+json"))
+                })
+            });
+        match json_media {
+            Some((_, media)) => {
+                let schema = media
+                    .get("schema")
+                    .map(|schema| inline_schema(self.document, schema, 0))
+                    .unwrap_or_else(|| json!({ "type": "object", "properties": {} }));
+                Ok(Some((schema, body_required)))
+            }
+            None if body_required => Err("the request body is not JSON".to_string()),
+            None => Ok(None),
+        }
+    }
+}
+
+fn is_required(value: &JsonValue) -> bool {
+    value.get("required").and_then(JsonValue::as_bool) == Some(true)
+}
+
+/// `<source>__<operationId>`, with characters the Responses API refuses in
+/// function names replaced by `_`.
+fn tool_name(source_name: &str, operation_id: &str) -> String {
+    format!("{source_name}__{operation_id}")
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '_' || ch == '-' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Follow a local `$ref` (`#/components/...`). Anything else is returned
+/// unchanged.
+fn resolve_ref<'a>(document: &'a JsonValue, value: &'a JsonValue) -> &'a JsonValue {
+    let mut value = value;
+    for _ in 0..MAX_REF_DEPTH {
+        let Some(pointer) = value
+            .get("$ref")
+            .and_then(JsonValue::as_str)
+            .and_then(|reference| reference.strip_prefix('#'))
+        else {
+            break;
+        };
+        match document.pointer(pointer) {
+            Some(target) => value = target,
+            None => break,
+        }
+    }
+    value
+}
+
+/// Copy `schema` with every local `$ref` inlined and `allOf` lists of object
+/// schemas merged, since the tool schema subset supports neither.
+fn inline_schema(document: &JsonValue, schema: &JsonValue, depth: usize) -> JsonValue {
+    if depth > MAX_REF_DEPTH {
+        return json!({ "type": "object", "properties": {} });
+    }
+    let schema = resolve_ref(document, schema);
+    match schema {
+        JsonValue::Object(map) => {
+            let mut inlined = Map::new();
+            for (key, value) in map {
+                let value = match key.as_str() {
+                    "properties" => JsonValue::Object(
+                        value
+                            .as_object()
+                            .map(|properties| {
+                                properties
+                                    .iter()
+                                    .map(|(name, property)| {
+                                        (name.clone(), inline_schema(document, property, depth + 1))
+                                    })
+                                    .collect()
+                            })
+                            .unwrap_or_default(),
+                    ),
+                    "items" | "additionalProperties" if value.is_object() => {
+                        inline_schema(document, value, depth + 1)
+                    }
+                    "oneOf" | "anyOf" | "allOf" => JsonValue::Array(
+                        value
+                            .as_array()
+                            .map(|schemas| {
+                                schemas
+                                    .iter()
+                                    .map(|schema| inline_schema(document, schema, depth + 1))
+                                    .collect()
+                            })
+                            .unwrap_or_default(),
+                    ),
+                    _ => value.clone(),
+                };
+                inlined.insert(key.clone(), value);
+            }
+            if let Some(JsonValue::Array(parts)) = inlined.remove("allOf") {
+                merge_all_of(&mut inlined, parts);
+            }
+            JsonValue::Object(inlined)
+        }
+        other => other.clone(),
+    }
+}
+
+fn merge_all_of(schema: &mut Map<String, JsonValue>, parts: Vec<JsonValue>) {
+    for part in parts {
+        let JsonValue::Object(part) = part else {
+            continue;
+        };
+        for (key, value) in part {
+            match (key.as_str(), schema.get_mut(&key), value) {
+                ("properties", Some(JsonValue::Object(existing)), JsonValue::Object(more)) => {
+                    existing.extend(more);
+                }
+                ("required", Some(JsonValue::Array(existing)), JsonValue::Array(more)) => {
+                    existing.extend(more);
+                }
+                (_, Some(_), _) => {}
+                (_, None, value) => {
+                    schema.insert(key, value);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::types::DynamicToolApproval;
+    use codex_utils_absolute_path::AbsolutePathBuf;
+    use pretty_assertions::assert_eq;
+
+    const PETSTORE: &str = r##"
+openapi: 3.0.3
+servers:
+  - url: https://pets.example.com/v1
+paths:
+  /pets/{petId}:
+    parameters:
+      - name: petId
+        in: path
+        description: Pet to act on
+        schema: { type: string }
+    get:
+      operationId: showPetById
+      summary: Info for a specific pet
+    put:
+      operationId: updatePet
+      parameters:
+        - name: dryRun
+          in: query
+          schema: { type: boolean }
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema: { $ref: "#/components/schemas/NewPet" }
+  /upload:
+    post:
+      operationId: uploadPhoto
+      requestBody:
+        required: true
+        content:
+          image/png: {}
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [name]
+      properties:
+        name: { type: string }
+    NewPet:
+      allOf:
+        - $ref: "#/components/schemas/Pet"
+        - type: object
+          properties:
+            tag: { type: string }
+"##;
+
+    fn source(spec: &Path) -> anyhow::Result<OpenApiSourceToml> {
+        Ok(OpenApiSourceToml {
+            spec: AbsolutePathBuf::from_absolute_path(spec)?,
+            base_url: None,
+            operations: None,
+            http_headers: Some(HashMap::from([(
+                "Authorization".to_string(),
+                "Bearer token".to_string(),
+            )])),
+            timeout_ms: None,
+            approval: None,
+        })
+    }
+
+    #[test]
+    fn converts_operations_into_tools() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let spec = tmp.path().join("pets.yaml");
+        std::fs::write(&spec, PETSTORE)?;
+        let sources = HashMap::from([("pets".to_string(), source(&spec)?)]);
+
+        let mut warnings = Vec::new();
+        let tools = load_openapi_tools(&sources, &mut warnings);
+
+        let operation = |method: &str, parameters: Vec<OpenApiParameter>, has_body| {
+            DynamicToolBackend::OpenApi(OpenApiOperation {
+                source: "pets".to_string(),
+                method: method.to_string(),
+                base_url: "https://pets.example.com/v1".to_string(),
+                path: "/pets/{petId}".to_string(),
+                parameters,
+                has_body,
+                http_headers: HashMap::from([(
+                    "Authorization".to_string(),
+                    "Bearer token".to_string(),
+                )]),
+            })
+        };
+        let pet_id = OpenApiParameter {
+            name: "petId".to_string(),
+            location: OpenApiParameterLocation::Path,
+        };
+        assert_eq!(
+            tools,
+            vec![
+                ConfigDynamicTool {
+                    name: "pets__showPetById".to_string(),
+                    description: "Info for a specific pet\n\nGET /pets/{petId}".to_string(),
+                    backend: operation("GET", vec![pet_id.clone()], false),
+                    input_schema: json!({
+                        "type": "object",
+                        "properties": {
+                            "petId": { "type": "string", "description": "Pet to act on" },
+                        },
+                        "required": ["petId"],
+                        "additionalProperties": false,
+                    }),
+                    timeout: DEFAULT_TIMEOUT,
+                    approval: DynamicToolApproval::Auto,
+                    deferred: false,
+                },
+                ConfigDynamicTool {
+                    name: "pets__updatePet".to_string(),
+                    description: "PUT /pets/{petId}".to_string(),
+                    backend: operation(
+                        "PUT",
+                        vec![
+                            pet_id,
+                            OpenApiParameter {
+                                name: "dryRun".to_string(),
+                                location: OpenApiParameterLocation::Query,
+                            },
+                        ],
+                        true,
+                    ),
+                    input_schema: json!({
+                        "type": "object",
+                        "properties": {
+                            "petId": { "type": "string", "description": "Pet to act on" },
+                            "dryRun": { "type": "boolean" },
+                            "body": {
+                                "type": "object",
+                                "required": ["name"],
+                                "properties": {
+                                    "name": { "type": "string" },
+                                    "tag": { "type": "string" },
+                                },
+                            },
+                        },
+                        "required": ["petId", "body"],
+                        "additionalProperties": false,
+                    }),
+                    timeout: DEFAULT_TIMEOUT,
+                    approval: DynamicToolApproval::Auto,
+                    deferred: false,
+                },
+            ]
+        );
+        assert_eq!(
+            warnings,
+            vec![
+                "Ignoring OpenAPI operation `uploadPhoto` from `pets`: the request body is not \
+                 JSON"
+                    .to_string()
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn large_sources_are_deferred() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let spec = tmp.path().join("big.json");
+        let paths: Map<String, JsonValue> = (0..=MAX_LISTED_OPERATIONS)
+            .map(|idx| {
+                (
+                    format!("/items/{idx}"),
+                    json!({ "get": { "operationId": format!("getItem{idx}") } }),
+                )
+            })
+            .collect();
+        std::fs::write(
+            &spec,
+            json!({
+                "openapi": "3.1.0",
+                "servers": [{ "url": "https://items.example.com" }],
+                "paths": paths,
+            })
+            .to_string(),
+        )?;
+
+        let mut big = source(&spec)?;
+        let mut warnings = Vec::new();
+        let tools = load_openapi_tools(
+            &HashMap::from([("items".to_string(), big.clone())]),
+            &mut warnings,
+        );
+        assert_eq!(tools.len(), MAX_LISTED_OPERATIONS + 1);
+        assert!(tools.iter().all(|tool| tool.deferred));
+
+        big.operations = Some(vec!["getItem3".to_string(), "getItem99".to_string()]);
+        let tools = load_openapi_tools(&HashMap::from([("items".to_string(), big)]), &mut warnings);
+        assert_eq!(
+            tools
+                .iter()
+                .map(|tool| (tool.name.as_str(), tool.deferred))
+                .collect::<Vec<_>>(),
+            vec![("items__getItem3", false)]
+        );
+        assert_eq!(
+            warnings,
+            vec!["OpenAPI source `items` has no operation `getItem99`".to_string()]
+        );
+        Ok(())
+    }
+}
@@ -3,6 +3,8 @@ use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::config::types::FailoverToml;
+use crate::config::types::ModelRoute;
 use crate::config::types::Personality;
 use crate::config::types::WindowsToml;
 use crate::protocol::AskForApproval;
@@ -31,6 +33,7 @@ pub struct ConfigProfile {
     /// Optional path to a file containing model instructions.
     pub model_instructions_file: Option<AbsolutePathBuf>,
     pub js_repl_node_path: Option<AbsolutePathBuf>,
+    pub py_repl_python_path: Option<AbsolutePathBuf>,
     /// Deprecated: ignored. Use `model_instructions_file`.
     #[schemars(skip)]
     pub experimental_instructions_file: Option<AbsolutePathBuf>,
@@ -50,6 +53,11 @@ pub struct ConfigProfile {
     #[schemars(schema_with = "crate::config::schema::features_schema")]
     pub features: Option<crate::features::FeaturesToml>,
     pub oss_provider: Option<String>,
+    /// Fallback providers to switch to when this profile's provider is rate
+    /// limited or unavailable.
+    pub failover: Option<FailoverToml>,
+    /// Replaces the top-level `model_routes` for this profile.
+    pub model_routes: Option<Vec<ModelRoute>>,
 }
 
 impl From<ConfigProfile> for codex_app_server_protocol::Profile {
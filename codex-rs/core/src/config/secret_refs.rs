@@ -0,0 +1,436 @@
+//! Resolution of `secret:NAME` references in config values.
+//!
+//! Values in `shell_environment_policy.set`, MCP server `env` tables, HTTP
+//! tool and OpenAPI source headers and a provider's `env_key` /
+//! `experimental_bearer_token` may name a secret stored with
+//! `codex secrets set` instead of holding the value itself.
+//! Resolved values are registered with `codex_secrets` so they are redacted
+//! from tool output and rollouts.
+//!
+//! A secret that is not stored only affects what references it: the MCP
+//! server, tool or OpenAPI source is disabled and the shell variable left
+//! unset, each with a startup warning. A provider is only an error when the
+//! session uses it.
+
+use std::cell::OnceCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Error;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use codex_secrets::SecretName;
+use codex_secrets::SecretsBackendKind;
+use codex_secrets::SecretsManager;
+
+use crate::config::types::ConfigDynamicTool;
+use crate::config::types::DynamicToolBackend;
+use crate::config::types::McpServerConfig;
+use crate::config::types::McpServerDisabledReason;
+use crate::config::types::McpServerTransportConfig;
+use crate::config::types::OpenApiSourceToml;
+use crate::config::types::ShellEnvironmentPolicy;
+use crate::model_provider_info::ModelProviderInfo;
+
+/// A reference to a secret that is not stored.
+#[derive(Debug)]
+pub(crate) struct MissingSecret {
+    setting: String,
+    name: SecretName,
+}
+
+impl fmt::Display for MissingSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { setting, name } = self;
+        write!(
+            f,
+            "`{setting}` references secret `{name}`, which is not set; \
+             run `codex secrets set {name}`"
+        )
+    }
+}
+
+impl std::error::Error for MissingSecret {}
+
+impl From<MissingSecret> for Error {
+    fn from(missing: MissingSecret) -> Self {
+        Error::new(ErrorKind::NotFound, missing)
+    }
+}
+
+/// Separates a missing secret, which only disables what references it, from
+/// errors that fail the whole config load.
+fn missing_secret(err: Error) -> std::io::Result<MissingSecret> {
+    err.downcast::<MissingSecret>()
+}
+
+pub(crate) struct SecretReferenceResolver<'a> {
+    codex_home: &'a Path,
+    cwd: &'a Path,
+    /// Opened on the first reference so configs without any never touch the
+    /// keyring.
+    manager: OnceCell<SecretsManager>,
+}
+
+impl<'a> SecretReferenceResolver<'a> {
+    pub(crate) fn new(codex_home: &'a Path, cwd: &'a Path) -> Self {
+        Self {
+            codex_home,
+            cwd,
+            manager: OnceCell::new(),
+        }
+    }
+
+    /// Variables whose secret is missing are left unset.
+    pub(crate) fn resolve_shell_environment(
+        &self,
+        policy: &mut ShellEnvironmentPolicy,
+        startup_warnings: &mut Vec<String>,
+    ) -> std::io::Result<()> {
+        let mut unset = Vec::new();
+        for (key, value) in policy.r#set.iter_mut() {
+            if let Err(err) =
+                self.resolve_value(&format!("shell_environment_policy.set.{key}"), value)
+            {
+                let missing = missing_secret(err)?;
+                startup_warnings.push(format!("{missing}; `{key}` is left unset"));
+                unset.push(key.clone());
+            }
+        }
+        for key in unset {
+            policy.r#set.remove(&key);
+        }
+        Ok(())
+    }
+
+    /// Servers whose env references a missing secret are disabled.
+    pub(crate) fn resolve_mcp_servers(
+        &self,
+        servers: &mut HashMap<String, McpServerConfig>,
+        startup_warnings: &mut Vec<String>,
+    ) -> std::io::Result<()> {
+        for (server_name, server) in servers.iter_mut() {
+            let McpServerTransportConfig::Stdio { env: Some(env), .. } = &mut server.transport
+            else {
+                continue;
+            };
+            let mut missing = None;
+            for (key, value) in env.iter_mut() {
+                if let Err(err) =
+                    self.resolve_value(&format!("mcp_servers.{server_name}.env.{key}"), value)
+                {
+                    missing = Some(missing_secret(err)?);
+                    break;
+                }
+            }
+            if let Some(missing) = missing {
+                startup_warnings.push(format!("MCP server `{server_name}` is disabled: {missing}"));
+                server.enabled = false;
+                server.disabled_reason = Some(McpServerDisabledReason::MissingSecret {
+                    name: missing.name.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Tools whose headers reference a missing secret are dropped.
+    pub(crate) fn resolve_dynamic_tools(
+        &self,
+        tools: &mut Vec<ConfigDynamicTool>,
+        startup_warnings: &mut Vec<String>,
+    ) -> std::io::Result<()> {
+        let mut resolved = Vec::with_capacity(tools.len());
+        for mut tool in std::mem::take(tools) {
+            let mut missing = None;
+            if let DynamicToolBackend::Http { http_headers, .. } = &mut tool.backend {
+                for (header, value) in http_headers.iter_mut() {
+                    if let Err(err) = self.resolve_value(
+                        &format!("dynamic_tools.{}.http_headers.{header}", tool.name),
+                        value,
+                    ) {
+                        missing = Some(missing_secret(err)?);
+                        break;
+                    }
+                }
+            }
+            match missing {
+                Some(missing) => startup_warnings.push(format!(
+                    "dynamic tool `{}` is disabled: {missing}",
+                    tool.name
+                )),
+                None => resolved.push(tool),
+            }
+        }
+        *tools = resolved;
+        Ok(())
+    }
+
+    /// Sources whose headers reference a missing secret are dropped.
+    pub(crate) fn resolve_openapi_sources(
+        &self,
+        sources: &mut HashMap<String, OpenApiSourceToml>,
+        startup_warnings: &mut Vec<String>,
+    ) -> std::io::Result<()> {
+        let mut disabled = Vec::new();
+        for (source_name, source) in sources.iter_mut() {
+            for (header, value) in source.http_headers.iter_mut().flatten() {
+                if let Err(err) = self.resolve_value(
+                    &format!("openapi.{source_name}.http_headers.{header}"),
+                    value,
+                ) {
+                    let missing = missing_secret(err)?;
+                    startup_warnings.push(format!(
+                        "OpenAPI source `{source_name}` is disabled: {missing}"
+                    ));
+                    disabled.push(source_name.clone());
+                    break;
+                }
+            }
+        }
+        for source_name in disabled {
+            sources.remove(&source_name);
+        }
+        Ok(())
+    }
+
+    /// `env_key = "secret:NAME"` is accepted for convenience: the secret
+    /// becomes the provider's bearer token in place of an environment
+    /// variable lookup.
+    ///
+    /// Providers that reference a missing secret are returned, unresolved,
+    /// so the caller can fail only when the session actually uses one.
+    pub(crate) fn resolve_model_providers(
+        &self,
+        providers: &mut HashMap<String, ModelProviderInfo>,
+    ) -> std::io::Result<HashMap<String, MissingSecret>> {
+        let mut unresolved = HashMap::new();
+        for (provider_id, provider) in providers.iter_mut() {
+            if let Err(err) = self.resolve_provider(provider_id, provider) {
+                unresolved.insert(provider_id.clone(), missing_secret(err)?);
+            }
+        }
+        Ok(unresolved)
+    }
+
+    fn resolve_provider(
+        &self,
+        provider_id: &str,
+        provider: &mut ModelProviderInfo,
+    ) -> std::io::Result<()> {
+        if let Some(token) = provider.experimental_bearer_token.as_mut() {
+            self.resolve_value(
+                &format!("model_providers.{provider_id}.experimental_bearer_token"),
+                token,
+            )?;
+        }
+        if let Some(mut env_key) = provider.env_key.clone()
+            && self.resolve_value(
+                &format!("model_providers.{provider_id}.env_key"),
+                &mut env_key,
+            )?
+        {
+            provider.env_key = None;
+            provider.experimental_bearer_token = Some(env_key);
+        }
+        Ok(())
+    }
+
+    /// Replace `value` in place when it is a secret reference. Returns
+    /// whether it was one.
+    fn resolve_value(&self, setting: &str, value: &mut String) -> std::io::Result<bool> {
+        let Some(name) = codex_secrets::parse_secret_reference(value) else {
+            return Ok(false);
+        };
+        let name = name.map_err(|err| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("`{setting}` has an invalid secret reference: {err}"),
+            )
+        })?;
+        let resolved = self.lookup(&name).map_err(|err| {
+            Error::other(format!(
+                "failed to read secret `{name}` for `{setting}`: {err}"
+            ))
+        })?;
+        let Some(resolved) = resolved else {
+            return Err(MissingSecret {
+                setting: setting.to_string(),
+                name,
+            }
+            .into());
+        };
+        codex_secrets::register_secret_value(&name, &resolved);
+        *value = resolved;
+        Ok(true)
+    }
+
+    fn lookup(&self, name: &SecretName) -> anyhow::Result<Option<String>> {
+        self.manager
+            .get_or_init(|| {
+                SecretsManager::new(self.codex_home.to_path_buf(), SecretsBackendKind::Local)
+            })
+            .resolve(name, self.cwd)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_keyring_store::tests::MockKeyringStore;
+    use codex_secrets::SecretScope;
+    use pretty_assertions::assert_eq;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn resolver_with_secret<'a>(
+        codex_home: &'a TempDir,
+        cwd: &'a TempDir,
+        name: &str,
+        value: &str,
+    ) -> anyhow::Result<SecretReferenceResolver<'a>> {
+        let manager = SecretsManager::new_with_keyring_store(
+            codex_home.path().to_path_buf(),
+            SecretsBackendKind::Local,
+            Arc::new(MockKeyringStore::default()),
+        );
+        manager.set(&SecretScope::Global, &SecretName::new(name)?, value)?;
+        let resolver = SecretReferenceResolver::new(codex_home.path(), cwd.path());
+        let _ = resolver.manager.set(manager);
+        Ok(resolver)
+    }
+
+    #[test]
+    fn resolves_references_and_redacts_their_values() -> anyhow::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cwd = TempDir::new()?;
+        let resolver =
+            resolver_with_secret(&codex_home, &cwd, "NPM_TOKEN", "npm-token-secret-value")?;
+
+        let mut policy = ShellEnvironmentPolicy::default();
+        policy
+            .r#set
+            .insert("NPM_TOKEN".to_string(), "secret:NPM_TOKEN".to_string());
+        policy
+            .r#set
+            .insert("NODE_ENV".to_string(), "production".to_string());
+        resolver.resolve_shell_environment(&mut policy, &mut Vec::new())?;
+
+        assert_eq!(
+            policy.r#set.get("NPM_TOKEN").map(String::as_str),
+            Some("npm-token-secret-value")
+        );
+        assert_eq!(
+            policy.r#set.get("NODE_ENV").map(String::as_str),
+            Some("production")
+        );
+        assert_eq!(
+            codex_secrets::redact_secret_values("//registry/:_authToken=npm-token-secret-value"),
+            "//registry/:_authToken=[REDACTED:NPM_TOKEN]"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn provider_env_key_reference_becomes_bearer_token() -> anyhow::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cwd = TempDir::new()?;
+        let resolver = resolver_with_secret(&codex_home, &cwd, "PROXY_KEY", "proxy-key-value")?;
+
+        let mut provider = ModelProviderInfo::create_openai_provider();
+        provider.env_key = Some("secret:PROXY_KEY".to_string());
+        let mut providers = HashMap::from([("proxy".to_string(), provider)]);
+        assert!(resolver.resolve_model_providers(&mut providers)?.is_empty());
+
+        let provider = &providers["proxy"];
+        assert_eq!(provider.env_key, None);
+        assert_eq!(
+            provider.experimental_bearer_token.as_deref(),
+            Some("proxy-key-value")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn missing_secret_leaves_shell_variable_unset() -> anyhow::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cwd = TempDir::new()?;
+        let resolver = resolver_with_secret(&codex_home, &cwd, "OTHER", "other-value")?;
+
+        let mut policy = ShellEnvironmentPolicy::default();
+        policy
+            .r#set
+            .insert("NPM_TOKEN".to_string(), "secret:NPM_TOKEN".to_string());
+        let mut warnings = Vec::new();
+        resolver.resolve_shell_environment(&mut policy, &mut warnings)?;
+
+        assert_eq!(policy.r#set.get("NPM_TOKEN"), None);
+        assert_eq!(
+            warnings,
+            vec![
+                "`shell_environment_policy.set.NPM_TOKEN` references secret `NPM_TOKEN`, which \
+                 is not set; run `codex secrets set NPM_TOKEN`; `NPM_TOKEN` is left unset"
+                    .to_string()
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn missing_secret_disables_only_the_mcp_server_using_it() -> anyhow::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cwd = TempDir::new()?;
+        let resolver = resolver_with_secret(&codex_home, &cwd, "DOCS_TOKEN", "docs-token-value")?;
+
+        let mut servers: HashMap<String, McpServerConfig> = toml::from_str(
+            r#"
+            [docs]
+            command = "docs-mcp"
+            env = { TOKEN = "secret:DOCS_TOKEN" }
+
+            [github]
+            command = "github-mcp"
+            env = { TOKEN = "secret:GITHUB_TOKEN" }
+            "#,
+        )?;
+        let mut warnings = Vec::new();
+        resolver.resolve_mcp_servers(&mut servers, &mut warnings)?;
+
+        assert!(servers["docs"].enabled);
+        let github = &servers["github"];
+        assert!(!github.enabled);
+        assert_eq!(
+            github.disabled_reason,
+            Some(McpServerDisabledReason::MissingSecret {
+                name: "GITHUB_TOKEN".to_string()
+            })
+        );
+        assert_eq!(warnings.len(), 1);
+        assert!(
+            warnings[0].starts_with("MCP server `github` is disabled: "),
+            "{warnings:?}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn missing_provider_secret_is_reported_per_provider() -> anyhow::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cwd = TempDir::new()?;
+        let resolver = resolver_with_secret(&codex_home, &cwd, "OTHER", "other-value")?;
+
+        let mut provider = ModelProviderInfo::create_openai_provider();
+        provider.env_key = Some("secret:PROXY_KEY".to_string());
+        let mut providers = HashMap::from([("proxy".to_string(), provider)]);
+        let mut unresolved = resolver.resolve_model_providers(&mut providers)?;
+
+        let err = Error::from(unresolved.remove("proxy").expect("proxy is unresolved"));
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        assert_eq!(
+            err.to_string(),
+            "`model_providers.proxy.env_key` references secret `PROXY_KEY`, which is not \
+             set; run `codex secrets set PROXY_KEY`"
+        );
+        Ok(())
+    }
+}
@@ -4,10 +4,13 @@
 // definitions that do not contain business logic.
 
 use crate::config_loader::RequirementSource;
+use crate::model_provider_info::ModelProviderInfo;
 pub use codex_protocol::config_types::AltScreenMode;
 pub use codex_protocol::config_types::ModeKind;
 pub use codex_protocol::config_types::Personality;
 pub use codex_protocol::config_types::WebSearchMode;
+use codex_protocol::openai_models::ReasoningEffort;
+pub use codex_protocol::protocol::FailoverTrigger;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
@@ -41,6 +44,7 @@ pub struct WindowsToml {
 pub enum McpServerDisabledReason {
     Unknown,
     Requirements { source: RequirementSource },
+    MissingSecret { name: String },
 }
 
 impl fmt::Display for McpServerDisabledReason {
@@ -50,6 +54,9 @@ impl fmt::Display for McpServerDisabledReason {
             McpServerDisabledReason::Requirements { source } => {
                 write!(f, "requirements ({source})")
             }
+            McpServerDisabledReason::MissingSecret { name } => {
+                write!(f, "secret `{name}` is not set")
+            }
         }
     }
 }
@@ -336,6 +343,319 @@ pub enum HistoryPersistence {
     None,
 }
 
+// ===== Provider failover =====
+
+/// Ordered fallback chain declared by a profile (`[profiles.<name>.failover]`).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct FailoverToml {
+    /// Provider and model pairs tried, in order, after the profile's own provider.
+    #[serde(default)]
+    pub chain: Vec<FailoverTargetToml>,
+
+    /// Failures that move the session to the next entry. Defaults to all of them.
+    pub triggers: Option<Vec<FailoverTrigger>>,
+
+    /// Seconds a failed entry is skipped before it is tried again. Defaults to 300.
+    pub cooldown_secs: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct FailoverTargetToml {
+    /// Key into the `model_providers` map.
+    pub model_provider: String,
+
+    /// Model to request from this provider. Defaults to the session's model.
+    pub model: Option<String>,
+
+    /// Overrides `cooldown_secs` for this entry.
+    pub cooldown_secs: Option<u64>,
+}
+
+/// Resolved failover chain for the active profile.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailoverConfig {
+    /// Fallback entries; the session's own provider is implicitly first.
+    pub chain: Vec<FailoverTarget>,
+    pub triggers: Vec<FailoverTrigger>,
+    /// Cool-down applied to the session's own provider after it fails.
+    pub cooldown: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailoverTarget {
+    pub provider_id: String,
+    pub provider: ModelProviderInfo,
+    pub model: Option<String>,
+    pub cooldown: Duration,
+}
+
+// ===== Model routing =====
+
+/// Kind of work a model request is made for, as matched by `[[model_routes]]`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelRouteTask {
+    /// A regular turn outside plan mode.
+    Chat,
+    /// A regular turn in a plan collaboration mode.
+    Plan,
+    /// A code review (`/review`).
+    Review,
+    /// A context compaction summary, manual or automatic.
+    Compact,
+    /// A turn run by a spawned sub-agent.
+    SubAgent,
+}
+
+/// One `[[model_routes]]` rule. Rules are checked in order and the first one
+/// whose conditions all hold picks the model for the request; unset
+/// conditions match anything.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ModelRoute {
+    /// Label recorded in the rollout when this rule is applied. Defaults to
+    /// `model_routes[<index>]`.
+    pub name: Option<String>,
+
+    /// Task kind the rule applies to.
+    pub task: Option<ModelRouteTask>,
+
+    /// Sub-agent role id the rule applies to.
+    pub role: Option<String>,
+
+    /// Only match follow-up requests within a turn whose previous response
+    /// called nothing but this tool (for example `search_tool_bm25`). Rules
+    /// without it are only considered at the start of a turn.
+    pub after_tool: Option<String>,
+
+    /// Only match when the estimated prompt is at least this many tokens.
+    pub min_prompt_tokens: Option<i64>,
+
+    /// Only match when the estimated prompt is at most this many tokens.
+    pub max_prompt_tokens: Option<i64>,
+
+    /// Model to use. Defaults to the session's model.
+    pub model: Option<String>,
+
+    /// Reasoning effort to use. Defaults to the model's effort selection.
+    pub model_reasoning_effort: Option<ReasoningEffort>,
+}
+
+// ===== Log store =====
+
+/// Retention for the `logs` table of the state database (`[logs]`).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct LogsConfigToml {
+    /// Delete entries older than this many days. Defaults to 90; `0` keeps
+    /// entries regardless of age.
+    pub retention_days: Option<u32>,
+
+    /// Keep at most this many of the newest entries. Defaults to 1000000; `0`
+    /// removes the cap.
+    pub max_rows: Option<u64>,
+}
+
+// ===== Remembered approvals =====
+
+/// Where "don't ask again" command approvals are saved.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RememberedApprovalsScope {
+    /// Save them for the current repository only, in
+    /// `$CODEX_HOME/remembered_approvals.json`.
+    #[default]
+    Repository,
+    /// Append them to `$CODEX_HOME/rules/default.rules`, which applies to
+    /// every repository. Refused prefixes are still saved for the current
+    /// repository only.
+    Global,
+}
+
+/// Settings for approvals Codex remembers across sessions
+/// (`[remembered_approvals]`).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct RememberedApprovals {
+    /// Where command approvals are saved. Defaults to `repository`.
+    #[serde(default)]
+    pub scope: RememberedApprovalsScope,
+
+    /// Forget decisions saved for a repository this many days after they
+    /// were granted. Unset keeps them until revoked with `/permissions`.
+    pub expire_after_days: Option<u32>,
+}
+
+// ===== Token budgets =====
+
+/// Window a token budget is counted over: a UTC day, or a UTC week starting
+/// on Monday.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenBudgetPeriod {
+    Day,
+    #[default]
+    Week,
+}
+
+/// Token budget for a project (`[projects."<path>".token_budget]`).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct TokenBudget {
+    /// Window usage is counted over. Defaults to `week`.
+    pub period: Option<TokenBudgetPeriod>,
+
+    /// Warn when a turn starts after the project has used this many tokens in
+    /// the current period.
+    pub soft_limit: Option<u64>,
+
+    /// Refuse to start turns once the project has used this many tokens in
+    /// the current period.
+    pub hard_limit: Option<u64>,
+}
+
+/// Hard ceilings for a single session (`[session_budget]`). Once one is
+/// reached the session refuses to sample the model until it is restarted.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct SessionBudget {
+    /// Stop once the session's model responses have used more than this many tokens.
+    pub max_tokens: Option<u64>,
+
+    /// Stop once the session has been running for this many seconds.
+    pub timeout_secs: Option<u64>,
+}
+
+/// When a tool declared under `[dynamic_tools]` asks for approval.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DynamicToolApproval {
+    /// Commands are approved like shell commands under the session's approval
+    /// policy; HTTP requests other than `GET` and `HEAD` (including every HTTP
+    /// tool call, which is a `POST`) ask unless the policy is `never`.
+    #[default]
+    Auto,
+    /// Ask before every call.
+    Always,
+    /// Never ask. Commands still run in the sandbox.
+    Never,
+}
+
+/// A tool declared in `[dynamic_tools.<name>]` or `.codex/tools/<name>.toml`.
+/// Exactly one of `command` and `url` must be set.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct DynamicToolToml {
+    /// Description shown to the model.
+    pub description: String,
+
+    /// Executable and arguments. The call's JSON arguments are written to its
+    /// stdin and it must print a JSON `DynamicToolResponse` on stdout.
+    pub command: Option<Vec<String>>,
+
+    /// Endpoint that receives the JSON arguments as a POST body and answers
+    /// with a JSON `DynamicToolResponse`.
+    pub url: Option<String>,
+
+    /// Extra headers sent to `url`. Values may be `secret:NAME` references.
+    pub http_headers: Option<HashMap<String, String>>,
+
+    /// JSON Schema the arguments must satisfy. Defaults to an object without
+    /// properties.
+    pub input_schema: Option<serde_json::Value>,
+
+    /// Maximum duration of one call, in milliseconds. Defaults to 60000.
+    pub timeout_ms: Option<u64>,
+
+    /// When to ask before calling the tool. Defaults to `auto`.
+    pub approval: Option<DynamicToolApproval>,
+}
+
+/// How a config-declared tool is invoked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DynamicToolBackend {
+    Command {
+        command: Vec<String>,
+    },
+    Http {
+        url: String,
+        http_headers: HashMap<String, String>,
+    },
+    OpenApi(OpenApiOperation),
+}
+
+/// Resolved form of [`DynamicToolToml`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigDynamicTool {
+    pub name: String,
+    pub description: String,
+    pub backend: DynamicToolBackend,
+    pub input_schema: serde_json::Value,
+    pub timeout: Duration,
+    pub approval: DynamicToolApproval,
+    /// Only exposed to the model once `search_tool_bm25` has selected it.
+    pub deferred: bool,
+}
+
+/// An OpenAPI 3 document whose operations are exposed as tools
+/// (`[openapi.<name>]`).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct OpenApiSourceToml {
+    /// Path to the document, in JSON or YAML.
+    pub spec: AbsolutePathBuf,
+
+    /// Server the operations are sent to. Defaults to the first entry of the
+    /// document's `servers`.
+    pub base_url: Option<String>,
+
+    /// `operationId`s to expose. Defaults to every operation.
+    pub operations: Option<Vec<String>>,
+
+    /// Headers sent with every request, typically for auth. Values may be
+    /// `secret:NAME` references.
+    pub http_headers: Option<HashMap<String, String>>,
+
+    /// Maximum duration of one call, in milliseconds. Defaults to 60000.
+    pub timeout_ms: Option<u64>,
+
+    /// When to ask before calling an operation. Defaults to `auto`.
+    pub approval: Option<DynamicToolApproval>,
+}
+
+/// Argument of an OpenAPI tool that carries the request body.
+pub const OPENAPI_BODY_ARGUMENT: &str = "body";
+
+/// One OpenAPI operation. The tool's arguments hold its parameters by name
+/// and the request body under [`OPENAPI_BODY_ARGUMENT`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenApiOperation {
+    pub source: String,
+    /// Upper-case HTTP method.
+    pub method: String,
+    pub base_url: String,
+    /// Path template such as `/pets/{petId}`.
+    pub path: String,
+    pub parameters: Vec<OpenApiParameter>,
+    pub has_body: bool,
+    pub http_headers: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenApiParameter {
+    pub name: String,
+    pub location: OpenApiParameterLocation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenApiParameterLocation {
+    Path,
+    Query,
+    Header,
+}
+
 // ===== Analytics configuration =====
 
 /// Analytics settings loaded from config.toml. Fields are optional so we can apply defaults.
@@ -433,6 +753,15 @@ pub enum OtelExporterKind {
         #[serde(default)]
         tls: Option<OtelTlsConfig>,
     },
+    /// Serve metrics for scraping at `http://<listen_addr>/metrics` (metrics only).
+    Prometheus {
+        /// Socket address to bind, e.g. `127.0.0.1:9464`.
+        listen_addr: String,
+    },
+    /// Append metrics to a local file as JSON lines (metrics only).
+    JsonLines {
+        path: AbsolutePathBuf,
+    },
 }
 
 /// OTEL settings loaded from config.toml. Fields are optional so we can apply defaults.
@@ -3,8 +3,8 @@ use crate::context_manager::normalize;
 use crate::instructions::SkillInstructions;
 use crate::instructions::UserInstructions;
 use crate::session_prefix::is_session_prefix;
+use crate::tokenizer::Tokenizer;
 use crate::truncate::TruncationPolicy;
-use crate::truncate::approx_token_count;
 use crate::truncate::approx_tokens_from_byte_count_i64;
 use crate::truncate::truncate_function_output_items_with_policy;
 use crate::truncate::truncate_text;
@@ -18,7 +18,10 @@ use codex_protocol::models::ResponseItem;
 use codex_protocol::openai_models::InputModality;
 use codex_protocol::protocol::TokenUsage;
 use codex_protocol::protocol::TokenUsageInfo;
+use codex_secrets::redact_secret_values;
 use std::ops::Deref;
+use std::sync::Mutex;
+use std::sync::PoisonError;
 
 /// Transcript of thread history
 #[derive(Debug, Clone, Default)]
@@ -26,6 +29,62 @@ pub(crate) struct ContextManager {
     /// The oldest items are at the beginning of the vector.
     items: Vec<ResponseItem>,
     token_info: Option<TokenUsageInfo>,
+    item_tokens: ItemTokenCounts,
+}
+
+/// Token counts for `ContextManager::items`, by index. Counting an item
+/// serializes it and runs the tokenizer, so counts are kept until the history
+/// is changed by anything other than appending.
+#[derive(Debug, Default)]
+struct ItemTokenCounts {
+    inner: Mutex<ItemTokenCountsInner>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ItemTokenCountsInner {
+    tokenizer: &'static str,
+    counts: Vec<i64>,
+}
+
+impl Clone for ItemTokenCounts {
+    fn clone(&self) -> Self {
+        let inner = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        Self {
+            inner: Mutex::new(inner.clone()),
+        }
+    }
+}
+
+impl ItemTokenCounts {
+    fn clear(&mut self) {
+        self.inner
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .counts
+            .clear();
+    }
+
+    /// Calls `f` with one count per item, counting only items that are new
+    /// since the last call with the same tokenizer.
+    fn with_counts<R>(
+        &self,
+        items: &[ResponseItem],
+        tokenizer: &dyn Tokenizer,
+        f: impl FnOnce(&[i64]) -> R,
+    ) -> R {
+        let mut inner = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        if inner.tokenizer != tokenizer.name() || inner.counts.len() > items.len() {
+            inner.tokenizer = tokenizer.name();
+            inner.counts.clear();
+        }
+        let known = inner.counts.len();
+        inner.counts.extend(
+            items[known..]
+                .iter()
+                .map(|item| estimate_item_token_count(item, tokenizer)),
+        );
+        f(&inner.counts)
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -41,6 +100,7 @@ impl ContextManager {
         Self {
             items: Vec::new(),
             token_info: TokenUsageInfo::new_or_append(&None, &None, None),
+            item_tokens: ItemTokenCounts::default(),
         }
     }
 
@@ -62,8 +122,12 @@ impl ContextManager {
     }
 
     /// `items` is ordered from oldest to newest.
-    pub(crate) fn record_items<I>(&mut self, items: I, policy: TruncationPolicy)
-    where
+    pub(crate) fn record_items<I>(
+        &mut self,
+        items: I,
+        policy: TruncationPolicy,
+        tokenizer: &dyn Tokenizer,
+    ) where
         I: IntoIterator,
         I::Item: std::ops::Deref<Target = ResponseItem>,
     {
@@ -74,7 +138,7 @@ impl ContextManager {
                 continue;
             }
 
-            let processed = self.process_item(item_ref, policy);
+            let processed = self.process_item(item_ref, policy, tokenizer);
             self.items.push(processed);
         }
     }
@@ -95,34 +159,32 @@ impl ContextManager {
         &self.items
     }
 
-    // Estimate token usage using byte-based heuristics from the truncation helpers.
-    // This is a coarse lower bound, not a tokenizer-accurate count.
+    // Estimate token usage with the turn's tokenizer. Items are counted as the
+    // JSON the model sees, so this is still an estimate of the billed prompt.
     pub(crate) fn estimate_token_count(&self, turn_context: &TurnContext) -> Option<i64> {
         let model_info = &turn_context.model_info;
         let personality = turn_context.personality.or(turn_context.config.personality);
         let base_instructions = BaseInstructions {
             text: model_info.get_model_instructions(personality),
         };
-        self.estimate_token_count_with_base_instructions(&base_instructions)
+        self.estimate_token_count_with_base_instructions(&base_instructions, turn_context.tokenizer)
     }
 
     pub(crate) fn estimate_token_count_with_base_instructions(
         &self,
         base_instructions: &BaseInstructions,
+        tokenizer: &dyn Tokenizer,
     ) -> Option<i64> {
         let base_tokens =
-            i64::try_from(approx_token_count(&base_instructions.text)).unwrap_or(i64::MAX);
+            i64::try_from(tokenizer.count_tokens(&base_instructions.text)).unwrap_or(i64::MAX);
 
-        let items_tokens = self
-            .items
-            .iter()
-            .map(estimate_item_token_count)
-            .fold(0i64, i64::saturating_add);
+        let items_tokens = self.sum_item_tokens(0, tokenizer, |_| true);
 
         Some(base_tokens.saturating_add(items_tokens))
     }
 
     pub(crate) fn remove_first_item(&mut self) {
+        self.item_tokens.clear();
         if !self.items.is_empty() {
             // Remove the oldest item (front of the list). Items are ordered from
             // oldest → newest, so index 0 is the first entry recorded.
@@ -135,6 +197,7 @@ impl ContextManager {
     }
 
     pub(crate) fn remove_last_item(&mut self) -> bool {
+        self.item_tokens.clear();
         if let Some(removed) = self.items.pop() {
             normalize::remove_corresponding_for(&mut self.items, &removed);
             true
@@ -145,6 +208,7 @@ impl ContextManager {
 
     pub(crate) fn replace(&mut self, items: Vec<ResponseItem>) {
         self.items = items;
+        self.item_tokens.clear();
     }
 
     /// Replace image content in the last turn if it originated from a tool output.
@@ -157,6 +221,7 @@ impl ContextManager {
             return false;
         };
 
+        self.item_tokens.clear();
         match &mut self.items[index] {
             ResponseItem::FunctionCallOutput { output, .. } => {
                 let Some(content_items) = output.content_items_mut() else {
@@ -222,7 +287,7 @@ impl ContextManager {
         );
     }
 
-    fn get_non_last_reasoning_items_tokens(&self) -> i64 {
+    fn get_non_last_reasoning_items_tokens(&self, tokenizer: &dyn Tokenizer) -> i64 {
         // Get reasoning items excluding all the ones after the last user message.
         let Some(last_user_index) = self
             .items
@@ -232,56 +297,80 @@ impl ContextManager {
             return 0;
         };
 
-        self.items
-            .iter()
-            .take(last_user_index)
-            .filter(|item| {
-                matches!(
-                    item,
+        self.sum_item_tokens(0, tokenizer, |index| {
+            index < last_user_index
+                && matches!(
+                    self.items[index],
                     ResponseItem::Reasoning {
                         encrypted_content: Some(_),
                         ..
                     }
                 )
+        })
+    }
+
+    /// Sums the cached token counts of items from `start` on that match `include`.
+    fn sum_item_tokens(
+        &self,
+        start: usize,
+        tokenizer: &dyn Tokenizer,
+        include: impl Fn(usize) -> bool,
+    ) -> i64 {
+        self.item_tokens
+            .with_counts(&self.items, tokenizer, |counts| {
+                counts
+                    .iter()
+                    .enumerate()
+                    .skip(start)
+                    .filter(|(index, _)| include(*index))
+                    .map(|(_, count)| *count)
+                    .fold(0i64, i64::saturating_add)
             })
-            .map(estimate_item_token_count)
-            .fold(0i64, i64::saturating_add)
     }
 
     // These are local items added after the most recent model-emitted item.
     // They are not reflected in `last_token_usage.total_tokens`.
     fn items_after_last_model_generated_item(&self) -> &[ResponseItem] {
-        let start = self
-            .items
+        &self.items[self.first_item_after_last_model_generated_item()..]
+    }
+
+    fn first_item_after_last_model_generated_item(&self) -> usize {
+        self.items
             .iter()
             .rposition(is_model_generated_item)
-            .map_or(self.items.len(), |index| index.saturating_add(1));
-        &self.items[start..]
+            .map_or(self.items.len(), |index| index.saturating_add(1))
     }
 
     /// When true, the server already accounted for past reasoning tokens and
     /// the client should not re-estimate them.
-    pub(crate) fn get_total_token_usage(&self, server_reasoning_included: bool) -> i64 {
+    pub(crate) fn get_total_token_usage(
+        &self,
+        server_reasoning_included: bool,
+        tokenizer: &dyn Tokenizer,
+    ) -> i64 {
         let last_tokens = self
             .token_info
             .as_ref()
             .map(|info| info.last_token_usage.total_tokens)
             .unwrap_or(0);
-        let items_after_last_model_generated_tokens = self
-            .items_after_last_model_generated_item()
-            .iter()
-            .map(estimate_item_token_count)
-            .fold(0i64, i64::saturating_add);
+        let items_after_last_model_generated_tokens = self.sum_item_tokens(
+            self.first_item_after_last_model_generated_item(),
+            tokenizer,
+            |_| true,
+        );
         if server_reasoning_included {
             last_tokens.saturating_add(items_after_last_model_generated_tokens)
         } else {
             last_tokens
-                .saturating_add(self.get_non_last_reasoning_items_tokens())
+                .saturating_add(self.get_non_last_reasoning_items_tokens(tokenizer))
                 .saturating_add(items_after_last_model_generated_tokens)
         }
     }
 
-    pub(crate) fn get_total_token_usage_breakdown(&self) -> TotalTokenUsageBreakdown {
+    pub(crate) fn get_total_token_usage_breakdown(
+        &self,
+        tokenizer: &dyn Tokenizer,
+    ) -> TotalTokenUsageBreakdown {
         let last_usage = self
             .token_info
             .as_ref()
@@ -296,11 +385,12 @@ impl ContextManager {
                 .iter()
                 .map(estimate_response_item_model_visible_bytes)
                 .fold(0i64, i64::saturating_add),
-            estimated_tokens_of_items_added_since_last_successful_api_response:
-                items_after_last_model_generated
-                    .iter()
-                    .map(estimate_item_token_count)
-                    .fold(0i64, i64::saturating_add),
+            estimated_tokens_of_items_added_since_last_successful_api_response: self
+                .sum_item_tokens(
+                    self.first_item_after_last_model_generated_item(),
+                    tokenizer,
+                    |_| true,
+                ),
             estimated_bytes_of_items_added_since_last_successful_api_response:
                 items_after_last_model_generated
                     .iter()
@@ -314,6 +404,8 @@ impl ContextManager {
     /// 2. every output has a corresponding call entry
     /// 3. when images are unsupported, image content is stripped from messages and tool outputs
     fn normalize_history(&mut self, input_modalities: &[InputModality]) {
+        self.item_tokens.clear();
+
         // all function/tool calls must have a corresponding output
         normalize::ensure_call_outputs_present(&mut self.items);
 
@@ -324,19 +416,42 @@ impl ContextManager {
         normalize::strip_images_when_unsupported(input_modalities, &mut self.items);
     }
 
-    fn process_item(&self, item: &ResponseItem, policy: TruncationPolicy) -> ResponseItem {
+    fn process_item(
+        &self,
+        item: &ResponseItem,
+        policy: TruncationPolicy,
+        tokenizer: &dyn Tokenizer,
+    ) -> ResponseItem {
         let policy_with_serialization_budget = policy * 1.2;
+        // Tool output is redacted before it is truncated so a secret cut in
+        // half still disappears.
         match item {
             ResponseItem::FunctionCallOutput { call_id, output } => {
                 let body = match &output.body {
-                    FunctionCallOutputBody::Text(content) => FunctionCallOutputBody::Text(
-                        truncate_text(content, policy_with_serialization_budget),
-                    ),
+                    FunctionCallOutputBody::Text(content) => {
+                        FunctionCallOutputBody::Text(truncate_text(
+                            &redact_secret_values(content),
+                            policy_with_serialization_budget,
+                            tokenizer,
+                        ))
+                    }
                     FunctionCallOutputBody::ContentItems(items) => {
+                        let items: Vec<FunctionCallOutputContentItem> = items
+                            .iter()
+                            .map(|item| match item {
+                                FunctionCallOutputContentItem::InputText { text } => {
+                                    FunctionCallOutputContentItem::InputText {
+                                        text: redact_secret_values(text).into_owned(),
+                                    }
+                                }
+                                FunctionCallOutputContentItem::InputImage { .. } => item.clone(),
+                            })
+                            .collect();
                         FunctionCallOutputBody::ContentItems(
                             truncate_function_output_items_with_policy(
-                                items,
+                                &items,
                                 policy_with_serialization_budget,
+                                tokenizer,
                             ),
                         )
                     }
@@ -350,7 +465,11 @@ impl ContextManager {
                 }
             }
             ResponseItem::CustomToolCallOutput { call_id, output } => {
-                let truncated = truncate_text(output, policy_with_serialization_budget);
+                let truncated = truncate_text(
+                    &redact_secret_values(output),
+                    policy_with_serialization_budget,
+                    tokenizer,
+                );
                 ResponseItem::CustomToolCallOutput {
                     call_id: call_id.clone(),
                     output: truncated,
@@ -395,9 +514,23 @@ fn estimate_reasoning_length(encoded_len: usize) -> usize {
         .saturating_sub(650)
 }
 
-fn estimate_item_token_count(item: &ResponseItem) -> i64 {
-    let model_visible_bytes = estimate_response_item_model_visible_bytes(item);
-    approx_tokens_from_byte_count_i64(model_visible_bytes)
+fn estimate_item_token_count(item: &ResponseItem, tokenizer: &dyn Tokenizer) -> i64 {
+    match item {
+        ResponseItem::GhostSnapshot { .. } => 0,
+        // Encrypted payloads are opaque; size them from their encoded length.
+        ResponseItem::Reasoning {
+            encrypted_content: Some(_),
+            ..
+        }
+        | ResponseItem::Compaction { .. } => {
+            approx_tokens_from_byte_count_i64(estimate_response_item_model_visible_bytes(item))
+        }
+        item => serde_json::to_string(item)
+            .map(|serialized| {
+                i64::try_from(tokenizer.count_tokens(&serialized)).unwrap_or(i64::MAX)
+            })
+            .unwrap_or_default(),
+    }
 }
 
 pub(crate) fn estimate_response_item_model_visible_bytes(item: &ResponseItem) -> i64 {
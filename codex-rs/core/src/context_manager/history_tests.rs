@@ -1,4 +1,7 @@
 use super::*;
+use crate::tokenizer::BpeTokenizer;
+use crate::tokenizer::HeuristicTokenizer;
+use crate::tokenizer::Tokenizer;
 use crate::truncate;
 use crate::truncate::TruncationPolicy;
 use codex_git::GhostCommit;
@@ -36,7 +39,11 @@ fn create_history_with_items(items: Vec<ResponseItem>) -> ContextManager {
     let mut h = ContextManager::new();
     // Use a generous but fixed token budget; tests only rely on truncation
     // behavior, not on a specific model's token limit.
-    h.record_items(items.iter(), TruncationPolicy::Tokens(10_000));
+    h.record_items(
+        items.iter(),
+        TruncationPolicy::Tokens(10_000),
+        &HeuristicTokenizer,
+    );
     h
 }
 
@@ -96,7 +103,11 @@ fn reasoning_with_encrypted_content(len: usize) -> ResponseItem {
 }
 
 fn truncate_exec_output(content: &str) -> String {
-    truncate::truncate_text(content, TruncationPolicy::Tokens(EXEC_FORMAT_MAX_TOKENS))
+    truncate::truncate_text(
+        content,
+        TruncationPolicy::Tokens(EXEC_FORMAT_MAX_TOKENS),
+        &HeuristicTokenizer,
+    )
 }
 
 fn approx_token_count_for_text(text: &str) -> i64 {
@@ -118,12 +129,16 @@ fn filters_non_api_messages() {
         phase: None,
     };
     let reasoning = reasoning_msg("thinking...");
-    h.record_items([&system, &reasoning, &ResponseItem::Other], policy);
+    h.record_items(
+        [&system, &reasoning, &ResponseItem::Other],
+        policy,
+        &HeuristicTokenizer,
+    );
 
     // User and assistant should be retained.
     let u = user_msg("hi");
     let a = assistant_msg("hello");
-    h.record_items([&u, &a], policy);
+    h.record_items([&u, &a], policy, &HeuristicTokenizer);
 
     let items = h.raw_items();
     assert_eq!(
@@ -190,15 +205,18 @@ fn items_after_last_model_generated_tokens_include_user_and_tool_output() {
         user_msg("new user message"),
         custom_tool_call_output("call-tail", "new tool output"),
     ]);
-    let expected_tokens = estimate_item_token_count(&user_msg("new user message")).saturating_add(
-        estimate_item_token_count(&custom_tool_call_output("call-tail", "new tool output")),
-    );
+    let expected_tokens =
+        estimate_item_token_count(&user_msg("new user message"), &HeuristicTokenizer)
+            .saturating_add(estimate_item_token_count(
+                &custom_tool_call_output("call-tail", "new tool output"),
+                &HeuristicTokenizer,
+            ));
 
     assert_eq!(
         history
             .items_after_last_model_generated_item()
             .iter()
-            .map(estimate_item_token_count)
+            .map(|item| estimate_item_token_count(item, &HeuristicTokenizer))
             .fold(0i64, i64::saturating_add),
         expected_tokens
     );
@@ -212,7 +230,7 @@ fn items_after_last_model_generated_tokens_are_zero_without_model_generated_item
         history
             .items_after_last_model_generated_item()
             .iter()
-            .map(estimate_item_token_count)
+            .map(|item| estimate_item_token_count(item, &HeuristicTokenizer))
             .fold(0i64, i64::saturating_add),
         0
     );
@@ -233,12 +251,13 @@ fn total_token_usage_includes_all_items_after_last_model_generated_item() {
     history.record_items(
         [&added_user, &added_tool_output],
         TruncationPolicy::Tokens(10_000),
+        &HeuristicTokenizer,
     );
 
     assert_eq!(
-        history.get_total_token_usage(true),
-        100 + estimate_item_token_count(&added_user)
-            + estimate_item_token_count(&added_tool_output)
+        history.get_total_token_usage(true, &HeuristicTokenizer),
+        100 + estimate_item_token_count(&added_user, &HeuristicTokenizer)
+            + estimate_item_token_count(&added_tool_output, &HeuristicTokenizer)
     );
 }
 
@@ -372,10 +391,10 @@ fn estimate_token_count_with_base_instructions_uses_provided_text() {
     };
 
     let short_estimate = history
-        .estimate_token_count_with_base_instructions(&short_base)
+        .estimate_token_count_with_base_instructions(&short_base, &HeuristicTokenizer)
         .expect("token estimate");
     let long_estimate = history
-        .estimate_token_count_with_base_instructions(&long_base)
+        .estimate_token_count_with_base_instructions(&long_base, &HeuristicTokenizer)
         .expect("token estimate");
 
     let expected_delta = approx_token_count_for_text(&long_base.text)
@@ -383,6 +402,85 @@ fn estimate_token_count_with_base_instructions_uses_provided_text() {
     assert_eq!(long_estimate - short_estimate, expected_delta);
 }
 
+#[test]
+fn bpe_estimate_tracks_appended_and_replaced_items() {
+    let tokenizer = BpeTokenizer::O200K_BASE;
+    let base = BaseInstructions {
+        text: String::new(),
+    };
+    let bpe_tokens = |items: &[ResponseItem]| -> i64 {
+        items
+            .iter()
+            .map(|item| {
+                let serialized = serde_json::to_string(item).expect("serialize item");
+                i64::try_from(tokenizer.count_tokens(&serialized)).expect("token count")
+            })
+            .sum()
+    };
+
+    let first = vec![user_msg("how many tokens is this?"), assistant_msg("a few")];
+    let mut history = create_history_with_items(first.clone());
+    assert_eq!(
+        history.estimate_token_count_with_base_instructions(&base, &tokenizer),
+        Some(bpe_tokens(&first))
+    );
+
+    let appended = user_msg(&"lorem ipsum dolor sit amet ".repeat(20));
+    history.record_items(
+        [&appended],
+        TruncationPolicy::Tokens(10_000),
+        &HeuristicTokenizer,
+    );
+    let mut all = first;
+    all.push(appended);
+    assert_eq!(
+        history.estimate_token_count_with_base_instructions(&base, &tokenizer),
+        Some(bpe_tokens(&all))
+    );
+
+    let replaced = vec![assistant_msg("summary")];
+    history.replace(replaced.clone());
+    assert_eq!(
+        history.estimate_token_count_with_base_instructions(&base, &tokenizer),
+        Some(bpe_tokens(&replaced))
+    );
+
+    // Switching tokenizers recounts instead of reusing the BPE counts.
+    let serialized = serde_json::to_string(&replaced[0]).expect("serialize item");
+    assert_eq!(
+        history.estimate_token_count_with_base_instructions(&base, &HeuristicTokenizer),
+        Some(approx_token_count_for_text(&serialized))
+    );
+}
+
+#[test]
+fn record_items_redacts_registered_secret_values() {
+    let name = codex_secrets::SecretName::new("HISTORY_TEST_TOKEN").expect("valid name");
+    codex_secrets::register_secret_value(&name, "history-secret-0451");
+    let history = create_history_with_items(vec![
+        ResponseItem::FunctionCallOutput {
+            call_id: "call-1".to_string(),
+            output: FunctionCallOutputPayload::from_text(
+                "token: history-secret-0451\n".to_string(),
+            ),
+        },
+        custom_tool_call_output("call-2", "echo history-secret-0451"),
+    ]);
+
+    assert_eq!(
+        history.raw_items(),
+        vec![
+            ResponseItem::FunctionCallOutput {
+                call_id: "call-1".to_string(),
+                output: FunctionCallOutputPayload::from_text(
+                    "token: [REDACTED:HISTORY_TEST_TOKEN]\n".to_string(),
+                ),
+            },
+            custom_tool_call_output("call-2", "echo [REDACTED:HISTORY_TEST_TOKEN]"),
+        ]
+    );
+}
+
 #[test]
 fn remove_first_item_removes_matching_output_for_function_call() {
     let items = vec![
@@ -712,7 +810,7 @@ fn record_items_truncates_function_call_output_content() {
         },
     };
 
-    history.record_items([&item], policy);
+    history.record_items([&item], policy, &HeuristicTokenizer);
 
     assert_eq!(history.items.len(), 1);
     match &history.items[0] {
@@ -743,7 +841,7 @@ fn record_items_truncates_custom_tool_call_output_content() {
         output: long_output.clone(),
     };
 
-    history.record_items([&item], policy);
+    history.record_items([&item], policy, &HeuristicTokenizer);
 
     assert_eq!(history.items.len(), 1);
     match &history.items[0] {
@@ -775,7 +873,7 @@ fn record_items_respects_custom_token_limit() {
         },
     };
 
-    history.record_items([&item], policy);
+    history.record_items([&item], policy, &HeuristicTokenizer);
 
     let stored = match &history.items[0] {
         ResponseItem::FunctionCallOutput { output, .. } => output,
@@ -1,6 +1,7 @@
 use crate::exec::ExecToolCallOutput;
 use crate::token_data::KnownPlan;
 use crate::token_data::PlanType;
+use crate::tokenizer::default_tokenizer;
 use crate::truncate::TruncationPolicy;
 use crate::truncate::truncate_text;
 use chrono::DateTime;
@@ -70,6 +71,11 @@ pub enum CodexErr {
     #[error("stream disconnected before completion: {0}")]
     Stream(String, Option<Duration>),
 
+    /// Like [`CodexErr::Stream`], but the stream was dropped because it stayed
+    /// silent for longer than the provider's idle timeout.
+    #[error("stream disconnected before completion: {0}")]
+    StreamIdleTimeout(String),
+
     #[error(
         "Codex ran out of room in the model's context window. Start a new thread or clear earlier history before retrying."
     )]
@@ -211,6 +217,7 @@ impl CodexErr {
             | CodexErr::UsageLimitReached(_)
             | CodexErr::ServerOverloaded => false,
             CodexErr::Stream(..)
+            | CodexErr::StreamIdleTimeout(_)
             | CodexErr::Timeout
             | CodexErr::UnexpectedStatus(_)
             | CodexErr::ResponseStreamFailed(_)
@@ -643,6 +650,7 @@ pub fn get_error_message_ui(e: &CodexErr) -> String {
     truncate_text(
         &message,
         TruncationPolicy::Bytes(ERROR_MESSAGE_UI_MAX_BYTES),
+        default_tokenizer(),
     )
 }
 
@@ -4,11 +4,13 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use arc_swap::ArcSwap;
+use chrono::Utc;
 
 use crate::config_loader::ConfigLayerStack;
 use crate::config_loader::ConfigLayerStackOrdering;
 use crate::is_dangerous_command::command_might_be_dangerous;
 use crate::is_safe_command::is_known_safe_command;
+use crate::remembered_approvals::RememberedPrefix;
 use codex_execpolicy::AmendError;
 use codex_execpolicy::Decision;
 use codex_execpolicy::Error as ExecPolicyRuleError;
@@ -17,6 +19,7 @@ use codex_execpolicy::Policy;
 use codex_execpolicy::PolicyParser;
 use codex_execpolicy::RuleMatch;
 use codex_execpolicy::blocking_append_allow_prefix_rule;
+use codex_protocol::approvals::DecisionProvenance;
 use codex_protocol::approvals::ExecPolicyAmendment;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::SandboxPolicy;
@@ -77,10 +80,18 @@ pub enum ExecPolicyUpdateError {
         #[from]
         source: ExecPolicyRuleError,
     },
+
+    #[error("failed to save remembered approval: {source}")]
+    RememberApproval { source: std::io::Error },
 }
 
 pub(crate) struct ExecPolicyManager {
+    /// Rules loaded from rules files, plus amendments appended to them.
+    base: ArcSwap<Policy>,
+    /// `base` with the repository's remembered command prefixes layered on top.
     policy: ArcSwap<Policy>,
+    /// The prefixes layered into `policy`, kept so expired ones can be dropped.
+    remembered: ArcSwap<Vec<RememberedPrefix>>,
 }
 
 pub(crate) struct ExecApprovalRequest<'a> {
@@ -94,7 +105,9 @@ pub(crate) struct ExecApprovalRequest<'a> {
 impl ExecPolicyManager {
     pub(crate) fn new(policy: Arc<Policy>) -> Self {
         Self {
+            base: ArcSwap::from(Arc::clone(&policy)),
             policy: ArcSwap::from(policy),
+            remembered: ArcSwap::from_pointee(Vec::new()),
         }
     }
 
@@ -107,9 +120,43 @@ impl ExecPolicyManager {
     }
 
     pub(crate) fn current(&self) -> Arc<Policy> {
+        self.drop_expired_remembered_prefixes();
         self.policy.load_full()
     }
 
+    /// Replaces the remembered command prefixes layered on top of the rules
+    /// files.
+    pub(crate) fn set_remembered_prefixes(
+        &self,
+        prefixes: &[RememberedPrefix],
+    ) -> Result<(), ExecPolicyRuleError> {
+        let mut policy = self.base.load_full().as_ref().clone();
+        for remembered in prefixes {
+            policy.add_prefix_rule(&remembered.prefix, remembered.decision)?;
+        }
+        self.policy.store(Arc::new(policy));
+        self.remembered.store(Arc::new(prefixes.to_vec()));
+        Ok(())
+    }
+
+    /// Rebuilds the policy without remembered prefixes whose expiry has
+    /// passed, so a decision lapses mid-session instead of at the next reload.
+    fn drop_expired_remembered_prefixes(&self) {
+        let remembered = self.remembered.load();
+        let now = Utc::now();
+        if !remembered.iter().any(|prefix| prefix.is_expired(now)) {
+            return;
+        }
+        let active: Vec<RememberedPrefix> = remembered
+            .iter()
+            .filter(|prefix| !prefix.is_expired(now))
+            .cloned()
+            .collect();
+        if let Err(err) = self.set_remembered_prefixes(&active) {
+            tracing::warn!("failed to drop expired remembered command approvals: {err}");
+        }
+    }
+
     pub(crate) async fn create_exec_approval_requirement_for_command(
         &self,
         req: ExecApprovalRequest<'_>,
@@ -140,14 +187,24 @@ impl ExecPolicyManager {
         let requested_amendment =
             derive_requested_execpolicy_amendment(prefix_rule.as_ref(), &evaluation.matched_rules);
 
+        let provenance = derive_decision_provenance(
+            &exec_policy,
+            &evaluation,
+            approval_policy,
+            sandbox_policy,
+            sandbox_permissions,
+        );
+
         match evaluation.decision {
             Decision::Forbidden => ExecApprovalRequirement::Forbidden {
                 reason: derive_forbidden_reason(command, &evaluation),
+                provenance,
             },
             Decision::Prompt => {
                 if matches!(approval_policy, AskForApproval::Never) {
                     ExecApprovalRequirement::Forbidden {
                         reason: PROMPT_CONFLICT_REASON.to_string(),
+                        provenance,
                     }
                 } else {
                     ExecApprovalRequirement::NeedsApproval {
@@ -161,6 +218,7 @@ impl ExecPolicyManager {
                                 None
                             }
                         }),
+                        provenance,
                     }
                 }
             }
@@ -174,6 +232,7 @@ impl ExecPolicyManager {
                 } else {
                     None
                 },
+                provenance,
             },
         }
     }
@@ -197,6 +256,9 @@ impl ExecPolicyManager {
             source,
         })?;
 
+        let mut updated_base = self.base.load_full().as_ref().clone();
+        updated_base.add_prefix_rule(&prefix, Decision::Allow)?;
+        self.base.store(Arc::new(updated_base));
         let mut updated_policy = self.current().as_ref().clone();
         updated_policy.add_prefix_rule(&prefix, Decision::Allow)?;
         self.policy.store(Arc::new(updated_policy));
@@ -283,8 +345,25 @@ pub fn render_decision_for_unmatched_command(
     command: &[String],
     sandbox_permissions: SandboxPermissions,
 ) -> Decision {
+    render_unmatched_command(
+        approval_policy,
+        sandbox_policy,
+        command,
+        sandbox_permissions,
+    )
+    .0
+}
+
+/// Like [`render_decision_for_unmatched_command`], but also reports which
+/// heuristic produced the decision.
+fn render_unmatched_command(
+    approval_policy: AskForApproval,
+    sandbox_policy: &SandboxPolicy,
+    command: &[String],
+    sandbox_permissions: SandboxPermissions,
+) -> (Decision, DecisionProvenance) {
     if is_known_safe_command(command) {
-        return Decision::Allow;
+        return (Decision::Allow, DecisionProvenance::KnownSafeCommand);
     }
 
     // On Windows, ReadOnly sandbox is not a real sandbox, so special-case it
@@ -298,15 +377,22 @@ pub fn render_decision_for_unmatched_command(
     // We prefer to prompt the user rather than outright forbid the command,
     // but if the user has explicitly disabled prompts, we must
     // forbid the command.
-    if command_might_be_dangerous(command) || runtime_sandbox_provides_safety {
-        return if matches!(approval_policy, AskForApproval::Never) {
+    let command_is_dangerous = command_might_be_dangerous(command);
+    if command_is_dangerous || runtime_sandbox_provides_safety {
+        let decision = if matches!(approval_policy, AskForApproval::Never) {
             Decision::Forbidden
         } else {
             Decision::Prompt
         };
+        let provenance = if command_is_dangerous {
+            DecisionProvenance::DangerousCommand
+        } else {
+            DecisionProvenance::ApprovalPolicy { approval_policy }
+        };
+        return (decision, provenance);
     }
 
-    match approval_policy {
+    let decision = match approval_policy {
         AskForApproval::Never | AskForApproval::OnFailure => {
             // We allow the command to run, relying on the sandbox for
             // protection.
@@ -337,7 +423,11 @@ pub fn render_decision_for_unmatched_command(
                 }
             }
         }
-    }
+    };
+    (
+        decision,
+        DecisionProvenance::ApprovalPolicy { approval_policy },
+    )
 }
 
 fn default_policy_path(codex_home: &Path) -> PathBuf {
@@ -429,6 +519,58 @@ fn derive_requested_execpolicy_amendment(
     Some(ExecPolicyAmendment::new(prefix_rule.clone()))
 }
 
+/// Explain which rule or heuristic produced `evaluation.decision`. As with the
+/// prompt and forbidden reasons, the most specific execpolicy rule wins over
+/// heuristics.
+fn derive_decision_provenance(
+    policy: &Policy,
+    evaluation: &Evaluation,
+    approval_policy: AskForApproval,
+    sandbox_policy: &SandboxPolicy,
+    sandbox_permissions: SandboxPermissions,
+) -> DecisionProvenance {
+    let deciding_matches = evaluation
+        .matched_rules
+        .iter()
+        .filter(|rule_match| rule_match.decision() == evaluation.decision);
+
+    let most_specific_rule = deciding_matches
+        .clone()
+        .filter_map(|rule_match| match rule_match {
+            RuleMatch::PrefixRuleMatch {
+                matched_prefix,
+                justification,
+                ..
+            } => Some((rule_match, matched_prefix, justification)),
+            RuleMatch::HeuristicsRuleMatch { .. } => None,
+        })
+        .max_by_key(|(_, matched_prefix, _)| matched_prefix.len());
+    if let Some((rule_match, matched_prefix, justification)) = most_specific_rule {
+        let source = policy.rule_source(rule_match);
+        return DecisionProvenance::ExecPolicyRule {
+            matched_prefix: matched_prefix.clone(),
+            justification: justification.clone(),
+            file: source.map(|source| source.path.clone()),
+            line: source.map(|source| source.line),
+        };
+    }
+
+    deciding_matches
+        .find_map(|rule_match| match rule_match {
+            RuleMatch::HeuristicsRuleMatch { command, .. } => Some(
+                render_unmatched_command(
+                    approval_policy,
+                    sandbox_policy,
+                    command,
+                    sandbox_permissions,
+                )
+                .1,
+            ),
+            RuleMatch::PrefixRuleMatch { .. } => None,
+        })
+        .unwrap_or(DecisionProvenance::ApprovalPolicy { approval_policy })
+}
+
 /// Only return a reason when a policy rule drove the prompt decision.
 fn derive_prompt_reason(command_args: &[String], evaluation: &Evaluation) -> Option<String> {
     let command = render_shlex_command(command_args);
@@ -811,7 +953,13 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         assert_eq!(
             requirement,
             ExecApprovalRequirement::Forbidden {
-                reason: "`bash -lc 'rm -rf /some/important/folder'` rejected: policy forbids commands starting with `rm`".to_string()
+                reason: "`bash -lc 'rm -rf /some/important/folder'` rejected: policy forbids commands starting with `rm`".to_string(),
+                provenance: DecisionProvenance::ExecPolicyRule {
+                    matched_prefix: vec!["rm".to_string()],
+                    justification: None,
+                    file: Some("test.rules".to_string()),
+                    line: Some(2),
+                },
             }
         );
     }
@@ -863,6 +1011,12 @@ prefix_rule(pattern=["rm"], decision="forbidden")
             ExecApprovalRequirement::Skip {
                 bypass_sandbox: true,
                 proposed_execpolicy_amendment: None,
+                provenance: DecisionProvenance::ExecPolicyRule {
+                    matched_prefix: vec!["python3".to_string()],
+                    justification: None,
+                    file: Some("test.rules".to_string()),
+                    line: Some(1),
+                },
             }
         );
     }
@@ -890,6 +1044,9 @@ prefix_rule(pattern=["rm"], decision="forbidden")
             ExecApprovalRequirement::NeedsApproval {
                 reason: None,
                 proposed_execpolicy_amendment: None,
+                provenance: DecisionProvenance::ApprovalPolicy {
+                    approval_policy: AskForApproval::UnlessTrusted,
+                },
             }
         );
     }
@@ -918,6 +1075,9 @@ prefix_rule(pattern=["rm"], decision="forbidden")
             ExecApprovalRequirement::NeedsApproval {
                 reason: None,
                 proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(requested_prefix)),
+                provenance: DecisionProvenance::ApprovalPolicy {
+                    approval_policy: AskForApproval::UnlessTrusted,
+                },
             }
         );
     }
@@ -955,7 +1115,13 @@ prefix_rule(
         assert_eq!(
             requirement,
             ExecApprovalRequirement::Forbidden {
-                reason: "`rm -rf /some/important/folder` rejected: destructive command".to_string()
+                reason: "`rm -rf /some/important/folder` rejected: destructive command".to_string(),
+                provenance: DecisionProvenance::ExecPolicyRule {
+                    matched_prefix: vec!["rm".to_string()],
+                    justification: Some("destructive command".to_string()),
+                    file: Some("test.rules".to_string()),
+                    line: Some(2),
+                },
             }
         );
     }
@@ -986,6 +1152,12 @@ prefix_rule(
             ExecApprovalRequirement::NeedsApproval {
                 reason: Some("`rm` requires approval by policy".to_string()),
                 proposed_execpolicy_amendment: None,
+                provenance: DecisionProvenance::ExecPolicyRule {
+                    matched_prefix: vec!["rm".to_string()],
+                    justification: None,
+                    file: Some("test.rules".to_string()),
+                    line: Some(1),
+                },
             }
         );
     }
@@ -1014,7 +1186,13 @@ prefix_rule(
         assert_eq!(
             requirement,
             ExecApprovalRequirement::Forbidden {
-                reason: PROMPT_CONFLICT_REASON.to_string()
+                reason: PROMPT_CONFLICT_REASON.to_string(),
+                provenance: DecisionProvenance::ExecPolicyRule {
+                    matched_prefix: vec!["rm".to_string()],
+                    justification: None,
+                    file: Some("test.rules".to_string()),
+                    line: Some(1),
+                },
             }
         );
     }
@@ -1038,7 +1216,10 @@ prefix_rule(
             requirement,
             ExecApprovalRequirement::NeedsApproval {
                 reason: None,
-                proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(command))
+                proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(command)),
+                provenance: DecisionProvenance::ApprovalPolicy {
+                    approval_policy: AskForApproval::UnlessTrusted,
+                },
             }
         );
     }
@@ -1063,6 +1244,9 @@ prefix_rule(
             ExecApprovalRequirement::NeedsApproval {
                 reason: None,
                 proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(command)),
+                provenance: DecisionProvenance::ApprovalPolicy {
+                    approval_policy: AskForApproval::UnlessTrusted,
+                },
             }
         );
     }
@@ -1091,6 +1275,9 @@ prefix_rule(
             ExecApprovalRequirement::NeedsApproval {
                 reason: None,
                 proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(command)),
+                provenance: DecisionProvenance::ApprovalPolicy {
+                    approval_policy: AskForApproval::UnlessTrusted,
+                },
             }
         );
     }
@@ -1124,6 +1311,9 @@ prefix_rule(
                     "cargo".to_string(),
                     "install".to_string(),
                 ])),
+                provenance: DecisionProvenance::ApprovalPolicy {
+                    approval_policy: AskForApproval::OnRequest,
+                },
             }
         );
     }
@@ -1156,7 +1346,10 @@ prefix_rule(
                 reason: None,
                 proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec![
                     "orange".to_string()
-                ]))
+                ])),
+                provenance: DecisionProvenance::ApprovalPolicy {
+                    approval_policy: AskForApproval::UnlessTrusted,
+                },
             }
         );
     }
@@ -1212,6 +1405,67 @@ prefix_rule(
         ));
     }
 
+    #[test]
+    fn remembered_prefixes_replace_each_other_but_keep_base_rules() {
+        let manager = ExecPolicyManager::default();
+        let check = |command: &[&str]| {
+            let command: Vec<String> = command.iter().map(ToString::to_string).collect();
+            manager
+                .current()
+                .check(&command, &|_| Decision::Prompt)
+                .decision
+        };
+        let remembered = |tokens: &[&str], decision| RememberedPrefix {
+            prefix: tokens.iter().map(ToString::to_string).collect(),
+            decision,
+            expires_at: None,
+        };
+
+        manager
+            .set_remembered_prefixes(&[
+                remembered(&["cargo", "test"], Decision::Allow),
+                remembered(&["rm"], Decision::Forbidden),
+            ])
+            .expect("set remembered prefixes");
+        assert_eq!(check(&["cargo", "test", "-p", "core"]), Decision::Allow);
+        assert_eq!(check(&["rm", "-rf", "target"]), Decision::Forbidden);
+
+        manager
+            .set_remembered_prefixes(&[remembered(&["make"], Decision::Allow)])
+            .expect("set remembered prefixes");
+        assert_eq!(check(&["cargo", "test"]), Decision::Prompt);
+        assert_eq!(check(&["rm", "-rf", "target"]), Decision::Prompt);
+        assert_eq!(check(&["make"]), Decision::Allow);
+    }
+
+    #[test]
+    fn expired_remembered_prefixes_stop_applying() {
+        let manager = ExecPolicyManager::default();
+        manager
+            .set_remembered_prefixes(&[
+                RememberedPrefix {
+                    prefix: vec!["make".to_string()],
+                    decision: Decision::Allow,
+                    expires_at: Some(Utc::now() - chrono::Duration::seconds(1)),
+                },
+                RememberedPrefix {
+                    prefix: vec!["cargo".to_string()],
+                    decision: Decision::Allow,
+                    expires_at: None,
+                },
+            ])
+            .expect("set remembered prefixes");
+
+        let check = |command: &str| {
+            manager
+                .current()
+                .check(&[command.to_string()], &|_| Decision::Prompt)
+                .decision
+        };
+        assert_eq!(check("make"), Decision::Prompt);
+        assert_eq!(check("cargo"), Decision::Allow);
+    }
+
     #[tokio::test]
     async fn proposed_execpolicy_amendment_is_present_for_single_command_without_policy_match() {
         let command = vec!["cargo".to_string(), "build".to_string()];
@@ -1231,7 +1485,10 @@ prefix_rule(
             requirement,
             ExecApprovalRequirement::NeedsApproval {
                 reason: None,
-                proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(command))
+                proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(command)),
+                provenance: DecisionProvenance::ApprovalPolicy {
+                    approval_policy: AskForApproval::UnlessTrusted,
+                },
             }
         );
     }
@@ -1262,6 +1519,12 @@ prefix_rule(
             ExecApprovalRequirement::NeedsApproval {
                 reason: Some("`rm` requires approval by policy".to_string()),
                 proposed_execpolicy_amendment: None,
+                provenance: DecisionProvenance::ExecPolicyRule {
+                    matched_prefix: vec!["rm".to_string()],
+                    justification: None,
+                    file: Some("test.rules".to_string()),
+                    line: Some(1),
+                },
             }
         );
     }
@@ -1292,6 +1555,9 @@ prefix_rule(
                     "cargo".to_string(),
                     "build".to_string()
                 ])),
+                provenance: DecisionProvenance::ApprovalPolicy {
+                    approval_policy: AskForApproval::UnlessTrusted,
+                },
             }
         );
     }
@@ -1326,6 +1592,9 @@ prefix_rule(
                 proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec![
                     "apple".to_string()
                 ])),
+                provenance: DecisionProvenance::ApprovalPolicy {
+                    approval_policy: AskForApproval::UnlessTrusted,
+                },
             }
         );
     }
@@ -1350,6 +1619,7 @@ prefix_rule(
             ExecApprovalRequirement::Skip {
                 bypass_sandbox: false,
                 proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(command)),
+                provenance: DecisionProvenance::KnownSafeCommand,
             }
         );
     }
@@ -1380,6 +1650,12 @@ prefix_rule(
             ExecApprovalRequirement::Skip {
                 bypass_sandbox: true,
                 proposed_execpolicy_amendment: None,
+                provenance: DecisionProvenance::ExecPolicyRule {
+                    matched_prefix: vec!["echo".to_string()],
+                    justification: None,
+                    file: Some("test.rules".to_string()),
+                    line: Some(1),
+                },
             }
         );
     }
@@ -1403,6 +1679,7 @@ prefix_rule(
             ExecApprovalRequirement::NeedsApproval {
                 reason: None,
                 proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(command)),
+                provenance: DecisionProvenance::DangerousCommand,
             }
         );
     }
@@ -1441,6 +1718,9 @@ prefix_rule(
                 ExecApprovalRequirement::NeedsApproval {
                     reason: None,
                     proposed_execpolicy_amendment: expected_amendment.clone(),
+                    provenance: DecisionProvenance::ApprovalPolicy {
+                        approval_policy: AskForApproval::OnRequest,
+                    },
                 },
             )
         } else {
@@ -1449,6 +1729,9 @@ prefix_rule(
                 ExecApprovalRequirement::Skip {
                     bypass_sandbox: false,
                     proposed_execpolicy_amendment: expected_amendment.clone(),
+                    provenance: DecisionProvenance::ApprovalPolicy {
+                        approval_policy: AskForApproval::OnRequest,
+                    },
                 },
             )
         };
@@ -1476,6 +1759,7 @@ prefix_rule(
                     "-rf",
                     "/important/data",
                 ]))),
+                provenance: DecisionProvenance::DangerousCommand,
             },
             policy
                 .create_exec_approval_requirement_for_command(ExecApprovalRequest {
@@ -1495,6 +1779,7 @@ prefix_rule(
         assert_eq!(
             ExecApprovalRequirement::Forbidden {
                 reason: "`rm -rf /important/data` rejected: blocked by policy".to_string(),
+                provenance: DecisionProvenance::DangerousCommand,
             },
             policy
                 .create_exec_approval_requirement_for_command(ExecApprovalRequest {
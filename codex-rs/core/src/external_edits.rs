@@ -0,0 +1,326 @@
+//! Attribution of workspace edits made outside Codex.
+//!
+//! The file watcher reports every non-ignored change under the workspace. Changes
+//! to paths an agent's patch touched, and any change observed while a tool that
+//! writes files without reporting them (a shell command, an MCP tool) is running,
+//! are the agents' own; everything else was made by someone else, usually the
+//! user in an editor. Those paths are queued and injected as an `<external_edits>` context
+//! item at the start of the next turn so the model re-reads them instead of
+//! trusting stale tool output.
+
+use std::collections::BTreeSet;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::sync::Weak;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+
+use crate::session_prefix::EXTERNAL_EDITS_OPEN_TAG;
+use crate::tools::context::SharedTurnDiffTracker;
+use crate::turn_diff_tracker::TurnDiffTracker;
+
+/// Watcher events are delivered after a short debounce, so edits the agent
+/// made just before a tool call returned can arrive once it has finished.
+const AGENT_ACTIVITY_GRACE: Duration = Duration::from_secs(1);
+/// Upper bound on paths listed in the context item.
+const MAX_LISTED_PATHS: usize = 50;
+
+/// Tool activity of every agent sharing a workspace watcher.
+///
+/// One instance is shared by all threads of a `ThreadManager`, including
+/// sub-agents, so an edit made by any agent's tool is never reported to another
+/// session as external.
+#[derive(Default)]
+pub(crate) struct AgentActivity {
+    state: Mutex<AgentActivityState>,
+}
+
+#[derive(Default)]
+struct AgentActivityState {
+    running_tools: usize,
+    quiet_until: Option<Instant>,
+    /// Running tools that may write files without reporting which ones.
+    untracked_writers: usize,
+    untracked_quiet_until: Option<Instant>,
+    /// Paths agents reported writing since the tools last went quiet.
+    touched: HashSet<PathBuf>,
+}
+
+/// Marks a tool call as in flight until dropped.
+pub(crate) struct AgentActivityGuard {
+    activity: Arc<AgentActivity>,
+    untracked_writes: bool,
+}
+
+impl Drop for AgentActivityGuard {
+    fn drop(&mut self) {
+        let mut state = self.activity.lock_state();
+        let quiet_until = Some(Instant::now() + AGENT_ACTIVITY_GRACE);
+        state.running_tools = state.running_tools.saturating_sub(1);
+        state.quiet_until = quiet_until;
+        if self.untracked_writes {
+            state.untracked_writers = state.untracked_writers.saturating_sub(1);
+            state.untracked_quiet_until = quiet_until;
+        }
+    }
+}
+
+fn is_busy(running: usize, quiet_until: Option<Instant>) -> bool {
+    running > 0 || quiet_until.is_some_and(|quiet_until| Instant::now() < quiet_until)
+}
+
+impl AgentActivity {
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, AgentActivityState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Note that an agent's tool started running. When `untracked_writes` is
+    /// set, every workspace change is attributed to agents until the returned
+    /// guard is dropped; otherwise only the paths reported via
+    /// [`AgentActivity::note_writes`] are.
+    pub(crate) fn tool_started(self: &Arc<Self>, untracked_writes: bool) -> AgentActivityGuard {
+        let mut state = self.lock_state();
+        if !is_busy(state.running_tools, state.quiet_until) {
+            state.touched.clear();
+        }
+        state.running_tools += 1;
+        if untracked_writes {
+            state.untracked_writers += 1;
+        }
+        AgentActivityGuard {
+            activity: Arc::clone(self),
+            untracked_writes,
+        }
+    }
+
+    fn note_writes<'a>(&self, paths: impl IntoIterator<Item = &'a PathBuf>) {
+        self.lock_state().touched.extend(paths.into_iter().cloned());
+    }
+
+    /// Whether a change to `path` observed now was made by an agent.
+    fn made_change(&self, path: &Path) -> bool {
+        let state = self.lock_state();
+        if !is_busy(state.running_tools, state.quiet_until) {
+            return false;
+        }
+        is_busy(state.untracked_writers, state.untracked_quiet_until)
+            || state.touched.contains(path)
+    }
+}
+
+#[derive(Default)]
+struct ExternalEditsState {
+    pending: BTreeSet<PathBuf>,
+    turn_diff_tracker: Weak<tokio::sync::Mutex<TurnDiffTracker>>,
+}
+
+/// Session-scoped record of files changed outside Codex.
+#[derive(Default)]
+pub(crate) struct ExternalEdits {
+    activity: Arc<AgentActivity>,
+    state: Mutex<ExternalEditsState>,
+}
+
+impl ExternalEdits {
+    pub(crate) fn new(activity: Arc<AgentActivity>) -> Self {
+        Self {
+            activity,
+            state: Mutex::default(),
+        }
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, ExternalEditsState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// The activity tracker shared with the other sessions, for sub-agents spawned
+    /// by this one.
+    pub(crate) fn agent_activity(&self) -> Arc<AgentActivity> {
+        Arc::clone(&self.activity)
+    }
+
+    /// Note that one of this session's tools started running.
+    pub(crate) fn agent_tool_started(&self) -> AgentActivityGuard {
+        self.activity.tool_started(false)
+    }
+
+    /// Note that one of this session's tools may now write files it does not
+    /// report, such as a shell command.
+    pub(crate) fn agent_untracked_writes_started(&self) -> AgentActivityGuard {
+        self.activity.tool_started(true)
+    }
+
+    /// Note that one of this session's tools is about to write `paths`.
+    pub(crate) fn note_agent_writes<'a>(&self, paths: impl IntoIterator<Item = &'a PathBuf>) {
+        self.activity.note_writes(paths);
+    }
+
+    /// Use `tracker` for the turn that is currently running.
+    pub(crate) fn set_turn_diff_tracker(&self, tracker: &SharedTurnDiffTracker) {
+        self.lock_state().turn_diff_tracker = Arc::downgrade(tracker);
+    }
+
+    pub(crate) fn turn_diff_tracker(&self) -> Option<SharedTurnDiffTracker> {
+        self.lock_state().turn_diff_tracker.upgrade()
+    }
+
+    /// Record watcher-reported `paths`. Returns the paths under `cwd` that were
+    /// changed outside Codex; changes made by an agent's tool are dropped.
+    pub(crate) fn record(&self, paths: Vec<PathBuf>, cwd: &Path) -> Vec<PathBuf> {
+        let external: Vec<PathBuf> = paths
+            .into_iter()
+            .filter(|path| path.starts_with(cwd) && !self.activity.made_change(path))
+            .collect();
+        let mut state = self.lock_state();
+        state.pending.extend(external.iter().cloned());
+        external
+    }
+
+    /// Drain the pending paths into a context item for the next turn.
+    pub(crate) fn take_context_item(&self, cwd: &Path) -> Option<ResponseItem> {
+        let pending = std::mem::take(&mut self.lock_state().pending);
+        if pending.is_empty() {
+            return None;
+        }
+        Some(ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: render_external_edits(&pending, cwd),
+            }],
+            end_turn: None,
+            phase: None,
+        })
+    }
+}
+
+fn render_external_edits(paths: &BTreeSet<PathBuf>, cwd: &Path) -> String {
+    let mut text = format!(
+        "{EXTERNAL_EDITS_OPEN_TAG}\nThese files were changed outside Codex since the last turn. Re-read them before relying on earlier contents.\n"
+    );
+    for path in paths.iter().take(MAX_LISTED_PATHS) {
+        let status = if path.exists() { "modified" } else { "deleted" };
+        let display = path.strip_prefix(cwd).unwrap_or(path).display();
+        text.push_str(&format!("- {status}: {display}\n"));
+    }
+    if paths.len() > MAX_LISTED_PATHS {
+        text.push_str(&format!(
+            "- ...and {} more\n",
+            paths.len() - MAX_LISTED_PATHS
+        ));
+    }
+    text.push_str("</external_edits>");
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn changes_during_untracked_writes_are_not_external() {
+        let edits = ExternalEdits::default();
+        let cwd = PathBuf::from("/repo");
+
+        let guard = edits.agent_untracked_writes_started();
+        assert_eq!(
+            edits.record(vec![cwd.join("a.rs")], &cwd),
+            Vec::<PathBuf>::new()
+        );
+        drop(guard);
+        // Still inside the grace period.
+        assert_eq!(
+            edits.record(vec![cwd.join("a.rs")], &cwd),
+            Vec::<PathBuf>::new()
+        );
+        assert_eq!(edits.take_context_item(&cwd), None);
+    }
+
+    #[test]
+    fn only_paths_the_tool_wrote_are_dropped() {
+        let edits = ExternalEdits::default();
+        let cwd = PathBuf::from("/repo");
+
+        let guard = edits.agent_tool_started();
+        edits.note_agent_writes([&cwd.join("patched.rs")]);
+        assert_eq!(
+            edits.record(vec![cwd.join("patched.rs"), cwd.join("user.rs")], &cwd),
+            vec![cwd.join("user.rs")]
+        );
+        drop(guard);
+        // Still inside the grace period.
+        assert_eq!(
+            edits.record(vec![cwd.join("patched.rs")], &cwd),
+            Vec::<PathBuf>::new()
+        );
+
+        // A later tool starts from a clean slate once the agents went quiet.
+        edits.activity.lock_state().quiet_until = Some(Instant::now());
+        let _guard = edits.agent_tool_started();
+        assert_eq!(
+            edits.record(vec![cwd.join("patched.rs")], &cwd),
+            vec![cwd.join("patched.rs")]
+        );
+    }
+
+    #[test]
+    fn other_sessions_tool_activity_is_not_external() {
+        let activity = Arc::new(AgentActivity::default());
+        let edits = ExternalEdits::new(Arc::clone(&activity));
+        let sub_agent = ExternalEdits::new(edits.agent_activity());
+        let cwd = PathBuf::from("/repo");
+
+        let _guard = sub_agent.agent_untracked_writes_started();
+        assert_eq!(
+            edits.record(vec![cwd.join("a.rs")], &cwd),
+            Vec::<PathBuf>::new()
+        );
+        assert_eq!(edits.take_context_item(&cwd), None);
+
+        let unrelated = ExternalEdits::default();
+        assert_eq!(
+            unrelated.record(vec![cwd.join("a.rs")], &cwd),
+            vec![cwd.join("a.rs")]
+        );
+    }
+
+    #[test]
+    fn external_changes_are_listed_once_relative_to_cwd() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let cwd = dir.path().to_path_buf();
+        std::fs::write(cwd.join("kept.rs"), "").expect("write file");
+        let edits = ExternalEdits::default();
+
+        assert_eq!(
+            edits.record(
+                vec![
+                    cwd.join("kept.rs"),
+                    cwd.join("gone.rs"),
+                    PathBuf::from("/elsewhere/x.rs"),
+                ],
+                &cwd,
+            ),
+            vec![cwd.join("kept.rs"), cwd.join("gone.rs")]
+        );
+        edits.record(vec![cwd.join("kept.rs")], &cwd);
+
+        let Some(ResponseItem::Message { content, .. }) = edits.take_context_item(&cwd) else {
+            panic!("expected a context item");
+        };
+        assert_eq!(
+            content,
+            vec![ContentItem::InputText {
+                text: "<external_edits>\nThese files were changed outside Codex since the last turn. Re-read them before relying on earlier contents.\n- deleted: gone.rs\n- modified: kept.rs\n</external_edits>".to_string(),
+            }]
+        );
+        assert_eq!(edits.take_context_item(&cwd), None);
+    }
+}
@@ -1,5 +1,6 @@
-//! Watches skill roots for changes and broadcasts coarse-grained
-//! `FileWatcherEvent`s that higher-level components react to on the next turn.
+//! Watches skill roots and session workspaces for changes and broadcasts
+//! coarse-grained `FileWatcherEvent`s that higher-level components react to on
+//! the next turn.
 
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -8,13 +9,19 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::RwLock;
+use std::sync::Weak;
 use std::time::Duration;
 
+use ignore::Match;
+use ignore::WalkBuilder;
+use ignore::gitignore::Gitignore;
+use ignore::gitignore::GitignoreBuilder;
 use notify::Event;
 use notify::EventKind;
 use notify::RecommendedWatcher;
 use notify::RecursiveMode;
 use notify::Watcher;
+use notify::event::CreateKind;
 use tokio::runtime::Handle;
 use tokio::sync::broadcast;
 use tokio::sync::mpsc;
@@ -23,15 +30,25 @@ use tokio::time::sleep_until;
 use tracing::warn;
 
 use crate::config::Config;
+use crate::features::Feature;
 use crate::skills::loader::skill_roots_from_layer_stack_with_agents;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FileWatcherEvent {
-    SkillsChanged { paths: Vec<PathBuf> },
+    SkillsChanged {
+        paths: Vec<PathBuf>,
+    },
+    /// Files that are not gitignored were created, modified or removed under a
+    /// session workspace. Subscribers decide whether the change was their own.
+    WorkspaceChanged {
+        paths: Vec<PathBuf>,
+    },
 }
 
+#[derive(Default)]
 struct WatchState {
     skills_roots: HashSet<PathBuf>,
+    workspaces: Vec<WorkspaceRoot>,
 }
 
 struct FileWatcherInner {
@@ -40,18 +57,65 @@ struct FileWatcherInner {
 }
 
 const WATCHER_THROTTLE_INTERVAL: Duration = Duration::from_secs(10);
+/// Workspace edits must reach sessions before the user's next message, so they
+/// are only coalesced briefly.
+const WORKSPACE_THROTTLE_INTERVAL: Duration = Duration::from_millis(250);
+/// Upper bound on directories watched per workspace. Workspaces are watched one
+/// directory at a time so that gitignored trees (`target/`, `node_modules/`)
+/// never consume watches.
+const MAX_WORKSPACE_WATCH_DIRS: usize = 4096;
+
+/// A watched workspace and the gitignore rules that apply inside it.
+struct WorkspaceRoot {
+    root: PathBuf,
+    /// Matchers ordered from the deepest directory to the shallowest so the
+    /// closest `.gitignore` decides, as in git.
+    ignores: Vec<Gitignore>,
+}
+
+impl WorkspaceRoot {
+    fn contains(&self, path: &Path) -> bool {
+        path.starts_with(&self.root)
+    }
+
+    fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        if path
+            .components()
+            .any(|component| component.as_os_str() == ".git")
+        {
+            return true;
+        }
+        for gitignore in &self.ignores {
+            if !path.starts_with(gitignore.path()) {
+                continue;
+            }
+            match gitignore.matched_path_or_any_parents(path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        false
+    }
+}
 
 /// Coalesces bursts of paths and emits at most once per interval.
 struct ThrottledPaths {
     pending: HashSet<PathBuf>,
     next_allowed_at: Instant,
+    interval: Duration,
 }
 
 impl ThrottledPaths {
     fn new(now: Instant) -> Self {
+        Self::with_interval(now, WATCHER_THROTTLE_INTERVAL)
+    }
+
+    fn with_interval(now: Instant, interval: Duration) -> Self {
         Self {
             pending: HashSet::new(),
             next_allowed_at: now,
+            interval,
         }
     }
 
@@ -80,13 +144,13 @@ impl ThrottledPaths {
     fn take_with_next_allowed(&mut self, now: Instant) -> Vec<PathBuf> {
         let mut paths: Vec<PathBuf> = self.pending.drain().collect();
         paths.sort_unstable_by(|a, b| a.as_os_str().cmp(b.as_os_str()));
-        self.next_allowed_at = now + WATCHER_THROTTLE_INTERVAL;
+        self.next_allowed_at = now + self.interval;
         paths
     }
 }
 
 pub(crate) struct FileWatcher {
-    inner: Option<Mutex<FileWatcherInner>>,
+    inner: Option<Arc<Mutex<FileWatcherInner>>>,
     state: Arc<RwLock<WatchState>>,
     tx: broadcast::Sender<FileWatcherEvent>,
 }
@@ -103,11 +167,9 @@ impl FileWatcher {
             watched_paths: HashMap::new(),
         };
         let (tx, _) = broadcast::channel(128);
-        let state = Arc::new(RwLock::new(WatchState {
-            skills_roots: HashSet::new(),
-        }));
+        let state = Arc::new(RwLock::new(WatchState::default()));
         let file_watcher = Self {
-            inner: Some(Mutex::new(inner)),
+            inner: Some(Arc::new(Mutex::new(inner))),
             state: Arc::clone(&state),
             tx: tx.clone(),
         };
@@ -119,9 +181,7 @@ impl FileWatcher {
         let (tx, _) = broadcast::channel(1);
         Self {
             inner: None,
-            state: Arc::new(RwLock::new(WatchState {
-                skills_roots: HashSet::new(),
-            })),
+            state: Arc::new(RwLock::new(WatchState::default())),
             tx,
         }
     }
@@ -136,6 +196,9 @@ impl FileWatcher {
         for root in roots {
             self.register_skills_root(root.path);
         }
+        if config.features.enabled(Feature::ExternalEditTracking) {
+            self.register_workspace_root(config.cwd.clone());
+        }
     }
 
     // Bridge `notify`'s callback-based events into the Tokio runtime and
@@ -146,14 +209,22 @@ impl FileWatcher {
         state: Arc<RwLock<WatchState>>,
         tx: broadcast::Sender<FileWatcherEvent>,
     ) {
+        // Hold the watcher weakly: it owns the sender feeding `raw_rx`, so a strong
+        // reference would keep this loop alive after the `FileWatcher` is dropped.
+        let inner = self.inner.as_ref().map(Arc::downgrade);
         if let Ok(handle) = Handle::try_current() {
             handle.spawn(async move {
                 let now = Instant::now();
                 let mut skills = ThrottledPaths::new(now);
+                let mut workspace = ThrottledPaths::with_interval(now, WORKSPACE_THROTTLE_INTERVAL);
 
                 loop {
                     let now = Instant::now();
-                    let next_deadline = skills.next_deadline(now);
+                    let next_deadline =
+                        match (skills.next_deadline(now), workspace.next_deadline(now)) {
+                            (Some(a), Some(b)) => Some(a.min(b)),
+                            (a, b) => a.or(b),
+                        };
                     let timer_deadline = next_deadline
                         .unwrap_or_else(|| now + Duration::from_secs(60 * 60 * 24 * 365));
                     let timer = sleep_until(timer_deadline);
@@ -164,12 +235,25 @@ impl FileWatcher {
                             match res {
                                 Some(Ok(event)) => {
                                     let skills_paths = classify_event(&event, &state);
+                                    let workspace_changes =
+                                        classify_workspace_event(&event, &state);
+                                    if let Some(inner) = inner.as_ref().and_then(Weak::upgrade) {
+                                        for dir in workspace_changes.new_dirs {
+                                            watch_path(&inner, dir, RecursiveMode::NonRecursive);
+                                        }
+                                    }
                                     let now = Instant::now();
                                     skills.add(skills_paths);
+                                    workspace.add(workspace_changes.paths);
 
                                     if let Some(paths) = skills.take_ready(now) {
                                         let _ = tx.send(FileWatcherEvent::SkillsChanged { paths });
                                     }
+                                    if let Some(paths) = workspace.take_ready(now) {
+                                        let _ = tx.send(FileWatcherEvent::WorkspaceChanged {
+                                            paths,
+                                        });
+                                    }
                                 }
                                 Some(Err(err)) => {
                                     warn!("file watcher error: {err}");
@@ -181,6 +265,11 @@ impl FileWatcher {
                                     if let Some(paths) = skills.take_pending(now) {
                                         let _ = tx.send(FileWatcherEvent::SkillsChanged { paths });
                                     }
+                                    if let Some(paths) = workspace.take_pending(now) {
+                                        let _ = tx.send(FileWatcherEvent::WorkspaceChanged {
+                                            paths,
+                                        });
+                                    }
                                     break;
                                 }
                             }
@@ -190,6 +279,11 @@ impl FileWatcher {
                             if let Some(paths) = skills.take_ready(now) {
                                 let _ = tx.send(FileWatcherEvent::SkillsChanged { paths });
                             }
+                            if let Some(paths) = workspace.take_ready(now) {
+                                let _ = tx.send(FileWatcherEvent::WorkspaceChanged {
+                                    paths,
+                                });
+                            }
                         }
                     }
                 }
@@ -210,32 +304,159 @@ impl FileWatcher {
         self.watch_path(root, RecursiveMode::Recursive);
     }
 
+    /// Watch the non-ignored directories of `root` and start reporting edits
+    /// under it. The walk runs off the async runtime since large workspaces
+    /// take a while to enumerate.
+    pub(crate) fn register_workspace_root(&self, root: PathBuf) {
+        {
+            let mut state = match self.state.write() {
+                Ok(state) => state,
+                Err(err) => err.into_inner(),
+            };
+            if state
+                .workspaces
+                .iter()
+                .any(|workspace| root.starts_with(&workspace.root))
+            {
+                return;
+            }
+            state.workspaces.push(WorkspaceRoot {
+                root: root.clone(),
+                ignores: Vec::new(),
+            });
+        }
+        let Some(inner) = self.inner.clone() else {
+            return;
+        };
+        let state = Arc::clone(&self.state);
+        let watch = move || {
+            let dirs = workspace_dirs(&root);
+            let ignores = load_gitignores(&root, &dirs);
+            {
+                let mut state = match state.write() {
+                    Ok(state) => state,
+                    Err(err) => err.into_inner(),
+                };
+                if let Some(workspace) = state
+                    .workspaces
+                    .iter_mut()
+                    .find(|workspace| workspace.root == root)
+                {
+                    workspace.ignores = ignores;
+                }
+            }
+            for dir in dirs {
+                watch_path(&inner, dir, RecursiveMode::NonRecursive);
+            }
+        };
+        match Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn_blocking(watch);
+            }
+            Err(_) => watch(),
+        }
+    }
+
     fn watch_path(&self, path: PathBuf, mode: RecursiveMode) {
         let Some(inner) = &self.inner else {
             return;
         };
-        if !path.exists() {
+        watch_path(inner, path, mode);
+    }
+}
+
+fn watch_path(inner: &Mutex<FileWatcherInner>, path: PathBuf, mode: RecursiveMode) {
+    if !path.exists() {
+        return;
+    }
+    let watch_path = path;
+    let mut guard = match inner.lock() {
+        Ok(guard) => guard,
+        Err(err) => err.into_inner(),
+    };
+    if let Some(existing) = guard.watched_paths.get(&watch_path) {
+        if *existing == RecursiveMode::Recursive || *existing == mode {
             return;
         }
-        let watch_path = path;
-        let mut guard = match inner.lock() {
-            Ok(guard) => guard,
-            Err(err) => err.into_inner(),
-        };
-        if let Some(existing) = guard.watched_paths.get(&watch_path) {
-            if *existing == RecursiveMode::Recursive || *existing == mode {
-                return;
-            }
-            if let Err(err) = guard.watcher.unwatch(&watch_path) {
-                warn!("failed to unwatch {}: {err}", watch_path.display());
+        if let Err(err) = guard.watcher.unwatch(&watch_path) {
+            warn!("failed to unwatch {}: {err}", watch_path.display());
+        }
+    }
+    if let Err(err) = guard.watcher.watch(&watch_path, mode) {
+        warn!("failed to watch {}: {err}", watch_path.display());
+        return;
+    }
+    guard.watched_paths.insert(watch_path, mode);
+}
+
+/// Directories under `root` that git would not ignore, capped at
+/// [`MAX_WORKSPACE_WATCH_DIRS`].
+fn workspace_dirs(root: &Path) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    let walker = WalkBuilder::new(root)
+        .hidden(false)
+        .follow_links(false)
+        .require_git(false)
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .build();
+    for entry in walker.flatten() {
+        if !entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_dir())
+        {
+            continue;
+        }
+        if dirs.len() == MAX_WORKSPACE_WATCH_DIRS {
+            warn!(
+                "not watching more than {MAX_WORKSPACE_WATCH_DIRS} directories under {}",
+                root.display()
+            );
+            break;
+        }
+        dirs.push(entry.into_path());
+    }
+    dirs
+}
+
+/// Collect the `.gitignore` files that apply inside `root`: those in `dirs`
+/// (the workspace's own directories), in its ancestors up to the repository
+/// root, and the repository's `info/exclude`.
+fn load_gitignores(root: &Path, dirs: &[PathBuf]) -> Vec<Gitignore> {
+    let mut ignores = Vec::new();
+    let mut push = |path: &Path| {
+        if path.is_file() {
+            let (gitignore, err) = Gitignore::new(path);
+            if let Some(err) = err {
+                warn!("failed to parse {}: {err}", path.display());
             }
+            ignores.push(gitignore);
         }
-        if let Err(err) = guard.watcher.watch(&watch_path, mode) {
-            warn!("failed to watch {}: {err}", watch_path.display());
-            return;
+    };
+
+    for ancestor in root.ancestors().skip(1) {
+        push(&ancestor.join(".gitignore"));
+        if ancestor.join(".git").exists() {
+            break;
+        }
+    }
+    for dir in dirs {
+        push(&dir.join(".gitignore"));
+    }
+    if let Some(repo_root) = root.ancestors().find(|dir| dir.join(".git").is_dir()) {
+        let exclude = repo_root.join(".git/info/exclude");
+        if exclude.is_file() {
+            let mut builder = GitignoreBuilder::new(repo_root);
+            if let Some(err) = builder.add(&exclude) {
+                warn!("failed to parse {}: {err}", exclude.display());
+            }
+            if let Ok(gitignore) = builder.build() {
+                ignores.push(gitignore);
+            }
         }
-        guard.watched_paths.insert(watch_path, mode);
     }
+
+    ignores.sort_by_key(|gitignore| std::cmp::Reverse(gitignore.path().components().count()));
+    ignores
 }
 
 fn classify_event(event: &Event, state: &RwLock<WatchState>) -> Vec<PathBuf> {
@@ -268,6 +489,50 @@ fn is_skills_path(path: &Path, roots: &HashSet<PathBuf>) -> bool {
     roots.iter().any(|root| path.starts_with(root))
 }
 
+#[derive(Debug, Default, PartialEq)]
+struct WorkspaceChanges {
+    paths: Vec<PathBuf>,
+    /// Newly created directories that need their own watch.
+    new_dirs: Vec<PathBuf>,
+}
+
+fn classify_workspace_event(event: &Event, state: &RwLock<WatchState>) -> WorkspaceChanges {
+    let mut changes = WorkspaceChanges::default();
+    if !matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) {
+        return changes;
+    }
+    let state = match state.read() {
+        Ok(state) => state,
+        Err(err) => err.into_inner(),
+    };
+
+    for path in &event.paths {
+        let Some(workspace) = state
+            .workspaces
+            .iter()
+            .find(|workspace| workspace.contains(path))
+        else {
+            continue;
+        };
+        let is_dir = matches!(event.kind, EventKind::Create(CreateKind::Folder)) || path.is_dir();
+        if workspace.is_ignored(path, is_dir) {
+            continue;
+        }
+        if is_dir {
+            if matches!(event.kind, EventKind::Create(_)) {
+                changes.new_dirs.push(path.clone());
+            }
+            continue;
+        }
+        changes.paths.push(path.clone());
+    }
+
+    changes
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -332,6 +597,7 @@ mod tests {
         let root = path("/tmp/skills");
         let state = RwLock::new(WatchState {
             skills_roots: HashSet::from([root.clone()]),
+            workspaces: Vec::new(),
         });
         let event = notify_event(
             EventKind::Create(CreateKind::Any),
@@ -351,6 +617,7 @@ mod tests {
         let root_b = path("/tmp/workspace/.codex/skills");
         let state = RwLock::new(WatchState {
             skills_roots: HashSet::from([root_a.clone(), root_b.clone()]),
+            workspaces: Vec::new(),
         });
         let event = notify_event(
             EventKind::Modify(ModifyKind::Any),
@@ -373,6 +640,7 @@ mod tests {
         let root = path("/tmp/skills");
         let state = RwLock::new(WatchState {
             skills_roots: HashSet::from([root.clone()]),
+            workspaces: Vec::new(),
         });
         let path = root.join("demo/SKILL.md");
 
@@ -389,6 +657,88 @@ mod tests {
         assert_eq!(classify_event(&other_event, &state), Vec::<PathBuf>::new());
     }
 
+    #[test]
+    fn classify_workspace_event_honors_gitignore() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path().to_path_buf();
+        std::fs::create_dir(root.join(".git")).expect("create .git");
+        std::fs::create_dir_all(root.join("src")).expect("create src");
+        std::fs::create_dir_all(root.join("target")).expect("create target");
+        std::fs::write(root.join(".gitignore"), "target/\n*.log\n").expect("write .gitignore");
+        std::fs::write(root.join("src/.gitignore"), "!keep.log\n")
+            .expect("write nested .gitignore");
+
+        let state = RwLock::new(WatchState {
+            skills_roots: HashSet::new(),
+            workspaces: vec![WorkspaceRoot {
+                root: root.clone(),
+                ignores: load_gitignores(&root, &workspace_dirs(&root)),
+            }],
+        });
+        let event = notify_event(
+            EventKind::Modify(ModifyKind::Any),
+            vec![
+                root.join("src/lib.rs"),
+                root.join("target/debug/out.rs"),
+                root.join("debug.log"),
+                root.join("src/keep.log"),
+                root.join(".git/index"),
+                path("/elsewhere/file.rs"),
+            ],
+        );
+
+        assert_eq!(
+            classify_workspace_event(&event, &state),
+            WorkspaceChanges {
+                paths: vec![root.join("src/lib.rs"), root.join("src/keep.log")],
+                new_dirs: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn classify_workspace_event_reports_new_directories() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path().to_path_buf();
+        std::fs::create_dir_all(root.join("pkg")).expect("create pkg");
+        let state = RwLock::new(WatchState {
+            skills_roots: HashSet::new(),
+            workspaces: vec![WorkspaceRoot {
+                root: root.clone(),
+                ignores: Vec::new(),
+            }],
+        });
+        let event = notify_event(
+            EventKind::Create(CreateKind::Folder),
+            vec![root.join("pkg")],
+        );
+
+        assert_eq!(
+            classify_workspace_event(&event, &state),
+            WorkspaceChanges {
+                paths: Vec::new(),
+                new_dirs: vec![root.join("pkg")],
+            }
+        );
+    }
+
+    #[test]
+    fn workspace_dirs_skip_ignored_trees() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path().to_path_buf();
+        std::fs::create_dir(root.join(".git")).expect("create .git");
+        std::fs::create_dir_all(root.join("src/nested")).expect("create src");
+        std::fs::create_dir_all(root.join("node_modules/dep")).expect("create node_modules");
+        std::fs::write(root.join(".gitignore"), "node_modules/\n").expect("write .gitignore");
+
+        let mut dirs = workspace_dirs(&root);
+        dirs.sort();
+        assert_eq!(
+            dirs,
+            vec![root.clone(), root.join("src"), root.join("src/nested")]
+        );
+    }
+
     #[test]
     fn register_skills_root_dedupes_state_entries() {
         let watcher = FileWatcher::noop();
@@ -177,6 +177,9 @@ fn parse_git_remote_urls(stdout: &str) -> Option<BTreeMap<String, String>> {
     }
 }
 
+/// `<sha> <US> <commit_time> <US> <subject>`, parsed by [`parse_commit_log`].
+const COMMIT_LOG_FORMAT: &str = "%H%x1f%ct%x1f%s";
+
 /// A minimal commit summary entry used for pickers (subject + timestamp + sha).
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CommitLogEntry {
@@ -199,14 +202,13 @@ pub async fn recent_commits(cwd: &Path, limit: usize) -> Vec<CommitLogEntry> {
         return Vec::new();
     }
 
-    let fmt = "%H%x1f%ct%x1f%s"; // <sha> <US> <commit_time> <US> <subject>
     let limit_arg = (limit > 0).then(|| limit.to_string());
     let mut args: Vec<String> = vec!["log".to_string()];
     if let Some(n) = &limit_arg {
         args.push("-n".to_string());
         args.push(n.clone());
     }
-    args.push(format!("--pretty=format:{fmt}"));
+    args.push(format!("--pretty=format:{COMMIT_LOG_FORMAT}"));
     let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
     let Some(log_out) = run_git_command_with_timeout(&arg_refs, cwd).await else {
         return Vec::new();
@@ -215,7 +217,47 @@ pub async fn recent_commits(cwd: &Path, limit: usize) -> Vec<CommitLogEntry> {
         return Vec::new();
     }
 
-    let text = String::from_utf8_lossy(&log_out.stdout);
+    parse_commit_log(&String::from_utf8_lossy(&log_out.stdout))
+}
+
+/// Return the commits in `base..head`, oldest first, so a stack can be walked in the order
+/// it was written. Fails when either ref does not name a commit.
+pub async fn commits_in_range(
+    cwd: &Path,
+    base: &str,
+    head: &str,
+) -> anyhow::Result<Vec<CommitLogEntry>> {
+    for rev in [base, head] {
+        verify_commit(cwd, rev).await?;
+    }
+    let range = format!("{base}..{head}");
+    let pretty = format!("--pretty=format:{COMMIT_LOG_FORMAT}");
+    let output = run_git_command_with_timeout(&["log", "--reverse", &pretty, &range, "--"], cwd)
+        .await
+        .ok_or_else(|| anyhow::anyhow!("git log {range} timed out"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git log {range} failed: {}", stderr.trim());
+    }
+
+    Ok(parse_commit_log(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Fails unless `git rev-parse --verify` resolves `rev` to a commit. Revisions starting with
+/// `-` are rejected so they cannot be passed to git as options.
+async fn verify_commit(cwd: &Path, rev: &str) -> anyhow::Result<()> {
+    if !rev.starts_with('-') {
+        let commit = format!("{rev}^{{commit}}");
+        let output =
+            run_git_command_with_timeout(&["rev-parse", "--verify", "--quiet", &commit], cwd).await;
+        if output.is_some_and(|output| output.status.success()) {
+            return Ok(());
+        }
+    }
+    anyhow::bail!("'{rev}' does not name a commit")
+}
+
+fn parse_commit_log(text: &str) -> Vec<CommitLogEntry> {
     let mut entries: Vec<CommitLogEntry> = Vec::new();
     for line in text.lines() {
         let mut parts = line.split('\u{001f}');
@@ -804,6 +846,45 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_commits_in_range_lists_oldest_first() {
+        skip_if_sandbox!();
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let repo_path = create_test_git_repo(&temp_dir).await;
+
+        for (contents, message) in [("one", "first change"), ("two", "second change")] {
+            fs::write(repo_path.join("file.txt"), contents).unwrap();
+            Command::new("git")
+                .args(["add", "file.txt"])
+                .current_dir(&repo_path)
+                .output()
+                .await
+                .expect("git add");
+            Command::new("git")
+                .args(["commit", "-m", message])
+                .current_dir(&repo_path)
+                .output()
+                .await
+                .expect("git commit");
+        }
+
+        let entries = commits_in_range(&repo_path, "HEAD~2", "HEAD")
+            .await
+            .expect("commits in range");
+        let subjects: Vec<&str> = entries.iter().map(|e| e.subject.as_str()).collect();
+        assert_eq!(subjects, vec!["first change", "second change"]);
+
+        let err = commits_in_range(&repo_path, "no-such-ref", "HEAD")
+            .await
+            .expect_err("unknown ref");
+        assert_eq!(err.to_string(), "'no-such-ref' does not name a commit");
+
+        let err = commits_in_range(&repo_path, "--output=log.txt", "HEAD")
+            .await
+            .expect_err("option-like ref");
+        assert_eq!(err.to_string(), "'--output=log.txt' does not name a commit");
+    }
+
     async fn create_test_git_repo_with_remote(temp_dir: &TempDir) -> (PathBuf, String) {
         let repo_path = create_test_git_repo(temp_dir).await;
         let remote_path = temp_dir.path().join("remote.git");
@@ -32,6 +32,7 @@ pub mod error;
 pub mod exec;
 pub mod exec_env;
 mod exec_policy;
+mod external_edits;
 pub mod features;
 mod file_watcher;
 mod flags;
@@ -40,6 +41,7 @@ pub mod instructions;
 pub mod landlock;
 pub mod mcp;
 mod mcp_connection_manager;
+mod model_routing;
 pub mod models_manager;
 pub mod network_proxy_loader;
 pub use mcp_connection_manager::MCP_SANDBOX_STATE_CAPABILITY;
@@ -54,15 +56,20 @@ pub mod path_utils;
 pub mod personality_migration;
 pub mod plugins;
 mod proposed_plan_parser;
+mod provider_failover;
+pub mod remembered_approvals;
 mod sandbox_tags;
 pub mod sandboxing;
+mod session_budget;
 mod session_prefix;
 mod shell_detect;
 mod stream_events_utils;
 mod tagged_block_parser;
 pub mod test_support;
 mod text_encoding;
+mod token_budget;
 pub mod token_data;
+mod tokenizer;
 mod truncate;
 mod unified_exec;
 pub mod windows_sandbox;
@@ -97,6 +104,7 @@ pub mod project_doc;
 mod rollout;
 pub(crate) mod safety;
 pub mod seatbelt;
+pub mod secret_redaction;
 pub mod shell;
 pub mod shell_snapshot;
 pub mod skills;
@@ -10,6 +10,8 @@ use crate::protocol::EventMsg;
 use crate::protocol::McpInvocation;
 use crate::protocol::McpToolCallBeginEvent;
 use crate::protocol::McpToolCallEndEvent;
+use crate::remembered_approvals::ApprovalSubject;
+use crate::remembered_approvals::RememberedDecision;
 use codex_protocol::mcp::CallToolResult;
 use codex_protocol::models::FunctionCallOutputBody;
 use codex_protocol::models::FunctionCallOutputPayload;
@@ -67,7 +69,9 @@ pub(crate) async fn handle_mcp_tool_call(
             .await
     {
         let result = match decision {
-            McpToolApprovalDecision::Accept | McpToolApprovalDecision::AcceptAndRemember => {
+            McpToolApprovalDecision::Accept
+            | McpToolApprovalDecision::AcceptAndRemember
+            | McpToolApprovalDecision::AcceptForRepository => {
                 let tool_call_begin_event = EventMsg::McpToolCallBegin(McpToolCallBeginEvent {
                     call_id: call_id.clone(),
                     invocation: invocation.clone(),
@@ -104,7 +108,7 @@ pub(crate) async fn handle_mcp_tool_call(
                 .await;
                 result
             }
-            McpToolApprovalDecision::Decline => {
+            McpToolApprovalDecision::Decline | McpToolApprovalDecision::DeclineForRepository => {
                 let message = "user rejected MCP tool call".to_string();
                 notify_mcp_tool_call_skip(
                     sess.as_ref(),
@@ -214,7 +218,9 @@ async fn notify_mcp_tool_call_event(sess: &Session, turn_context: &TurnContext,
 enum McpToolApprovalDecision {
     Accept,
     AcceptAndRemember,
+    AcceptForRepository,
     Decline,
+    DeclineForRepository,
     Cancel,
 }
 
@@ -228,7 +234,9 @@ struct McpToolApprovalMetadata {
 const MCP_TOOL_APPROVAL_QUESTION_ID_PREFIX: &str = "mcp_tool_call_approval";
 const MCP_TOOL_APPROVAL_ACCEPT: &str = "Approve Once";
 const MCP_TOOL_APPROVAL_ACCEPT_AND_REMEMBER: &str = "Approve this Session";
+const MCP_TOOL_APPROVAL_ACCEPT_FOR_REPOSITORY: &str = "Always Approve in this Repository";
 const MCP_TOOL_APPROVAL_DECLINE: &str = "Deny";
+const MCP_TOOL_APPROVAL_DECLINE_FOR_REPOSITORY: &str = "Always Deny in this Repository";
 const MCP_TOOL_APPROVAL_CANCEL: &str = "Cancel";
 
 #[derive(Debug, Serialize)]
@@ -256,6 +264,17 @@ async fn maybe_request_mcp_tool_approval(
     if !requires_mcp_tool_approval(&metadata.annotations) {
         return None;
     }
+    let remembered = sess
+        .services
+        .remembered_approvals
+        .read()
+        .await
+        .mcp_tool_decision(server, tool_name);
+    match remembered {
+        Some(RememberedDecision::Allow) => return Some(McpToolApprovalDecision::Accept),
+        Some(RememberedDecision::Deny) => return Some(McpToolApprovalDecision::Decline),
+        None => {}
+    }
     let approval_key = metadata
         .connector_id
         .as_deref()
@@ -291,6 +310,20 @@ async fn maybe_request_mcp_tool_approval(
     {
         remember_mcp_tool_approval(sess, key).await;
     }
+    let remembered = match decision {
+        McpToolApprovalDecision::AcceptForRepository => Some(RememberedDecision::Allow),
+        McpToolApprovalDecision::DeclineForRepository => Some(RememberedDecision::Deny),
+        _ => None,
+    };
+    if let Some(remembered) = remembered {
+        let subject = ApprovalSubject::McpTool {
+            server: server.to_string(),
+            tool: tool_name.to_string(),
+        };
+        if let Err(err) = sess.remember_approval(subject, remembered).await {
+            tracing::warn!("failed to remember MCP tool approval: {err}");
+        }
+    }
     Some(decision)
 }
 
@@ -367,11 +400,19 @@ fn build_mcp_tool_approval_question(
             description: "Run the tool and remember this choice for this session.".to_string(),
         });
     }
+    options.push(RequestUserInputQuestionOption {
+        label: MCP_TOOL_APPROVAL_ACCEPT_FOR_REPOSITORY.to_string(),
+        description: "Run the tool and stop asking in this repository.".to_string(),
+    });
     options.extend([
         RequestUserInputQuestionOption {
             label: MCP_TOOL_APPROVAL_DECLINE.to_string(),
             description: "Decline this tool call and continue.".to_string(),
         },
+        RequestUserInputQuestionOption {
+            label: MCP_TOOL_APPROVAL_DECLINE_FOR_REPOSITORY.to_string(),
+            description: "Decline the tool and stop asking in this repository.".to_string(),
+        },
         RequestUserInputQuestionOption {
             label: MCP_TOOL_APPROVAL_CANCEL.to_string(),
             description: "Cancel this tool call".to_string(),
@@ -403,6 +444,16 @@ fn parse_mcp_tool_approval_response(
         return McpToolApprovalDecision::Cancel;
     };
     if answers
+        .iter()
+        .any(|answer| answer == MCP_TOOL_APPROVAL_ACCEPT_FOR_REPOSITORY)
+    {
+        McpToolApprovalDecision::AcceptForRepository
+    } else if answers
+        .iter()
+        .any(|answer| answer == MCP_TOOL_APPROVAL_DECLINE_FOR_REPOSITORY)
+    {
+        McpToolApprovalDecision::DeclineForRepository
+    } else if answers
         .iter()
         .any(|answer| answer == MCP_TOOL_APPROVAL_ACCEPT_AND_REMEMBER)
     {
@@ -463,6 +514,7 @@ async fn notify_mcp_tool_call_skip(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use codex_protocol::request_user_input::RequestUserInputAnswer;
     use pretty_assertions::assert_eq;
 
     fn annotations(
@@ -497,6 +549,40 @@ mod tests {
         assert_eq!(requires_mcp_tool_approval(&annotations), false);
     }
 
+    #[test]
+    fn repository_answers_parse_to_remembered_decisions() {
+        let response = |answer: &str| {
+            Some(RequestUserInputResponse {
+                answers: [(
+                    "q".to_string(),
+                    RequestUserInputAnswer {
+                        answers: vec![answer.to_string()],
+                    },
+                )]
+                .into_iter()
+                .collect(),
+            })
+        };
+        assert_eq!(
+            parse_mcp_tool_approval_response(
+                response(MCP_TOOL_APPROVAL_ACCEPT_FOR_REPOSITORY),
+                "q"
+            ),
+            McpToolApprovalDecision::AcceptForRepository
+        );
+        assert_eq!(
+            parse_mcp_tool_approval_response(
+                response(MCP_TOOL_APPROVAL_DECLINE_FOR_REPOSITORY),
+                "q"
+            ),
+            McpToolApprovalDecision::DeclineForRepository
+        );
+        assert_eq!(
+            parse_mcp_tool_approval_response(response(MCP_TOOL_APPROVAL_DECLINE), "q"),
+            McpToolApprovalDecision::Decline
+        );
+    }
+
     #[test]
     fn sanitize_mcp_tool_result_for_model_rewrites_image_content() {
         let result = Ok(CallToolResult {
@@ -1,4 +1,5 @@
 use crate::memories::memory_root;
+use crate::tokenizer::default_tokenizer;
 use crate::truncate::TruncationPolicy;
 use crate::truncate::truncate_text;
 use askama::Template;
@@ -49,8 +50,11 @@ pub(super) fn build_stage_one_input_message(
     rollout_cwd: &Path,
     rollout_contents: &str,
 ) -> anyhow::Result<String> {
-    let truncated_rollout_contents =
-        truncate_text(rollout_contents, TruncationPolicy::Tokens(150_000));
+    let truncated_rollout_contents = truncate_text(
+        rollout_contents,
+        TruncationPolicy::Tokens(150_000),
+        default_tokenizer(),
+    );
 
     let rollout_path = rollout_path.display().to_string();
     let rollout_cwd = rollout_cwd.display().to_string();
@@ -88,7 +92,11 @@ mod tests {
     #[test]
     fn build_stage_one_input_message_truncates_rollout_with_standard_policy() {
         let input = format!("{}{}{}", "a".repeat(700_000), "middle", "z".repeat(700_000));
-        let expected_truncated = truncate_text(&input, TruncationPolicy::Tokens(150_000));
+        let expected_truncated = truncate_text(
+            &input,
+            TruncationPolicy::Tokens(150_000),
+            default_tokenizer(),
+        );
         let message = build_stage_one_input_message(
             Path::new("/tmp/rollout.jsonl"),
             Path::new("/tmp"),
@@ -1,7 +1,6 @@
 use crate::error::CodexErr;
 use crate::error::Result;
-use once_cell::sync::Lazy;
-use regex::Regex;
+use crate::secret_redaction::redact_secrets;
 use serde::Deserialize;
 use serde_json::Value;
 use serde_json::json;
@@ -10,14 +9,6 @@ use serde_json::json;
 pub(super) const RAW_MEMORY_PROMPT: &str =
     include_str!("../../templates/memories/stage_one_system.md");
 
-static OPENAI_KEY_REGEX: Lazy<Regex> = Lazy::new(|| compile_regex(r"sk-[A-Za-z0-9]{20,}"));
-static AWS_ACCESS_KEY_ID_REGEX: Lazy<Regex> = Lazy::new(|| compile_regex(r"\bAKIA[0-9A-Z]{16}\b"));
-static BEARER_TOKEN_REGEX: Lazy<Regex> =
-    Lazy::new(|| compile_regex(r"(?i)\bBearer\s+[A-Za-z0-9._\-]{16,}\b"));
-static SECRET_ASSIGNMENT_REGEX: Lazy<Regex> = Lazy::new(|| {
-    compile_regex(r#"(?i)\b(api[_-]?key|token|secret|password)\b(\s*[:=]\s*)(["']?)[^\s"']{8,}"#)
-});
-
 /// Parsed stage-1 model output payload.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -137,34 +128,10 @@ fn normalize_stage_one_output(mut output: StageOneOutput) -> Result<StageOneOutp
     Ok(output)
 }
 
-fn redact_secrets(input: &str) -> String {
-    let redacted = OPENAI_KEY_REGEX.replace_all(input, "[REDACTED_SECRET]");
-    let redacted = AWS_ACCESS_KEY_ID_REGEX.replace_all(&redacted, "[REDACTED_SECRET]");
-    let redacted = BEARER_TOKEN_REGEX.replace_all(&redacted, "Bearer [REDACTED_SECRET]");
-
-    SECRET_ASSIGNMENT_REGEX
-        .replace_all(&redacted, "$1$2$3[REDACTED_SECRET]")
-        .to_string()
-}
-
-fn compile_regex(pattern: &str) -> Regex {
-    match Regex::new(pattern) {
-        Ok(regex) => regex,
-        // Panic is ok thanks to `load_regex` test.
-        Err(err) => panic!("invalid regex pattern `{pattern}`: {err}"),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn load_regex() {
-        // The goal of this test is just to compile all the regex to prevent the panic
-        let _ = redact_secrets("secret");
-    }
-
     #[test]
     fn normalize_stage_one_output_redacts_summary() {
         let output = StageOneOutput {
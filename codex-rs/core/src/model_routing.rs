@@ -0,0 +1,322 @@
+//! Selection of `[[model_routes]]` rules.
+//!
+//! A route is chosen when a turn starts (and for compaction and review turns,
+//! which get their own context). Rules with `after_tool` are only checked for
+//! follow-up requests inside a turn, so a cheap model can digest tool output
+//! such as `search_tool_bm25` results before the turn's own model continues.
+
+use codex_protocol::config_types::ModeKind;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::SubAgentSource;
+
+use crate::codex::TurnContext;
+use crate::config::types::ModelRoute;
+use crate::config::types::ModelRouteTask;
+
+/// The request a route is being chosen for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RouteRequest<'a> {
+    pub(crate) task: ModelRouteTask,
+    pub(crate) role: Option<&'a str>,
+    /// Estimated prompt size; rules with size bounds never match when unknown.
+    pub(crate) prompt_tokens: Option<i64>,
+    /// Tool the previous response called, for follow-up requests.
+    pub(crate) after_tool: Option<&'a str>,
+}
+
+impl<'a> RouteRequest<'a> {
+    /// Request for a turn running in `turn_context`, classified by its session
+    /// source and collaboration mode.
+    pub(crate) fn for_turn(turn_context: &'a TurnContext, prompt_tokens: Option<i64>) -> Self {
+        let task = match &turn_context.session_source {
+            SessionSource::SubAgent(SubAgentSource::Review) => ModelRouteTask::Review,
+            SessionSource::SubAgent(SubAgentSource::Compact) => ModelRouteTask::Compact,
+            SessionSource::SubAgent(_) => ModelRouteTask::SubAgent,
+            _ => match turn_context.collaboration_mode.mode {
+                ModeKind::Plan | ModeKind::HeavyPlan => ModelRouteTask::Plan,
+                _ => ModelRouteTask::Chat,
+            },
+        };
+        Self {
+            task,
+            role: turn_context.config.agent_role.as_deref(),
+            prompt_tokens,
+            after_tool: None,
+        }
+    }
+}
+
+/// The first rule matching `request`, with the label recorded for it.
+pub(crate) fn select_route<'r>(
+    routes: &'r [ModelRoute],
+    request: &RouteRequest<'_>,
+) -> Option<(String, &'r ModelRoute)> {
+    routes
+        .iter()
+        .enumerate()
+        .find(|(_, route)| route_matches(route, request))
+        .map(|(index, route)| {
+            let label = route
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("model_routes[{index}]"));
+            (label, route)
+        })
+}
+
+fn route_matches(route: &ModelRoute, request: &RouteRequest<'_>) -> bool {
+    if route.after_tool.as_deref() != request.after_tool {
+        return false;
+    }
+    if route.task.is_some_and(|task| task != request.task) {
+        return false;
+    }
+    if let Some(role) = route.role.as_deref()
+        && request.role != Some(role)
+    {
+        return false;
+    }
+    if route.min_prompt_tokens.is_none() && route.max_prompt_tokens.is_none() {
+        return true;
+    }
+    let Some(prompt_tokens) = request.prompt_tokens else {
+        return false;
+    };
+    route
+        .min_prompt_tokens
+        .is_none_or(|min| prompt_tokens >= min)
+        && route
+            .max_prompt_tokens
+            .is_none_or(|max| prompt_tokens <= max)
+}
+
+/// The tool called by the most recent response in `history`, when every call
+/// in it went to that one tool.
+pub(crate) fn last_response_tool(history: &[ResponseItem]) -> Option<&str> {
+    let mut tool: Option<&str> = None;
+    for item in history.iter().rev() {
+        let name = match item {
+            ResponseItem::FunctionCall { name, .. } | ResponseItem::CustomToolCall { name, .. } => {
+                name.as_str()
+            }
+            ResponseItem::FunctionCallOutput { .. }
+            | ResponseItem::CustomToolCallOutput { .. }
+            | ResponseItem::Reasoning { .. }
+            | ResponseItem::GhostSnapshot { .. } => continue,
+            _ => break,
+        };
+        match tool {
+            Some(existing) if existing != name => return None,
+            _ => tool = Some(name),
+        }
+    }
+    tool
+}
+
+/// Remove the reasoning items of the current turn (everything after the last
+/// user message). Encrypted reasoning only works with the model that produced
+/// it, and once a turn has sampled several models the items no longer say
+/// which one that was.
+pub(crate) fn drop_turn_reasoning(input: &mut Vec<ResponseItem>) {
+    let turn_start = input
+        .iter()
+        .rposition(|item| matches!(item, ResponseItem::Message { role, .. } if role == "user"))
+        .map_or(0, |index| index + 1);
+    let mut index = 0;
+    input.retain(|item| {
+        let keep = index < turn_start || !matches!(item, ResponseItem::Reasoning { .. });
+        index += 1;
+        keep
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::ContentItem;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    fn route(task: Option<ModelRouteTask>, model: &str) -> ModelRoute {
+        ModelRoute {
+            task,
+            model: Some(model.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn request(task: ModelRouteTask) -> RouteRequest<'static> {
+        RouteRequest {
+            task,
+            role: None,
+            prompt_tokens: Some(1_000),
+            after_tool: None,
+        }
+    }
+
+    fn selected(routes: &[ModelRoute], request: &RouteRequest<'_>) -> Option<(String, String)> {
+        select_route(routes, request)
+            .map(|(label, route)| (label, route.model.clone().unwrap_or_default()))
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let routes = vec![
+            route(Some(ModelRouteTask::Compact), "mini"),
+            ModelRoute {
+                name: Some("big-prompts".to_string()),
+                min_prompt_tokens: Some(50_000),
+                ..route(None, "large")
+            },
+            route(None, "mid"),
+        ];
+
+        assert_eq!(
+            selected(&routes, &request(ModelRouteTask::Compact)),
+            Some(("model_routes[0]".to_string(), "mini".to_string()))
+        );
+        assert_eq!(
+            selected(&routes, &request(ModelRouteTask::Chat)),
+            Some(("model_routes[2]".to_string(), "mid".to_string()))
+        );
+        let large = RouteRequest {
+            prompt_tokens: Some(80_000),
+            ..request(ModelRouteTask::Chat)
+        };
+        assert_eq!(
+            selected(&routes, &large),
+            Some(("big-prompts".to_string(), "large".to_string()))
+        );
+    }
+
+    #[test]
+    fn size_bounds_and_roles_must_hold() {
+        let routes = vec![
+            ModelRoute {
+                role: Some("explorer".to_string()),
+                ..route(Some(ModelRouteTask::SubAgent), "mini")
+            },
+            ModelRoute {
+                max_prompt_tokens: Some(10_000),
+                ..route(None, "small")
+            },
+        ];
+
+        let unknown_size = RouteRequest {
+            prompt_tokens: None,
+            ..request(ModelRouteTask::SubAgent)
+        };
+        assert_eq!(selected(&routes, &unknown_size), None);
+        let explorer = RouteRequest {
+            role: Some("explorer"),
+            ..unknown_size
+        };
+        assert_eq!(
+            selected(&routes, &explorer),
+            Some(("model_routes[0]".to_string(), "mini".to_string()))
+        );
+    }
+
+    #[test]
+    fn after_tool_rules_only_match_follow_ups() {
+        let routes = vec![ModelRoute {
+            after_tool: Some("search_tool_bm25".to_string()),
+            ..route(None, "mini")
+        }];
+
+        assert_eq!(selected(&routes, &request(ModelRouteTask::Chat)), None);
+        let follow_up = RouteRequest {
+            after_tool: Some("search_tool_bm25"),
+            ..request(ModelRouteTask::Chat)
+        };
+        assert_eq!(
+            selected(&routes, &follow_up),
+            Some(("model_routes[0]".to_string(), "mini".to_string()))
+        );
+    }
+
+    #[test]
+    fn last_response_tool_requires_a_single_tool() {
+        let call = |name: &str, call_id: &str| ResponseItem::FunctionCall {
+            id: None,
+            name: name.to_string(),
+            arguments: "{}".to_string(),
+            call_id: call_id.to_string(),
+        };
+        let output = |call_id: &str| ResponseItem::FunctionCallOutput {
+            call_id: call_id.to_string(),
+            output: FunctionCallOutputPayload::from_text("ok".to_string()),
+        };
+        let user = ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: Vec::new(),
+            end_turn: None,
+            phase: None,
+        };
+
+        let history = vec![
+            user.clone(),
+            call("shell", "1"),
+            output("1"),
+            user.clone(),
+            call("search_tool_bm25", "2"),
+            call("search_tool_bm25", "3"),
+            output("2"),
+            output("3"),
+        ];
+        assert_eq!(last_response_tool(&history), Some("search_tool_bm25"));
+
+        let mixed = vec![
+            user.clone(),
+            call("search_tool_bm25", "1"),
+            call("shell", "2"),
+        ];
+        assert_eq!(last_response_tool(&mixed), None);
+        assert_eq!(last_response_tool(&[user]), None);
+    }
+
+    #[test]
+    fn drop_turn_reasoning_keeps_earlier_turns() {
+        let user = |text: &str| ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+            end_turn: None,
+            phase: None,
+        };
+        let reasoning = |id: &str| ResponseItem::Reasoning {
+            id: id.to_string(),
+            summary: Vec::new(),
+            content: None,
+            encrypted_content: Some(format!("encrypted-{id}")),
+        };
+        let call = ResponseItem::FunctionCall {
+            id: None,
+            name: "search_tool_bm25".to_string(),
+            arguments: "{}".to_string(),
+            call_id: "1".to_string(),
+        };
+
+        let mut input = vec![
+            user("first"),
+            reasoning("earlier-turn"),
+            user("second"),
+            reasoning("this-turn"),
+            call.clone(),
+        ];
+        drop_turn_reasoning(&mut input);
+        assert_eq!(
+            input,
+            vec![
+                user("first"),
+                reasoning("earlier-turn"),
+                user("second"),
+                call
+            ]
+        );
+    }
+}
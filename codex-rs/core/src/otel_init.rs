@@ -9,6 +9,7 @@ use codex_otel::config::OtelSettings;
 use codex_otel::config::OtelTlsConfig as OtelTlsSettings;
 use codex_otel::otel_provider::OtelProvider;
 use std::error::Error;
+use std::net::SocketAddr;
 
 /// Build an OpenTelemetry provider from the app Config.
 ///
@@ -19,59 +20,67 @@ pub fn build_provider(
     service_name_override: Option<&str>,
     default_analytics_enabled: bool,
 ) -> Result<Option<OtelProvider>, Box<dyn Error>> {
-    let to_otel_exporter = |kind: &Kind| match kind {
-        Kind::None => OtelExporter::None,
-        Kind::Statsig => OtelExporter::Statsig,
-        Kind::OtlpHttp {
-            endpoint,
-            headers,
-            protocol,
-            tls,
-        } => {
-            let protocol = match protocol {
-                Protocol::Json => OtelHttpProtocol::Json,
-                Protocol::Binary => OtelHttpProtocol::Binary,
-            };
+    let to_otel_exporter = |kind: &Kind| -> Result<OtelExporter, Box<dyn Error>> {
+        Ok(match kind {
+            Kind::None => OtelExporter::None,
+            Kind::Statsig => OtelExporter::Statsig,
+            Kind::OtlpHttp {
+                endpoint,
+                headers,
+                protocol,
+                tls,
+            } => {
+                let protocol = match protocol {
+                    Protocol::Json => OtelHttpProtocol::Json,
+                    Protocol::Binary => OtelHttpProtocol::Binary,
+                };
 
-            OtelExporter::OtlpHttp {
+                OtelExporter::OtlpHttp {
+                    endpoint: endpoint.clone(),
+                    headers: headers
+                        .iter()
+                        .map(|(k, v)| (k.clone(), v.clone()))
+                        .collect(),
+                    protocol,
+                    tls: tls.as_ref().map(|config| OtelTlsSettings {
+                        ca_certificate: config.ca_certificate.clone(),
+                        client_certificate: config.client_certificate.clone(),
+                        client_private_key: config.client_private_key.clone(),
+                    }),
+                }
+            }
+            Kind::OtlpGrpc {
+                endpoint,
+                headers,
+                tls,
+            } => OtelExporter::OtlpGrpc {
                 endpoint: endpoint.clone(),
                 headers: headers
                     .iter()
                     .map(|(k, v)| (k.clone(), v.clone()))
                     .collect(),
-                protocol,
                 tls: tls.as_ref().map(|config| OtelTlsSettings {
                     ca_certificate: config.ca_certificate.clone(),
                     client_certificate: config.client_certificate.clone(),
                     client_private_key: config.client_private_key.clone(),
                 }),
-            }
-        }
-        Kind::OtlpGrpc {
-            endpoint,
-            headers,
-            tls,
-        } => OtelExporter::OtlpGrpc {
-            endpoint: endpoint.clone(),
-            headers: headers
-                .iter()
-                .map(|(k, v)| (k.clone(), v.clone()))
-                .collect(),
-            tls: tls.as_ref().map(|config| OtelTlsSettings {
-                ca_certificate: config.ca_certificate.clone(),
-                client_certificate: config.client_certificate.clone(),
-                client_private_key: config.client_private_key.clone(),
-            }),
-        },
+            },
+            Kind::Prometheus { listen_addr } => OtelExporter::Prometheus {
+                listen_addr: listen_addr.parse::<SocketAddr>().map_err(|err| {
+                    format!("invalid Prometheus listen_addr `{listen_addr}`: {err}")
+                })?,
+            },
+            Kind::JsonLines { path } => OtelExporter::JsonLines { path: path.clone() },
+        })
     };
 
-    let exporter = to_otel_exporter(&config.otel.exporter);
-    let trace_exporter = to_otel_exporter(&config.otel.trace_exporter);
+    let exporter = to_otel_exporter(&config.otel.exporter)?;
+    let trace_exporter = to_otel_exporter(&config.otel.trace_exporter)?;
     let metrics_exporter = if config
         .analytics_enabled
         .unwrap_or(default_analytics_enabled)
     {
-        to_otel_exporter(&config.otel.metrics_exporter)
+        to_otel_exporter(&config.otel.metrics_exporter)?
     } else {
         OtelExporter::None
     };
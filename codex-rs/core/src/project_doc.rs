@@ -53,6 +53,20 @@ fn render_js_repl_instructions(config: &Config) -> Option<String> {
     Some(section)
 }
 
+fn render_py_repl_instructions(config: &Config) -> Option<String> {
+    if !config.features.enabled(Feature::PyRepl) {
+        return None;
+    }
+
+    let mut section = String::from("## Python REPL\n");
+    section.push_str("- Use `py_repl` for Python in a persistent kernel. Globals and `codex.state` persist for the session (best effort) and are cleared by `py_repl_reset`.\n");
+    section.push_str("- `py_repl` is a freeform/custom tool. Direct `py_repl` calls must send raw Python tool input (optionally with first-line `# codex-py-repl: timeout_ms=15000`). Do not wrap code in JSON (for example `{\"code\":\"...\"}`), quotes, or markdown code fences.\n");
+    section.push_str("- Helpers available in `py_repl`: `codex.state`, `codex.tmp_dir`, and `codex.show_image(path_or_figure)`. Open matplotlib figures are attached to the result automatically.\n");
+    section.push_str("- The value of a trailing expression is echoed like in the interactive interpreter; use `print` for anything else.");
+
+    Some(section)
+}
+
 /// Combines `Config::instructions` and `AGENTS.md` (if present) into a single
 /// string of instructions.
 pub(crate) async fn get_user_instructions(
@@ -87,6 +101,13 @@ pub(crate) async fn get_user_instructions(
         output.push_str(&js_repl_section);
     }
 
+    if let Some(py_repl_section) = render_py_repl_instructions(config) {
+        if !output.is_empty() {
+            output.push_str("\n\n");
+        }
+        output.push_str(&py_repl_section);
+    }
+
     let skills_section = skills.and_then(render_skills_section);
     if let Some(skills_section) = skills_section {
         if !output.is_empty() {
@@ -413,6 +434,21 @@ mod tests {
         assert_eq!(res, expected);
     }
 
+    #[tokio::test]
+    async fn py_repl_instructions_are_appended_when_enabled() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let mut cfg = make_config(&tmp, 4096, None).await;
+        cfg.features.enable(Feature::PyRepl);
+
+        let res = get_user_instructions(&cfg, None)
+            .await
+            .expect("py_repl instructions expected");
+        assert!(
+            res.starts_with("## Python REPL\n- Use `py_repl` for Python in a persistent kernel.")
+        );
+        assert!(res.contains("`# codex-py-repl: timeout_ms=15000`"));
+    }
+
     /// When both system instructions *and* a project doc are present the two
     /// should be concatenated with the separator.
     #[tokio::test]
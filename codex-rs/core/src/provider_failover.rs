@@ -0,0 +1,255 @@
+//! Session-scoped bookkeeping for a profile's provider failover chain.
+//!
+//! Entry `0` is always the session's own provider and model; entries `1..`
+//! are the fallbacks declared in `[profiles.<name>.failover]`. When a turn
+//! fails with one of the configured triggers, the active entry is put into a
+//! cool-down and the session moves to the first entry that is not cooling
+//! down. At the start of every turn the session returns to the earliest
+//! available entry, so the primary provider is retried once its cool-down
+//! expires.
+
+use std::time::Duration;
+use std::time::Instant;
+
+use http::StatusCode;
+
+use crate::config::types::FailoverConfig;
+use crate::config::types::FailoverTarget;
+use crate::error::CodexErr;
+use crate::protocol::FailoverTrigger;
+
+/// A move from one chain entry to another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FailoverSwitch {
+    pub(crate) from: usize,
+    pub(crate) to: usize,
+    /// Cool-down applied to `from`, when the switch was caused by a failure.
+    pub(crate) cooldown: Option<Duration>,
+}
+
+#[derive(Debug)]
+pub(crate) struct FailoverState {
+    config: FailoverConfig,
+    /// Per entry, the instant until which it is skipped.
+    cooldown_until: Vec<Option<Instant>>,
+    active: usize,
+}
+
+impl FailoverState {
+    pub(crate) fn new(config: FailoverConfig) -> Self {
+        let entries = config.chain.len() + 1;
+        Self {
+            config,
+            cooldown_until: vec![None; entries],
+            active: 0,
+        }
+    }
+
+    pub(crate) fn active(&self) -> usize {
+        self.active
+    }
+
+    /// The fallback target for `index`; `None` for the session's own provider.
+    pub(crate) fn target(&self, index: usize) -> Option<&FailoverTarget> {
+        index
+            .checked_sub(1)
+            .and_then(|index| self.config.chain.get(index))
+    }
+
+    /// Pick the entry that should serve the next turn: the earliest one that
+    /// is not cooling down, or the current one when every entry is.
+    pub(crate) fn select_for_turn(&mut self, now: Instant) -> Option<FailoverSwitch> {
+        let preferred = (0..self.cooldown_until.len()).find(|&index| self.available(index, now))?;
+        if preferred == self.active {
+            return None;
+        }
+        let from = self.active;
+        self.active = preferred;
+        Some(FailoverSwitch {
+            from,
+            to: preferred,
+            cooldown: None,
+        })
+    }
+
+    /// Record that the active entry failed with `trigger` and move to the next
+    /// available entry. Returns `None` when the trigger is not configured or
+    /// there is nowhere left to go.
+    pub(crate) fn fail_over(
+        &mut self,
+        trigger: FailoverTrigger,
+        now: Instant,
+    ) -> Option<FailoverSwitch> {
+        if !self.config.triggers.contains(&trigger) {
+            return None;
+        }
+        let from = self.active;
+        let cooldown = self.cooldown_for(from);
+        self.cooldown_until[from] = Some(now + cooldown);
+        let to = (0..self.cooldown_until.len()).find(|&index| self.available(index, now))?;
+        self.active = to;
+        Some(FailoverSwitch {
+            from,
+            to,
+            cooldown: Some(cooldown),
+        })
+    }
+
+    fn available(&self, index: usize, now: Instant) -> bool {
+        self.cooldown_until[index].is_none_or(|until| until <= now)
+    }
+
+    fn cooldown_for(&self, index: usize) -> Duration {
+        self.target(index)
+            .map_or(self.config.cooldown, |target| target.cooldown)
+    }
+}
+
+/// Classify a sampling error into the failover trigger it corresponds to.
+pub(crate) fn failover_trigger(err: &CodexErr) -> Option<FailoverTrigger> {
+    let for_status = |status: StatusCode| {
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            Some(FailoverTrigger::RateLimit)
+        } else if status.is_server_error() {
+            Some(FailoverTrigger::ServerError)
+        } else {
+            None
+        }
+    };
+    match err {
+        CodexErr::UsageLimitReached(_) | CodexErr::QuotaExceeded => {
+            Some(FailoverTrigger::RateLimit)
+        }
+        CodexErr::RetryLimit(err) => for_status(err.status),
+        CodexErr::UnexpectedStatus(err) => for_status(err.status),
+        CodexErr::InternalServerError | CodexErr::ServerOverloaded => {
+            Some(FailoverTrigger::ServerError)
+        }
+        CodexErr::StreamIdleTimeout(_) => Some(FailoverTrigger::StreamIdleTimeout),
+        CodexErr::ContextWindowExceeded => Some(FailoverTrigger::ContextWindowExceeded),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::RetryLimitReachedError;
+    use crate::model_provider_info::ModelProviderInfo;
+    use pretty_assertions::assert_eq;
+
+    fn target(provider_id: &str, cooldown_secs: u64) -> FailoverTarget {
+        FailoverTarget {
+            provider_id: provider_id.to_string(),
+            provider: ModelProviderInfo::create_openai_provider(),
+            model: None,
+            cooldown: Duration::from_secs(cooldown_secs),
+        }
+    }
+
+    fn state(triggers: Vec<FailoverTrigger>) -> FailoverState {
+        FailoverState::new(FailoverConfig {
+            chain: vec![target("openai", 60), target("backup", 120)],
+            triggers,
+            cooldown: Duration::from_secs(300),
+        })
+    }
+
+    #[test]
+    fn walks_down_the_chain_and_returns_after_cooldown() {
+        let mut state = state(vec![FailoverTrigger::RateLimit]);
+        let start = Instant::now();
+
+        assert_eq!(
+            state.fail_over(FailoverTrigger::RateLimit, start),
+            Some(FailoverSwitch {
+                from: 0,
+                to: 1,
+                cooldown: Some(Duration::from_secs(300)),
+            })
+        );
+        assert_eq!(
+            state.fail_over(FailoverTrigger::RateLimit, start),
+            Some(FailoverSwitch {
+                from: 1,
+                to: 2,
+                cooldown: Some(Duration::from_secs(60)),
+            })
+        );
+        assert_eq!(
+            state.target(state.active()).map(|t| t.provider_id.as_str()),
+            Some("backup")
+        );
+
+        // The first fallback recovers before the primary does.
+        let later = start + Duration::from_secs(61);
+        assert_eq!(
+            state.select_for_turn(later),
+            Some(FailoverSwitch {
+                from: 2,
+                to: 1,
+                cooldown: None,
+            })
+        );
+        assert_eq!(state.select_for_turn(later), None);
+
+        let much_later = start + Duration::from_secs(301);
+        assert_eq!(
+            state.select_for_turn(much_later).map(|switch| switch.to),
+            Some(0)
+        );
+        assert_eq!(state.target(state.active()), None);
+    }
+
+    #[test]
+    fn ignores_unconfigured_triggers_and_exhausted_chains() {
+        let mut state = state(vec![FailoverTrigger::ServerError]);
+        let now = Instant::now();
+
+        assert_eq!(state.fail_over(FailoverTrigger::RateLimit, now), None);
+        assert_eq!(state.active(), 0);
+
+        state.fail_over(FailoverTrigger::ServerError, now);
+        state.fail_over(FailoverTrigger::ServerError, now);
+        assert_eq!(state.fail_over(FailoverTrigger::ServerError, now), None);
+        // Every entry is cooling down, so the next turn stays where it is.
+        assert_eq!(state.select_for_turn(now), None);
+    }
+
+    #[test]
+    fn classifies_errors() {
+        let retry_limit = |status| {
+            CodexErr::RetryLimit(RetryLimitReachedError {
+                status,
+                request_id: None,
+            })
+        };
+        assert_eq!(
+            failover_trigger(&retry_limit(StatusCode::TOO_MANY_REQUESTS)),
+            Some(FailoverTrigger::RateLimit)
+        );
+        assert_eq!(
+            failover_trigger(&retry_limit(StatusCode::BAD_GATEWAY)),
+            Some(FailoverTrigger::ServerError)
+        );
+        assert_eq!(
+            failover_trigger(&CodexErr::StreamIdleTimeout(
+                "idle timeout waiting for SSE".to_string()
+            )),
+            Some(FailoverTrigger::StreamIdleTimeout)
+        );
+        // Other stream errors do not fail over, whatever their message says.
+        assert_eq!(
+            failover_trigger(&CodexErr::Stream(
+                "idle timeout waiting for SSE".to_string(),
+                None
+            )),
+            None
+        );
+        assert_eq!(
+            failover_trigger(&CodexErr::ContextWindowExceeded),
+            Some(FailoverTrigger::ContextWindowExceeded)
+        );
+        assert_eq!(failover_trigger(&CodexErr::TurnAborted), None);
+    }
+}
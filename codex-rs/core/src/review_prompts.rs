@@ -1,6 +1,12 @@
+use crate::rollout::RolloutRecorder;
 use codex_git::merge_base_with_head;
+use codex_git::resolve_commit;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::ReviewFinding;
+use codex_protocol::protocol::ReviewFindingStatus;
 use codex_protocol::protocol::ReviewRequest;
 use codex_protocol::protocol::ReviewTarget;
+use codex_protocol::protocol::RolloutItem;
 use std::path::Path;
 
 #[derive(Clone, Debug, PartialEq)]
@@ -19,6 +25,21 @@ const COMMIT_PROMPT_WITH_TITLE: &str = "Review the code changes introduced by co
 const COMMIT_PROMPT: &str =
     "Review the code changes introduced by commit {sha}. Provide prioritized, actionable findings.";
 
+const COMMIT_RANGE_PROMPT: &str = "Review the code changes between {base} and {head}. Run `git diff {base}..{head}` to inspect them and `git log {base}..{head}` to see the commits involved. Provide prioritized, actionable findings.";
+
+const PATCH_PROMPT: &str = "Review the code changes in the patch below{label}. The patch may not be applied to the working tree; use the repository only for surrounding context, and report locations as they appear in the patched files. Provide prioritized, actionable findings.\n\n```diff\n{diff}\n```";
+
+/// Largest patch [`ReviewTarget::Patch`] accepts. The whole patch is inlined into the prompt,
+/// so anything bigger would crowd out the context the reviewer needs to read the code.
+pub const MAX_REVIEW_PATCH_BYTES: usize = 256 * 1024;
+
+const INCREMENTAL_PROMPT: &str = "Review only the code changes made since commit {sha}, a snapshot of the working tree taken when an earlier review ran. Run `git diff {sha}` to inspect them (this includes uncommitted changes) and do not report issues in code those changes did not touch. Provide prioritized, actionable findings.";
+
+const INCREMENTAL_PREVIOUS_FINDINGS: &str = "The earlier review already reported the findings below. Do not report them again unless the new changes made them worse:";
+
+const DISMISSED_FINDINGS: &str =
+    "The user already looked at the findings below and dismissed them. Do not report them again:";
+
 pub fn resolve_review_request(
     request: ReviewRequest,
     cwd: &Path,
@@ -64,7 +85,173 @@ pub fn review_prompt(target: &ReviewTarget, cwd: &Path) -> anyhow::Result<String
             }
             Ok(prompt.to_string())
         }
+        ReviewTarget::CommitRange { base, head } => {
+            for rev in [base, head] {
+                if resolve_commit(cwd, rev)?.is_none() {
+                    anyhow::bail!("'{rev}' does not name a commit");
+                }
+            }
+            Ok(COMMIT_RANGE_PROMPT
+                .replace("{base}", base)
+                .replace("{head}", head))
+        }
+        ReviewTarget::Patch { diff, label } => {
+            let diff = diff.trim_end();
+            if diff.trim().is_empty() {
+                anyhow::bail!("Patch to review is empty");
+            }
+            if diff.len() > MAX_REVIEW_PATCH_BYTES {
+                anyhow::bail!(
+                    "Patch to review is {} KiB, more than the {} KiB limit; review it in parts",
+                    diff.len() / 1024,
+                    MAX_REVIEW_PATCH_BYTES / 1024
+                );
+            }
+            let label = label
+                .as_deref()
+                .map(|label| format!(" ({label})"))
+                .unwrap_or_default();
+            // Substitute the diff last so placeholders inside it are left alone.
+            Ok(PATCH_PROMPT
+                .replace("{label}", &label)
+                .replace("{diff}", diff))
+        }
+        ReviewTarget::Incremental {
+            since_sha,
+            previous_findings,
+        } => {
+            let mut prompt = INCREMENTAL_PROMPT.replace("{sha}", since_sha);
+            push_findings_section(
+                &mut prompt,
+                INCREMENTAL_PREVIOUS_FINDINGS,
+                previous_findings,
+            );
+            Ok(prompt)
+        }
+    }
+}
+
+/// Tells the reviewer which findings the user already dismissed in this thread.
+pub(crate) fn append_dismissed_findings(prompt: &mut String, dismissed: &[ReviewFinding]) {
+    push_findings_section(prompt, DISMISSED_FINDINGS, dismissed);
+}
+
+fn push_findings_section(prompt: &mut String, heading: &str, findings: &[ReviewFinding]) {
+    if findings.is_empty() {
+        return;
+    }
+    prompt.push_str("\n\n");
+    prompt.push_str(heading);
+    for finding in findings {
+        let location = &finding.code_location;
+        prompt.push_str(&format!(
+            "\n- {} — {}:{}-{}",
+            finding.title,
+            location.absolute_file_path.display(),
+            location.line_range.start,
+            location.line_range.end
+        ));
+    }
+}
+
+/// How many lines apart two findings with the same title may be and still count as one issue,
+/// so that edits above it in the file do not make a known finding look new.
+const SAME_FINDING_LINE_SLACK: u32 = 10;
+
+/// Whether two findings describe the same issue: same title and file, and either the same
+/// explanation or line ranges within [`SAME_FINDING_LINE_SLACK`] lines of each other.
+pub fn is_same_finding(a: &ReviewFinding, b: &ReviewFinding) -> bool {
+    if a.title != b.title
+        || a.code_location.absolute_file_path != b.code_location.absolute_file_path
+    {
+        return false;
+    }
+    if a.body == b.body {
+        return true;
+    }
+    let (a, b) = (&a.code_location.line_range, &b.code_location.line_range);
+    a.start <= b.end.saturating_add(SAME_FINDING_LINE_SLACK)
+        && b.start <= a.end.saturating_add(SAME_FINDING_LINE_SLACK)
+}
+
+/// Applies one accept/dismiss decision to the list of dismissed findings.
+pub(crate) fn record_finding_status(
+    dismissed: &mut Vec<ReviewFinding>,
+    finding: &ReviewFinding,
+    status: ReviewFindingStatus,
+) {
+    dismissed.retain(|existing| !is_same_finding(existing, finding));
+    if status == ReviewFindingStatus::Dismissed {
+        dismissed.push(finding.clone());
+    }
+}
+
+/// Replays the finding decisions recorded in a rollout and returns the findings that are
+/// still dismissed.
+pub(crate) fn dismissed_findings_from_items(items: &[RolloutItem]) -> Vec<ReviewFinding> {
+    let mut dismissed = Vec::new();
+    for item in items {
+        if let RolloutItem::EventMsg(EventMsg::ReviewFindingStatus(ev)) = item {
+            record_finding_status(&mut dismissed, &ev.finding, ev.status);
+        }
+    }
+    dismissed
+}
+
+/// Builds an [`ReviewTarget::Incremental`] target from the rollout of an earlier review
+/// thread: the working tree snapshot its latest review took and every finding it (or the
+/// reviews it was itself based on) reported.
+pub async fn incremental_review_target(rollout_path: &Path) -> anyhow::Result<ReviewTarget> {
+    let history = RolloutRecorder::get_rollout_history(rollout_path).await?;
+    incremental_review_target_from_items(&history.get_rollout_items())
+}
+
+fn incremental_review_target_from_items(items: &[RolloutItem]) -> anyhow::Result<ReviewTarget> {
+    let mut previous_findings: Vec<ReviewFinding> = Vec::new();
+    let mut reviewed = false;
+    let mut since_sha = None;
+    for item in items {
+        let RolloutItem::EventMsg(msg) = item else {
+            continue;
+        };
+        let findings = match msg {
+            EventMsg::EnteredReviewMode(ReviewRequest {
+                target:
+                    ReviewTarget::Incremental {
+                        previous_findings: earlier,
+                        ..
+                    },
+                ..
+            }) => earlier,
+            EventMsg::ExitedReviewMode(ev) => {
+                reviewed = true;
+                if let Some(snapshot_sha) = &ev.snapshot_sha {
+                    since_sha = Some(snapshot_sha.clone());
+                }
+                match &ev.review_output {
+                    Some(output) => &output.findings,
+                    None => continue,
+                }
+            }
+            _ => continue,
+        };
+        for finding in findings {
+            if !previous_findings.contains(finding) {
+                previous_findings.push(finding.clone());
+            }
+        }
     }
+    if !reviewed {
+        anyhow::bail!("The given thread has not finished a review");
+    }
+    let Some(since_sha) = since_sha else {
+        anyhow::bail!("The earlier review did not record a snapshot of the working tree");
+    };
+
+    Ok(ReviewTarget::Incremental {
+        since_sha,
+        previous_findings,
+    })
 }
 
 pub fn user_facing_hint(target: &ReviewTarget) -> String {
@@ -80,6 +267,15 @@ pub fn user_facing_hint(target: &ReviewTarget) -> String {
             }
         }
         ReviewTarget::Custom { instructions } => instructions.trim().to_string(),
+        ReviewTarget::CommitRange { base, head } => format!("changes in {base}..{head}"),
+        ReviewTarget::Patch { label, .. } => match label {
+            Some(label) => format!("patch {label}"),
+            None => "patch".to_string(),
+        },
+        ReviewTarget::Incremental { since_sha, .. } => {
+            let short_sha: String = since_sha.chars().take(7).collect();
+            format!("changes since {short_sha}")
+        }
     }
 }
 
@@ -91,3 +287,190 @@ impl From<ResolvedReviewRequest> for ReviewRequest {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::ExitedReviewModeEvent;
+    use codex_protocol::protocol::GitInfo;
+    use codex_protocol::protocol::ReviewCodeLocation;
+    use codex_protocol::protocol::ReviewFindingStatusEvent;
+    use codex_protocol::protocol::ReviewLineRange;
+    use codex_protocol::protocol::ReviewOutputEvent;
+    use codex_protocol::protocol::SessionMeta;
+    use codex_protocol::protocol::SessionMetaLine;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn finding(title: &str) -> ReviewFinding {
+        ReviewFinding {
+            title: title.to_string(),
+            body: String::new(),
+            confidence_score: 0.5,
+            priority: 1,
+            code_location: ReviewCodeLocation {
+                absolute_file_path: PathBuf::from("/repo/src/lib.rs"),
+                line_range: ReviewLineRange { start: 3, end: 4 },
+            },
+        }
+    }
+
+    fn exited(findings: Vec<ReviewFinding>, snapshot_sha: Option<&str>) -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::ExitedReviewMode(ExitedReviewModeEvent {
+            review_output: Some(ReviewOutputEvent {
+                findings,
+                ..Default::default()
+            }),
+            snapshot_sha: snapshot_sha.map(str::to_string),
+        }))
+    }
+
+    #[test]
+    fn incremental_target_collects_findings_of_earlier_reviews() {
+        let meta = RolloutItem::SessionMeta(SessionMetaLine {
+            meta: SessionMeta::default(),
+            git: Some(GitInfo {
+                commit_hash: Some("abc1234def".to_string()),
+                branch: None,
+                repository_url: None,
+            }),
+        });
+        let entered = RolloutItem::EventMsg(EventMsg::EnteredReviewMode(ReviewRequest {
+            target: ReviewTarget::Incremental {
+                since_sha: "1111111aaa".to_string(),
+                previous_findings: vec![finding("[P1] Old bug")],
+            },
+            user_facing_hint: None,
+        }));
+        // The thread started at abc1234def, but the diff must start from the working tree
+        // its latest review looked at.
+        let items = vec![
+            meta,
+            exited(vec![finding("[P1] Old bug")], Some("1111111aaa")),
+            entered,
+            exited(
+                vec![finding("[P1] Old bug"), finding("[P2] New bug")],
+                Some("2222222bbb"),
+            ),
+        ];
+
+        let target = incremental_review_target_from_items(&items).expect("incremental target");
+
+        assert_eq!(
+            target,
+            ReviewTarget::Incremental {
+                since_sha: "2222222bbb".to_string(),
+                previous_findings: vec![finding("[P1] Old bug"), finding("[P2] New bug")],
+            }
+        );
+        assert_eq!(user_facing_hint(&target), "changes since 2222222");
+        assert_eq!(
+            review_prompt(&target, Path::new("/repo")).expect("prompt"),
+            format!(
+                "{}\n\n{INCREMENTAL_PREVIOUS_FINDINGS}\n- [P1] Old bug — /repo/src/lib.rs:3-4\n\
+                 - [P2] New bug — /repo/src/lib.rs:3-4",
+                INCREMENTAL_PROMPT.replace("{sha}", "2222222bbb")
+            )
+        );
+    }
+
+    #[test]
+    fn dismissed_findings_replay_the_latest_decision() {
+        let status = |title: &str, start: u32, status: ReviewFindingStatus| {
+            let mut finding = finding(title);
+            finding.code_location.line_range = ReviewLineRange { start, end: start };
+            RolloutItem::EventMsg(EventMsg::ReviewFindingStatus(ReviewFindingStatusEvent {
+                finding,
+                status,
+            }))
+        };
+        let items = vec![
+            status("[P1] Noise", 3, ReviewFindingStatus::Dismissed),
+            status("[P2] Real bug", 7, ReviewFindingStatus::Dismissed),
+            // Accepting the same finding later, even at shifted lines, undoes the dismissal.
+            status("[P2] Real bug", 9, ReviewFindingStatus::Accepted),
+            status("[P3] Style", 1, ReviewFindingStatus::Accepted),
+        ];
+
+        let dismissed = dismissed_findings_from_items(&items);
+        assert_eq!(dismissed.len(), 1);
+        assert!(is_same_finding(&dismissed[0], &finding("[P1] Noise")));
+
+        let mut prompt = UNCOMMITTED_PROMPT.to_string();
+        append_dismissed_findings(&mut prompt, &dismissed);
+        assert_eq!(
+            prompt,
+            format!(
+                "{UNCOMMITTED_PROMPT}\n\n{DISMISSED_FINDINGS}\n\
+                 - [P1] Noise — /repo/src/lib.rs:3-3"
+            )
+        );
+    }
+
+    #[test]
+    fn same_finding_needs_the_same_body_or_nearby_lines() {
+        let at = |body: &str, start: u32| {
+            let mut finding = finding("[P1] Bug");
+            finding.body = body.to_string();
+            finding.code_location.line_range = ReviewLineRange {
+                start,
+                end: start + 1,
+            };
+            finding
+        };
+
+        assert!(is_same_finding(&at("x", 3), &at("x", 300)));
+        assert!(is_same_finding(&at("x", 3), &at("y", 12)));
+        assert!(!is_same_finding(&at("x", 3), &at("y", 40)));
+        assert!(!is_same_finding(&at("x", 3), &finding("[P2] Other bug")));
+    }
+
+    #[test]
+    fn incremental_target_requires_a_finished_review_with_a_snapshot() {
+        let items = vec![RolloutItem::SessionMeta(SessionMetaLine {
+            meta: SessionMeta::default(),
+            git: Some(GitInfo {
+                commit_hash: Some("abc1234".to_string()),
+                branch: None,
+                repository_url: None,
+            }),
+        })];
+
+        let err = incremental_review_target_from_items(&items).expect_err("no review yet");
+        assert_eq!(
+            err.to_string(),
+            "The given thread has not finished a review"
+        );
+
+        let items = vec![exited(Vec::new(), None)];
+        let err = incremental_review_target_from_items(&items).expect_err("no snapshot");
+        assert_eq!(
+            err.to_string(),
+            "The earlier review did not record a snapshot of the working tree"
+        );
+    }
+
+    #[test]
+    fn patch_prompt_embeds_the_diff() {
+        let target = ReviewTarget::Patch {
+            diff: "--- a/x\n+++ b/x\n@@ -1 +1 @@\n-{head}\n+new\n".to_string(),
+            label: Some("fix.diff".to_string()),
+        };
+
+        let prompt = review_prompt(&target, Path::new("/repo")).expect("prompt");
+
+        assert!(prompt.contains("in the patch below (fix.diff)."));
+        assert!(prompt.ends_with("```diff\n--- a/x\n+++ b/x\n@@ -1 +1 @@\n-{head}\n+new\n```"));
+        assert_eq!(user_facing_hint(&target), "patch fix.diff");
+
+        let oversized = ReviewTarget::Patch {
+            diff: "+x\n".repeat(MAX_REVIEW_PATCH_BYTES),
+            label: None,
+        };
+        let err = review_prompt(&oversized, Path::new("/repo")).expect_err("too large");
+        assert_eq!(
+            err.to_string(),
+            "Patch to review is 767 KiB, more than the 256 KiB limit; review it in parts"
+        );
+    }
+}
@@ -63,8 +63,10 @@ pub(crate) fn should_persist_event_msg(ev: &EventMsg) -> bool {
         | EventMsg::ContextCompacted(_)
         | EventMsg::EnteredReviewMode(_)
         | EventMsg::ExitedReviewMode(_)
+        | EventMsg::ReviewFindingStatus(_)
         | EventMsg::ThreadRolledBack(_)
         | EventMsg::UndoCompleted(_)
+        | EventMsg::ProviderFailover(_)
         | EventMsg::TurnAborted(_)
         | EventMsg::TurnStarted(_)
         | EventMsg::TurnComplete(_) => true,
@@ -104,6 +106,7 @@ pub(crate) fn should_persist_event_msg(ev: &EventMsg) -> bool {
         | EventMsg::GetHistoryEntryResponse(_)
         | EventMsg::PreparedContextResponse(_)
         | EventMsg::UndoStarted(_)
+        | EventMsg::SnapshotTimeline(_)
         | EventMsg::McpListToolsResponse(_)
         | EventMsg::McpStartupUpdate(_)
         | EventMsg::McpStartupComplete(_)
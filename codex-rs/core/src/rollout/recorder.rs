@@ -854,7 +854,9 @@ impl JsonlWriter {
         self.write_line(&line).await
     }
     async fn write_line(&mut self, item: &impl serde::Serialize) -> std::io::Result<()> {
-        let mut json = serde_json::to_string(item)?;
+        let json = serde_json::to_string(item)?;
+        // Never persist the value of a secret resolved from config.
+        let mut json = codex_secrets::redact_secret_values(&json).into_owned();
         json.push('\n');
         self.file.write_all(json.as_bytes()).await?;
         self.file.flush().await?;
@@ -0,0 +1,98 @@
+//! Per-session hard ceilings (`[session_budget]`).
+//!
+//! The limits are checked when a turn starts and before every sampling
+//! request, and the wall-clock limit also cancels a request that is still
+//! streaming when it runs out, so every client of core (exec, the TUI and
+//! app-server) gets the same enforcement.
+
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::config::types::SessionBudget;
+
+/// Describe the first limit in `budget` the session has gone past, if any.
+///
+/// Like `codex exec --max-tokens`, the token limit trips once usage exceeds it.
+pub(crate) fn exceeded_message(
+    budget: &SessionBudget,
+    tokens_used: u64,
+    elapsed: Duration,
+) -> Option<String> {
+    if let Some(limit) = budget.max_tokens
+        && tokens_used > limit
+    {
+        return Some(format!(
+            "This session has used {tokens_used} tokens, exceeding its budget of {limit}. \
+             Raise `session_budget.max_tokens` or start a new session to keep working."
+        ));
+    }
+    if let Some(limit) = budget.timeout_secs
+        && elapsed.as_secs() >= limit
+    {
+        return Some(format!(
+            "This session has run for {}s, reaching its time budget of {limit}s. \
+             Raise `session_budget.timeout_secs` or start a new session to keep working.",
+            elapsed.as_secs()
+        ));
+    }
+    None
+}
+
+/// Instant at which the session's wall-clock budget runs out, if one is set.
+pub(crate) fn deadline(budget: &SessionBudget, started_at: Instant) -> Option<Instant> {
+    budget
+        .timeout_secs
+        .map(|secs| started_at + Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn reports_the_limit_that_was_reached() {
+        let budget = SessionBudget {
+            max_tokens: Some(1_000),
+            timeout_secs: Some(60),
+        };
+        assert_eq!(
+            exceeded_message(&budget, 1_000, Duration::from_secs(59)),
+            None
+        );
+        assert_eq!(
+            exceeded_message(&budget, 1_001, Duration::from_secs(1)),
+            Some(
+                "This session has used 1001 tokens, exceeding its budget of 1000. Raise \
+                 `session_budget.max_tokens` or start a new session to keep working."
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            exceeded_message(&budget, 0, Duration::from_secs(61)),
+            Some(
+                "This session has run for 61s, reaching its time budget of 60s. Raise \
+                 `session_budget.timeout_secs` or start a new session to keep working."
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            exceeded_message(&SessionBudget::default(), u64::MAX, Duration::MAX),
+            None
+        );
+    }
+
+    #[test]
+    fn deadline_follows_timeout() {
+        let started_at = Instant::now();
+        let budget = SessionBudget {
+            max_tokens: None,
+            timeout_secs: Some(5),
+        };
+        assert_eq!(
+            deadline(&budget, started_at),
+            Some(started_at + Duration::from_secs(5))
+        );
+        assert_eq!(deadline(&SessionBudget::default(), started_at), None);
+    }
+}
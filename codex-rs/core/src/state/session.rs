@@ -1,17 +1,34 @@
 //! Session-wide mutable state.
 
+use codex_git::GhostCommit;
 use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::ReviewFinding;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::time::Instant;
 
 use crate::codex::SessionConfiguration;
 use crate::context_manager::ContextManager;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
+use crate::provider_failover::FailoverState;
 use crate::tasks::RegularTask;
+use crate::tokenizer::Tokenizer;
 use crate::truncate::TruncationPolicy;
 
+/// A working tree state replaced by an undo or snapshot restore.
+#[derive(Debug, Clone)]
+pub(crate) struct RedoSnapshot {
+    /// The replaced state, which redo restores.
+    pub(crate) replaced: GhostCommit,
+    /// The working tree as the undo left it. Redo is refused once the tree no longer
+    /// matches, since restoring `replaced` would discard the newer edits.
+    pub(crate) left_behind: GhostCommit,
+    /// The ghost snapshot an undo removed from history and its index, put back on redo.
+    pub(crate) history_item: Option<(usize, ResponseItem)>,
+}
+
 /// Persistent, session-scoped state previously stored directly on `Session`.
 pub(crate) struct SessionState {
     pub(crate) session_configuration: SessionConfiguration,
@@ -30,6 +47,19 @@ pub(crate) struct SessionState {
     /// Startup regular task pre-created during session initialization.
     pub(crate) startup_regular_task: Option<RegularTask>,
     pub(crate) active_mcp_tool_selection: Option<Vec<String>>,
+    /// Position in the profile's provider failover chain, when one is configured.
+    pub(crate) provider_failover: Option<FailoverState>,
+    /// Review findings the user dismissed; later reviews in this thread skip them.
+    pub(crate) dismissed_review_findings: Vec<ReviewFinding>,
+    /// Working tree states replaced by undo or snapshot restores, most recent last.
+    /// Cleared whenever a new ghost snapshot is captured.
+    pub(crate) redo_snapshots: Vec<RedoSnapshot>,
+    /// When the session started, for its `session_budget.timeout_secs`.
+    pub(crate) started_at: Instant,
+    /// Tokens used by the session's model responses, for its
+    /// `session_budget.max_tokens`. Usage seeded from a resumed rollout does
+    /// not count.
+    pub(crate) tokens_used: u64,
 }
 
 impl SessionState {
@@ -47,16 +77,25 @@ impl SessionState {
             previous_model: None,
             startup_regular_task: None,
             active_mcp_tool_selection: None,
+            provider_failover: None,
+            dismissed_review_findings: Vec::new(),
+            redo_snapshots: Vec::new(),
+            started_at: Instant::now(),
+            tokens_used: 0,
         }
     }
 
     // History helpers
-    pub(crate) fn record_items<I>(&mut self, items: I, policy: TruncationPolicy)
-    where
+    pub(crate) fn record_items<I>(
+        &mut self,
+        items: I,
+        policy: TruncationPolicy,
+        tokenizer: &dyn Tokenizer,
+    ) where
         I: IntoIterator,
         I::Item: std::ops::Deref<Target = ResponseItem>,
     {
-        self.history.record_items(items, policy);
+        self.history.record_items(items, policy, tokenizer);
     }
 
     pub(crate) fn previous_model(&self) -> Option<String> {
@@ -108,9 +147,13 @@ impl SessionState {
         self.history.set_token_usage_full(context_window);
     }
 
-    pub(crate) fn get_total_token_usage(&self, server_reasoning_included: bool) -> i64 {
+    pub(crate) fn get_total_token_usage(
+        &self,
+        server_reasoning_included: bool,
+        tokenizer: &dyn Tokenizer,
+    ) -> i64 {
         self.history
-            .get_total_token_usage(server_reasoning_included)
+            .get_total_token_usage(server_reasoning_included, tokenizer)
     }
 
     pub(crate) fn set_server_reasoning_included(&mut self, included: bool) {
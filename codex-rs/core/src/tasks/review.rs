@@ -21,11 +21,13 @@ use crate::codex_delegate::run_codex_thread_one_shot;
 use crate::config::Constrained;
 use crate::review_format::format_review_findings_block;
 use crate::review_format::render_review_output_text;
+use crate::review_prompts::is_same_finding;
 use crate::state::TaskKind;
 use codex_protocol::user_input::UserInput;
 
 use super::SessionTask;
 use super::SessionTaskContext;
+use super::undo::capture_working_tree;
 
 #[derive(Clone, Copy)]
 pub(crate) struct ReviewTask;
@@ -55,6 +57,11 @@ impl SessionTask for ReviewTask {
             .otel_manager
             .counter("codex.task.review", 1, &[]);
 
+        // Remember what the review looked at so a later incremental review can diff against it.
+        let snapshot_sha = capture_working_tree(&ctx)
+            .await
+            .map(|ghost_commit| ghost_commit.id().to_string());
+
         // Start sub-codex conversation and get the receiver for events.
         let output = match start_review_conversation(
             session.clone(),
@@ -67,14 +74,24 @@ impl SessionTask for ReviewTask {
             Some(receiver) => process_review_events(session.clone(), ctx.clone(), receiver).await,
             None => None,
         };
+        let output = match output {
+            Some(output) => Some(without_dismissed_findings(&session.session, output).await),
+            None => None,
+        };
         if !cancellation_token.is_cancelled() {
-            exit_review_mode(session.clone_session(), output.clone(), ctx.clone()).await;
+            exit_review_mode(
+                session.clone_session(),
+                output.clone(),
+                snapshot_sha,
+                ctx.clone(),
+            )
+            .await;
         }
         None
     }
 
     async fn abort(&self, session: Arc<SessionTaskContext>, ctx: Arc<TurnContext>) {
-        exit_review_mode(session.clone_session(), None, ctx).await;
+        exit_review_mode(session.clone_session(), None, None, ctx).await;
     }
 }
 
@@ -169,6 +186,21 @@ async fn process_review_events(
     None
 }
 
+/// Drops findings the user already dismissed earlier in this thread, in case the reviewer
+/// raised them again despite the prompt.
+async fn without_dismissed_findings(
+    session: &Session,
+    mut output: ReviewOutputEvent,
+) -> ReviewOutputEvent {
+    let dismissed = session.dismissed_review_findings().await;
+    output.findings.retain(|finding| {
+        !dismissed
+            .iter()
+            .any(|dismissed| is_same_finding(dismissed, finding))
+    });
+    output
+}
+
 /// Parse a ReviewOutputEvent from a text blob returned by the reviewer model.
 /// If the text is valid JSON matching ReviewOutputEvent, deserialize it.
 /// Otherwise, attempt to extract the first JSON object substring and parse it.
@@ -191,11 +223,12 @@ fn parse_review_output_event(text: &str) -> ReviewOutputEvent {
     }
 }
 
-/// Emits an ExitedReviewMode Event with optional ReviewOutput,
+/// Emits an ExitedReviewMode Event with optional ReviewOutput and working tree snapshot,
 /// and records a developer message with the review output.
 pub(crate) async fn exit_review_mode(
     session: Arc<Session>,
     review_output: Option<ReviewOutputEvent>,
+    snapshot_sha: Option<String>,
     ctx: Arc<TurnContext>,
 ) {
     const REVIEW_USER_MESSAGE_ID: &str = "review_rollout_user";
@@ -238,7 +271,10 @@ pub(crate) async fn exit_review_mode(
     session
         .send_event(
             ctx.as_ref(),
-            EventMsg::ExitedReviewMode(ExitedReviewModeEvent { review_output }),
+            EventMsg::ExitedReviewMode(ExitedReviewModeEvent {
+                review_output,
+                snapshot_sha,
+            }),
         )
         .await;
     session
@@ -1,15 +1,28 @@
+use std::path::PathBuf;
 use std::sync::Arc;
 
+use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::event_mapping::parse_turn_item;
 use crate::protocol::EventMsg;
+use crate::protocol::SnapshotFileStat;
+use crate::protocol::SnapshotTimelineEntry;
+use crate::protocol::SnapshotTimelineEvent;
 use crate::protocol::UndoCompletedEvent;
 use crate::protocol::UndoStartedEvent;
+use crate::state::RedoSnapshot;
 use crate::state::TaskKind;
 use crate::tasks::SessionTask;
 use crate::tasks::SessionTaskContext;
 use async_trait::async_trait;
+use codex_git::CreateGhostCommitOptions;
+use codex_git::GhostCommit;
 use codex_git::RestoreGhostCommitOptions;
+use codex_git::create_ghost_commit;
+use codex_git::ghost_commit_diff_stats;
+use codex_git::restore_ghost_commit_paths;
 use codex_git::restore_ghost_commit_with_options;
+use codex_protocol::items::TurnItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::user_input::UserInput;
 use tokio_util::sync::CancellationToken;
@@ -17,11 +30,41 @@ use tracing::error;
 use tracing::info;
 use tracing::warn;
 
-pub(crate) struct UndoTask;
+/// Which working tree state an [`UndoTask`] restores.
+enum UndoTarget {
+    /// The most recent ghost snapshot, which is removed from history afterwards so the
+    /// next undo steps further back.
+    Latest,
+    /// A snapshot from the timeline. An empty `paths` restores every file.
+    Snapshot {
+        snapshot_id: String,
+        paths: Vec<PathBuf>,
+    },
+    /// The state replaced by the most recent undo or snapshot restore.
+    Redo,
+}
+
+pub(crate) struct UndoTask {
+    target: UndoTarget,
+}
 
 impl UndoTask {
     pub(crate) fn new() -> Self {
-        Self
+        Self {
+            target: UndoTarget::Latest,
+        }
+    }
+
+    pub(crate) fn restore_snapshot(snapshot_id: String, paths: Vec<PathBuf>) -> Self {
+        Self {
+            target: UndoTarget::Snapshot { snapshot_id, paths },
+        }
+    }
+
+    pub(crate) fn redo() -> Self {
+        Self {
+            target: UndoTarget::Redo,
+        }
     }
 }
 
@@ -44,10 +87,15 @@ impl SessionTask for UndoTask {
             .otel_manager
             .counter("codex.task.undo", 1, &[]);
         let sess = session.clone_session();
+        let (started, cancelled) = match self.target {
+            UndoTarget::Latest => ("Undo in progress...", "Undo cancelled."),
+            UndoTarget::Snapshot { .. } => ("Restoring snapshot...", "Restore cancelled."),
+            UndoTarget::Redo => ("Redo in progress...", "Redo cancelled."),
+        };
         sess.send_event(
             ctx.as_ref(),
             EventMsg::UndoStarted(UndoStartedEvent {
-                message: Some("Undo in progress...".to_string()),
+                message: Some(started.to_string()),
             }),
         )
         .await;
@@ -57,7 +105,7 @@ impl SessionTask for UndoTask {
                 ctx.as_ref(),
                 EventMsg::UndoCompleted(UndoCompletedEvent {
                     success: false,
-                    message: Some("Undo cancelled.".to_string()),
+                    message: Some(cancelled.to_string()),
                 }),
             )
             .await;
@@ -71,41 +119,126 @@ impl SessionTask for UndoTask {
             message: None,
         };
 
-        let Some((idx, ghost_commit)) =
-            items
+        let mut redo = None;
+        let target = match &self.target {
+            UndoTarget::Latest => items
                 .iter()
                 .enumerate()
                 .rev()
                 .find_map(|(idx, item)| match item {
                     ResponseItem::GhostSnapshot { ghost_commit } => {
-                        Some((idx, ghost_commit.clone()))
+                        Some((Some(idx), ghost_commit.clone()))
                     }
                     _ => None,
                 })
-        else {
-            completed.message = Some("No ghost snapshot available to undo.".to_string());
-            sess.send_event(ctx.as_ref(), EventMsg::UndoCompleted(completed))
-                .await;
-            return None;
+                .ok_or_else(|| "No ghost snapshot available to undo.".to_string()),
+            UndoTarget::Snapshot { snapshot_id, .. } => items
+                .iter()
+                .find_map(|item| match item {
+                    ResponseItem::GhostSnapshot { ghost_commit }
+                        if ghost_commit.id() == snapshot_id =>
+                    {
+                        Some((None, ghost_commit.clone()))
+                    }
+                    _ => None,
+                })
+                .ok_or_else(|| format!("Snapshot {snapshot_id} is not part of this session.")),
+            UndoTarget::Redo => match sess.pop_redo_snapshot().await {
+                Some(snapshot) => {
+                    let replaced = snapshot.replaced.clone();
+                    redo = Some(snapshot);
+                    Ok((None, replaced))
+                }
+                None => Err("Nothing to redo.".to_string()),
+            },
+        };
+        let (history_idx, ghost_commit) = match target {
+            Ok(target) => target,
+            Err(message) => {
+                completed.message = Some(message);
+                sess.send_event(ctx.as_ref(), EventMsg::UndoCompleted(completed))
+                    .await;
+                return None;
+            }
         };
 
+        // Redo overwrites the working tree, so it only runs while the tree is still the one
+        // the undo left behind; edits made since then would otherwise be lost.
+        if let Some(snapshot) = redo.take() {
+            if !working_tree_matches(&ctx, &snapshot.left_behind).await {
+                sess.push_redo_snapshot(snapshot).await;
+                completed.message = Some(
+                    "The working tree changed after the undo, so redo would discard those \
+                     changes. Revert them first to redo."
+                        .to_string(),
+                );
+                sess.send_event(ctx.as_ref(), EventMsg::UndoCompleted(completed))
+                    .await;
+                return None;
+            }
+            redo = Some(snapshot);
+        }
+
+        // Keep the state being replaced so a later redo can bring it back. Redo itself
+        // restores a state that was captured this way, so there is nothing to keep.
+        let replaced = match self.target {
+            UndoTarget::Redo => None,
+            UndoTarget::Latest | UndoTarget::Snapshot { .. } => capture_working_tree(&ctx).await,
+        };
+
+        let paths = match &self.target {
+            UndoTarget::Snapshot { paths, .. } => paths.clone(),
+            UndoTarget::Latest | UndoTarget::Redo => Vec::new(),
+        };
+        let file_count = paths.len();
         let commit_id = ghost_commit.id().to_string();
         let repo_path = ctx.cwd.clone();
         let ghost_snapshot = ctx.ghost_snapshot.clone();
+        let commit_to_restore = ghost_commit.clone();
         let restore_result = tokio::task::spawn_blocking(move || {
             let options = RestoreGhostCommitOptions::new(&repo_path).ghost_snapshot(ghost_snapshot);
-            restore_ghost_commit_with_options(&options, &ghost_commit)
+            if paths.is_empty() {
+                restore_ghost_commit_with_options(&options, &commit_to_restore)
+            } else {
+                restore_ghost_commit_paths(&options, &commit_to_restore, &paths)
+            }
         })
         .await;
 
         match restore_result {
             Ok(Ok(())) => {
-                items.remove(idx);
-                sess.replace_history(items).await;
+                let removed = history_idx.map(|idx| (idx, items.remove(idx)));
+                // Put back the ghost snapshot the undo removed, so undo can step over the
+                // redone turn again.
+                let restored = redo.take().and_then(|snapshot| snapshot.history_item);
+                let history_changed = removed.is_some() || restored.is_some();
+                if let Some((idx, item)) = restored {
+                    items.insert(idx.min(items.len()), item);
+                }
+                if history_changed {
+                    sess.replace_history(items).await;
+                }
+                if let Some(replaced) = replaced
+                    && let Some(left_behind) = capture_working_tree(&ctx).await
+                {
+                    sess.push_redo_snapshot(RedoSnapshot {
+                        replaced,
+                        left_behind,
+                        history_item: removed,
+                    })
+                    .await;
+                }
                 let short_id: String = commit_id.chars().take(7).collect();
                 info!(commit_id = commit_id, "Undo restored ghost snapshot");
                 completed.success = true;
-                completed.message = Some(format!("Undo restored snapshot {short_id}."));
+                completed.message = Some(match self.target {
+                    UndoTarget::Latest => format!("Undo restored snapshot {short_id}."),
+                    UndoTarget::Snapshot { .. } if file_count > 0 => {
+                        format!("Restored {file_count} file(s) from snapshot {short_id}.")
+                    }
+                    UndoTarget::Snapshot { .. } => format!("Restored snapshot {short_id}."),
+                    UndoTarget::Redo => format!("Redo restored snapshot {short_id}."),
+                });
             }
             Ok(Err(err)) => {
                 let message = format!("Failed to restore snapshot {commit_id}: {err}");
@@ -119,8 +252,135 @@ impl SessionTask for UndoTask {
             }
         }
 
+        if let Some(snapshot) = redo {
+            sess.push_redo_snapshot(snapshot).await;
+        }
         sess.send_event(ctx.as_ref(), EventMsg::UndoCompleted(completed))
             .await;
         None
     }
 }
+
+/// Lists the ghost snapshots in history, oldest first, with the user message that started
+/// each turn and the files changed from each snapshot to the next one. The latest snapshot
+/// is compared with the current working tree.
+pub(crate) async fn snapshot_timeline(sess: &Session, ctx: &TurnContext) -> SnapshotTimelineEvent {
+    let history = sess.clone_history().await;
+    let mut snapshots = Vec::new();
+    let mut summary = None;
+    for item in history.raw_items() {
+        match item {
+            ResponseItem::GhostSnapshot { ghost_commit } => {
+                snapshots.push((ghost_commit.clone(), summary.take()));
+            }
+            _ => {
+                if let Some(TurnItem::UserMessage(message)) = parse_turn_item(item) {
+                    summary = Some(message.message());
+                }
+            }
+        }
+    }
+
+    let current = if snapshots.is_empty() {
+        None
+    } else {
+        capture_working_tree(ctx).await
+    };
+    let commits: Vec<GhostCommit> = snapshots.iter().map(|(commit, _)| commit.clone()).collect();
+    let repo_path = ctx.cwd.clone();
+    let file_stats = tokio::task::spawn_blocking(move || {
+        commits
+            .iter()
+            .enumerate()
+            .map(|(idx, from)| {
+                let Some(to) = commits.get(idx + 1).or(current.as_ref()) else {
+                    return Vec::new();
+                };
+                ghost_commit_diff_stats(&repo_path, from, to).unwrap_or_else(|err| {
+                    warn!(
+                        "failed to compute diff stats for snapshot {}: {err}",
+                        from.id()
+                    );
+                    Vec::new()
+                })
+            })
+            .collect::<Vec<_>>()
+    })
+    .await
+    .unwrap_or_else(|err| {
+        error!("snapshot diff stats task failed: {err}");
+        Vec::new()
+    });
+
+    let mut file_stats = file_stats.into_iter();
+    let entries = snapshots
+        .into_iter()
+        .zip(1u32..)
+        .map(|((ghost_commit, summary), turn)| SnapshotTimelineEntry {
+            snapshot_id: ghost_commit.id().to_string(),
+            turn,
+            summary,
+            files: file_stats
+                .next()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|stat| SnapshotFileStat {
+                    path: stat.path,
+                    added: stat.added,
+                    removed: stat.removed,
+                })
+                .collect(),
+        })
+        .collect();
+
+    SnapshotTimelineEvent {
+        entries,
+        redo_available: sess.redo_available().await,
+    }
+}
+
+/// Whether the working tree has no changes relative to `expected`.
+async fn working_tree_matches(ctx: &TurnContext, expected: &GhostCommit) -> bool {
+    let Some(current) = capture_working_tree(ctx).await else {
+        return false;
+    };
+    let repo_path = ctx.cwd.clone();
+    let expected = expected.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        ghost_commit_diff_stats(&repo_path, &expected, &current)
+    })
+    .await;
+    match result {
+        Ok(Ok(stats)) => stats.is_empty(),
+        Ok(Err(err)) => {
+            warn!("failed to compare the working tree with the undo result: {err}");
+            false
+        }
+        Err(err) => {
+            error!("working tree comparison task failed: {err}");
+            false
+        }
+    }
+}
+
+/// Captures the current working tree as a ghost commit, so it can be restored later.
+pub(super) async fn capture_working_tree(ctx: &TurnContext) -> Option<GhostCommit> {
+    let repo_path = ctx.cwd.clone();
+    let ghost_snapshot = ctx.ghost_snapshot.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let options = CreateGhostCommitOptions::new(&repo_path).ghost_snapshot(ghost_snapshot);
+        create_ghost_commit(&options)
+    })
+    .await;
+    match result {
+        Ok(Ok(ghost_commit)) => Some(ghost_commit),
+        Ok(Err(err)) => {
+            warn!("failed to capture the working tree: {err}");
+            None
+        }
+        Err(err) => {
+            error!("working tree capture task failed: {err}");
+            None
+        }
+    }
+}
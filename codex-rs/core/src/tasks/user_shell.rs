@@ -207,6 +207,7 @@ pub(crate) async fn execute_user_shell_command(
                         exit_code: -1,
                         duration: Duration::ZERO,
                         formatted_output: aborted_message,
+                        provenance: None,
                     }),
                 )
                 .await;
@@ -232,7 +233,9 @@ pub(crate) async fn execute_user_shell_command(
                         formatted_output: format_exec_output_str(
                             &output,
                             turn_context.truncation_policy,
+                            turn_context.tokenizer,
                         ),
+                        provenance: None,
                     }),
                 )
                 .await;
@@ -271,7 +274,9 @@ pub(crate) async fn execute_user_shell_command(
                         formatted_output: format_exec_output_str(
                             &exec_output,
                             turn_context.truncation_policy,
+                            turn_context.tokenizer,
                         ),
+                        provenance: None,
                     }),
                 )
                 .await;
@@ -9,6 +9,7 @@ use crate::codex_thread::CodexThread;
 use crate::config::Config;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
+use crate::external_edits::AgentActivity;
 use crate::file_watcher::FileWatcher;
 use crate::file_watcher::FileWatcherEvent;
 use crate::models_manager::manager::ModelsManager;
@@ -94,6 +95,7 @@ fn build_file_watcher(codex_home: PathBuf, skills_manager: Arc<SkillsManager>) -
                     Ok(FileWatcherEvent::SkillsChanged { .. }) => {
                         skills_manager.clear_cache();
                     }
+                    Ok(FileWatcherEvent::WorkspaceChanged { .. }) => {}
                     Err(broadcast::error::RecvError::Closed) => break,
                     Err(broadcast::error::RecvError::Lagged(_)) => continue,
                 }
@@ -131,6 +133,8 @@ pub(crate) struct ThreadManagerState {
     models_manager: Arc<ModelsManager>,
     skills_manager: Arc<SkillsManager>,
     file_watcher: Arc<FileWatcher>,
+    /// Tool activity of every thread, used to tell agent edits from external ones.
+    agent_activity: Arc<AgentActivity>,
     session_source: SessionSource,
     // Captures submitted ops for testing purpose when test mode is enabled.
     ops_log: Option<SharedCapturedOps>,
@@ -152,6 +156,7 @@ impl ThreadManager {
                 models_manager: Arc::new(ModelsManager::new(codex_home, auth_manager.clone())),
                 skills_manager,
                 file_watcher,
+                agent_activity: Arc::new(AgentActivity::default()),
                 auth_manager,
                 session_source,
                 ops_log: should_use_test_thread_manager_behavior()
@@ -203,6 +208,7 @@ impl ThreadManager {
                 )),
                 skills_manager,
                 file_watcher,
+                agent_activity: Arc::new(AgentActivity::default()),
                 auth_manager,
                 session_source: SessionSource::Exec,
                 ops_log: should_use_test_thread_manager_behavior()
@@ -492,6 +498,7 @@ impl ThreadManagerState {
             Arc::clone(&self.models_manager),
             Arc::clone(&self.skills_manager),
             Arc::clone(&self.file_watcher),
+            Arc::clone(&self.agent_activity),
             initial_history,
             session_source,
             agent_control,
@@ -0,0 +1,158 @@
+//! Per-project token accounting.
+//!
+//! Every model response's token usage is recorded in the state database,
+//! keyed by the project it ran in, and `[projects."<path>".token_budget]`
+//! limits are checked against those records when a turn starts.
+
+use std::path::Path;
+
+use chrono::Utc;
+use codex_state::TokenUsageEntry;
+use codex_state::UsagePeriod;
+use tracing::warn;
+
+use crate::config::types::TokenBudget;
+use crate::config::types::TokenBudgetPeriod;
+use crate::git_info::resolve_root_git_project_for_trust;
+use crate::protocol::TokenUsage;
+use crate::state_db::StateDbHandle;
+
+/// Key usage is attributed to: the root of the main git project (so
+/// worktrees count towards their repository), or `cwd` outside git.
+pub(crate) fn project_key(cwd: &Path) -> String {
+    resolve_root_git_project_for_trust(cwd)
+        .unwrap_or_else(|| cwd.to_path_buf())
+        .to_string_lossy()
+        .into_owned()
+}
+
+pub(crate) struct ResponseUsage<'a> {
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: &'a str,
+    pub(crate) model: &'a str,
+    pub(crate) provider: &'a str,
+    pub(crate) cwd: &'a Path,
+    pub(crate) usage: &'a TokenUsage,
+}
+
+/// Record one response's usage without holding up the turn.
+pub(crate) fn record_response_usage(state_db: StateDbHandle, response: ResponseUsage<'_>) {
+    let entry = TokenUsageEntry {
+        ts: Utc::now().timestamp(),
+        thread_id: response.thread_id,
+        turn_id: response.turn_id.to_string(),
+        model: response.model.to_string(),
+        provider: response.provider.to_string(),
+        repo: Some(project_key(response.cwd)),
+        cwd: response.cwd.to_string_lossy().into_owned(),
+        input_tokens: response.usage.input_tokens,
+        cached_input_tokens: response.usage.cached_input_tokens,
+        output_tokens: response.usage.output_tokens,
+        reasoning_output_tokens: response.usage.reasoning_output_tokens,
+        total_tokens: response.usage.total_tokens,
+    };
+    tokio::spawn(async move {
+        if let Err(err) = state_db.record_token_usage(&entry).await {
+            warn!("failed to record token usage: {err}");
+        }
+    });
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum BudgetStatus {
+    WithinBudget,
+    SoftLimitReached { used: u64, limit: u64 },
+    HardLimitReached { used: u64, limit: u64 },
+}
+
+impl BudgetStatus {
+    pub(crate) fn message(&self, budget: &TokenBudget) -> Option<String> {
+        let period = match budget.period.unwrap_or_default() {
+            TokenBudgetPeriod::Day => "today",
+            TokenBudgetPeriod::Week => "this week",
+        };
+        match self {
+            Self::WithinBudget => None,
+            Self::SoftLimitReached { used, limit } => Some(format!(
+                "This project has used {used} tokens {period}, over its soft budget of {limit}."
+            )),
+            Self::HardLimitReached { used, limit } => Some(format!(
+                "This project has used {used} tokens {period}, reaching its token budget of \
+                 {limit}. Raise `token_budget.hard_limit` for this project to keep working."
+            )),
+        }
+    }
+}
+
+pub(crate) fn evaluate_budget(budget: &TokenBudget, used: u64) -> BudgetStatus {
+    if let Some(limit) = budget.hard_limit
+        && used >= limit
+    {
+        return BudgetStatus::HardLimitReached { used, limit };
+    }
+    if let Some(limit) = budget.soft_limit
+        && used >= limit
+    {
+        return BudgetStatus::SoftLimitReached { used, limit };
+    }
+    BudgetStatus::WithinBudget
+}
+
+/// Compare the project's usage in the current period with its budget.
+pub(crate) async fn check_budget(
+    state_db: &StateDbHandle,
+    budget: &TokenBudget,
+    cwd: &Path,
+) -> anyhow::Result<BudgetStatus> {
+    if budget.soft_limit.is_none() && budget.hard_limit.is_none() {
+        return Ok(BudgetStatus::WithinBudget);
+    }
+    let period = match budget.period.unwrap_or_default() {
+        TokenBudgetPeriod::Day => UsagePeriod::Day,
+        TokenBudgetPeriod::Week => UsagePeriod::Week,
+    };
+    let since = period.start_of(Utc::now().timestamp());
+    let used = state_db.repo_tokens_since(&project_key(cwd), since).await?;
+    Ok(evaluate_budget(budget, u64::try_from(used).unwrap_or(0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn hard_limit_takes_precedence_over_soft_limit() {
+        let budget = TokenBudget {
+            period: None,
+            soft_limit: Some(100),
+            hard_limit: Some(200),
+        };
+        assert_eq!(evaluate_budget(&budget, 99), BudgetStatus::WithinBudget);
+        assert_eq!(
+            evaluate_budget(&budget, 150),
+            BudgetStatus::SoftLimitReached {
+                used: 150,
+                limit: 100
+            }
+        );
+        assert_eq!(
+            evaluate_budget(&budget, 200),
+            BudgetStatus::HardLimitReached {
+                used: 200,
+                limit: 200
+            }
+        );
+        assert_eq!(
+            BudgetStatus::SoftLimitReached {
+                used: 150,
+                limit: 100
+            }
+            .message(&budget),
+            Some(
+                "This project has used 150 tokens this week, over its soft budget of 100."
+                    .to_string()
+            )
+        );
+    }
+}
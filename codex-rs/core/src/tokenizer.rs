@@ -0,0 +1,177 @@
+//! Token counting for context accounting.
+//!
+//! Truncation budgets, history estimates and compaction triggers all need to
+//! know how many tokens a piece of text costs. The exact answer depends on the
+//! model family, so callers go through a [`Tokenizer`] chosen with
+//! [`tokenizer_for_model`]: OpenAI models use an embedded BPE vocabulary and
+//! everything else falls back to the `bytes / 4` heuristic.
+
+use std::fmt;
+
+use tiktoken_rs::CoreBPE;
+
+use crate::truncate::approx_bytes_for_tokens;
+use crate::truncate::approx_token_count;
+
+/// Inputs longer than this are estimated instead of encoded so that a single
+/// huge tool output cannot stall a turn on BPE merges.
+const MAX_BPE_INPUT_BYTES: usize = 256 * 1024;
+
+/// Counts tokens for one model family.
+pub(crate) trait Tokenizer: fmt::Debug + Send + Sync {
+    /// Short identifier used in logs (e.g. `o200k_base`).
+    fn name(&self) -> &'static str;
+
+    fn count_tokens(&self, text: &str) -> usize;
+
+    /// Number of bytes of `text` expected to hold `tokens` tokens, given that
+    /// the whole of `text` counts as `text_tokens`.
+    fn bytes_for_tokens(&self, text: &str, text_tokens: usize, tokens: usize) -> usize {
+        if text_tokens == 0 {
+            return text.len();
+        }
+        let bytes = (text.len() as u128).saturating_mul(tokens as u128) / text_tokens as u128;
+        usize::try_from(bytes).unwrap_or(usize::MAX).min(text.len())
+    }
+}
+
+/// Assumes a fixed number of bytes per token. Cheap, and close enough for
+/// models whose vocabulary we do not ship.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct HeuristicTokenizer;
+
+impl Tokenizer for HeuristicTokenizer {
+    fn name(&self) -> &'static str {
+        "heuristic"
+    }
+
+    fn count_tokens(&self, text: &str) -> usize {
+        approx_token_count(text)
+    }
+
+    fn bytes_for_tokens(&self, _text: &str, _text_tokens: usize, tokens: usize) -> usize {
+        approx_bytes_for_tokens(tokens)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BpeEncoding {
+    O200kBase,
+    Cl100kBase,
+}
+
+/// Counts tokens with one of the BPE vocabularies embedded in `tiktoken-rs`.
+/// The vocabulary is loaded on first use.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BpeTokenizer {
+    encoding: BpeEncoding,
+}
+
+impl BpeTokenizer {
+    pub(crate) const O200K_BASE: Self = Self {
+        encoding: BpeEncoding::O200kBase,
+    };
+    pub(crate) const CL100K_BASE: Self = Self {
+        encoding: BpeEncoding::Cl100kBase,
+    };
+
+    fn bpe(&self) -> &'static CoreBPE {
+        match self.encoding {
+            BpeEncoding::O200kBase => tiktoken_rs::o200k_base_singleton(),
+            BpeEncoding::Cl100kBase => tiktoken_rs::cl100k_base_singleton(),
+        }
+    }
+}
+
+impl Tokenizer for BpeTokenizer {
+    fn name(&self) -> &'static str {
+        match self.encoding {
+            BpeEncoding::O200kBase => "o200k_base",
+            BpeEncoding::Cl100kBase => "cl100k_base",
+        }
+    }
+
+    fn count_tokens(&self, text: &str) -> usize {
+        if text.is_empty() {
+            return 0;
+        }
+        if text.len() > MAX_BPE_INPUT_BYTES {
+            return approx_token_count(text);
+        }
+        self.bpe().encode_ordinary(text).len()
+    }
+}
+
+static HEURISTIC: HeuristicTokenizer = HeuristicTokenizer;
+static O200K_BASE: BpeTokenizer = BpeTokenizer::O200K_BASE;
+static CL100K_BASE: BpeTokenizer = BpeTokenizer::CL100K_BASE;
+
+/// The tokenizer used when no model is known.
+pub(crate) fn default_tokenizer() -> &'static dyn Tokenizer {
+    &HEURISTIC
+}
+
+/// Select the tokenizer for a model slug. Unknown families use the heuristic.
+pub(crate) fn tokenizer_for_model(slug: &str) -> &'static dyn Tokenizer {
+    let slug = slug.rsplit('/').next().unwrap_or(slug).to_ascii_lowercase();
+    const O200K_PREFIXES: &[&str] = &[
+        "gpt-5", "gpt-4o", "gpt-4.1", "gpt-4.5", "gpt-oss", "o1", "o3", "o4", "codex",
+    ];
+    const CL100K_PREFIXES: &[&str] = &["gpt-4", "gpt-3.5", "text-embedding-"];
+    if O200K_PREFIXES.iter().any(|prefix| slug.starts_with(prefix)) {
+        &O200K_BASE
+    } else if CL100K_PREFIXES
+        .iter()
+        .any(|prefix| slug.starts_with(prefix))
+    {
+        &CL100K_BASE
+    } else {
+        &HEURISTIC
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn selects_tokenizer_by_model_family() {
+        assert_eq!(tokenizer_for_model("gpt-5.2-codex").name(), "o200k_base");
+        assert_eq!(
+            tokenizer_for_model("openai/gpt-4o-mini").name(),
+            "o200k_base"
+        );
+        assert_eq!(tokenizer_for_model("o3").name(), "o200k_base");
+        assert_eq!(tokenizer_for_model("gpt-4-turbo").name(), "cl100k_base");
+        assert_eq!(tokenizer_for_model("llama3.1:8b").name(), "heuristic");
+        assert_eq!(default_tokenizer().name(), "heuristic");
+    }
+
+    #[test]
+    fn bpe_counts_differ_from_byte_heuristic() {
+        let tokenizer = BpeTokenizer::O200K_BASE;
+        assert_eq!(tokenizer.count_tokens(""), 0);
+        assert_eq!(tokenizer.count_tokens("hello world"), 2);
+
+        // Runs of whitespace merge into a handful of tokens.
+        let spaces = " ".repeat(400);
+        assert!(tokenizer.count_tokens(&spaces) < HeuristicTokenizer.count_tokens(&spaces));
+    }
+
+    #[test]
+    fn bpe_falls_back_to_heuristic_for_huge_inputs() {
+        let text = "a".repeat(MAX_BPE_INPUT_BYTES + 1);
+        assert_eq!(
+            BpeTokenizer::CL100K_BASE.count_tokens(&text),
+            approx_token_count(&text)
+        );
+    }
+
+    #[test]
+    fn bytes_for_tokens_scales_with_observed_density() {
+        let text = "x".repeat(100);
+        assert_eq!(BpeTokenizer::O200K_BASE.bytes_for_tokens(&text, 50, 10), 20);
+        assert_eq!(HeuristicTokenizer.bytes_for_tokens(&text, 25, 10), 40);
+    }
+}
@@ -15,6 +15,7 @@ use crate::protocol::PatchApplyEndEvent;
 use crate::protocol::TurnDiffEvent;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::sandboxing::ToolError;
+use codex_protocol::approvals::DecisionProvenance;
 use codex_protocol::parse_command::ParsedCommand;
 use std::collections::HashMap;
 use std::path::Path;
@@ -29,6 +30,8 @@ pub(crate) struct ToolEventCtx<'a> {
     pub turn: &'a TurnContext,
     pub call_id: &'a str,
     pub turn_diff_tracker: Option<&'a SharedTurnDiffTracker>,
+    /// Reported on `ExecCommandEnd` so clients can explain why the command ran.
+    pub decision_provenance: Option<&'a DecisionProvenance>,
 }
 
 impl<'a> ToolEventCtx<'a> {
@@ -43,8 +46,17 @@ impl<'a> ToolEventCtx<'a> {
             turn,
             call_id,
             turn_diff_tracker,
+            decision_provenance: None,
         }
     }
+
+    pub fn with_decision_provenance(
+        mut self,
+        decision_provenance: Option<&'a DecisionProvenance>,
+    ) -> Self {
+        self.decision_provenance = decision_provenance;
+        self
+    }
 }
 
 pub(crate) enum ToolEventStage {
@@ -172,6 +184,17 @@ impl ToolEmitter {
                 },
                 ToolEventStage::Begin,
             ) => {
+                let moved_to = changes.values().filter_map(|change| match change {
+                    FileChange::Update {
+                        move_path: Some(dest),
+                        ..
+                    } => Some(dest),
+                    _ => None,
+                });
+                ctx.session
+                    .services
+                    .external_edits
+                    .note_agent_writes(changes.keys().chain(moved_to));
                 if let Some(tracker) = ctx.turn_diff_tracker {
                     let mut guard = tracker.lock().await;
                     guard.on_patch_begin(changes);
@@ -261,10 +284,16 @@ impl ToolEmitter {
         ctx: ToolEventCtx<'_>,
     ) -> String {
         match self {
-            Self::Shell { freeform: true, .. } => {
-                super::format_exec_output_for_model_freeform(output, ctx.turn.truncation_policy)
-            }
-            _ => super::format_exec_output_for_model_structured(output, ctx.turn.truncation_policy),
+            Self::Shell { freeform: true, .. } => super::format_exec_output_for_model_freeform(
+                output,
+                ctx.turn.truncation_policy,
+                ctx.turn.tokenizer,
+            ),
+            _ => super::format_exec_output_for_model_structured(
+                output,
+                ctx.turn.truncation_policy,
+                ctx.turn.tokenizer,
+            ),
         }
     }
 
@@ -385,7 +414,11 @@ async fn emit_exec_stage(
                 aggregated_output: output.aggregated_output.text.clone(),
                 exit_code: output.exit_code,
                 duration: output.duration,
-                formatted_output: format_exec_output_str(&output, ctx.turn.truncation_policy),
+                formatted_output: format_exec_output_str(
+                    &output,
+                    ctx.turn.truncation_policy,
+                    ctx.turn.tokenizer,
+                ),
             };
             emit_exec_end(ctx, exec_input, exec_result).await;
         }
@@ -427,6 +460,7 @@ async fn emit_exec_end(
                 exit_code: exec_result.exit_code,
                 duration: exec_result.duration,
                 formatted_output: exec_result.formatted_output,
+                provenance: ctx.decision_provenance.cloned(),
             }),
         )
         .await;
@@ -454,13 +488,19 @@ async fn emit_patch_end(
         .await;
 
     if let Some(tracker) = ctx.turn_diff_tracker {
-        let unified_diff = {
+        let (unified_diff, external_unified_diff) = {
             let mut guard = tracker.lock().await;
-            guard.get_unified_diff()
+            (guard.get_unified_diff(), guard.get_external_unified_diff())
         };
         if let Ok(Some(unified_diff)) = unified_diff {
             ctx.session
-                .send_event(ctx.turn, EventMsg::TurnDiff(TurnDiffEvent { unified_diff }))
+                .send_event(
+                    ctx.turn,
+                    EventMsg::TurnDiff(TurnDiffEvent {
+                        unified_diff,
+                        external_unified_diff,
+                    }),
+                )
                 .await;
         }
     }
@@ -77,6 +77,10 @@ impl ToolHandler for ApplyPatchHandler {
         true
     }
 
+    fn reports_file_writes(&self) -> bool {
+        true
+    }
+
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
         let ToolInvocation {
             session,
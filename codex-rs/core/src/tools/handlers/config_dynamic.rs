@@ -0,0 +1,813 @@
+//! Handler for tools declared in `[dynamic_tools]` / `tools/*.toml`.
+//!
+//! Command tools run through the same orchestrator as shell commands, so
+//! they are sandboxed and approved like any other exec. The JSON arguments
+//! are piped to the command's stdin and its stdout must be a JSON
+//! `DynamicToolResponse`. HTTP tools POST the arguments and read the same
+//! response shape from the body. OpenAPI operations map the arguments onto
+//! the path, query, headers and body of the request and return the response
+//! body as text. HTTP requests go through the managed network proxy when one
+//! is running, so its allow and deny lists apply to them as well.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use codex_network_proxy::NetworkProxy;
+use codex_protocol::approvals::DecisionProvenance;
+use codex_protocol::dynamic_tools::DynamicToolCallOutputContentItem;
+use codex_protocol::dynamic_tools::DynamicToolResponse;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::ReviewDecision;
+use reqwest::Method;
+use reqwest::header::HeaderMap;
+use reqwest::header::HeaderName;
+use reqwest::header::HeaderValue;
+use serde::Serialize;
+use serde_json::Value;
+use url::Url;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::config::types::ConfigDynamicTool;
+use crate::config::types::DynamicToolApproval;
+use crate::config::types::DynamicToolBackend;
+use crate::config::types::OPENAPI_BODY_ARGUMENT;
+use crate::config::types::OpenApiOperation;
+use crate::config::types::OpenApiParameterLocation;
+use crate::default_client::get_codex_user_agent;
+use crate::exec_env::create_env;
+use crate::exec_policy::ExecApprovalRequest;
+use crate::function_tool::FunctionCallError;
+use crate::protocol::ExecCommandSource;
+use crate::sandboxing::SandboxPermissions;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::events::ToolEmitter;
+use crate::tools::events::ToolEventCtx;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::orchestrator::ToolOrchestrator;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::tools::runtimes::shell::ShellRequest;
+use crate::tools::runtimes::shell::ShellRuntime;
+use crate::tools::sandboxing::ExecApprovalRequirement;
+use crate::tools::sandboxing::ToolCtx;
+use crate::tools::sandboxing::with_cached_approval;
+
+/// Environment variable the stdin wrapper reads the arguments from.
+const ARGUMENTS_ENV_VAR: &str = "CODEX_TOOL_ARGUMENTS";
+
+pub struct ConfigDynamicToolHandler;
+
+#[derive(Serialize)]
+struct ConfigDynamicToolApprovalKey<'a> {
+    dynamic_tool: &'a str,
+}
+
+#[async_trait]
+impl ToolHandler for ConfigDynamicToolHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn is_mutating(&self, _invocation: &ToolInvocation) -> bool {
+        true
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            call_id,
+            tool_name,
+            payload,
+            ..
+        } = invocation;
+
+        let ToolPayload::Function { arguments } = payload else {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "unsupported payload for dynamic tool {tool_name}"
+            )));
+        };
+        let Some(tool) = turn
+            .tools_config
+            .config_dynamic_tools
+            .iter()
+            .find(|tool| tool.name == tool_name)
+            .cloned()
+        else {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "unknown dynamic tool {tool_name}"
+            )));
+        };
+
+        let args: Value = parse_arguments(&arguments)?;
+        validate_arguments(&tool.input_schema, &args, "arguments")
+            .map_err(FunctionCallError::RespondToModel)?;
+
+        let response = match &tool.backend {
+            DynamicToolBackend::Command { command } => {
+                run_command_tool(&session, &turn, &tool, command, &args, call_id).await?
+            }
+            DynamicToolBackend::Http { url, http_headers } => {
+                if http_request_needs_approval(tool.approval, "POST", turn.approval_policy) {
+                    let display = vec!["POST".to_string(), url.clone()];
+                    let provenance = http_approval_provenance(&tool, turn.approval_policy);
+                    request_approval(
+                        &session,
+                        &turn,
+                        &tool.name,
+                        &call_id,
+                        display,
+                        None,
+                        Some(provenance),
+                    )
+                    .await?;
+                }
+                call_http_tool(&tool, url, http_headers, &args, turn.network.as_ref()).await?
+            }
+            DynamicToolBackend::OpenApi(operation) => {
+                if http_request_needs_approval(
+                    tool.approval,
+                    &operation.method,
+                    turn.approval_policy,
+                ) {
+                    let display = vec![
+                        operation.method.clone(),
+                        format!("{}{}", operation.base_url, operation.path),
+                    ];
+                    let provenance = http_approval_provenance(&tool, turn.approval_policy);
+                    request_approval(
+                        &session,
+                        &turn,
+                        &tool.name,
+                        &call_id,
+                        display,
+                        None,
+                        Some(provenance),
+                    )
+                    .await?;
+                }
+                call_openapi_operation(&tool, operation, &args, turn.network.as_ref()).await?
+            }
+        };
+
+        let DynamicToolResponse {
+            content_items,
+            success,
+        } = response;
+        let body = content_items
+            .into_iter()
+            .map(FunctionCallOutputContentItem::from)
+            .collect::<Vec<_>>();
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::ContentItems(body),
+            success: Some(success),
+        })
+    }
+}
+
+async fn run_command_tool(
+    session: &Arc<Session>,
+    turn: &Arc<TurnContext>,
+    tool: &ConfigDynamicTool,
+    command: &[String],
+    args: &Value,
+    call_id: String,
+) -> Result<DynamicToolResponse, FunctionCallError> {
+    let wrapped_command = stdin_wrapper(command)?;
+    let cwd = turn.cwd.clone();
+    let exec_approval_requirement = match tool.approval {
+        // A tool's own `approval` setting stands in for the approval policy.
+        DynamicToolApproval::Never => skip_approval(tool_config_provenance(tool)),
+        DynamicToolApproval::Always => {
+            let provenance = tool_config_provenance(tool);
+            request_approval(
+                session,
+                turn,
+                &tool.name,
+                &call_id,
+                command.to_vec(),
+                None,
+                Some(provenance.clone()),
+            )
+            .await?;
+            skip_approval(provenance)
+        }
+        DynamicToolApproval::Auto => {
+            let requirement = session
+                .services
+                .exec_policy
+                .create_exec_approval_requirement_for_command(ExecApprovalRequest {
+                    command,
+                    approval_policy: turn.approval_policy,
+                    sandbox_policy: &turn.sandbox_policy,
+                    sandbox_permissions: SandboxPermissions::UseDefault,
+                    prefix_rule: None,
+                })
+                .await;
+            match requirement {
+                ExecApprovalRequirement::NeedsApproval {
+                    reason, provenance, ..
+                } => {
+                    request_approval(
+                        session,
+                        turn,
+                        &tool.name,
+                        &call_id,
+                        command.to_vec(),
+                        reason,
+                        Some(provenance.clone()),
+                    )
+                    .await?;
+                    skip_approval(provenance)
+                }
+                requirement => requirement,
+            }
+        }
+    };
+
+    let mut env = create_env(
+        &turn.shell_environment_policy,
+        Some(session.conversation_id),
+    );
+    env.extend(session.dependency_env().await);
+    env.insert(ARGUMENTS_ENV_VAR.to_string(), args.to_string());
+
+    let emitter = ToolEmitter::shell(
+        command.to_vec(),
+        cwd.clone(),
+        ExecCommandSource::Agent,
+        false,
+    );
+    let event_ctx = ToolEventCtx::new(session.as_ref(), turn.as_ref(), &call_id, None);
+    emitter.begin(event_ctx).await;
+
+    let req = ShellRequest {
+        command: wrapped_command,
+        cwd,
+        timeout_ms: Some(u64::try_from(tool.timeout.as_millis()).unwrap_or(u64::MAX)),
+        env,
+        network: turn.network.clone(),
+        sandbox_permissions: SandboxPermissions::UseDefault,
+        justification: None,
+        exec_approval_requirement,
+    };
+    let mut orchestrator = ToolOrchestrator::new();
+    let mut runtime = ShellRuntime::new();
+    let tool_ctx = ToolCtx {
+        session: session.as_ref(),
+        turn: turn.as_ref(),
+        call_id: call_id.clone(),
+        tool_name: tool.name.clone(),
+    };
+    let out = orchestrator
+        .run(&mut runtime, &req, &tool_ctx, turn, turn.approval_policy)
+        .await;
+    let stdout = out
+        .as_ref()
+        .ok()
+        .map(|output| output.stdout.text.clone())
+        .unwrap_or_default();
+    let event_ctx = ToolEventCtx::new(session.as_ref(), turn.as_ref(), &call_id, None)
+        .with_decision_provenance(orchestrator.decision_provenance());
+    // Failures (non-zero exit, timeout, rejection) are reported to the model
+    // the same way as for shell commands.
+    emitter.finish(event_ctx, out).await?;
+
+    parse_response(&tool.name, &stdout)
+}
+
+/// HTTP tools and OpenAPI operations share one rule: under `auto`, anything
+/// but a `GET` or `HEAD` may change remote state, so it asks unless the
+/// approval policy rules out asking.
+fn http_request_needs_approval(
+    approval: DynamicToolApproval,
+    method: &str,
+    approval_policy: AskForApproval,
+) -> bool {
+    match approval {
+        DynamicToolApproval::Always => true,
+        DynamicToolApproval::Auto => {
+            !matches!(method, "GET" | "HEAD") && approval_policy != AskForApproval::Never
+        }
+        DynamicToolApproval::Never => false,
+    }
+}
+
+/// Provenance of a decision made by the tool's own `always` or `never` setting.
+fn tool_config_provenance(tool: &ConfigDynamicTool) -> DecisionProvenance {
+    DecisionProvenance::ToolConfig {
+        tool: tool.name.clone(),
+        always_ask: tool.approval == DynamicToolApproval::Always,
+    }
+}
+
+/// An HTTP request asks because of the tool's `always` setting, or under `auto` because the
+/// approval policy allows asking.
+fn http_approval_provenance(
+    tool: &ConfigDynamicTool,
+    approval_policy: AskForApproval,
+) -> DecisionProvenance {
+    match tool.approval {
+        DynamicToolApproval::Auto => DecisionProvenance::ApprovalPolicy { approval_policy },
+        DynamicToolApproval::Always | DynamicToolApproval::Never => tool_config_provenance(tool),
+    }
+}
+
+fn skip_approval(provenance: DecisionProvenance) -> ExecApprovalRequirement {
+    ExecApprovalRequirement::Skip {
+        bypass_sandbox: false,
+        proposed_execpolicy_amendment: None,
+        provenance,
+    }
+}
+
+/// Feed the arguments to `command` on stdin. Exec runs commands with a null
+/// stdin, so a POSIX shell pipes them in from the environment.
+fn stdin_wrapper(command: &[String]) -> Result<Vec<String>, FunctionCallError> {
+    if cfg!(windows) {
+        return Err(FunctionCallError::RespondToModel(
+            "command-backed dynamic tools are not supported on Windows".to_string(),
+        ));
+    }
+    let mut wrapped = vec![
+        "/bin/sh".to_string(),
+        "-c".to_string(),
+        format!("printf '%s' \"${ARGUMENTS_ENV_VAR}\" | \"$@\""),
+        "codex-dynamic-tool".to_string(),
+    ];
+    wrapped.extend(command.iter().cloned());
+    Ok(wrapped)
+}
+
+async fn request_approval(
+    session: &Session,
+    turn: &TurnContext,
+    tool_name: &str,
+    call_id: &str,
+    display_command: Vec<String>,
+    reason: Option<String>,
+    provenance: Option<DecisionProvenance>,
+) -> Result<(), FunctionCallError> {
+    if turn.approval_policy == AskForApproval::Never {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "{tool_name} requires approval, which the approval policy does not allow"
+        )));
+    }
+    let reason = reason.or_else(|| Some(format!("Run the {tool_name} tool")));
+    let decision = with_cached_approval(
+        &session.services,
+        tool_name,
+        vec![ConfigDynamicToolApprovalKey {
+            dynamic_tool: tool_name,
+        }],
+        || {
+            session.request_command_approval(
+                turn,
+                call_id.to_string(),
+                display_command,
+                turn.cwd.clone(),
+                reason,
+                None,
+                provenance,
+            )
+        },
+    )
+    .await;
+    match decision {
+        ReviewDecision::Approved
+        | ReviewDecision::ApprovedExecpolicyAmendment { .. }
+        | ReviewDecision::ApprovedForSession => Ok(()),
+        ReviewDecision::Denied
+        | ReviewDecision::DeniedExecpolicyAmendment { .. }
+        | ReviewDecision::Abort => Err(FunctionCallError::RespondToModel(format!(
+            "{tool_name} call rejected by user"
+        ))),
+    }
+}
+
+async fn call_http_tool(
+    tool: &ConfigDynamicTool,
+    url: &str,
+    http_headers: &HashMap<String, String>,
+    args: &Value,
+    network: Option<&NetworkProxy>,
+) -> Result<DynamicToolResponse, FunctionCallError> {
+    let request = http_client(tool, network)?
+        .post(url)
+        .headers(header_map(http_headers))
+        .json(args);
+    let (status, body) = send(tool, request).await?;
+    if !status.is_success() {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "{} returned HTTP {status}: {body}",
+            tool.name
+        )));
+    }
+    parse_response(&tool.name, &body)
+}
+
+async fn call_openapi_operation(
+    tool: &ConfigDynamicTool,
+    operation: &OpenApiOperation,
+    args: &Value,
+    network: Option<&NetworkProxy>,
+) -> Result<DynamicToolResponse, FunctionCallError> {
+    let method = Method::from_bytes(operation.method.as_bytes()).map_err(|err| {
+        FunctionCallError::RespondToModel(format!("{} has an invalid method: {err}", tool.name))
+    })?;
+    let (url, headers) = openapi_request_parts(operation, args).map_err(|err| {
+        FunctionCallError::RespondToModel(format!("{} request failed: {err}", tool.name))
+    })?;
+    let mut request = http_client(tool, network)?
+        .request(method, url)
+        .headers(headers);
+    if operation.has_body
+        && let Some(body) = args.get(OPENAPI_BODY_ARGUMENT)
+    {
+        request = request.json(body);
+    }
+    let (status, body) = send(tool, request).await?;
+
+    let text = if !status.is_success() {
+        format!("HTTP {status}: {body}")
+    } else if body.is_empty() {
+        format!("HTTP {status}")
+    } else {
+        body
+    };
+    Ok(DynamicToolResponse {
+        content_items: vec![DynamicToolCallOutputContentItem::InputText { text }],
+        success: status.is_success(),
+    })
+}
+
+/// Substitute path parameters segment by segment so their values are
+/// percent-encoded, and collect query parameters and headers.
+fn openapi_request_parts(
+    operation: &OpenApiOperation,
+    args: &Value,
+) -> Result<(Url, HeaderMap), String> {
+    let mut url = Url::parse(&operation.base_url).map_err(|err| err.to_string())?;
+    let mut headers = header_map(&operation.http_headers);
+    let mut segments: Vec<String> = operation
+        .path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(str::to_string)
+        .collect();
+    let mut query = Vec::new();
+    for parameter in &operation.parameters {
+        let Some(value) = args.get(&parameter.name) else {
+            continue;
+        };
+        let values = parameter_values(value);
+        match parameter.location {
+            OpenApiParameterLocation::Path => {
+                let placeholder = format!("{{{}}}", parameter.name);
+                let value = values.join(",");
+                for segment in &mut segments {
+                    *segment = segment.replace(&placeholder, &value);
+                }
+            }
+            OpenApiParameterLocation::Query => {
+                query.extend(
+                    values
+                        .into_iter()
+                        .map(|value| (parameter.name.clone(), value)),
+                );
+            }
+            OpenApiParameterLocation::Header => {
+                let name = HeaderName::try_from(parameter.name.as_str())
+                    .map_err(|err| format!("invalid header `{}`: {err}", parameter.name))?;
+                let value = HeaderValue::try_from(values.join(","))
+                    .map_err(|err| format!("invalid value for header `{name}`: {err}"))?;
+                headers.insert(name, value);
+            }
+        }
+    }
+
+    url.path_segments_mut()
+        .map_err(|()| format!("`{}` cannot have a path", operation.base_url))?
+        .pop_if_empty()
+        .extend(&segments);
+    if !query.is_empty() {
+        url.query_pairs_mut().extend_pairs(query);
+    }
+    Ok((url, headers))
+}
+
+/// Arrays become one value per element, matching OpenAPI's default `form`
+/// style with `explode: true`.
+fn parameter_values(value: &Value) -> Vec<String> {
+    let scalar = |value: &Value| match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    };
+    match value {
+        Value::Array(items) => items.iter().map(scalar).collect(),
+        other => vec![scalar(other)],
+    }
+}
+
+fn header_map(http_headers: &HashMap<String, String>) -> HeaderMap {
+    let mut headers = HeaderMap::with_capacity(http_headers.len());
+    for (name, value) in http_headers {
+        if let (Ok(name), Ok(value)) = (HeaderName::try_from(name), HeaderValue::try_from(value)) {
+            headers.insert(name, value);
+        }
+    }
+    headers
+}
+
+fn http_client(
+    tool: &ConfigDynamicTool,
+    network: Option<&NetworkProxy>,
+) -> Result<reqwest::Client, FunctionCallError> {
+    let mut builder = reqwest::Client::builder()
+        .user_agent(get_codex_user_agent())
+        .timeout(tool.timeout);
+    if let Some(network) = network {
+        let proxy = reqwest::Proxy::all(format!("http://{}", network.http_addr()))
+            .map_err(|err| FunctionCallError::Fatal(format!("invalid network proxy: {err}")))?;
+        builder = builder.proxy(proxy);
+    }
+    builder
+        .build()
+        .map_err(|err| FunctionCallError::Fatal(format!("failed to build HTTP client: {err}")))
+}
+
+async fn send(
+    tool: &ConfigDynamicTool,
+    request: reqwest::RequestBuilder,
+) -> Result<(reqwest::StatusCode, String), FunctionCallError> {
+    let response = request.send().await.map_err(|err| {
+        FunctionCallError::RespondToModel(format!("{} request failed: {err}", tool.name))
+    })?;
+    let status = response.status();
+    let body = response.text().await.map_err(|err| {
+        FunctionCallError::RespondToModel(format!(
+            "{} response could not be read: {err}",
+            tool.name
+        ))
+    })?;
+    Ok((status, body))
+}
+
+fn parse_response(tool_name: &str, output: &str) -> Result<DynamicToolResponse, FunctionCallError> {
+    serde_json::from_str(output.trim()).map_err(|err| {
+        FunctionCallError::RespondToModel(format!(
+            "{tool_name} did not return a valid DynamicToolResponse: {err}"
+        ))
+    })
+}
+
+/// Check `value` against the subset of JSON Schema that tool definitions
+/// use: `type`, `properties`, `required`, `additionalProperties: false`,
+/// `items` and `enum`. Other keywords are ignored.
+fn validate_arguments(schema: &Value, value: &Value, path: &str) -> Result<(), String> {
+    let Some(schema) = schema.as_object() else {
+        return Ok(());
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(kind) => vec![kind.as_str()],
+            Value::Array(kinds) => kinds.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|kind| has_type(value, kind)) {
+            return Err(format!("`{path}` must be of type {}", allowed.join(" or ")));
+        }
+    }
+
+    if let Some(Value::Array(options)) = schema.get("enum")
+        && !options.contains(value)
+    {
+        return Err(format!(
+            "`{path}` must be one of {}",
+            Value::Array(options.clone())
+        ));
+    }
+
+    if let Value::Object(fields) = value {
+        let properties = schema.get("properties").and_then(Value::as_object);
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for field in required.iter().filter_map(Value::as_str) {
+                if !fields.contains_key(field) {
+                    return Err(format!("`{path}.{field}` is required"));
+                }
+            }
+        }
+        for (field, field_value) in fields {
+            match properties.and_then(|properties| properties.get(field)) {
+                Some(field_schema) => {
+                    validate_arguments(field_schema, field_value, &format!("{path}.{field}"))?;
+                }
+                None if schema.get("additionalProperties") == Some(&Value::Bool(false)) => {
+                    return Err(format!("`{path}.{field}` is not allowed"));
+                }
+                None => {}
+            }
+        }
+    }
+
+    if let (Value::Array(items), Some(item_schema)) = (value, schema.get("items")) {
+        for (index, item) in items.iter().enumerate() {
+            validate_arguments(item_schema, item, &format!("{path}[{index}]"))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn has_type(value: &Value, kind: &str) -> bool {
+    match kind {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use std::time::Duration;
+
+    #[test]
+    fn validates_arguments_against_schema() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "paths": { "type": "array", "items": { "type": "string" } },
+                "level": { "type": "string", "enum": ["warn", "error"] },
+                "limit": { "type": "integer" },
+            },
+            "required": ["paths"],
+            "additionalProperties": false,
+        });
+        let check = |args: Value| validate_arguments(&schema, &args, "arguments");
+
+        assert_eq!(check(json!({ "paths": ["a.rs"], "limit": 3 })), Ok(()));
+        assert_eq!(
+            check(json!({})),
+            Err("`arguments.paths` is required".to_string())
+        );
+        assert_eq!(
+            check(json!({ "paths": ["a.rs", 1] })),
+            Err("`arguments.paths[1]` must be of type string".to_string())
+        );
+        assert_eq!(
+            check(json!({ "paths": [], "level": "info" })),
+            Err(r#"`arguments.level` must be one of ["warn","error"]"#.to_string())
+        );
+        assert_eq!(
+            check(json!({ "paths": [], "limit": 1.5 })),
+            Err("`arguments.limit` must be of type integer".to_string())
+        );
+        assert_eq!(
+            check(json!({ "paths": [], "verbose": true })),
+            Err("`arguments.verbose` is not allowed".to_string())
+        );
+    }
+
+    #[test]
+    fn maps_arguments_onto_openapi_requests() -> Result<(), String> {
+        use crate::config::types::OpenApiParameter;
+
+        let parameter = |name: &str, location| OpenApiParameter {
+            name: name.to_string(),
+            location,
+        };
+        let operation = OpenApiOperation {
+            source: "pets".to_string(),
+            method: "GET".to_string(),
+            base_url: "https://pets.example.com/v1/".to_string(),
+            path: "/owners/{owner}/pets".to_string(),
+            parameters: vec![
+                parameter("owner", OpenApiParameterLocation::Path),
+                parameter("tag", OpenApiParameterLocation::Query),
+                parameter("limit", OpenApiParameterLocation::Query),
+                parameter("X-Trace", OpenApiParameterLocation::Header),
+            ],
+            has_body: false,
+            http_headers: HashMap::from([(
+                "Authorization".to_string(),
+                "Bearer token".to_string(),
+            )]),
+        };
+
+        let (url, headers) = openapi_request_parts(
+            &operation,
+            &json!({ "owner": "a/b c", "tag": ["cat", "dog"], "limit": 5, "X-Trace": "1" }),
+        )?;
+
+        assert_eq!(
+            url.as_str(),
+            "https://pets.example.com/v1/owners/a%2Fb%20c/pets?tag=cat&tag=dog&limit=5"
+        );
+        assert_eq!(
+            headers
+                .get("authorization")
+                .and_then(|value| value.to_str().ok()),
+            Some("Bearer token")
+        );
+        assert_eq!(
+            headers.get("x-trace").and_then(|value| value.to_str().ok()),
+            Some("1")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn http_tools_and_openapi_writes_share_auto_approval() {
+        use DynamicToolApproval::*;
+
+        for policy in [AskForApproval::OnRequest, AskForApproval::UnlessTrusted] {
+            assert!(http_request_needs_approval(Auto, "POST", policy));
+            assert!(http_request_needs_approval(Auto, "DELETE", policy));
+            assert!(!http_request_needs_approval(Auto, "GET", policy));
+            assert!(!http_request_needs_approval(Never, "POST", policy));
+        }
+        assert!(!http_request_needs_approval(
+            Auto,
+            "POST",
+            AskForApproval::Never
+        ));
+        assert!(http_request_needs_approval(
+            Always,
+            "GET",
+            AskForApproval::Never
+        ));
+    }
+
+    #[test]
+    fn tool_settings_record_tool_config_provenance() {
+        let tool = |approval| ConfigDynamicTool {
+            name: "deploy".to_string(),
+            description: String::new(),
+            backend: DynamicToolBackend::Http {
+                url: "https://deploy.example.com".to_string(),
+                http_headers: HashMap::new(),
+            },
+            input_schema: json!({ "type": "object" }),
+            timeout: Duration::from_secs(1),
+            approval,
+            deferred: false,
+        };
+
+        assert_eq!(
+            http_approval_provenance(&tool(DynamicToolApproval::Always), AskForApproval::Never),
+            DecisionProvenance::ToolConfig {
+                tool: "deploy".to_string(),
+                always_ask: true,
+            }
+        );
+        assert_eq!(
+            tool_config_provenance(&tool(DynamicToolApproval::Never)),
+            DecisionProvenance::ToolConfig {
+                tool: "deploy".to_string(),
+                always_ask: false,
+            }
+        );
+        assert_eq!(
+            http_approval_provenance(&tool(DynamicToolApproval::Auto), AskForApproval::OnRequest),
+            DecisionProvenance::ApprovalPolicy {
+                approval_policy: AskForApproval::OnRequest,
+            }
+        );
+    }
+
+    #[test]
+    fn parses_tool_responses() {
+        assert_eq!(
+            parse_response(
+                "lint",
+                "{\"contentItems\":[{\"type\":\"inputText\",\"text\":\"ok\"}],\"success\":true}\n"
+            ),
+            Ok(DynamicToolResponse {
+                content_items: vec![DynamicToolCallOutputContentItem::InputText {
+                    text: "ok".to_string(),
+                }],
+                success: true,
+            })
+        );
+        assert!(matches!(
+            parse_response("lint", "not json"),
+            Err(FunctionCallError::RespondToModel(message))
+                if message.starts_with("lint did not return a valid DynamicToolResponse")
+        ));
+    }
+}
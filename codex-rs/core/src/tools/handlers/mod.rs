@@ -1,5 +1,6 @@
 pub mod apply_patch;
 pub(crate) mod collab;
+mod config_dynamic;
 mod dynamic;
 mod grep_files;
 mod js_repl;
@@ -7,6 +8,7 @@ mod list_dir;
 mod mcp;
 mod mcp_resource;
 mod plan;
+mod py_repl;
 mod read_file;
 mod request_user_input;
 mod search_tool_bm25;
@@ -21,6 +23,7 @@ use serde::Deserialize;
 use crate::function_tool::FunctionCallError;
 pub use apply_patch::ApplyPatchHandler;
 pub use collab::CollabHandler;
+pub use config_dynamic::ConfigDynamicToolHandler;
 pub use dynamic::DynamicToolHandler;
 pub use grep_files::GrepFilesHandler;
 pub use js_repl::JsReplHandler;
@@ -29,6 +32,8 @@ pub use list_dir::ListDirHandler;
 pub use mcp::McpHandler;
 pub use mcp_resource::McpResourceHandler;
 pub use plan::PlanHandler;
+pub use py_repl::PyReplHandler;
+pub use py_repl::PyReplResetHandler;
 pub use read_file::ReadFileHandler;
 pub use request_user_input::RequestUserInputHandler;
 pub(crate) use request_user_input::request_user_input_tool_description;
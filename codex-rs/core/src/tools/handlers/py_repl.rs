@@ -0,0 +1,319 @@
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+use std::sync::Arc;
+
+use crate::features::Feature;
+use crate::function_tool::FunctionCallError;
+use crate::protocol::EventMsg;
+use crate::protocol::ViewImageToolCallEvent;
+use crate::tokenizer::Tokenizer;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::py_repl::PY_REPL_PRAGMA_PREFIX;
+use crate::tools::py_repl::PyReplArgs;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::formatted_truncate_text;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::ResponseInputItem;
+use codex_protocol::models::local_image_content_items_with_label_number;
+use codex_protocol::openai_models::InputModality;
+
+pub struct PyReplHandler;
+pub struct PyReplResetHandler;
+
+#[async_trait]
+impl ToolHandler for PyReplHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    fn matches_kind(&self, payload: &ToolPayload) -> bool {
+        matches!(
+            payload,
+            ToolPayload::Function { .. } | ToolPayload::Custom { .. }
+        )
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            tracker,
+            payload,
+            call_id,
+            ..
+        } = invocation;
+
+        if !session.features().enabled(Feature::PyRepl) {
+            return Err(FunctionCallError::RespondToModel(
+                "py_repl is disabled by feature flag".to_string(),
+            ));
+        }
+
+        let args = match payload {
+            ToolPayload::Function { arguments } => parse_arguments(&arguments)?,
+            ToolPayload::Custom { input } => parse_freeform_args(&input)?,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "py_repl expects custom or function payload".to_string(),
+                ));
+            }
+        };
+        let manager = turn.py_repl.manager().await?;
+        let result = manager
+            .execute(Arc::clone(&session), Arc::clone(&turn), tracker, args)
+            .await
+            .map_err(|err| truncate_failure(err, turn.truncation_policy, turn.tokenizer))?;
+
+        let mut output =
+            formatted_truncate_text(&result.output, turn.truncation_policy, turn.tokenizer);
+        if !result.images.is_empty() {
+            // Images reach the model the same way `view_image` attaches them.
+            let supports_images = turn
+                .model_info
+                .input_modalities
+                .contains(&InputModality::Image);
+            let mut attached = 0;
+            if supports_images {
+                for path in &result.images {
+                    let content: Vec<ContentItem> =
+                        local_image_content_items_with_label_number(path, None);
+                    let input = ResponseInputItem::Message {
+                        role: "user".to_string(),
+                        content,
+                    };
+                    if session.inject_response_items(vec![input]).await.is_err() {
+                        break;
+                    }
+                    session
+                        .send_event(
+                            turn.as_ref(),
+                            EventMsg::ViewImageToolCall(ViewImageToolCallEvent {
+                                call_id: call_id.clone(),
+                                path: path.clone(),
+                            }),
+                        )
+                        .await;
+                    attached += 1;
+                }
+            }
+            let note = if attached == result.images.len() {
+                format!("[attached {attached} image(s)]")
+            } else {
+                let paths = result
+                    .images
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("[images saved to: {paths}]")
+            };
+            if !output.is_empty() {
+                output.push('\n');
+            }
+            output.push_str(&note);
+        }
+
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(output),
+            success: Some(true),
+        })
+    }
+}
+
+#[async_trait]
+impl ToolHandler for PyReplResetHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        if !invocation.session.features().enabled(Feature::PyRepl) {
+            return Err(FunctionCallError::RespondToModel(
+                "py_repl is disabled by feature flag".to_string(),
+            ));
+        }
+        let manager = invocation.turn.py_repl.manager().await?;
+        manager.reset().await?;
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text("py_repl kernel reset".to_string()),
+            success: Some(true),
+        })
+    }
+}
+
+fn parse_freeform_args(input: &str) -> Result<PyReplArgs, FunctionCallError> {
+    if input.trim().is_empty() {
+        return Err(FunctionCallError::RespondToModel(
+            "py_repl expects raw Python tool input (non-empty). Provide Python source text, optionally with first-line `# codex-py-repl: ...`."
+                .to_string(),
+        ));
+    }
+
+    let mut args = PyReplArgs {
+        code: input.to_string(),
+        timeout_ms: None,
+    };
+
+    let mut lines = input.splitn(2, '\n');
+    let first_line = lines.next().unwrap_or_default();
+    let rest = lines.next().unwrap_or_default();
+    let trimmed = first_line.trim_start();
+    let Some(pragma) = trimmed.strip_prefix(PY_REPL_PRAGMA_PREFIX) else {
+        reject_json_or_quoted_source(&args.code)?;
+        return Ok(args);
+    };
+
+    let mut timeout_ms: Option<u64> = None;
+    for token in pragma.split_whitespace() {
+        let (key, value) = token.split_once('=').ok_or_else(|| {
+            FunctionCallError::RespondToModel(format!(
+                "py_repl pragma expects space-separated key=value pairs (supported keys: timeout_ms); got `{token}`"
+            ))
+        })?;
+        match key {
+            "timeout_ms" => {
+                if timeout_ms.is_some() {
+                    return Err(FunctionCallError::RespondToModel(
+                        "py_repl pragma specifies timeout_ms more than once".to_string(),
+                    ));
+                }
+                let parsed = value.parse::<u64>().map_err(|_| {
+                    FunctionCallError::RespondToModel(format!(
+                        "py_repl pragma timeout_ms must be an integer; got `{value}`"
+                    ))
+                })?;
+                timeout_ms = Some(parsed);
+            }
+            _ => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "py_repl pragma only supports timeout_ms; got `{key}`"
+                )));
+            }
+        }
+    }
+
+    if rest.trim().is_empty() {
+        return Err(FunctionCallError::RespondToModel(
+            "py_repl pragma must be followed by Python source on subsequent lines".to_string(),
+        ));
+    }
+
+    reject_json_or_quoted_source(rest)?;
+    args.code = rest.to_string();
+    args.timeout_ms = timeout_ms;
+    Ok(args)
+}
+
+fn reject_json_or_quoted_source(code: &str) -> Result<(), FunctionCallError> {
+    let trimmed = code.trim();
+    if trimmed.starts_with("```") {
+        return Err(FunctionCallError::RespondToModel(
+            "py_repl expects raw Python source, not markdown code fences. Resend plain Python only (optional first line `# codex-py-repl: ...`)."
+                .to_string(),
+        ));
+    }
+    // A dict literal or a bare string is valid Python, but as a whole cell it
+    // almost always means the model wrapped its code in JSON.
+    let Ok(value) = serde_json::from_str::<JsonValue>(trimmed) else {
+        return Ok(());
+    };
+    match value {
+        JsonValue::Object(map) if map.contains_key("code") => {
+            Err(FunctionCallError::RespondToModel(
+                "py_repl is a freeform tool and expects raw Python source. Resend plain Python only (optional first line `# codex-py-repl: ...`); do not send JSON (`{\"code\":...}`), quoted code, or markdown fences."
+                    .to_string(),
+            ))
+        }
+        JsonValue::String(_) => Err(FunctionCallError::RespondToModel(
+            "py_repl is a freeform tool and expects raw Python source. Resend plain Python only (optional first line `# codex-py-repl: ...`); do not send JSON (`{\"code\":...}`), quoted code, or markdown fences."
+                .to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Failures carry the kernel output and traceback, which can be as long as any successful
+/// output, so they are truncated the same way.
+fn truncate_failure(
+    err: FunctionCallError,
+    policy: TruncationPolicy,
+    tokenizer: &dyn Tokenizer,
+) -> FunctionCallError {
+    match err {
+        FunctionCallError::RespondToModel(message) => {
+            FunctionCallError::RespondToModel(formatted_truncate_text(&message, policy, tokenizer))
+        }
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_freeform_args;
+    use super::truncate_failure;
+    use crate::function_tool::FunctionCallError;
+    use crate::tokenizer::HeuristicTokenizer;
+    use crate::truncate::TruncationPolicy;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn truncate_failure_shortens_long_tracebacks() {
+        let traceback = "Traceback line\n".repeat(2_000);
+        let err = truncate_failure(
+            FunctionCallError::RespondToModel(traceback.clone()),
+            TruncationPolicy::Bytes(1_000),
+            &HeuristicTokenizer,
+        );
+        let FunctionCallError::RespondToModel(message) = err else {
+            panic!("expected RespondToModel");
+        };
+        assert!(message.len() < traceback.len());
+        assert!(message.starts_with("Total output lines: 2000"));
+    }
+
+    #[test]
+    fn parse_freeform_args_without_pragma() {
+        let args = parse_freeform_args("print('ok')").expect("parse args");
+        assert_eq!(args.code, "print('ok')");
+        assert_eq!(args.timeout_ms, None);
+    }
+
+    #[test]
+    fn parse_freeform_args_with_pragma() {
+        let input = "# codex-py-repl: timeout_ms=15000\nprint('ok')";
+        let args = parse_freeform_args(input).expect("parse args");
+        assert_eq!(args.code, "print('ok')");
+        assert_eq!(args.timeout_ms, Some(15_000));
+    }
+
+    #[test]
+    fn parse_freeform_args_rejects_unknown_key() {
+        let err = parse_freeform_args("# codex-py-repl: nope=1\nprint('ok')")
+            .expect_err("expected error");
+        assert_eq!(
+            err.to_string(),
+            "py_repl pragma only supports timeout_ms; got `nope`"
+        );
+    }
+
+    #[test]
+    fn parse_freeform_args_allows_dict_literals() {
+        let args = parse_freeform_args(r#"{"rows": 3}"#).expect("dict literal is valid Python");
+        assert_eq!(args.code, r#"{"rows": 3}"#);
+    }
+
+    #[test]
+    fn parse_freeform_args_rejects_json_wrapped_code() {
+        let err = parse_freeform_args(r#"{"code":"print(1)"}"#).expect_err("expected error");
+        assert_eq!(
+            err.to_string(),
+            "py_repl is a freeform tool and expects raw Python source. Resend plain Python only (optional first line `# codex-py-repl: ...`); do not send JSON (`{\"code\":...}`), quoted code, or markdown fences."
+        );
+    }
+}
@@ -6,6 +6,8 @@ use codex_protocol::models::FunctionCallOutputBody;
 use serde::Deserialize;
 use serde_json::json;
 
+use crate::config::types::ConfigDynamicTool;
+use crate::config::types::DynamicToolBackend;
 use crate::function_tool::FunctionCallError;
 use crate::mcp_connection_manager::ToolInfo;
 use crate::tools::context::ToolInvocation;
@@ -66,6 +68,38 @@ impl ToolEntry {
             search_text,
         }
     }
+
+    /// Entry for a deferred config tool, such as an OpenAPI operation.
+    fn from_config_tool(tool: &ConfigDynamicTool) -> Self {
+        let server_name = match &tool.backend {
+            DynamicToolBackend::OpenApi(operation) => operation.source.clone(),
+            DynamicToolBackend::Command { .. } | DynamicToolBackend::Http { .. } => {
+                "config".to_string()
+            }
+        };
+        let input_keys = tool
+            .input_schema
+            .get("properties")
+            .and_then(serde_json::Value::as_object)
+            .map(|map| map.keys().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        let mut parts = vec![
+            tool.name.clone(),
+            server_name.clone(),
+            tool.description.clone(),
+        ];
+        parts.extend(input_keys.iter().cloned());
+        Self {
+            name: tool.name.clone(),
+            server_name,
+            title: None,
+            description: Some(tool.description.clone()),
+            connector_id: None,
+            connector_name: None,
+            input_keys,
+            search_text: parts.join(" "),
+        }
+    }
 }
 
 #[async_trait]
@@ -76,7 +110,10 @@ impl ToolHandler for SearchToolBm25Handler {
 
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
         let ToolInvocation {
-            payload, session, ..
+            payload,
+            session,
+            turn,
+            ..
         } = invocation;
 
         let arguments = match payload {
@@ -115,6 +152,13 @@ impl ToolHandler for SearchToolBm25Handler {
         let mut entries: Vec<ToolEntry> = mcp_tools
             .into_iter()
             .map(|(name, info)| ToolEntry::new(name, info))
+            .chain(
+                turn.tools_config
+                    .config_dynamic_tools
+                    .iter()
+                    .filter(|tool| tool.deferred)
+                    .map(ToolEntry::from_config_tool),
+            )
             .collect();
         entries.sort_by(|a, b| a.name.cmp(&b.name));
 
@@ -329,7 +329,8 @@ impl ShellHandler {
         let out = orchestrator
             .run(&mut runtime, &req, &tool_ctx, &turn, turn.approval_policy)
             .await;
-        let event_ctx = ToolEventCtx::new(session.as_ref(), turn.as_ref(), &call_id, None);
+        let event_ctx = ToolEventCtx::new(session.as_ref(), turn.as_ref(), &call_id, None)
+            .with_decision_provenance(orchestrator.decision_provenance());
         let content = emitter.finish(event_ctx, out).await?;
         Ok(ToolOutput::Function {
             body: FunctionCallOutputBody::Text(content),
@@ -213,6 +213,7 @@ impl ToolHandler for UnifiedExecHandler {
                         input: &args.chars,
                         yield_time_ms: args.yield_time_ms,
                         max_output_tokens: args.max_output_tokens,
+                        tokenizer: context.turn.tokenizer,
                     })
                     .await
                     .map_err(|err| {
@@ -230,71 +230,17 @@ impl JsReplManager {
                 .to_string(),
         );
 
-        let spec = CommandSpec {
-            program: node_path.to_string_lossy().to_string(),
-            args: vec![
+        let mut child = spawn_sandboxed_kernel(
+            turn.as_ref(),
+            node_path.to_string_lossy().to_string(),
+            vec![
                 "--experimental-vm-modules".to_string(),
                 kernel_path.to_string_lossy().to_string(),
             ],
-            cwd: turn.cwd.clone(),
             env,
-            expiration: ExecExpiration::DefaultTimeout,
-            sandbox_permissions: SandboxPermissions::UseDefault,
-            justification: None,
-        };
-
-        let sandbox = SandboxManager::new();
-        let has_managed_network_requirements = turn
-            .config
-            .config_layer_stack
-            .requirements_toml()
-            .network
-            .is_some();
-        let sandbox_type = sandbox.select_initial(
-            &turn.sandbox_policy,
-            SandboxablePreference::Auto,
-            turn.windows_sandbox_level,
-            has_managed_network_requirements,
-        );
-        let exec_env = sandbox
-            .transform(crate::sandboxing::SandboxTransformRequest {
-                spec,
-                policy: &turn.sandbox_policy,
-                sandbox: sandbox_type,
-                enforce_managed_network: has_managed_network_requirements,
-                network: None,
-                sandbox_policy_cwd: &turn.cwd,
-                codex_linux_sandbox_exe: turn.codex_linux_sandbox_exe.as_ref(),
-                use_linux_sandbox_bwrap: turn
-                    .features
-                    .enabled(crate::features::Feature::UseLinuxSandboxBwrap),
-                windows_sandbox_level: turn.windows_sandbox_level,
-            })
-            .map_err(|err| format!("failed to configure sandbox for js_repl: {err}"))?;
-
-        let mut cmd =
-            tokio::process::Command::new(exec_env.command.first().cloned().unwrap_or_default());
-        if exec_env.command.len() > 1 {
-            cmd.args(&exec_env.command[1..]);
-        }
-        #[cfg(unix)]
-        cmd.arg0(
-            exec_env
-                .arg0
-                .clone()
-                .unwrap_or_else(|| exec_env.command.first().cloned().unwrap_or_default()),
-        );
-        cmd.current_dir(&exec_env.cwd);
-        cmd.env_clear();
-        cmd.envs(exec_env.env);
-        cmd.stdin(std::process::Stdio::piped())
-            .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::piped())
-            .kill_on_drop(true);
-
-        let mut child = cmd
-            .spawn()
-            .map_err(|err| format!("failed to start Node runtime: {err}"))?;
+            "js_repl",
+            "Node runtime",
+        )?;
         let stdout = child
             .stdout
             .take()
@@ -436,6 +382,78 @@ impl JsReplManager {
     }
 }
 
+/// Spawn a REPL kernel process with piped stdio under the turn's sandbox policy.
+pub(crate) fn spawn_sandboxed_kernel(
+    turn: &TurnContext,
+    program: String,
+    args: Vec<String>,
+    env: HashMap<String, String>,
+    tool_name: &str,
+    runtime: &str,
+) -> Result<Child, String> {
+    let spec = CommandSpec {
+        program,
+        args,
+        cwd: turn.cwd.clone(),
+        env,
+        expiration: ExecExpiration::DefaultTimeout,
+        sandbox_permissions: SandboxPermissions::UseDefault,
+        justification: None,
+    };
+
+    let sandbox = SandboxManager::new();
+    let has_managed_network_requirements = turn
+        .config
+        .config_layer_stack
+        .requirements_toml()
+        .network
+        .is_some();
+    let sandbox_type = sandbox.select_initial(
+        &turn.sandbox_policy,
+        SandboxablePreference::Auto,
+        turn.windows_sandbox_level,
+        has_managed_network_requirements,
+    );
+    let exec_env = sandbox
+        .transform(crate::sandboxing::SandboxTransformRequest {
+            spec,
+            policy: &turn.sandbox_policy,
+            sandbox: sandbox_type,
+            enforce_managed_network: has_managed_network_requirements,
+            network: None,
+            sandbox_policy_cwd: &turn.cwd,
+            codex_linux_sandbox_exe: turn.codex_linux_sandbox_exe.as_ref(),
+            use_linux_sandbox_bwrap: turn
+                .features
+                .enabled(crate::features::Feature::UseLinuxSandboxBwrap),
+            windows_sandbox_level: turn.windows_sandbox_level,
+        })
+        .map_err(|err| format!("failed to configure sandbox for {tool_name}: {err}"))?;
+
+    let mut cmd =
+        tokio::process::Command::new(exec_env.command.first().cloned().unwrap_or_default());
+    if exec_env.command.len() > 1 {
+        cmd.args(&exec_env.command[1..]);
+    }
+    #[cfg(unix)]
+    cmd.arg0(
+        exec_env
+            .arg0
+            .clone()
+            .unwrap_or_else(|| exec_env.command.first().cloned().unwrap_or_default()),
+    );
+    cmd.current_dir(&exec_env.cwd);
+    cmd.env_clear();
+    cmd.envs(exec_env.env);
+    cmd.stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true);
+
+    cmd.spawn()
+        .map_err(|err| format!("failed to start {runtime}: {err}"))
+}
+
 #[derive(Clone, Debug, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 enum KernelToHost {
@@ -4,6 +4,7 @@ pub(crate) mod handlers;
 pub mod js_repl;
 pub mod orchestrator;
 pub mod parallel;
+pub mod py_repl;
 pub mod registry;
 pub mod router;
 pub mod runtimes;
@@ -11,6 +12,7 @@ pub mod sandboxing;
 pub mod spec;
 
 use crate::exec::ExecToolCallOutput;
+use crate::tokenizer::Tokenizer;
 use crate::truncate::TruncationPolicy;
 use crate::truncate::formatted_truncate_text;
 use crate::truncate::truncate_text;
@@ -28,6 +30,7 @@ pub(crate) const TELEMETRY_PREVIEW_TRUNCATION_NOTICE: &str =
 pub fn format_exec_output_for_model_structured(
     exec_output: &ExecToolCallOutput,
     truncation_policy: TruncationPolicy,
+    tokenizer: &dyn Tokenizer,
 ) -> String {
     let ExecToolCallOutput {
         exit_code,
@@ -50,7 +53,7 @@ pub fn format_exec_output_for_model_structured(
     // round to 1 decimal place
     let duration_seconds = ((duration.as_secs_f32()) * 10.0).round() / 10.0;
 
-    let formatted_output = format_exec_output_str(exec_output, truncation_policy);
+    let formatted_output = format_exec_output_str(exec_output, truncation_policy, tokenizer);
 
     let payload = ExecOutput {
         output: &formatted_output,
@@ -67,6 +70,7 @@ pub fn format_exec_output_for_model_structured(
 pub fn format_exec_output_for_model_freeform(
     exec_output: &ExecToolCallOutput,
     truncation_policy: TruncationPolicy,
+    tokenizer: &dyn Tokenizer,
 ) -> String {
     // round to 1 decimal place
     let duration_seconds = ((exec_output.duration.as_secs_f32()) * 10.0).round() / 10.0;
@@ -75,7 +79,7 @@ pub fn format_exec_output_for_model_freeform(
 
     let total_lines = content.lines().count();
 
-    let formatted_output = truncate_text(&content, truncation_policy);
+    let formatted_output = truncate_text(&content, truncation_policy, tokenizer);
 
     let mut sections = Vec::new();
 
@@ -94,11 +98,12 @@ pub fn format_exec_output_for_model_freeform(
 pub fn format_exec_output_str(
     exec_output: &ExecToolCallOutput,
     truncation_policy: TruncationPolicy,
+    tokenizer: &dyn Tokenizer,
 ) -> String {
     let content = build_content_with_timeout(exec_output);
 
     // Truncate for model consumption before serialization.
-    formatted_truncate_text(&content, truncation_policy)
+    formatted_truncate_text(&content, truncation_policy, tokenizer)
 }
 
 /// Extracts exec output content and prepends a timeout message if the command timed out.
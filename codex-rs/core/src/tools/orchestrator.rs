@@ -4,7 +4,8 @@ Module: orchestrator
 Central place for approvals + sandbox selection + retry semantics. Drives a
 simple sequence for any ToolRuntime: approval → select sandbox → attempt →
 retry with an escalated sandbox strategy on denial (no re‑approval thanks to
-caching).
+caching). Records the provenance of the decision that let the call run (or
+rejected it) so callers can report it alongside the result.
 */
 use crate::error::CodexErr;
 use crate::error::SandboxErr;
@@ -18,22 +19,32 @@ use crate::tools::sandboxing::SandboxOverride;
 use crate::tools::sandboxing::ToolCtx;
 use crate::tools::sandboxing::ToolError;
 use crate::tools::sandboxing::ToolRuntime;
+use crate::tools::sandboxing::approved_for_session;
 use crate::tools::sandboxing::default_exec_approval_requirement;
 use codex_otel::ToolDecisionSource;
+use codex_protocol::approvals::DecisionProvenance;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::ReviewDecision;
 
 pub(crate) struct ToolOrchestrator {
     sandbox: SandboxManager,
+    decision_provenance: Option<DecisionProvenance>,
 }
 
 impl ToolOrchestrator {
     pub fn new() -> Self {
         Self {
             sandbox: SandboxManager::new(),
+            decision_provenance: None,
         }
     }
 
+    /// Why the last call to [`ToolOrchestrator::run`] was allowed to run or
+    /// was rejected. `None` until `run` has been called.
+    pub fn decision_provenance(&self) -> Option<&DecisionProvenance> {
+        self.decision_provenance.as_ref()
+    }
+
     pub async fn run<Rq, Out, T>(
         &mut self,
         tool: &mut T,
@@ -57,26 +68,36 @@ impl ToolOrchestrator {
         let requirement = tool.exec_approval_requirement(req).unwrap_or_else(|| {
             default_exec_approval_requirement(approval_policy, &turn_ctx.sandbox_policy)
         });
+        self.decision_provenance = Some(requirement.provenance().clone());
         match requirement {
             ExecApprovalRequirement::Skip { .. } => {
                 otel.tool_decision(otel_tn, otel_ci, &ReviewDecision::Approved, otel_cfg);
             }
-            ExecApprovalRequirement::Forbidden { reason } => {
+            ExecApprovalRequirement::Forbidden { reason, .. } => {
                 return Err(ToolError::Rejected(reason));
             }
-            ExecApprovalRequirement::NeedsApproval { reason, .. } => {
+            ExecApprovalRequirement::NeedsApproval {
+                reason, provenance, ..
+            } => {
+                let keys = tool.approval_keys(req);
+                if approved_for_session(&tool_ctx.session.services, &keys).await {
+                    self.decision_provenance = Some(DecisionProvenance::SessionApproval);
+                }
                 let approval_ctx = ApprovalCtx {
                     session: tool_ctx.session,
                     turn: turn_ctx,
                     call_id: &tool_ctx.call_id,
                     retry_reason: reason,
+                    provenance,
                 };
                 let decision = tool.start_approval_async(req, approval_ctx).await;
 
                 otel.tool_decision(otel_tn, otel_ci, &decision, otel_user.clone());
 
                 match decision {
-                    ReviewDecision::Denied | ReviewDecision::Abort => {
+                    ReviewDecision::Denied
+                    | ReviewDecision::DeniedExecpolicyAmendment { .. }
+                    | ReviewDecision::Abort => {
                         return Err(ToolError::Rejected("rejected by user".to_string()));
                     }
                     ReviewDecision::Approved
@@ -138,6 +159,7 @@ impl ToolOrchestrator {
                 }
 
                 // Ask for approval before retrying with the escalated sandbox.
+                self.decision_provenance = Some(DecisionProvenance::SandboxDenialRetry);
                 if !tool.should_bypass_approval(approval_policy, already_approved) {
                     let reason_msg = build_denial_reason_from_output(output.as_ref());
                     let approval_ctx = ApprovalCtx {
@@ -145,13 +167,16 @@ impl ToolOrchestrator {
                         turn: turn_ctx,
                         call_id: &tool_ctx.call_id,
                         retry_reason: Some(reason_msg),
+                        provenance: DecisionProvenance::SandboxDenialRetry,
                     };
 
                     let decision = tool.start_approval_async(req, approval_ctx).await;
                     otel.tool_decision(otel_tn, otel_ci, &decision, otel_user);
 
                     match decision {
-                        ReviewDecision::Denied | ReviewDecision::Abort => {
+                        ReviewDecision::Denied
+                        | ReviewDecision::DeniedExecpolicyAmendment { .. }
+                        | ReviewDecision::Abort => {
                             return Err(ToolError::Rejected("rejected by user".to_string()));
                         }
                         ReviewDecision::Approved
@@ -69,9 +69,13 @@ impl ToolCallRuntime {
             aborted = false,
         );
 
+        let external_edits = Arc::clone(&self.session.services.external_edits);
+        let snapshot_tracker = Arc::clone(&self.tracker);
+
         let handle: AbortOnDropHandle<Result<ResponseInputItem, FunctionCallError>> =
             AbortOnDropHandle::new(tokio::spawn(async move {
-                tokio::select! {
+                let _agent_activity = external_edits.agent_tool_started();
+                let result = tokio::select! {
                     _ = cancellation_token.cancelled() => {
                         let secs = started.elapsed().as_secs_f32().max(0.1);
                         dispatch_span.record("aborted", true);
@@ -89,7 +93,9 @@ impl ToolCallRuntime {
                             .instrument(dispatch_span.clone())
                             .await
                     } => res,
-                }
+                };
+                snapshot_tracker.lock().await.refresh_agent_snapshots();
+                result
             }));
 
         async move {
@@ -0,0 +1,140 @@
+# Python kernel for py_repl.
+# Communicates over JSON lines on stdin and a private duplicate of stdout.
+# File descriptor 1 is pointed at stderr so that output written directly to
+# it (including by subprocesses) cannot corrupt the protocol stream.
+
+import ast
+import contextlib
+import io
+import json
+import linecache
+import os
+import sys
+import traceback
+import types
+
+_protocol = os.fdopen(os.dup(1), "w", encoding="utf-8", buffering=1)
+os.dup2(2, 1)
+sys.stdout = io.TextIOWrapper(os.fdopen(1, "wb", closefd=False), encoding="utf-8", line_buffering=True)
+
+TMP_DIR = os.environ.get("CODEX_PY_TMP_DIR") or os.getcwd()
+_image_counter = 0
+_pending_images = []
+
+
+def _next_image_path(suffix=".png"):
+    global _image_counter
+    _image_counter += 1
+    return os.path.join(TMP_DIR, f"py_repl_image_{_image_counter}{suffix}")
+
+
+def _show_image(image):
+    """Attach an image to the tool result.
+
+    Accepts a path to an image file, a matplotlib figure, or an object with a
+    `savefig` method (such as a seaborn grid).
+    """
+    if isinstance(image, (str, os.PathLike)):
+        path = os.path.abspath(os.fspath(image))
+        if not os.path.isfile(path):
+            raise FileNotFoundError(path)
+        _pending_images.append(path)
+        return path
+    savefig = getattr(image, "savefig", None)
+    if savefig is None:
+        raise TypeError("codex.show_image expects a file path or an object with savefig()")
+    path = _next_image_path()
+    savefig(path, bbox_inches="tight")
+    _pending_images.append(path)
+    return path
+
+
+def _collect_matplotlib_figures():
+    # Only look at pyplot if the cell (or an earlier one) imported it.
+    pyplot = sys.modules.get("matplotlib.pyplot")
+    if pyplot is None:
+        return
+    for number in pyplot.get_fignums():
+        figure = pyplot.figure(number)
+        path = _next_image_path()
+        figure.savefig(path, bbox_inches="tight")
+        _pending_images.append(path)
+    pyplot.close("all")
+
+
+# Explicit long-lived mutable store exposed as `codex.state`, mirroring js_repl.
+codex = types.SimpleNamespace(state={}, tmp_dir=TMP_DIR, show_image=_show_image)
+namespace = {"__name__": "__main__", "__builtins__": __builtins__, "codex": codex}
+cell_counter = 0
+
+
+def _format_exception():
+    exc_type, exc, tb = sys.exc_info()
+    # Drop the kernel's own frames so tracebacks start at the user's cell.
+    while tb is not None and tb.tb_frame.f_code.co_filename == __file__:
+        tb = tb.tb_next
+    return "".join(traceback.format_exception(exc_type, exc, tb)).rstrip()
+
+
+def _run_cell(code):
+    global cell_counter
+    cell_counter += 1
+    filename = f"<cell-{cell_counter}>"
+    # Register the source so tracebacks can show the offending lines.
+    linecache.cache[filename] = (len(code), None, code.splitlines(True), filename)
+    tree = ast.parse(code, filename=filename, mode="exec")
+    # Like the interactive interpreter, echo the value of a trailing expression.
+    last_expr = None
+    if tree.body and isinstance(tree.body[-1], ast.Expr):
+        last_expr = ast.Expression(tree.body.pop().value)
+    exec(compile(tree, filename, "exec"), namespace)
+    if last_expr is not None:
+        value = eval(compile(last_expr, filename, "eval"), namespace)
+        if value is not None:
+            namespace["_"] = value
+            print(repr(value))
+
+
+def _handle_exec(message):
+    _pending_images.clear()
+    buffer = io.StringIO()
+    ok = True
+    error = None
+    with contextlib.redirect_stdout(buffer), contextlib.redirect_stderr(buffer):
+        try:
+            _run_cell(message.get("code") or "")
+        except BaseException:  # noqa: BLE001 - report everything, including SystemExit
+            ok = False
+            error = _format_exception()
+        try:
+            _collect_matplotlib_figures()
+        except Exception:  # noqa: BLE001 - figures are best effort
+            pass
+    return {
+        "type": "exec_result",
+        "id": message.get("id"),
+        "ok": ok,
+        "output": buffer.getvalue().rstrip("\n"),
+        "error": error,
+        "images": list(_pending_images),
+    }
+
+
+def _send(message):
+    _protocol.write(json.dumps(message))
+    _protocol.write("\n")
+    _protocol.flush()
+
+
+def main():
+    for line in sys.stdin:
+        try:
+            message = json.loads(line)
+        except ValueError:
+            continue
+        if message.get("type") == "exec":
+            _send(_handle_exec(message))
+
+
+if __name__ == "__main__":
+    main()
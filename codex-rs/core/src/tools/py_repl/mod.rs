@@ -0,0 +1,688 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use codex_protocol::ThreadId;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::process::Child;
+use tokio::process::ChildStdin;
+use tokio::sync::Mutex;
+use tokio::sync::OnceCell;
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::exec_env::create_env;
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::SharedTurnDiffTracker;
+use crate::tools::js_repl::spawn_sandboxed_kernel;
+
+pub(crate) const PY_REPL_PRAGMA_PREFIX: &str = "# codex-py-repl:";
+const KERNEL_SOURCE: &str = include_str!("kernel.py");
+const PY_REPL_MIN_PYTHON_VERSION: PythonVersion = PythonVersion {
+    major: 3,
+    minor: 9,
+    patch: 0,
+};
+/// Virtualenv directory names checked in the session cwd and its ancestors.
+const VENV_DIR_NAMES: &[&str] = &[".venv", "venv"];
+
+/// Per-session py_repl handle stored on the turn context.
+pub(crate) struct PyReplHandle {
+    python_path: Option<PathBuf>,
+    codex_home: PathBuf,
+    cell: OnceCell<Arc<PyReplManager>>,
+}
+
+impl fmt::Debug for PyReplHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PyReplHandle").finish_non_exhaustive()
+    }
+}
+
+impl PyReplHandle {
+    pub(crate) fn with_python_path(python_path: Option<PathBuf>, codex_home: PathBuf) -> Self {
+        Self {
+            python_path,
+            codex_home,
+            cell: OnceCell::new(),
+        }
+    }
+
+    pub(crate) async fn manager(&self) -> Result<Arc<PyReplManager>, FunctionCallError> {
+        self.cell
+            .get_or_try_init(|| async {
+                PyReplManager::new(self.python_path.clone(), self.codex_home.clone()).await
+            })
+            .await
+            .cloned()
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PyReplArgs {
+    pub code: String,
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+}
+
+#[derive(Clone, Debug)]
+pub struct PyExecResult {
+    pub output: String,
+    /// Images produced by the cell (`codex.show_image` or open matplotlib figures).
+    pub images: Vec<PathBuf>,
+}
+
+struct KernelState {
+    _child: Child,
+    stdin: Arc<Mutex<ChildStdin>>,
+    pending_execs: Arc<Mutex<HashMap<String, tokio::sync::oneshot::Sender<ExecResultMessage>>>>,
+    shutdown: CancellationToken,
+}
+
+pub struct PyReplManager {
+    python_path: Option<PathBuf>,
+    codex_home: PathBuf,
+    tmp_dir: tempfile::TempDir,
+    kernel: Mutex<Option<KernelState>>,
+    exec_lock: Arc<tokio::sync::Semaphore>,
+}
+
+impl PyReplManager {
+    async fn new(
+        python_path: Option<PathBuf>,
+        codex_home: PathBuf,
+    ) -> Result<Arc<Self>, FunctionCallError> {
+        let tmp_dir = tempfile::tempdir().map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to create py_repl temp dir: {err}"))
+        })?;
+
+        Ok(Arc::new(Self {
+            python_path,
+            codex_home,
+            tmp_dir,
+            kernel: Mutex::new(None),
+            exec_lock: Arc::new(tokio::sync::Semaphore::new(1)),
+        }))
+    }
+
+    pub async fn reset(&self) -> Result<(), FunctionCallError> {
+        let state = {
+            let mut guard = self.kernel.lock().await;
+            guard.take()
+        };
+        if let Some(state) = state {
+            state.shutdown.cancel();
+        }
+        Ok(())
+    }
+
+    pub async fn execute(
+        &self,
+        session: Arc<Session>,
+        turn: Arc<TurnContext>,
+        _tracker: SharedTurnDiffTracker,
+        args: PyReplArgs,
+    ) -> Result<PyExecResult, FunctionCallError> {
+        let _permit = self.exec_lock.clone().acquire_owned().await.map_err(|_| {
+            FunctionCallError::RespondToModel("py_repl execution unavailable".to_string())
+        })?;
+
+        let (stdin, pending_execs) = {
+            let mut kernel = self.kernel.lock().await;
+            if kernel.is_none() {
+                let state = self
+                    .start_kernel(Arc::clone(&turn), Some(session.conversation_id))
+                    .await
+                    .map_err(FunctionCallError::RespondToModel)?;
+                *kernel = Some(state);
+            }
+
+            let Some(state) = kernel.as_ref() else {
+                return Err(FunctionCallError::RespondToModel(
+                    "py_repl kernel unavailable".to_string(),
+                ));
+            };
+            (Arc::clone(&state.stdin), Arc::clone(&state.pending_execs))
+        };
+
+        let (req_id, rx) = {
+            let req_id = Uuid::new_v4().to_string();
+            let mut pending = pending_execs.lock().await;
+            let (tx, rx) = tokio::sync::oneshot::channel();
+            pending.insert(req_id.clone(), tx);
+            (req_id, rx)
+        };
+
+        let payload = HostToKernel::Exec {
+            id: req_id.clone(),
+            code: args.code,
+        };
+        Self::write_message(&stdin, &payload).await?;
+
+        let timeout_ms = args.timeout_ms.unwrap_or(30_000);
+        let response = match tokio::time::timeout(Duration::from_millis(timeout_ms), rx).await {
+            Ok(Ok(msg)) => msg,
+            Ok(Err(_)) => {
+                let mut pending = pending_execs.lock().await;
+                pending.remove(&req_id);
+                return Err(FunctionCallError::RespondToModel(
+                    "py_repl kernel closed unexpectedly".to_string(),
+                ));
+            }
+            Err(_) => {
+                self.reset().await?;
+                return Err(FunctionCallError::RespondToModel(
+                    "py_repl execution timed out; kernel reset, rerun your request".to_string(),
+                ));
+            }
+        };
+
+        match response {
+            ExecResultMessage::Ok { output, images } => Ok(PyExecResult {
+                output,
+                images: images.into_iter().map(PathBuf::from).collect(),
+            }),
+            ExecResultMessage::Err { message } => Err(FunctionCallError::RespondToModel(message)),
+        }
+    }
+
+    async fn start_kernel(
+        &self,
+        turn: Arc<TurnContext>,
+        thread_id: Option<ThreadId>,
+    ) -> Result<KernelState, String> {
+        let python_path = resolve_python(self.python_path.as_deref(), &turn.cwd).ok_or_else(|| {
+            "Python interpreter not found; create a .venv, install Python 3, or set py_repl_python_path".to_string()
+        })?;
+        ensure_python_version(&python_path).await?;
+
+        let kernel_path = self.tmp_dir.path().join("py_repl_kernel.py");
+        tokio::fs::write(&kernel_path, KERNEL_SOURCE)
+            .await
+            .map_err(|err| format!("failed to write py_repl kernel: {err}"))?;
+
+        let mut env = create_env(&turn.shell_environment_policy, thread_id);
+        env.insert(
+            "CODEX_PY_TMP_DIR".to_string(),
+            self.tmp_dir.path().to_string_lossy().to_string(),
+        );
+        env.insert(
+            "CODEX_PY_REPL_HOME".to_string(),
+            self.codex_home
+                .join("py_repl")
+                .to_string_lossy()
+                .to_string(),
+        );
+        // Render plots off-screen; figures are collected after each cell.
+        env.insert("MPLBACKEND".to_string(), "Agg".to_string());
+        env.insert("PYTHONUNBUFFERED".to_string(), "1".to_string());
+        env.insert("PYTHONIOENCODING".to_string(), "utf-8".to_string());
+
+        let mut child = spawn_sandboxed_kernel(
+            turn.as_ref(),
+            python_path.to_string_lossy().to_string(),
+            vec!["-u".to_string(), kernel_path.to_string_lossy().to_string()],
+            env,
+            "py_repl",
+            "Python interpreter",
+        )?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| "py_repl kernel missing stdout".to_string())?;
+        let stderr = child.stderr.take();
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| "py_repl kernel missing stdin".to_string())?;
+
+        let shutdown = CancellationToken::new();
+        let pending_execs: Arc<
+            Mutex<HashMap<String, tokio::sync::oneshot::Sender<ExecResultMessage>>>,
+        > = Arc::new(Mutex::new(HashMap::new()));
+
+        tokio::spawn(Self::read_stdout(
+            stdout,
+            Arc::clone(&pending_execs),
+            shutdown.clone(),
+        ));
+        if let Some(stderr) = stderr {
+            tokio::spawn(Self::read_stderr(stderr, shutdown.clone()));
+        } else {
+            warn!("py_repl kernel missing stderr");
+        }
+
+        Ok(KernelState {
+            _child: child,
+            stdin: Arc::new(Mutex::new(stdin)),
+            pending_execs,
+            shutdown,
+        })
+    }
+
+    async fn write_message(
+        stdin: &Arc<Mutex<ChildStdin>>,
+        msg: &HostToKernel,
+    ) -> Result<(), FunctionCallError> {
+        let encoded = serde_json::to_string(msg).map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to serialize kernel message: {err}"))
+        })?;
+        let mut guard = stdin.lock().await;
+        guard.write_all(encoded.as_bytes()).await.map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to write to kernel: {err}"))
+        })?;
+        guard.write_all(b"\n").await.map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to flush kernel message: {err}"))
+        })?;
+        Ok(())
+    }
+
+    async fn read_stdout(
+        stdout: tokio::process::ChildStdout,
+        pending_execs: Arc<Mutex<HashMap<String, tokio::sync::oneshot::Sender<ExecResultMessage>>>>,
+        shutdown: CancellationToken,
+    ) {
+        let mut reader = BufReader::new(stdout).lines();
+
+        loop {
+            let line = tokio::select! {
+                _ = shutdown.cancelled() => break,
+                res = reader.next_line() => match res {
+                    Ok(Some(line)) => line,
+                    Ok(None) => break,
+                    Err(err) => {
+                        warn!("py_repl kernel stream ended: {err}");
+                        break;
+                    }
+                },
+            };
+
+            let msg = match serde_json::from_str::<KernelToHost>(&line) {
+                Ok(msg) => msg,
+                Err(err) => {
+                    warn!("py_repl kernel sent invalid json: {err} (line: {line})");
+                    continue;
+                }
+            };
+
+            let KernelToHost::ExecResult {
+                id,
+                ok,
+                output,
+                error,
+                images,
+            } = msg;
+
+            let mut pending = pending_execs.lock().await;
+            if let Some(tx) = pending.remove(&id) {
+                let payload = if ok {
+                    ExecResultMessage::Ok { output, images }
+                } else {
+                    ExecResultMessage::Err {
+                        message: failure_message(&output, error),
+                    }
+                };
+                let _ = tx.send(payload);
+            }
+        }
+
+        let mut pending = pending_execs.lock().await;
+        for (_id, tx) in pending.drain() {
+            let _ = tx.send(ExecResultMessage::Err {
+                message: "py_repl kernel exited unexpectedly".to_string(),
+            });
+        }
+    }
+
+    async fn read_stderr(stderr: tokio::process::ChildStderr, shutdown: CancellationToken) {
+        let mut reader = BufReader::new(stderr).lines();
+
+        loop {
+            let line = tokio::select! {
+                _ = shutdown.cancelled() => break,
+                res = reader.next_line() => match res {
+                    Ok(Some(line)) => line,
+                    Ok(None) => break,
+                    Err(err) => {
+                        warn!("py_repl kernel stderr ended: {err}");
+                        break;
+                    }
+                },
+            };
+            let trimmed = line.trim();
+            if !trimmed.is_empty() {
+                warn!("py_repl stderr: {trimmed}");
+            }
+        }
+    }
+}
+
+/// Output printed before an exception is useful context, so keep it ahead of
+/// the traceback.
+fn failure_message(output: &str, error: Option<String>) -> String {
+    let error = error.unwrap_or_else(|| "py_repl execution failed".to_string());
+    if output.trim().is_empty() {
+        error
+    } else {
+        format!("{output}\n{error}")
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum KernelToHost {
+    ExecResult {
+        id: String,
+        ok: bool,
+        output: String,
+        #[serde(default)]
+        error: Option<String>,
+        #[serde(default)]
+        images: Vec<String>,
+    },
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum HostToKernel {
+    Exec { id: String, code: String },
+}
+
+#[derive(Debug)]
+enum ExecResultMessage {
+    Ok { output: String, images: Vec<String> },
+    Err { message: String },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct PythonVersion {
+    major: u64,
+    minor: u64,
+    patch: u64,
+}
+
+impl fmt::Display for PythonVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl PythonVersion {
+    /// Parse `python --version` output such as `Python 3.12.1` or `Python 3.13.0rc2`.
+    fn parse(input: &str) -> Result<Self, String> {
+        let trimmed = input.trim();
+        let version = trimmed.strip_prefix("Python ").unwrap_or(trimmed);
+        let mut parts = version.split('.');
+        let mut next = |name: &str| -> Result<u64, String> {
+            let part = parts
+                .next()
+                .ok_or_else(|| format!("missing {name} version"))?;
+            let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+            digits
+                .parse::<u64>()
+                .map_err(|err| format!("invalid {name} version: {err}"))
+        };
+        let major = next("major")?;
+        let minor = next("minor")?;
+        let patch = next("patch").unwrap_or(0);
+        Ok(Self {
+            major,
+            minor,
+            patch,
+        })
+    }
+}
+
+async fn read_python_version(python_path: &Path) -> Result<PythonVersion, String> {
+    let output = tokio::process::Command::new(python_path)
+        .arg("--version")
+        .output()
+        .await
+        .map_err(|err| format!("failed to execute Python: {err}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "failed to read Python version (status {status})",
+            status = output.status
+        ));
+    }
+
+    // Python 2 printed its version to stderr.
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let text = if stdout.trim().is_empty() {
+        stderr.trim()
+    } else {
+        stdout.trim()
+    };
+    PythonVersion::parse(text)
+        .map_err(|err| format!("failed to parse Python version output `{text}`: {err}"))
+}
+
+async fn ensure_python_version(python_path: &Path) -> Result<(), String> {
+    let required = PY_REPL_MIN_PYTHON_VERSION;
+    let found = read_python_version(python_path).await?;
+    if found < required {
+        return Err(format!(
+            "Python interpreter too old for py_repl (resolved {python_path}): found {found}, requires >= {required}. Set py_repl_python_path to a newer interpreter.",
+            python_path = python_path.display()
+        ));
+    }
+    Ok(())
+}
+
+fn venv_python(venv: &Path) -> PathBuf {
+    if cfg!(windows) {
+        venv.join("Scripts").join("python.exe")
+    } else {
+        venv.join("bin").join("python")
+    }
+}
+
+/// Resolve the interpreter in priority order: `CODEX_PY_REPL_PYTHON_PATH`,
+/// the configured path, an active `VIRTUAL_ENV`, a `.venv`/`venv` in `cwd` or
+/// one of its ancestors, then `python3`/`python` on `PATH`.
+pub(crate) fn resolve_python(config_path: Option<&Path>, cwd: &Path) -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("CODEX_PY_REPL_PYTHON_PATH") {
+        let p = PathBuf::from(path);
+        if p.exists() {
+            return Some(p);
+        }
+    }
+
+    if let Some(path) = config_path
+        && path.exists()
+    {
+        return Some(path.to_path_buf());
+    }
+
+    if let Some(venv) = std::env::var_os("VIRTUAL_ENV") {
+        let python = venv_python(Path::new(&venv));
+        if python.exists() {
+            return Some(python);
+        }
+    }
+
+    for dir in cwd.ancestors() {
+        for name in VENV_DIR_NAMES {
+            let python = venv_python(&dir.join(name));
+            if python.exists() {
+                return Some(python);
+            }
+        }
+    }
+
+    ["python3", "python"]
+        .into_iter()
+        .find_map(|name| which::which(name).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codex::make_session_and_context;
+    use crate::turn_diff_tracker::TurnDiffTracker;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn python_version_parses_release_and_prerelease() {
+        assert_eq!(
+            PythonVersion::parse("Python 3.12.1\n").unwrap(),
+            PythonVersion {
+                major: 3,
+                minor: 12,
+                patch: 1,
+            }
+        );
+        assert_eq!(
+            PythonVersion::parse("Python 3.13.0rc2").unwrap(),
+            PythonVersion {
+                major: 3,
+                minor: 13,
+                patch: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn resolve_python_prefers_project_venv() {
+        if std::env::var_os("CODEX_PY_REPL_PYTHON_PATH").is_some()
+            || std::env::var_os("VIRTUAL_ENV").is_some()
+        {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let python = venv_python(&dir.path().join(".venv"));
+        std::fs::create_dir_all(python.parent().unwrap()).unwrap();
+        std::fs::write(&python, "").unwrap();
+        let nested = dir.path().join("src/pkg");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        assert_eq!(resolve_python(None, &nested), Some(python));
+    }
+
+    #[test]
+    fn failure_message_keeps_output_before_traceback() {
+        assert_eq!(
+            failure_message("partial\n", Some("Traceback".to_string())),
+            "partial\n\nTraceback"
+        );
+        assert_eq!(failure_message("", None), "py_repl execution failed");
+    }
+
+    async fn can_run_py_repl_runtime_tests() -> bool {
+        if std::env::var_os("CODEX_SANDBOX").is_some() {
+            return false;
+        }
+        let Ok(cwd) = std::env::current_dir() else {
+            return false;
+        };
+        let Some(python_path) = resolve_python(None, &cwd) else {
+            return false;
+        };
+        matches!(
+            read_python_version(&python_path).await,
+            Ok(found) if found >= PY_REPL_MIN_PYTHON_VERSION
+        )
+    }
+
+    #[tokio::test]
+    async fn py_repl_persists_state_and_echoes_last_expression() -> anyhow::Result<()> {
+        if !can_run_py_repl_runtime_tests().await {
+            return Ok(());
+        }
+
+        let (session, turn) = make_session_and_context().await;
+        let session = Arc::new(session);
+        let turn = Arc::new(turn);
+        let tracker = Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::default()));
+        let manager = turn.py_repl.manager().await?;
+
+        let first = manager
+            .execute(
+                Arc::clone(&session),
+                Arc::clone(&turn),
+                Arc::clone(&tracker),
+                PyReplArgs {
+                    code: "x = 41\nprint('set')".to_string(),
+                    timeout_ms: Some(10_000),
+                },
+            )
+            .await?;
+        assert_eq!(first.output, "set");
+
+        let second = manager
+            .execute(
+                Arc::clone(&session),
+                Arc::clone(&turn),
+                Arc::clone(&tracker),
+                PyReplArgs {
+                    code: "x + 1".to_string(),
+                    timeout_ms: Some(10_000),
+                },
+            )
+            .await?;
+        assert_eq!(second.output, "42");
+
+        manager.reset().await?;
+        let err = manager
+            .execute(
+                session,
+                turn,
+                tracker,
+                PyReplArgs {
+                    code: "x".to_string(),
+                    timeout_ms: Some(10_000),
+                },
+            )
+            .await
+            .expect_err("reset should clear bindings");
+        assert!(err.to_string().contains("NameError"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn py_repl_timeout_does_not_deadlock() -> anyhow::Result<()> {
+        if !can_run_py_repl_runtime_tests().await {
+            return Ok(());
+        }
+
+        let (session, turn) = make_session_and_context().await;
+        let session = Arc::new(session);
+        let turn = Arc::new(turn);
+        let tracker = Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::default()));
+        let manager = turn.py_repl.manager().await?;
+
+        let result = tokio::time::timeout(
+            Duration::from_secs(3),
+            manager.execute(
+                session,
+                turn,
+                tracker,
+                PyReplArgs {
+                    code: "while True:\n    pass".to_string(),
+                    timeout_ms: Some(50),
+                },
+            ),
+        )
+        .await
+        .expect("execute should return, not deadlock")
+        .expect_err("expected timeout error");
+
+        assert_eq!(
+            result.to_string(),
+            "py_repl execution timed out; kernel reset, rerun your request"
+        );
+        Ok(())
+    }
+}
@@ -47,6 +47,13 @@ pub trait ToolHandler: Send + Sync {
         false
     }
 
+    /// Returns `true` if every file the handler writes is announced through a
+    /// patch event beforehand, so other changes made while it runs can be told
+    /// apart from the agent's own.
+    fn reports_file_writes(&self) -> bool {
+        false
+    }
+
     /// Perform the actual [ToolInvocation] and returns a [ToolOutput] containing
     /// the final output to return to the model.
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError>;
@@ -129,6 +136,15 @@ impl ToolRegistry {
         }
 
         let is_mutating = handler.is_mutating(&invocation).await;
+        let _untracked_writes = ((is_mutating && !handler.reports_file_writes())
+            || handler.kind() == ToolKind::Mcp)
+            .then(|| {
+                invocation
+                    .session
+                    .services
+                    .external_edits
+                    .agent_untracked_writes_started()
+            });
         let output_cell = tokio::sync::Mutex::new(None);
         let invocation_for_tool = invocation.clone();
 
@@ -231,6 +247,10 @@ impl ToolRegistryBuilder {
             .push(ConfiguredToolSpec::new(spec, supports_parallel_tool_calls));
     }
 
+    pub fn has_handler(&self, name: &str) -> bool {
+        self.handlers.contains_key(name)
+    }
+
     pub fn register_handler(&mut self, name: impl Into<String>, handler: Arc<dyn ToolHandler>) {
         let name = name.into();
         if self
@@ -101,6 +101,7 @@ impl Approvable<UnifiedExecRequest> for UnifiedExecRuntime<'_> {
             .retry_reason
             .clone()
             .or_else(|| req.justification.clone());
+        let provenance = ctx.provenance;
         Box::pin(async move {
             with_cached_approval(&session.services, "unified_exec", keys, || async move {
                 session
@@ -113,6 +114,7 @@ impl Approvable<UnifiedExecRequest> for UnifiedExecRuntime<'_> {
                         req.exec_approval_requirement
                             .proposed_execpolicy_amendment()
                             .cloned(),
+                        Some(provenance),
                     )
                     .await
             })
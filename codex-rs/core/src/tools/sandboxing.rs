@@ -13,6 +13,7 @@ use crate::sandboxing::SandboxManager;
 use crate::sandboxing::SandboxTransformError;
 use crate::state::SessionServices;
 use codex_network_proxy::NetworkProxy;
+use codex_protocol::approvals::DecisionProvenance;
 use codex_protocol::approvals::ExecPolicyAmendment;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::ReviewDecision;
@@ -73,13 +74,7 @@ where
         return fetch().await;
     }
 
-    let already_approved = {
-        let store = services.tool_approvals.lock().await;
-        keys.iter()
-            .all(|key| matches!(store.get(key), Some(ReviewDecision::ApprovedForSession)))
-    };
-
-    if already_approved {
+    if approved_for_session(services, &keys).await {
         return ReviewDecision::ApprovedForSession;
     }
 
@@ -104,12 +99,27 @@ where
     decision
 }
 
+/// Whether every key was already approved for the rest of the session, in
+/// which case [`with_cached_approval`] will not prompt.
+pub(crate) async fn approved_for_session<K>(services: &SessionServices, keys: &[K]) -> bool
+where
+    K: Serialize,
+{
+    if keys.is_empty() {
+        return false;
+    }
+    let store = services.tool_approvals.lock().await;
+    keys.iter()
+        .all(|key| matches!(store.get(key), Some(ReviewDecision::ApprovedForSession)))
+}
+
 #[derive(Clone)]
 pub(crate) struct ApprovalCtx<'a> {
     pub session: &'a Session,
     pub turn: &'a TurnContext,
     pub call_id: &'a str,
     pub retry_reason: Option<String>,
+    pub provenance: DecisionProvenance,
 }
 
 // Specifies what tool orchestrator should do with a given tool call.
@@ -123,6 +133,7 @@ pub(crate) enum ExecApprovalRequirement {
         /// Proposed execpolicy amendment to skip future approvals for similar commands
         /// Only applies if the command fails to run in sandbox and codex prompts the user to run outside the sandbox.
         proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
+        provenance: DecisionProvenance,
     },
     /// Approval required for this tool call.
     NeedsApproval {
@@ -130,9 +141,13 @@ pub(crate) enum ExecApprovalRequirement {
         /// Proposed execpolicy amendment to skip future approvals for similar commands
         /// See core/src/exec_policy.rs for more details on how proposed_execpolicy_amendment is determined.
         proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
+        provenance: DecisionProvenance,
     },
     /// Execution forbidden for this tool call.
-    Forbidden { reason: String },
+    Forbidden {
+        reason: String,
+        provenance: DecisionProvenance,
+    },
 }
 
 impl ExecApprovalRequirement {
@@ -149,6 +164,15 @@ impl ExecApprovalRequirement {
             _ => None,
         }
     }
+
+    /// What produced this requirement.
+    pub fn provenance(&self) -> &DecisionProvenance {
+        match self {
+            Self::Skip { provenance, .. }
+            | Self::NeedsApproval { provenance, .. }
+            | Self::Forbidden { provenance, .. } => provenance,
+        }
+    }
 }
 
 /// - Never, OnFailure: do not ask
@@ -167,15 +191,20 @@ pub(crate) fn default_exec_approval_requirement(
         AskForApproval::UnlessTrusted => true,
     };
 
+    let provenance = DecisionProvenance::ApprovalPolicy {
+        approval_policy: policy,
+    };
     if needs_approval {
         ExecApprovalRequirement::NeedsApproval {
             reason: None,
             proposed_execpolicy_amendment: None,
+            provenance,
         }
     } else {
         ExecApprovalRequirement::Skip {
             bypass_sandbox: false,
             proposed_execpolicy_amendment: None,
+            provenance,
         }
     }
 }
@@ -319,6 +348,9 @@ mod tests {
             ExecApprovalRequirement::Skip {
                 bypass_sandbox: false,
                 proposed_execpolicy_amendment: None,
+                provenance: DecisionProvenance::ApprovalPolicy {
+                    approval_policy: AskForApproval::OnRequest,
+                },
             }
         );
     }
@@ -333,6 +365,9 @@ mod tests {
             ExecApprovalRequirement::NeedsApproval {
                 reason: None,
                 proposed_execpolicy_amendment: None,
+                provenance: DecisionProvenance::ApprovalPolicy {
+                    approval_policy: AskForApproval::OnRequest,
+                },
             }
         );
     }
@@ -3,6 +3,7 @@ use crate::client_common::tools::FreeformTool;
 use crate::client_common::tools::FreeformToolFormat;
 use crate::client_common::tools::ResponsesApiTool;
 use crate::client_common::tools::ToolSpec;
+use crate::config::types::ConfigDynamicTool;
 use crate::features::Feature;
 use crate::features::Features;
 use crate::tools::handlers::PLAN_TOOL;
@@ -34,12 +35,14 @@ pub(crate) struct ToolsConfig {
     pub web_search_mode: Option<WebSearchMode>,
     pub search_tool: bool,
     pub js_repl_enabled: bool,
+    pub py_repl_enabled: bool,
     pub collab_tools: bool,
     pub collaboration_modes_tools: bool,
     pub request_rule_enabled: bool,
     pub experimental_supported_tools: Vec<String>,
     pub plugin_spawnable_roles: Vec<String>,
     pub plugin_categories: Vec<String>,
+    pub config_dynamic_tools: Vec<ConfigDynamicTool>,
 }
 
 pub(crate) struct ToolsConfigParams<'a> {
@@ -59,6 +62,7 @@ impl ToolsConfig {
         } = params;
         let include_apply_patch_tool = features.enabled(Feature::ApplyPatchFreeform);
         let include_js_repl = features.enabled(Feature::JsRepl);
+        let include_py_repl = features.enabled(Feature::PyRepl);
         let include_collab_tools = features.enabled(Feature::Collab);
         let include_collaboration_modes_tools = features.enabled(Feature::CollaborationModes);
         let request_rule_enabled = features.enabled(Feature::RequestRule);
@@ -95,6 +99,7 @@ impl ToolsConfig {
             web_search_mode: *web_search_mode,
             search_tool: include_search_tool,
             js_repl_enabled: include_js_repl,
+            py_repl_enabled: include_py_repl,
             collab_tools: include_collab_tools,
             collaboration_modes_tools: include_collaboration_modes_tools,
             request_rule_enabled,
@@ -110,8 +115,22 @@ impl ToolsConfig {
             plugin_categories: plugins
                 .map(|registry| registry.categories().map(|cat| cat.id.clone()).collect())
                 .unwrap_or_default(),
+            config_dynamic_tools: Vec::new(),
         }
     }
+
+    /// Expose the tools declared in config (`[dynamic_tools]`, `[openapi]`).
+    /// Deferred tools are only reachable through `search_tool_bm25`, so without
+    /// it (`Feature::Apps` is off) they are listed like every other tool.
+    pub fn with_config_dynamic_tools(mut self, tools: &[ConfigDynamicTool]) -> Self {
+        self.config_dynamic_tools = tools.to_vec();
+        if !self.search_tool {
+            for tool in &mut self.config_dynamic_tools {
+                tool.deferred = false;
+            }
+        }
+        self
+    }
 }
 
 pub(crate) fn filter_tools_for_model(tools: Vec<ToolSpec>, _config: &ToolsConfig) -> Vec<ToolSpec> {
@@ -1114,6 +1133,35 @@ fn create_js_repl_tool() -> ToolSpec {
     })
 }
 
+fn create_py_repl_tool() -> ToolSpec {
+    const PY_REPL_FREEFORM_GRAMMAR: &str = r#"start: /[\s\S]*/"#;
+
+    ToolSpec::Freeform(FreeformTool {
+        name: "py_repl".to_string(),
+        description: "Runs Python in a persistent kernel; the value of a trailing expression is echoed and matplotlib figures are attached as images. This is a freeform tool: send raw Python source text, optionally with a first-line pragma like `# codex-py-repl: timeout_ms=15000`; do not send JSON/quotes/markdown fences."
+            .to_string(),
+        format: FreeformToolFormat {
+            r#type: "grammar".to_string(),
+            syntax: "lark".to_string(),
+            definition: PY_REPL_FREEFORM_GRAMMAR.to_string(),
+        },
+    })
+}
+
+fn create_py_repl_reset_tool() -> ToolSpec {
+    ToolSpec::Function(ResponsesApiTool {
+        name: "py_repl_reset".to_string(),
+        description: "Restarts the py_repl kernel for this run and clears all Python globals."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties: BTreeMap::new(),
+            required: None,
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_js_repl_reset_tool() -> ToolSpec {
     ToolSpec::Function(ResponsesApiTool {
         name: "js_repl_reset".to_string(),
@@ -1434,6 +1482,7 @@ pub(crate) fn build_specs(
 ) -> ToolRegistryBuilder {
     use crate::tools::handlers::ApplyPatchHandler;
     use crate::tools::handlers::CollabHandler;
+    use crate::tools::handlers::ConfigDynamicToolHandler;
     use crate::tools::handlers::DynamicToolHandler;
     use crate::tools::handlers::GrepFilesHandler;
     use crate::tools::handlers::JsReplHandler;
@@ -1442,6 +1491,8 @@ pub(crate) fn build_specs(
     use crate::tools::handlers::McpHandler;
     use crate::tools::handlers::McpResourceHandler;
     use crate::tools::handlers::PlanHandler;
+    use crate::tools::handlers::PyReplHandler;
+    use crate::tools::handlers::PyReplResetHandler;
     use crate::tools::handlers::ReadFileHandler;
     use crate::tools::handlers::RequestUserInputHandler;
     use crate::tools::handlers::SearchToolBm25Handler;
@@ -1459,6 +1510,7 @@ pub(crate) fn build_specs(
     let plan_handler = Arc::new(PlanHandler);
     let apply_patch_handler = Arc::new(ApplyPatchHandler);
     let dynamic_tool_handler = Arc::new(DynamicToolHandler);
+    let config_dynamic_tool_handler = Arc::new(ConfigDynamicToolHandler);
     let view_image_handler = Arc::new(ViewImageHandler);
     let mcp_handler = Arc::new(McpHandler);
     let mcp_resource_handler = Arc::new(McpResourceHandler);
@@ -1467,6 +1519,8 @@ pub(crate) fn build_specs(
     let search_tool_handler = Arc::new(SearchToolBm25Handler);
     let js_repl_handler = Arc::new(JsReplHandler);
     let js_repl_reset_handler = Arc::new(JsReplResetHandler);
+    let py_repl_handler = Arc::new(PyReplHandler);
+    let py_repl_reset_handler = Arc::new(PyReplResetHandler);
 
     match &config.shell_type {
         ConfigShellToolType::Default => {
@@ -1523,6 +1577,13 @@ pub(crate) fn build_specs(
         builder.register_handler("js_repl_reset", js_repl_reset_handler);
     }
 
+    if config.py_repl_enabled {
+        builder.push_spec(create_py_repl_tool());
+        builder.push_spec(create_py_repl_reset_tool());
+        builder.register_handler("py_repl", py_repl_handler);
+        builder.register_handler("py_repl_reset", py_repl_reset_handler);
+    }
+
     if config.collaboration_modes_tools {
         builder.push_spec(create_request_user_input_tool());
         builder.register_handler("request_user_input", request_user_input_handler);
@@ -1647,6 +1708,33 @@ pub(crate) fn build_specs(
         }
     }
 
+    for tool in &config.config_dynamic_tools {
+        if builder.has_handler(&tool.name) {
+            tracing::warn!(
+                "Ignoring dynamic tool {:?} from config: a tool with that name already exists",
+                tool.name
+            );
+            continue;
+        }
+        let spec = DynamicToolSpec {
+            name: tool.name.clone(),
+            description: tool.description.clone(),
+            input_schema: tool.input_schema.clone(),
+        };
+        match dynamic_tool_to_openai_tool(&spec) {
+            Ok(converted_tool) => {
+                builder.push_spec(ToolSpec::Function(converted_tool));
+                builder.register_handler(tool.name.clone(), config_dynamic_tool_handler.clone());
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Failed to convert dynamic tool {:?} to OpenAI tool: {e:?}",
+                    tool.name
+                );
+            }
+        }
+    }
+
     builder
 }
 
@@ -1654,12 +1742,15 @@ pub(crate) fn build_specs(
 mod tests {
     use crate::client_common::tools::FreeformTool;
     use crate::config::test_config;
+    use crate::config::types::DynamicToolApproval;
+    use crate::config::types::DynamicToolBackend;
     use crate::models_manager::manager::ModelsManager;
     use crate::models_manager::model_info::with_config_overrides;
     use crate::tools::registry::ConfiguredToolSpec;
     use codex_protocol::openai_models::ModelInfo;
     use codex_protocol::openai_models::ModelsResponse;
     use pretty_assertions::assert_eq;
+    use std::time::Duration;
 
     use super::*;
 
@@ -1894,6 +1985,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn deferred_config_tools_do_not_enable_search_without_apps() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let deferred = ConfigDynamicTool {
+            name: "petstore__listPets".to_string(),
+            description: "List pets".to_string(),
+            backend: DynamicToolBackend::Http {
+                url: "http://localhost/pets".to_string(),
+                http_headers: HashMap::new(),
+            },
+            input_schema: serde_json::json!({"type": "object", "properties": {}}),
+            timeout: Duration::from_secs(1),
+            approval: DynamicToolApproval::Auto,
+            deferred: true,
+        };
+
+        let mut features = Features::with_defaults();
+        features.disable(Feature::Apps);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+            plugins: None,
+        })
+        .with_config_dynamic_tools(std::slice::from_ref(&deferred));
+        assert!(!tools_config.search_tool);
+        assert!(
+            tools_config
+                .config_dynamic_tools
+                .iter()
+                .all(|tool| !tool.deferred)
+        );
+
+        features.enable(Feature::Apps);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+            plugins: None,
+        })
+        .with_config_dynamic_tools(std::slice::from_ref(&deferred));
+        assert!(tools_config.search_tool);
+        assert_eq!(tools_config.config_dynamic_tools, vec![deferred]);
+    }
+
     #[test]
     fn request_user_input_requires_collaboration_modes_feature() {
         let config = test_config();
@@ -1965,6 +2103,36 @@ mod tests {
         assert_contains_tool_names(&tools, &["js_repl", "js_repl_reset"]);
     }
 
+    #[test]
+    fn py_repl_enabled_adds_tools() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let features = Features::with_defaults();
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+            plugins: None,
+        });
+        let (tools, _) = build_specs(&tools_config, None, &[]).build();
+        assert!(
+            !tools.iter().any(|tool| tool.spec.name() == "py_repl"),
+            "py_repl should be disabled when the feature is off"
+        );
+
+        let mut features = Features::with_defaults();
+        features.enable(Feature::PyRepl);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+            plugins: None,
+        });
+        let (tools, _) = build_specs(&tools_config, None, &[]).build();
+        assert_contains_tool_names(&tools, &["py_repl", "py_repl_reset"]);
+    }
+
     fn assert_model_tools(
         model_slug: &str,
         features: &Features,
@@ -7,6 +7,8 @@ use codex_protocol::openai_models::TruncationMode;
 use codex_protocol::openai_models::TruncationPolicyConfig;
 use codex_protocol::protocol::TruncationPolicy as ProtocolTruncationPolicy;
 
+use crate::tokenizer::Tokenizer;
+
 const APPROX_BYTES_PER_TOKEN: usize = 4;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -76,20 +78,32 @@ impl std::ops::Mul<f64> for TruncationPolicy {
     }
 }
 
-pub(crate) fn formatted_truncate_text(content: &str, policy: TruncationPolicy) -> String {
-    if content.len() <= policy.byte_budget() {
+pub(crate) fn formatted_truncate_text(
+    content: &str,
+    policy: TruncationPolicy,
+    tokenizer: &dyn Tokenizer,
+) -> String {
+    let fits = match policy {
+        TruncationPolicy::Bytes(bytes) => content.len() <= bytes,
+        TruncationPolicy::Tokens(tokens) => tokenizer.count_tokens(content) <= tokens,
+    };
+    if fits {
         return content.to_string();
     }
     let total_lines = content.lines().count();
-    let result = truncate_text(content, policy);
+    let result = truncate_text(content, policy, tokenizer);
     format!("Total output lines: {total_lines}\n\n{result}")
 }
 
-pub(crate) fn truncate_text(content: &str, policy: TruncationPolicy) -> String {
+pub(crate) fn truncate_text(
+    content: &str,
+    policy: TruncationPolicy,
+    tokenizer: &dyn Tokenizer,
+) -> String {
     match policy {
         TruncationPolicy::Bytes(_) => truncate_with_byte_estimate(content, policy),
         TruncationPolicy::Tokens(_) => {
-            let (truncated, _) = truncate_with_token_budget(content, policy);
+            let (truncated, _) = truncate_with_token_budget(content, policy, tokenizer);
             truncated
         }
     }
@@ -100,6 +114,7 @@ pub(crate) fn truncate_text(content: &str, policy: TruncationPolicy) -> String {
 pub(crate) fn truncate_function_output_items_with_policy(
     items: &[FunctionCallOutputContentItem],
     policy: TruncationPolicy,
+    tokenizer: &dyn Tokenizer,
 ) -> Vec<FunctionCallOutputContentItem> {
     let mut out: Vec<FunctionCallOutputContentItem> = Vec::with_capacity(items.len());
     let mut remaining_budget = match policy {
@@ -118,7 +133,7 @@ pub(crate) fn truncate_function_output_items_with_policy(
 
                 let cost = match policy {
                     TruncationPolicy::Bytes(_) => text.len(),
-                    TruncationPolicy::Tokens(_) => approx_token_count(text),
+                    TruncationPolicy::Tokens(_) => tokenizer.count_tokens(text),
                 };
 
                 if cost <= remaining_budget {
@@ -129,7 +144,7 @@ pub(crate) fn truncate_function_output_items_with_policy(
                         TruncationPolicy::Bytes(_) => TruncationPolicy::Bytes(remaining_budget),
                         TruncationPolicy::Tokens(_) => TruncationPolicy::Tokens(remaining_budget),
                     };
-                    let snippet = truncate_text(text, snippet_policy);
+                    let snippet = truncate_text(text, snippet_policy, tokenizer);
                     if snippet.is_empty() {
                         omitted_text_items += 1;
                     } else {
@@ -159,25 +174,36 @@ pub(crate) fn truncate_function_output_items_with_policy(
 /// preserving the beginning and the end. Returns the possibly truncated string
 /// and `Some(original_token_count)` if truncation occurred; otherwise returns
 /// the original string and `None`.
-fn truncate_with_token_budget(s: &str, policy: TruncationPolicy) -> (String, Option<u64>) {
+fn truncate_with_token_budget(
+    s: &str,
+    policy: TruncationPolicy,
+    tokenizer: &dyn Tokenizer,
+) -> (String, Option<u64>) {
     if s.is_empty() {
         return (String::new(), None);
     }
     let max_tokens = policy.token_budget();
 
-    let byte_len = s.len();
-    if max_tokens > 0 && byte_len <= approx_bytes_for_tokens(max_tokens) {
+    let total_tokens = tokenizer.count_tokens(s);
+    if max_tokens > 0 && total_tokens <= max_tokens {
         return (s.to_string(), None);
     }
+    let total = u64::try_from(total_tokens).unwrap_or(u64::MAX);
 
-    let truncated = truncate_with_byte_estimate(s, policy);
-    let approx_total_usize = approx_token_count(s);
-    let approx_total = u64::try_from(approx_total_usize).unwrap_or(u64::MAX);
-    if truncated == s {
-        (truncated, None)
-    } else {
-        (truncated, Some(approx_total))
+    // Cut on bytes, sized from the tokenizer's view of how dense this text is.
+    let max_bytes = tokenizer.bytes_for_tokens(s, total_tokens, max_tokens);
+    let removed_tokens = u64::try_from(total_tokens.saturating_sub(max_tokens)).unwrap_or(u64::MAX);
+    if max_bytes == 0 {
+        return (format_truncation_marker(policy, total), Some(total));
+    }
+    if s.len() <= max_bytes {
+        return (s.to_string(), None);
     }
+
+    let (left_budget, right_budget) = split_budget(max_bytes);
+    let (_, left, right) = split_string(s, left_budget, right_budget);
+    let marker = format_truncation_marker(policy, removed_tokens);
+    (assemble_truncated_output(left, right, &marker), Some(total))
 }
 
 /// Truncate a string using a byte budget derived from the token budget, without
@@ -322,6 +348,9 @@ mod tests {
     use super::truncate_function_output_items_with_policy;
     use super::truncate_text;
     use super::truncate_with_token_budget;
+    use crate::tokenizer::BpeTokenizer;
+    use crate::tokenizer::HeuristicTokenizer;
+    use crate::tokenizer::Tokenizer;
     use codex_protocol::models::FunctionCallOutputContentItem;
     use pretty_assertions::assert_eq;
 
@@ -366,7 +395,7 @@ mod tests {
 
         assert_eq!(
             "Total output lines: 1\n\n…13 chars truncated…t",
-            formatted_truncate_text(content, TruncationPolicy::Bytes(1)),
+            formatted_truncate_text(content, TruncationPolicy::Bytes(1), &HeuristicTokenizer),
         );
     }
 
@@ -376,7 +405,7 @@ mod tests {
 
         assert_eq!(
             "Total output lines: 1\n\nex…3 tokens truncated…ut",
-            formatted_truncate_text(content, TruncationPolicy::Tokens(1)),
+            formatted_truncate_text(content, TruncationPolicy::Tokens(1), &HeuristicTokenizer),
         );
     }
 
@@ -386,7 +415,7 @@ mod tests {
 
         assert_eq!(
             content,
-            formatted_truncate_text(content, TruncationPolicy::Tokens(10)),
+            formatted_truncate_text(content, TruncationPolicy::Tokens(10), &HeuristicTokenizer),
         );
     }
 
@@ -396,7 +425,7 @@ mod tests {
 
         assert_eq!(
             content,
-            formatted_truncate_text(content, TruncationPolicy::Bytes(20)),
+            formatted_truncate_text(content, TruncationPolicy::Bytes(20), &HeuristicTokenizer),
         );
     }
 
@@ -406,7 +435,7 @@ mod tests {
 
         assert_eq!(
             "Total output lines: 1\n\nthis is an…10 tokens truncated… truncated",
-            formatted_truncate_text(content, TruncationPolicy::Tokens(5)),
+            formatted_truncate_text(content, TruncationPolicy::Tokens(5), &HeuristicTokenizer),
         );
     }
 
@@ -416,7 +445,7 @@ mod tests {
 
         assert_eq!(
             "Total output lines: 1\n\nthis is an exam…30 chars truncated…ld be truncated",
-            formatted_truncate_text(content, TruncationPolicy::Bytes(30)),
+            formatted_truncate_text(content, TruncationPolicy::Bytes(30), &HeuristicTokenizer),
         );
     }
 
@@ -427,7 +456,7 @@ mod tests {
 
         assert_eq!(
             "Total output lines: 2\n\nthis is an exam…51 chars truncated…some other line",
-            formatted_truncate_text(content, TruncationPolicy::Bytes(30)),
+            formatted_truncate_text(content, TruncationPolicy::Bytes(30), &HeuristicTokenizer),
         );
     }
 
@@ -438,7 +467,7 @@ mod tests {
 
         assert_eq!(
             "Total output lines: 2\n\nthis is an example o…11 tokens truncated…also some other line",
-            formatted_truncate_text(content, TruncationPolicy::Tokens(10)),
+            formatted_truncate_text(content, TruncationPolicy::Tokens(10), &HeuristicTokenizer),
         );
     }
 
@@ -446,7 +475,8 @@ mod tests {
     fn truncate_with_token_budget_returns_original_when_under_limit() {
         let s = "short output";
         let limit = 100;
-        let (out, original) = truncate_with_token_budget(s, TruncationPolicy::Tokens(limit));
+        let (out, original) =
+            truncate_with_token_budget(s, TruncationPolicy::Tokens(limit), &HeuristicTokenizer);
         assert_eq!(out, s);
         assert_eq!(original, None);
     }
@@ -454,7 +484,8 @@ mod tests {
     #[test]
     fn truncate_with_token_budget_reports_truncation_at_zero_limit() {
         let s = "abcdef";
-        let (out, original) = truncate_with_token_budget(s, TruncationPolicy::Tokens(0));
+        let (out, original) =
+            truncate_with_token_budget(s, TruncationPolicy::Tokens(0), &HeuristicTokenizer);
         assert_eq!(out, "…2 tokens truncated…");
         assert_eq!(original, Some(2));
     }
@@ -462,15 +493,33 @@ mod tests {
     #[test]
     fn truncate_middle_tokens_handles_utf8_content() {
         let s = "😀😀😀😀😀😀😀😀😀😀\nsecond line with text\n";
-        let (out, tokens) = truncate_with_token_budget(s, TruncationPolicy::Tokens(8));
+        let (out, tokens) =
+            truncate_with_token_budget(s, TruncationPolicy::Tokens(8), &HeuristicTokenizer);
         assert_eq!(out, "😀😀😀😀…8 tokens truncated… line with text\n");
         assert_eq!(tokens, Some(16));
     }
 
+    #[test]
+    fn truncate_with_token_budget_uses_tokenizer_counts() {
+        let tokenizer = BpeTokenizer::O200K_BASE;
+        let s = "fn main() { println!(\"hello\"); }\n".repeat(40);
+        let total = tokenizer.count_tokens(&s);
+        assert_ne!(total, HeuristicTokenizer.count_tokens(&s));
+
+        let (out, original) =
+            truncate_with_token_budget(&s, TruncationPolicy::Tokens(20), &tokenizer);
+        assert_eq!(original, Some(total as u64));
+        assert!(
+            out.contains(&format!("…{} tokens truncated…", total - 20)),
+            "unexpected marker: {out}"
+        );
+        assert!(out.starts_with("fn main()") && out.ends_with("}\n"));
+    }
+
     #[test]
     fn truncate_middle_bytes_handles_utf8_content() {
         let s = "😀😀😀😀😀😀😀😀😀😀\nsecond line with text\n";
-        let out = truncate_text(s, TruncationPolicy::Bytes(20));
+        let out = truncate_text(s, TruncationPolicy::Bytes(20), &HeuristicTokenizer);
         assert_eq!(out, "😀😀…21 chars truncated…with text\n");
     }
 
@@ -497,8 +546,11 @@ mod tests {
             FunctionCallOutputContentItem::InputText { text: t5 },
         ];
 
-        let output =
-            truncate_function_output_items_with_policy(&items, TruncationPolicy::Tokens(limit));
+        let output = truncate_function_output_items_with_policy(
+            &items,
+            TruncationPolicy::Tokens(limit),
+            &HeuristicTokenizer,
+        );
 
         // Expect: t1 (full), t2 (full), image, t3 (truncated), summary mentioning 2 omitted.
         assert_eq!(output.len(), 5);
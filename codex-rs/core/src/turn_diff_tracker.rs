@@ -29,6 +29,9 @@ struct BaselineFileInfo {
 /// 2. Keep a stable internal filename (uuid) per external path for rename tracking.
 /// 3. To compute the aggregated unified diff, compare each baseline snapshot to the current file on disk entirely in-memory
 ///    using the `similar` crate and emit unified diffs with rewritten external paths.
+/// 4. Edits made outside Codex to tracked files are folded into the baseline (see
+///    [`TurnDiffTracker::on_external_change`]) so the aggregated diff only shows the agent's
+///    changes, and are reported separately by [`TurnDiffTracker::get_external_unified_diff`].
 #[derive(Default)]
 pub struct TurnDiffTracker {
     /// Map external path -> internal filename (uuid).
@@ -40,6 +43,10 @@ pub struct TurnDiffTracker {
     temp_name_to_current_path: HashMap<String, PathBuf>,
     /// Cache of known git worktree roots to avoid repeated filesystem walks.
     git_root_cache: Vec<PathBuf>,
+    /// Internal filename -> file contents as the agent last left them (`None` when absent).
+    agent_snapshots: HashMap<String, Option<Vec<u8>>>,
+    /// Unified diffs of edits made outside Codex to tracked files, in the order observed.
+    external_diffs: Vec<String>,
 }
 
 impl TurnDiffTracker {
@@ -127,6 +134,67 @@ impl TurnDiffTracker {
         }
     }
 
+    /// Record the current on-disk contents of every tracked file as the agent's own state.
+    /// Called after each tool call so later differences can be attributed to someone else.
+    pub fn refresh_agent_snapshots(&mut self) {
+        let internals: Vec<String> = self.baseline_file_info.keys().cloned().collect();
+        for internal in internals {
+            let Some(path) = self.get_path_for_internal(&internal) else {
+                continue;
+            };
+            let bytes = read_current_bytes(&path);
+            self.agent_snapshots.insert(internal, bytes);
+        }
+    }
+
+    /// Handle a change to `path` that was made outside Codex.
+    ///
+    /// If the file is tracked and differs from the agent's last snapshot, the difference is
+    /// recorded as an external diff and merged into the baseline, so the aggregated diff keeps
+    /// showing only the agent's edits. When the merge conflicts (or the agent created the file)
+    /// the baseline is left unchanged. Returns true when an external change was recorded.
+    pub fn on_external_change(&mut self, path: &Path) -> bool {
+        let Some(internal) = self.external_to_temp_name.get(path).cloned() else {
+            return false;
+        };
+        let Some(snapshot) = self.agent_snapshots.get(&internal).cloned() else {
+            return false;
+        };
+        let current = read_current_bytes(path);
+        if current == snapshot {
+            return false;
+        }
+
+        let display = self.relative_to_git_root_str(path);
+        self.external_diffs.push(render_bytes_diff(
+            &display,
+            snapshot.as_deref(),
+            current.as_deref(),
+        ));
+
+        if let (Some(snapshot), Some(current), Some(info)) = (
+            snapshot.as_deref(),
+            current.as_deref(),
+            self.baseline_file_info.get_mut(&internal),
+        ) && info.oid != ZERO_OID
+            && let Ok(merged) = diffy::merge_bytes(snapshot, &info.content, current)
+        {
+            info.oid = format!("{:x}", git_blob_sha1_hex_bytes(&merged));
+            info.content = merged;
+        }
+        self.agent_snapshots.insert(internal, current);
+        true
+    }
+
+    /// Unified diff of the edits made outside Codex to tracked files during this turn.
+    pub fn get_external_unified_diff(&self) -> Option<String> {
+        if self.external_diffs.is_empty() {
+            None
+        } else {
+            Some(self.external_diffs.concat())
+        }
+    }
+
     fn get_path_for_internal(&self, internal: &str) -> Option<PathBuf> {
         self.temp_name_to_current_path
             .get(internal)
@@ -368,6 +436,54 @@ impl TurnDiffTracker {
     }
 }
 
+fn read_current_bytes(path: &Path) -> Option<Vec<u8>> {
+    let mode = file_mode_for_path(path)?;
+    blob_bytes(path, mode)
+}
+
+/// Render a git-style diff of one file between two in-memory versions.
+fn render_bytes_diff(display: &str, left: Option<&[u8]>, right: Option<&[u8]>) -> String {
+    let oid = |bytes: Option<&[u8]>| {
+        bytes.map_or_else(
+            || ZERO_OID.to_string(),
+            |b| format!("{:x}", git_blob_sha1_hex_bytes(b)),
+        )
+    };
+    let old_header = if left.is_some() {
+        format!("a/{display}")
+    } else {
+        DEV_NULL.to_string()
+    };
+    let new_header = if right.is_some() {
+        format!("b/{display}")
+    } else {
+        DEV_NULL.to_string()
+    };
+
+    let mut out = format!("diff --git a/{display} b/{display}\n");
+    out.push_str(&format!("index {}..{}\n", oid(left), oid(right)));
+    let left_text = left.map(std::str::from_utf8).transpose();
+    let right_text = right.map(std::str::from_utf8).transpose();
+    if let (Ok(l), Ok(r)) = (left_text, right_text) {
+        let diff = similar::TextDiff::from_lines(l.unwrap_or(""), r.unwrap_or(""));
+        out.push_str(
+            &diff
+                .unified_diff()
+                .context_radius(3)
+                .header(&old_header, &new_header)
+                .to_string(),
+        );
+    } else {
+        out.push_str(&format!(
+            "--- {old_header}\n+++ {new_header}\nBinary files differ\n"
+        ));
+    }
+    if !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
 /// Compute the Git SHA-1 blob object ID for the given content (bytes).
 fn git_blob_sha1_hex_bytes(data: &[u8]) -> Output<sha1::Sha1> {
     // Git blob hash is sha1 of: "blob <len>\0<data>"
@@ -893,4 +1009,50 @@ index {ZERO_OID}..{right_oid}
         };
         assert_eq!(combined, expected_combined);
     }
+
+    #[test]
+    fn external_edits_are_reported_separately_from_agent_edits() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("notes.txt");
+        fs::write(&file, "one\ntwo\nthree\nfour\nfive\nsix\nseven\neight\n").unwrap();
+
+        let mut acc = TurnDiffTracker::new();
+        acc.on_patch_begin(&HashMap::from([(
+            file.clone(),
+            FileChange::Update {
+                unified_diff: "".to_owned(),
+                move_path: None,
+            },
+        )]));
+        // The agent edits the first line.
+        fs::write(&file, "ONE\ntwo\nthree\nfour\nfive\nsix\nseven\neight\n").unwrap();
+        acc.refresh_agent_snapshots();
+        assert!(!acc.on_external_change(&file));
+
+        // Someone else edits the last line.
+        fs::write(&file, "ONE\ntwo\nthree\nfour\nfive\nsix\nseven\nEIGHT\n").unwrap();
+        assert!(acc.on_external_change(&file));
+
+        let agent = acc.get_unified_diff().unwrap().unwrap();
+        let agent = normalize_diff_for_test(&agent, dir.path());
+        assert!(agent.contains("-one\n+ONE\n"), "{agent}");
+        assert!(!agent.contains("EIGHT"), "{agent}");
+
+        let external = acc.get_external_unified_diff().unwrap();
+        let external = normalize_diff_for_test(&external, dir.path());
+        assert!(external.contains("-eight\n+EIGHT\n"), "{external}");
+        assert!(!external.contains("-one"), "{external}");
+    }
+
+    #[test]
+    fn external_change_to_untracked_file_is_ignored() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("other.txt");
+        fs::write(&file, "x\n").unwrap();
+
+        let mut acc = TurnDiffTracker::new();
+        acc.refresh_agent_snapshots();
+        assert!(!acc.on_external_change(&file));
+        assert_eq!(acc.get_external_unified_diff(), None);
+    }
 }
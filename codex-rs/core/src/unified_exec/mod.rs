@@ -35,6 +35,7 @@ use tokio::sync::Mutex;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::sandboxing::SandboxPermissions;
+use crate::tokenizer::Tokenizer;
 
 mod async_watcher;
 mod errors;
@@ -97,6 +98,7 @@ pub(crate) struct WriteStdinRequest<'a> {
     pub input: &'a str,
     pub yield_time_ms: u64,
     pub max_output_tokens: Option<usize>,
+    pub tokenizer: &'a dyn Tokenizer,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -172,6 +174,7 @@ mod tests {
     use crate::codex::make_session_and_context;
     use crate::protocol::AskForApproval;
     use crate::protocol::SandboxPolicy;
+    use crate::tokenizer::default_tokenizer;
     use crate::unified_exec::ExecCommandRequest;
     use crate::unified_exec::WriteStdinRequest;
     use core_test_support::skip_if_sandbox;
@@ -234,6 +237,7 @@ mod tests {
                 input,
                 yield_time_ms,
                 max_output_tokens: None,
+                tokenizer: default_tokenizer(),
             })
             .await
     }
@@ -15,6 +15,7 @@ use crate::exec::ExecToolCallOutput;
 use crate::exec::SandboxType;
 use crate::exec::StreamOutput;
 use crate::exec::is_likely_sandbox_denied;
+use crate::tokenizer::default_tokenizer;
 use crate::truncate::TruncationPolicy;
 use crate::truncate::formatted_truncate_text;
 use codex_utils_pty::ExecCommandSession;
@@ -182,6 +183,7 @@ impl UnifiedExecProcess {
             let snippet = formatted_truncate_text(
                 text,
                 TruncationPolicy::Tokens(UNIFIED_EXEC_OUTPUT_MAX_TOKENS),
+                default_tokenizer(),
             );
             let message = if snippet.is_empty() {
                 format!("Process exited with code {exit_code}")
@@ -24,7 +24,6 @@ use crate::tools::runtimes::unified_exec::UnifiedExecRequest as UnifiedExecToolR
 use crate::tools::runtimes::unified_exec::UnifiedExecRuntime;
 use crate::tools::sandboxing::ToolCtx;
 use crate::truncate::TruncationPolicy;
-use crate::truncate::approx_token_count;
 use crate::truncate::formatted_truncate_text;
 use crate::unified_exec::ExecCommandRequest;
 use crate::unified_exec::MAX_UNIFIED_EXEC_PROCESSES;
@@ -200,7 +199,11 @@ impl UnifiedExecProcessManager {
         let wall_time = Instant::now().saturating_duration_since(start);
 
         let text = String::from_utf8_lossy(&collected).to_string();
-        let output = formatted_truncate_text(&text, TruncationPolicy::Tokens(max_tokens));
+        let output = formatted_truncate_text(
+            &text,
+            TruncationPolicy::Tokens(max_tokens),
+            context.turn.tokenizer,
+        );
         let exit_code = process.exit_code();
         let has_exited = process.has_exited() || exit_code.is_some();
         let chunk_id = generate_chunk_id();
@@ -244,7 +247,7 @@ impl UnifiedExecProcessManager {
             .await;
         };
 
-        let original_token_count = approx_token_count(&text);
+        let original_token_count = context.turn.tokenizer.count_tokens(&text);
         let response = UnifiedExecResponse {
             event_call_id: context.call_id.clone(),
             chunk_id,
@@ -316,8 +319,12 @@ impl UnifiedExecProcessManager {
         let wall_time = Instant::now().saturating_duration_since(start);
 
         let text = String::from_utf8_lossy(&collected).to_string();
-        let output = formatted_truncate_text(&text, TruncationPolicy::Tokens(max_tokens));
-        let original_token_count = approx_token_count(&text);
+        let output = formatted_truncate_text(
+            &text,
+            TruncationPolicy::Tokens(max_tokens),
+            request.tokenizer,
+        );
+        let original_token_count = request.tokenizer.count_tokens(&text);
         let chunk_id = generate_chunk_id();
 
         // After polling, refresh_process_state tells us whether the PTY is
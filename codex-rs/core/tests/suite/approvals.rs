@@ -1768,13 +1768,29 @@ async fn approving_execpolicy_amendment_persists_policy_and_skips_future_prompts
         "expected developer message documenting saved rule, got: {developer_messages:?}"
     );
 
-    let policy_path = test.home.path().join("rules").join("default.rules");
-    let policy_contents = fs::read_to_string(&policy_path)?;
+    // The prefix is remembered for this repository rather than written to the
+    // global rules file.
     assert!(
-        policy_contents
-            .contains(r#"prefix_rule(pattern=["touch", "allow-prefix.txt"], decision="allow")"#),
-        "unexpected policy contents: {policy_contents}"
+        !test
+            .home
+            .path()
+            .join("rules")
+            .join("default.rules")
+            .exists()
     );
+    let remembered_path = test.home.path().join("remembered_approvals.json");
+    let remembered: Value = serde_json::from_str(&fs::read_to_string(&remembered_path)?)?;
+    let entries = remembered["repositories"]
+        .as_object()
+        .and_then(|repositories| repositories.values().next())
+        .cloned()
+        .unwrap_or_default();
+    assert_eq!(
+        entries[0]["subject"],
+        json!({"type": "command_prefix", "prefix": ["touch", "allow-prefix.txt"]}),
+        "unexpected remembered approvals: {remembered}"
+    );
+    assert_eq!(entries[0]["decision"], "allow");
 
     let first_output = parse_result(
         &first_results
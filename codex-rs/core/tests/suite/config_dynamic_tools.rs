@@ -0,0 +1,240 @@
+#![cfg(not(target_os = "windows"))]
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::Result;
+use codex_core::config::types::ConfigDynamicTool;
+use codex_core::config::types::DynamicToolApproval;
+use codex_core::config::types::DynamicToolBackend;
+use codex_core::protocol::AskForApproval;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_core::protocol::SandboxPolicy;
+use codex_protocol::config_types::ReasoningSummary;
+use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ResponseMock;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::body_json;
+use wiremock::matchers::header;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+/// Echoes the JSON arguments it reads on stdin back as the tool's text.
+const ECHO_ARGUMENTS_SCRIPT: &str = r#"args=$(cat)
+escaped=$(printf '%s' "$args" | sed 's/"/\\"/g')
+printf '{"contentItems":[{"type":"inputText","text":"%s"}],"success":true}' "$escaped""#;
+
+fn dynamic_tool(name: &str, backend: DynamicToolBackend) -> ConfigDynamicTool {
+    ConfigDynamicTool {
+        name: name.to_string(),
+        description: format!("Test tool {name}"),
+        backend,
+        input_schema: json!({ "type": "object" }),
+        timeout: Duration::from_secs(10),
+        approval: DynamicToolApproval::Auto,
+        deferred: false,
+    }
+}
+
+async fn mount_tool_call(
+    server: &MockServer,
+    call_id: &str,
+    tool: &str,
+    args: &Value,
+) -> ResponseMock {
+    mount_sse_sequence(
+        server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_function_call(call_id, tool, &args.to_string()),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_assistant_message("msg-1", "done"),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await
+}
+
+async fn build_with_tools(server: &MockServer, tools: Vec<ConfigDynamicTool>) -> Result<TestCodex> {
+    test_codex()
+        .with_config(move |config| config.dynamic_tools = tools)
+        .build(server)
+        .await
+}
+
+/// Text of the tool output sent back to the model in the follow-up request.
+fn output_text(mock: &ResponseMock, call_id: &str) -> Option<String> {
+    let output = mock.last_request()?.function_call_output(call_id);
+    output["output"][0]["text"].as_str().map(str::to_string)
+}
+
+async fn mount_lookup_endpoint(server: &MockServer) {
+    Mock::given(method("POST"))
+        .and(path("/tools/lookup"))
+        .and(header("authorization", "Bearer lookup-token"))
+        .and(body_json(json!({ "id": "T-1" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "contentItems": [{ "type": "inputText", "text": "ticket T-1 is open" }],
+            "success": true,
+        })))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+fn lookup_tool(server: &MockServer) -> ConfigDynamicTool {
+    dynamic_tool(
+        "ticket_lookup",
+        DynamicToolBackend::Http {
+            url: format!("{}/tools/lookup", server.uri()),
+            http_headers: HashMap::from([(
+                "Authorization".to_string(),
+                "Bearer lookup-token".to_string(),
+            )]),
+        },
+    )
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn command_tool_receives_arguments_on_stdin() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let tool = dynamic_tool(
+        "echo_arguments",
+        DynamicToolBackend::Command {
+            command: vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                ECHO_ARGUMENTS_SCRIPT.to_string(),
+            ],
+        },
+    );
+    let test = build_with_tools(&server, vec![tool]).await?;
+    let args = json!({ "name": "codex" });
+    let mock = mount_tool_call(&server, "echo-call", "echo_arguments", &args).await;
+
+    test.submit_turn("echo my arguments").await?;
+
+    assert_eq!(
+        output_text(&mock, "echo-call"),
+        Some(r#"{"name":"codex"}"#.to_string())
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn http_tool_posts_arguments_to_its_endpoint() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    mount_lookup_endpoint(&server).await;
+    let test = build_with_tools(&server, vec![lookup_tool(&server)]).await?;
+    let mock = mount_tool_call(
+        &server,
+        "lookup-call",
+        "ticket_lookup",
+        &json!({ "id": "T-1" }),
+    )
+    .await;
+
+    // `submit_turn` uses the `never` approval policy, so `auto` does not ask.
+    test.submit_turn("look up T-1").await?;
+
+    assert_eq!(
+        output_text(&mock, "lookup-call"),
+        Some("ticket T-1 is open".to_string())
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn http_tool_asks_for_approval_like_openapi_writes() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    mount_lookup_endpoint(&server).await;
+    let tool = lookup_tool(&server);
+    let DynamicToolBackend::Http { url, .. } = &tool.backend else {
+        unreachable!("lookup tool is an HTTP tool");
+    };
+    let url = url.clone();
+    let test = build_with_tools(&server, vec![tool]).await?;
+    let mock = mount_tool_call(
+        &server,
+        "lookup-call",
+        "ticket_lookup",
+        &json!({ "id": "T-1" }),
+    )
+    .await;
+
+    test.codex
+        .submit(Op::UserTurn {
+            items: vec![UserInput::Text {
+                text: "look up T-1".to_string(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+            cwd: test.cwd_path().to_path_buf(),
+            approval_policy: AskForApproval::OnRequest,
+            sandbox_policy: SandboxPolicy::DangerFullAccess,
+            model: test.session_configured.model.clone(),
+            effort: None,
+            summary: ReasoningSummary::Auto,
+            collaboration_mode: None,
+            personality: None,
+        })
+        .await?;
+
+    let EventMsg::ExecApprovalRequest(approval) = wait_for_event(&test.codex, |event| {
+        matches!(
+            event,
+            EventMsg::ExecApprovalRequest(_) | EventMsg::TurnComplete(_)
+        )
+    })
+    .await
+    else {
+        panic!("expected an approval request before the HTTP tool ran");
+    };
+    assert_eq!(approval.command, vec!["POST".to_string(), url]);
+
+    test.codex
+        .submit(Op::ExecApproval {
+            id: approval.call_id,
+            turn_id: None,
+            decision: ReviewDecision::Approved,
+        })
+        .await?;
+    wait_for_event(&test.codex, |event| {
+        matches!(event, EventMsg::TurnComplete(_))
+    })
+    .await;
+
+    assert_eq!(
+        output_text(&mock, "lookup-call"),
+        Some("ticket T-1 is open".to_string())
+    );
+    Ok(())
+}
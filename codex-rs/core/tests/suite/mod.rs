@@ -69,6 +69,7 @@ mod collaboration_instructions;
 mod compact;
 mod compact_remote;
 mod compact_resume_fork;
+mod config_dynamic_tools;
 mod deprecation_notice;
 mod exec;
 mod exec_policy;
@@ -113,6 +114,7 @@ mod sqlite_state;
 mod stream_error_allows_next_turn;
 mod stream_no_completed;
 mod text_encoding_fix;
+mod token_budget;
 mod tool_harness;
 mod tool_parallelism;
 mod tools;
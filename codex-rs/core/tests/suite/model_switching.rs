@@ -1,5 +1,6 @@
 use anyhow::Result;
 use codex_core::CodexAuth;
+use codex_core::config::types::ModelRoute;
 use codex_core::config::types::Personality;
 use codex_core::features::Feature;
 use codex_core::models_manager::manager::RefreshStrategy;
@@ -19,7 +20,11 @@ use codex_protocol::openai_models::ReasoningEffortPreset;
 use codex_protocol::openai_models::TruncationPolicyConfig;
 use codex_protocol::openai_models::default_input_modalities;
 use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
 use core_test_support::responses::ev_completed_with_tokens;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_reasoning_item;
 use core_test_support::responses::ev_response_created;
 use core_test_support::responses::mount_models_once;
 use core_test_support::responses::mount_sse_sequence;
@@ -721,3 +726,52 @@ allows_collab_tools = false
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn after_tool_route_drops_reasoning_of_the_previous_model() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = MockServer::start().await;
+    let resp_mock = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_reasoning_item("rs-1", &["looking it up"], &[]),
+                ev_function_call("call-1", "lookup", "{}"),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_assistant_message("msg-1", "done"),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+
+    let mut builder = test_codex()
+        .with_model("gpt-5.2-codex")
+        .with_config(|config| {
+            config.model_routes = vec![ModelRoute {
+                after_tool: Some("lookup".to_string()),
+                model: Some("gpt-5.1-codex-mini".to_string()),
+                ..Default::default()
+            }];
+        });
+    let test = builder.build(&server).await?;
+    test.submit_turn("look it up").await?;
+
+    let requests = resp_mock.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].body_json()["model"], "gpt-5.1-codex-mini");
+    assert!(requests[1].has_function_call("call-1"));
+    let reasoning_items = requests[1]
+        .input()
+        .iter()
+        .filter(|item| item["type"] == "reasoning")
+        .count();
+    assert_eq!(reasoning_items, 0);
+
+    Ok(())
+}
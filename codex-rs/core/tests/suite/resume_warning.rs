@@ -33,6 +33,7 @@ fn resume_history(
         developer_instructions: None,
         final_output_json_schema: None,
         truncation_policy: None,
+        model_route: None,
     };
 
     InitialHistory::Resumed(ResumedHistory {
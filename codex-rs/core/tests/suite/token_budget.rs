@@ -0,0 +1,130 @@
+use anyhow::Result;
+use codex_core::config::ProjectConfig;
+use codex_core::config::types::SessionBudget;
+use codex_core::config::types::TokenBudget;
+use codex_core::features::Feature;
+use codex_core::protocol::CodexErrorInfo;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use codex_state::TokenUsageEntry;
+use core_test_support::responses::ev_completed_with_tokens;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::sse_completed;
+use core_test_support::responses::start_mock_server;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn hard_limit_refuses_turns_once_the_project_budget_is_spent() -> Result<()> {
+    let server = start_mock_server().await;
+    let response_mock = mount_sse_sequence(&server, vec![sse_completed("resp-1")]).await;
+    let mut builder = test_codex().with_config(|config| {
+        config.features.enable(Feature::Sqlite);
+        config.active_project = ProjectConfig {
+            trust_level: None,
+            token_budget: Some(TokenBudget {
+                period: None,
+                soft_limit: None,
+                hard_limit: Some(1_000),
+            }),
+        };
+    });
+    let test = builder.build(&server).await?;
+
+    // Outside git, usage is attributed to the working directory.
+    let project = test.config.cwd.to_string_lossy().into_owned();
+    let db = test.codex.state_db().expect("state db enabled");
+    db.record_token_usage(&TokenUsageEntry {
+        ts: chrono::Utc::now().timestamp(),
+        thread_id: "earlier-thread".to_string(),
+        turn_id: "earlier-turn".to_string(),
+        model: "gpt-5.1".to_string(),
+        provider: "openai".to_string(),
+        repo: Some(project),
+        cwd: test.config.cwd.to_string_lossy().into_owned(),
+        input_tokens: 900,
+        cached_input_tokens: 0,
+        output_tokens: 100,
+        reasoning_output_tokens: 0,
+        total_tokens: 1_000,
+    })
+    .await?;
+
+    test.codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "over budget".to_string(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+
+    let error = wait_for_event(&test.codex, |ev| matches!(ev, EventMsg::Error(_))).await;
+    let EventMsg::Error(error) = error else {
+        unreachable!("wait_for_event returned a non-error event");
+    };
+    assert_eq!(
+        error.codex_error_info,
+        Some(CodexErrorInfo::UsageLimitExceeded)
+    );
+    assert!(error.message.contains("reaching its token budget of 1000"));
+    wait_for_event(&test.codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    assert_eq!(response_mock.requests().len(), 0);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn session_budget_stops_sampling_once_the_session_used_its_tokens() -> Result<()> {
+    let server = start_mock_server().await;
+    let response_mock = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_function_call("call-1", "lookup", "{}"),
+                ev_completed_with_tokens("resp-1", 1_500),
+            ]),
+            sse_completed("resp-2"),
+        ],
+    )
+    .await;
+    let mut builder = test_codex().with_config(|config| {
+        config.session_budget = SessionBudget {
+            max_tokens: Some(1_000),
+            timeout_secs: None,
+        };
+    });
+    let test = builder.build(&server).await?;
+
+    test.codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "look it up".to_string(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+
+    let error = wait_for_event(&test.codex, |ev| matches!(ev, EventMsg::Error(_))).await;
+    let EventMsg::Error(error) = error else {
+        unreachable!("wait_for_event returned a non-error event");
+    };
+    assert_eq!(
+        error.codex_error_info,
+        Some(CodexErrorInfo::UsageLimitExceeded)
+    );
+    assert!(error.message.contains("exceeding its budget of 1000"));
+    wait_for_event(&test.codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    // The tool call's follow-up request is never sent.
+    assert_eq!(response_mock.requests().len(), 1);
+    Ok(())
+}
@@ -2,6 +2,7 @@
 
 use std::fs;
 use std::path::Path;
+use std::path::PathBuf;
 use std::process::Command;
 use std::sync::Arc;
 
@@ -118,6 +119,16 @@ async fn invoke_undo(codex: &Arc<CodexThread>) -> Result<UndoCompletedEvent> {
     Ok(event)
 }
 
+async fn invoke_redo(codex: &Arc<CodexThread>) -> Result<UndoCompletedEvent> {
+    codex.submit(Op::Redo).await?;
+    let event = wait_for_event_match(codex, |msg| match msg {
+        EventMsg::UndoCompleted(done) => Some(done.clone()),
+        _ => None,
+    })
+    .await;
+    Ok(event)
+}
+
 async fn expect_successful_undo(codex: &Arc<CodexThread>) -> Result<UndoCompletedEvent> {
     let event = invoke_undo(codex).await?;
     assert!(
@@ -548,3 +559,131 @@ async fn undo_preserves_unrelated_staged_changes() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn snapshot_timeline_restores_selected_files_and_redoes() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let harness = undo_harness().await?;
+    init_git_repo(harness.cwd())?;
+
+    let story = harness.path("story.txt");
+    let notes = harness.path("notes.txt");
+    fs::write(&story, "initial\n")?;
+    fs::write(&notes, "notes\n")?;
+    git(harness.cwd(), &["add", "story.txt", "notes.txt"])?;
+    git(harness.cwd(), &["commit", "-m", "seed files"])?;
+
+    run_apply_patch_turn(
+        &harness,
+        "first change",
+        "timeline-turn-1",
+        "*** Begin Patch\n*** Update File: story.txt\n@@\n-initial\n+turn one\n*** End Patch",
+        "ok",
+    )
+    .await?;
+    run_apply_patch_turn(
+        &harness,
+        "second change",
+        "timeline-turn-2",
+        "*** Begin Patch\n*** Update File: notes.txt\n@@\n-notes\n+more notes\n*** End Patch",
+        "ok",
+    )
+    .await?;
+
+    let codex = Arc::clone(&harness.test().codex);
+    codex.submit(Op::ListSnapshots).await?;
+    let timeline = wait_for_event_match(&codex, |msg| match msg {
+        EventMsg::SnapshotTimeline(timeline) => Some(timeline.clone()),
+        _ => None,
+    })
+    .await;
+    let summaries: Vec<Option<&str>> = timeline
+        .entries
+        .iter()
+        .map(|entry| entry.summary.as_deref())
+        .collect();
+    assert_eq!(summaries, vec![Some("first change"), Some("second change")]);
+    let changed: Vec<Vec<PathBuf>> = timeline
+        .entries
+        .iter()
+        .map(|entry| entry.files.iter().map(|file| file.path.clone()).collect())
+        .collect();
+    assert_eq!(
+        changed,
+        vec![
+            vec![PathBuf::from("story.txt")],
+            vec![PathBuf::from("notes.txt")]
+        ]
+    );
+    assert!(!timeline.redo_available);
+
+    codex
+        .submit(Op::RestoreSnapshot {
+            snapshot_id: timeline.entries[0].snapshot_id.clone(),
+            paths: vec![PathBuf::from("story.txt")],
+        })
+        .await?;
+    let restored = wait_for_event_match(&codex, |msg| match msg {
+        EventMsg::UndoCompleted(done) => Some(done.clone()),
+        _ => None,
+    })
+    .await;
+    assert!(restored.success, "restore failed: {:?}", restored.message);
+    assert_eq!(fs::read_to_string(&story)?, "initial\n");
+    assert_eq!(fs::read_to_string(&notes)?, "more notes\n");
+
+    codex.submit(Op::Redo).await?;
+    let redone = wait_for_event_match(&codex, |msg| match msg {
+        EventMsg::UndoCompleted(done) => Some(done.clone()),
+        _ => None,
+    })
+    .await;
+    assert!(redone.success, "redo failed: {:?}", redone.message);
+    assert_eq!(fs::read_to_string(&story)?, "turn one\n");
+    assert_eq!(fs::read_to_string(&notes)?, "more notes\n");
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn redo_is_refused_after_new_edits_and_can_be_undone() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let harness = undo_harness().await?;
+    init_git_repo(harness.cwd())?;
+
+    let story = harness.path("story.txt");
+    fs::write(&story, "initial\n")?;
+    git(harness.cwd(), &["add", "story.txt"])?;
+    git(harness.cwd(), &["commit", "-m", "seed story"])?;
+
+    run_apply_patch_turn(
+        &harness,
+        "change story",
+        "redo-turn-1",
+        "*** Begin Patch\n*** Update File: story.txt\n@@\n-initial\n+turn one\n*** End Patch",
+        "ok",
+    )
+    .await?;
+
+    let codex = Arc::clone(&harness.test().codex);
+    expect_successful_undo(&codex).await?;
+    assert_eq!(fs::read_to_string(&story)?, "initial\n");
+
+    fs::write(&story, "manual edit\n")?;
+    let refused = invoke_redo(&codex).await?;
+    assert!(!refused.success, "redo should have been refused");
+    assert_eq!(fs::read_to_string(&story)?, "manual edit\n");
+
+    fs::write(&story, "initial\n")?;
+    let redone = invoke_redo(&codex).await?;
+    assert!(redone.success, "redo failed: {:?}", redone.message);
+    assert_eq!(fs::read_to_string(&story)?, "turn one\n");
+
+    // The redo put the turn's snapshot back into history, so it can be undone again.
+    expect_successful_undo(&codex).await?;
+    assert_eq!(fs::read_to_string(&story)?, "initial\n");
+
+    Ok(())
+}
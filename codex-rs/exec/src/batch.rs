@@ -0,0 +1,565 @@
+//! `codex exec --batch`: run many independent tasks from one process.
+//!
+//! Each line of the tasks file is a JSON object describing a prompt and where
+//! to run it. Tasks share a single [`ThreadManager`] (and therefore auth, the
+//! cached model list and HTTP clients) and each runs in its own thread. One
+//! JSON result is written to stdout per task as it finishes, followed by a
+//! summary table on stderr.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_core::ThreadManager;
+use codex_core::config::ConfigBuilder;
+use codex_core::config::ConfigOverrides;
+use codex_core::config_loader::CloudRequirementsLoader;
+use codex_core::git_info::get_git_repo_root;
+use codex_core::models_manager::manager::RefreshStrategy;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_core::protocol::TokenUsage;
+use codex_protocol::approvals::ElicitationAction;
+use codex_protocol::user_input::UserInput;
+use codex_utils_cli::CliConfigOverrides;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::warn;
+
+use crate::budget;
+use crate::budget::BudgetLimits;
+use crate::budget::BudgetTracker;
+use crate::exec_events::BudgetExceededEvent;
+use crate::exec_events::Usage;
+
+/// Number of tasks run at once when `--concurrency` is not given.
+pub(crate) const DEFAULT_BATCH_CONCURRENCY: usize = 4;
+
+/// One line of the tasks file.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct BatchTask {
+    /// Identifier echoed in the result. Defaults to the line number.
+    #[serde(default)]
+    pub(crate) id: Option<String>,
+    pub(crate) prompt: String,
+    /// Working directory, relative to the tasks file. Defaults to the
+    /// directory `codex exec` runs in.
+    #[serde(default)]
+    pub(crate) cwd: Option<PathBuf>,
+    #[serde(default)]
+    pub(crate) model: Option<String>,
+    #[serde(default)]
+    pub(crate) profile: Option<String>,
+    /// Either an inline JSON Schema or a path (relative to the tasks file) to one.
+    #[serde(default)]
+    pub(crate) output_schema: Option<Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum BatchTaskStatus {
+    Completed,
+    Failed,
+    BudgetExceeded,
+}
+
+impl BatchTaskStatus {
+    fn label(self) -> &'static str {
+        match self {
+            BatchTaskStatus::Completed => "completed",
+            BatchTaskStatus::Failed => "failed",
+            BatchTaskStatus::BudgetExceeded => "budget_exceeded",
+        }
+    }
+}
+
+/// Result line written to stdout for every task.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub(crate) struct BatchTaskResult {
+    pub(crate) id: String,
+    pub(crate) status: BatchTaskStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) thread_id: Option<String>,
+    pub(crate) cwd: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) final_message: Option<String>,
+    pub(crate) usage: Usage,
+    pub(crate) duration_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) budget_exceeded: Option<BudgetExceededEvent>,
+}
+
+/// Everything tasks need from the surrounding `codex exec` invocation.
+pub(crate) struct BatchContext {
+    pub(crate) thread_manager: Arc<ThreadManager>,
+    pub(crate) config_overrides: CliConfigOverrides,
+    pub(crate) harness_overrides: ConfigOverrides,
+    pub(crate) cloud_requirements: CloudRequirementsLoader,
+    pub(crate) budget_limits: BudgetLimits,
+    pub(crate) skip_git_repo_check: bool,
+    /// Working directory for tasks that do not set `cwd`.
+    pub(crate) default_cwd: PathBuf,
+}
+
+/// Parse a tasks file. Blank lines are ignored; errors name the offending line.
+pub(crate) fn parse_tasks(contents: &str) -> Result<Vec<(String, BatchTask)>, String> {
+    let mut tasks = Vec::new();
+    for (idx, line) in contents.lines().enumerate() {
+        let line_number = idx + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let task: BatchTask = serde_json::from_str(line)
+            .map_err(|err| format!("invalid task on line {line_number}: {err}"))?;
+        if task.prompt.trim().is_empty() {
+            return Err(format!("task on line {line_number} has an empty prompt"));
+        }
+        let id = task
+            .id
+            .clone()
+            .unwrap_or_else(|| format!("line-{line_number}"));
+        tasks.push((id, task));
+    }
+    Ok(tasks)
+}
+
+/// Run every task in `tasks_path` and return whether all of them completed.
+pub(crate) async fn run_batch(
+    context: BatchContext,
+    tasks_path: &Path,
+    concurrency: usize,
+) -> anyhow::Result<bool> {
+    let contents = std::fs::read_to_string(tasks_path).map_err(|err| {
+        anyhow::anyhow!("failed to read tasks file {}: {err}", tasks_path.display())
+    })?;
+    let tasks = parse_tasks(&contents).map_err(anyhow::Error::msg)?;
+    let base_dir = match tasks_path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => std::path::absolute(parent)?,
+        _ => std::env::current_dir()?,
+    };
+
+    let context = Arc::new(context);
+    let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+    let mut join_set = JoinSet::new();
+    for (index, (id, task)) in tasks.into_iter().enumerate() {
+        let context = Arc::clone(&context);
+        let semaphore = Arc::clone(&semaphore);
+        let base_dir = base_dir.clone();
+        join_set.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            (index, run_task(&context, &base_dir, id, task).await)
+        });
+    }
+
+    let mut results = Vec::new();
+    while let Some(joined) = join_set.join_next().await {
+        let (index, result) = joined?;
+        print_result(&result);
+        results.push((index, result));
+    }
+    results.sort_by_key(|(index, _)| *index);
+    let results: Vec<BatchTaskResult> = results.into_iter().map(|(_, result)| result).collect();
+
+    print_summary(&results);
+    Ok(results
+        .iter()
+        .all(|result| result.status == BatchTaskStatus::Completed))
+}
+
+#[allow(clippy::print_stdout)]
+fn print_result(result: &BatchTaskResult) {
+    match serde_json::to_string(result) {
+        Ok(line) => println!("{line}"),
+        Err(err) => warn!("failed to serialize batch result: {err}"),
+    }
+}
+
+#[allow(clippy::print_stderr)]
+fn print_summary(results: &[BatchTaskResult]) {
+    eprint!("{}", format_summary(results));
+}
+
+async fn run_task(
+    context: &BatchContext,
+    base_dir: &Path,
+    id: String,
+    task: BatchTask,
+) -> BatchTaskResult {
+    let started_at = Instant::now();
+    let cwd = match &task.cwd {
+        Some(cwd) => base_dir.join(cwd),
+        None => context.default_cwd.clone(),
+    };
+    let mut result = BatchTaskResult {
+        id,
+        status: BatchTaskStatus::Failed,
+        thread_id: None,
+        cwd: cwd.clone(),
+        final_message: None,
+        usage: Usage::default(),
+        duration_ms: 0,
+        error: None,
+        budget_exceeded: None,
+    };
+    if let Err(err) = drive_task(context, base_dir, cwd, task, &mut result).await {
+        result.status = BatchTaskStatus::Failed;
+        result.error = Some(err.to_string());
+    }
+    result.duration_ms = u64::try_from(started_at.elapsed().as_millis()).unwrap_or(u64::MAX);
+    result
+}
+
+async fn drive_task(
+    context: &BatchContext,
+    base_dir: &Path,
+    cwd: PathBuf,
+    task: BatchTask,
+    result: &mut BatchTaskResult,
+) -> anyhow::Result<()> {
+    let BatchTask {
+        prompt,
+        model,
+        profile,
+        output_schema,
+        ..
+    } = task;
+    let output_schema = match output_schema {
+        Some(Value::String(path)) => {
+            let path = base_dir.join(path);
+            let contents = std::fs::read_to_string(&path).map_err(|err| {
+                anyhow::anyhow!("failed to read output schema {}: {err}", path.display())
+            })?;
+            Some(serde_json::from_str::<Value>(&contents).map_err(|err| {
+                anyhow::anyhow!("output schema {} is not valid JSON: {err}", path.display())
+            })?)
+        }
+        other => other,
+    };
+
+    let mut overrides = context.harness_overrides.clone();
+    overrides.cwd = Some(cwd.clone());
+    if model.is_some() {
+        overrides.model = model;
+    }
+    if profile.is_some() {
+        overrides.config_profile = profile;
+    }
+    let config = ConfigBuilder::default()
+        .cli_overrides(
+            context
+                .config_overrides
+                .parse_overrides()
+                .map_err(anyhow::Error::msg)?,
+        )
+        .harness_overrides(overrides)
+        .cloud_requirements(context.cloud_requirements.clone())
+        .build()
+        .await?;
+
+    if !context.skip_git_repo_check && get_git_repo_root(&config.cwd).is_none() {
+        anyhow::bail!("{} is not inside a trusted directory", config.cwd.display());
+    }
+
+    let model = context
+        .thread_manager
+        .get_models_manager()
+        .get_default_model(&config.model, &config, RefreshStrategy::OnlineIfUncached)
+        .await;
+    let new_thread = context.thread_manager.start_thread(config.clone()).await?;
+    let thread = new_thread.thread;
+    let thread_id = new_thread.thread_id;
+    result.thread_id = Some(thread_id.to_string());
+
+    let turn = Op::UserTurn {
+        items: vec![UserInput::Text {
+            text: prompt,
+            text_elements: Vec::new(),
+        }],
+        cwd: config.cwd.to_path_buf(),
+        approval_policy: config.approval_policy.value(),
+        sandbox_policy: config.sandbox_policy.get().clone(),
+        model,
+        effort: config.model_reasoning_effort,
+        summary: config.model_reasoning_summary,
+        final_output_json_schema: output_schema,
+        collaboration_mode: None,
+        personality: None,
+    };
+    // Shut the thread down however the turn ends, including on errors, so a
+    // failed task does not leave its session (and MCP servers) running.
+    let outcome = run_turn(context, &thread, thread_id, turn, result).await;
+    shutdown_thread(&context.thread_manager, thread_id, &thread).await;
+    outcome
+}
+
+/// Submit the task's turn and follow its events until the turn ends,
+/// recording the outcome in `result`.
+async fn run_turn(
+    context: &BatchContext,
+    thread: &codex_core::CodexThread,
+    thread_id: codex_protocol::ThreadId,
+    turn: Op,
+    result: &mut BatchTaskResult,
+) -> anyhow::Result<()> {
+    thread.submit(turn).await?;
+
+    let mut budget = BudgetTracker::new(context.budget_limits);
+    let mut last_usage: Option<TokenUsage> = None;
+    let mut error = None;
+    let mut give_up_at = None;
+    loop {
+        let deadline = give_up_at.or_else(|| budget.deadline());
+        let event = tokio::select! {
+            event = thread.next_event() => event?,
+            () = budget::sleep_until(deadline) => {
+                if result.budget_exceeded.is_some() {
+                    warn!("batch task {} did not stop after its budget was exceeded", result.id);
+                    break;
+                }
+                result.budget_exceeded = Some(budget.timeout_exceeded());
+                give_up_at = Some(Instant::now() + budget::BUDGET_SHUTDOWN_GRACE);
+                thread.submit(Op::Interrupt).await.ok();
+                continue;
+            }
+        };
+        if let Some(exceeded) = budget.observe(thread_id, &event.msg)
+            && result.budget_exceeded.is_none()
+        {
+            result.budget_exceeded = Some(exceeded);
+            give_up_at = Some(Instant::now() + budget::BUDGET_SHUTDOWN_GRACE);
+            thread.submit(Op::Interrupt).await.ok();
+        }
+        match event.msg {
+            EventMsg::TokenCount(ev) => {
+                if let Some(info) = ev.info {
+                    last_usage = Some(info.total_token_usage);
+                }
+            }
+            EventMsg::ElicitationRequest(ev) => {
+                thread
+                    .submit(Op::ResolveElicitation {
+                        server_name: ev.server_name,
+                        request_id: ev.id,
+                        decision: ElicitationAction::Cancel,
+                    })
+                    .await?;
+            }
+            EventMsg::Error(ev) => error = Some(ev.message),
+            EventMsg::TurnComplete(ev) => {
+                result.final_message = ev.last_agent_message;
+                break;
+            }
+            EventMsg::TurnAborted(_) => {
+                error.get_or_insert_with(|| "turn aborted".to_string());
+                break;
+            }
+            EventMsg::ShutdownComplete => break,
+            _ => {}
+        }
+    }
+
+    if let Some(usage) = last_usage {
+        result.usage = Usage {
+            input_tokens: usage.input_tokens,
+            cached_input_tokens: usage.cached_input_tokens,
+            output_tokens: usage.output_tokens,
+        };
+    }
+    result.status = if result.budget_exceeded.is_some() {
+        BatchTaskStatus::BudgetExceeded
+    } else if error.is_some() {
+        BatchTaskStatus::Failed
+    } else {
+        BatchTaskStatus::Completed
+    };
+    result.error = error;
+    Ok(())
+}
+
+/// Ask the thread to shut down and release it from the manager so long
+/// batches do not accumulate idle sessions (and their MCP servers).
+async fn shutdown_thread(
+    thread_manager: &ThreadManager,
+    thread_id: codex_protocol::ThreadId,
+    thread: &codex_core::CodexThread,
+) {
+    if thread.submit(Op::Shutdown).await.is_ok() {
+        let drained = tokio::time::timeout(budget::BUDGET_SHUTDOWN_GRACE, async {
+            while let Ok(event) = thread.next_event().await {
+                if matches!(event.msg, EventMsg::ShutdownComplete) {
+                    break;
+                }
+            }
+        })
+        .await;
+        if drained.is_err() {
+            warn!("thread {thread_id} did not shut down cleanly");
+        }
+    }
+    thread_manager.remove_thread(&thread_id).await;
+}
+
+/// Render the end-of-run table, one row per task in file order.
+pub(crate) fn format_summary(results: &[BatchTaskResult]) -> String {
+    const HEADERS: [&str; 4] = ["ID", "STATUS", "DURATION", "TOKENS"];
+    let rows: Vec<[String; 4]> = results
+        .iter()
+        .map(|result| {
+            [
+                result.id.clone(),
+                result.status.label().to_string(),
+                codex_utils_elapsed::format_duration(Duration::from_millis(result.duration_ms)),
+                (result.usage.input_tokens + result.usage.output_tokens).to_string(),
+            ]
+        })
+        .collect();
+    let mut widths = HEADERS.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut out = String::new();
+    let headers = HEADERS.map(str::to_string);
+    for row in std::iter::once(&headers).chain(&rows) {
+        let line = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    let completed = results
+        .iter()
+        .filter(|result| result.status == BatchTaskStatus::Completed)
+        .count();
+    out.push_str(&format!(
+        "{} tasks: {completed} completed, {} failed\n",
+        results.len(),
+        results.len() - completed
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exec_events::BudgetKind;
+    use pretty_assertions::assert_eq;
+
+    fn result(id: &str, status: BatchTaskStatus, duration_ms: u64, tokens: i64) -> BatchTaskResult {
+        BatchTaskResult {
+            id: id.to_string(),
+            status,
+            thread_id: None,
+            cwd: PathBuf::from("/repo"),
+            final_message: None,
+            usage: Usage {
+                input_tokens: tokens,
+                cached_input_tokens: 0,
+                output_tokens: 0,
+            },
+            duration_ms,
+            error: None,
+            budget_exceeded: None,
+        }
+    }
+
+    #[test]
+    fn parses_tasks_and_defaults_ids_to_line_numbers() {
+        let contents = r#"{"id": "lint", "prompt": "fix lint", "cwd": "repos/a", "model": "gpt-5.1"}
+
+{"prompt": "write docs", "profile": "fast", "output_schema": "schema.json"}
+"#;
+        let tasks = parse_tasks(contents).expect("tasks parse");
+
+        assert_eq!(
+            tasks,
+            vec![
+                (
+                    "lint".to_string(),
+                    BatchTask {
+                        id: Some("lint".to_string()),
+                        prompt: "fix lint".to_string(),
+                        cwd: Some(PathBuf::from("repos/a")),
+                        model: Some("gpt-5.1".to_string()),
+                        profile: None,
+                        output_schema: None,
+                    }
+                ),
+                (
+                    "line-3".to_string(),
+                    BatchTask {
+                        id: None,
+                        prompt: "write docs".to_string(),
+                        cwd: None,
+                        model: None,
+                        profile: Some("fast".to_string()),
+                        output_schema: Some(Value::String("schema.json".to_string())),
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_line_of_invalid_task() {
+        let err = parse_tasks("{\"prompt\": \"ok\"}\n{\"promt\": \"typo\"}\n")
+            .expect_err("unknown field should be rejected");
+        assert!(err.starts_with("invalid task on line 2:"), "{err}");
+
+        let err = parse_tasks("{\"prompt\": \"  \"}").expect_err("empty prompt");
+        assert_eq!(err, "task on line 1 has an empty prompt");
+    }
+
+    #[test]
+    fn serializes_result_without_empty_fields() {
+        let mut budget_tripped = result("slow", BatchTaskStatus::BudgetExceeded, 1_500, 10);
+        budget_tripped.budget_exceeded = Some(BudgetExceededEvent {
+            budget: BudgetKind::Timeout,
+            limit: 1,
+            used: 1,
+        });
+
+        assert_eq!(
+            serde_json::to_value(&budget_tripped).expect("serialize"),
+            serde_json::json!({
+                "id": "slow",
+                "status": "budget_exceeded",
+                "cwd": "/repo",
+                "usage": {"input_tokens": 10, "cached_input_tokens": 0, "output_tokens": 0},
+                "duration_ms": 1500,
+                "budget_exceeded": {"budget": "timeout", "limit": 1, "used": 1},
+            })
+        );
+    }
+
+    #[test]
+    fn summary_table_aligns_columns() {
+        let summary = format_summary(&[
+            result("lint", BatchTaskStatus::Completed, 250, 1200),
+            result("write-docs", BatchTaskStatus::Failed, 75_000, 42),
+        ]);
+
+        assert_eq!(
+            summary,
+            "\
+ID          STATUS     DURATION  TOKENS
+lint        completed  250ms     1200
+write-docs  failed     1m 15s    42
+2 tasks: 1 completed, 1 failed
+"
+        );
+    }
+}
@@ -0,0 +1,290 @@
+//! Hard ceilings for unattended `codex exec` runs.
+//!
+//! Core enforces `--max-tokens` and `--timeout` for each thread on its own
+//! (`session_budget`). The tracker here adds the limits core does not know
+//! about, sums usage across every thread the run spawns (including
+//! sub-agents) and ends the process with a dedicated exit code.
+
+use std::collections::HashMap;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_core::protocol::EventMsg;
+use codex_protocol::ThreadId;
+
+use crate::exec_events::BudgetExceededEvent;
+use crate::exec_events::BudgetKind;
+
+/// Process exit code used when a run is aborted because a budget tripped.
+pub const BUDGET_EXCEEDED_EXIT_CODE: i32 = 3;
+
+/// How long to wait for core to confirm shutdown after a budget trips before
+/// exiting anyway.
+pub(crate) const BUDGET_SHUTDOWN_GRACE: Duration = Duration::from_secs(10);
+
+/// Limits configured on the command line. `None` means unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct BudgetLimits {
+    pub(crate) max_turns: Option<u64>,
+    pub(crate) max_tokens: Option<u64>,
+    pub(crate) max_tool_calls: Option<u64>,
+    pub(crate) timeout: Option<Duration>,
+}
+
+/// Tracks consumption across all threads of a run and reports the first
+/// budget that is exceeded.
+#[derive(Debug)]
+pub(crate) struct BudgetTracker {
+    limits: BudgetLimits,
+    started_at: Instant,
+    turns: u64,
+    tool_calls: u64,
+    /// Latest cumulative token usage reported by each thread.
+    tokens_by_thread: HashMap<ThreadId, i64>,
+}
+
+impl BudgetTracker {
+    pub(crate) fn new(limits: BudgetLimits) -> Self {
+        Self::new_at(limits, Instant::now())
+    }
+
+    fn new_at(limits: BudgetLimits, started_at: Instant) -> Self {
+        Self {
+            limits,
+            started_at,
+            turns: 0,
+            tool_calls: 0,
+            tokens_by_thread: HashMap::new(),
+        }
+    }
+
+    /// Instant at which the wall-clock budget runs out, if one is set.
+    pub(crate) fn deadline(&self) -> Option<Instant> {
+        self.limits.timeout.map(|timeout| self.started_at + timeout)
+    }
+
+    /// Event describing an exhausted wall-clock budget.
+    pub(crate) fn timeout_exceeded(&self) -> BudgetExceededEvent {
+        let limit = self.limits.timeout.unwrap_or_default().as_secs();
+        BudgetExceededEvent {
+            budget: BudgetKind::Timeout,
+            limit,
+            used: self.started_at.elapsed().as_secs().max(limit),
+        }
+    }
+
+    /// Account for an event and return the budget it exhausted, if any.
+    ///
+    /// A "turn" is one model response: core reports cumulative token usage
+    /// after every completed response, so each increase counts as one turn.
+    pub(crate) fn observe(
+        &mut self,
+        thread_id: ThreadId,
+        msg: &EventMsg,
+    ) -> Option<BudgetExceededEvent> {
+        match msg {
+            EventMsg::TokenCount(ev) => {
+                let total = ev.info.as_ref()?.total_token_usage.total_tokens;
+                let previous = self.tokens_by_thread.insert(thread_id, total);
+                if total <= previous.unwrap_or(0) {
+                    return None;
+                }
+                self.turns += 1;
+                exceeded(BudgetKind::MaxTurns, self.limits.max_turns, self.turns).or_else(|| {
+                    exceeded(
+                        BudgetKind::MaxTokens,
+                        self.limits.max_tokens,
+                        self.total_tokens(),
+                    )
+                })
+            }
+            EventMsg::ExecCommandBegin(_)
+            | EventMsg::McpToolCallBegin(_)
+            | EventMsg::PatchApplyBegin(_)
+            | EventMsg::WebSearchBegin(_)
+            | EventMsg::DynamicToolCallRequest(_) => {
+                self.tool_calls += 1;
+                exceeded(
+                    BudgetKind::MaxToolCalls,
+                    self.limits.max_tool_calls,
+                    self.tool_calls,
+                )
+            }
+            _ => None,
+        }
+    }
+
+    fn total_tokens(&self) -> u64 {
+        self.tokens_by_thread
+            .values()
+            .map(|tokens| u64::try_from(*tokens).unwrap_or(0))
+            .sum()
+    }
+}
+
+/// Command-line flag that configures the given budget, for human-readable output.
+pub(crate) fn budget_flag(budget: BudgetKind) -> &'static str {
+    match budget {
+        BudgetKind::MaxTurns => "--max-turns",
+        BudgetKind::MaxTokens => "--max-tokens",
+        BudgetKind::MaxToolCalls => "--max-tool-calls",
+        BudgetKind::Timeout => "--timeout",
+    }
+}
+
+/// Resolve at `deadline`, or never when there is none.
+pub(crate) async fn sleep_until(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline.into()).await,
+        None => std::future::pending().await,
+    }
+}
+
+fn exceeded(budget: BudgetKind, limit: Option<u64>, used: u64) -> Option<BudgetExceededEvent> {
+    let limit = limit?;
+    (used > limit).then_some(BudgetExceededEvent {
+        budget,
+        limit,
+        used,
+    })
+}
+
+/// Parse a `--timeout` value: a number of seconds, or a number followed by
+/// `s`, `m` or `h` (e.g. `90`, `45s`, `10m`, `2h`).
+pub(crate) fn parse_timeout(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let (digits, multiplier) = match value.char_indices().last() {
+        Some((idx, 's')) => (&value[..idx], 1),
+        Some((idx, 'm')) => (&value[..idx], 60),
+        Some((idx, 'h')) => (&value[..idx], 60 * 60),
+        _ => (value, 1),
+    };
+    let amount: u64 = digits
+        .parse()
+        .map_err(|_| format!("invalid duration `{value}`; expected e.g. 90, 45s, 10m or 2h"))?;
+    if amount == 0 {
+        return Err("timeout must be greater than zero".to_string());
+    }
+    Ok(Duration::from_secs(amount.saturating_mul(multiplier)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::ExecCommandBeginEvent;
+    use codex_core::protocol::ExecCommandSource;
+    use codex_core::protocol::TokenCountEvent;
+    use codex_core::protocol::TokenUsage;
+    use codex_core::protocol::TokenUsageInfo;
+    use pretty_assertions::assert_eq;
+
+    fn token_count(total_tokens: i64) -> EventMsg {
+        let usage = TokenUsage {
+            total_tokens,
+            ..TokenUsage::default()
+        };
+        EventMsg::TokenCount(TokenCountEvent {
+            info: Some(TokenUsageInfo {
+                total_token_usage: usage.clone(),
+                last_token_usage: usage,
+                model_context_window: None,
+            }),
+            rate_limits: None,
+        })
+    }
+
+    fn exec_begin() -> EventMsg {
+        EventMsg::ExecCommandBegin(ExecCommandBeginEvent {
+            call_id: "call".to_string(),
+            process_id: None,
+            turn_id: "turn".to_string(),
+            command: vec!["ls".to_string()],
+            cwd: std::env::temp_dir(),
+            parsed_cmd: Vec::new(),
+            source: ExecCommandSource::Agent,
+            interaction_input: None,
+        })
+    }
+
+    #[test]
+    fn counts_turns_once_per_usage_increase() {
+        let thread = ThreadId::new();
+        let mut tracker = BudgetTracker::new(BudgetLimits {
+            max_turns: Some(2),
+            ..BudgetLimits::default()
+        });
+
+        assert_eq!(tracker.observe(thread, &token_count(10)), None);
+        // Rate-limit refreshes re-send the same totals and must not count.
+        assert_eq!(tracker.observe(thread, &token_count(10)), None);
+        assert_eq!(tracker.observe(thread, &token_count(20)), None);
+        assert_eq!(
+            tracker.observe(thread, &token_count(30)),
+            Some(BudgetExceededEvent {
+                budget: BudgetKind::MaxTurns,
+                limit: 2,
+                used: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn sums_tokens_across_threads() {
+        let mut tracker = BudgetTracker::new(BudgetLimits {
+            max_tokens: Some(100),
+            ..BudgetLimits::default()
+        });
+
+        assert_eq!(tracker.observe(ThreadId::new(), &token_count(60)), None);
+        assert_eq!(
+            tracker.observe(ThreadId::new(), &token_count(50)),
+            Some(BudgetExceededEvent {
+                budget: BudgetKind::MaxTokens,
+                limit: 100,
+                used: 110,
+            })
+        );
+    }
+
+    #[test]
+    fn trips_after_too_many_tool_calls() {
+        let thread = ThreadId::new();
+        let mut tracker = BudgetTracker::new(BudgetLimits {
+            max_tool_calls: Some(1),
+            ..BudgetLimits::default()
+        });
+
+        assert_eq!(tracker.observe(thread, &exec_begin()), None);
+        assert_eq!(
+            tracker.observe(thread, &exec_begin()).map(|ev| ev.budget),
+            Some(BudgetKind::MaxToolCalls)
+        );
+    }
+
+    #[test]
+    fn deadline_follows_timeout() {
+        let started_at = Instant::now();
+        let tracker = BudgetTracker::new_at(
+            BudgetLimits {
+                timeout: Some(Duration::from_secs(5)),
+                ..BudgetLimits::default()
+            },
+            started_at,
+        );
+        assert_eq!(
+            tracker.deadline(),
+            Some(started_at + Duration::from_secs(5))
+        );
+        assert_eq!(BudgetTracker::new(BudgetLimits::default()).deadline(), None);
+    }
+
+    #[test]
+    fn parses_timeouts() {
+        assert_eq!(parse_timeout("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_timeout("45s"), Ok(Duration::from_secs(45)));
+        assert_eq!(parse_timeout("10m"), Ok(Duration::from_secs(600)));
+        assert_eq!(parse_timeout("2h"), Ok(Duration::from_secs(7200)));
+        assert!(parse_timeout("0").is_err());
+        assert!(parse_timeout("soon").is_err());
+    }
+}
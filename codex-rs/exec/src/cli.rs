@@ -1,3 +1,4 @@
+use clap::ArgGroup;
 use clap::Args;
 use clap::FromArgMatches;
 use clap::Parser;
@@ -95,6 +96,53 @@ pub struct Cli {
     )]
     pub json: bool,
 
+    /// Print a machine-readable report to stdout once the run finishes: `sarif` for
+    /// review findings, `junit` for turn and tool call results.
+    #[arg(
+        long = "format",
+        value_name = "FORMAT",
+        value_enum,
+        global = true,
+        conflicts_with_all = ["json", "batch"]
+    )]
+    pub format: Option<OutputFormat>,
+
+    /// Abort after this many model responses across all threads.
+    #[arg(long = "max-turns", value_name = "N")]
+    pub max_turns: Option<u64>,
+
+    /// Abort once total token usage across all threads exceeds this many tokens.
+    #[arg(long = "max-tokens", value_name = "N")]
+    pub max_tokens: Option<u64>,
+
+    /// Abort after this many tool calls (commands, patches, MCP and web search calls).
+    #[arg(long = "max-tool-calls", value_name = "N")]
+    pub max_tool_calls: Option<u64>,
+
+    /// Abort when the run exceeds this wall-clock time (e.g. 90, 45s, 10m, 2h).
+    /// A tripped budget exits with code 3.
+    #[arg(long = "timeout", value_name = "DURATION", value_parser = crate::budget::parse_timeout)]
+    pub timeout: Option<std::time::Duration>,
+
+    /// Run every task in a JSONL file (one `{"prompt": ..., "cwd": ...}` object
+    /// per line) instead of a single prompt. Writes one JSON result per task to
+    /// stdout and a summary table to stderr. Budget flags apply to each task.
+    #[arg(
+        long = "batch",
+        value_name = "FILE",
+        conflicts_with_all = ["prompt", "images", "output_schema", "last_message_file"]
+    )]
+    pub batch: Option<PathBuf>,
+
+    /// Number of batch tasks to run at once (default: 4).
+    #[arg(long = "concurrency", value_name = "N", requires = "batch")]
+    pub concurrency: Option<usize>,
+
+    /// Serve model responses from a recording made with `record_model_streams`
+    /// instead of calling the provider. Tool calls run against the local workspace.
+    #[arg(long = "replay", value_name = "DIR", conflicts_with = "batch")]
+    pub replay: Option<PathBuf>,
+
     /// Specifies file where the last message from the agent should be written.
     #[arg(long = "output-last-message", short = 'o', value_name = "FILE")]
     pub last_message_file: Option<PathBuf>,
@@ -206,35 +254,56 @@ impl FromArgMatches for ResumeArgs {
 }
 
 #[derive(Parser, Debug)]
+#[command(
+    group(
+        ArgGroup::new("review_target")
+            .args([
+                "uncommitted",
+                "base",
+                "commit",
+                "range",
+                "stack",
+                "patch",
+                "since_review",
+                "prompt",
+            ])
+            .multiple(false)
+    )
+)]
 pub struct ReviewArgs {
     /// Review staged, unstaged, and untracked changes.
-    #[arg(
-        long = "uncommitted",
-        default_value_t = false,
-        conflicts_with_all = ["base", "commit", "prompt"]
-    )]
+    #[arg(long = "uncommitted", default_value_t = false)]
     pub uncommitted: bool,
 
     /// Review changes against the given base branch.
-    #[arg(
-        long = "base",
-        value_name = "BRANCH",
-        conflicts_with_all = ["uncommitted", "commit", "prompt"]
-    )]
+    #[arg(long = "base", value_name = "BRANCH")]
     pub base: Option<String>,
 
     /// Review the changes introduced by a commit.
-    #[arg(
-        long = "commit",
-        value_name = "SHA",
-        conflicts_with_all = ["uncommitted", "base", "prompt"]
-    )]
+    #[arg(long = "commit", value_name = "SHA")]
     pub commit: Option<String>,
 
     /// Optional commit title to display in the review summary.
     #[arg(long = "title", value_name = "TITLE", requires = "commit")]
     pub commit_title: Option<String>,
 
+    /// Review the combined changes of a commit range (e.g. `main..feature`).
+    #[arg(long = "range", value_name = "BASE..HEAD")]
+    pub range: Option<String>,
+
+    /// Review each commit of a range on its own, oldest first, with separate findings.
+    #[arg(long = "stack", value_name = "BASE..HEAD")]
+    pub stack: Option<String>,
+
+    /// Review a patch or diff file instead of the repository's changes. Use `-` for stdin.
+    #[arg(long = "patch", value_name = "FILE")]
+    pub patch: Option<PathBuf>,
+
+    /// Review only what changed since an earlier review thread, skipping the findings it
+    /// already reported.
+    #[arg(long = "since-review", value_name = "THREAD_ID")]
+    pub since_review: Option<String>,
+
     /// Custom review instructions. If `-` is used, read from stdin.
     #[arg(value_name = "PROMPT", value_hint = clap::ValueHint::Other)]
     pub prompt: Option<String>,
@@ -249,6 +318,16 @@ pub enum Color {
     Auto,
 }
 
+/// Report formats for `--format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum OutputFormat {
+    /// SARIF 2.1.0 log of review findings. Only valid with `review`.
+    Sarif,
+    /// JUnit XML with one test case per turn and per tool call.
+    Junit,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -283,4 +362,61 @@ mod tests {
         });
         assert_eq!(effective_prompt.as_deref(), Some(PROMPT));
     }
+
+    #[test]
+    fn parses_budget_flags() {
+        let cli = Cli::parse_from([
+            "codex-exec",
+            "--max-turns",
+            "5",
+            "--max-tokens",
+            "100000",
+            "--max-tool-calls",
+            "20",
+            "--timeout",
+            "10m",
+            "fix the tests",
+        ]);
+
+        assert_eq!(cli.max_turns, Some(5));
+        assert_eq!(cli.max_tokens, Some(100_000));
+        assert_eq!(cli.max_tool_calls, Some(20));
+        assert_eq!(cli.timeout, Some(std::time::Duration::from_secs(600)));
+        assert_eq!(cli.prompt.as_deref(), Some("fix the tests"));
+    }
+
+    #[test]
+    fn batch_conflicts_with_prompt() {
+        let cli = Cli::parse_from(["codex-exec", "--batch", "tasks.jsonl", "--concurrency", "8"]);
+        assert_eq!(cli.batch, Some(PathBuf::from("tasks.jsonl")));
+        assert_eq!(cli.concurrency, Some(8));
+
+        let err = Cli::try_parse_from(["codex-exec", "--batch", "tasks.jsonl", "fix the tests"])
+            .expect_err("--batch and a prompt are mutually exclusive");
+        assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn format_applies_to_review_and_conflicts_with_json() {
+        let cli = Cli::parse_from(["codex-exec", "review", "--uncommitted", "--format", "sarif"]);
+        assert_eq!(cli.format, Some(OutputFormat::Sarif));
+        assert!(matches!(cli.command, Some(Command::Review(_))));
+
+        let err = Cli::try_parse_from(["codex-exec", "--json", "--format", "junit", "fix it"])
+            .expect_err("--json and --format are mutually exclusive");
+        assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn review_targets_are_mutually_exclusive() {
+        let cli = Cli::parse_from(["codex-exec", "review", "--stack", "main..HEAD"]);
+        let Some(Command::Review(args)) = cli.command else {
+            panic!("expected review command");
+        };
+        assert_eq!(args.stack.as_deref(), Some("main..HEAD"));
+
+        let err = Cli::try_parse_from(["codex-exec", "review", "--range", "a..b", "--patch", "-"])
+            .expect_err("--range and --patch are mutually exclusive");
+        assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+    }
 }
@@ -4,6 +4,8 @@ use codex_core::config::Config;
 use codex_core::protocol::Event;
 use codex_core::protocol::SessionConfiguredEvent;
 
+use crate::exec_events::BudgetExceededEvent;
+
 pub(crate) enum CodexStatus {
     Running,
     InitiateShutdown,
@@ -22,6 +24,10 @@ pub(crate) trait EventProcessor {
     /// Handle a single event emitted by the agent.
     fn process_event(&mut self, event: Event) -> CodexStatus;
 
+    /// Report that the run was aborted because a budget was exceeded. Called once, after the
+    /// event loop has drained, so it is the last thing emitted.
+    fn process_budget_exceeded(&mut self, exceeded: &BudgetExceededEvent);
+
     fn print_final_output(&mut self) {}
 }
 
@@ -24,6 +24,7 @@ use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
+use codex_core::protocol::ProviderFailoverEvent;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_core::protocol::StreamErrorEvent;
 use codex_core::protocol::TurnAbortReason;
@@ -43,9 +44,11 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::Instant;
 
+use crate::budget::budget_flag;
 use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
 use crate::event_processor::handle_last_message;
+use crate::exec_events::BudgetExceededEvent;
 use codex_protocol::plan_tool::StepStatus;
 use codex_protocol::plan_tool::UpdatePlanArgs;
 use codex_utils_sandbox_summary::create_config_summary_entries;
@@ -248,9 +251,31 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 };
                 ts_msg!(self, "{}", message.style(self.dimmed));
             }
+            EventMsg::ProviderFailover(ProviderFailoverEvent {
+                from_provider,
+                from_model,
+                to_provider,
+                to_model,
+                trigger,
+                ..
+            }) => {
+                let reason = trigger.map_or_else(String::new, |trigger| format!(" ({trigger})"));
+                ts_msg!(
+                    self,
+                    "{} {from_provider}/{from_model} -> {to_provider}/{to_model}{reason}",
+                    "provider failover:".style(self.yellow).style(self.bold)
+                );
+            }
             EventMsg::TurnStarted(_) => {
                 // Ignore.
             }
+            EventMsg::EnteredReviewMode(request) => {
+                // Marks where each review's output starts, which matters for `--stack`.
+                let hint = request.user_facing_hint.unwrap_or_else(|| {
+                    codex_core::review_prompts::user_facing_hint(&request.target)
+                });
+                ts_msg!(self, "{} {hint}", "review".style(self.magenta));
+            }
             EventMsg::ElicitationRequest(ev) => {
                 ts_msg!(
                     self,
@@ -519,13 +544,26 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     eprintln!("{}", line.style(self.dimmed));
                 }
             }
-            EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => {
+            EventMsg::TurnDiff(TurnDiffEvent {
+                unified_diff,
+                external_unified_diff,
+            }) => {
                 ts_msg!(
                     self,
                     "{}",
                     "file update:".style(self.magenta).style(self.italic)
                 );
                 eprintln!("{unified_diff}");
+                if let Some(external_unified_diff) = external_unified_diff {
+                    ts_msg!(
+                        self,
+                        "{}",
+                        "edited outside codex:"
+                            .style(self.magenta)
+                            .style(self.italic)
+                    );
+                    eprintln!("{}", external_unified_diff.style(self.dimmed));
+                }
             }
             EventMsg::AgentReasoning(agent_reasoning_event) => {
                 if self.show_agent_reasoning {
@@ -785,8 +823,8 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::RemoteSkillDownloaded(_)
             | EventMsg::RawResponseItem(_)
             | EventMsg::UserMessage(_)
-            | EventMsg::EnteredReviewMode(_)
             | EventMsg::ExitedReviewMode(_)
+            | EventMsg::ReviewFindingStatus(_)
             | EventMsg::AgentMessageDelta(_)
             | EventMsg::AgentReasoningDelta(_)
             | EventMsg::AgentReasoningRawContentDelta(_)
@@ -799,6 +837,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::SkillsUpdateAvailable
             | EventMsg::UndoCompleted(_)
             | EventMsg::UndoStarted(_)
+            | EventMsg::SnapshotTimeline(_)
             | EventMsg::ThreadRolledBack(_)
             | EventMsg::RequestUserInput(_)
             | EventMsg::CollabResumeBegin(_)
@@ -808,6 +847,17 @@ impl EventProcessor for EventProcessorWithHumanOutput {
         CodexStatus::Running
     }
 
+    fn process_budget_exceeded(&mut self, exceeded: &BudgetExceededEvent) {
+        ts_msg!(
+            self,
+            "{} {} exceeded (used {}, limit {})",
+            "budget:".style(self.red),
+            budget_flag(exceeded.budget),
+            exceeded.used,
+            exceeded.limit
+        );
+    }
+
     fn print_final_output(&mut self) {
         if let Some(usage_info) = &self.last_total_token_usage {
             eprintln!(
@@ -6,6 +6,7 @@ use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
 use crate::event_processor::handle_last_message;
 use crate::exec_events::AgentMessageItem;
+use crate::exec_events::BudgetExceededEvent;
 use crate::exec_events::CollabAgentState;
 use crate::exec_events::CollabAgentStatus;
 use crate::exec_events::CollabTool;
@@ -313,6 +314,7 @@ impl EventProcessorWithJsonOutput {
                 aggregated_output: String::new(),
                 exit_code: None,
                 status: CommandExecutionStatus::InProgress,
+                provenance: None,
             }),
         };
 
@@ -704,6 +706,7 @@ impl EventProcessorWithJsonOutput {
                 aggregated_output,
                 exit_code: Some(ev.exit_code),
                 status,
+                provenance: ev.provenance.clone(),
             }),
         };
 
@@ -781,6 +784,7 @@ impl EventProcessorWithJsonOutput {
                         aggregated_output: running.aggregated_output,
                         exit_code: None,
                         status: CommandExecutionStatus::Completed,
+                        provenance: None,
                     }),
                 };
                 items.push(ThreadEvent::ItemCompleted(ItemCompletedEvent { item }));
@@ -877,4 +881,12 @@ impl EventProcessor for EventProcessorWithJsonOutput {
             _ => CodexStatus::Running,
         }
     }
+
+    #[allow(clippy::print_stdout)]
+    fn process_budget_exceeded(&mut self, exceeded: &BudgetExceededEvent) {
+        match serde_json::to_string(&ThreadEvent::BudgetExceeded(exceeded.clone())) {
+            Ok(line) => println!("{line}"),
+            Err(e) => error!("Failed to serialize event: {e:?}"),
+        }
+    }
 }
@@ -0,0 +1,424 @@
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_core::config::Config;
+use codex_core::protocol;
+
+use crate::budget::budget_flag;
+use crate::event_processor::CodexStatus;
+use crate::event_processor::EventProcessor;
+use crate::event_processor::handle_last_message;
+use crate::event_processor_with_jsonl_output::EventProcessorWithJsonOutput;
+use crate::exec_events::BudgetExceededEvent;
+use crate::exec_events::CollabTool;
+use crate::exec_events::CollabToolCallStatus;
+use crate::exec_events::CommandExecutionStatus;
+use crate::exec_events::McpToolCallStatus;
+use crate::exec_events::PatchApplyStatus;
+use crate::exec_events::ThreadEvent;
+use crate::exec_events::ThreadItemDetails;
+
+const SUITE_NAME: &str = "codex exec";
+
+/// Records the turns and tool calls of a `codex exec` run and prints them as a JUnit XML report
+/// once the run has finished. Events are first mapped to the same [`ThreadEvent`]s that `--json`
+/// prints, so both outputs agree on what succeeded and what failed.
+pub(crate) struct EventProcessorWithJunitOutput {
+    thread_events: EventProcessorWithJsonOutput,
+    last_message_path: Option<PathBuf>,
+    report: JunitReport,
+}
+
+impl EventProcessorWithJunitOutput {
+    pub(crate) fn new(last_message_path: Option<PathBuf>) -> Self {
+        Self {
+            thread_events: EventProcessorWithJsonOutput::new(None),
+            last_message_path,
+            report: JunitReport::new(),
+        }
+    }
+}
+
+impl EventProcessor for EventProcessorWithJunitOutput {
+    fn print_config_summary(
+        &mut self,
+        _: &Config,
+        _: &str,
+        session_configured: &protocol::SessionConfiguredEvent,
+    ) {
+        self.process_event(protocol::Event {
+            id: String::new(),
+            msg: protocol::EventMsg::SessionConfigured(session_configured.clone()),
+        });
+    }
+
+    fn process_event(&mut self, event: protocol::Event) -> CodexStatus {
+        for thread_event in self.thread_events.collect_thread_events(&event) {
+            self.report.record(thread_event);
+        }
+
+        match event.msg {
+            protocol::EventMsg::TurnComplete(protocol::TurnCompleteEvent {
+                last_agent_message,
+                ..
+            }) => {
+                if let Some(output_file) = self.last_message_path.as_deref() {
+                    handle_last_message(last_agent_message.as_deref(), output_file);
+                }
+                CodexStatus::InitiateShutdown
+            }
+            protocol::EventMsg::TurnAborted(_) => CodexStatus::InitiateShutdown,
+            protocol::EventMsg::ShutdownComplete => CodexStatus::Shutdown,
+            _ => CodexStatus::Running,
+        }
+    }
+
+    fn process_budget_exceeded(&mut self, exceeded: &BudgetExceededEvent) {
+        self.report
+            .record(ThreadEvent::BudgetExceeded(exceeded.clone()));
+    }
+
+    #[allow(clippy::print_stdout)]
+    fn print_final_output(&mut self) {
+        print!("{}", self.report.render());
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct TestCase {
+    classname: &'static str,
+    name: String,
+    time: Option<Duration>,
+    failure: Option<TestFailure>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct TestFailure {
+    message: String,
+    details: String,
+}
+
+/// One test case per turn (failed when the turn failed) and per finished tool call (failed
+/// when the command, patch, MCP or collab call failed). A tripped budget is its own failure.
+#[derive(Debug)]
+struct JunitReport {
+    started_at: Instant,
+    thread_id: Option<String>,
+    turn_started_at: Option<Instant>,
+    turns: usize,
+    cases: Vec<TestCase>,
+}
+
+impl JunitReport {
+    fn new() -> Self {
+        Self {
+            started_at: Instant::now(),
+            thread_id: None,
+            turn_started_at: None,
+            turns: 0,
+            cases: Vec::new(),
+        }
+    }
+
+    fn record(&mut self, event: ThreadEvent) {
+        match event {
+            ThreadEvent::ThreadStarted(ev) => self.thread_id = Some(ev.thread_id),
+            ThreadEvent::TurnStarted(_) => self.turn_started_at = Some(Instant::now()),
+            ThreadEvent::TurnCompleted(_) => self.finish_turn(None),
+            ThreadEvent::TurnFailed(ev) => self.finish_turn(Some(TestFailure {
+                message: ev.error.message.clone(),
+                details: ev.error.message,
+            })),
+            ThreadEvent::ItemCompleted(ev) => {
+                if let Some(case) = tool_case(ev.item.details) {
+                    self.cases.push(case);
+                }
+            }
+            ThreadEvent::BudgetExceeded(ev) => {
+                let flag = budget_flag(ev.budget);
+                self.cases.push(TestCase {
+                    classname: "codex.budget",
+                    name: flag.to_string(),
+                    time: None,
+                    failure: Some(TestFailure {
+                        message: format!("{flag} exceeded"),
+                        details: format!("used {}, limit {}", ev.used, ev.limit),
+                    }),
+                });
+            }
+            ThreadEvent::ItemStarted(_) | ThreadEvent::ItemUpdated(_) | ThreadEvent::Error(_) => {}
+        }
+    }
+
+    fn finish_turn(&mut self, failure: Option<TestFailure>) {
+        self.turns += 1;
+        self.cases.push(TestCase {
+            classname: "codex.turn",
+            name: format!("turn {}", self.turns),
+            time: self.turn_started_at.take().map(|started| started.elapsed()),
+            failure,
+        });
+    }
+
+    fn render(&self) -> String {
+        self.render_with_time(self.started_at.elapsed())
+    }
+
+    fn render_with_time(&self, total: Duration) -> String {
+        let tests = self.cases.len();
+        let failures = self
+            .cases
+            .iter()
+            .filter(|case| case.failure.is_some())
+            .count();
+        let time = format_seconds(total);
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            xml,
+            "<testsuites name=\"{SUITE_NAME}\" tests=\"{tests}\" failures=\"{failures}\" \
+             time=\"{time}\">"
+        );
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{SUITE_NAME}\" tests=\"{tests}\" failures=\"{failures}\" \
+             errors=\"0\" skipped=\"0\" time=\"{time}\">"
+        );
+        if let Some(thread_id) = &self.thread_id {
+            xml.push_str("    <properties>\n");
+            let _ = writeln!(
+                xml,
+                "      <property name=\"thread_id\" value=\"{}\"/>",
+                escape_xml(thread_id)
+            );
+            xml.push_str("    </properties>\n");
+        }
+        for case in &self.cases {
+            let _ = write!(
+                xml,
+                "    <testcase classname=\"{}\" name=\"{}\"",
+                case.classname,
+                escape_xml(&case.name)
+            );
+            if let Some(time) = case.time {
+                let _ = write!(xml, " time=\"{}\"", format_seconds(time));
+            }
+            match &case.failure {
+                None => xml.push_str("/>\n"),
+                Some(failure) => {
+                    let _ = writeln!(
+                        xml,
+                        ">\n      <failure message=\"{}\">{}</failure>\n    </testcase>",
+                        escape_xml(&failure.message),
+                        escape_xml(&failure.details)
+                    );
+                }
+            }
+        }
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+        xml
+    }
+}
+
+/// Maps a finished tool call to a test case; other items (messages, reasoning, plans) are not
+/// tests. Declined commands count as failures so that blocked work shows up in CI.
+fn tool_case(details: ThreadItemDetails) -> Option<TestCase> {
+    let (classname, name, failure) = match details {
+        ThreadItemDetails::CommandExecution(item) => {
+            let failure = match item.status {
+                CommandExecutionStatus::Failed => Some(TestFailure {
+                    message: match item.exit_code {
+                        Some(code) => format!("exited with code {code}"),
+                        None => "command failed".to_string(),
+                    },
+                    details: item.aggregated_output,
+                }),
+                CommandExecutionStatus::Declined => Some(TestFailure {
+                    message: "command was declined".to_string(),
+                    details: item.aggregated_output,
+                }),
+                CommandExecutionStatus::Completed | CommandExecutionStatus::InProgress => None,
+            };
+            ("codex.command", item.command, failure)
+        }
+        ThreadItemDetails::FileChange(item) => {
+            let paths = item
+                .changes
+                .iter()
+                .map(|change| change.path.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            let failure = (item.status == PatchApplyStatus::Failed).then(|| TestFailure {
+                message: "patch failed to apply".to_string(),
+                details: paths.clone(),
+            });
+            ("codex.patch", paths, failure)
+        }
+        ThreadItemDetails::McpToolCall(item) => {
+            let failure = (item.status == McpToolCallStatus::Failed).then(|| {
+                let message = item
+                    .error
+                    .map(|error| error.message)
+                    .unwrap_or_else(|| "tool call failed".to_string());
+                TestFailure {
+                    message: message.clone(),
+                    details: message,
+                }
+            });
+            (
+                "codex.mcp",
+                format!("{}.{}", item.server, item.tool),
+                failure,
+            )
+        }
+        ThreadItemDetails::CollabToolCall(item) => {
+            let failure = (item.status == CollabToolCallStatus::Failed).then(|| TestFailure {
+                message: "collab tool call failed".to_string(),
+                details: item.receiver_thread_ids.join(", "),
+            });
+            let name = match item.tool {
+                CollabTool::SpawnAgent => "spawn_agent",
+                CollabTool::SendInput => "send_input",
+                CollabTool::Wait => "wait",
+                CollabTool::CloseAgent => "close_agent",
+            };
+            ("codex.collab", name.to_string(), failure)
+        }
+        ThreadItemDetails::AgentMessage(_)
+        | ThreadItemDetails::Reasoning(_)
+        | ThreadItemDetails::WebSearch(_)
+        | ThreadItemDetails::TodoList(_)
+        | ThreadItemDetails::Error(_) => return None,
+    };
+    Some(TestCase {
+        classname,
+        name,
+        time: None,
+        failure,
+    })
+}
+
+fn format_seconds(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64())
+}
+
+/// Escapes text for XML attributes and content, dropping characters XML 1.0 cannot represent
+/// (such as the ANSI escapes that show up in command output).
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(ch),
+            ch if ch < ' ' || ch == '\u{fffe}' || ch == '\u{ffff}' => {}
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exec_events::CommandExecutionItem;
+    use crate::exec_events::ItemCompletedEvent;
+    use crate::exec_events::McpToolCallItem;
+    use crate::exec_events::McpToolCallItemError;
+    use crate::exec_events::ThreadErrorEvent;
+    use crate::exec_events::ThreadItem;
+    use crate::exec_events::ThreadStartedEvent;
+    use crate::exec_events::TurnCompletedEvent;
+    use crate::exec_events::TurnFailedEvent;
+    use crate::exec_events::TurnStartedEvent;
+    use crate::exec_events::Usage;
+    use pretty_assertions::assert_eq;
+
+    fn completed(details: ThreadItemDetails) -> ThreadEvent {
+        ThreadEvent::ItemCompleted(ItemCompletedEvent {
+            item: ThreadItem {
+                id: "item_0".to_string(),
+                details,
+            },
+        })
+    }
+
+    #[test]
+    fn turns_and_tool_calls_become_test_cases() {
+        let mut report = JunitReport::new();
+        let events = vec![
+            ThreadEvent::ThreadStarted(ThreadStartedEvent {
+                thread_id: "thread-1".to_string(),
+            }),
+            ThreadEvent::TurnStarted(TurnStartedEvent {}),
+            completed(ThreadItemDetails::CommandExecution(CommandExecutionItem {
+                command: "cargo test".to_string(),
+                aggregated_output: "test a ... FAILED\n\u{1b}[31merror\u{1b}[0m: 1 < 2".to_string(),
+                exit_code: Some(101),
+                status: CommandExecutionStatus::Failed,
+                provenance: None,
+            })),
+            completed(ThreadItemDetails::CommandExecution(CommandExecutionItem {
+                command: "ls".to_string(),
+                aggregated_output: String::new(),
+                exit_code: Some(0),
+                status: CommandExecutionStatus::Completed,
+                provenance: None,
+            })),
+            completed(ThreadItemDetails::McpToolCall(McpToolCallItem {
+                server: "docs".to_string(),
+                tool: "search".to_string(),
+                arguments: serde_json::Value::Null,
+                result: None,
+                error: Some(McpToolCallItemError {
+                    message: "timed out".to_string(),
+                }),
+                status: McpToolCallStatus::Failed,
+            })),
+            ThreadEvent::TurnCompleted(TurnCompletedEvent {
+                usage: Usage::default(),
+            }),
+            ThreadEvent::TurnStarted(TurnStartedEvent {}),
+            ThreadEvent::TurnFailed(TurnFailedEvent {
+                error: ThreadErrorEvent {
+                    message: "stream \"disconnected\"".to_string(),
+                },
+            }),
+        ];
+        for event in events {
+            report.record(event);
+        }
+        for case in &mut report.cases {
+            case.time = None;
+        }
+
+        assert_eq!(
+            report.render_with_time(Duration::from_millis(1500)),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="codex exec" tests="5" failures="3" time="1.500">
+  <testsuite name="codex exec" tests="5" failures="3" errors="0" skipped="0" time="1.500">
+    <properties>
+      <property name="thread_id" value="thread-1"/>
+    </properties>
+    <testcase classname="codex.command" name="cargo test">
+      <failure message="exited with code 101">test a ... FAILED
+[31merror[0m: 1 &lt; 2</failure>
+    </testcase>
+    <testcase classname="codex.command" name="ls"/>
+    <testcase classname="codex.mcp" name="docs.search">
+      <failure message="timed out">timed out</failure>
+    </testcase>
+    <testcase classname="codex.turn" name="turn 1"/>
+    <testcase classname="codex.turn" name="turn 2">
+      <failure message="stream &quot;disconnected&quot;">stream &quot;disconnected&quot;</failure>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+        );
+    }
+}
@@ -0,0 +1,362 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_core::config::Config;
+use codex_core::protocol::Event;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::ReviewFinding;
+use codex_core::protocol::ReviewOutputEvent;
+use codex_core::protocol::ReviewTarget;
+use codex_core::protocol::SessionConfiguredEvent;
+use codex_core::protocol::TurnCompleteEvent;
+use serde_json::Value as JsonValue;
+use serde_json::json;
+
+use crate::budget::budget_flag;
+use crate::event_processor::CodexStatus;
+use crate::event_processor::EventProcessor;
+use crate::event_processor::handle_last_message;
+use crate::exec_events::BudgetExceededEvent;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_RULE_ID: &str = "codex-review";
+const SRCROOT: &str = "SRCROOT";
+
+/// Collects the findings of a `codex exec review` run and prints them as a single SARIF 2.1.0
+/// log once the run has finished. Progress and errors go to stderr so stdout stays parseable.
+pub(crate) struct EventProcessorWithSarifOutput {
+    cwd: PathBuf,
+    last_message_path: Option<PathBuf>,
+    current_target: Option<ReviewTarget>,
+    reviews: Vec<CompletedReview>,
+    failed_reviews: usize,
+}
+
+/// One finished review; a `--stack` run produces one per commit.
+struct CompletedReview {
+    target: Option<ReviewTarget>,
+    output: ReviewOutputEvent,
+}
+
+impl EventProcessorWithSarifOutput {
+    pub(crate) fn new(cwd: PathBuf, last_message_path: Option<PathBuf>) -> Self {
+        Self {
+            cwd,
+            last_message_path,
+            current_target: None,
+            reviews: Vec::new(),
+            failed_reviews: 0,
+        }
+    }
+}
+
+impl EventProcessor for EventProcessorWithSarifOutput {
+    fn print_config_summary(&mut self, _: &Config, _: &str, _: &SessionConfiguredEvent) {}
+
+    #[allow(clippy::print_stderr)]
+    fn process_event(&mut self, event: Event) -> CodexStatus {
+        match event.msg {
+            EventMsg::EnteredReviewMode(request) => {
+                self.current_target = Some(request.target);
+                CodexStatus::Running
+            }
+            EventMsg::ExitedReviewMode(ev) => {
+                let target = self.current_target.take();
+                match ev.review_output {
+                    Some(output) => self.reviews.push(CompletedReview { target, output }),
+                    None => self.failed_reviews += 1,
+                }
+                CodexStatus::Running
+            }
+            EventMsg::Error(ev) => {
+                eprintln!("ERROR: {}", ev.message);
+                CodexStatus::Running
+            }
+            EventMsg::TurnComplete(TurnCompleteEvent {
+                last_agent_message, ..
+            }) => {
+                if let Some(output_file) = self.last_message_path.as_deref() {
+                    handle_last_message(last_agent_message.as_deref(), output_file);
+                }
+                CodexStatus::InitiateShutdown
+            }
+            EventMsg::TurnAborted(_) => CodexStatus::InitiateShutdown,
+            EventMsg::ShutdownComplete => CodexStatus::Shutdown,
+            _ => CodexStatus::Running,
+        }
+    }
+
+    #[allow(clippy::print_stderr)]
+    fn process_budget_exceeded(&mut self, exceeded: &BudgetExceededEvent) {
+        eprintln!(
+            "budget: {} exceeded (used {}, limit {})",
+            budget_flag(exceeded.budget),
+            exceeded.used,
+            exceeded.limit
+        );
+    }
+
+    #[allow(clippy::print_stdout)]
+    fn print_final_output(&mut self) {
+        let successful = !self.reviews.is_empty() && self.failed_reviews == 0;
+        let log = sarif_log(&self.cwd, &self.reviews, successful);
+        match serde_json::to_string_pretty(&log) {
+            Ok(text) => println!("{text}"),
+            Err(err) => tracing::error!("Failed to serialize SARIF log: {err:?}"),
+        }
+    }
+}
+
+/// Builds the SARIF log for the finished reviews. A run whose reviewer failed or was aborted
+/// still yields a valid log, with `executionSuccessful: false`.
+fn sarif_log(cwd: &Path, reviews: &[CompletedReview], successful: bool) -> JsonValue {
+    let results: Vec<JsonValue> = reviews
+        .iter()
+        .flat_map(|review| {
+            let commit = match &review.target {
+                Some(ReviewTarget::Commit { sha, .. }) => Some(sha.as_str()),
+                _ => None,
+            };
+            review
+                .output
+                .findings
+                .iter()
+                .map(move |finding| sarif_result(cwd, finding, commit))
+        })
+        .collect();
+    let summaries: Vec<JsonValue> = reviews
+        .iter()
+        .map(|review| {
+            json!({
+                "target": review.target.as_ref().map(codex_core::review_prompts::user_facing_hint),
+                "overallCorrectness": review.output.overall_correctness,
+                "overallExplanation": review.output.overall_explanation,
+                "overallConfidenceScore": review.output.overall_confidence_score,
+            })
+        })
+        .collect();
+    let run = json!({
+        "tool": {
+            "driver": {
+                "name": "codex",
+                "informationUri": "https://github.com/openai/codex",
+                "version": env!("CARGO_PKG_VERSION"),
+                "rules": [{
+                    "id": SARIF_RULE_ID,
+                    "name": "CodexReviewFinding",
+                    "shortDescription": { "text": "Issue reported by codex review" },
+                }],
+            },
+        },
+        "originalUriBaseIds": {
+            SRCROOT: { "uri": directory_uri(cwd) },
+        },
+        "invocations": [{ "executionSuccessful": successful }],
+        "results": results,
+        "properties": { "reviews": summaries },
+    });
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [run],
+    })
+}
+
+fn sarif_result(cwd: &Path, finding: &ReviewFinding, commit: Option<&str>) -> JsonValue {
+    let location = &finding.code_location;
+    let artifact_location = match location.absolute_file_path.strip_prefix(cwd) {
+        Ok(relative) => json!({ "uri": encode_path(relative), "uriBaseId": SRCROOT }),
+        Err(_) => json!({ "uri": file_uri(&location.absolute_file_path) }),
+    };
+    let start_line = location.line_range.start.max(1);
+    let end_line = location.line_range.end.max(start_line);
+    let text = if finding.body.trim().is_empty() {
+        finding.title.clone()
+    } else {
+        format!("{}\n\n{}", finding.title, finding.body)
+    };
+    let mut result = json!({
+        "ruleId": SARIF_RULE_ID,
+        "level": sarif_level(finding.priority),
+        "message": { "text": text },
+        "locations": [{
+            "physicalLocation": {
+                "artifactLocation": artifact_location,
+                "region": { "startLine": start_line, "endLine": end_line },
+            },
+        }],
+        "properties": {
+            "priority": finding.priority,
+            "confidence": finding.confidence_score,
+        },
+    });
+    if let Some(commit) = commit {
+        result["properties"]["commit"] = json!(commit);
+    }
+    result
+}
+
+/// Maps review priorities (P0 is most severe) onto SARIF levels.
+fn sarif_level(priority: i32) -> &'static str {
+    match priority {
+        i32::MIN..=1 => "error",
+        2 => "warning",
+        _ => "note",
+    }
+}
+
+fn directory_uri(path: &Path) -> String {
+    let mut uri = file_uri(path);
+    if !uri.ends_with('/') {
+        uri.push('/');
+    }
+    uri
+}
+
+fn file_uri(path: &Path) -> String {
+    let encoded = encode_path(path);
+    if encoded.starts_with('/') {
+        format!("file://{encoded}")
+    } else {
+        format!("file:///{encoded}")
+    }
+}
+
+/// Percent-encodes a path for use in a URI, keeping `/` as the segment separator.
+fn encode_path(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                encoded.push(byte as char);
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::ReviewCodeLocation;
+    use codex_core::protocol::ReviewLineRange;
+    use pretty_assertions::assert_eq;
+
+    fn finding(path: &str, priority: i32, start: u32, end: u32) -> ReviewFinding {
+        ReviewFinding {
+            title: "[P1] Off-by-one in loop".to_string(),
+            body: "The loop skips the last element.".to_string(),
+            confidence_score: 0.75,
+            priority,
+            code_location: ReviewCodeLocation {
+                absolute_file_path: PathBuf::from(path),
+                line_range: ReviewLineRange { start, end },
+            },
+        }
+    }
+
+    #[test]
+    fn findings_become_results_relative_to_cwd() {
+        let output = ReviewOutputEvent {
+            findings: vec![
+                finding("/repo/src/my lib.rs", 1, 10, 12),
+                finding("/elsewhere/main.rs", 3, 0, 0),
+            ],
+            overall_correctness: "patch is incorrect".to_string(),
+            overall_explanation: "One bug.".to_string(),
+            overall_confidence_score: 0.5,
+        };
+
+        let reviews = [CompletedReview {
+            target: Some(ReviewTarget::UncommittedChanges),
+            output,
+        }];
+
+        let log = sarif_log(Path::new("/repo"), &reviews, true);
+        let run = &log["runs"][0];
+
+        assert_eq!(
+            run["originalUriBaseIds"][SRCROOT]["uri"],
+            json!("file:///repo/")
+        );
+        assert_eq!(run["invocations"][0]["executionSuccessful"], json!(true));
+        assert_eq!(
+            run["properties"]["reviews"],
+            json!([{
+                "target": "current changes",
+                "overallCorrectness": "patch is incorrect",
+                "overallExplanation": "One bug.",
+                "overallConfidenceScore": 0.5,
+            }])
+        );
+        assert_eq!(
+            run["results"][0],
+            json!({
+                "ruleId": SARIF_RULE_ID,
+                "level": "error",
+                "message": {
+                    "text": "[P1] Off-by-one in loop\n\nThe loop skips the last element.",
+                },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": "src/my%20lib.rs", "uriBaseId": SRCROOT },
+                        "region": { "startLine": 10, "endLine": 12 },
+                    },
+                }],
+                "properties": { "priority": 1, "confidence": 0.75 },
+            })
+        );
+        assert_eq!(run["results"][1]["level"], json!("note"));
+        assert_eq!(
+            run["results"][1]["locations"][0]["physicalLocation"],
+            json!({
+                "artifactLocation": { "uri": "file:///elsewhere/main.rs" },
+                "region": { "startLine": 1, "endLine": 1 },
+            })
+        );
+    }
+
+    #[test]
+    fn stacked_reviews_tag_findings_with_their_commit() {
+        let review = |sha: &str, path: &str| CompletedReview {
+            target: Some(ReviewTarget::Commit {
+                sha: sha.to_string(),
+                title: None,
+            }),
+            output: ReviewOutputEvent {
+                findings: vec![finding(path, 2, 1, 1)],
+                ..Default::default()
+            },
+        };
+        let reviews = [review("aaa", "/repo/a.rs"), review("bbb", "/repo/b.rs")];
+
+        let log = sarif_log(Path::new("/repo"), &reviews, true);
+        let results = &log["runs"][0]["results"];
+
+        assert_eq!(results[0]["properties"]["commit"], json!("aaa"));
+        assert_eq!(results[1]["properties"]["commit"], json!("bbb"));
+        assert_eq!(
+            results[1]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            json!("b.rs")
+        );
+        assert_eq!(
+            log["runs"][0]["properties"]["reviews"][1]["target"],
+            json!("commit bbb")
+        );
+    }
+
+    #[test]
+    fn missing_review_output_is_an_unsuccessful_empty_run() {
+        let log = sarif_log(Path::new("/repo"), &[], false);
+
+        assert_eq!(log["version"], json!("2.1.0"));
+        assert_eq!(log["runs"][0]["results"], json!([]));
+        assert_eq!(
+            log["runs"][0]["invocations"][0]["executionSuccessful"],
+            json!(false)
+        );
+        assert_eq!(log["runs"][0]["properties"], json!({ "reviews": [] }));
+    }
+}
@@ -1,3 +1,4 @@
+use codex_protocol::approvals::DecisionProvenance;
 use codex_protocol::models::WebSearchAction;
 use serde::Deserialize;
 use serde::Serialize;
@@ -34,6 +35,10 @@ pub enum ThreadEvent {
     /// Represents an unrecoverable error emitted directly by the event stream.
     #[serde(rename = "error")]
     Error(ThreadErrorEvent),
+    /// Emitted as the final event when a `--max-*` or `--timeout` budget was exceeded and the
+    /// run was aborted.
+    #[serde(rename = "budget.exceeded")]
+    BudgetExceeded(BudgetExceededEvent),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -82,6 +87,25 @@ pub struct ItemUpdatedEvent {
     pub item: ThreadItem,
 }
 
+/// Budget that stopped a `codex exec` run.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetKind {
+    MaxTurns,
+    MaxTokens,
+    MaxToolCalls,
+    Timeout,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct BudgetExceededEvent {
+    pub budget: BudgetKind,
+    /// The configured limit (seconds for `timeout`).
+    pub limit: u64,
+    /// Consumption when the budget tripped (seconds for `timeout`).
+    pub used: u64,
+}
+
 /// Fatal error emitted by the stream.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 pub struct ThreadErrorEvent {
@@ -158,6 +182,11 @@ pub struct CommandExecutionItem {
     pub aggregated_output: String,
     pub exit_code: Option<i32>,
     pub status: CommandExecutionStatus,
+    /// What allowed the command to run or rejected it. Set once the command
+    /// finishes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub provenance: Option<DecisionProvenance>,
 }
 
 /// A set of file changes by the agent.
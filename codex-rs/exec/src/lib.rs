@@ -4,14 +4,20 @@
 // For both modes, any other output must be written to stderr.
 #![deny(clippy::print_stdout)]
 
+mod batch;
+mod budget;
 mod cli;
 mod event_processor;
 mod event_processor_with_human_output;
 pub mod event_processor_with_jsonl_output;
+mod event_processor_with_junit_output;
+mod event_processor_with_sarif_output;
 pub mod exec_events;
 
+pub use budget::BUDGET_EXCEEDED_EXIT_CODE;
 pub use cli::Cli;
 pub use cli::Command;
+pub use cli::OutputFormat;
 pub use cli::ReviewArgs;
 use codex_cloud_requirements::cloud_requirements_loader;
 use codex_core::AuthManager;
@@ -26,6 +32,7 @@ use codex_core::config::ConfigOverrides;
 use codex_core::config::find_codex_home;
 use codex_core::config::load_config_as_toml_with_cli_overrides;
 use codex_core::config::resolve_oss_provider;
+use codex_core::config::types::SessionBudget;
 use codex_core::config_loader::ConfigLoadError;
 use codex_core::config_loader::format_config_error_with_source;
 use codex_core::git_info::get_git_repo_root;
@@ -45,10 +52,14 @@ use codex_utils_oss::ensure_oss_provider_ready;
 use codex_utils_oss::get_default_model_for_oss_provider;
 use event_processor_with_human_output::EventProcessorWithHumanOutput;
 use event_processor_with_jsonl_output::EventProcessorWithJsonOutput;
+use event_processor_with_junit_output::EventProcessorWithJunitOutput;
+use event_processor_with_sarif_output::EventProcessorWithSarifOutput;
 use serde_json::Value;
 use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::io::IsTerminal;
 use std::io::Read;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use supports_color::Stream;
@@ -61,6 +72,8 @@ use tracing_subscriber::EnvFilter;
 use tracing_subscriber::prelude::*;
 use uuid::Uuid;
 
+use crate::budget::BudgetLimits;
+use crate::budget::BudgetTracker;
 use crate::cli::Command as ExecCommand;
 use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
@@ -75,7 +88,8 @@ enum InitialOperation {
         output_schema: Option<Value>,
     },
     Review {
-        review_request: ReviewRequest,
+        /// Run one after another; more than one only for `--stack`.
+        review_requests: VecDeque<ReviewRequest>,
     },
 }
 
@@ -107,11 +121,30 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         color,
         last_message_file,
         json: json_mode,
+        format,
         sandbox_mode: sandbox_mode_cli_arg,
         prompt,
         output_schema: output_schema_path,
+        max_turns,
+        max_tokens,
+        max_tool_calls,
+        timeout,
+        batch,
+        concurrency,
+        replay,
         config_overrides,
     } = cli;
+    if format == Some(cli::OutputFormat::Sarif) && !matches!(command, Some(ExecCommand::Review(_)))
+    {
+        anyhow::bail!("--format sarif is only supported for `codex exec review`");
+    }
+    let budget_limits = BudgetLimits {
+        max_turns,
+        max_tokens,
+        max_tool_calls,
+        timeout,
+    };
+    let mut budget = BudgetTracker::new(budget_limits);
 
     let (stdout_with_ansi, stderr_with_ansi) = match color {
         cli::Color::Always => (true, true),
@@ -250,6 +283,7 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         model_provider: model_provider.clone(),
         codex_linux_sandbox_exe,
         js_repl_node_path: None,
+        py_repl_python_path: None,
         base_instructions: None,
         developer_instructions: None,
         personality: None,
@@ -258,9 +292,21 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         show_raw_agent_reasoning: oss.then_some(true),
         tools_web_search_request: None,
         ephemeral: ephemeral.then_some(true),
+        model_stream_replay: replay,
+        // Core enforces these for each thread on its own; the budget tracker
+        // below covers the run as a whole.
+        session_budget: SessionBudget {
+            max_tokens,
+            timeout_secs: timeout.map(|timeout| timeout.as_secs()),
+        },
         additional_writable_roots: add_dir,
     };
 
+    // Batch tasks rebuild their config per task (cwd, model and profile can
+    // differ), so keep the inputs around.
+    let batch_config_inputs = batch
+        .is_some()
+        .then(|| (overrides.clone(), cloud_requirements.clone()));
     let config = ConfigBuilder::default()
         .cli_overrides(cli_kv_overrides)
         .harness_overrides(overrides)
@@ -298,9 +344,16 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         .with(otel_logger_layer)
         .try_init();
 
-    let mut event_processor: Box<dyn EventProcessor> = match json_mode {
-        true => Box::new(EventProcessorWithJsonOutput::new(last_message_file.clone())),
-        _ => Box::new(EventProcessorWithHumanOutput::create_with_ansi(
+    let mut event_processor: Box<dyn EventProcessor> = match (format, json_mode) {
+        (Some(cli::OutputFormat::Sarif), _) => Box::new(EventProcessorWithSarifOutput::new(
+            config.cwd.to_path_buf(),
+            last_message_file.clone(),
+        )),
+        (Some(cli::OutputFormat::Junit), _) => Box::new(EventProcessorWithJunitOutput::new(
+            last_message_file.clone(),
+        )),
+        (None, true) => Box::new(EventProcessorWithJsonOutput::new(last_message_file.clone())),
+        (None, false) => Box::new(EventProcessorWithHumanOutput::create_with_ansi(
             stdout_with_ansi,
             &config,
             last_message_file.clone(),
@@ -339,7 +392,8 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
 
     // When --yolo (dangerously_bypass_approvals_and_sandbox) is set, also skip the git repo check
     // since the user is explicitly running in an externally sandboxed environment.
-    if !skip_git_repo_check
+    if batch.is_none()
+        && !skip_git_repo_check
         && !dangerously_bypass_approvals_and_sandbox
         && get_git_repo_root(&default_cwd).is_none()
     {
@@ -357,6 +411,29 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         auth_manager.clone(),
         SessionSource::Exec,
     ));
+
+    if let (Some(tasks_path), Some((harness_overrides, cloud_requirements))) =
+        (batch, batch_config_inputs)
+    {
+        if command.is_some() {
+            anyhow::bail!("--batch cannot be combined with a subcommand");
+        }
+        let context = batch::BatchContext {
+            thread_manager,
+            config_overrides,
+            harness_overrides,
+            cloud_requirements,
+            budget_limits,
+            skip_git_repo_check: skip_git_repo_check || dangerously_bypass_approvals_and_sandbox,
+            default_cwd: default_cwd.clone(),
+        };
+        let concurrency = concurrency.unwrap_or(batch::DEFAULT_BATCH_CONCURRENCY);
+        if !batch::run_batch(context, &tasks_path, concurrency).await? {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     let default_model = thread_manager
         .get_models_manager()
         .get_default_model(&config.model, &config, RefreshStrategy::OnlineIfUncached)
@@ -382,9 +459,19 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     };
     let (initial_operation, prompt_summary) = match (command, prompt, images) {
         (Some(ExecCommand::Review(review_cli)), _, _) => {
-            let review_request = build_review_request(review_cli)?;
-            let summary = codex_core::review_prompts::user_facing_hint(&review_request.target);
-            (InitialOperation::Review { review_request }, summary)
+            let review_requests = build_review_requests(review_cli, &config).await?;
+            let summary = match review_requests.as_slice() {
+                [review_request] => {
+                    codex_core::review_prompts::user_facing_hint(&review_request.target)
+                }
+                requests => format!("{} commits, one review each", requests.len()),
+            };
+            (
+                InitialOperation::Review {
+                    review_requests: review_requests.into(),
+                },
+                summary,
+            )
         }
         (Some(ExecCommand::Resume(args)), root_prompt, imgs) => {
             let prompt_arg = args
@@ -492,6 +579,8 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         });
     }
 
+    // Reviews still to run after the current one finishes (`--stack`).
+    let mut pending_reviews = VecDeque::new();
     match initial_operation {
         InitialOperation::UserTurn {
             items,
@@ -514,7 +603,13 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
             info!("Sent prompt with event ID: {task_id}");
             task_id
         }
-        InitialOperation::Review { review_request } => {
+        InitialOperation::Review {
+            mut review_requests,
+        } => {
+            let Some(review_request) = review_requests.pop_front() else {
+                anyhow::bail!("Nothing to review");
+            };
+            pending_reviews = review_requests;
             let task_id = thread.submit(Op::Review { review_request }).await?;
             info!("Sent review request with event ID: {task_id}");
             task_id
@@ -526,7 +621,47 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     // exit with a non-zero status for automation-friendly signaling.
     let mut error_seen = false;
     let mut shutdown_requested = false;
-    while let Some(envelope) = rx.recv().await {
+    // Set once a budget trips; the run then winds down and exits with
+    // BUDGET_EXCEEDED_EXIT_CODE instead of its usual status.
+    let mut budget_exceeded = None;
+    let mut shutdown_deadline = None;
+    let primary_thread = thread.clone();
+    loop {
+        let deadline = if budget_exceeded.is_some() {
+            shutdown_deadline
+        } else {
+            budget.deadline()
+        };
+        let (envelope, tripped) = tokio::select! {
+            envelope = rx.recv() => match envelope {
+                Some(envelope) => {
+                    let tripped = budget.observe(envelope.thread_id, &envelope.event.msg);
+                    (Some(envelope), tripped)
+                }
+                None => break,
+            },
+            () = budget::sleep_until(deadline) => {
+                if budget_exceeded.is_some() {
+                    warn!("thread did not shut down after budget was exceeded; exiting");
+                    break;
+                }
+                (None, Some(budget.timeout_exceeded()))
+            }
+        };
+        if let Some(exceeded) = tripped
+            && budget_exceeded.is_none()
+        {
+            budget_exceeded = Some(exceeded);
+            shutdown_deadline = Some(std::time::Instant::now() + budget::BUDGET_SHUTDOWN_GRACE);
+            primary_thread.submit(Op::Interrupt).await.ok();
+            if !shutdown_requested {
+                primary_thread.submit(Op::Shutdown).await?;
+                shutdown_requested = true;
+            }
+        }
+        let Some(envelope) = envelope else {
+            continue;
+        };
         let ThreadEventEnvelope {
             thread_id,
             thread,
@@ -567,6 +702,7 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         if thread_id != primary_thread_id && matches!(&event.msg, EventMsg::TurnComplete(_)) {
             continue;
         }
+        let turn_aborted = matches!(&event.msg, EventMsg::TurnAborted(_));
         let shutdown = event_processor.process_event(event);
         if thread_id != primary_thread_id && matches!(shutdown, CodexStatus::InitiateShutdown) {
             continue;
@@ -574,6 +710,13 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         match shutdown {
             CodexStatus::Running => continue,
             CodexStatus::InitiateShutdown => {
+                if !shutdown_requested
+                    && !turn_aborted
+                    && let Some(review_request) = pending_reviews.pop_front()
+                {
+                    thread.submit(Op::Review { review_request }).await?;
+                    continue;
+                }
                 if !shutdown_requested {
                     thread.submit(Op::Shutdown).await?;
                     shutdown_requested = true;
@@ -583,6 +726,11 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
             CodexStatus::Shutdown => continue,
         }
     }
+    if let Some(exceeded) = budget_exceeded {
+        event_processor.process_budget_exceeded(&exceeded);
+        event_processor.print_final_output();
+        std::process::exit(BUDGET_EXCEEDED_EXIT_CODE);
+    }
     event_processor.print_final_output();
     if error_seen {
         std::process::exit(1);
@@ -808,6 +956,46 @@ fn resolve_prompt(prompt_arg: Option<String>) -> String {
     }
 }
 
+/// Builds the review(s) to run: one per commit for `--stack`, otherwise a single request.
+async fn build_review_requests(
+    args: ReviewArgs,
+    config: &Config,
+) -> anyhow::Result<Vec<ReviewRequest>> {
+    if let Some(range) = args.stack.as_deref() {
+        let (base, head) = parse_commit_range(range)?;
+        let commits = codex_core::git_info::commits_in_range(&config.cwd, &base, &head).await?;
+        if commits.is_empty() {
+            anyhow::bail!("No commits to review in {range}");
+        }
+        return Ok(commits
+            .into_iter()
+            .map(|commit| ReviewRequest {
+                target: ReviewTarget::Commit {
+                    sha: commit.sha,
+                    title: Some(commit.subject),
+                },
+                user_facing_hint: None,
+            })
+            .collect());
+    }
+    if let Some(thread) = args.since_review.as_deref() {
+        let path = if Uuid::parse_str(thread).is_ok() {
+            find_thread_path_by_id_str(&config.codex_home, thread).await?
+        } else {
+            find_thread_path_by_name_str(&config.codex_home, thread).await?
+        };
+        let Some(path) = path else {
+            anyhow::bail!("No review thread found for {thread}");
+        };
+        let target = codex_core::review_prompts::incremental_review_target(&path).await?;
+        return Ok(vec![ReviewRequest {
+            target,
+            user_facing_hint: None,
+        }]);
+    }
+    Ok(vec![build_review_request(args)?])
+}
+
 fn build_review_request(args: ReviewArgs) -> anyhow::Result<ReviewRequest> {
     let target = if args.uncommitted {
         ReviewTarget::UncommittedChanges
@@ -818,6 +1006,12 @@ fn build_review_request(args: ReviewArgs) -> anyhow::Result<ReviewRequest> {
             sha,
             title: args.commit_title,
         }
+    } else if let Some(range) = args.range {
+        let (base, head) = parse_commit_range(&range)?;
+        ReviewTarget::CommitRange { base, head }
+    } else if let Some(path) = args.patch {
+        let (diff, label) = read_patch(&path)?;
+        ReviewTarget::Patch { diff, label }
     } else if let Some(prompt_arg) = args.prompt {
         let prompt = resolve_prompt(Some(prompt_arg)).trim().to_string();
         if prompt.is_empty() {
@@ -828,7 +1022,8 @@ fn build_review_request(args: ReviewArgs) -> anyhow::Result<ReviewRequest> {
         }
     } else {
         anyhow::bail!(
-            "Specify --uncommitted, --base, --commit, or provide custom review instructions"
+            "Specify --uncommitted, --base, --commit, --range, --stack, --patch, --since-review, \
+             or provide custom review instructions"
         );
     };
 
@@ -838,6 +1033,31 @@ fn build_review_request(args: ReviewArgs) -> anyhow::Result<ReviewRequest> {
     })
 }
 
+/// Splits `BASE..HEAD` into its two refs. Symmetric `BASE...HEAD` ranges are rejected because
+/// a review needs a single direction to diff in.
+fn parse_commit_range(range: &str) -> anyhow::Result<(String, String)> {
+    match range.split_once("..") {
+        Some((base, head)) if !base.is_empty() && !head.is_empty() && !head.starts_with('.') => {
+            Ok((base.to_string(), head.to_string()))
+        }
+        _ => anyhow::bail!("Expected a commit range like BASE..HEAD, got '{range}'"),
+    }
+}
+
+/// Reads the patch to review from `path`, or from stdin when it is `-`.
+fn read_patch(path: &Path) -> anyhow::Result<(String, Option<String>)> {
+    if path == Path::new("-") {
+        let mut diff = String::new();
+        std::io::stdin()
+            .read_to_string(&mut diff)
+            .map_err(|err| anyhow::anyhow!("Failed to read patch from stdin: {err}"))?;
+        return Ok((diff, None));
+    }
+    let diff = std::fs::read_to_string(path)
+        .map_err(|err| anyhow::anyhow!("Failed to read patch {}: {err}", path.display()))?;
+    Ok((diff, Some(path.display().to_string())))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -850,6 +1070,10 @@ mod tests {
             base: None,
             commit: None,
             commit_title: None,
+            range: None,
+            stack: None,
+            patch: None,
+            since_review: None,
             prompt: None,
         })
         .expect("builds uncommitted review request");
@@ -869,6 +1093,10 @@ mod tests {
             base: None,
             commit: Some("123456789".to_string()),
             commit_title: Some("Add review command".to_string()),
+            range: None,
+            stack: None,
+            patch: None,
+            since_review: None,
             prompt: None,
         })
         .expect("builds commit review request");
@@ -891,6 +1119,10 @@ mod tests {
             base: None,
             commit: None,
             commit_title: None,
+            range: None,
+            stack: None,
+            patch: None,
+            since_review: None,
             prompt: Some("  custom review instructions  ".to_string()),
         })
         .expect("builds custom review request");
@@ -905,6 +1137,34 @@ mod tests {
         assert_eq!(request, expected);
     }
 
+    #[test]
+    fn builds_commit_range_review_request() {
+        let request = build_review_request(ReviewArgs {
+            uncommitted: false,
+            base: None,
+            commit: None,
+            commit_title: None,
+            range: Some("main..feature".to_string()),
+            stack: None,
+            patch: None,
+            since_review: None,
+            prompt: None,
+        })
+        .expect("builds range review request");
+
+        let expected = ReviewRequest {
+            target: ReviewTarget::CommitRange {
+                base: "main".to_string(),
+                head: "feature".to_string(),
+            },
+            user_facing_hint: None,
+        };
+
+        assert_eq!(request, expected);
+        assert!(parse_commit_range("main...feature").is_err());
+        assert!(parse_commit_range("main").is_err());
+    }
+
     #[test]
     fn decode_prompt_bytes_strips_utf8_bom() {
         let input = [0xEF, 0xBB, 0xBF, b'h', b'i', b'\n'];
@@ -55,6 +55,7 @@ use codex_exec::exec_events::TurnStartedEvent;
 use codex_exec::exec_events::Usage;
 use codex_exec::exec_events::WebSearchItem;
 use codex_protocol::ThreadId;
+use codex_protocol::approvals::DecisionProvenance;
 use codex_protocol::config_types::ModeKind;
 use codex_protocol::mcp::CallToolResult;
 use codex_protocol::models::WebSearchAction;
@@ -880,6 +881,7 @@ fn exec_command_end_success_produces_completed_command_item() {
                     aggregated_output: String::new(),
                     exit_code: None,
                     status: CommandExecutionStatus::InProgress,
+                    provenance: None,
                 }),
             },
         })]
@@ -903,6 +905,7 @@ fn exec_command_end_success_produces_completed_command_item() {
             exit_code: 0,
             duration: Duration::from_millis(5),
             formatted_output: String::new(),
+            provenance: None,
         }),
     );
     let out_ok = ep.collect_thread_events(&end_ok);
@@ -916,6 +919,71 @@ fn exec_command_end_success_produces_completed_command_item() {
                     aggregated_output: "hi\n".to_string(),
                     exit_code: Some(0),
                     status: CommandExecutionStatus::Completed,
+                    provenance: None,
+                }),
+            },
+        })]
+    );
+}
+
+#[test]
+fn exec_command_end_reports_decision_provenance() {
+    let mut ep = EventProcessorWithJsonOutput::new(None);
+    let command = vec!["git".to_string(), "status".to_string()];
+    let cwd = std::env::current_dir().unwrap();
+    let provenance = DecisionProvenance::ExecPolicyRule {
+        matched_prefix: command.clone(),
+        justification: None,
+        file: Some("/home/user/.codex/rules/default.rules".to_string()),
+        line: Some(3),
+    };
+
+    let begin = event(
+        "p1",
+        EventMsg::ExecCommandBegin(ExecCommandBeginEvent {
+            call_id: "1".to_string(),
+            process_id: None,
+            turn_id: "turn-1".to_string(),
+            command: command.clone(),
+            cwd: cwd.clone(),
+            parsed_cmd: Vec::new(),
+            source: ExecCommandSource::Agent,
+            interaction_input: None,
+        }),
+    );
+    ep.collect_thread_events(&begin);
+
+    let end = event(
+        "p2",
+        EventMsg::ExecCommandEnd(ExecCommandEndEvent {
+            call_id: "1".to_string(),
+            process_id: None,
+            turn_id: "turn-1".to_string(),
+            command,
+            cwd,
+            parsed_cmd: Vec::new(),
+            source: ExecCommandSource::Agent,
+            interaction_input: None,
+            stdout: String::new(),
+            stderr: String::new(),
+            aggregated_output: "clean\n".to_string(),
+            exit_code: 0,
+            duration: Duration::from_millis(5),
+            formatted_output: String::new(),
+            provenance: Some(provenance.clone()),
+        }),
+    );
+    assert_eq!(
+        ep.collect_thread_events(&end),
+        vec![ThreadEvent::ItemCompleted(ItemCompletedEvent {
+            item: ThreadItem {
+                id: "item_0".to_string(),
+                details: ThreadItemDetails::CommandExecution(CommandExecutionItem {
+                    command: "git status".to_string(),
+                    aggregated_output: "clean\n".to_string(),
+                    exit_code: Some(0),
+                    status: CommandExecutionStatus::Completed,
+                    provenance: Some(provenance),
                 }),
             },
         })]
@@ -957,6 +1025,7 @@ fn command_execution_output_delta_updates_item_progress() {
                     aggregated_output: String::new(),
                     exit_code: None,
                     status: CommandExecutionStatus::InProgress,
+                    provenance: None,
                 }),
             },
         })]
@@ -990,6 +1059,7 @@ fn command_execution_output_delta_updates_item_progress() {
             exit_code: 0,
             duration: Duration::from_millis(3),
             formatted_output: String::new(),
+            provenance: None,
         }),
     );
     let out_end = ep.collect_thread_events(&end);
@@ -1003,6 +1073,7 @@ fn command_execution_output_delta_updates_item_progress() {
                     aggregated_output: String::new(),
                     exit_code: Some(0),
                     status: CommandExecutionStatus::Completed,
+                    provenance: None,
                 }),
             },
         })]
@@ -1040,6 +1111,7 @@ fn exec_command_end_failure_produces_failed_command_item() {
                     aggregated_output: String::new(),
                     exit_code: None,
                     status: CommandExecutionStatus::InProgress,
+                    provenance: None,
                 }),
             },
         })]
@@ -1063,6 +1135,7 @@ fn exec_command_end_failure_produces_failed_command_item() {
             exit_code: 1,
             duration: Duration::from_millis(2),
             formatted_output: String::new(),
+            provenance: None,
         }),
     );
     let out_fail = ep.collect_thread_events(&end_fail);
@@ -1076,6 +1149,7 @@ fn exec_command_end_failure_produces_failed_command_item() {
                     aggregated_output: String::new(),
                     exit_code: Some(1),
                     status: CommandExecutionStatus::Failed,
+                    provenance: None,
                 }),
             },
         })]
@@ -1104,6 +1178,7 @@ fn exec_command_end_without_begin_is_ignored() {
             exit_code: 0,
             duration: Duration::from_millis(1),
             formatted_output: String::new(),
+            provenance: None,
         }),
     );
     let out = ep.collect_thread_events(&end_only);
@@ -0,0 +1,95 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+
+/// Run two tasks from a JSONL file in one process and verify that each gets
+/// its own result line, in its own working directory.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn batch_runs_each_task_and_reports_results() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let repo_a = test.cwd_path().join("repo-a");
+    let repo_b = test.cwd_path().join("repo-b");
+    std::fs::create_dir_all(&repo_a)?;
+    std::fs::create_dir_all(&repo_b)?;
+    let tasks_path = test.cwd_path().join("tasks.jsonl");
+    std::fs::write(
+        &tasks_path,
+        "{\"id\": \"a\", \"prompt\": \"first\", \"cwd\": \"repo-a\"}\n\
+         {\"id\": \"b\", \"prompt\": \"second\", \"cwd\": \"repo-b\"}\n",
+    )?;
+
+    let server = responses::start_mock_server().await;
+    responses::mount_sse_sequence(
+        &server,
+        vec![
+            responses::sse(vec![
+                responses::ev_assistant_message("m1", "done"),
+                responses::ev_completed("r1"),
+            ]),
+            responses::sse(vec![
+                responses::ev_assistant_message("m2", "done"),
+                responses::ev_completed("r2"),
+            ]),
+        ],
+    )
+    .await;
+
+    let output = test
+        .cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--batch")
+        .arg(&tasks_path)
+        .arg("--concurrency")
+        .arg("1")
+        .assert()
+        .code(0)
+        .get_output()
+        .clone();
+
+    let stdout = String::from_utf8(output.stdout)?;
+    let results: Vec<Value> = stdout
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    let summary: Vec<(String, String, String, String)> = results
+        .iter()
+        .map(|result| {
+            (
+                result["id"].as_str().unwrap().to_string(),
+                result["status"].as_str().unwrap().to_string(),
+                result["final_message"].as_str().unwrap().to_string(),
+                result["cwd"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    let cwd = |dir: &std::path::Path| dir.display().to_string();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                "a".to_string(),
+                "completed".to_string(),
+                "done".to_string(),
+                cwd(&repo_a)
+            ),
+            (
+                "b".to_string(),
+                "completed".to_string(),
+                "done".to_string(),
+                cwd(&repo_b)
+            ),
+        ]
+    );
+
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(
+        stderr.contains("2 tasks: 2 completed, 0 failed"),
+        "stderr: {stderr}"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+
+/// Verify that exceeding `--max-tokens` aborts the run with the dedicated exit
+/// code and that the final JSONL event names the budget that tripped.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exits_with_budget_code_when_token_budget_exceeded() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_assistant_message("m1", "done"),
+        responses::ev_completed_with_tokens("resp1", 100),
+    ]);
+    responses::mount_sse_once(&server, body).await;
+
+    let output = test
+        .cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--experimental-json")
+        .arg("--max-tokens")
+        .arg("10")
+        .arg("tell me something")
+        .assert()
+        .code(codex_exec::BUDGET_EXCEEDED_EXIT_CODE)
+        .get_output()
+        .stdout
+        .clone();
+
+    let stdout = String::from_utf8(output)?;
+    let last_line = stdout
+        .lines()
+        .next_back()
+        .expect("at least one JSONL event");
+    let event: Value = serde_json::from_str(last_line)?;
+    assert_eq!(event["type"], "budget.exceeded");
+    assert_eq!(event["budget"], "max_tokens");
+    assert_eq!(event["limit"], 10);
+    assert_eq!(event["used"], 100);
+
+    Ok(())
+}
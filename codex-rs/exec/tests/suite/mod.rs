@@ -2,10 +2,13 @@
 mod add_dir;
 mod apply_patch;
 mod auth_env;
+mod batch;
+mod budget_exit;
 mod ephemeral;
 mod mcp_required_exit;
 mod originator;
 mod output_schema;
+mod replay;
 mod resume;
 mod sandbox;
 mod server_error_exit;
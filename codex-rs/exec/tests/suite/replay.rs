@@ -0,0 +1,78 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+use wiremock::MockServer;
+
+async fn responses_request_count(server: &MockServer) -> usize {
+    server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter(|request| request.url.path().ends_with("/responses"))
+        .count()
+}
+
+/// Records a session with `record_model_streams`, then replays it with
+/// `codex exec --replay` against a server that must not be called.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn replay_serves_recorded_responses_without_calling_the_provider() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let prompt = "what was recorded?";
+
+    let recording_server = responses::start_mock_server().await;
+    responses::mount_sse_once(
+        &recording_server,
+        responses::sse(vec![
+            responses::ev_response_created("resp-1"),
+            responses::ev_assistant_message("msg-1", "the recorded answer"),
+            responses::ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+    test.cmd_with_server(&recording_server)
+        .arg("--skip-git-repo-check")
+        .arg("-c")
+        .arg("record_model_streams=true")
+        .arg(prompt)
+        .assert()
+        .code(0);
+
+    let recordings: Vec<_> =
+        std::fs::read_dir(test.home_path().join("recordings"))?.collect::<Result<_, _>>()?;
+    assert_eq!(recordings.len(), 1);
+    let recording = recordings[0].path();
+    assert!(recording.join("0001.request.json").exists());
+    assert!(recording.join("0001.sse").exists());
+
+    let replay_server = responses::start_mock_server().await;
+    let last_message = test.cwd_path().join("last-message.txt");
+    test.cmd_with_server(&replay_server)
+        .arg("--skip-git-repo-check")
+        .arg("--replay")
+        .arg(&recording)
+        .arg("--output-last-message")
+        .arg(&last_message)
+        .arg(prompt)
+        .assert()
+        .code(0);
+    assert_eq!(
+        std::fs::read_to_string(&last_message)?,
+        "the recorded answer"
+    );
+
+    // A prompt the recording never answered fails instead of getting the
+    // recorded response for a different request.
+    test.cmd_with_server(&replay_server)
+        .arg("--skip-git-repo-check")
+        .arg("--replay")
+        .arg(&recording)
+        .arg("a prompt that was never recorded")
+        .assert()
+        .code(1);
+
+    assert_eq!(responses_request_count(&replay_server).await, 0);
+    Ok(())
+}
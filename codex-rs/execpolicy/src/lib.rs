@@ -21,3 +21,4 @@ pub use policy::Policy;
 pub use rule::Rule;
 pub use rule::RuleMatch;
 pub use rule::RuleRef;
+pub use rule::RuleSource;
@@ -22,6 +22,7 @@ use crate::rule::PatternToken;
 use crate::rule::PrefixPattern;
 use crate::rule::PrefixRule;
 use crate::rule::RuleRef;
+use crate::rule::RuleSource;
 use crate::rule::validate_match_examples;
 use crate::rule::validate_not_match_examples;
 
@@ -237,6 +238,11 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
             .transpose()?
             .unwrap_or_default();
 
+        let source = eval.call_stack_top_location().map(|span| RuleSource {
+            path: span.filename().to_string(),
+            line: span.resolve_span().begin.line + 1,
+        });
+
         let mut builder = policy_builder(eval);
 
         let (first_token, remaining_tokens) = pattern_tokens
@@ -256,6 +262,7 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
                     },
                     decision,
                     justification: justification.clone(),
+                    source: source.clone(),
                 }) as RuleRef
             })
             .collect();
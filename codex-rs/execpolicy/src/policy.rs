@@ -6,6 +6,7 @@ use crate::rule::PrefixPattern;
 use crate::rule::PrefixRule;
 use crate::rule::RuleMatch;
 use crate::rule::RuleRef;
+use crate::rule::RuleSource;
 use multimap::MultiMap;
 use serde::Deserialize;
 use serde::Serialize;
@@ -71,6 +72,7 @@ impl Policy {
             },
             decision,
             justification: None,
+            source: None,
         });
 
         self.rules_by_program.insert(first_token.clone(), rule);
@@ -106,6 +108,19 @@ impl Policy {
         Evaluation::from_matches(matched_rules)
     }
 
+    /// Returns where the rule that produced `rule_match` was declared. When
+    /// several rules yield the same match, the first one added wins.
+    pub fn rule_source(&self, rule_match: &RuleMatch) -> Option<&RuleSource> {
+        let RuleMatch::PrefixRuleMatch { matched_prefix, .. } = rule_match else {
+            return None;
+        };
+        self.rules_by_program
+            .get_vec(matched_prefix.first()?)?
+            .iter()
+            .find(|rule| rule.matches(matched_prefix).as_ref() == Some(rule_match))
+            .and_then(|rule| rule.source())
+    }
+
     /// Returns matching rules for the given command. If no rules match and
     /// `heuristics_fallback` is provided, returns a single
     /// `HeuristicsRuleMatch` with the decision rendered by
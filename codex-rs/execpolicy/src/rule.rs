@@ -85,11 +85,21 @@ impl RuleMatch {
     }
 }
 
+/// Where a rule was declared, using the same 1-based line numbering as
+/// [`crate::ErrorLocation`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RuleSource {
+    pub path: String,
+    pub line: usize,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PrefixRule {
     pub pattern: PrefixPattern,
     pub decision: Decision,
     pub justification: Option<String>,
+    /// Set for rules parsed from a policy file; `None` for rules added in code.
+    pub source: Option<RuleSource>,
 }
 
 pub trait Rule: Any + Debug + Send + Sync {
@@ -97,6 +107,10 @@ pub trait Rule: Any + Debug + Send + Sync {
 
     fn matches(&self, cmd: &[String]) -> Option<RuleMatch>;
 
+    fn source(&self) -> Option<&RuleSource> {
+        None
+    }
+
     fn as_any(&self) -> &dyn Any;
 }
 
@@ -117,6 +131,10 @@ impl Rule for PrefixRule {
             })
     }
 
+    fn source(&self) -> Option<&RuleSource> {
+        self.source.as_ref()
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -11,6 +11,7 @@ use codex_execpolicy::Policy;
 use codex_execpolicy::PolicyParser;
 use codex_execpolicy::RuleMatch;
 use codex_execpolicy::RuleRef;
+use codex_execpolicy::RuleSource;
 use codex_execpolicy::blocking_append_allow_prefix_rule;
 use codex_execpolicy::rule::PatternToken;
 use codex_execpolicy::rule::PrefixPattern;
@@ -185,6 +186,7 @@ fn add_prefix_rule_extends_policy() -> Result<()> {
             },
             decision: Decision::Prompt,
             justification: None,
+            source: None,
         })],
         rules
     );
@@ -245,6 +247,10 @@ prefix_rule(
                 },
                 decision: Decision::Prompt,
                 justification: None,
+                source: Some(RuleSource {
+                    path: "first.rules".to_string(),
+                    line: 2,
+                }),
             }),
             RuleSnapshot::Prefix(PrefixRule {
                 pattern: PrefixPattern {
@@ -253,6 +259,10 @@ prefix_rule(
                 },
                 decision: Decision::Forbidden,
                 justification: None,
+                source: Some(RuleSource {
+                    path: "second.rules".to_string(),
+                    line: 2,
+                }),
             }),
         ],
         git_rules
@@ -319,6 +329,10 @@ prefix_rule(
             },
             decision: Decision::Allow,
             justification: None,
+            source: Some(RuleSource {
+                path: "test.rules".to_string(),
+                line: 2,
+            }),
         })],
         bash_rules
     );
@@ -330,6 +344,10 @@ prefix_rule(
             },
             decision: Decision::Allow,
             justification: None,
+            source: Some(RuleSource {
+                path: "test.rules".to_string(),
+                line: 2,
+            }),
         })],
         sh_rules
     );
@@ -389,6 +407,10 @@ prefix_rule(
             },
             decision: Decision::Allow,
             justification: None,
+            source: Some(RuleSource {
+                path: "test.rules".to_string(),
+                line: 2,
+            }),
         })],
         rules
     );
@@ -572,3 +594,47 @@ fn heuristics_match_is_returned_when_no_policy_matches() {
         evaluation
     );
 }
+
+#[test]
+fn rule_source_points_at_declaring_rule() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["git"],
+    decision = "prompt",
+)
+
+prefix_rule(
+    pattern = ["git", "push"],
+    decision = "forbidden",
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("team.rules", policy_src)?;
+    let policy = parser.build();
+
+    let evaluation = policy.check(&tokens(&["git", "push", "origin"]), &allow_all);
+    let sources: Vec<Option<&RuleSource>> = evaluation
+        .matched_rules
+        .iter()
+        .map(|rule_match| policy.rule_source(rule_match))
+        .collect();
+    assert_eq!(
+        vec![
+            Some(&RuleSource {
+                path: "team.rules".to_string(),
+                line: 2,
+            }),
+            Some(&RuleSource {
+                path: "team.rules".to_string(),
+                line: 7,
+            }),
+        ],
+        sources
+    );
+
+    let mut policy = Policy::empty();
+    policy.add_prefix_rule(&tokens(&["ls"]), Decision::Allow)?;
+    let evaluation = policy.check(&tokens(&["ls"]), &prompt_all);
+    assert_eq!(None, policy.rule_source(&evaluation.matched_rules[0]));
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 
 use codex_utils_absolute_path::AbsolutePathBuf;
@@ -73,4 +74,27 @@ pub enum OtelExporter {
         protocol: OtelHttpProtocol,
         tls: Option<OtelTlsConfig>,
     },
+    /// Serve metrics in the Prometheus text format on `http://<listen_addr>/metrics`.
+    ///
+    /// This is intended for metrics only.
+    Prometheus {
+        listen_addr: SocketAddr,
+    },
+    /// Append metrics to a local file, one JSON object per data point.
+    ///
+    /// This is intended for metrics only.
+    JsonLines {
+        path: AbsolutePathBuf,
+    },
+}
+
+impl OtelExporter {
+    /// Whether logs and traces are exported when this exporter is configured
+    /// for them. Metrics-only exporters disable those signals.
+    pub fn exports_logs_and_traces(&self) -> bool {
+        !matches!(
+            self,
+            OtelExporter::None | OtelExporter::Prometheus { .. } | OtelExporter::JsonLines { .. }
+        )
+    }
 }
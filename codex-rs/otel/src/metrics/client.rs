@@ -4,6 +4,8 @@ use crate::metrics::MetricsError;
 use crate::metrics::Result;
 use crate::metrics::config::MetricsConfig;
 use crate::metrics::config::MetricsExporter;
+use crate::metrics::json_lines::JsonLinesMetricExporter;
+use crate::metrics::prometheus::PrometheusServer;
 use crate::metrics::timer::Timer;
 use crate::metrics::validation::validate_metric_name;
 use crate::metrics::validation::validate_tag_key;
@@ -34,6 +36,7 @@ use opentelemetry_sdk::metrics::reader::MetricReader;
 use opentelemetry_semantic_conventions as semconv;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::Weak;
@@ -86,6 +89,7 @@ struct MetricsClientInner {
     histograms: Mutex<HashMap<String, Histogram<f64>>>,
     duration_histograms: Mutex<HashMap<String, Histogram<f64>>>,
     runtime_reader: Option<Arc<ManualReader>>,
+    prometheus: Option<PrometheusServer>,
     default_tags: BTreeMap<String, String>,
 }
 
@@ -172,9 +176,11 @@ impl MetricsClientInner {
         self.meter_provider
             .force_flush()
             .map_err(|source| MetricsError::ProviderShutdown { source })?;
-        self.meter_provider
-            .shutdown()
-            .map_err(|source| MetricsError::ProviderShutdown { source })?;
+        let result = self.meter_provider.shutdown();
+        if let Some(prometheus) = &self.prometheus {
+            prometheus.stop();
+        }
+        result.map_err(|source| MetricsError::ProviderShutdown { source })?;
         Ok(())
     }
 }
@@ -219,10 +225,26 @@ impl MetricsClient {
             )
         });
 
+        let mut prometheus = None;
         let (meter_provider, meter) = match exporter {
             MetricsExporter::InMemory(exporter) => {
                 build_provider(resource, exporter, export_interval, runtime_reader.clone())
             }
+            MetricsExporter::Otlp(OtelExporter::Prometheus { listen_addr }) => {
+                // Prometheus expects monotonically increasing counters, so this
+                // reader aggregates cumulatively and is collected on every scrape.
+                let reader = Arc::new(
+                    ManualReader::builder()
+                        .with_temporality(Temporality::Cumulative)
+                        .build(),
+                );
+                prometheus = Some(PrometheusServer::start(listen_addr, Arc::clone(&reader))?);
+                build_pull_provider(resource, reader, runtime_reader.clone())
+            }
+            MetricsExporter::Otlp(OtelExporter::JsonLines { path }) => {
+                let exporter = JsonLinesMetricExporter::open(path.as_path())?;
+                build_provider(resource, exporter, export_interval, runtime_reader.clone())
+            }
             MetricsExporter::Otlp(exporter) => {
                 let exporter = build_otlp_metric_exporter(exporter, Temporality::Delta)?;
                 build_provider(resource, exporter, export_interval, runtime_reader.clone())
@@ -236,6 +258,7 @@ impl MetricsClient {
             histograms: Mutex::new(HashMap::new()),
             duration_histograms: Mutex::new(HashMap::new()),
             runtime_reader,
+            prometheus,
             default_tags,
         })))
     }
@@ -284,6 +307,12 @@ impl MetricsClient {
         Ok(snapshot)
     }
 
+    /// Address the Prometheus `/metrics` endpoint is bound to, when that
+    /// exporter is configured. Useful when listening on port 0.
+    pub fn prometheus_addr(&self) -> Option<SocketAddr> {
+        self.0.prometheus.as_ref().map(PrometheusServer::local_addr)
+    }
+
     /// Flush metrics and stop the underlying OTEL meter provider.
     pub fn shutdown(&self) -> Result<()> {
         self.0.shutdown()
@@ -329,12 +358,33 @@ where
     (provider, meter)
 }
 
+fn build_pull_provider(
+    resource: Resource,
+    reader: Arc<ManualReader>,
+    runtime_reader: Option<Arc<ManualReader>>,
+) -> (SdkMeterProvider, Meter) {
+    let mut provider_builder = SdkMeterProvider::builder()
+        .with_resource(resource)
+        .with_reader(SharedManualReader::new(reader));
+    if let Some(reader) = runtime_reader {
+        provider_builder = provider_builder.with_reader(SharedManualReader::new(reader));
+    }
+    let provider = provider_builder.build();
+    let meter = provider.meter(METER_NAME);
+    (provider, meter)
+}
+
 fn build_otlp_metric_exporter(
     exporter: OtelExporter,
     temporality: Temporality,
 ) -> Result<opentelemetry_otlp::MetricExporter> {
     match exporter {
         OtelExporter::None => Err(MetricsError::ExporterDisabled),
+        OtelExporter::Prometheus { .. } | OtelExporter::JsonLines { .. } => {
+            Err(MetricsError::InvalidConfig {
+                message: "Prometheus and JSON-lines exporters are not OTLP exporters".to_string(),
+            })
+        }
         OtelExporter::Statsig => build_otlp_metric_exporter(
             crate::config::resolve_exporter(&OtelExporter::Statsig),
             temporality,
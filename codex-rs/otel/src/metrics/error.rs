@@ -1,3 +1,5 @@
+use std::net::SocketAddr;
+use std::path::PathBuf;
 use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, MetricsError>;
@@ -29,6 +31,20 @@ pub enum MetricsError {
     #[error("invalid OTLP metrics configuration: {message}")]
     InvalidConfig { message: String },
 
+    #[error("failed to serve Prometheus metrics on {addr}")]
+    PrometheusBind {
+        addr: SocketAddr,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("failed to open metrics file {}", path.display())]
+    JsonLinesOpen {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
     #[error("failed to flush or shutdown metrics provider")]
     ProviderShutdown {
         #[source]
@@ -0,0 +1,127 @@
+//! Push exporter that appends metrics to a local file as JSON lines.
+//!
+//! Each periodic export writes one line per data point, so the file can be
+//! tailed or shipped by an agent without running an OTLP collector.
+
+use crate::metrics::MetricsError;
+use crate::metrics::Result;
+use crate::metrics::points::SampleValue;
+use crate::metrics::points::metric_families;
+use chrono::SecondsFormat;
+use chrono::Utc;
+use opentelemetry_sdk::error::OTelSdkError;
+use opentelemetry_sdk::error::OTelSdkResult;
+use opentelemetry_sdk::metrics::Temporality;
+use opentelemetry_sdk::metrics::data::ResourceMetrics;
+use opentelemetry_sdk::metrics::exporter::PushMetricExporter;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+#[derive(Debug)]
+pub(crate) struct JsonLinesMetricExporter {
+    writer: Mutex<Option<BufWriter<File>>>,
+}
+
+#[derive(Serialize)]
+struct JsonLine<'a> {
+    timestamp: &'a str,
+    name: &'a str,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    unit: &'a str,
+    attributes: &'a BTreeMap<String, String>,
+    #[serde(flatten)]
+    value: &'a SampleValue,
+}
+
+impl JsonLinesMetricExporter {
+    /// Open `path` for appending, creating it (and its parent directory) when missing.
+    pub(crate) fn open(path: &Path) -> Result<Self> {
+        let open = || {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            OpenOptions::new().create(true).append(true).open(path)
+        };
+        let file = open().map_err(|source| MetricsError::JsonLinesOpen {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Ok(Self {
+            writer: Mutex::new(Some(BufWriter::new(file))),
+        })
+    }
+
+    fn write(&self, metrics: &ResourceMetrics) -> OTelSdkResult {
+        let timestamp = Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true);
+        let mut guard = self
+            .writer
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let Some(writer) = guard.as_mut() else {
+            return Err(OTelSdkError::AlreadyShutdown);
+        };
+        for family in metric_families(metrics) {
+            for sample in &family.samples {
+                let line = JsonLine {
+                    timestamp: &timestamp,
+                    name: &family.name,
+                    unit: &family.unit,
+                    attributes: &sample.attributes,
+                    value: &sample.value,
+                };
+                serde_json::to_writer(&mut *writer, &line)
+                    .map_err(|err| OTelSdkError::InternalFailure(err.to_string()))?;
+                writer
+                    .write_all(b"\n")
+                    .map_err(|err| OTelSdkError::InternalFailure(err.to_string()))?;
+            }
+        }
+        writer
+            .flush()
+            .map_err(|err| OTelSdkError::InternalFailure(err.to_string()))
+    }
+}
+
+impl PushMetricExporter for JsonLinesMetricExporter {
+    async fn export(&self, metrics: &ResourceMetrics) -> OTelSdkResult {
+        self.write(metrics)
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        let mut guard = self
+            .writer
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        match guard.as_mut() {
+            Some(writer) => writer
+                .flush()
+                .map_err(|err| OTelSdkError::InternalFailure(err.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    fn shutdown_with_timeout(&self, _timeout: Duration) -> OTelSdkResult {
+        let writer = self
+            .writer
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .take();
+        match writer {
+            Some(mut writer) => writer
+                .flush()
+                .map_err(|err| OTelSdkError::InternalFailure(err.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    fn temporality(&self) -> Temporality {
+        Temporality::Delta
+    }
+}
@@ -1,7 +1,10 @@
 mod client;
 mod config;
 mod error;
+mod json_lines;
 pub(crate) mod names;
+mod points;
+mod prometheus;
 pub(crate) mod runtime_metrics;
 pub(crate) mod timer;
 pub(crate) mod validation;
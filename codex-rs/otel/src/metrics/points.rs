@@ -0,0 +1,186 @@
+//! Flattening of OTEL metric snapshots into plain samples.
+//!
+//! The local exporters (Prometheus and JSON lines) do not speak OTLP, so they
+//! share this intermediate representation instead of walking the SDK data
+//! model themselves.
+
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::metrics::data::AggregatedMetrics;
+use opentelemetry_sdk::metrics::data::Metric;
+use opentelemetry_sdk::metrics::data::MetricData;
+use opentelemetry_sdk::metrics::data::ResourceMetrics;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// All samples recorded for one metric name.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct MetricFamily {
+    pub(crate) name: String,
+    pub(crate) description: String,
+    pub(crate) unit: String,
+    pub(crate) samples: Vec<Sample>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Sample {
+    pub(crate) attributes: BTreeMap<String, String>,
+    pub(crate) value: SampleValue,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub(crate) enum SampleValue {
+    Counter {
+        value: Number,
+    },
+    Gauge {
+        value: Number,
+    },
+    Histogram {
+        count: u64,
+        sum: f64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        min: Option<f64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        max: Option<f64>,
+        bounds: Vec<f64>,
+        bucket_counts: Vec<u64>,
+    },
+}
+
+/// A sample value that keeps integer metrics integral when rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(untagged)]
+pub(crate) enum Number {
+    U64(u64),
+    I64(i64),
+    F64(f64),
+}
+
+impl Number {
+    pub(crate) fn as_f64(self) -> f64 {
+        match self {
+            Number::U64(value) => value as f64,
+            Number::I64(value) => value as f64,
+            Number::F64(value) => value,
+        }
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::U64(value) => write!(f, "{value}"),
+            Number::I64(value) => write!(f, "{value}"),
+            Number::F64(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+trait IntoNumber: Copy {
+    fn into_number(self) -> Number;
+}
+
+impl IntoNumber for u64 {
+    fn into_number(self) -> Number {
+        Number::U64(self)
+    }
+}
+
+impl IntoNumber for i64 {
+    fn into_number(self) -> Number {
+        Number::I64(self)
+    }
+}
+
+impl IntoNumber for f64 {
+    fn into_number(self) -> Number {
+        Number::F64(self)
+    }
+}
+
+/// Group every data point in `snapshot` by metric name, sorted by name.
+pub(crate) fn metric_families(snapshot: &ResourceMetrics) -> Vec<MetricFamily> {
+    let mut families: BTreeMap<String, MetricFamily> = BTreeMap::new();
+    for metric in snapshot
+        .scope_metrics()
+        .flat_map(opentelemetry_sdk::metrics::data::ScopeMetrics::metrics)
+    {
+        let samples = match metric.data() {
+            AggregatedMetrics::U64(data) => samples(data),
+            AggregatedMetrics::I64(data) => samples(data),
+            AggregatedMetrics::F64(data) => samples(data),
+        };
+        if samples.is_empty() {
+            continue;
+        }
+        families
+            .entry(metric.name().to_string())
+            .or_insert_with(|| family(metric))
+            .samples
+            .extend(samples);
+    }
+    families.into_values().collect()
+}
+
+fn family(metric: &Metric) -> MetricFamily {
+    MetricFamily {
+        name: metric.name().to_string(),
+        description: metric.description().to_string(),
+        unit: metric.unit().to_string(),
+        samples: Vec::new(),
+    }
+}
+
+fn samples<T: IntoNumber>(data: &MetricData<T>) -> Vec<Sample> {
+    match data {
+        MetricData::Sum(sum) => {
+            let monotonic = sum.is_monotonic();
+            sum.data_points()
+                .map(|point| {
+                    let value = point.value().into_number();
+                    Sample {
+                        attributes: attributes(point.attributes()),
+                        value: if monotonic {
+                            SampleValue::Counter { value }
+                        } else {
+                            SampleValue::Gauge { value }
+                        },
+                    }
+                })
+                .collect()
+        }
+        MetricData::Gauge(gauge) => gauge
+            .data_points()
+            .map(|point| Sample {
+                attributes: attributes(point.attributes()),
+                value: SampleValue::Gauge {
+                    value: point.value().into_number(),
+                },
+            })
+            .collect(),
+        MetricData::Histogram(histogram) => histogram
+            .data_points()
+            .map(|point| Sample {
+                attributes: attributes(point.attributes()),
+                value: SampleValue::Histogram {
+                    count: point.count(),
+                    sum: point.sum().into_number().as_f64(),
+                    min: point.min().map(|min| min.into_number().as_f64()),
+                    max: point.max().map(|max| max.into_number().as_f64()),
+                    bounds: point.bounds().collect(),
+                    bucket_counts: point.bucket_counts().collect(),
+                },
+            })
+            .collect(),
+        // Codex does not record exponential histograms.
+        MetricData::ExponentialHistogram(_) => Vec::new(),
+    }
+}
+
+fn attributes<'a>(attributes: impl Iterator<Item = &'a KeyValue>) -> BTreeMap<String, String> {
+    attributes
+        .map(|kv| (kv.key.to_string(), kv.value.to_string()))
+        .collect()
+}
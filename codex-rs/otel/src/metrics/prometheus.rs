@@ -0,0 +1,318 @@
+//! Pull-based Prometheus exporter.
+//!
+//! Metrics are aggregated cumulatively by a [`ManualReader`] and rendered in
+//! the Prometheus text exposition format (0.0.4) whenever `/metrics` is
+//! scraped. The server is a minimal blocking HTTP/1.1 responder on a
+//! dedicated thread so it works regardless of whether the host process runs a
+//! tokio runtime.
+
+use crate::metrics::MetricsError;
+use crate::metrics::Result;
+use crate::metrics::points::MetricFamily;
+use crate::metrics::points::SampleValue;
+use crate::metrics::points::metric_families;
+use opentelemetry_sdk::metrics::ManualReader;
+use opentelemetry_sdk::metrics::data::ResourceMetrics;
+use opentelemetry_sdk::metrics::reader::MetricReader;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::Read;
+use std::io::Write;
+use std::net::Ipv4Addr;
+use std::net::Ipv6Addr;
+use std::net::SocketAddr;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tracing::debug;
+use tracing::warn;
+
+const METRICS_PATH: &str = "/metrics";
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+const MAX_REQUEST_HEAD_BYTES: usize = 8 * 1024;
+const REQUEST_READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Background HTTP server exposing `/metrics`.
+#[derive(Debug)]
+pub(crate) struct PrometheusServer {
+    local_addr: SocketAddr,
+    stopped: Arc<AtomicBool>,
+    handle: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl PrometheusServer {
+    pub(crate) fn start(listen_addr: SocketAddr, reader: Arc<ManualReader>) -> Result<Self> {
+        let listener =
+            TcpListener::bind(listen_addr).map_err(|source| MetricsError::PrometheusBind {
+                addr: listen_addr,
+                source,
+            })?;
+        let local_addr = listener
+            .local_addr()
+            .map_err(|source| MetricsError::PrometheusBind {
+                addr: listen_addr,
+                source,
+            })?;
+        debug!("Serving Prometheus metrics on http://{local_addr}{METRICS_PATH}");
+
+        let stopped = Arc::new(AtomicBool::new(false));
+        let thread_stopped = Arc::clone(&stopped);
+        let handle = std::thread::Builder::new()
+            .name("codex-prometheus".to_string())
+            .spawn(move || serve(listener, &reader, &thread_stopped))
+            .map_err(|source| MetricsError::PrometheusBind {
+                addr: listen_addr,
+                source,
+            })?;
+
+        Ok(Self {
+            local_addr,
+            stopped,
+            handle: Mutex::new(Some(handle)),
+        })
+    }
+
+    pub(crate) fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Stop accepting scrapes and wait for the server thread to exit.
+    pub(crate) fn stop(&self) {
+        let handle = self
+            .handle
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .take();
+        let Some(handle) = handle else {
+            return;
+        };
+        self.stopped.store(true, Ordering::SeqCst);
+        // Wake the blocking `accept` so the thread observes the flag.
+        let _ = TcpStream::connect(wake_addr(self.local_addr));
+        let _ = handle.join();
+    }
+}
+
+impl Drop for PrometheusServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn wake_addr(addr: SocketAddr) -> SocketAddr {
+    match addr {
+        SocketAddr::V4(v4) if v4.ip().is_unspecified() => {
+            SocketAddr::from((Ipv4Addr::LOCALHOST, v4.port()))
+        }
+        SocketAddr::V6(v6) if v6.ip().is_unspecified() => {
+            SocketAddr::from((Ipv6Addr::LOCALHOST, v6.port()))
+        }
+        addr => addr,
+    }
+}
+
+fn serve(listener: TcpListener, reader: &ManualReader, stopped: &AtomicBool) {
+    for stream in listener.incoming() {
+        if stopped.load(Ordering::SeqCst) {
+            break;
+        }
+        match stream {
+            Ok(stream) => {
+                if let Err(err) = handle_connection(stream, reader) {
+                    debug!("Prometheus scrape failed: {err}");
+                }
+            }
+            Err(err) => warn!("Prometheus listener accept failed: {err}"),
+        }
+    }
+}
+
+fn handle_connection(mut stream: TcpStream, reader: &ManualReader) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(REQUEST_READ_TIMEOUT))?;
+    let head = read_request_head(&mut stream)?;
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let target = request_line.next().unwrap_or_default();
+    let path = target.split('?').next().unwrap_or_default();
+
+    let (status, content_type, body) = if method != "GET" {
+        ("405 Method Not Allowed", "text/plain", String::new())
+    } else if path != METRICS_PATH {
+        ("404 Not Found", "text/plain", String::new())
+    } else {
+        let mut snapshot = ResourceMetrics::default();
+        match reader.collect(&mut snapshot) {
+            Ok(()) => ("200 OK", CONTENT_TYPE, render(&snapshot)),
+            Err(err) => (
+                "503 Service Unavailable",
+                "text/plain",
+                format!("failed to collect metrics: {err}\n"),
+            ),
+        }
+    };
+
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes())?;
+    stream.flush()
+}
+
+fn read_request_head(stream: &mut TcpStream) -> std::io::Result<String> {
+    let mut head = Vec::new();
+    let mut buf = [0_u8; 1024];
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read = stream.read(&mut buf)?;
+        if read == 0 {
+            break;
+        }
+        head.extend_from_slice(&buf[..read]);
+        if head.len() > MAX_REQUEST_HEAD_BYTES {
+            break;
+        }
+    }
+    Ok(String::from_utf8_lossy(&head).into_owned())
+}
+
+/// Render a snapshot in the Prometheus text exposition format.
+pub(crate) fn render(snapshot: &ResourceMetrics) -> String {
+    let mut out = String::new();
+    for family in metric_families(snapshot) {
+        render_family(&mut out, &family);
+    }
+    out
+}
+
+fn render_family(out: &mut String, family: &MetricFamily) {
+    let base = sanitize_metric_name(&family.name);
+    let Some(first) = family.samples.first() else {
+        return;
+    };
+    let (name, kind) = match first.value {
+        SampleValue::Counter { .. } => (format!("{base}_total"), "counter"),
+        SampleValue::Gauge { .. } => (base, "gauge"),
+        SampleValue::Histogram { .. } => (base, "histogram"),
+    };
+    if !family.description.is_empty() {
+        let _ = writeln!(out, "# HELP {name} {}", escape_help(&family.description));
+    }
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+
+    for sample in &family.samples {
+        match &sample.value {
+            SampleValue::Counter { value } | SampleValue::Gauge { value } => {
+                let labels = format_labels(&sample.attributes, None);
+                let _ = writeln!(out, "{name}{labels} {value}");
+            }
+            SampleValue::Histogram {
+                count,
+                sum,
+                bounds,
+                bucket_counts,
+                ..
+            } => {
+                let mut cumulative = 0_u64;
+                for (bound, bucket) in bounds.iter().zip(bucket_counts) {
+                    cumulative = cumulative.saturating_add(*bucket);
+                    let labels = format_labels(&sample.attributes, Some(&bound.to_string()));
+                    let _ = writeln!(out, "{name}_bucket{labels} {cumulative}");
+                }
+                let labels_inf = format_labels(&sample.attributes, Some("+Inf"));
+                let _ = writeln!(out, "{name}_bucket{labels_inf} {count}");
+                let labels = format_labels(&sample.attributes, None);
+                let _ = writeln!(out, "{name}_sum{labels} {sum}");
+                let _ = writeln!(out, "{name}_count{labels} {count}");
+            }
+        }
+    }
+}
+
+fn format_labels(attributes: &BTreeMap<String, String>, le: Option<&str>) -> String {
+    let mut pairs: Vec<String> = attributes
+        .iter()
+        .map(|(key, value)| {
+            format!(
+                "{}=\"{}\"",
+                sanitize_label_name(key),
+                escape_label_value(value)
+            )
+        })
+        .collect();
+    if let Some(le) = le {
+        pairs.push(format!("le=\"{le}\""));
+    }
+    if pairs.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", pairs.join(","))
+    }
+}
+
+/// Map an OTEL metric name (e.g. `codex.tool.call`) onto the Prometheus
+/// character set (`codex_tool_call`).
+pub(crate) fn sanitize_metric_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == ':' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if sanitized.starts_with(|c: char| c.is_ascii_digit()) {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+fn sanitize_label_name(name: &str) -> String {
+    sanitize_metric_name(name).replace(':', "_")
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn escape_help(help: &str) -> String {
+    help.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn sanitizes_metric_names() {
+        assert_eq!(sanitize_metric_name("codex.tool.call"), "codex_tool_call");
+        assert_eq!(
+            sanitize_metric_name("codex.api_request.duration_ms"),
+            "codex_api_request_duration_ms"
+        );
+        assert_eq!(sanitize_metric_name("9lives"), "_9lives");
+    }
+
+    #[test]
+    fn renders_labels_with_escaping() {
+        let attributes = BTreeMap::from([
+            ("tool".to_string(), "shell".to_string()),
+            ("error.kind".to_string(), "say \"hi\"".to_string()),
+        ]);
+        assert_eq!(
+            format_labels(&attributes, Some("5")),
+            r#"{error_kind="say \"hi\"",tool="shell",le="5"}"#
+        );
+        assert_eq!(format_labels(&BTreeMap::new(), None), "");
+    }
+}
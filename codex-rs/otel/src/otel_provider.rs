@@ -68,8 +68,10 @@ impl OtelProvider {
     }
 
     pub fn from(settings: &OtelSettings) -> Result<Option<Self>, Box<dyn Error>> {
-        let log_enabled = !matches!(settings.exporter, OtelExporter::None);
-        let trace_enabled = !matches!(settings.trace_exporter, OtelExporter::None);
+        warn_if_metrics_only(&settings.exporter, "logs");
+        warn_if_metrics_only(&settings.trace_exporter, "traces");
+        let log_enabled = settings.exporter.exports_logs_and_traces();
+        let trace_enabled = settings.trace_exporter.exports_logs_and_traces();
 
         let metric_exporter = crate::config::resolve_exporter(&settings.metrics_exporter);
         let metrics = if matches!(metric_exporter, OtelExporter::None) {
@@ -220,6 +222,17 @@ fn extract_traceparent_context(traceparent: String, tracestate: Option<String>)
     Some(context)
 }
 
+fn warn_if_metrics_only(exporter: &OtelExporter, signal: &str) {
+    if matches!(
+        exporter,
+        OtelExporter::Prometheus { .. } | OtelExporter::JsonLines { .. }
+    ) {
+        warn!(
+            "Prometheus and JSON-lines exporters only support metrics; {signal} are not exported"
+        );
+    }
+}
+
 fn make_resource(settings: &OtelSettings) -> Resource {
     Resource::builder()
         .with_service_name(settings.service_name.clone())
@@ -240,7 +253,9 @@ fn build_logger(
     let mut builder = SdkLoggerProvider::builder().with_resource(resource.clone());
 
     match crate::config::resolve_exporter(exporter) {
-        OtelExporter::None => return Ok(builder.build()),
+        OtelExporter::None | OtelExporter::Prometheus { .. } | OtelExporter::JsonLines { .. } => {
+            return Ok(builder.build());
+        }
         OtelExporter::Statsig => unreachable!("statsig exporter should be resolved"),
         OtelExporter::OtlpGrpc {
             endpoint,
@@ -307,7 +322,9 @@ fn build_tracer_provider(
     exporter: &OtelExporter,
 ) -> Result<SdkTracerProvider, Box<dyn Error>> {
     let span_exporter = match crate::config::resolve_exporter(exporter) {
-        OtelExporter::None => return Ok(SdkTracerProvider::builder().build()),
+        OtelExporter::None | OtelExporter::Prometheus { .. } | OtelExporter::JsonLines { .. } => {
+            return Ok(SdkTracerProvider::builder().build());
+        }
         OtelExporter::Statsig => unreachable!("statsig exporter should be resolved"),
         OtelExporter::OtlpGrpc {
             endpoint,
@@ -0,0 +1,134 @@
+use codex_otel::config::OtelExporter;
+use codex_otel::metrics::MetricsClient;
+use codex_otel::metrics::MetricsConfig;
+use codex_otel::metrics::Result;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use std::io::Read;
+use std::io::Write;
+use std::net::SocketAddr;
+use std::net::TcpStream;
+use std::time::Duration;
+
+fn scrape(addr: SocketAddr, path: &str) -> String {
+    let mut stream = TcpStream::connect(addr).expect("connect to metrics endpoint");
+    write!(stream, "GET {path} HTTP/1.1\r\nHost: {addr}\r\n\r\n").expect("send request");
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .expect("read metrics response");
+    response
+}
+
+#[test]
+fn prometheus_exporter_serves_metrics_endpoint() -> Result<()> {
+    let config = MetricsConfig::otlp(
+        "test",
+        "codex-cli",
+        env!("CARGO_PKG_VERSION"),
+        OtelExporter::Prometheus {
+            listen_addr: "127.0.0.1:0".parse().expect("valid socket address"),
+        },
+    );
+    let metrics = MetricsClient::new(config)?;
+    let addr = metrics
+        .prometheus_addr()
+        .expect("prometheus endpoint should be bound");
+
+    metrics.counter(
+        "codex.tool.call",
+        1,
+        &[("tool", "shell"), ("success", "true")],
+    )?;
+    metrics.counter(
+        "codex.tool.call",
+        2,
+        &[("tool", "shell"), ("success", "true")],
+    )?;
+    metrics.record_duration(
+        "codex.tool.call.duration_ms",
+        Duration::from_millis(40),
+        &[("tool", "shell")],
+    )?;
+
+    let response = scrape(addr, "/metrics");
+    assert!(
+        response.starts_with("HTTP/1.1 200 OK"),
+        "unexpected response: {response}"
+    );
+    let body = response
+        .split_once("\r\n\r\n")
+        .map(|(_, body)| body)
+        .unwrap_or_default();
+    for expected in [
+        "# TYPE codex_tool_call_total counter",
+        "codex_tool_call_total{success=\"true\",tool=\"shell\"} 3",
+        "# TYPE codex_tool_call_duration_ms histogram",
+        "codex_tool_call_duration_ms_bucket{tool=\"shell\",le=\"+Inf\"} 1",
+        "codex_tool_call_duration_ms_sum{tool=\"shell\"} 40",
+        "codex_tool_call_duration_ms_count{tool=\"shell\"} 1",
+    ] {
+        assert!(
+            body.lines().any(|line| line == expected),
+            "missing `{expected}` in:\n{body}"
+        );
+    }
+
+    // Counters stay cumulative across scrapes.
+    let again = scrape(addr, "/metrics");
+    assert!(again.contains("codex_tool_call_total{success=\"true\",tool=\"shell\"} 3"));
+
+    assert!(scrape(addr, "/other").starts_with("HTTP/1.1 404 Not Found"));
+
+    metrics.shutdown()?;
+    assert!(
+        TcpStream::connect(addr).is_err(),
+        "endpoint should stop listening after shutdown"
+    );
+    Ok(())
+}
+
+#[test]
+fn json_lines_exporter_appends_one_line_per_data_point() -> Result<()> {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = AbsolutePathBuf::from_absolute_path(dir.path().join("metrics/codex.jsonl"))
+        .expect("absolute path");
+    let config = MetricsConfig::otlp(
+        "test",
+        "codex-cli",
+        env!("CARGO_PKG_VERSION"),
+        OtelExporter::JsonLines { path: path.clone() },
+    );
+    let metrics = MetricsClient::new(config)?;
+
+    metrics.counter("codex.api_request", 2, &[("status", "200")])?;
+    metrics.record_duration(
+        "codex.api_request.duration_ms",
+        Duration::from_millis(120),
+        &[("status", "200")],
+    )?;
+    metrics.shutdown()?;
+
+    let contents = std::fs::read_to_string(path.as_path()).expect("read metrics file");
+    let lines: Vec<Value> = contents
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("valid json line"))
+        .collect();
+    assert_eq!(lines.len(), 2, "unexpected lines: {contents}");
+
+    let counter = &lines[0];
+    assert_eq!(counter["name"], "codex.api_request");
+    assert_eq!(counter["kind"], "counter");
+    assert_eq!(counter["value"], 2);
+    assert_eq!(counter["attributes"]["status"], "200");
+    assert!(counter["timestamp"].is_string());
+
+    let histogram = &lines[1];
+    assert_eq!(histogram["name"], "codex.api_request.duration_ms");
+    assert_eq!(histogram["kind"], "histogram");
+    assert_eq!(histogram["unit"], "ms");
+    assert_eq!(histogram["count"], 1);
+    assert_eq!(histogram["sum"], 120.0);
+    Ok(())
+}
@@ -1,3 +1,4 @@
+mod local_exporters;
 mod manager_metrics;
 mod otlp_http_loopback;
 mod runtime_summary;
@@ -3,6 +3,7 @@ use std::path::PathBuf;
 
 use crate::mcp::RequestId;
 use crate::parse_command::ParsedCommand;
+use crate::protocol::AskForApproval;
 use crate::protocol::FileChange;
 use schemars::JsonSchema;
 use serde::Deserialize;
@@ -37,6 +38,46 @@ impl From<Vec<String>> for ExecPolicyAmendment {
     }
 }
 
+/// What decided whether a command runs without asking, needs approval, or is
+/// rejected.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DecisionProvenance {
+    /// The command is on the built-in list of read-only commands.
+    KnownSafeCommand,
+    /// The command was flagged as potentially dangerous.
+    DangerousCommand,
+    /// An execpolicy `prefix_rule` matched the command.
+    ExecPolicyRule {
+        matched_prefix: Vec<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional)]
+        justification: Option<String>,
+        /// Rules file that declared the rule. Unset for rules that did not
+        /// come from a file, such as those from requirements.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional)]
+        file: Option<String>,
+        /// 1-based line of the `prefix_rule(...)` call in `file`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional)]
+        line: Option<usize>,
+    },
+    /// The user already approved this command for the rest of the session.
+    SessionApproval,
+    /// The command was denied by the sandbox and is being retried without it.
+    SandboxDenialRetry,
+    /// No rule or heuristic matched, so the approval policy decided.
+    ApprovalPolicy { approval_policy: AskForApproval },
+    /// A tool declared in config decided with its own `approval` setting, in place of the
+    /// approval policy.
+    ToolConfig {
+        tool: String,
+        /// True for `approval = "always"`, false for `approval = "never"`.
+        always_ask: bool,
+    },
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ExecApprovalRequestEvent {
     /// Identifier for the associated exec call, if available.
@@ -57,6 +98,10 @@ pub struct ExecApprovalRequestEvent {
     #[ts(optional)]
     pub proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
     pub parsed_cmd: Vec<ParsedCommand>,
+    /// What caused this approval request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub provenance: Option<DecisionProvenance>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -25,6 +25,41 @@ pub enum ParsedCommand {
         query: Option<String>,
         path: Option<String>,
     },
+    Write {
+        cmd: String,
+        /// (Best effort) Paths created or modified by the command, including
+        /// output redirection targets. Relative paths resolve against the `cwd`.
+        paths: Vec<String>,
+    },
+    Delete {
+        cmd: String,
+        /// (Best effort) Paths removed by the command. Relative paths resolve
+        /// against the `cwd`.
+        paths: Vec<String>,
+    },
+    Install {
+        cmd: String,
+        /// Packages named on the command line. Empty when installing from a
+        /// manifest or lockfile.
+        packages: Vec<String>,
+    },
+    Build {
+        cmd: String,
+    },
+    Test {
+        cmd: String,
+    },
+    Git {
+        cmd: String,
+        /// The git subcommand, e.g. `commit` or `status`. `git grep` and
+        /// `git ls-files` are summarized as `search` and `list_files` instead.
+        subcommand: String,
+    },
+    Fetch {
+        cmd: String,
+        /// The URL being requested, when one appears on the command line.
+        url: Option<String>,
+    },
     Unknown {
         cmd: String,
     },
@@ -51,6 +51,7 @@ use tracing::error;
 use ts_rs::TS;
 
 pub use crate::approvals::ApplyPatchApprovalRequestEvent;
+pub use crate::approvals::DecisionProvenance;
 pub use crate::approvals::ElicitationAction;
 pub use crate::approvals::ExecApprovalRequestEvent;
 pub use crate::approvals::ExecPolicyAmendment;
@@ -95,6 +96,10 @@ pub enum Op {
     /// Terminate all running background terminal processes for this thread.
     CleanBackgroundTerminals,
 
+    /// Re-read the approvals remembered for this repository, for example after
+    /// some were revoked outside the session.
+    ReloadRememberedApprovals,
+
     /// Legacy user input.
     ///
     /// Prefer [`Op::UserTurn`] so the caller provides full turn context
@@ -308,6 +313,24 @@ pub enum Op {
     /// Request Codex to undo a turn (turn are stacked so it is the same effect as CMD + Z).
     Undo,
 
+    /// Request the ghost snapshots recorded in this session, oldest first.
+    /// Codex replies with an `EventMsg::SnapshotTimeline` event.
+    ListSnapshots,
+
+    /// Restore the working tree to a ghost snapshot listed in the snapshot
+    /// timeline. When `paths` is non-empty, only those files (relative to the
+    /// session cwd) are restored. Conversation history is left unchanged, and
+    /// the replaced state can be brought back with [`Op::Redo`].
+    RestoreSnapshot {
+        snapshot_id: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        paths: Vec<PathBuf>,
+    },
+
+    /// Reapply the working tree state replaced by the most recent undo or
+    /// snapshot restore.
+    Redo,
+
     /// Request Codex to drop the last N user turns from in-memory context.
     ///
     /// This does not attempt to revert local filesystem changes. Clients are
@@ -317,6 +340,14 @@ pub enum Op {
     /// Request a code review from the agent.
     Review { review_request: ReviewRequest },
 
+    /// Record that the user accepted or dismissed a review finding. The
+    /// decision is persisted in the rollout, and later reviews in the
+    /// thread do not report dismissed findings again.
+    SetReviewFindingStatus {
+        finding: ReviewFinding,
+        status: ReviewFindingStatus,
+    },
+
     /// Request to shut down codex instance.
     Shutdown,
 
@@ -974,10 +1005,16 @@ pub enum EventMsg {
 
     UndoCompleted(UndoCompletedEvent),
 
+    /// Ghost snapshots of the session, sent in response to `Op::ListSnapshots`.
+    SnapshotTimeline(SnapshotTimelineEvent),
+
     /// Notification that a model stream experienced an error or disconnect
     /// and the system is handling it (e.g., retrying with backoff).
     StreamError(StreamErrorEvent),
 
+    /// The session switched to another provider in its failover chain.
+    ProviderFailover(ProviderFailoverEvent),
+
     /// Notification that the agent is about to apply a code patch. Mirrors
     /// `ExecCommandBegin` so front‑ends can show progress indicators.
     PatchApplyBegin(PatchApplyBeginEvent),
@@ -1024,6 +1061,9 @@ pub enum EventMsg {
     /// Exited review mode with an optional final result to apply.
     ExitedReviewMode(ExitedReviewModeEvent),
 
+    /// A user accepted or dismissed a review finding.
+    ReviewFindingStatus(ReviewFindingStatusEvent),
+
     RawResponseItem(RawResponseItemEvent),
 
     ItemStarted(ItemStartedEvent),
@@ -1293,6 +1333,24 @@ impl HasLegacyEvent for EventMsg {
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ExitedReviewModeEvent {
     pub review_output: Option<ReviewOutputEvent>,
+    /// Ghost commit capturing the working tree the review looked at.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub snapshot_sha: Option<String>,
+}
+
+/// Decision a user recorded for a single review finding.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewFindingStatus {
+    Accepted,
+    Dismissed,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ReviewFindingStatusEvent {
+    pub finding: ReviewFinding,
+    pub status: ReviewFindingStatus,
 }
 
 // Individual event payload types matching each `EventMsg` variant.
@@ -1930,6 +1988,9 @@ pub struct TurnContextItem {
     pub final_output_json_schema: Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub truncation_policy: Option<TruncationPolicy>,
+    /// `[[model_routes]]` rule that selected `model` and `effort`, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_route: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
@@ -1991,6 +2052,30 @@ pub enum ReviewTarget {
     #[serde(rename_all = "camelCase")]
     #[ts(rename_all = "camelCase")]
     Custom { instructions: String },
+
+    /// Review the combined changes of a commit range (`git diff base..head`).
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    CommitRange { base: String, head: String },
+
+    /// Review a patch that is not necessarily applied to the working tree.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    Patch {
+        diff: String,
+        /// Where the patch came from (e.g., a file name), for UIs.
+        label: Option<String>,
+    },
+
+    /// Review only what changed since an earlier review, without repeating its findings.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    Incremental {
+        /// Snapshot of the working tree taken when the earlier review ran.
+        since_sha: String,
+        /// Findings already reported by the earlier review(s).
+        previous_findings: Vec<ReviewFinding>,
+    },
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
@@ -2119,6 +2204,10 @@ pub struct ExecCommandEndEvent {
     pub duration: Duration,
     /// Formatted output from the command, as seen by the model.
     pub formatted_output: String,
+    /// What allowed the command to run, when it went through approval.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub provenance: Option<DecisionProvenance>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -2188,6 +2277,38 @@ pub struct UndoCompletedEvent {
     pub message: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct SnapshotTimelineEvent {
+    /// Snapshots in the order they were captured, oldest first.
+    pub entries: Vec<SnapshotTimelineEntry>,
+    /// Whether `Op::Redo` has a replaced working tree state to reapply.
+    pub redo_available: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct SnapshotTimelineEntry {
+    /// Identifier to pass to `Op::RestoreSnapshot`.
+    pub snapshot_id: String,
+    /// 1-based position of the turn that captured the snapshot.
+    pub turn: u32,
+    /// The user message that started the turn, when it is still in history.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// Files changed between this snapshot and the next one, or the current
+    /// working tree for the latest snapshot.
+    pub files: Vec<SnapshotFileStat>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct SnapshotFileStat {
+    /// Path relative to the session cwd.
+    pub path: PathBuf,
+    /// Lines added, or `None` for binary files.
+    pub added: Option<u64>,
+    /// Lines removed, or `None` for binary files.
+    pub removed: Option<u64>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ThreadRolledBackEvent {
     /// Number of user turns that were removed from context.
@@ -2206,6 +2327,34 @@ pub struct StreamErrorEvent {
     pub additional_details: Option<String>,
 }
 
+/// Provider failure that moves a session to the next entry of its profile's
+/// failover chain.
+#[derive(
+    Debug, Clone, Copy, Display, Deserialize, Serialize, PartialEq, Eq, Hash, JsonSchema, TS,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum FailoverTrigger {
+    RateLimit,
+    ServerError,
+    StreamIdleTimeout,
+    ContextWindowExceeded,
+}
+
+/// Recorded whenever a session changes provider (and possibly model) through
+/// its failover chain. `trigger` is `None` when the session returns to an
+/// earlier entry whose cool-down has expired; `cooldown_secs` is how long
+/// `from_provider` is skipped after a failure.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ProviderFailoverEvent {
+    pub from_provider: String,
+    pub from_model: String,
+    pub to_provider: String,
+    pub to_model: String,
+    pub trigger: Option<FailoverTrigger>,
+    pub cooldown_secs: Option<u64>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct StreamInfoEvent {
     pub message: String,
@@ -2246,7 +2395,12 @@ pub struct PatchApplyEndEvent {
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct TurnDiffEvent {
+    /// Changes made by the agent during the turn.
     pub unified_diff: String,
+    /// Changes made outside Codex to files the agent touched during the turn.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub external_unified_diff: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -2535,6 +2689,13 @@ pub enum ReviewDecision {
     #[default]
     Denied,
 
+    /// User has denied this command and wants future commands that start with
+    /// the proposed execpolicy amendment's prefix to be refused without asking
+    /// in the current repository.
+    DeniedExecpolicyAmendment {
+        proposed_execpolicy_amendment: ExecPolicyAmendment,
+    },
+
     /// User has denied this command and the agent should not do anything until
     /// the user's next command.
     Abort,
@@ -2549,6 +2710,7 @@ impl ReviewDecision {
             ReviewDecision::ApprovedExecpolicyAmendment { .. } => "approved_with_amendment",
             ReviewDecision::ApprovedForSession => "approved_for_session",
             ReviewDecision::Denied => "denied",
+            ReviewDecision::DeniedExecpolicyAmendment { .. } => "denied_with_amendment",
             ReviewDecision::Abort => "abort",
         }
     }
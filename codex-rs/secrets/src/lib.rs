@@ -13,11 +13,26 @@ use sha2::Digest;
 use sha2::Sha256;
 
 mod local;
+mod redact;
 
 pub use local::LocalSecretsBackend;
+pub use redact::redact_secret_values;
+pub use redact::register_secret_value;
 
 const KEYRING_SERVICE: &str = "codex";
 
+/// Prefix marking a config value as a reference to a stored secret, e.g.
+/// `NPM_TOKEN = "secret:NPM_TOKEN"`.
+pub const SECRET_REFERENCE_PREFIX: &str = "secret:";
+
+/// Returns the referenced secret name when `value` is a `secret:NAME`
+/// reference, or `None` for ordinary values.
+pub fn parse_secret_reference(value: &str) -> Option<Result<SecretName>> {
+    value
+        .strip_prefix(SECRET_REFERENCE_PREFIX)
+        .map(SecretName::new)
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct SecretName(String);
 
@@ -134,6 +149,16 @@ impl SecretsManager {
     pub fn list(&self, scope_filter: Option<&SecretScope>) -> Result<Vec<SecretListEntry>> {
         self.backend.list(scope_filter)
     }
+
+    /// Look `name` up for the environment `cwd` belongs to, falling back to
+    /// the global scope.
+    pub fn resolve(&self, name: &SecretName, cwd: &Path) -> Result<Option<String>> {
+        let scope = SecretScope::environment(environment_id_from_cwd(cwd))?;
+        if let Some(value) = self.get(&scope, name)? {
+            return Ok(Some(value));
+        }
+        self.get(&SecretScope::Global, name)
+    }
 }
 
 pub fn environment_id_from_cwd(cwd: &Path) -> String {
@@ -240,4 +265,44 @@ mod tests {
         assert_eq!(manager.get(&scope, &name)?, None);
         Ok(())
     }
+
+    #[test]
+    fn resolve_prefers_environment_scope() -> Result<()> {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let cwd = tempfile::tempdir().expect("tempdir");
+        let manager = SecretsManager::new_with_keyring_store(
+            codex_home.path().to_path_buf(),
+            SecretsBackendKind::Local,
+            Arc::new(MockKeyringStore::default()),
+        );
+        let name = SecretName::new("NPM_TOKEN")?;
+        assert_eq!(manager.resolve(&name, cwd.path())?, None);
+
+        manager.set(&SecretScope::Global, &name, "global-token")?;
+        assert_eq!(
+            manager.resolve(&name, cwd.path())?,
+            Some("global-token".to_string())
+        );
+
+        let env_scope = SecretScope::environment(environment_id_from_cwd(cwd.path()))?;
+        manager.set(&env_scope, &name, "env-token")?;
+        assert_eq!(
+            manager.resolve(&name, cwd.path())?,
+            Some("env-token".to_string())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parses_secret_references() {
+        assert!(parse_secret_reference("plain-value").is_none());
+        assert_eq!(
+            parse_secret_reference("secret:NPM_TOKEN").and_then(Result::ok),
+            Some(SecretName("NPM_TOKEN".to_string()))
+        );
+        assert!(matches!(
+            parse_secret_reference("secret:npm-token"),
+            Some(Err(_))
+        ));
+    }
 }
@@ -0,0 +1,95 @@
+//! Process-wide registry of resolved secret values.
+//!
+//! Whenever a `secret:NAME` reference is resolved, its value is registered
+//! here so that any later text headed for the model or for disk can be
+//! scrubbed with [`redact_secret_values`].
+
+use std::borrow::Cow;
+use std::sync::LazyLock;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+
+use crate::SecretName;
+
+/// Values shorter than this are not redacted; replacing them would mangle
+/// ordinary output far more often than it would hide a real credential.
+const MIN_REDACTED_LEN: usize = 4;
+
+struct RegisteredValue {
+    value: String,
+    replacement: String,
+}
+
+static REGISTERED: LazyLock<RwLock<Vec<RegisteredValue>>> =
+    LazyLock::new(|| RwLock::new(Vec::new()));
+
+/// Remember `value` so that [`redact_secret_values`] replaces it with
+/// `[REDACTED:NAME]`. The JSON-escaped form is registered as well so values
+/// containing quotes or backslashes are also caught in serialized output.
+pub fn register_secret_value(name: &SecretName, value: &str) {
+    if value.len() < MIN_REDACTED_LEN {
+        return;
+    }
+    let replacement = format!("[REDACTED:{name}]");
+    let mut variants = vec![value.to_string()];
+    if let Ok(quoted) = serde_json::to_string(value)
+        && let Some(escaped) = quoted
+            .strip_prefix('"')
+            .and_then(|quoted| quoted.strip_suffix('"'))
+        && escaped != value
+    {
+        variants.push(escaped.to_string());
+    }
+
+    let mut registered = REGISTERED.write().unwrap_or_else(PoisonError::into_inner);
+    for value in variants {
+        if registered.iter().any(|existing| existing.value == value) {
+            continue;
+        }
+        registered.push(RegisteredValue {
+            value,
+            replacement: replacement.clone(),
+        });
+    }
+    // Longest first, so a secret that contains another is replaced whole.
+    registered.sort_by(|a, b| b.value.len().cmp(&a.value.len()));
+}
+
+/// Replace every registered secret value in `text` with `[REDACTED:NAME]`.
+pub fn redact_secret_values(text: &str) -> Cow<'_, str> {
+    let registered = REGISTERED.read().unwrap_or_else(PoisonError::into_inner);
+    let mut redacted = Cow::Borrowed(text);
+    for entry in registered.iter() {
+        if redacted.contains(entry.value.as_str()) {
+            redacted = Cow::Owned(redacted.replace(entry.value.as_str(), &entry.replacement));
+        }
+    }
+    redacted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn registered_values_are_replaced_with_their_name() {
+        let name = SecretName::new("REDACT_TEST_TOKEN").expect("valid name");
+        register_secret_value(&name, "tok-\"quoted\"-0451");
+        let short = SecretName::new("REDACT_TEST_SHORT").expect("valid name");
+        register_secret_value(&short, "ab");
+
+        assert_eq!(
+            redact_secret_values("token=tok-\"quoted\"-0451 ab"),
+            "token=[REDACTED:REDACT_TEST_TOKEN] ab"
+        );
+        assert_eq!(
+            redact_secret_values(r#"{"output":"tok-\"quoted\"-0451"}"#),
+            r#"{"output":"[REDACTED:REDACT_TEST_TOKEN]"}"#
+        );
+        assert!(matches!(
+            redact_secret_values("nothing to hide"),
+            Cow::Borrowed(_)
+        ));
+    }
+}
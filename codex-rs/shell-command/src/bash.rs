@@ -51,6 +51,122 @@ pub fn try_parse_word_only_commands_sequence(tree: &Tree, src: &str) -> Option<V
     // Allow only safe punctuation / operator tokens; anything else causes reject.
     const ALLOWED_PUNCT_TOKENS: &[&str] = &["&&", "||", ";", "|", "\"", "'"];
 
+    let command_nodes = collect_command_nodes(tree, ALLOWED_KINDS, ALLOWED_PUNCT_TOKENS)?;
+    let mut commands = Vec::new();
+    for node in command_nodes {
+        if let Some(words) = parse_plain_command_from_node(node, src) {
+            commands.push(words);
+        } else {
+            return None;
+        }
+    }
+    Some(commands)
+}
+
+/// A plain command from a shell script together with the files its output is
+/// redirected into.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedirectedCommand {
+    pub words: Vec<String>,
+    /// Destinations of output redirections (`>`, `>>`, `&>`, `>|`). File
+    /// descriptor duplications such as `2>&1` and `/dev/null` are omitted.
+    pub write_targets: Vec<String>,
+    /// Sources of input redirections (`<`). Here-documents are omitted.
+    pub read_sources: Vec<String>,
+    /// The command and its redirections as written, without here-document
+    /// bodies.
+    pub text: String,
+}
+
+/// Like [`try_parse_word_only_commands_sequence`], but also accepts file
+/// redirections and here-documents attached to the commands and reports the
+/// files each command reads from and writes to.
+///
+/// Here-document bodies containing expansions or substitutions are rejected,
+/// as are redirections whose destination is not a literal word.
+pub fn try_parse_commands_with_redirects(tree: &Tree, src: &str) -> Option<Vec<RedirectedCommand>> {
+    if tree.root_node().has_error() {
+        return None;
+    }
+
+    const ALLOWED_KINDS: &[&str] = &[
+        "program",
+        "list",
+        "pipeline",
+        "command",
+        "command_name",
+        "word",
+        "string",
+        "string_content",
+        "raw_string",
+        "number",
+        "concatenation",
+        // redirections
+        "redirected_statement",
+        "file_redirect",
+        "file_descriptor",
+        "heredoc_redirect",
+        "heredoc_start",
+        "heredoc_body",
+        "heredoc_content",
+        "heredoc_end",
+    ];
+    const ALLOWED_PUNCT_TOKENS: &[&str] = &[
+        "&&", "||", ";", "|", "\"", "'", "<", ">", ">>", "&>", "&>>", ">|", "<&", ">&", "<&-",
+        ">&-", "<<", "<<-",
+    ];
+
+    let command_nodes = collect_command_nodes(tree, ALLOWED_KINDS, ALLOWED_PUNCT_TOKENS)?;
+    let mut commands = Vec::new();
+    for &node in &command_nodes {
+        let words = parse_plain_command_from_node(node, src)?;
+        let mut write_targets = Vec::new();
+        let mut read_sources = Vec::new();
+        let mut text = first_line(node, src)?.to_string();
+        let mut current = node;
+        while let Some(parent) = current.parent() {
+            current = parent;
+            if parent.kind() != "redirected_statement" {
+                continue;
+            }
+            let body = parent.child_by_field_name("body")?;
+            // Like bash, attribute the redirections of `a | b > out` to the
+            // last command of the redirected statement.
+            let last_command_in_body = command_nodes
+                .iter()
+                .filter(|other| {
+                    other.start_byte() >= body.start_byte() && other.end_byte() <= body.end_byte()
+                })
+                .max_by_key(|other| other.start_byte());
+            if last_command_in_body.is_none_or(|last| last.id() != node.id()) {
+                continue;
+            }
+            let mut cursor = parent.walk();
+            for child in parent.named_children(&mut cursor) {
+                if child.id() != body.id() {
+                    collect_redirect_targets(child, src, &mut write_targets, &mut read_sources)?;
+                    text.push(' ');
+                    text.push_str(first_line(child, src)?);
+                }
+            }
+        }
+        commands.push(RedirectedCommand {
+            words,
+            write_targets,
+            read_sources,
+            text,
+        });
+    }
+    Some(commands)
+}
+
+/// Walks the parse tree, rejecting any node kind or token outside the given
+/// allow-lists, and returns the `command` nodes in source order.
+fn collect_command_nodes<'tree>(
+    tree: &'tree Tree,
+    allowed_kinds: &[&str],
+    allowed_punct_tokens: &[&str],
+) -> Option<Vec<Node<'tree>>> {
     let root = tree.root_node();
     let mut cursor = root.walk();
     let mut stack = vec![root];
@@ -58,18 +174,23 @@ pub fn try_parse_word_only_commands_sequence(tree: &Tree, src: &str) -> Option<V
     while let Some(node) = stack.pop() {
         let kind = node.kind();
         if node.is_named() {
-            if !ALLOWED_KINDS.contains(&kind) {
+            if !allowed_kinds.contains(&kind) {
                 return None;
             }
             if kind == "command" {
                 command_nodes.push(node);
             }
+            // A redirection without a command (e.g. `> file`) truncates files
+            // without any argv to attribute the write to.
+            if kind == "redirected_statement" && node.child_by_field_name("body").is_none() {
+                return None;
+            }
         } else {
             // Reject any punctuation / operator tokens that are not explicitly allowed.
-            if kind.chars().any(|c| "&;|".contains(c)) && !ALLOWED_PUNCT_TOKENS.contains(&kind) {
+            if kind.chars().any(|c| "&;|".contains(c)) && !allowed_punct_tokens.contains(&kind) {
                 return None;
             }
-            if !(ALLOWED_PUNCT_TOKENS.contains(&kind) || kind.trim().is_empty()) {
+            if !(allowed_punct_tokens.contains(&kind) || kind.trim().is_empty()) {
                 // If it's a quote token or operator it's allowed above; we also allow whitespace tokens.
                 // Any other punctuation like parentheses, braces, redirects, backticks, etc are rejected.
                 return None;
@@ -82,16 +203,92 @@ pub fn try_parse_word_only_commands_sequence(tree: &Tree, src: &str) -> Option<V
 
     // Walk uses a stack (LIFO), so re-sort by position to restore source order.
     command_nodes.sort_by_key(Node::start_byte);
+    Some(command_nodes)
+}
 
-    let mut commands = Vec::new();
-    for node in command_nodes {
-        if let Some(words) = parse_plain_command_from_node(node, src) {
-            commands.push(words);
-        } else {
-            return None;
+fn first_line<'a>(node: Node<'_>, src: &'a str) -> Option<&'a str> {
+    let text = node.utf8_text(src.as_bytes()).ok()?;
+    Some(text.lines().next().unwrap_or_default().trim())
+}
+
+/// Appends the destinations of output redirections and the sources of input
+/// redirections found under `node`. Nested commands (e.g. a pipeline fed by a
+/// here-document) are skipped since their redirections are attributed to them
+/// directly.
+fn collect_redirect_targets(
+    node: Node<'_>,
+    src: &str,
+    write_targets: &mut Vec<String>,
+    read_sources: &mut Vec<String>,
+) -> Option<()> {
+    let mut stack = vec![node];
+    while let Some(current) = stack.pop() {
+        match current.kind() {
+            "command" | "pipeline" | "list" => continue,
+            "file_redirect" => {
+                if let Some(target) = file_redirect_write_target(current, src)? {
+                    write_targets.push(target);
+                }
+                if let Some(source) = file_redirect_read_source(current, src)? {
+                    read_sources.push(source);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        let mut cursor = current.walk();
+        for child in current.named_children(&mut cursor) {
+            stack.push(child);
         }
     }
-    Some(commands)
+    Some(())
+}
+
+/// Returns `Some(Some(path))` when the redirection writes to a file,
+/// `Some(None)` when it only reads or duplicates a descriptor, and `None` when
+/// the destination cannot be determined statically.
+fn file_redirect_write_target(redirect: Node<'_>, src: &str) -> Option<Option<String>> {
+    let (operator, destination) = file_redirect_parts(redirect, src)?;
+    let Some(destination) = destination else {
+        return Some(None);
+    };
+    let writes = match operator {
+        ">" | ">>" | "&>" | "&>>" | ">|" => true,
+        // `>&1` duplicates a descriptor, while `>& file` behaves like `&>`.
+        ">&" => !(destination == "-" || destination.chars().all(|c| c.is_ascii_digit())),
+        _ => false,
+    };
+    if !writes
+        || matches!(
+            destination.as_str(),
+            "/dev/null" | "/dev/stdout" | "/dev/stderr"
+        )
+    {
+        return Some(None);
+    }
+    Some(Some(destination))
+}
+
+/// Returns `Some(Some(path))` when the redirection reads a file (`< path`),
+/// `Some(None)` for any other redirection, and `None` when the source cannot
+/// be determined statically.
+fn file_redirect_read_source(redirect: Node<'_>, src: &str) -> Option<Option<String>> {
+    let (operator, destination) = file_redirect_parts(redirect, src)?;
+    Some(destination.filter(|_| operator == "<"))
+}
+
+/// The operator of a file redirection and its literal destination, if any.
+fn file_redirect_parts(redirect: Node<'_>, src: &str) -> Option<(&'static str, Option<String>)> {
+    let mut cursor = redirect.walk();
+    let operator = redirect
+        .children(&mut cursor)
+        .find(|child| !child.is_named())?
+        .kind();
+    let destination = match redirect.child_by_field_name("destination") {
+        Some(node) => Some(parse_literal_word(node, src)?),
+        None => None,
+    };
+    Some((operator, destination))
 }
 
 pub fn extract_bash_command(command: &[String]) -> Option<(&str, &str)> {
@@ -151,47 +348,47 @@ fn parse_plain_command_from_node(cmd: tree_sitter::Node, src: &str) -> Option<Ve
                 }
                 words.push(word_node.utf8_text(src.as_bytes()).ok()?.to_owned());
             }
-            "word" | "number" => {
-                words.push(child.utf8_text(src.as_bytes()).ok()?.to_owned());
-            }
-            "string" => {
-                let parsed = parse_double_quoted_string(child, src)?;
-                words.push(parsed);
-            }
-            "raw_string" => {
-                let parsed = parse_raw_string(child, src)?;
-                words.push(parsed);
-            }
-            "concatenation" => {
-                // Handle concatenated arguments like -g"*.py"
-                let mut concatenated = String::new();
-                let mut concat_cursor = child.walk();
-                for part in child.named_children(&mut concat_cursor) {
-                    match part.kind() {
-                        "word" | "number" => {
-                            concatenated
-                                .push_str(part.utf8_text(src.as_bytes()).ok()?.to_owned().as_str());
-                        }
-                        "string" => {
-                            let parsed = parse_double_quoted_string(part, src)?;
-                            concatenated.push_str(&parsed);
-                        }
-                        "raw_string" => {
-                            let parsed = parse_raw_string(part, src)?;
-                            concatenated.push_str(&parsed);
-                        }
-                        _ => return None,
+            _ => words.push(parse_literal_word(child, src)?),
+        }
+    }
+    Some(words)
+}
+
+/// Parses a single literal shell word: a bare word or number, a quoted string
+/// without expansions, or a concatenation of those.
+fn parse_literal_word(node: Node<'_>, src: &str) -> Option<String> {
+    match node.kind() {
+        "word" | "number" => Some(node.utf8_text(src.as_bytes()).ok()?.to_owned()),
+        "string" => parse_double_quoted_string(node, src),
+        "raw_string" => parse_raw_string(node, src),
+        "concatenation" => {
+            // Handle concatenated arguments like -g"*.py"
+            let mut concatenated = String::new();
+            let mut concat_cursor = node.walk();
+            for part in node.named_children(&mut concat_cursor) {
+                match part.kind() {
+                    "word" | "number" => {
+                        concatenated
+                            .push_str(part.utf8_text(src.as_bytes()).ok()?.to_owned().as_str());
                     }
+                    "string" => {
+                        let parsed = parse_double_quoted_string(part, src)?;
+                        concatenated.push_str(&parsed);
+                    }
+                    "raw_string" => {
+                        let parsed = parse_raw_string(part, src)?;
+                        concatenated.push_str(&parsed);
+                    }
+                    _ => return None,
                 }
-                if concatenated.is_empty() {
-                    return None;
-                }
-                words.push(concatenated);
             }
-            _ => return None,
+            if concatenated.is_empty() {
+                return None;
+            }
+            Some(concatenated)
         }
+        _ => None,
     }
-    Some(words)
 }
 
 fn parse_heredoc_command_words(cmd: Node<'_>, src: &str) -> Option<Vec<String>> {
@@ -413,6 +610,76 @@ mod tests {
         assert!(parse_seq("echo hi & echo bye").is_none());
     }
 
+    fn parse_redirected(src: &str) -> Option<Vec<RedirectedCommand>> {
+        let tree = try_parse_shell(src)?;
+        try_parse_commands_with_redirects(&tree, src)
+    }
+
+    #[test]
+    fn reports_output_redirect_targets() {
+        let cmds = parse_redirected("cargo test 2>&1 > log.txt && echo done >> notes.md").unwrap();
+        assert_eq!(
+            cmds,
+            vec![
+                RedirectedCommand {
+                    words: vec!["cargo".to_string(), "test".to_string()],
+                    write_targets: vec!["log.txt".to_string()],
+                    read_sources: Vec::new(),
+                    text: "cargo test 2>&1 > log.txt".to_string(),
+                },
+                RedirectedCommand {
+                    words: vec!["echo".to_string(), "done".to_string()],
+                    write_targets: vec!["notes.md".to_string()],
+                    read_sources: Vec::new(),
+                    text: "echo done >> notes.md".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_descriptor_duplication_and_dev_null() {
+        let cmds = parse_redirected("rg foo 2>/dev/null | head -n 5 2>&1").unwrap();
+        assert!(cmds.iter().all(|cmd| cmd.write_targets.is_empty()));
+        assert_eq!(cmds.len(), 2);
+    }
+
+    #[test]
+    fn reports_heredoc_write_target() {
+        let cmds = parse_redirected("cat <<'EOF' > notes.txt\nhello\nEOF").unwrap();
+        assert_eq!(
+            cmds,
+            vec![RedirectedCommand {
+                words: vec!["cat".to_string()],
+                write_targets: vec!["notes.txt".to_string()],
+                read_sources: Vec::new(),
+                text: "cat <<'EOF' > notes.txt".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_input_redirect_sources() {
+        let cmds = parse_redirected("wc -l < Cargo.toml && cat < /dev/tcp/example.com/80").unwrap();
+        assert_eq!(
+            cmds.iter()
+                .map(|cmd| cmd.read_sources.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                vec!["Cargo.toml".to_string()],
+                vec!["/dev/tcp/example.com/80".to_string()],
+            ]
+        );
+        assert!(cmds.iter().all(|cmd| cmd.write_targets.is_empty()));
+    }
+
+    #[test]
+    fn rejects_non_literal_redirect_targets() {
+        assert!(parse_redirected("echo hi > \"$OUT\"").is_none());
+        assert!(parse_redirected("echo hi > $(mktemp)").is_none());
+        assert!(parse_redirected("> truncated.txt").is_none());
+    }
+
     #[test]
     fn rejects_command_and_process_substitutions_and_expansions() {
         assert!(parse_seq("echo $(pwd)").is_none());
@@ -1,4 +1,7 @@
+use crate::bash::extract_bash_command;
 use crate::bash::parse_shell_lc_plain_commands;
+use crate::bash::try_parse_commands_with_redirects;
+use crate::bash::try_parse_shell;
 // Find the first matching git subcommand, skipping known global options that
 // may appear before it (e.g., `-C`, `-c`, `--git-dir`).
 // Implemented in `is_dangerous_command` and shared here.
@@ -39,9 +42,30 @@ pub fn is_known_safe_command(command: &[String]) -> bool {
     {
         return true;
     }
+
+    // Redirections that only read files, duplicate descriptors (`2>&1`) or
+    // discard output (`2>/dev/null`) do not write anything, so scripts using
+    // them are safe when every command is. Reading from a device is not: bash
+    // opens a network connection for `/dev/tcp/...` and `/dev/udp/...`.
+    if let Some((_, script)) = extract_bash_command(&command)
+        && let Some(tree) = try_parse_shell(script)
+        && let Some(all_commands) = try_parse_commands_with_redirects(&tree, script)
+        && !all_commands.is_empty()
+        && all_commands.iter().all(|cmd| {
+            cmd.write_targets.is_empty()
+                && !cmd.read_sources.iter().any(|source| is_device_path(source))
+                && is_safe_to_call_with_exec(&cmd.words)
+        })
+    {
+        return true;
+    }
     false
 }
 
+fn is_device_path(path: &str) -> bool {
+    path == "/dev" || path.starts_with("/dev/")
+}
+
 fn is_safe_to_call_with_exec(command: &[String]) -> bool {
     let Some(cmd0) = command.first().map(String::as_str) else {
         return false;
@@ -588,5 +612,47 @@ mod tests {
             !is_known_safe_command(&vec_str(&["bash", "-lc", "ls > out.txt"])),
             "> redirection should be rejected"
         );
+        assert!(
+            !is_known_safe_command(&vec_str(&["bash", "-lc", "ls 2>&1 | tee out.txt"])),
+            "tee into a file should be rejected"
+        );
+        assert!(
+            !is_known_safe_command(&vec_str(&["bash", "-lc", "cat <<'EOF' > out.txt\nhi\nEOF"])),
+            "heredoc redirected into a file should be rejected"
+        );
+    }
+
+    #[test]
+    fn bash_lc_safe_examples_with_non_writing_redirects() {
+        assert!(is_known_safe_command(&vec_str(&[
+            "bash",
+            "-lc",
+            "rg -n TODO src 2>/dev/null"
+        ])));
+        assert!(is_known_safe_command(&vec_str(&[
+            "bash",
+            "-lc",
+            "git status 2>&1 | head -n 20"
+        ])));
+        assert!(is_known_safe_command(&vec_str(&[
+            "bash",
+            "-lc",
+            "wc -l < Cargo.toml"
+        ])));
+    }
+
+    #[test]
+    fn bash_lc_input_redirect_from_device_is_not_safe() {
+        for script in [
+            "cat < /dev/tcp/example.com/80",
+            "head -n 1 < /dev/udp/10.0.0.1/53",
+            "wc -c < /dev/urandom",
+            "cat Cargo.toml && cat </dev/tcp/127.0.0.1/22",
+        ] {
+            assert!(
+                !is_known_safe_command(&vec_str(&["bash", "-lc", script])),
+                "{script:?} should require approval"
+            );
+        }
     }
 }
@@ -1,4 +1,6 @@
+use crate::bash::RedirectedCommand;
 use crate::bash::extract_bash_command;
+use crate::bash::try_parse_commands_with_redirects;
 use crate::bash::try_parse_shell;
 use crate::bash::try_parse_word_only_commands_sequence;
 use crate::powershell::extract_powershell_command;
@@ -63,11 +65,23 @@ mod tests {
     }
 
     #[test]
-    fn git_status_is_unknown() {
+    fn git_status_is_git() {
         assert_parsed(
             &vec_str(&["git", "status"]),
-            vec![ParsedCommand::Unknown {
+            vec![ParsedCommand::Git {
                 cmd: "git status".to_string(),
+                subcommand: "status".to_string(),
+            }],
+        );
+    }
+
+    #[test]
+    fn git_subcommand_skips_global_options() {
+        assert_parsed(
+            &shlex_split_safe("git -C codex-rs commit -m 'fix: typo'"),
+            vec![ParsedCommand::Git {
+                cmd: "git -C codex-rs commit -m 'fix: typo'".to_string(),
+                subcommand: "commit".to_string(),
             }],
         );
     }
@@ -118,8 +132,9 @@ mod tests {
         let inner = "git status | wc -l";
         assert_parsed(
             &vec_str(&["bash", "-lc", inner]),
-            vec![ParsedCommand::Unknown {
+            vec![ParsedCommand::Git {
                 cmd: "git status".to_string(),
+                subcommand: "status".to_string(),
             }],
         );
     }
@@ -129,8 +144,185 @@ mod tests {
         let inner = "echo foo > bar";
         assert_parsed(
             &vec_str(&["bash", "-lc", inner]),
-            vec![ParsedCommand::Unknown {
+            vec![ParsedCommand::Write {
                 cmd: "echo foo > bar".to_string(),
+                paths: vec!["bar".to_string()],
+            }],
+        );
+    }
+
+    #[test]
+    fn bash_lc_redirect_keeps_command_summary() {
+        let inner = "cargo test -p codex-core 2>&1 > test.log";
+        assert_parsed(
+            &vec_str(&["bash", "-lc", inner]),
+            vec![
+                ParsedCommand::Test {
+                    cmd: "cargo test -p codex-core".to_string(),
+                },
+                ParsedCommand::Write {
+                    cmd: inner.to_string(),
+                    paths: vec!["test.log".to_string()],
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn bash_lc_heredoc_redirect_is_write() {
+        let inner = "cd docs && cat <<'EOF' > notes.md\n# Notes\nEOF";
+        assert_parsed(
+            &vec_str(&["bash", "-lc", inner]),
+            vec![ParsedCommand::Write {
+                cmd: "cat <<'EOF' > notes.md".to_string(),
+                paths: vec!["docs/notes.md".to_string()],
+            }],
+        );
+    }
+
+    #[test]
+    fn bash_lc_redirect_to_dev_null_is_not_write() {
+        let inner = "rg -n TODO src 2>/dev/null";
+        assert_parsed(
+            &vec_str(&["bash", "-lc", inner]),
+            vec![ParsedCommand::Search {
+                cmd: "rg -n TODO src".to_string(),
+                query: Some("TODO".to_string()),
+                path: Some("src".to_string()),
+            }],
+        );
+    }
+
+    #[test]
+    fn supports_deletions_and_writes() {
+        assert_parsed(
+            &shlex_split_safe("rm -rf target dist"),
+            vec![ParsedCommand::Delete {
+                cmd: "rm -rf target dist".to_string(),
+                paths: vec!["target".to_string(), "dist".to_string()],
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("mkdir -p src/bin"),
+            vec![ParsedCommand::Write {
+                cmd: "mkdir -p src/bin".to_string(),
+                paths: vec!["src/bin".to_string()],
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("cp -r assets build/assets"),
+            vec![ParsedCommand::Write {
+                cmd: "cp -r assets build/assets".to_string(),
+                paths: vec!["build/assets".to_string()],
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("sed -i 's/foo/bar/g' src/lib.rs src/main.rs"),
+            vec![ParsedCommand::Write {
+                cmd: "sed -i s/foo/bar/g src/lib.rs src/main.rs".to_string(),
+                paths: vec!["src/lib.rs".to_string(), "src/main.rs".to_string()],
+            }],
+        );
+        assert_parsed(
+            &vec_str(&["bash", "-lc", "echo done | tee -a log.txt"]),
+            vec![ParsedCommand::Write {
+                cmd: "tee -a log.txt".to_string(),
+                paths: vec!["log.txt".to_string()],
+            }],
+        );
+    }
+
+    #[test]
+    fn supports_package_installs() {
+        assert_parsed(
+            &shlex_split_safe("npm install --save-dev vitest"),
+            vec![ParsedCommand::Install {
+                cmd: "npm install --save-dev vitest".to_string(),
+                packages: vec!["vitest".to_string()],
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("pip install -r requirements.txt"),
+            vec![ParsedCommand::Install {
+                cmd: "pip install -r requirements.txt".to_string(),
+                packages: Vec::new(),
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("sudo apt-get install -y jq"),
+            vec![ParsedCommand::Install {
+                cmd: "sudo apt-get install -y jq".to_string(),
+                packages: vec!["jq".to_string()],
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("cargo add serde --features derive"),
+            vec![ParsedCommand::Install {
+                cmd: "cargo add serde --features derive".to_string(),
+                packages: vec!["serde".to_string()],
+            }],
+        );
+        assert_parsed(
+            &shlex_split_safe("python3 -m pip install requests"),
+            vec![ParsedCommand::Install {
+                cmd: "python3 -m pip install requests".to_string(),
+                packages: vec!["requests".to_string()],
+            }],
+        );
+    }
+
+    #[test]
+    fn supports_builds_and_tests() {
+        for (cmd, build) in [
+            ("cargo build --release", true),
+            ("cargo +nightly check", true),
+            ("make", true),
+            ("./gradlew assemble", true),
+            ("yarn build", true),
+            ("cargo test -p codex-core", false),
+            ("make test", false),
+            ("go test ./...", false),
+            ("pytest -q tests", false),
+            ("python -m pytest", false),
+            ("npx vitest run", false),
+            ("npm run test:unit", false),
+        ] {
+            let expected = if build {
+                ParsedCommand::Build {
+                    cmd: cmd.to_string(),
+                }
+            } else {
+                ParsedCommand::Test {
+                    cmd: cmd.to_string(),
+                }
+            };
+            assert_parsed(&shlex_split_safe(cmd), vec![expected]);
+        }
+    }
+
+    #[test]
+    fn supports_network_fetches() {
+        assert_parsed(
+            &vec_str(&[
+                "bash",
+                "-lc",
+                "curl -fsSL https://example.com/install.sh | sh",
+            ]),
+            vec![
+                ParsedCommand::Fetch {
+                    cmd: "curl -fsSL https://example.com/install.sh".to_string(),
+                    url: Some("https://example.com/install.sh".to_string()),
+                },
+                ParsedCommand::Unknown {
+                    cmd: "sh".to_string(),
+                },
+            ],
+        );
+        assert_parsed(
+            &shlex_split_safe("wget -q -O out.tar.gz"),
+            vec![ParsedCommand::Fetch {
+                cmd: "wget -q -O out.tar.gz".to_string(),
+                url: None,
             }],
         );
     }
@@ -536,10 +728,10 @@ mod tests {
     }
 
     #[test]
-    fn supports_npm_run_build_is_unknown() {
+    fn supports_npm_run_build_is_build() {
         assert_parsed(
             &vec_str(&["npm", "run", "build"]),
-            vec![ParsedCommand::Unknown {
+            vec![ParsedCommand::Build {
                 cmd: "npm run build".to_string(),
             }],
         );
@@ -1368,22 +1560,7 @@ pub fn parse_command_impl(command: &[String]) -> Vec<ParsedCommand> {
             continue;
         }
         let parsed = summarize_main_tokens(tokens);
-        let parsed = match parsed {
-            ParsedCommand::Read { cmd, name, path } => {
-                if let Some(base) = &cwd {
-                    let full = join_paths(base, &path.to_string_lossy());
-                    ParsedCommand::Read {
-                        cmd,
-                        name,
-                        path: PathBuf::from(full),
-                    }
-                } else {
-                    ParsedCommand::Read { cmd, name, path }
-                }
-            }
-            other => other,
-        };
-        commands.push(parsed);
+        commands.push(resolve_against_cwd(parsed, cwd.as_deref()));
     }
 
     while let Some(next) = simplify_once(&commands) {
@@ -1877,8 +2054,9 @@ fn parse_shell_lc_commands(original: &[String]) -> Option<Vec<ParsedCommand>> {
     // Only handle bash/zsh here; PowerShell is stripped separately without bash parsing.
     let (_, script) = extract_bash_command(original)?;
 
-    if let Some(tree) = try_parse_shell(script)
-        && let Some(all_commands) = try_parse_word_only_commands_sequence(&tree, script)
+    let tree = try_parse_shell(script);
+    if let Some(tree) = &tree
+        && let Some(all_commands) = try_parse_word_only_commands_sequence(tree, script)
         && !all_commands.is_empty()
     {
         let script_tokens = shlex_split(script).unwrap_or_else(|| vec![script.to_string()]);
@@ -1909,22 +2087,7 @@ fn parse_shell_lc_commands(original: &[String]) -> Option<Vec<ParsedCommand>> {
                 continue;
             }
             let parsed = summarize_main_tokens(&tokens);
-            let parsed = match parsed {
-                ParsedCommand::Read { cmd, name, path } => {
-                    if let Some(base) = &cwd {
-                        let full = join_paths(base, &path.to_string_lossy());
-                        ParsedCommand::Read {
-                            cmd,
-                            name,
-                            path: PathBuf::from(full),
-                        }
-                    } else {
-                        ParsedCommand::Read { cmd, name, path }
-                    }
-                }
-                other => other,
-            };
-            commands.push(parsed);
+            commands.push(resolve_against_cwd(parsed, cwd.as_deref()));
         }
         if commands.len() > 1 {
             commands.retain(|pc| !matches!(pc, ParsedCommand::Unknown { cmd } if cmd == "true"));
@@ -1998,11 +2161,101 @@ fn parse_shell_lc_commands(original: &[String]) -> Option<Vec<ParsedCommand>> {
         }
         return Some(commands);
     }
+    if let Some(tree) = &tree
+        && let Some(redirected) = try_parse_commands_with_redirects(tree, script)
+        && !redirected.is_empty()
+    {
+        let commands = summarize_redirected_commands(redirected);
+        if !commands.is_empty() {
+            return Some(commands);
+        }
+    }
     Some(vec![ParsedCommand::Unknown {
         cmd: script.to_string(),
     }])
 }
 
+/// Summarizes a script whose commands redirect output into files. Each
+/// command is summarized as usual and its redirect targets are reported as a
+/// write. Reads and unknown commands that only feed a redirect (e.g.
+/// `cat <<EOF > file`) are reported as just the write.
+fn summarize_redirected_commands(redirected: Vec<RedirectedCommand>) -> Vec<ParsedCommand> {
+    let mut commands: Vec<ParsedCommand> = Vec::new();
+    let mut cwd: Option<String> = None;
+    for RedirectedCommand {
+        words,
+        write_targets,
+        text,
+        ..
+    } in redirected
+    {
+        if let Some((head, tail)) = words.split_first()
+            && head == "cd"
+        {
+            if let Some(dir) = cd_target(tail) {
+                cwd = Some(match &cwd {
+                    Some(base) => join_paths(base, &dir),
+                    None => dir.clone(),
+                });
+            }
+            continue;
+        }
+        if is_small_formatting_command(&words) && write_targets.is_empty() {
+            continue;
+        }
+        let parsed = summarize_main_tokens(&words);
+        let feeds_redirect_only = !write_targets.is_empty()
+            && matches!(
+                parsed,
+                ParsedCommand::Read { .. } | ParsedCommand::Unknown { .. }
+            );
+        if !feeds_redirect_only {
+            commands.push(resolve_against_cwd(parsed, cwd.as_deref()));
+        }
+        if !write_targets.is_empty() {
+            let write = ParsedCommand::Write {
+                cmd: text,
+                paths: write_targets,
+            };
+            commands.push(resolve_against_cwd(write, cwd.as_deref()));
+        }
+    }
+    if commands.len() > 1 {
+        commands.retain(|pc| !matches!(pc, ParsedCommand::Unknown { cmd } if cmd == "true"));
+        while let Some(next) = simplify_once(&commands) {
+            commands = next;
+        }
+    }
+    commands
+}
+
+/// Resolves the paths of a parsed command against the directory entered by a
+/// preceding `cd`, if any.
+fn resolve_against_cwd(parsed: ParsedCommand, cwd: Option<&str>) -> ParsedCommand {
+    let Some(base) = cwd else {
+        return parsed;
+    };
+    let join_all = |paths: Vec<String>| -> Vec<String> {
+        paths.iter().map(|path| join_paths(base, path)).collect()
+    };
+    match parsed {
+        ParsedCommand::Read { cmd, name, path } => ParsedCommand::Read {
+            cmd,
+            name,
+            path: PathBuf::from(join_paths(base, &path.to_string_lossy())),
+        },
+        ParsedCommand::Write { cmd, paths } => ParsedCommand::Write {
+            cmd,
+            paths: join_all(paths),
+        },
+        ParsedCommand::Delete { cmd, paths } => ParsedCommand::Delete {
+            cmd,
+            paths: join_all(paths),
+        },
+        other => other,
+    }
+}
+
 /// Return true if this looks like a small formatting helper in a pipeline.
 /// Examples: `head -n 40`, `tail -n +10`, `wc -l`, `awk ...`, `cut ...`, `tr ...`.
 /// We try to keep variants that clearly include a file path (e.g. `tail -n 30 file`).
@@ -2014,7 +2267,9 @@ fn is_small_formatting_command(tokens: &[String]) -> bool {
     match cmd {
         // Always formatting; typically used in pipes.
         // `nl` is special-cased below to allow `nl <file>` to be treated as a read command.
-        "wc" | "tr" | "cut" | "sort" | "uniq" | "tee" | "column" | "yes" | "printf" => true,
+        "wc" | "tr" | "cut" | "sort" | "uniq" | "column" | "yes" | "printf" => true,
+        // `tee file` writes the file, so only a bare `tee` is formatting.
+        "tee" => positional_operands(&tokens[1..], &[]).is_empty(),
         "xargs" => !is_mutating_xargs_command(tokens),
         "awk" => awk_data_file_operand(&tokens[1..]).is_none(),
         "head" => {
@@ -2067,9 +2322,10 @@ fn is_small_formatting_command(tokens: &[String]) -> bool {
             }
         }
         "sed" => {
-            // Keep `sed -n <range> file` (treated as a file read elsewhere);
-            // otherwise consider it a formatting helper in a pipeline.
-            sed_read_path(&tokens[1..]).is_none()
+            // Keep `sed -n <range> file` (treated as a file read elsewhere)
+            // and in-place edits; otherwise consider it a formatting helper in
+            // a pipeline.
+            sed_read_path(&tokens[1..]).is_none() && !has_in_place_edit_flag(&tokens[1..])
         }
         _ => false,
     }
@@ -2242,8 +2498,14 @@ fn summarize_main_tokens(main_cmd: &[String]) -> ParsedCommand {
                     path,
                 }
             }
-            _ => ParsedCommand::Unknown {
-                cmd: shlex_join(main_cmd),
+            _ => match git_subcommand(tail) {
+                Some(subcommand) => ParsedCommand::Git {
+                    cmd: shlex_join(main_cmd),
+                    subcommand,
+                },
+                None => ParsedCommand::Unknown {
+                    cmd: shlex_join(main_cmd),
+                },
             },
         },
         Some((head, tail)) if head == "fd" => {
@@ -2531,9 +2793,7 @@ fn summarize_main_tokens(main_cmd: &[String]) -> ParsedCommand {
                     path: PathBuf::from(path),
                 }
             } else {
-                ParsedCommand::Unknown {
-                    cmd: shlex_join(main_cmd),
-                }
+                summarize_side_effect_or_unknown(main_cmd)
             }
         }
         Some((head, tail)) if is_python_command(head) => {
@@ -2543,15 +2803,315 @@ fn summarize_main_tokens(main_cmd: &[String]) -> ParsedCommand {
                     path: None,
                 }
             } else {
-                ParsedCommand::Unknown {
-                    cmd: shlex_join(main_cmd),
-                }
+                summarize_side_effect_or_unknown(main_cmd)
             }
         }
         // Other commands
-        _ => ParsedCommand::Unknown {
+        _ => summarize_side_effect_or_unknown(main_cmd),
+    }
+}
+
+fn summarize_side_effect_or_unknown(main_cmd: &[String]) -> ParsedCommand {
+    summarize_side_effect(shlex_join(main_cmd), main_cmd).unwrap_or_else(|| {
+        ParsedCommand::Unknown {
             cmd: shlex_join(main_cmd),
+        }
+    })
+}
+
+/// Summarizes commands with side effects: deletions, file writes, package
+/// installs, builds, test runs and network fetches. Returns `None` for
+/// anything else so callers can fall back to `Unknown`.
+fn summarize_side_effect(cmd: String, words: &[String]) -> Option<ParsedCommand> {
+    let (head, tail) = match words.split_first()? {
+        (head, rest) if head == "sudo" => match rest.split_first() {
+            Some((head, rest)) if !head.starts_with('-') => (head, rest),
+            _ => return None,
         },
+        (head, rest) => (head, rest),
+    };
+    let tail = trim_at_connector(tail);
+    let program = head.rsplit('/').next().unwrap_or(head);
+    match program {
+        "rm" | "rmdir" | "unlink" | "shred" => Some(ParsedCommand::Delete {
+            cmd,
+            paths: owned_operands(&tail, &[]),
+        }),
+        "touch" | "mkdir" => Some(ParsedCommand::Write {
+            cmd,
+            paths: owned_operands(&tail, &["-d", "-r", "-t", "-m", "--mode", "--date"]),
+        }),
+        "cp" | "mv" | "ln" | "rsync" => Some(ParsedCommand::Write {
+            cmd,
+            paths: copy_destination(&tail).into_iter().collect(),
+        }),
+        "tee" => {
+            let paths = owned_operands(&tail, &[]);
+            if paths.is_empty() {
+                None
+            } else {
+                Some(ParsedCommand::Write { cmd, paths })
+            }
+        }
+        "sed" | "perl" if has_in_place_edit_flag(&tail) => Some(ParsedCommand::Write {
+            cmd,
+            paths: in_place_edit_paths(program, &tail),
+        }),
+        "curl" | "wget" | "http" | "https" | "xh" => Some(ParsedCommand::Fetch {
+            cmd,
+            url: tail.iter().find(|arg| arg.contains("://")).cloned(),
+        }),
+        "make" | "gmake" | "ninja" | "just" => {
+            let target = positional_operands(&tail, &["-C", "-f", "--directory", "--file"])
+                .into_iter()
+                .find(|arg| !arg.contains('=') && !arg.chars().all(|c| c.is_ascii_digit()));
+            match target.map(String::as_str) {
+                Some("test" | "tests" | "check") => Some(ParsedCommand::Test { cmd }),
+                None if program == "just" => None,
+                _ => Some(ParsedCommand::Build { cmd }),
+            }
+        }
+        "cargo" => {
+            let (sub, args) = split_subcommand(&tail, &["-C", "--config", "-Z"])?;
+            match sub {
+                "build" | "b" | "check" | "c" => Some(ParsedCommand::Build { cmd }),
+                "test" | "t" | "nextest" | "bench" => Some(ParsedCommand::Test { cmd }),
+                "install" | "add" => Some(ParsedCommand::Install {
+                    cmd,
+                    packages: owned_operands(
+                        args,
+                        &[
+                            "-F",
+                            "--features",
+                            "--version",
+                            "--git",
+                            "--branch",
+                            "--tag",
+                            "--rev",
+                            "--path",
+                            "--root",
+                            "--registry",
+                            "--rename",
+                            "--target",
+                            "-p",
+                            "--package",
+                        ],
+                    ),
+                }),
+                _ => None,
+            }
+        }
+        "go" => {
+            let (sub, args) = split_subcommand(&tail, &[])?;
+            match sub {
+                "build" => Some(ParsedCommand::Build { cmd }),
+                "test" => Some(ParsedCommand::Test { cmd }),
+                "get" | "install" => Some(ParsedCommand::Install {
+                    cmd,
+                    packages: owned_operands(args, &[]),
+                }),
+                _ => None,
+            }
+        }
+        "npm" | "pnpm" | "yarn" | "bun" => {
+            let Some((sub, args)) =
+                split_subcommand(&tail, &["--prefix", "-C", "--dir", "--cwd", "--filter"])
+            else {
+                // A bare `yarn` installs the project's dependencies.
+                return (program == "yarn").then_some(ParsedCommand::Install {
+                    cmd,
+                    packages: Vec::new(),
+                });
+            };
+            match sub {
+                "install" | "i" | "add" | "ci" => Some(ParsedCommand::Install {
+                    cmd,
+                    packages: owned_operands(args, &["--save-prefix", "--tag"]),
+                }),
+                "test" | "t" => Some(ParsedCommand::Test { cmd }),
+                "build" => Some(ParsedCommand::Build { cmd }),
+                "run" | "run-script" => match first_non_flag_operand(args, &[]).as_deref() {
+                    Some(script) if script == "build" || script.starts_with("build:") => {
+                        Some(ParsedCommand::Build { cmd })
+                    }
+                    Some(script) if script == "test" || script.starts_with("test:") => {
+                        Some(ParsedCommand::Test { cmd })
+                    }
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+        "npx" | "pnpx" | "bunx" => {
+            match first_non_flag_operand(&tail, &["-p", "--package"])?.as_str() {
+                "jest" | "vitest" | "mocha" | "playwright" => Some(ParsedCommand::Test { cmd }),
+                "tsc" => Some(ParsedCommand::Build { cmd }),
+                _ => None,
+            }
+        }
+        "pip" | "pip3" | "pipx" | "gem" | "apt" | "apt-get" | "dnf" | "yum" | "brew" | "zypper"
+        | "conda" | "mamba" | "port" | "apk" | "composer" | "bundle" | "poetry" | "pdm" => {
+            let (sub, args) = split_subcommand(&tail, &[])?;
+            let installs = match program {
+                "apk" => sub == "add",
+                "composer" => matches!(sub, "require" | "install"),
+                "bundle" | "poetry" | "pdm" => matches!(sub, "add" | "install"),
+                _ => sub == "install",
+            };
+            if installs {
+                return Some(ParsedCommand::Install {
+                    cmd,
+                    packages: owned_operands(
+                        args,
+                        &[
+                            "-r",
+                            "--requirement",
+                            "-c",
+                            "--constraint",
+                            "-e",
+                            "--editable",
+                            "-t",
+                            "--target",
+                            "-i",
+                            "--index-url",
+                            "--extra-index-url",
+                        ],
+                    ),
+                });
+            }
+            if matches!(program, "poetry" | "pdm") && sub == "run" {
+                return summarize_side_effect(cmd, args);
+            }
+            None
+        }
+        "pacman" if tail.iter().any(|arg| arg.starts_with("-S")) => Some(ParsedCommand::Install {
+            cmd,
+            packages: owned_operands(&tail, &[]),
+        }),
+        "uv" => {
+            let (sub, args) = split_subcommand(&tail, &[])?;
+            match sub {
+                "add" => Some(ParsedCommand::Install {
+                    cmd,
+                    packages: owned_operands(args, &[]),
+                }),
+                "pip" => {
+                    let pip: Vec<String> = std::iter::once("pip".to_string())
+                        .chain(args.iter().cloned())
+                        .collect();
+                    summarize_side_effect(cmd, &pip)
+                }
+                "run" => summarize_side_effect(cmd, args),
+                _ => None,
+            }
+        }
+        _ if is_python_command(program) => {
+            let module_idx = tail.iter().position(|arg| arg == "-m")? + 1;
+            match tail.get(module_idx)?.as_str() {
+                "pytest" | "unittest" => Some(ParsedCommand::Test { cmd }),
+                "pip" => summarize_side_effect(cmd, &tail[module_idx..]),
+                _ => None,
+            }
+        }
+        "pytest" | "py.test" | "jest" | "vitest" | "mocha" | "rspec" | "tox" | "nox"
+        | "phpunit" | "ctest" => Some(ParsedCommand::Test { cmd }),
+        "tsc" => Some(ParsedCommand::Build { cmd }),
+        "gradle" | "gradlew" | "mvn" | "mvnw" | "bazel" | "bazelisk" | "dotnet" | "swift" => {
+            match split_subcommand(&tail, &[])?.0 {
+                "test" => Some(ParsedCommand::Test { cmd }),
+                "build" | "assemble" | "compile" | "package" | "install" => {
+                    Some(ParsedCommand::Build { cmd })
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns the first positional operand (the subcommand) and the arguments
+/// following it, skipping flags and the values of `flags_with_vals`.
+fn split_subcommand<'a>(
+    args: &'a [String],
+    flags_with_vals: &[&str],
+) -> Option<(&'a str, &'a [String])> {
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        if flags_with_vals.contains(&arg.as_str()) {
+            i += 2;
+            continue;
+        }
+        // `+nightly` selects a toolchain for rustup proxies like cargo.
+        if arg.starts_with('-') || arg.starts_with('+') {
+            i += 1;
+            continue;
+        }
+        return Some((arg.as_str(), &args[i + 1..]));
+    }
+    None
+}
+
+fn owned_operands(args: &[String], flags_with_vals: &[&str]) -> Vec<String> {
+    positional_operands(args, flags_with_vals)
+        .into_iter()
+        .filter(|arg| !arg.is_empty())
+        .cloned()
+        .collect()
+}
+
+fn git_subcommand(args: &[String]) -> Option<String> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if matches!(
+            arg.as_str(),
+            "-C" | "-c" | "--git-dir" | "--work-tree" | "--namespace"
+        ) {
+            iter.next();
+            continue;
+        }
+        if !arg.starts_with('-') {
+            return Some(arg.clone());
+        }
+    }
+    None
+}
+
+/// Destination of `cp`/`mv`/`ln`: the `-t` directory or the last operand.
+fn copy_destination(args: &[String]) -> Option<String> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "-t" || arg == "--target-directory" {
+            return iter.next().cloned();
+        }
+        if let Some(dir) = arg.strip_prefix("--target-directory=") {
+            return Some(dir.to_string());
+        }
+    }
+    let operands = positional_operands(args, &["-S", "--suffix"]);
+    if operands.len() < 2 {
+        return None;
+    }
+    operands.last().map(|dest| (*dest).clone())
+}
+
+fn has_in_place_edit_flag(args: &[String]) -> bool {
+    xargs_has_in_place_flag(args) || args.iter().any(|arg| arg.starts_with("--in-place"))
+}
+
+/// Files edited by `sed -i`/`perl -pi`: every operand except the inline
+/// script, which is the first operand unless given through `-e`/`-f`.
+fn in_place_edit_paths(program: &str, args: &[String]) -> Vec<String> {
+    let script_flags: &[&str] = match program {
+        "sed" => &["-e", "--expression", "-f", "--file"],
+        _ => &["-e", "-E"],
+    };
+    let operands = owned_operands(args, script_flags);
+    let has_script_flag = args.iter().any(|arg| script_flags.contains(&arg.as_str()));
+    if has_script_flag {
+        operands
+    } else {
+        operands.into_iter().skip(1).collect()
     }
 }
 
@@ -0,0 +1,19 @@
+CREATE TABLE token_usage (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    ts INTEGER NOT NULL,
+    thread_id TEXT NOT NULL,
+    turn_id TEXT NOT NULL,
+    model TEXT NOT NULL,
+    provider TEXT NOT NULL,
+    repo TEXT,
+    cwd TEXT NOT NULL,
+    input_tokens INTEGER NOT NULL,
+    cached_input_tokens INTEGER NOT NULL,
+    output_tokens INTEGER NOT NULL,
+    reasoning_output_tokens INTEGER NOT NULL,
+    total_tokens INTEGER NOT NULL
+);
+
+CREATE INDEX idx_token_usage_ts ON token_usage(ts);
+CREATE INDEX idx_token_usage_repo_ts ON token_usage(repo, ts);
+CREATE INDEX idx_token_usage_thread_id ON token_usage(thread_id);
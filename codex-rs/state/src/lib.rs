@@ -13,8 +13,14 @@ mod runtime;
 
 pub use model::LogEntry;
 pub use model::LogQuery;
+pub use model::LogRetention;
 pub use model::LogRow;
 pub use model::Phase2JobClaimOutcome;
+pub use model::TokenUsageEntry;
+pub use model::UsageBucket;
+pub use model::UsageGroupBy;
+pub use model::UsagePeriod;
+pub use model::UsageQuery;
 /// Preferred entrypoint: owns configuration and metrics.
 pub use runtime::StateRuntime;
 
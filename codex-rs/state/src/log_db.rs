@@ -18,8 +18,6 @@
 //! # }
 //! ```
 
-use chrono::Duration as ChronoDuration;
-use chrono::Utc;
 use std::time::Duration;
 use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
@@ -35,21 +33,32 @@ use tracing_subscriber::Layer;
 use tracing_subscriber::registry::LookupSpan;
 
 use crate::LogEntry;
+use crate::LogRetention;
 use crate::StateRuntime;
 
 const LOG_QUEUE_CAPACITY: usize = 512;
 const LOG_BATCH_SIZE: usize = 64;
 const LOG_FLUSH_INTERVAL: Duration = Duration::from_millis(250);
-const LOG_RETENTION_DAYS: i64 = 90;
+/// How often retention is re-applied while a process keeps logging.
+const LOG_RETENTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
 
 pub struct LogDbLayer {
     sender: mpsc::Sender<LogEntry>,
 }
 
 pub fn start(state_db: std::sync::Arc<StateRuntime>) -> LogDbLayer {
+    start_with_retention(state_db, LogRetention::default())
+}
+
+/// Like [`start`], pruning the `logs` table with `retention` at startup and
+/// then periodically for as long as the process runs.
+pub fn start_with_retention(
+    state_db: std::sync::Arc<StateRuntime>,
+    retention: LogRetention,
+) -> LogDbLayer {
     let (sender, receiver) = mpsc::channel(LOG_QUEUE_CAPACITY);
     tokio::spawn(run_inserter(std::sync::Arc::clone(&state_db), receiver));
-    tokio::spawn(run_retention_cleanup(state_db));
+    tokio::spawn(run_retention_cleanup(state_db, retention));
 
     LogDbLayer { sender }
 }
@@ -233,12 +242,12 @@ async fn flush(state_db: &std::sync::Arc<StateRuntime>, buffer: &mut Vec<LogEntr
     let _ = state_db.insert_logs(entries.as_slice()).await;
 }
 
-async fn run_retention_cleanup(state_db: std::sync::Arc<StateRuntime>) {
-    let Some(cutoff) = Utc::now().checked_sub_signed(ChronoDuration::days(LOG_RETENTION_DAYS))
-    else {
-        return;
-    };
-    let _ = state_db.delete_logs_before(cutoff.timestamp()).await;
+async fn run_retention_cleanup(state_db: std::sync::Arc<StateRuntime>, retention: LogRetention) {
+    let mut interval = tokio::time::interval(LOG_RETENTION_INTERVAL);
+    loop {
+        interval.tick().await;
+        let _ = state_db.prune_logs(&retention).await;
+    }
 }
 
 #[derive(Default)]
@@ -23,6 +23,7 @@ pub struct LogRow {
     pub target: String,
     pub message: Option<String>,
     pub thread_id: Option<String>,
+    pub module_path: Option<String>,
     pub file: Option<String>,
     pub line: Option<i64>,
 }
@@ -40,3 +41,26 @@ pub struct LogQuery {
     pub limit: Option<usize>,
     pub descending: bool,
 }
+
+/// Limits applied to the `logs` table by [`crate::StateRuntime::prune_logs`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogRetention {
+    /// Delete entries older than this many days.
+    pub max_age_days: Option<u32>,
+    /// Keep at most this many of the newest entries.
+    pub max_rows: Option<u64>,
+}
+
+impl LogRetention {
+    pub const DEFAULT_MAX_AGE_DAYS: u32 = 90;
+    pub const DEFAULT_MAX_ROWS: u64 = 1_000_000;
+}
+
+impl Default for LogRetention {
+    fn default() -> Self {
+        Self {
+            max_age_days: Some(Self::DEFAULT_MAX_AGE_DAYS),
+            max_rows: Some(Self::DEFAULT_MAX_ROWS),
+        }
+    }
+}
@@ -2,11 +2,13 @@ mod backfill_state;
 mod log;
 mod memories;
 mod thread_metadata;
+mod usage;
 
 pub use backfill_state::BackfillState;
 pub use backfill_state::BackfillStatus;
 pub use log::LogEntry;
 pub use log::LogQuery;
+pub use log::LogRetention;
 pub use log::LogRow;
 pub use memories::Phase2JobClaimOutcome;
 pub use memories::Stage1JobClaim;
@@ -20,6 +22,11 @@ pub use thread_metadata::SortKey;
 pub use thread_metadata::ThreadMetadata;
 pub use thread_metadata::ThreadMetadataBuilder;
 pub use thread_metadata::ThreadsPage;
+pub use usage::TokenUsageEntry;
+pub use usage::UsageBucket;
+pub use usage::UsageGroupBy;
+pub use usage::UsagePeriod;
+pub use usage::UsageQuery;
 
 pub(crate) use memories::Stage1OutputRow;
 pub(crate) use thread_metadata::ThreadRow;
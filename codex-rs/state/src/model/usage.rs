@@ -0,0 +1,76 @@
+/// Token usage of one model response, recorded in the `token_usage` table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenUsageEntry {
+    pub ts: i64,
+    pub thread_id: String,
+    pub turn_id: String,
+    pub model: String,
+    pub provider: String,
+    /// Project the usage is attributed to: the root of the main git project, or the working
+    /// directory outside git.
+    pub repo: Option<String>,
+    pub cwd: String,
+    pub input_tokens: i64,
+    pub cached_input_tokens: i64,
+    pub output_tokens: i64,
+    pub reasoning_output_tokens: i64,
+    pub total_tokens: i64,
+}
+
+/// Calendar window used to bucket usage and to reset budgets (UTC).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UsagePeriod {
+    #[default]
+    Day,
+    /// Weeks start on Monday.
+    Week,
+}
+
+impl UsagePeriod {
+    const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+    /// Unix time at which the period containing `ts` starts.
+    pub fn start_of(self, ts: i64) -> i64 {
+        let day = ts.div_euclid(Self::SECONDS_PER_DAY);
+        let start_day = match self {
+            Self::Day => day,
+            // 1970-01-01 was a Thursday, three days after a Monday.
+            Self::Week => day - (day + 3).rem_euclid(7),
+        };
+        start_day * Self::SECONDS_PER_DAY
+    }
+}
+
+/// Dimension usage is broken down by.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UsageGroupBy {
+    #[default]
+    Model,
+    Repo,
+    Thread,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct UsageQuery {
+    pub from_ts: Option<i64>,
+    pub to_ts: Option<i64>,
+    pub repo: Option<String>,
+    pub model: Option<String>,
+    pub thread_id: Option<String>,
+    pub period: UsagePeriod,
+    pub group_by: UsageGroupBy,
+}
+
+/// Usage aggregated over one period for one model, repo or thread.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UsageBucket {
+    pub period_start: i64,
+    /// Model slug, thread id or project (see [`TokenUsageEntry::repo`]).
+    pub key: String,
+    pub turns: i64,
+    pub input_tokens: i64,
+    pub cached_input_tokens: i64,
+    pub output_tokens: i64,
+    pub reasoning_output_tokens: i64,
+    pub total_tokens: i64,
+}
@@ -1,6 +1,7 @@
 use crate::DB_ERROR_METRIC;
 use crate::LogEntry;
 use crate::LogQuery;
+use crate::LogRetention;
 use crate::LogRow;
 use crate::SortKey;
 use crate::ThreadMetadata;
@@ -43,6 +44,8 @@ const METRIC_DB_INIT: &str = "codex.db.init";
 
 mod memories;
 // Memory-specific CRUD and phase job lifecycle methods live in `runtime/memories.rs`.
+mod usage;
+// Token usage accounting lives in `runtime/usage.rs`.
 
 #[derive(Clone)]
 pub struct StateRuntime {
@@ -387,10 +390,54 @@ FROM threads
         Ok(result.rows_affected())
     }
 
+    /// Delete all but the newest `max_rows` log entries.
+    async fn delete_logs_beyond(&self, max_rows: u64) -> anyhow::Result<u64> {
+        let result = sqlx::query(
+            "DELETE FROM logs WHERE id <= (SELECT id FROM logs ORDER BY id DESC LIMIT 1 OFFSET ?)",
+        )
+        .bind(i64::try_from(max_rows).unwrap_or(i64::MAX))
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Apply `retention` to the logs table and return the number of deleted
+    /// rows. Freed pages stay in the database file and are reused by new rows.
+    pub async fn prune_logs(&self, retention: &LogRetention) -> anyhow::Result<u64> {
+        let mut deleted_rows = 0;
+        if let Some(max_age_days) = retention.max_age_days
+            && let Some(cutoff) =
+                Utc::now().checked_sub_signed(chrono::Duration::days(i64::from(max_age_days)))
+        {
+            deleted_rows += self.delete_logs_before(cutoff.timestamp()).await?;
+        }
+        if let Some(max_rows) = retention.max_rows {
+            deleted_rows += self.delete_logs_beyond(max_rows).await?;
+        }
+        Ok(deleted_rows)
+    }
+
+    /// Rebuild the database file with `VACUUM` when at least a quarter of it
+    /// is free pages, returning whether it did. This rewrites the whole file
+    /// while holding a write lock, so it only runs from `codex logs prune`.
+    pub async fn vacuum_if_sparse(&self) -> anyhow::Result<bool> {
+        let page_count: i64 = sqlx::query_scalar("PRAGMA page_count")
+            .fetch_one(self.pool.as_ref())
+            .await?;
+        let freelist_count: i64 = sqlx::query_scalar("PRAGMA freelist_count")
+            .fetch_one(self.pool.as_ref())
+            .await?;
+        if page_count == 0 || freelist_count.saturating_mul(4) < page_count {
+            return Ok(false);
+        }
+        sqlx::query("VACUUM").execute(self.pool.as_ref()).await?;
+        Ok(true)
+    }
+
     /// Query logs with optional filters.
     pub async fn query_logs(&self, query: &LogQuery) -> anyhow::Result<Vec<LogRow>> {
         let mut builder = QueryBuilder::<Sqlite>::new(
-            "SELECT id, ts, ts_nanos, level, target, message, thread_id, file, line FROM logs WHERE 1 = 1",
+            "SELECT id, ts, ts_nanos, level, target, message, thread_id, module_path, file, line FROM logs WHERE 1 = 1",
         );
         push_log_filters(&mut builder, query);
         if query.descending {
@@ -900,6 +947,9 @@ mod tests {
     use super::StateRuntime;
     use super::ThreadMetadata;
     use super::state_db_filename;
+    use crate::LogEntry;
+    use crate::LogQuery;
+    use crate::LogRetention;
     use crate::model::Phase2JobClaimOutcome;
     use crate::model::Stage1JobClaimOutcome;
     use crate::model::Stage1StartupClaimParams;
@@ -2125,6 +2175,92 @@ VALUES (?, ?, ?, ?, ?)
         let _ = tokio::fs::remove_dir_all(codex_home).await;
     }
 
+    #[tokio::test]
+    async fn prune_logs_drops_expired_and_excess_rows() {
+        let codex_home = unique_temp_dir();
+        let runtime = StateRuntime::init(codex_home.clone(), "test-provider".to_string(), None)
+            .await
+            .expect("initialize runtime");
+
+        let now = Utc::now().timestamp();
+        let entry = |ts: i64, message: &str| LogEntry {
+            ts,
+            ts_nanos: 0,
+            level: "INFO".to_string(),
+            target: "codex_core".to_string(),
+            message: Some(message.to_string()),
+            thread_id: None,
+            module_path: Some("codex_core::codex".to_string()),
+            file: None,
+            line: None,
+        };
+        let entries = vec![
+            entry(now - Duration::days(40).num_seconds(), "expired"),
+            entry(now - 30, "oldest kept by age"),
+            entry(now - 20, "middle"),
+            entry(now - 10, "newest"),
+        ];
+        runtime.insert_logs(&entries).await.expect("insert logs");
+
+        let deleted_rows = runtime
+            .prune_logs(&LogRetention {
+                max_age_days: Some(30),
+                max_rows: Some(2),
+            })
+            .await
+            .expect("prune logs");
+        assert_eq!(deleted_rows, 2);
+
+        let messages = runtime
+            .query_logs(&LogQuery::default())
+            .await
+            .expect("query logs")
+            .into_iter()
+            .map(|row| row.message.unwrap_or_default())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["middle".to_string(), "newest".to_string()]);
+
+        let _ = tokio::fs::remove_dir_all(codex_home).await;
+    }
+
+    #[tokio::test]
+    async fn vacuum_if_sparse_only_rebuilds_mostly_free_databases() {
+        let codex_home = unique_temp_dir();
+        let runtime = StateRuntime::init(codex_home.clone(), "test-provider".to_string(), None)
+            .await
+            .expect("initialize runtime");
+        assert!(!runtime.vacuum_if_sparse().await.expect("vacuum fresh db"));
+
+        let now = Utc::now().timestamp();
+        let entries = (0..2_000)
+            .map(|index| LogEntry {
+                ts: now,
+                ts_nanos: index,
+                level: "INFO".to_string(),
+                target: "codex_core".to_string(),
+                message: Some("x".repeat(512)),
+                thread_id: None,
+                module_path: None,
+                file: None,
+                line: None,
+            })
+            .collect::<Vec<_>>();
+        runtime.insert_logs(&entries).await.expect("insert logs");
+        let retention = LogRetention {
+            max_age_days: None,
+            max_rows: Some(1),
+        };
+        assert_eq!(
+            runtime.prune_logs(&retention).await.expect("prune logs"),
+            1_999
+        );
+
+        assert!(runtime.vacuum_if_sparse().await.expect("vacuum pruned db"));
+        assert!(!runtime.vacuum_if_sparse().await.expect("vacuum compact db"));
+
+        let _ = tokio::fs::remove_dir_all(codex_home).await;
+    }
+
     fn test_thread_metadata(
         codex_home: &Path,
         thread_id: ThreadId,
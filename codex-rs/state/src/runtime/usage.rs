@@ -0,0 +1,218 @@
+use super::*;
+use crate::model::TokenUsageEntry;
+use crate::model::UsageBucket;
+use crate::model::UsageGroupBy;
+use crate::model::UsagePeriod;
+use crate::model::UsageQuery;
+
+impl StateRuntime {
+    /// Record the token usage of one model response.
+    pub async fn record_token_usage(&self, entry: &TokenUsageEntry) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+INSERT INTO token_usage (
+    ts,
+    thread_id,
+    turn_id,
+    model,
+    provider,
+    repo,
+    cwd,
+    input_tokens,
+    cached_input_tokens,
+    output_tokens,
+    reasoning_output_tokens,
+    total_tokens
+) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(entry.ts)
+        .bind(&entry.thread_id)
+        .bind(&entry.turn_id)
+        .bind(&entry.model)
+        .bind(&entry.provider)
+        .bind(&entry.repo)
+        .bind(&entry.cwd)
+        .bind(entry.input_tokens)
+        .bind(entry.cached_input_tokens)
+        .bind(entry.output_tokens)
+        .bind(entry.reasoning_output_tokens)
+        .bind(entry.total_tokens)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Total tokens used in `repo` since `since_ts` (unix seconds).
+    pub async fn repo_tokens_since(&self, repo: &str, since_ts: i64) -> anyhow::Result<i64> {
+        let row = sqlx::query(
+            r#"
+SELECT COALESCE(SUM(total_tokens), 0) AS total
+FROM token_usage
+WHERE repo = ? AND ts >= ?
+            "#,
+        )
+        .bind(repo)
+        .bind(since_ts)
+        .fetch_one(self.pool.as_ref())
+        .await?;
+        Ok(row.try_get("total")?)
+    }
+
+    /// Usage matching `query`, summed per period and per model, project or
+    /// thread. Buckets are ordered by period, then by total tokens.
+    pub async fn usage_breakdown(&self, query: &UsageQuery) -> anyhow::Result<Vec<UsageBucket>> {
+        // Mirrors `UsagePeriod::start_of`; 1970-01-01 was a Thursday.
+        let period_start = match query.period {
+            UsagePeriod::Day => "(ts / 86400) * 86400",
+            UsagePeriod::Week => "(ts / 86400 - (ts / 86400 + 3) % 7) * 86400",
+        };
+        let key_column = match query.group_by {
+            UsageGroupBy::Model => "model",
+            UsageGroupBy::Repo => "COALESCE(repo, '')",
+            UsageGroupBy::Thread => "thread_id",
+        };
+        let mut builder = QueryBuilder::<Sqlite>::new("SELECT ");
+        builder
+            .push(period_start)
+            .push(" AS period_start, ")
+            .push(key_column)
+            .push(
+                r#" AS key,
+    COUNT(DISTINCT thread_id || ' ' || turn_id) AS turns,
+    SUM(input_tokens) AS input_tokens,
+    SUM(cached_input_tokens) AS cached_input_tokens,
+    SUM(output_tokens) AS output_tokens,
+    SUM(reasoning_output_tokens) AS reasoning_output_tokens,
+    SUM(total_tokens) AS total_tokens
+FROM token_usage
+WHERE 1 = 1"#,
+            );
+        if let Some(from_ts) = query.from_ts {
+            builder.push(" AND ts >= ").push_bind(from_ts);
+        }
+        if let Some(to_ts) = query.to_ts {
+            builder.push(" AND ts <= ").push_bind(to_ts);
+        }
+        if let Some(repo) = query.repo.as_ref() {
+            builder.push(" AND repo = ").push_bind(repo.clone());
+        }
+        if let Some(model) = query.model.as_ref() {
+            builder.push(" AND model = ").push_bind(model.clone());
+        }
+        if let Some(thread_id) = query.thread_id.as_ref() {
+            builder
+                .push(" AND thread_id = ")
+                .push_bind(thread_id.clone());
+        }
+        builder.push(
+            " GROUP BY period_start, key ORDER BY period_start ASC, total_tokens DESC, key ASC",
+        );
+        let rows = builder.build().fetch_all(self.pool.as_ref()).await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(UsageBucket {
+                    period_start: row.try_get("period_start")?,
+                    key: row.try_get("key")?,
+                    turns: row.try_get("turns")?,
+                    input_tokens: row.try_get("input_tokens")?,
+                    cached_input_tokens: row.try_get("cached_input_tokens")?,
+                    output_tokens: row.try_get("output_tokens")?,
+                    reasoning_output_tokens: row.try_get("reasoning_output_tokens")?,
+                    total_tokens: row.try_get("total_tokens")?,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn entry(ts: i64, turn_id: &str, model: &str, repo: &str, total: i64) -> TokenUsageEntry {
+        TokenUsageEntry {
+            ts,
+            thread_id: "thread-1".to_string(),
+            turn_id: turn_id.to_string(),
+            model: model.to_string(),
+            provider: "openai".to_string(),
+            repo: Some(repo.to_string()),
+            cwd: "/work".to_string(),
+            input_tokens: total - 10,
+            cached_input_tokens: 0,
+            output_tokens: 10,
+            reasoning_output_tokens: 0,
+            total_tokens: total,
+        }
+    }
+
+    #[test]
+    fn periods_start_at_utc_midnight_and_monday() {
+        // 2024-01-03T15:00:00Z is a Wednesday.
+        let ts = 1_704_294_000;
+        assert_eq!(UsagePeriod::Day.start_of(ts), 1_704_240_000);
+        assert_eq!(UsagePeriod::Week.start_of(ts), 1_704_067_200);
+        assert_eq!(UsagePeriod::Week.start_of(1_704_067_200), 1_704_067_200);
+    }
+
+    #[tokio::test]
+    async fn usage_breakdown_groups_by_period_and_key() -> anyhow::Result<()> {
+        let codex_home =
+            std::env::temp_dir().join(format!("codex-state-usage-test-{}", Uuid::new_v4()));
+        let runtime = StateRuntime::init(codex_home.clone(), "openai".to_string(), None).await?;
+
+        let monday = 1_704_067_200;
+        let day = 24 * 60 * 60;
+        for usage in [
+            entry(monday + 10, "turn-1", "gpt-5", "/repo/a", 100),
+            entry(monday + 20, "turn-1", "gpt-5", "/repo/a", 50),
+            entry(monday + 30, "turn-2", "gpt-5-mini", "/repo/b", 20),
+            entry(monday + day + 5, "turn-3", "gpt-5", "/scratch", 70),
+        ] {
+            runtime.record_token_usage(&usage).await?;
+        }
+
+        let by_model = runtime
+            .usage_breakdown(&UsageQuery {
+                period: UsagePeriod::Day,
+                group_by: UsageGroupBy::Model,
+                ..Default::default()
+            })
+            .await?;
+        let summary: Vec<(i64, &str, i64, i64)> = by_model
+            .iter()
+            .map(|b| (b.period_start, b.key.as_str(), b.turns, b.total_tokens))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (monday, "gpt-5", 1, 150),
+                (monday, "gpt-5-mini", 1, 20),
+                (monday + day, "gpt-5", 1, 70),
+            ]
+        );
+
+        let by_repo = runtime
+            .usage_breakdown(&UsageQuery {
+                period: UsagePeriod::Week,
+                group_by: UsageGroupBy::Repo,
+                ..Default::default()
+            })
+            .await?;
+        let summary: Vec<(&str, i64)> = by_repo
+            .iter()
+            .map(|b| (b.key.as_str(), b.total_tokens))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("/repo/a", 150), ("/scratch", 70), ("/repo/b", 20)]
+        );
+
+        assert_eq!(runtime.repo_tokens_since("/repo/a", monday + 15).await?, 50);
+        let _ = tokio::fs::remove_dir_all(codex_home).await;
+        Ok(())
+    }
+}
@@ -57,6 +57,7 @@ use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol::SessionSource;
 use codex_core::protocol::SkillErrorInfo;
 use codex_core::protocol::TokenUsage;
+use codex_core::remembered_approvals::RepositoryApprovals;
 #[cfg(target_os = "windows")]
 use codex_core::windows_sandbox::WindowsSandboxLevelExt;
 use codex_otel::OtelManager;
@@ -2489,6 +2490,39 @@ impl App {
             AppEvent::OpenPermissionsPopup => {
                 self.chat_widget.open_permissions_popup();
             }
+            AppEvent::OpenRememberedApprovals => {
+                match RepositoryApprovals::load(&self.config.codex_home, &self.config.cwd).await {
+                    Ok(approvals) => self.chat_widget.open_remembered_approvals_popup(&approvals),
+                    Err(err) => self
+                        .chat_widget
+                        .add_error_message(format!("Failed to read remembered approvals: {err}")),
+                }
+            }
+            AppEvent::RevokeRememberedApproval { subject } => {
+                let revoked = match RepositoryApprovals::load(
+                    &self.config.codex_home,
+                    &self.config.cwd,
+                )
+                .await
+                {
+                    Ok(mut approvals) => approvals.revoke(&subject).await,
+                    Err(err) => Err(err),
+                };
+                match revoked {
+                    Ok(_) => {
+                        self.chat_widget.submit_op(Op::ReloadRememberedApprovals);
+                        self.chat_widget.add_info_message(
+                            format!(
+                                "Codex will ask again before using {subject} in this repository."
+                            ),
+                            None,
+                        );
+                    }
+                    Err(err) => self
+                        .chat_widget
+                        .add_error_message(format!("Failed to revoke approval: {err}")),
+                }
+            }
             AppEvent::OpenReviewBranchPicker(cwd) => {
                 self.chat_widget.show_review_branch_picker(&cwd).await;
             }
@@ -2498,6 +2532,40 @@ impl App {
             AppEvent::OpenReviewCustomPrompt => {
                 self.chat_widget.show_review_custom_prompt();
             }
+            AppEvent::OpenReviewFindings => {
+                self.chat_widget.open_review_findings_popup();
+            }
+            AppEvent::OpenReviewFinding(index) => {
+                self.chat_widget.open_review_finding_popup(index);
+            }
+            AppEvent::SetReviewFindingStatus { index, status } => {
+                self.chat_widget.set_review_finding_status(index, status);
+            }
+            AppEvent::FixReviewFinding(index) => {
+                self.chat_widget.fix_review_finding(index);
+            }
+            AppEvent::ShowReviewFindingLocation(index) => {
+                self.chat_widget.show_review_finding_location(index);
+            }
+            AppEvent::ReviewFindingLocationResult(text) => {
+                let _ = tui.enter_alt_screen();
+                let pager_lines: Vec<ratatui::text::Line<'static>> =
+                    text.lines().map(ansi_escape_line).collect();
+                self.overlay = Some(Overlay::new_static_with_lines(
+                    pager_lines,
+                    "F I N D I N G".to_string(),
+                ));
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::OpenSnapshotTimeline => {
+                self.chat_widget.open_snapshot_timeline_popup();
+            }
+            AppEvent::OpenSnapshot(index) => {
+                self.chat_widget.open_snapshot_popup(index);
+            }
+            AppEvent::OpenSnapshotFiles(index) => {
+                self.chat_widget.open_snapshot_files_popup(index);
+            }
             AppEvent::SubmitUserMessageWithMode {
                 text,
                 collaboration_mode,
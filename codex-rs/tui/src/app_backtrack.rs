@@ -100,14 +100,22 @@ pub(crate) struct PendingBacktrackRollback {
 impl App {
     /// Route overlay events while the transcript overlay is active.
     ///
-    /// If backtrack preview is active, Esc / Left steps selection, Right steps forward, Enter
-    /// confirms. Otherwise, Esc begins preview mode and all other events are forwarded to the
-    /// overlay.
+    /// Keys claimed by the overlay's search prompt are forwarded first. Then, if backtrack preview
+    /// is active, Esc / Left steps selection, Right steps forward, Enter confirms. Otherwise, Esc
+    /// begins preview mode and all other events are forwarded to the overlay.
     pub(crate) async fn handle_backtrack_overlay_event(
         &mut self,
         tui: &mut tui::Tui,
         event: TuiEvent,
     ) -> Result<bool> {
+        if let TuiEvent::Key(key_event) = &event
+            && let Some(Overlay::Transcript(t)) = &self.overlay
+            && t.captures_key(*key_event)
+        {
+            // The transcript search prompt owns Esc/Enter/arrows while it is open.
+            self.overlay_forward_event(tui, event)?;
+            return Ok(true);
+        }
         if self.backtrack.overlay_preview_active {
             match event {
                 TuiEvent::Key(KeyEvent {
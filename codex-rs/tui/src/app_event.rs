@@ -14,6 +14,7 @@ use std::path::PathBuf;
 use codex_chatgpt::connectors::AppInfo;
 use codex_core::protocol::Event;
 use codex_core::protocol::RateLimitSnapshot;
+use codex_core::protocol::ReviewFindingStatus;
 use codex_file_search::FileMatch;
 use codex_protocol::ThreadId;
 use codex_protocol::openai_models::ModelPreset;
@@ -27,6 +28,7 @@ use codex_core::config::AgentRoleModelSeries;
 use codex_core::features::Feature;
 use codex_core::protocol::AskForApproval;
 use codex_core::protocol::SandboxPolicy;
+use codex_core::remembered_approvals::ApprovalSubject;
 use codex_protocol::config_types::CollaborationModeMask;
 use codex_protocol::config_types::Personality;
 use codex_protocol::openai_models::ReasoningEffort;
@@ -309,6 +311,14 @@ pub(crate) enum AppEvent {
     /// Re-open the permissions presets popup.
     OpenPermissionsPopup,
 
+    /// Show the approvals remembered for the current repository (`/permissions`).
+    OpenRememberedApprovals,
+
+    /// Forget an approval remembered for the current repository.
+    RevokeRememberedApproval {
+        subject: ApprovalSubject,
+    },
+
     /// Open the branch picker option from the review popup.
     OpenReviewBranchPicker(PathBuf),
 
@@ -318,6 +328,36 @@ pub(crate) enum AppEvent {
     /// Open the custom prompt option from the review popup.
     OpenReviewCustomPrompt,
 
+    /// Show the findings of the most recent review (`/findings`).
+    OpenReviewFindings,
+
+    /// Show the actions available for one finding of the most recent review.
+    OpenReviewFinding(usize),
+
+    /// Accept or dismiss a finding of the most recent review.
+    SetReviewFindingStatus {
+        index: usize,
+        status: ReviewFindingStatus,
+    },
+
+    /// Start a turn that fixes a single finding of the most recent review.
+    FixReviewFinding(usize),
+
+    /// Open the code and diff around a finding of the most recent review.
+    ShowReviewFindingLocation(usize),
+
+    /// Result of computing the location view of a review finding.
+    ReviewFindingLocationResult(String),
+
+    /// Show the snapshot timeline received for `/undo` again.
+    OpenSnapshotTimeline,
+
+    /// Show the restore options for one entry of the snapshot timeline.
+    OpenSnapshot(usize),
+
+    /// Pick which files to restore from one entry of the snapshot timeline.
+    OpenSnapshotFiles(usize),
+
     /// Submit a user message with an explicit collaboration mask.
     SubmitUserMessageWithMode {
         text: String,
@@ -9,6 +9,7 @@ use crate::bottom_pane::list_selection_view::ListSelectionView;
 use crate::bottom_pane::list_selection_view::SelectionItem;
 use crate::bottom_pane::list_selection_view::SelectionViewParams;
 use crate::diff_render::DiffSummary;
+use crate::exec_cell::parsed_command_effects;
 use crate::exec_command::strip_bash_lc_and_escape;
 use crate::history_cell;
 use crate::key_hint;
@@ -17,12 +18,14 @@ use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::Renderable;
 use codex_core::features::Features;
+use codex_core::protocol::DecisionProvenance;
 use codex_core::protocol::ElicitationAction;
 use codex_core::protocol::ExecPolicyAmendment;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::Op;
 use codex_core::protocol::ReviewDecision;
 use codex_protocol::mcp::RequestId;
+use codex_protocol::parse_command::ParsedCommand;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
@@ -42,7 +45,9 @@ pub(crate) enum ApprovalRequest {
         id: String,
         command: Vec<String>,
         reason: Option<String>,
+        parsed_cmd: Vec<ParsedCommand>,
         proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
+        provenance: Option<DecisionProvenance>,
     },
     ApplyPatch {
         id: String,
@@ -342,11 +347,22 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                 id,
                 command,
                 reason,
+                parsed_cmd,
                 proposed_execpolicy_amendment,
+                provenance,
+                provenance: None,
             } => {
                 let mut header: Vec<Line<'static>> = Vec::new();
                 if let Some(reason) = reason {
                     header.push(Line::from(vec!["Reason: ".into(), reason.italic()]));
+                }
+                if let Some(provenance) = provenance.as_ref() {
+                    header.push(Line::from(vec![
+                        "Why: ".into(),
+                        describe_decision_provenance(provenance).dim(),
+                    ]));
+                }
+                if !header.is_empty() {
                     header.push(Line::from(""));
                 }
                 let full_cmd = strip_bash_lc_and_escape(&command);
@@ -355,6 +371,14 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                     first.spans.insert(0, Span::from("$ "));
                 }
                 header.extend(full_cmd_lines);
+                let effects = parsed_command_effects(&parsed_cmd);
+                if !effects.is_empty() {
+                    header.push(Line::from(""));
+                    header.push(Line::from(vec![
+                        "This command ".into(),
+                        effects.join(", then ").bold(),
+                    ]));
+                }
                 Self {
                     variant: ApprovalVariant::Exec {
                         id,
@@ -409,6 +433,46 @@ impl From<ApprovalRequest> for ApprovalRequestState {
     }
 }
 
+/// One-line explanation of what asked for this approval.
+fn describe_decision_provenance(provenance: &DecisionProvenance) -> String {
+    match provenance {
+        DecisionProvenance::KnownSafeCommand => "known read-only command".to_string(),
+        DecisionProvenance::DangerousCommand => {
+            "command was flagged as potentially destructive".to_string()
+        }
+        DecisionProvenance::ExecPolicyRule {
+            matched_prefix,
+            file,
+            line,
+            ..
+        } => {
+            let rule = format!(
+                "matches rule `{}`",
+                strip_bash_lc_and_escape(matched_prefix)
+            );
+            match (file, line) {
+                (Some(file), Some(line)) => format!("{rule} ({file}:{line})"),
+                (Some(file), None) => format!("{rule} ({file})"),
+                (None, _) => rule,
+            }
+        }
+        DecisionProvenance::SessionApproval => "approved earlier in this session".to_string(),
+        DecisionProvenance::SandboxDenialRetry => {
+            "command failed in the sandbox; retrying without it".to_string()
+        }
+        DecisionProvenance::ApprovalPolicy { approval_policy } => {
+            format!("approval policy is `{approval_policy}`")
+        }
+        DecisionProvenance::ToolConfig { tool, always_ask } => {
+            if *always_ask {
+                format!("tool `{tool}` is configured to always ask")
+            } else {
+                format!("tool `{tool}` is configured to never ask")
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 enum ApprovalVariant {
     Exec {
@@ -455,22 +519,34 @@ fn exec_options(proposed_execpolicy_amendment: Option<ExecPolicyAmendment>) -> V
         additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
     }]
     .into_iter()
-    .chain(proposed_execpolicy_amendment.and_then(|prefix| {
+    .chain(proposed_execpolicy_amendment.into_iter().flat_map(|prefix| {
         let rendered_prefix = strip_bash_lc_and_escape(prefix.command());
         if rendered_prefix.contains('\n') || rendered_prefix.contains('\r') {
-            return None;
+            return Vec::new();
         }
 
-        Some(ApprovalOption {
-            label: format!(
-                "Yes, and don't ask again for commands that start with `{rendered_prefix}`"
-            ),
-            decision: ApprovalDecision::Review(ReviewDecision::ApprovedExecpolicyAmendment {
-                proposed_execpolicy_amendment: prefix,
-            }),
-            display_shortcut: None,
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('p'))],
-        })
+        vec![
+            ApprovalOption {
+                label: format!(
+                    "Yes, and don't ask again for commands that start with `{rendered_prefix}`"
+                ),
+                decision: ApprovalDecision::Review(ReviewDecision::ApprovedExecpolicyAmendment {
+                    proposed_execpolicy_amendment: prefix.clone(),
+                }),
+                display_shortcut: None,
+                additional_shortcuts: vec![key_hint::plain(KeyCode::Char('p'))],
+            },
+            ApprovalOption {
+                label: format!(
+                    "No, and always refuse commands that start with `{rendered_prefix}`"
+                ),
+                decision: ApprovalDecision::Review(ReviewDecision::DeniedExecpolicyAmendment {
+                    proposed_execpolicy_amendment: prefix,
+                }),
+                display_shortcut: None,
+                additional_shortcuts: vec![key_hint::plain(KeyCode::Char('d'))],
+            },
+        ]
     }))
     .chain([ApprovalOption {
         label: "No, and tell Codex what to do differently".to_string(),
@@ -539,7 +615,9 @@ mod tests {
             id: "test".to_string(),
             command: vec!["echo".to_string(), "hi".to_string()],
             reason: Some("reason".to_string()),
+            parsed_cmd: Vec::new(),
             proposed_execpolicy_amendment: None,
+            provenance: None,
         }
     }
 
@@ -581,9 +659,11 @@ mod tests {
                 id: "test".to_string(),
                 command: vec!["echo".to_string()],
                 reason: None,
+                parsed_cmd: Vec::new(),
                 proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec![
                     "echo".to_string(),
                 ])),
+                provenance: None,
             },
             tx,
             Features::with_defaults(),
@@ -610,6 +690,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn exec_deny_prefix_option_emits_denied_amendment() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let mut view = ApprovalOverlay::new(
+            ApprovalRequest::Exec {
+                id: "test".to_string(),
+                command: vec!["rm".to_string(), "-rf".to_string(), "build".to_string()],
+                reason: None,
+                parsed_cmd: Vec::new(),
+                proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec![
+                    "rm".to_string(),
+                ])),
+                provenance: None,
+            },
+            tx,
+            Features::with_defaults(),
+        );
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE));
+        let mut saw_op = false;
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::CodexOp(Op::ExecApproval { decision, .. }) = ev {
+                assert_eq!(
+                    decision,
+                    ReviewDecision::DeniedExecpolicyAmendment {
+                        proposed_execpolicy_amendment: ExecPolicyAmendment::new(vec![
+                            "rm".to_string()
+                        ])
+                    }
+                );
+                saw_op = true;
+                break;
+            }
+        }
+        assert!(
+            saw_op,
+            "expected deny decision to emit an op with command prefix"
+        );
+    }
+
     #[test]
     fn header_includes_command_snippet() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
@@ -619,7 +739,9 @@ mod tests {
             id: "test".into(),
             command,
             reason: None,
+            parsed_cmd: Vec::new(),
             proposed_execpolicy_amendment: None,
+            provenance: None,
         };
 
         let view = ApprovalOverlay::new(exec_request, tx, Features::with_defaults());
@@ -641,6 +763,88 @@ mod tests {
         );
     }
 
+    #[test]
+    fn header_summarizes_command_effects() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let exec_request = ApprovalRequest::Exec {
+            id: "test".into(),
+            command: vec![
+                "bash".into(),
+                "-lc".into(),
+                "npm install react > install.log".into(),
+            ],
+            reason: None,
+            parsed_cmd: vec![
+                ParsedCommand::Install {
+                    cmd: "npm install react".into(),
+                    packages: vec!["react".into()],
+                },
+                ParsedCommand::Write {
+                    cmd: "npm install react > install.log".into(),
+                    paths: vec!["install.log".into()],
+                },
+            ],
+            proposed_execpolicy_amendment: None,
+            provenance: None,
+        };
+
+        let view = ApprovalOverlay::new(exec_request, tx, Features::with_defaults());
+        let mut buf = Buffer::empty(Rect::new(0, 0, 80, view.desired_height(80)));
+        view.render(Rect::new(0, 0, 80, view.desired_height(80)), &mut buf);
+
+        let rendered: Vec<String> = (0..buf.area.height)
+            .map(|row| {
+                (0..buf.area.width)
+                    .map(|col| buf[(col, row)].symbol().to_string())
+                    .collect()
+            })
+            .collect();
+        assert!(
+            rendered
+                .iter()
+                .any(|line| line.contains("This command installs react, then writes install.log")),
+            "expected header to summarize effects, got {rendered:?}"
+        );
+    }
+
+    #[test]
+    fn header_explains_decision_provenance() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let exec_request = ApprovalRequest::Exec {
+            id: "test".into(),
+            command: vec!["git".into(), "push".into()],
+            reason: None,
+            parsed_cmd: Vec::new(),
+            proposed_execpolicy_amendment: None,
+            provenance: Some(DecisionProvenance::ExecPolicyRule {
+                matched_prefix: vec!["git".into(), "push".into()],
+                justification: None,
+                file: Some("team.rules".into()),
+                line: Some(3),
+            }),
+        };
+
+        let view = ApprovalOverlay::new(exec_request, tx, Features::with_defaults());
+        let mut buf = Buffer::empty(Rect::new(0, 0, 80, view.desired_height(80)));
+        view.render(Rect::new(0, 0, 80, view.desired_height(80)), &mut buf);
+
+        let rendered: Vec<String> = (0..buf.area.height)
+            .map(|row| {
+                (0..buf.area.width)
+                    .map(|col| buf[(col, row)].symbol().to_string())
+                    .collect()
+            })
+            .collect();
+        assert!(
+            rendered
+                .iter()
+                .any(|line| line.contains("Why: matches rule `git push` (team.rules:3)")),
+            "expected header to explain provenance, got {rendered:?}"
+        );
+    }
+
     #[test]
     fn exec_history_cell_wraps_with_two_space_indent() {
         let command = vec![
@@ -43,6 +43,7 @@ mod app_link_view;
 mod approval_overlay;
 mod multi_select_picker;
 mod request_user_input;
+mod snapshot_files_view;
 mod status_line_setup;
 pub(crate) use agent_spawn_config_view::AgentSpawnConfigView;
 pub(crate) use app_link_view::AppLinkView;
@@ -86,6 +87,7 @@ pub(crate) use feedback_view::feedback_selection_params;
 pub(crate) use feedback_view::feedback_upload_consent_params;
 pub(crate) use skills_toggle_view::SkillsToggleItem;
 pub(crate) use skills_toggle_view::SkillsToggleView;
+pub(crate) use snapshot_files_view::SnapshotFilesView;
 pub(crate) use status_line_setup::StatusLineItem;
 pub(crate) use status_line_setup::StatusLineSetupView;
 mod paste_burst;
@@ -991,7 +993,9 @@ mod tests {
             id: "1".to_string(),
             command: vec!["echo".into(), "ok".into()],
             reason: None,
+            parsed_cmd: Vec::new(),
             proposed_execpolicy_amendment: None,
+            provenance: None,
         }
     }
 
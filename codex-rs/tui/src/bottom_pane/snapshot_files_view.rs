@@ -0,0 +1,92 @@
+//! Picker for restoring only some files from a ghost snapshot.
+//!
+//! Lists the files that changed after the snapshot was captured, and restores the files the
+//! user selects to their state in that snapshot. Files that are not selected keep their
+//! current contents.
+
+use std::path::PathBuf;
+
+use codex_core::protocol::Op;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::bottom_pane::CancellationEvent;
+use crate::bottom_pane::bottom_pane_view::BottomPaneView;
+use crate::bottom_pane::multi_select_picker::MultiSelectItem;
+use crate::bottom_pane::multi_select_picker::MultiSelectPicker;
+use crate::render::renderable::Renderable;
+
+/// Wraps a [`MultiSelectPicker`] over the files changed since a snapshot. Confirming submits
+/// [`Op::RestoreSnapshot`] limited to the selected files.
+pub(crate) struct SnapshotFilesView {
+    picker: MultiSelectPicker,
+}
+
+impl SnapshotFilesView {
+    /// `files` pairs each path, relative to the session cwd, with a short description.
+    pub(crate) fn new(
+        title: String,
+        snapshot_id: String,
+        files: Vec<(PathBuf, String)>,
+        app_event_tx: AppEventSender,
+    ) -> Self {
+        let items = files
+            .into_iter()
+            .map(|(path, description)| MultiSelectItem {
+                id: path.to_string_lossy().into_owned(),
+                name: path.display().to_string(),
+                description: Some(description),
+                enabled: false,
+            })
+            .collect();
+
+        Self {
+            picker: MultiSelectPicker::builder(
+                title,
+                Some("Selected files go back to their state in the snapshot.".to_string()),
+                app_event_tx,
+            )
+            .instructions(vec![
+                "Use ↑↓ to navigate, space to select, enter to restore, esc to cancel.".into(),
+            ])
+            .items(items)
+            .on_confirm(move |ids, app_event| {
+                if ids.is_empty() {
+                    return;
+                }
+                app_event.send(AppEvent::CodexOp(Op::RestoreSnapshot {
+                    snapshot_id: snapshot_id.clone(),
+                    paths: ids.iter().map(PathBuf::from).collect(),
+                }));
+            })
+            .build(),
+        }
+    }
+}
+
+impl BottomPaneView for SnapshotFilesView {
+    fn handle_key_event(&mut self, key_event: crossterm::event::KeyEvent) {
+        self.picker.handle_key_event(key_event);
+    }
+
+    fn is_complete(&self) -> bool {
+        self.picker.complete
+    }
+
+    fn on_ctrl_c(&mut self) -> CancellationEvent {
+        self.picker.close();
+        CancellationEvent::Handled
+    }
+}
+
+impl Renderable for SnapshotFilesView {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        self.picker.render(area, buf)
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        self.picker.desired_height(width)
+    }
+}
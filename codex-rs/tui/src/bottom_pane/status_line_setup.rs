@@ -9,7 +9,7 @@
 //!
 //! # Available Status Line Items
 //!
-//! - Model information (name, reasoning level)
+//! - Model information (name, reasoning level, provider)
 //! - Directory paths (current dir, project root)
 //! - Git information (branch name)
 //! - Context usage (remaining %, used %, window size)
@@ -53,6 +53,10 @@ pub(crate) enum StatusLineItem {
     /// Model name with reasoning level suffix.
     ModelWithReasoning,
 
+    /// Model provider currently serving the session, marked when a failover
+    /// chain has moved away from the configured provider.
+    ModelProvider,
+
     /// Current working directory path.
     CurrentDir,
 
@@ -99,6 +103,9 @@ impl StatusLineItem {
         match self {
             StatusLineItem::ModelName => "Current model name",
             StatusLineItem::ModelWithReasoning => "Current model name with reasoning level",
+            StatusLineItem::ModelProvider => {
+                "Current model provider (marked while failed over to a fallback)"
+            }
             StatusLineItem::CurrentDir => "Current working directory",
             StatusLineItem::ProjectRoot => "Project root directory (omitted when unavailable)",
             StatusLineItem::GitBranch => "Current Git branch (omitted when unavailable)",
@@ -135,6 +142,7 @@ impl StatusLineItem {
         match self {
             StatusLineItem::ModelName => "gpt-5.2-codex",
             StatusLineItem::ModelWithReasoning => "gpt-5.2-codex medium",
+            StatusLineItem::ModelProvider => "openai",
             StatusLineItem::CurrentDir => "~/project/path",
             StatusLineItem::ProjectRoot => "~/project",
             StatusLineItem::GitBranch => "feat/awesome-feature",
@@ -81,6 +81,7 @@ use codex_core::protocol::ExecCommandEndEvent;
 use codex_core::protocol::ExecCommandOutputDeltaEvent;
 use codex_core::protocol::ExecCommandSource;
 use codex_core::protocol::ExitedReviewModeEvent;
+use codex_core::protocol::FailoverTrigger;
 use codex_core::protocol::ListCustomPromptsResponseEvent;
 use codex_core::protocol::ListSkillsResponseEvent;
 use codex_core::protocol::McpListToolsResponseEvent;
@@ -92,10 +93,13 @@ use codex_core::protocol::McpToolCallEndEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PreparedContextResponseEvent;
+use codex_core::protocol::ProviderFailoverEvent;
 use codex_core::protocol::RateLimitSnapshot;
+use codex_core::protocol::ReviewFindingStatusEvent;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::SkillMetadata as ProtocolSkillMetadata;
+use codex_core::protocol::SnapshotTimelineEvent;
 use codex_core::protocol::StreamErrorEvent;
 use codex_core::protocol::TerminalInteractionEvent;
 use codex_core::protocol::TokenUsage;
@@ -110,6 +114,9 @@ use codex_core::protocol::ViewImageToolCallEvent;
 use codex_core::protocol::WarningEvent;
 use codex_core::protocol::WebSearchBeginEvent;
 use codex_core::protocol::WebSearchEndEvent;
+use codex_core::remembered_approvals::RememberedApproval;
+use codex_core::remembered_approvals::RememberedDecision;
+use codex_core::remembered_approvals::RepositoryApprovals;
 use codex_core::skills::model::SkillMetadata;
 #[cfg(target_os = "windows")]
 use codex_core::windows_sandbox::WindowsSandboxLevelExt;
@@ -221,7 +228,10 @@ use self::agent::spawn_agent_from_existing;
 pub(crate) use self::agent::spawn_op_forwarder;
 mod session_header;
 use self::session_header::SessionHeader;
+mod review_findings;
+use self::review_findings::TrackedReviewFinding;
 mod skills;
+mod snapshot_timeline;
 use self::skills::collect_tool_mentions;
 use self::skills::find_app_mentions;
 use self::skills::find_skill_mentions_with_tool_mentions;
@@ -327,9 +337,14 @@ fn is_unified_exec_source(source: ExecCommandSource) -> bool {
 
 fn is_standard_tool_call(parsed_cmd: &[ParsedCommand]) -> bool {
     !parsed_cmd.is_empty()
-        && parsed_cmd
-            .iter()
-            .all(|parsed| !matches!(parsed, ParsedCommand::Unknown { .. }))
+        && parsed_cmd.iter().all(|parsed| {
+            matches!(
+                parsed,
+                ParsedCommand::Read { .. }
+                    | ParsedCommand::ListFiles { .. }
+                    | ParsedCommand::Search { .. }
+            )
+        })
 }
 
 const RATE_LIMIT_WARNING_THRESHOLDS: [f64; 3] = [75.0, 90.0, 95.0];
@@ -592,6 +607,13 @@ pub(crate) struct ChatWidget {
     is_review_mode: bool,
     // Snapshot of token usage to restore after review mode exits.
     pre_review_token_info: Option<Option<TokenUsageInfo>>,
+    // Findings of the most recent review, with the decision recorded for each.
+    review_findings: Vec<TrackedReviewFinding>,
+    // Latest accept/dismiss decision per finding in this thread, rebuilt from the replayed
+    // rollout on resume, so findings a later review reports again keep their status.
+    review_finding_decisions: Vec<ReviewFindingStatusEvent>,
+    // Latest snapshot timeline received for `/undo`.
+    snapshot_timeline: Option<SnapshotTimelineEvent>,
     // Whether the next streamed assistant content should be preceded by a final message separator.
     //
     // This is set whenever we insert a visible history cell that conceptually belongs to a turn.
@@ -607,6 +629,8 @@ pub(crate) struct ChatWidget {
     saw_plan_update_this_turn: bool,
     // Whether the current turn emitted a proposed plan item.
     saw_plan_item_this_turn: bool,
+    // Length of the current turn's external-edit diff already shown in history.
+    shown_external_diff_len: usize,
     // Incremental buffer for streamed plan content.
     plan_delta_buffer: String,
     // True while a plan item is streaming.
@@ -632,6 +656,8 @@ pub(crate) struct ChatWidget {
     status_line_invalid_items_warned: Arc<AtomicBool>,
     // Cached git branch name for the status line (None if unknown).
     status_line_branch: Option<String>,
+    // Fallback provider serving turns after a provider failover (None on the configured provider).
+    failover_provider: Option<String>,
     // CWD used to resolve the cached branch; change resets branch state.
     status_line_branch_cwd: Option<PathBuf>,
     // True while an async branch lookup is in flight.
@@ -1343,6 +1369,7 @@ impl ChatWidget {
         self.agent_turn_running = true;
         self.saw_plan_update_this_turn = false;
         self.saw_plan_item_this_turn = false;
+        self.shown_external_diff_len = 0;
         self.plan_delta_buffer.clear();
         self.plan_item_active = false;
         self.adaptive_chunking.reset();
@@ -2163,8 +2190,19 @@ impl ChatWidget {
         self.request_immediate_exit();
     }
 
-    fn on_turn_diff(&mut self, unified_diff: String) {
+    fn on_turn_diff(&mut self, unified_diff: String, external_unified_diff: Option<String>) {
         debug!("TurnDiffEvent: {unified_diff}");
+        // The external diff only grows during a turn; show the newly observed edits.
+        if let Some(external) = external_unified_diff
+            && let Some(new_edits) = external.get(self.shown_external_diff_len..)
+            && !new_edits.is_empty()
+        {
+            self.add_to_history(history_cell::new_external_diff_event(
+                new_edits.to_string(),
+                &self.config.cwd,
+            ));
+            self.shown_external_diff_len = external.len();
+        }
         self.refresh_status_line();
     }
 
@@ -2207,6 +2245,37 @@ impl ChatWidget {
         }
     }
 
+    fn on_provider_failover(&mut self, ev: ProviderFailoverEvent) {
+        let ProviderFailoverEvent {
+            from_provider,
+            from_model,
+            to_provider,
+            to_model,
+            trigger,
+            ..
+        } = ev;
+        self.failover_provider =
+            (to_provider != self.config.model_provider_id).then(|| to_provider.clone());
+        match trigger {
+            Some(trigger) => {
+                let reason = match trigger {
+                    FailoverTrigger::RateLimit => "a rate limit",
+                    FailoverTrigger::ServerError => "a server error",
+                    FailoverTrigger::StreamIdleTimeout => "a stream timeout",
+                    FailoverTrigger::ContextWindowExceeded => "exceeding the context window",
+                };
+                self.on_warning(format!(
+                    "Switched from {from_provider}/{from_model} to {to_provider}/{to_model} after {reason}."
+                ));
+            }
+            None => self.add_info_message(
+                format!("Switched back to {to_provider}/{to_model} after its cool-down."),
+                None,
+            ),
+        }
+        self.refresh_status_line();
+    }
+
     fn on_stream_error(&mut self, message: String, additional_details: Option<String>) {
         if self.retry_status_header.is_none() {
             self.retry_status_header = Some(self.current_status_header.clone());
@@ -2437,7 +2506,9 @@ impl ChatWidget {
             id: ev.call_id,
             command: ev.command,
             reason: ev.reason,
+            parsed_cmd: ev.parsed_cmd,
             proposed_execpolicy_amendment: ev.proposed_execpolicy_amendment,
+            provenance: ev.provenance,
         };
         self.bottom_pane
             .push_approval_request(request, &self.config.features);
@@ -2706,10 +2777,14 @@ impl ChatWidget {
             quit_shortcut_key: None,
             is_review_mode: false,
             pre_review_token_info: None,
+            review_findings: Vec::new(),
+            review_finding_decisions: Vec::new(),
+            snapshot_timeline: None,
             needs_final_message_separator: false,
             had_work_activity: false,
             saw_plan_update_this_turn: false,
             saw_plan_item_this_turn: false,
+            shown_external_diff_len: 0,
             plan_delta_buffer: String::new(),
             plan_item_active: false,
             last_separator_elapsed_secs: None,
@@ -2722,6 +2797,7 @@ impl ChatWidget {
             session_network_proxy: None,
             status_line_invalid_items_warned,
             status_line_branch: None,
+            failover_provider: None,
             status_line_branch_cwd: None,
             status_line_branch_pending: false,
             status_line_branch_lookup_complete: false,
@@ -2865,6 +2941,7 @@ impl ChatWidget {
             forked_from: None,
             saw_plan_update_this_turn: false,
             saw_plan_item_this_turn: false,
+            shown_external_diff_len: 0,
             plan_delta_buffer: String::new(),
             plan_item_active: false,
             queued_user_messages: VecDeque::new(),
@@ -2875,6 +2952,9 @@ impl ChatWidget {
             quit_shortcut_key: None,
             is_review_mode: false,
             pre_review_token_info: None,
+            review_findings: Vec::new(),
+            review_finding_decisions: Vec::new(),
+            snapshot_timeline: None,
             needs_final_message_separator: false,
             had_work_activity: false,
             last_separator_elapsed_secs: None,
@@ -2887,6 +2967,7 @@ impl ChatWidget {
             session_network_proxy: None,
             status_line_invalid_items_warned,
             status_line_branch: None,
+            failover_provider: None,
             status_line_branch_cwd: None,
             status_line_branch_pending: false,
             status_line_branch_lookup_complete: false,
@@ -3025,10 +3106,14 @@ impl ChatWidget {
             quit_shortcut_key: None,
             is_review_mode: false,
             pre_review_token_info: None,
+            review_findings: Vec::new(),
+            review_finding_decisions: Vec::new(),
+            snapshot_timeline: None,
             needs_final_message_separator: false,
             had_work_activity: false,
             saw_plan_update_this_turn: false,
             saw_plan_item_this_turn: false,
+            shown_external_diff_len: 0,
             plan_delta_buffer: String::new(),
             plan_item_active: false,
             last_separator_elapsed_secs: None,
@@ -3041,6 +3126,7 @@ impl ChatWidget {
             session_network_proxy: None,
             status_line_invalid_items_warned,
             status_line_branch: None,
+            failover_provider: None,
             status_line_branch_cwd: None,
             status_line_branch_pending: false,
             status_line_branch_lookup_complete: false,
@@ -3385,7 +3471,7 @@ impl ChatWidget {
                 self.open_permissions_popup();
             }
             SlashCommand::Permissions => {
-                self.open_permissions_popup();
+                self.app_event_tx.send(AppEvent::OpenRememberedApprovals);
             }
             SlashCommand::ElevateSandbox => {
                 #[cfg(target_os = "windows")]
@@ -3447,9 +3533,12 @@ impl ChatWidget {
                 }
                 self.request_quit_without_confirmation();
             }
-            // SlashCommand::Undo => {
-            //     self.app_event_tx.send(AppEvent::CodexOp(Op::Undo));
-            // }
+            SlashCommand::Undo => {
+                self.submit_op(Op::ListSnapshots);
+            }
+            SlashCommand::Redo => {
+                self.submit_op(Op::Redo);
+            }
             SlashCommand::Diff => {
                 self.add_diff_in_progress();
                 let tx = self.app_event_tx.clone();
@@ -3467,6 +3556,9 @@ impl ChatWidget {
                     tx.send(AppEvent::DiffResult(text));
                 });
             }
+            SlashCommand::Findings => {
+                self.open_review_findings_popup();
+            }
             SlashCommand::Mention => {
                 self.insert_str("@");
             }
@@ -4326,18 +4418,23 @@ impl ChatWidget {
                 });
             }
             EventMsg::ShutdownComplete => self.on_shutdown_complete(),
-            EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => self.on_turn_diff(unified_diff),
+            EventMsg::TurnDiff(TurnDiffEvent {
+                unified_diff,
+                external_unified_diff,
+            }) => self.on_turn_diff(unified_diff, external_unified_diff),
             EventMsg::DeprecationNotice(ev) => self.on_deprecation_notice(ev),
             EventMsg::BackgroundEvent(BackgroundEventEvent { message }) => {
                 self.on_background_event(message)
             }
             EventMsg::UndoStarted(ev) => self.on_undo_started(ev),
             EventMsg::UndoCompleted(ev) => self.on_undo_completed(ev),
+            EventMsg::SnapshotTimeline(ev) => self.on_snapshot_timeline(ev),
             EventMsg::StreamError(StreamErrorEvent {
                 message,
                 additional_details,
                 ..
             }) => self.on_stream_error(message, additional_details),
+            EventMsg::ProviderFailover(ev) => self.on_provider_failover(ev),
             EventMsg::UserMessage(ev) => {
                 if from_replay {
                     self.on_user_message_event(ev);
@@ -4347,6 +4444,7 @@ impl ChatWidget {
                 self.on_entered_review_mode(review_request, from_replay)
             }
             EventMsg::ExitedReviewMode(review) => self.on_exited_review_mode(review),
+            EventMsg::ReviewFindingStatus(ev) => self.on_review_finding_status(ev),
             EventMsg::ContextCompacted(_) => self.on_agent_message("Context compacted".to_owned()),
             EventMsg::CollabAgentSpawnBegin(_) => {}
             EventMsg::CollabAgentSpawnEnd(ev) => self.on_collab_event(collab::spawn_end(ev)),
@@ -4413,6 +4511,7 @@ impl ChatWidget {
             self.flush_answer_stream_with_separator();
             self.flush_interrupt_queue();
             self.flush_active_cell();
+            self.track_review_findings(&output.findings);
 
             if output.findings.is_empty() {
                 let explanation = output.overall_explanation.trim().to_string();
@@ -4439,6 +4538,15 @@ impl ChatWidget {
         self.add_to_history(history_cell::new_review_status_line(
             "<< Code review finished >>".to_string(),
         ));
+        if !self.review_findings.is_empty() {
+            self.add_info_message(
+                format!(
+                    "The review reported {} findings.",
+                    self.review_findings.len()
+                ),
+                Some("Use /findings to open, fix, accept or dismiss them.".to_string()),
+            );
+        }
         self.request_redraw();
     }
 
@@ -4684,6 +4792,10 @@ impl ChatWidget {
                     Self::status_line_reasoning_effort_label(self.effective_reasoning_effort());
                 Some(format!("{} {label}", self.model_display_name()))
             }
+            StatusLineItem::ModelProvider => Some(match &self.failover_provider {
+                Some(provider) => format!("{provider} (failover)"),
+                None => self.config.model_provider_id.clone(),
+            }),
             StatusLineItem::CurrentDir => {
                 Some(format_directory_display(self.status_line_cwd(), None))
             }
@@ -5555,7 +5667,7 @@ impl ChatWidget {
         );
     }
 
-    /// Open the permissions popup (alias for /permissions).
+    /// Open the permissions popup (alias for /approvals).
     pub(crate) fn open_approvals_popup(&mut self) {
         self.open_permissions_popup();
     }
@@ -5688,6 +5800,49 @@ impl ChatWidget {
         });
     }
 
+    /// Show the approvals remembered for the current repository. Selecting one revokes it.
+    pub(crate) fn open_remembered_approvals_popup(&mut self, approvals: &RepositoryApprovals) {
+        let mut items: Vec<SelectionItem> = approvals
+            .entries()
+            .iter()
+            .map(|entry| {
+                let subject = entry.subject.clone();
+                SelectionItem {
+                    name: entry.subject.to_string(),
+                    description: Some(describe_remembered_approval(entry)),
+                    selected_description: Some("Press enter to revoke.".to_string()),
+                    actions: vec![Box::new(move |tx| {
+                        tx.send(AppEvent::RevokeRememberedApproval {
+                            subject: subject.clone(),
+                        });
+                    })],
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+        items.push(SelectionItem {
+            name: "Approval mode".to_string(),
+            description: Some("Choose what Codex can do without approval.".to_string()),
+            actions: vec![Box::new(|tx| tx.send(AppEvent::OpenApprovalsPopup))],
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+
+        let subtitle = if approvals.entries().is_empty() {
+            format!("Nothing is remembered for {} yet.", approvals.repository())
+        } else {
+            format!("Remembered for {}.", approvals.repository())
+        };
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Permissions".to_string()),
+            subtitle: Some(subtitle),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
     pub(crate) fn open_experimental_popup(&mut self) {
         let features: Vec<ExperimentalFeatureItem> = FEATURES
             .iter()
@@ -7404,6 +7559,18 @@ const PLACEHOLDERS: [&str; 8] = [
     "Use /skills to list available skills",
 ];
 
+/// "Allowed" or "Denied", plus the expiry date when the decision has one.
+fn describe_remembered_approval(entry: &RememberedApproval) -> String {
+    let decision = match entry.decision {
+        RememberedDecision::Allow => "Allowed",
+        RememberedDecision::Deny => "Denied",
+    };
+    match entry.expires_at {
+        Some(expires_at) => format!("{decision} until {}", expires_at.format("%Y-%m-%d")),
+        None => decision.to_string(),
+    }
+}
+
 // Extract the first bold (Markdown) element in the form **...** from `s`.
 // Returns the inner text if found; otherwise `None`.
 fn extract_first_bold(s: &str) -> Option<String> {
@@ -0,0 +1,301 @@
+//! Interactive list of the findings reported by the most recent review.
+//!
+//! Each finding can be opened at its location, fixed in a dedicated turn, or accepted or
+//! dismissed. Decisions are sent to core, which records them in the rollout so later reviews
+//! in the thread skip what the user dismissed.
+
+use std::path::Path;
+
+use super::ChatWidget;
+use crate::app_event::AppEvent;
+use crate::bottom_pane::SelectionAction;
+use crate::bottom_pane::SelectionItem;
+use crate::bottom_pane::SelectionViewParams;
+use crate::bottom_pane::popup_consts::standard_popup_hint_line;
+use crate::get_git_diff::get_git_diff_for_path;
+use codex_core::protocol::Op;
+use codex_core::protocol::ReviewFinding;
+use codex_core::protocol::ReviewFindingStatus;
+use codex_core::protocol::ReviewFindingStatusEvent;
+use codex_core::review_prompts::is_same_finding;
+
+/// Lines of file content shown before and after a finding's line range.
+const LOCATION_CONTEXT_LINES: u32 = 3;
+
+/// A finding of the most recent review and the decision recorded for it, if any.
+#[derive(Debug, Clone)]
+pub(crate) struct TrackedReviewFinding {
+    pub(crate) finding: ReviewFinding,
+    pub(crate) status: Option<ReviewFindingStatus>,
+}
+
+impl ChatWidget {
+    /// Replaces the tracked findings with those of a review that just finished, keeping the
+    /// decisions already made on findings it reports again.
+    pub(super) fn track_review_findings(&mut self, findings: &[ReviewFinding]) {
+        self.review_findings = findings
+            .iter()
+            .map(|finding| TrackedReviewFinding {
+                finding: finding.clone(),
+                status: self
+                    .review_finding_decisions
+                    .iter()
+                    .find(|decision| is_same_finding(&decision.finding, finding))
+                    .map(|decision| decision.status),
+            })
+            .collect();
+    }
+
+    pub(super) fn on_review_finding_status(&mut self, event: ReviewFindingStatusEvent) {
+        for tracked in &mut self.review_findings {
+            if is_same_finding(&tracked.finding, &event.finding) {
+                tracked.status = Some(event.status);
+            }
+        }
+        self.review_finding_decisions
+            .retain(|decision| !is_same_finding(&decision.finding, &event.finding));
+        self.review_finding_decisions.push(event);
+        self.request_redraw();
+    }
+
+    pub(crate) fn open_review_findings_popup(&mut self) {
+        if self.review_findings.is_empty() {
+            self.add_info_message(
+                "The last review did not report any findings.".to_string(),
+                Some("Run /review to start a review.".to_string()),
+            );
+            return;
+        }
+
+        let items: Vec<SelectionItem> = self
+            .review_findings
+            .iter()
+            .enumerate()
+            .map(|(index, tracked)| {
+                let location = finding_location(&tracked.finding, &self.config.cwd);
+                let description = match tracked.status {
+                    Some(ReviewFindingStatus::Accepted) => format!("{location} · accepted"),
+                    Some(ReviewFindingStatus::Dismissed) => format!("{location} · dismissed"),
+                    None => location,
+                };
+                SelectionItem {
+                    name: tracked.finding.title.clone(),
+                    description: Some(description),
+                    actions: vec![Box::new(move |tx| {
+                        tx.send(AppEvent::OpenReviewFinding(index));
+                    })],
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        let open = self
+            .review_findings
+            .iter()
+            .filter(|tracked| tracked.status.is_none())
+            .count();
+        let initial_selected_idx = self
+            .review_findings
+            .iter()
+            .position(|tracked| tracked.status.is_none());
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Review findings".to_string()),
+            subtitle: Some(format!(
+                "{} findings, {open} without a decision.",
+                self.review_findings.len()
+            )),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            initial_selected_idx,
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn open_review_finding_popup(&mut self, index: usize) {
+        let Some(tracked) = self.review_findings.get(index) else {
+            return;
+        };
+        let items = vec![
+            SelectionItem {
+                name: "Show location".to_string(),
+                description: Some("Open the code around the finding and its diff.".to_string()),
+                actions: vec![Box::new(move |tx| {
+                    tx.send(AppEvent::ShowReviewFindingLocation(index));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Fix this".to_string(),
+                description: Some("Start a turn that fixes only this finding.".to_string()),
+                actions: vec![Box::new(move |tx| {
+                    tx.send(AppEvent::FixReviewFinding(index));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Accept".to_string(),
+                description: Some("Mark the finding as valid.".to_string()),
+                is_current: tracked.status == Some(ReviewFindingStatus::Accepted),
+                actions: vec![status_action(index, ReviewFindingStatus::Accepted)],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Dismiss".to_string(),
+                description: Some("Later reviews in this thread will not report it.".to_string()),
+                is_current: tracked.status == Some(ReviewFindingStatus::Dismissed),
+                actions: vec![status_action(index, ReviewFindingStatus::Dismissed)],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Back".to_string(),
+                description: Some("Return to the list of findings.".to_string()),
+                actions: vec![Box::new(|tx| tx.send(AppEvent::OpenReviewFindings))],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+        ];
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some(tracked.finding.title.clone()),
+            subtitle: Some(finding_location(&tracked.finding, &self.config.cwd)),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    /// Records a decision locally and in the rollout, then returns to the list so the next
+    /// undecided finding is selected.
+    pub(crate) fn set_review_finding_status(&mut self, index: usize, status: ReviewFindingStatus) {
+        let Some(tracked) = self.review_findings.get_mut(index) else {
+            return;
+        };
+        tracked.status = Some(status);
+        let finding = tracked.finding.clone();
+        self.submit_op(Op::SetReviewFindingStatus { finding, status });
+        self.open_review_findings_popup();
+    }
+
+    /// Accepts the finding and asks the agent to fix it, and nothing else, in a new turn.
+    pub(crate) fn fix_review_finding(&mut self, index: usize) {
+        let Some(tracked) = self.review_findings.get_mut(index) else {
+            return;
+        };
+        tracked.status = Some(ReviewFindingStatus::Accepted);
+        let finding = tracked.finding.clone();
+        let prompt = fix_finding_prompt(&finding);
+        self.submit_op(Op::SetReviewFindingStatus {
+            finding,
+            status: ReviewFindingStatus::Accepted,
+        });
+        self.queue_user_message(prompt.into());
+    }
+
+    pub(crate) fn show_review_finding_location(&mut self, index: usize) {
+        let Some(tracked) = self.review_findings.get(index) else {
+            return;
+        };
+        let finding = tracked.finding.clone();
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let text = finding_location_text(&finding).await;
+            tx.send(AppEvent::ReviewFindingLocationResult(text));
+        });
+    }
+}
+
+fn status_action(index: usize, status: ReviewFindingStatus) -> SelectionAction {
+    Box::new(move |tx| {
+        tx.send(AppEvent::SetReviewFindingStatus { index, status });
+    })
+}
+
+fn finding_location(finding: &ReviewFinding, cwd: &Path) -> String {
+    let location = &finding.code_location;
+    let path = location
+        .absolute_file_path
+        .strip_prefix(cwd)
+        .unwrap_or(&location.absolute_file_path);
+    format!(
+        "{}:{}-{}",
+        path.display(),
+        location.line_range.start,
+        location.line_range.end
+    )
+}
+
+fn fix_finding_prompt(finding: &ReviewFinding) -> String {
+    let location = &finding.code_location;
+    format!(
+        "Fix the following code review finding. Keep the change limited to this issue.\n\n\
+         {}\nLocation: {}:{}-{}\n\n{}",
+        finding.title,
+        location.absolute_file_path.display(),
+        location.line_range.start,
+        location.line_range.end,
+        finding.body.trim()
+    )
+}
+
+/// Builds the pager text for a finding: its description, the flagged lines with some
+/// surrounding context, and the file's current diff.
+async fn finding_location_text(finding: &ReviewFinding) -> String {
+    let location = &finding.code_location;
+    let path = &location.absolute_file_path;
+    let mut text = format!(
+        "{}\n{}:{}-{}\n\n{}\n\n",
+        finding.title,
+        path.display(),
+        location.line_range.start,
+        location.line_range.end,
+        finding.body.trim()
+    );
+
+    match tokio::fs::read_to_string(path).await {
+        Ok(contents) => text.push_str(&numbered_excerpt(
+            &contents,
+            location.line_range.start,
+            location.line_range.end,
+        )),
+        Err(err) => text.push_str(&format!("Failed to read {}: {err}\n", path.display())),
+    }
+    text.push('\n');
+
+    match get_git_diff_for_path(path).await {
+        Ok(diff) if diff.trim().is_empty() => text.push_str("No uncommitted changes in this file."),
+        Ok(diff) => text.push_str(&diff),
+        Err(err) => text.push_str(&format!("Failed to compute diff: {err}")),
+    }
+    text
+}
+
+/// Renders the lines `start..=end` (1-based) with line numbers and some context, marking
+/// the flagged lines with `>`.
+fn numbered_excerpt(contents: &str, start: u32, end: u32) -> String {
+    let start = start.max(1);
+    let end = end.max(start);
+    let first = start.saturating_sub(LOCATION_CONTEXT_LINES).max(1);
+    let last = end.saturating_add(LOCATION_CONTEXT_LINES);
+    let width = last.to_string().len();
+    let mut excerpt = String::new();
+    for (number, line) in (1u32..).zip(contents.lines()) {
+        if number < first {
+            continue;
+        }
+        if number > last {
+            break;
+        }
+        let marker = if (start..=end).contains(&number) {
+            '>'
+        } else {
+            ' '
+        };
+        excerpt.push_str(&format!("{marker} {number:>width$} │ {line}\n"));
+    }
+    excerpt
+}
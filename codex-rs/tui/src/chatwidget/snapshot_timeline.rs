@@ -0,0 +1,201 @@
+//! Timeline of the ghost snapshots captured at the start of each turn (`/undo`).
+//!
+//! Each snapshot can be restored in full or for selected files only. Restores keep the state
+//! they replace, so `/redo` or the Redo entry of the timeline can bring it back.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use super::ChatWidget;
+use crate::app_event::AppEvent;
+use crate::bottom_pane::SelectionItem;
+use crate::bottom_pane::SelectionViewParams;
+use crate::bottom_pane::SnapshotFilesView;
+use crate::bottom_pane::popup_consts::standard_popup_hint_line;
+use crate::text_formatting::truncate_text;
+use codex_core::protocol::Op;
+use codex_core::protocol::SnapshotTimelineEntry;
+use codex_core::protocol::SnapshotTimelineEvent;
+
+/// Maximum length of the turn summary shown for a snapshot.
+const SUMMARY_MAX_GRAPHEMES: usize = 60;
+
+impl ChatWidget {
+    pub(super) fn on_snapshot_timeline(&mut self, event: SnapshotTimelineEvent) {
+        self.snapshot_timeline = Some(event);
+        self.open_snapshot_timeline_popup();
+    }
+
+    pub(crate) fn open_snapshot_timeline_popup(&mut self) {
+        let Some(timeline) = self.snapshot_timeline.as_ref() else {
+            return;
+        };
+        if timeline.entries.is_empty() && !timeline.redo_available {
+            self.add_info_message(
+                "No snapshots to restore yet.".to_string(),
+                Some(
+                    "Snapshots are captured at the start of each turn when the `undo` feature is \
+                     enabled."
+                        .to_string(),
+                ),
+            );
+            return;
+        }
+
+        let mut items = Vec::new();
+        if timeline.redo_available {
+            items.push(SelectionItem {
+                name: "Redo".to_string(),
+                description: Some("Reapply what the last undo or restore replaced.".to_string()),
+                actions: vec![Box::new(|tx| tx.send(AppEvent::CodexOp(Op::Redo)))],
+                dismiss_on_select: true,
+                ..Default::default()
+            });
+        }
+        // Newest first, since rolling back usually starts from the latest turns.
+        for (index, entry) in timeline.entries.iter().enumerate().rev() {
+            items.push(SelectionItem {
+                name: entry_title(entry),
+                description: Some(files_description(entry)),
+                actions: vec![Box::new(move |tx| {
+                    tx.send(AppEvent::OpenSnapshot(index));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            });
+        }
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Snapshots".to_string()),
+            subtitle: Some("Restore the working tree to its state before a turn.".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn open_snapshot_popup(&mut self, index: usize) {
+        let Some(entry) = self
+            .snapshot_timeline
+            .as_ref()
+            .and_then(|timeline| timeline.entries.get(index))
+        else {
+            return;
+        };
+        let snapshot_id = entry.snapshot_id.clone();
+        let items = vec![
+            SelectionItem {
+                name: "Restore all files".to_string(),
+                description: Some("Undo every change made since this turn started.".to_string()),
+                actions: vec![Box::new(move |tx| {
+                    tx.send(AppEvent::CodexOp(Op::RestoreSnapshot {
+                        snapshot_id: snapshot_id.clone(),
+                        paths: Vec::new(),
+                    }));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Restore selected files".to_string(),
+                description: Some("Choose which changed files to restore.".to_string()),
+                actions: vec![Box::new(move |tx| {
+                    tx.send(AppEvent::OpenSnapshotFiles(index));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Back".to_string(),
+                description: Some("Return to the list of snapshots.".to_string()),
+                actions: vec![Box::new(|tx| tx.send(AppEvent::OpenSnapshotTimeline))],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+        ];
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some(entry_title(entry)),
+            subtitle: Some(files_description(entry)),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    /// Opens the file picker for a snapshot, listing every file changed by this turn or a
+    /// later one.
+    pub(crate) fn open_snapshot_files_popup(&mut self, index: usize) {
+        let Some(timeline) = self.snapshot_timeline.as_ref() else {
+            return;
+        };
+        let Some(entry) = timeline.entries.get(index) else {
+            return;
+        };
+        let turn = entry.turn;
+        let snapshot_id = entry.snapshot_id.clone();
+        let files = changed_files_since(&timeline.entries[index..]);
+        if files.is_empty() {
+            self.add_info_message(format!("No files changed since turn {turn}."), None);
+            return;
+        }
+        let view = SnapshotFilesView::new(
+            format!("Restore files to before turn {turn}"),
+            snapshot_id,
+            files,
+            self.app_event_tx.clone(),
+        );
+        self.bottom_pane.show_view(Box::new(view));
+    }
+}
+
+fn entry_title(entry: &SnapshotTimelineEntry) -> String {
+    let summary = entry
+        .summary
+        .as_deref()
+        .and_then(|summary| summary.lines().find(|line| !line.trim().is_empty()));
+    match summary {
+        Some(summary) => format!(
+            "Turn {}: {}",
+            entry.turn,
+            truncate_text(summary.trim(), SUMMARY_MAX_GRAPHEMES)
+        ),
+        None => format!("Turn {}", entry.turn),
+    }
+}
+
+fn files_description(entry: &SnapshotTimelineEntry) -> String {
+    if entry.files.is_empty() {
+        return "no file changes".to_string();
+    }
+    let added: u64 = entry.files.iter().filter_map(|file| file.added).sum();
+    let removed: u64 = entry.files.iter().filter_map(|file| file.removed).sum();
+    let count = entry.files.len();
+    let noun = if count == 1 { "file" } else { "files" };
+    format!("{count} {noun} changed · +{added} -{removed}")
+}
+
+/// Collects the files changed by `entries`, each described by the turns that changed it.
+fn changed_files_since(entries: &[SnapshotTimelineEntry]) -> Vec<(PathBuf, String)> {
+    let mut turns_by_path: BTreeMap<PathBuf, Vec<u32>> = BTreeMap::new();
+    for entry in entries {
+        for file in &entry.files {
+            turns_by_path
+                .entry(file.path.clone())
+                .or_default()
+                .push(entry.turn);
+        }
+    }
+    turns_by_path
+        .into_iter()
+        .map(|(path, turns)| {
+            let noun = if turns.len() == 1 { "turn" } else { "turns" };
+            let turns = turns
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            (path, format!("changed in {noun} {turns}"))
+        })
+        .collect()
+}
@@ -49,9 +49,17 @@ use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
 use codex_core::protocol::PreparedContextResponseEvent;
 use codex_core::protocol::RateLimitWindow;
+use codex_core::protocol::ReviewCodeLocation;
+use codex_core::protocol::ReviewFinding;
+use codex_core::protocol::ReviewFindingStatus;
+use codex_core::protocol::ReviewLineRange;
+use codex_core::protocol::ReviewOutputEvent;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::SessionSource;
+use codex_core::protocol::SnapshotFileStat;
+use codex_core::protocol::SnapshotTimelineEntry;
+use codex_core::protocol::SnapshotTimelineEvent;
 use codex_core::protocol::StreamErrorEvent;
 use codex_core::protocol::TerminalInteractionEvent;
 use codex_core::protocol::ThreadRolledBackEvent;
@@ -64,6 +72,9 @@ use codex_core::protocol::UndoCompletedEvent;
 use codex_core::protocol::UndoStartedEvent;
 use codex_core::protocol::ViewImageToolCallEvent;
 use codex_core::protocol::WarningEvent;
+use codex_core::remembered_approvals::ApprovalSubject;
+use codex_core::remembered_approvals::RememberedDecision;
+use codex_core::remembered_approvals::RepositoryApprovals;
 use codex_core::skills::model::SkillMetadata;
 use codex_otel::OtelManager;
 use codex_otel::RuntimeMetricsSummary;
@@ -887,6 +898,7 @@ async fn review_restores_context_window_indicator() {
         id: "review-end".into(),
         msg: EventMsg::ExitedReviewMode(ExitedReviewModeEvent {
             review_output: None,
+            snapshot_sha: None,
         }),
     });
     let _ = drain_insert_history(&mut rx);
@@ -1110,10 +1122,14 @@ async fn make_chatwidget_manual(
         quit_shortcut_key: None,
         is_review_mode: false,
         pre_review_token_info: None,
+        review_findings: Vec::new(),
+        review_finding_decisions: Vec::new(),
+        snapshot_timeline: None,
         needs_final_message_separator: false,
         had_work_activity: false,
         saw_plan_update_this_turn: false,
         saw_plan_item_this_turn: false,
+        shown_external_diff_len: 0,
         plan_delta_buffer: String::new(),
         plan_item_active: false,
         last_separator_elapsed_secs: None,
@@ -1126,6 +1142,7 @@ async fn make_chatwidget_manual(
         session_network_proxy: None,
         status_line_invalid_items_warned: Arc::new(AtomicBool::new(false)),
         status_line_branch: None,
+        failover_provider: None,
         status_line_branch_cwd: None,
         status_line_branch_pending: false,
         status_line_branch_lookup_complete: false,
@@ -2001,6 +2018,7 @@ async fn exec_approval_emits_proposed_command_and_decision_history() {
         ),
         proposed_execpolicy_amendment: None,
         parsed_cmd: vec![],
+        provenance: None,
     };
     chat.handle_codex_event(Event {
         id: "sub-short".into(),
@@ -2045,6 +2063,7 @@ async fn exec_approval_decision_truncates_multiline_and_long_commands() {
         ),
         proposed_execpolicy_amendment: None,
         parsed_cmd: vec![],
+        provenance: None,
     };
     chat.handle_codex_event(Event {
         id: "sub-multi".into(),
@@ -2095,6 +2114,7 @@ async fn exec_approval_decision_truncates_multiline_and_long_commands() {
         reason: None,
         proposed_execpolicy_amendment: None,
         parsed_cmd: vec![],
+        provenance: None,
     };
     chat.handle_codex_event(Event {
         id: "sub-long".into(),
@@ -2246,6 +2266,7 @@ fn end_exec(
             exit_code,
             duration: std::time::Duration::from_millis(5),
             formatted_output: aggregated,
+            provenance: None,
         }),
     });
 }
@@ -2707,6 +2728,7 @@ async fn exec_end_without_begin_uses_event_command() {
             exit_code: 0,
             duration: std::time::Duration::from_millis(5),
             formatted_output: "done".to_string(),
+            provenance: None,
         }),
     });
 
@@ -4791,6 +4813,231 @@ async fn approvals_popup_shows_disabled_presets() {
     );
 }
 
+#[tokio::test]
+async fn permissions_popup_lists_and_revokes_remembered_approvals() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    let codex_home = tempdir().expect("tempdir");
+    let mut approvals =
+        RepositoryApprovals::new(codex_home.path(), "github.com/acme/widgets".to_string());
+    let subject = ApprovalSubject::CommandPrefix {
+        prefix: vec!["cargo".to_string(), "test".to_string()],
+    };
+    approvals
+        .remember(subject.clone(), RememberedDecision::Allow, None)
+        .await
+        .expect("remember approval");
+
+    chat.open_remembered_approvals_popup(&approvals);
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(
+        popup.contains("Remembered for github.com/acme/widgets."),
+        "missing repository: {popup}"
+    );
+    assert!(popup.contains("`cargo test`"), "missing entry: {popup}");
+    assert!(popup.contains("Allowed"), "missing decision: {popup}");
+
+    chat.handle_key_event(KeyEvent::from(KeyCode::Enter));
+    assert_matches!(
+        rx.try_recv(),
+        Ok(AppEvent::RevokeRememberedApproval { subject: revoked }) if revoked == subject
+    );
+}
+
+#[tokio::test]
+async fn findings_popup_records_decisions_and_selects_next_open_finding() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
+    let finding = |title: &str, line: u32| ReviewFinding {
+        title: title.to_string(),
+        body: "Details.".to_string(),
+        confidence_score: 0.8,
+        priority: 1,
+        code_location: ReviewCodeLocation {
+            absolute_file_path: chat.config.cwd.join("src/lib.rs"),
+            line_range: ReviewLineRange {
+                start: line,
+                end: line,
+            },
+        },
+    };
+    let findings = vec![finding("[P1] Noise", 3), finding("[P2] Real bug", 9)];
+    chat.handle_codex_event(Event {
+        id: "review-end".into(),
+        msg: EventMsg::ExitedReviewMode(ExitedReviewModeEvent {
+            review_output: Some(ReviewOutputEvent {
+                findings: findings.clone(),
+                ..Default::default()
+            }),
+            snapshot_sha: None,
+        }),
+    });
+    let _ = drain_insert_history(&mut rx);
+
+    chat.open_review_findings_popup();
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(
+        popup.contains("2 findings, 2 without a decision."),
+        "{popup}"
+    );
+    assert!(
+        popup.contains("src/lib.rs:3-3"),
+        "missing location: {popup}"
+    );
+
+    chat.set_review_finding_status(0, ReviewFindingStatus::Dismissed);
+    let op = loop {
+        match op_rx.try_recv() {
+            Ok(op @ Op::SetReviewFindingStatus { .. }) => break op,
+            Ok(_) => continue,
+            Err(err) => panic!("expected a finding status op: {err:?}"),
+        }
+    };
+    assert_eq!(
+        op,
+        Op::SetReviewFindingStatus {
+            finding: findings[0].clone(),
+            status: ReviewFindingStatus::Dismissed,
+        }
+    );
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(
+        popup.contains("2 findings, 1 without a decision."),
+        "{popup}"
+    );
+    assert!(popup.contains("dismissed"), "missing status: {popup}");
+
+    chat.handle_key_event(KeyEvent::from(KeyCode::Enter));
+    assert_matches!(rx.try_recv(), Ok(AppEvent::OpenReviewFinding(1)));
+}
+
+#[tokio::test]
+async fn resumed_findings_keep_decisions_made_before_the_latest_review() {
+    let (mut chat, mut rx, _ops) = make_chatwidget_manual(None).await;
+    let finding = |title: &str, line: u32| ReviewFinding {
+        title: title.to_string(),
+        body: "Details.".to_string(),
+        confidence_score: 0.8,
+        priority: 1,
+        code_location: ReviewCodeLocation {
+            absolute_file_path: chat.config.cwd.join("src/lib.rs"),
+            line_range: ReviewLineRange {
+                start: line,
+                end: line,
+            },
+        },
+    };
+    let exited = |findings: Vec<ReviewFinding>| {
+        EventMsg::ExitedReviewMode(ExitedReviewModeEvent {
+            review_output: Some(ReviewOutputEvent {
+                findings,
+                ..Default::default()
+            }),
+            snapshot_sha: None,
+        })
+    };
+    let rollout_file = NamedTempFile::new().unwrap();
+    let configured = codex_core::protocol::SessionConfiguredEvent {
+        session_id: ThreadId::new(),
+        forked_from_id: None,
+        thread_name: None,
+        model: "test-model".to_string(),
+        model_provider_id: "test-provider".to_string(),
+        approval_policy: AskForApproval::Never,
+        sandbox_policy: SandboxPolicy::new_read_only_policy(),
+        cwd: chat.config.cwd.clone(),
+        reasoning_effort: Some(ReasoningEffortConfig::default()),
+        history_log_id: 0,
+        history_entry_count: 0,
+        // The second review reports the accepted bug again, two lines further down.
+        initial_messages: Some(vec![
+            exited(vec![finding("[P1] Noise", 3), finding("[P2] Real bug", 9)]),
+            EventMsg::ReviewFindingStatus(ReviewFindingStatusEvent {
+                finding: finding("[P2] Real bug", 9),
+                status: ReviewFindingStatus::Accepted,
+            }),
+            exited(vec![finding("[P2] Real bug", 11), finding("[P3] New", 20)]),
+        ]),
+        network_proxy: None,
+        rollout_path: Some(rollout_file.path().to_path_buf()),
+    };
+    chat.handle_codex_event(Event {
+        id: "initial".into(),
+        msg: EventMsg::SessionConfigured(configured),
+    });
+    let _ = drain_insert_history(&mut rx);
+
+    chat.open_review_findings_popup();
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(
+        popup.contains("2 findings, 1 without a decision."),
+        "{popup}"
+    );
+    assert!(
+        popup.contains("src/lib.rs:11-11 · accepted"),
+        "missing status: {popup}"
+    );
+}
+
+#[tokio::test]
+async fn undo_timeline_offers_redo_and_restores_selected_files() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
+    chat.dispatch_command(SlashCommand::Undo);
+    assert_matches!(op_rx.try_recv(), Ok(Op::ListSnapshots));
+
+    let file = |path: &str| SnapshotFileStat {
+        path: PathBuf::from(path),
+        added: Some(1),
+        removed: Some(1),
+    };
+    chat.handle_codex_event(Event {
+        id: "timeline".into(),
+        msg: EventMsg::SnapshotTimeline(SnapshotTimelineEvent {
+            entries: vec![
+                SnapshotTimelineEntry {
+                    snapshot_id: "snap-1".to_string(),
+                    turn: 1,
+                    summary: Some("first change".to_string()),
+                    files: vec![file("story.txt")],
+                },
+                SnapshotTimelineEntry {
+                    snapshot_id: "snap-2".to_string(),
+                    turn: 2,
+                    summary: Some("second change".to_string()),
+                    files: vec![file("notes.txt"), file("story.txt")],
+                },
+            ],
+            redo_available: true,
+        }),
+    });
+
+    let popup = render_bottom_popup(&chat, 100);
+    assert!(popup.contains("Redo"), "missing redo entry: {popup}");
+    assert!(popup.contains("Turn 2: second change"), "{popup}");
+    assert!(popup.contains("2 files changed · +2 -2"), "{popup}");
+    chat.handle_key_event(KeyEvent::from(KeyCode::Enter));
+    assert_matches!(rx.try_recv(), Ok(AppEvent::CodexOp(Op::Redo)));
+
+    chat.open_snapshot_files_popup(0);
+    let popup = render_bottom_popup(&chat, 100);
+    assert!(popup.contains("Restore files to before turn 1"), "{popup}");
+    assert!(popup.contains("changed in turns 1, 2"), "{popup}");
+    chat.handle_key_event(KeyEvent::from(KeyCode::Char(' ')));
+    chat.handle_key_event(KeyEvent::from(KeyCode::Enter));
+    let op = loop {
+        match rx.try_recv() {
+            Ok(AppEvent::CodexOp(op)) => break op,
+            Ok(_) => continue,
+            Err(err) => panic!("expected a restore op: {err:?}"),
+        }
+    };
+    assert_eq!(
+        op,
+        Op::RestoreSnapshot {
+            snapshot_id: "snap-1".to_string(),
+            paths: vec![PathBuf::from("notes.txt")],
+        }
+    );
+}
+
 #[tokio::test]
 async fn approvals_popup_navigation_skips_disabled() {
     let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
@@ -4888,6 +5135,7 @@ async fn approval_modal_exec_snapshot() -> anyhow::Result<()> {
             "world".into(),
         ])),
         parsed_cmd: vec![],
+        provenance: None,
     };
     chat.handle_codex_event(Event {
         id: "sub-approve".into(),
@@ -4941,6 +5189,7 @@ async fn approval_modal_exec_without_reason_snapshot() -> anyhow::Result<()> {
             "world".into(),
         ])),
         parsed_cmd: vec![],
+        provenance: None,
     };
     chat.handle_codex_event(Event {
         id: "sub-approve-noreason".into(),
@@ -4981,6 +5230,7 @@ async fn approval_modal_exec_multiline_prefix_hides_execpolicy_option_snapshot()
         reason: None,
         proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(command)),
         parsed_cmd: vec![],
+        provenance: None,
     };
     chat.handle_codex_event(Event {
         id: "sub-approve-multiline-trunc".into(),
@@ -5340,6 +5590,7 @@ async fn status_widget_and_approval_modal_snapshot() {
             "hello world".into(),
         ])),
         parsed_cmd: vec![],
+        provenance: None,
     };
     chat.handle_codex_event(Event {
         id: "sub-approve-exec".into(),
@@ -6358,6 +6609,7 @@ async fn chatwidget_exec_and_status_layout_vt100_snapshot() {
             exit_code: 0,
             duration: std::time::Duration::from_millis(16000),
             formatted_output: String::new(),
+            provenance: None,
         }),
     });
     chat.handle_codex_event(Event {
@@ -6555,3 +6807,40 @@ async fn review_queues_user_messages_snapshot() {
     .unwrap();
     assert_snapshot!(term.backend().vt100().screen().contents());
 }
+
+#[tokio::test]
+async fn provider_failover_updates_status_line_provider() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    let primary = chat.config.model_provider_id.clone();
+    let failover = |from: &str, to: &str, trigger| {
+        EventMsg::ProviderFailover(ProviderFailoverEvent {
+            from_provider: from.to_string(),
+            from_model: "gpt-5.2-codex".to_string(),
+            to_provider: to.to_string(),
+            to_model: "gpt-5.2-codex".to_string(),
+            trigger,
+            cooldown_secs: trigger.map(|_| 300),
+        })
+    };
+
+    chat.handle_codex_event(Event {
+        id: "turn-1".into(),
+        msg: failover(&primary, "backup", Some(FailoverTrigger::RateLimit)),
+    });
+    assert_eq!(
+        chat.status_line_value_for_item(&StatusLineItem::ModelProvider),
+        Some("backup (failover)".to_string())
+    );
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected one warning history cell");
+    assert!(lines_to_single_string(&cells[0]).contains("after a rate limit"));
+
+    chat.handle_codex_event(Event {
+        id: "turn-2".into(),
+        msg: failover("backup", &primary, None),
+    });
+    assert_eq!(
+        chat.status_line_value_for_item(&StatusLineItem::ModelProvider),
+        Some(primary)
+    );
+}
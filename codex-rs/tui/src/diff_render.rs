@@ -9,6 +9,7 @@ use ratatui::text::Line as RtLine;
 use ratatui::text::Span as RtSpan;
 use ratatui::widgets::Paragraph;
 use std::collections::HashMap;
+use std::collections::hash_map::Entry;
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -18,6 +19,9 @@ use crate::render::line_utils::prefix_lines;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::InsetRenderable;
 use crate::render::renderable::Renderable;
+use crate::render::syntax::Language;
+use crate::render::syntax::SyntaxTheme;
+use crate::render::syntax::highlight_code_with_theme;
 use codex_core::git_info::get_git_repo_root;
 use codex_core::protocol::FileChange;
 
@@ -39,16 +43,21 @@ impl DiffSummary {
     }
 }
 
-impl Renderable for FileChange {
+impl Renderable for Row {
     fn render(&self, area: Rect, buf: &mut Buffer) {
         let mut lines = vec![];
-        render_change(self, &mut lines, area.width as usize);
+        render_change(
+            &self.change,
+            self.language(),
+            &mut lines,
+            area.width as usize,
+        );
         Paragraph::new(lines).render(area, buf);
     }
 
     fn desired_height(&self, width: u16) -> u16 {
         let mut lines = vec![];
-        render_change(self, &mut lines, width as usize);
+        render_change(&self.change, self.language(), &mut lines, width as usize);
         lines.len() as u16
     }
 }
@@ -67,7 +76,7 @@ impl From<DiffSummary> for Box<dyn Renderable> {
             rows.push(Box::new(path));
             rows.push(Box::new(RtLine::from("")));
             rows.push(Box::new(InsetRenderable::new(
-                Box::new(row.change) as Box<dyn Renderable>,
+                Box::new(row) as Box<dyn Renderable>,
                 Insets::tlbr(0, 2, 0, 0),
             )));
         }
@@ -88,7 +97,6 @@ pub(crate) fn create_diff_summary(
 // Shared row for per-file presentation
 #[derive(Clone)]
 struct Row {
-    #[allow(dead_code)]
     path: PathBuf,
     move_path: Option<PathBuf>,
     added: usize,
@@ -96,6 +104,14 @@ struct Row {
     change: FileChange,
 }
 
+impl Row {
+    /// Language used to highlight the diff body, taken from the destination
+    /// path so renames to a new extension highlight as the new type.
+    fn language(&self) -> Option<Language> {
+        Language::from_path(self.move_path.as_deref().unwrap_or(&self.path))
+    }
+}
+
 fn collect_rows(changes: &HashMap<PathBuf, FileChange>) -> Vec<Row> {
     let mut rows: Vec<Row> = Vec::new();
     for (path, change) in changes.iter() {
@@ -133,17 +149,78 @@ fn render_line_count_summary(added: usize, removed: usize) -> Vec<RtSpan<'static
     spans
 }
 
-fn render_changes_block(rows: Vec<Row>, wrap_cols: usize, cwd: &Path) -> Vec<RtLine<'static>> {
-    let mut out: Vec<RtLine<'static>> = Vec::new();
+/// Render the diff of edits made outside Codex, as reported in a turn's
+/// `external_unified_diff`. Each file section is a git-style diff whose paths
+/// are relative to the repository root.
+pub(crate) fn create_external_diff_summary(
+    unified_diff: &str,
+    cwd: &Path,
+    wrap_cols: usize,
+) -> Vec<RtLine<'static>> {
+    let changes = split_git_diff(unified_diff);
+    let rows = collect_rows(&changes);
+    let total_added: usize = rows.iter().map(|r| r.added).sum();
+    let total_removed: usize = rows.iter().map(|r| r.removed).sum();
+    let noun = if rows.len() == 1 { "file" } else { "files" };
+    let mut header_spans: Vec<RtSpan<'static>> = vec!["• ".dim(), "Changed outside Codex".bold()];
+    header_spans.push(format!(" {} {noun} ", rows.len()).into());
+    header_spans.extend(render_line_count_summary(total_added, total_removed));
+
+    let mut out = vec![RtLine::from(header_spans)];
+    render_file_chunks(rows, true, wrap_cols, cwd, &mut out);
+    out
+}
 
-    let render_path = |row: &Row| -> Vec<RtSpan<'static>> {
-        let mut spans = Vec::new();
-        spans.push(display_path_for(&row.path, cwd).into());
-        if let Some(move_path) = &row.move_path {
-            spans.push(format!(" → {}", display_path_for(move_path, cwd)).into());
+/// Split a multi-file git diff into per-file updates keyed by the new path.
+fn split_git_diff(unified_diff: &str) -> HashMap<PathBuf, FileChange> {
+    const FILE_HEADER: &str = "diff --git ";
+    let mut starts: Vec<usize> = unified_diff
+        .match_indices(FILE_HEADER)
+        .map(|(idx, _)| idx)
+        .filter(|&idx| idx == 0 || unified_diff[..idx].ends_with('\n'))
+        .collect();
+    starts.push(unified_diff.len());
+
+    let mut changes = HashMap::new();
+    for bounds in starts.windows(2) {
+        let section = &unified_diff[bounds[0] + FILE_HEADER.len()..bounds[1]];
+        let Some((header, body)) = section.split_once('\n') else {
+            continue;
+        };
+        let Some((_, path)) = header.split_once(" b/") else {
+            continue;
+        };
+        let hunks = body.find("@@").map_or("", |idx| &body[idx..]);
+        match changes.entry(PathBuf::from(path)) {
+            // A file edited more than once: keep every edit's hunks.
+            Entry::Occupied(mut entry) => {
+                if let FileChange::Update { unified_diff, .. } = entry.get_mut() {
+                    unified_diff.push_str(hunks);
+                }
+            }
+            Entry::Vacant(entry) => {
+                let file_header = body.find("--- ").unwrap_or(body.len());
+                entry.insert(FileChange::Update {
+                    unified_diff: body[file_header..].to_string(),
+                    move_path: None,
+                });
+            }
         }
-        spans
-    };
+    }
+    changes
+}
+
+fn render_path(row: &Row, cwd: &Path) -> Vec<RtSpan<'static>> {
+    let mut spans = Vec::new();
+    spans.push(display_path_for(&row.path, cwd).into());
+    if let Some(move_path) = &row.move_path {
+        spans.push(format!(" → {}", display_path_for(move_path, cwd)).into());
+    }
+    spans
+}
+
+fn render_changes_block(rows: Vec<Row>, wrap_cols: usize, cwd: &Path) -> Vec<RtLine<'static>> {
+    let mut out: Vec<RtLine<'static>> = Vec::new();
 
     // Header
     let total_added: usize = rows.iter().map(|r| r.added).sum();
@@ -159,7 +236,7 @@ fn render_changes_block(rows: Vec<Row>, wrap_cols: usize, cwd: &Path) -> Vec<RtL
         };
         header_spans.push(verb.bold());
         header_spans.push(" ".into());
-        header_spans.extend(render_path(row));
+        header_spans.extend(render_path(row, cwd));
         header_spans.push(" ".into());
         header_spans.extend(render_line_count_summary(row.added, row.removed));
     } else {
@@ -169,31 +246,44 @@ fn render_changes_block(rows: Vec<Row>, wrap_cols: usize, cwd: &Path) -> Vec<RtL
     }
     out.push(RtLine::from(header_spans));
 
+    // Skip file header lines when the single-file header already shows the name.
+    render_file_chunks(rows, file_count != 1, wrap_cols, cwd, &mut out);
+    out
+}
+
+fn render_file_chunks(
+    rows: Vec<Row>,
+    show_file_headers: bool,
+    wrap_cols: usize,
+    cwd: &Path,
+    out: &mut Vec<RtLine<'static>>,
+) {
     for (idx, r) in rows.into_iter().enumerate() {
         // Insert a blank separator between file chunks (except before the first)
         if idx > 0 {
             out.push("".into());
         }
-        // File header line (skip when single-file header already shows the name)
-        let skip_file_header = file_count == 1;
-        if !skip_file_header {
+        if show_file_headers {
             let mut header: Vec<RtSpan<'static>> = Vec::new();
             header.push("  └ ".dim());
-            header.extend(render_path(&r));
+            header.extend(render_path(&r, cwd));
             header.push(" ".into());
             header.extend(render_line_count_summary(r.added, r.removed));
             out.push(RtLine::from(header));
         }
 
         let mut lines = vec![];
-        render_change(&r.change, &mut lines, wrap_cols - 4);
+        render_change(&r.change, r.language(), &mut lines, wrap_cols - 4);
         out.extend(prefix_lines(lines, "    ".into(), "    ".into()));
     }
-
-    out
 }
 
-fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usize) {
+fn render_change(
+    change: &FileChange,
+    language: Option<Language>,
+    out: &mut Vec<RtLine<'static>>,
+    width: usize,
+) {
     match change {
         FileChange::Add { content } => {
             let line_number_width = line_number_width(content.lines().count());
@@ -202,6 +292,7 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                     i + 1,
                     DiffLineType::Insert,
                     raw,
+                    language,
                     width,
                     line_number_width,
                 ));
@@ -214,6 +305,7 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                     i + 1,
                     DiffLineType::Delete,
                     raw,
+                    language,
                     width,
                     line_number_width,
                 ));
@@ -263,6 +355,7 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                                     new_ln,
                                     DiffLineType::Insert,
                                     s,
+                                    language,
                                     width,
                                     line_number_width,
                                 ));
@@ -274,6 +367,7 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                                     old_ln,
                                     DiffLineType::Delete,
                                     s,
+                                    language,
                                     width,
                                     line_number_width,
                                 ));
@@ -285,6 +379,7 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                                     new_ln,
                                     DiffLineType::Context,
                                     s,
+                                    language,
                                     width,
                                     line_number_width,
                                 ));
@@ -346,11 +441,11 @@ fn push_wrapped_diff_line(
     line_number: usize,
     kind: DiffLineType,
     text: &str,
+    language: Option<Language>,
     width: usize,
     line_number_width: usize,
 ) -> Vec<RtLine<'static>> {
     let ln_str = line_number.to_string();
-    let mut remaining_text: &str = text;
 
     // Reserve a fixed number of spaces (equal to the widest line number plus a
     // trailing spacer) so the sign column stays aligned across the diff block.
@@ -363,46 +458,82 @@ fn push_wrapped_diff_line(
         DiffLineType::Delete => ('-', style_del()),
         DiffLineType::Context => (' ', style_context()),
     };
+    let styled_chars: Vec<(char, Style)> = highlight_diff_text(text, &kind, line_style, language)
+        .iter()
+        .flat_map(|span| span.content.chars().map(move |c| (c, span.style)))
+        .collect();
+    let mut remaining: &[(char, Style)] = &styled_chars;
     let mut lines: Vec<RtLine<'static>> = Vec::new();
 
     loop {
-        // Fit the content for the current terminal row:
-        // compute how many columns are available after the prefix, then split
-        // at a UTF-8 character boundary so this row's chunk fits exactly.
+        // Fit the content for the current terminal row: compute how many
+        // columns are available after the prefix and take that many chars.
         let available_content_cols = width.saturating_sub(prefix_cols + 1).max(1);
-        let split_at_byte_index = remaining_text
-            .char_indices()
-            .nth(available_content_cols)
-            .map(|(i, _)| i)
-            .unwrap_or_else(|| remaining_text.len());
-        let (chunk, rest) = remaining_text.split_at(split_at_byte_index);
-        remaining_text = rest;
+        let (chunk, rest) = remaining.split_at(available_content_cols.min(remaining.len()));
+        remaining = rest;
 
         if first {
             // Build gutter (right-aligned line number plus spacer) as a dimmed span
             let gutter = format!("{ln_str:>gutter_width$} ");
             // Content with a sign ('+'/'-'/' ') styled per diff kind
-            let content = format!("{sign_char}{chunk}");
-            lines.push(RtLine::from(vec![
-                RtSpan::styled(gutter, style_gutter()),
-                RtSpan::styled(content, line_style),
-            ]));
+            let mut spans = vec![RtSpan::styled(gutter, style_gutter())];
+            spans.extend(group_styled_chars(
+                std::iter::once((sign_char, line_style)).chain(chunk.iter().copied()),
+            ));
+            lines.push(RtLine::from(spans));
             first = false;
         } else {
             // Continuation lines keep a space for the sign column so content aligns
             let gutter = format!("{:gutter_width$}  ", "");
-            lines.push(RtLine::from(vec![
-                RtSpan::styled(gutter, style_gutter()),
-                RtSpan::styled(chunk.to_string(), line_style),
-            ]));
+            let mut spans = vec![RtSpan::styled(gutter, style_gutter())];
+            spans.extend(group_styled_chars(chunk.iter().copied()));
+            lines.push(RtLine::from(spans));
         }
-        if remaining_text.is_empty() {
+        if remaining.is_empty() {
             break;
         }
     }
     lines
 }
 
+/// Split a diff line into styled spans. Context lines get full syntax colors;
+/// added and removed lines keep their green/red and only pick up modifiers so
+/// the diff kind stays readable at a glance.
+fn highlight_diff_text(
+    text: &str,
+    kind: &DiffLineType,
+    line_style: Style,
+    language: Option<Language>,
+) -> Vec<RtSpan<'static>> {
+    let Some(language) = language else {
+        return vec![RtSpan::styled(text.to_string(), line_style)];
+    };
+    let theme = match kind {
+        DiffLineType::Context => SyntaxTheme::current(),
+        DiffLineType::Insert | DiffLineType::Delete => SyntaxTheme::monochrome(),
+    };
+    highlight_code_with_theme(text, language, &theme)
+        .into_iter()
+        .flat_map(|line| line.spans)
+        .map(|span| {
+            let style = line_style.patch(span.style);
+            span.style(style)
+        })
+        .collect()
+}
+
+/// Regroup per-character styles into spans, merging runs of the same style.
+fn group_styled_chars(chars: impl IntoIterator<Item = (char, Style)>) -> Vec<RtSpan<'static>> {
+    let mut spans: Vec<RtSpan<'static>> = Vec::new();
+    for (ch, style) in chars {
+        match spans.last_mut() {
+            Some(last) if last.style == style => last.content.to_mut().push(ch),
+            _ => spans.push(RtSpan::styled(ch.to_string(), style)),
+        }
+    }
+    spans
+}
+
 fn line_number_width(max_line_number: usize) -> usize {
     if max_line_number == 0 {
         1
@@ -497,13 +628,58 @@ mod tests {
         let long_line = "this is a very long line that should wrap across multiple terminal columns and continue";
 
         // Call the wrapping function directly so we can precisely control the width
-        let lines =
-            push_wrapped_diff_line(1, DiffLineType::Insert, long_line, 80, line_number_width(1));
+        let lines = push_wrapped_diff_line(
+            1,
+            DiffLineType::Insert,
+            long_line,
+            None,
+            80,
+            line_number_width(1),
+        );
 
         // Render into a small terminal to capture the visual layout
         snapshot_lines("wrap_behavior_insert", lines, 90, 8);
     }
 
+    #[test]
+    fn diff_lines_are_highlighted_by_file_language() {
+        let lines = push_wrapped_diff_line(
+            1,
+            DiffLineType::Insert,
+            "let x = 1;",
+            Some(Language::Rust),
+            80,
+            1,
+        );
+        assert_eq!(
+            lines,
+            vec![RtLine::from(vec![
+                RtSpan::styled("1 ", style_gutter()),
+                RtSpan::styled("+", style_add()),
+                RtSpan::styled("let", style_add().bold()),
+                RtSpan::styled(" x = 1;", style_add()),
+            ])]
+        );
+
+        let context = push_wrapped_diff_line(
+            2,
+            DiffLineType::Context,
+            "fn f()",
+            Some(Language::Rust),
+            80,
+            1,
+        );
+        assert_eq!(
+            context,
+            vec![RtLine::from(vec![
+                RtSpan::styled("2 ", style_gutter()),
+                RtSpan::styled(" ", style_context()),
+                RtSpan::styled("fn", style_context().magenta()),
+                RtSpan::styled(" f()", style_context()),
+            ])]
+        );
+    }
+
     #[test]
     fn ui_snapshot_apply_update_block() {
         let mut changes: HashMap<PathBuf, FileChange> = HashMap::new();
@@ -601,6 +777,32 @@ mod tests {
         snapshot_lines("apply_delete_block", lines, 80, 12);
     }
 
+    #[test]
+    fn ui_snapshot_external_diff_block() {
+        let unified_diff = [
+            "diff --git a/src/lib.rs b/src/lib.rs",
+            "index 1111111..2222222",
+            "--- a/src/lib.rs",
+            "+++ b/src/lib.rs",
+            "@@ -1,3 +1,3 @@",
+            " line one",
+            "-line two",
+            "+line two changed",
+            " line three",
+            "diff --git a/notes.txt b/notes.txt",
+            "index 0000000..3333333",
+            "--- /dev/null",
+            "+++ b/notes.txt",
+            "@@ -0,0 +1,1 @@",
+            "+hello",
+            "",
+        ]
+        .join("\n");
+
+        let lines = create_external_diff_summary(&unified_diff, &PathBuf::from("/"), 80);
+        snapshot_lines("external_diff_block", lines, 80, 12);
+    }
+
     #[test]
     fn ui_snapshot_apply_update_block_wraps_long_lines() {
         // Create a patch with a long modified line to force wrapping
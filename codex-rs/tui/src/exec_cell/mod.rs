@@ -9,4 +9,5 @@ pub(crate) use render::OutputLinesParams;
 pub(crate) use render::TOOL_CALL_MAX_LINES;
 pub(crate) use render::new_active_exec_command;
 pub(crate) use render::output_lines;
+pub(crate) use render::parsed_command_effects;
 pub(crate) use render::spinner;
@@ -8,6 +8,8 @@ use crate::history_cell::HistoryCell;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::line_utils::prefix_lines;
 use crate::render::line_utils::push_owned_lines;
+use crate::render::syntax::Language;
+use crate::render::syntax::highlight_code_to_lines;
 use crate::shimmer::shimmer_spans;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_line;
@@ -35,6 +37,43 @@ pub(crate) struct OutputLinesParams {
     pub(crate) include_prefix: bool,
 }
 
+/// Short descriptions of what parsed commands change, such as `writes
+/// out.txt` or `installs serde`. Reads, listings, searches and unrecognized
+/// commands contribute nothing.
+pub(crate) fn parsed_command_effects(parsed: &[ParsedCommand]) -> Vec<String> {
+    parsed
+        .iter()
+        .filter_map(|parsed| match parsed {
+            ParsedCommand::Write { paths, .. } => Some(describe_paths("writes", paths)),
+            ParsedCommand::Delete { paths, .. } => Some(describe_paths("deletes", paths)),
+            ParsedCommand::Install { packages, .. } if packages.is_empty() => {
+                Some("installs dependencies".to_string())
+            }
+            ParsedCommand::Install { packages, .. } => {
+                Some(format!("installs {}", packages.join(", ")))
+            }
+            ParsedCommand::Build { .. } => Some("builds".to_string()),
+            ParsedCommand::Test { .. } => Some("runs tests".to_string()),
+            ParsedCommand::Git { subcommand, .. } => Some(format!("git {subcommand}")),
+            ParsedCommand::Fetch { url: Some(url), .. } => Some(format!("fetches {url}")),
+            ParsedCommand::Fetch { url: None, .. } => Some("uses the network".to_string()),
+            ParsedCommand::Read { .. }
+            | ParsedCommand::ListFiles { .. }
+            | ParsedCommand::Search { .. }
+            | ParsedCommand::Unknown { .. } => None,
+        })
+        .unique()
+        .collect()
+}
+
+fn describe_paths(verb: &str, paths: &[String]) -> String {
+    if paths.is_empty() {
+        format!("{verb} files")
+    } else {
+        format!("{verb} {}", paths.join(", "))
+    }
+}
+
 pub(crate) fn new_active_exec_command(
     call_id: String,
     command: Vec<String>,
@@ -226,7 +265,7 @@ impl HistoryCell for ExecCell {
                 if !call.is_unified_exec_interaction() {
                     let wrap_width = width.max(1) as usize;
                     let wrap_opts = RtOptions::new(wrap_width);
-                    for unwrapped in output.formatted_output.lines().map(ansi_escape_line) {
+                    for unwrapped in transcript_output_lines(call, output) {
                         let wrapped = word_wrap_line(&unwrapped, wrap_opts.clone());
                         push_owned_lines(&wrapped, &mut lines);
                     }
@@ -251,6 +290,26 @@ impl HistoryCell for ExecCell {
     }
 }
 
+/// Output lines for the transcript view. File reads of a recognized language
+/// are syntax highlighted; everything else keeps its ANSI styling.
+fn transcript_output_lines(call: &ExecCall, output: &CommandOutput) -> Vec<Line<'static>> {
+    let language = match call.parsed.as_slice() {
+        [ParsedCommand::Read { path, .. }] => Language::from_path(path),
+        _ => None,
+    };
+    let code = output.formatted_output.trim_end_matches('\n');
+    match language {
+        Some(language) if output.exit_code == 0 && !code.is_empty() && !code.contains('\u{1b}') => {
+            highlight_code_to_lines(code, language)
+        }
+        _ => output
+            .formatted_output
+            .lines()
+            .map(ansi_escape_line)
+            .collect(),
+    }
+}
+
 impl ExecCell {
     fn exploring_display_lines(&self, width: u16) -> Vec<Line<'static>> {
         let mut out: Vec<Line<'static>> = Vec::new();
@@ -329,7 +388,14 @@ impl ExecCell {
                             };
                             lines.push(("Search", spans));
                         }
-                        ParsedCommand::Unknown { cmd } => {
+                        ParsedCommand::Write { cmd, .. }
+                        | ParsedCommand::Delete { cmd, .. }
+                        | ParsedCommand::Install { cmd, .. }
+                        | ParsedCommand::Build { cmd }
+                        | ParsedCommand::Test { cmd }
+                        | ParsedCommand::Git { cmd, .. }
+                        | ParsedCommand::Fetch { cmd, .. }
+                        | ParsedCommand::Unknown { cmd } => {
                             lines.push(("Run", vec![cmd.clone().into()]));
                         }
                     }
@@ -431,6 +497,21 @@ impl ExecCell {
             ));
         }
 
+        let effects = parsed_command_effects(&call.parsed);
+        if !effects.is_empty() && !is_interaction && !call.is_user_shell_command() {
+            let effects_line = Line::from(effects.join(" · ").dim().italic());
+            let mut effects_lines: Vec<Line<'static>> = Vec::new();
+            push_owned_lines(
+                &word_wrap_line(&effects_line, continuation_opts),
+                &mut effects_lines,
+            );
+            lines.extend(prefix_lines(
+                effects_lines,
+                Span::from(layout.command_continuation.initial_prefix).dim(),
+                Span::from(layout.command_continuation.subsequent_prefix).dim(),
+            ));
+        }
+
         if let Some(output) = call.output.as_ref() {
             let line_limit = if call.is_user_shell_command() {
                 USER_SHELL_TOOL_CALL_MAX_LINES
@@ -61,6 +61,16 @@ pub(crate) async fn get_git_diff() -> io::Result<(bool, String)> {
     Ok((true, format!("{tracked_diff}{untracked_diff}")))
 }
 
+/// Return the diff of a single file's unstaged changes, or an empty string when the
+/// file is unchanged or the working directory is not a Git repository.
+pub(crate) async fn get_git_diff_for_path(path: &Path) -> io::Result<String> {
+    if !inside_git_repo().await? {
+        return Ok(String::new());
+    }
+    let path = path.to_string_lossy();
+    run_git_capture_diff(&["diff", "--color", "--", &path]).await
+}
+
 /// Helper that executes `git` with the given `args` and returns `stdout` as a
 /// UTF-8 string. Any non-zero exit status is considered an *error*.
 async fn run_git_capture_stdout(args: &[&str]) -> io::Result<String> {
@@ -11,6 +11,7 @@
 //! rendered transcript output can change.
 
 use crate::diff_render::create_diff_summary;
+use crate::diff_render::create_external_diff_summary;
 use crate::diff_render::display_path_for;
 use crate::exec_cell::CommandOutput;
 use crate::exec_cell::OutputLinesParams;
@@ -767,6 +768,20 @@ pub fn new_approval_decision_cell(
                 ],
             )
         }
+        DeniedExecpolicyAmendment {
+            proposed_execpolicy_amendment,
+        } => {
+            let snippet = Span::from(exec_snippet(&proposed_execpolicy_amendment.command)).dim();
+            (
+                "✗ ".red(),
+                vec![
+                    "You ".into(),
+                    "refused".bold(),
+                    " to ever let codex run commands that start with ".into(),
+                    snippet,
+                ],
+            )
+        }
         Abort => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
@@ -807,6 +822,19 @@ impl HistoryCell for PatchHistoryCell {
     }
 }
 
+/// Edits made outside Codex during the current turn.
+#[derive(Debug)]
+pub(crate) struct ExternalDiffHistoryCell {
+    unified_diff: String,
+    cwd: PathBuf,
+}
+
+impl HistoryCell for ExternalDiffHistoryCell {
+    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+        create_external_diff_summary(&self.unified_diff, &self.cwd, width as usize)
+    }
+}
+
 #[derive(Debug)]
 struct CompletedMcpToolCallWithImageOutput {
     _image: DynamicImage,
@@ -979,8 +1007,8 @@ pub(crate) fn new_session_info(
             ]),
             Line::from(vec![
                 "  ".into(),
-                "/permissions".into(),
-                " - choose what Codex is allowed to do".dim(),
+                "/approvals".into(),
+                " - choose what Codex can do without approval".dim(),
             ]),
             Line::from(vec![
                 "  ".into(),
@@ -2057,6 +2085,13 @@ pub(crate) fn new_patch_event(
     }
 }
 
+pub(crate) fn new_external_diff_event(unified_diff: String, cwd: &Path) -> ExternalDiffHistoryCell {
+    ExternalDiffHistoryCell {
+        unified_diff,
+        cwd: cwd.to_path_buf(),
+    }
+}
+
 pub(crate) fn new_patch_apply_failure(stderr: String) -> PlainHistoryCell {
     let mut lines: Vec<Line<'static>> = Vec::new();
 
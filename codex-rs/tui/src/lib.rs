@@ -390,7 +390,9 @@ pub async fn run_main(
 
     let log_db_layer = codex_core::state_db::get_state_db(&config, None)
         .await
-        .map(|db| log_db::start(db).with_filter(env_filter()));
+        .map(|db| {
+            log_db::start_with_retention(db, config.log_retention.clone()).with_filter(env_filter())
+        });
 
     let _ = tracing_subscriber::registry()
         .with(file_layer)
@@ -945,7 +947,10 @@ mod tests {
         let temp_dir = TempDir::new()?;
         let mut config = build_config(&temp_dir).await?;
         config.did_user_set_custom_approval_policy_or_sandbox_mode = false;
-        config.active_project = ProjectConfig { trust_level: None };
+        config.active_project = ProjectConfig {
+            trust_level: None,
+            token_budget: None,
+        };
         config.set_windows_sandbox_enabled(false);
 
         let should_show = should_show_trust_screen(&config);
@@ -961,7 +966,10 @@ mod tests {
         let temp_dir = TempDir::new()?;
         let mut config = build_config(&temp_dir).await?;
         config.did_user_set_custom_approval_policy_or_sandbox_mode = false;
-        config.active_project = ProjectConfig { trust_level: None };
+        config.active_project = ProjectConfig {
+            trust_level: None,
+            token_budget: None,
+        };
         config.set_windows_sandbox_enabled(true);
 
         let should_show = should_show_trust_screen(&config);
@@ -986,6 +994,7 @@ mod tests {
         config.did_user_set_custom_approval_policy_or_sandbox_mode = false;
         config.active_project = ProjectConfig {
             trust_level: Some(TrustLevel::Untrusted),
+            token_budget: None,
         };
 
         let should_show = should_show_trust_screen(&config);
@@ -1015,6 +1024,7 @@ mod tests {
             developer_instructions: None,
             final_output_json_schema: None,
             truncation_policy: None,
+            model_route: None,
         }
     }
 
@@ -1,4 +1,6 @@
 use crate::render::line_utils::line_to_static;
+use crate::render::syntax::Language;
+use crate::render::syntax::highlight_code_to_lines;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_line;
 use pulldown_cmark::CodeBlockKind;
@@ -99,6 +101,10 @@ where
     pending_marker_line: bool,
     in_paragraph: bool,
     in_code_block: bool,
+    /// Language of the current fenced code block, when it is one we highlight.
+    code_block_language: Option<Language>,
+    /// Code block text is buffered so it can be highlighted as a whole.
+    code_block_buffer: String,
     wrap_width: Option<usize>,
     current_line_content: Option<Line<'static>>,
     current_initial_indent: Vec<Span<'static>>,
@@ -124,6 +130,8 @@ where
             pending_marker_line: false,
             in_paragraph: false,
             in_code_block: false,
+            code_block_language: None,
+            code_block_buffer: String::new(),
             wrap_width,
             current_line_content: None,
             current_initial_indent: Vec::new(),
@@ -278,6 +286,10 @@ where
             self.push_line(Line::default());
         }
         self.pending_marker_line = false;
+        if self.in_code_block && self.code_block_language.is_some() {
+            self.code_block_buffer.push_str(&text);
+            return;
+        }
         if self.in_code_block && !self.needs_newline {
             let has_content = self
                 .current_line_content
@@ -394,12 +406,14 @@ where
         self.needs_newline = false;
     }
 
-    fn start_codeblock(&mut self, _lang: Option<String>, indent: Option<Span<'static>>) {
+    fn start_codeblock(&mut self, lang: Option<String>, indent: Option<Span<'static>>) {
         self.flush_current_line();
         if !self.text.lines.is_empty() {
             self.push_blank_line();
         }
         self.in_code_block = true;
+        self.code_block_language = lang.as_deref().and_then(Language::from_fence_info);
+        self.code_block_buffer.clear();
         self.indent_stack.push(IndentContext::new(
             vec![indent.unwrap_or_default()],
             None,
@@ -409,11 +423,43 @@ where
     }
 
     fn end_codeblock(&mut self) {
+        if let Some(language) = self.code_block_language.take() {
+            self.flush_highlighted_code(language);
+        }
         self.needs_newline = true;
         self.in_code_block = false;
         self.indent_stack.pop();
     }
 
+    fn flush_highlighted_code(&mut self, language: Language) {
+        let code = std::mem::take(&mut self.code_block_buffer);
+        if code.is_empty() {
+            return;
+        }
+        let code = code.strip_suffix('\n').unwrap_or(&code);
+        let base = self.inline_styles.last().copied().unwrap_or_default();
+        for (i, line) in highlight_code_to_lines(code, language)
+            .into_iter()
+            .enumerate()
+        {
+            if self.needs_newline {
+                self.push_line(Line::default());
+                self.needs_newline = false;
+            }
+            if i > 0 {
+                self.push_line(Line::default());
+            }
+            if line.spans.is_empty() {
+                self.push_span(Span::styled(String::new(), base));
+            }
+            for span in line.spans {
+                let style = base.patch(span.style);
+                self.push_span(span.style(style));
+            }
+        }
+        self.needs_newline = false;
+    }
+
     fn push_inline_style(&mut self, style: Style) {
         let current = self.inline_styles.last().copied().unwrap_or_default();
         let merged = current.patch(style);
@@ -652,8 +652,19 @@ fn link() {
 }
 
 #[test]
-fn code_block_unhighlighted() {
+fn code_block_highlighted_by_fence_language() {
     let text = render_markdown_text("```rust\nfn main() {}\n```\n");
+    let expected = Text::from_iter([Line::from_iter([
+        "".into(),
+        "fn".magenta(),
+        " main() {}".into(),
+    ])]);
+    assert_eq!(text, expected);
+}
+
+#[test]
+fn code_block_unknown_language_unhighlighted() {
+    let text = render_markdown_text("```text\nfn main() {}\n```\n");
     let expected = Text::from_iter([Line::from_iter(["", "fn main() {}"])]);
     assert_eq!(text, expected);
 }
@@ -14,12 +14,18 @@
 //! `TranscriptOverlay::sync_live_tail` uses the key to decide when the cached tail must be
 //! recomputed. `ChatWidget` is responsible for producing a key that changes when the active cell
 //! mutates in place or when its transcript output is time-dependent.
+//!
+//! The transcript overlay also supports `/` incremental search over the committed cells' transcript
+//! lines (which include full exec output that is collapsed in the main view). Matches are computed
+//! against the lines at the last rendered width so each match maps to an exact content row, and the
+//! overlay paints match highlights on top of the rendered buffer.
 
 use std::io::Result;
 use std::sync::Arc;
 
 use crate::chatwidget::ActiveCellTranscriptKey;
 use crate::history_cell::HistoryCell;
+use crate::history_cell::PatchHistoryCell;
 use crate::history_cell::UserHistoryCell;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
@@ -31,6 +37,7 @@ use crate::tui;
 use crate::tui::TuiEvent;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
 use ratatui::buffer::Buffer;
 use ratatui::buffer::Cell;
 use ratatui::layout::Rect;
@@ -44,6 +51,9 @@ use ratatui::widgets::Paragraph;
 use ratatui::widgets::Widget;
 use ratatui::widgets::WidgetRef;
 use ratatui::widgets::Wrap;
+use regex_lite::Regex;
+use regex_lite::RegexBuilder;
+use unicode_width::UnicodeWidthStr;
 
 pub(crate) enum Overlay {
     Transcript(TranscriptOverlay),
@@ -102,6 +112,14 @@ const KEY_ESC: KeyBinding = key_hint::plain(KeyCode::Esc);
 const KEY_ENTER: KeyBinding = key_hint::plain(KeyCode::Enter);
 const KEY_CTRL_T: KeyBinding = key_hint::ctrl(KeyCode::Char('t'));
 const KEY_CTRL_C: KeyBinding = key_hint::ctrl(KeyCode::Char('c'));
+const KEY_SLASH: KeyBinding = key_hint::plain(KeyCode::Char('/'));
+const KEY_N: KeyBinding = key_hint::plain(KeyCode::Char('n'));
+const KEY_SHIFT_N: KeyBinding = key_hint::shift(KeyCode::Char('N'));
+const KEY_U: KeyBinding = key_hint::plain(KeyCode::Char('u'));
+const KEY_D: KeyBinding = key_hint::plain(KeyCode::Char('d'));
+const KEY_ALT_C: KeyBinding = key_hint::alt(KeyCode::Char('c'));
+const KEY_ALT_R: KeyBinding = key_hint::alt(KeyCode::Char('r'));
+const KEY_BACKSPACE: KeyBinding = key_hint::plain(KeyCode::Backspace);
 
 // Common pager navigation hints rendered on the first line
 const PAGER_KEY_HINTS: &[(&[KeyBinding], &str)] = &[
@@ -345,6 +363,28 @@ impl PagerView {
         self.scroll_offset >= max_scroll
     }
 
+    /// Resolves the effective scroll offset (e.g. `usize::MAX` when pinned to the bottom) for a
+    /// content area of the given size.
+    fn resolved_scroll_offset(&self, area: Rect) -> usize {
+        let max_scroll = self
+            .content_height(area.width)
+            .saturating_sub(area.height as usize);
+        self.scroll_offset.min(max_scroll)
+    }
+
+    /// Scroll so that the given content row is visible, centering it when it is off screen.
+    fn ensure_row_visible(&mut self, row: usize, area: Rect) {
+        let height = area.height as usize;
+        if height == 0 {
+            return;
+        }
+        let top = self.resolved_scroll_offset(area);
+        if row < top || row >= top + height {
+            let max_scroll = self.content_height(area.width).saturating_sub(height);
+            self.scroll_offset = row.saturating_sub(height / 2).min(max_scroll);
+        }
+    }
+
     /// Request that the given text chunk index be scrolled into view on next render.
     fn scroll_chunk_into_view(&mut self, chunk_index: usize) {
         self.pending_scroll_chunk = Some(chunk_index);
@@ -430,6 +470,10 @@ pub(crate) struct TranscriptOverlay {
     highlight_cell: Option<usize>,
     /// Cache key for the render-only live tail appended after committed cells.
     live_tail_key: Option<LiveTailKey>,
+    /// Incremental `/` search state.
+    search: TranscriptSearch,
+    /// Cell jump requested by a key press, resolved on the next render once wrapping is known.
+    pending_jump: Option<CellJump>,
     is_done: bool,
 }
 
@@ -463,6 +507,8 @@ impl TranscriptOverlay {
             cells: transcript_cells,
             highlight_cell: None,
             live_tail_key: None,
+            search: TranscriptSearch::default(),
+            pending_jump: None,
             is_done: false,
         }
     }
@@ -519,6 +565,7 @@ impl TranscriptOverlay {
         let tail_renderable = self.take_live_tail_renderable();
         self.cells.push(cell);
         self.view.renderables = Self::render_cells(&self.cells, self.highlight_cell);
+        self.search.invalidate();
         if let Some(tail) = tail_renderable {
             let tail = if !had_prior_cells
                 && self
@@ -626,6 +673,7 @@ impl TranscriptOverlay {
     fn rebuild_renderables(&mut self) {
         let tail_renderable = self.take_live_tail_renderable();
         self.view.renderables = Self::render_cells(&self.cells, self.highlight_cell);
+        self.search.invalidate();
         if let Some(tail) = tail_renderable {
             self.view.renderables.push(tail);
         }
@@ -656,10 +704,26 @@ impl TranscriptOverlay {
     fn render_hints(&self, area: Rect, buf: &mut Buffer) {
         let line1 = Rect::new(area.x, area.y, area.width, 1);
         let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
+        let line3 = Rect::new(area.x, area.y.saturating_add(2), area.width, 1);
         render_key_hints(line1, buf, PAGER_KEY_HINTS);
 
+        if self.search.editing {
+            let pairs: Vec<(&[KeyBinding], &str)> = vec![
+                (&[KEY_ENTER], "to confirm"),
+                (&[KEY_ESC], "to cancel"),
+                (&[KEY_ALT_C], "to toggle case"),
+                (&[KEY_ALT_R], "to toggle regex"),
+            ];
+            render_key_hints(line2, buf, &pairs);
+            self.search.render_prompt(line3, buf);
+            return;
+        }
+
         let mut pairs: Vec<(&[KeyBinding], &str)> = vec![(&[KEY_Q], "to quit")];
-        if self.highlight_cell.is_some() {
+        if self.search.is_active() {
+            pairs.push((&[KEY_ESC], "to clear search"));
+            pairs.push((&[KEY_N, KEY_SHIFT_N], "to next/prev match"));
+        } else if self.highlight_cell.is_some() {
             pairs.push((&[KEY_ESC, KEY_LEFT], "to edit prev"));
             pairs.push((&[KEY_RIGHT], "to edit next"));
             pairs.push((&[KEY_ENTER], "to edit message"));
@@ -667,22 +731,205 @@ impl TranscriptOverlay {
             pairs.push((&[KEY_ESC], "to edit prev"));
         }
         render_key_hints(line2, buf, &pairs);
+
+        if self.search.is_active() {
+            self.search.render_prompt(line3, buf);
+        } else {
+            let pairs: Vec<(&[KeyBinding], &str)> = vec![
+                (&[KEY_SLASH], "to search"),
+                (&[KEY_U, KEY_D], "to next message/diff"),
+            ];
+            render_key_hints(line3, buf, &pairs);
+        }
     }
 
     pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
         let top_h = area.height.saturating_sub(3);
         let top = Rect::new(area.x, area.y, area.width, top_h);
         let bottom = Rect::new(area.x, area.y + top_h, area.width, 3);
+        let content_area = self.view.content_area(top);
+        self.refresh_search(content_area);
+        if let Some(jump) = self.pending_jump.take() {
+            self.apply_jump(jump, content_area);
+        }
         self.view.render(top, buf);
+        self.search
+            .render_highlights(content_area, self.view.scroll_offset, buf);
         self.render_hints(bottom, buf);
     }
+
+    /// Content row at which each committed cell's transcript lines start (after any top inset).
+    fn cell_content_rows(&self, width: u16) -> Vec<usize> {
+        let mut rows = Vec::with_capacity(self.cells.len());
+        let mut top = 0;
+        for (idx, renderable) in self
+            .view
+            .renderables
+            .iter()
+            .take(self.cells.len())
+            .enumerate()
+        {
+            let inset = usize::from(idx > 0 && !self.cells[idx].is_stream_continuation());
+            rows.push(top + inset);
+            top += renderable.desired_height(width) as usize;
+        }
+        rows
+    }
+
+    /// Recompute search matches when the query, flags, width or committed cells changed.
+    fn refresh_search(&mut self, area: Rect) {
+        let key = SearchKey {
+            query: self.search.query.clone(),
+            case_sensitive: self.search.case_sensitive,
+            regex: self.search.regex,
+            width: area.width,
+        };
+        if self.search.matches_key.as_ref() == Some(&key) {
+            if self.search.pending_scroll {
+                self.scroll_to_current_match(area);
+            }
+            return;
+        }
+        self.search.matches_key = Some(key);
+        self.search.matches.clear();
+        self.search.invalid_regex = false;
+
+        let regex = match self.search.build_regex() {
+            Some(Ok(regex)) => regex,
+            Some(Err(_)) => {
+                self.search.invalid_regex = true;
+                self.search.current = None;
+                return;
+            }
+            None => {
+                self.search.current = None;
+                return;
+            }
+        };
+
+        let rows = self.cell_content_rows(area.width);
+        for (cell, first_row) in self.cells.iter().zip(rows) {
+            for (line_idx, line) in cell.transcript_lines(area.width).iter().enumerate() {
+                let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+                for m in regex.find_iter(&text) {
+                    if m.start() == m.end() {
+                        continue;
+                    }
+                    self.search.matches.push(SearchMatch {
+                        row: first_row + line_idx,
+                        start_col: text[..m.start()].width(),
+                        end_col: text[..m.end()].width(),
+                    });
+                }
+            }
+        }
+
+        if self.search.retarget {
+            // The query changed: start from the first match at or below the current viewport top,
+            // like `less` and vim do.
+            let top = self.view.resolved_scroll_offset(area);
+            self.search.current = self
+                .search
+                .matches
+                .iter()
+                .position(|m| m.row >= top)
+                .or((!self.search.matches.is_empty()).then_some(0));
+            self.search.retarget = false;
+            self.search.pending_scroll = true;
+        } else if self
+            .search
+            .current
+            .is_some_and(|idx| idx >= self.search.matches.len())
+        {
+            self.search.current = None;
+        }
+        if self.search.pending_scroll {
+            self.scroll_to_current_match(area);
+        }
+    }
+
+    fn scroll_to_current_match(&mut self, area: Rect) {
+        self.search.pending_scroll = false;
+        if let Some(m) = self.search.current_match() {
+            self.view.ensure_row_visible(m.row, area);
+        }
+    }
+
+    fn apply_jump(&mut self, jump: CellJump, area: Rect) {
+        let rows = self.cell_content_rows(area.width);
+        let top = self.view.resolved_scroll_offset(area);
+        let is_target = |cell: &Arc<dyn HistoryCell>| match jump {
+            CellJump::NextUserMessage | CellJump::PrevUserMessage => {
+                cell.as_any().is::<UserHistoryCell>()
+            }
+            CellJump::NextDiff | CellJump::PrevDiff => cell.as_any().is::<PatchHistoryCell>(),
+        };
+        let mut candidates = self
+            .cells
+            .iter()
+            .zip(rows)
+            .filter(|(cell, _)| is_target(cell))
+            .map(|(_, row)| row);
+        let target = match jump {
+            CellJump::NextUserMessage | CellJump::NextDiff => candidates.find(|row| *row > top),
+            CellJump::PrevUserMessage | CellJump::PrevDiff => {
+                candidates.rev().find(|row| *row < top)
+            }
+        };
+        if let Some(row) = target {
+            let max_scroll = self
+                .view
+                .content_height(area.width)
+                .saturating_sub(area.height as usize);
+            self.view.scroll_offset = row.min(max_scroll);
+        }
+    }
+
+    /// Whether the overlay wants this key even though backtrack navigation would normally claim
+    /// it (typing into the search prompt, or `Esc` to clear an active search).
+    pub(crate) fn captures_key(&self, key_event: KeyEvent) -> bool {
+        self.search.editing || (self.search.is_active() && KEY_ESC.is_press(key_event))
+    }
+
+    /// Handles search and jump keys. Returns `false` for keys that should fall through to the
+    /// pager's scrolling bindings.
+    fn handle_search_key(&mut self, key_event: KeyEvent) -> bool {
+        if self.search.editing {
+            return self.search.handle_prompt_key(key_event);
+        }
+        match key_event {
+            e if KEY_SLASH.is_press(e) => self.search.start(),
+            e if KEY_ESC.is_press(e) && self.search.is_active() => self.search.clear(),
+            e if KEY_ALT_C.is_press(e) => self.search.toggle_case_sensitive(),
+            e if KEY_ALT_R.is_press(e) => self.search.toggle_regex(),
+            e if KEY_N.is_press(e) => self.search.step(true),
+            e if is_shifted_char_press(e, 'n') => self.search.step(false),
+            e if KEY_U.is_press(e) => self.pending_jump = Some(CellJump::NextUserMessage),
+            e if is_shifted_char_press(e, 'u') => {
+                self.pending_jump = Some(CellJump::PrevUserMessage)
+            }
+            e if KEY_D.is_press(e) => self.pending_jump = Some(CellJump::NextDiff),
+            e if is_shifted_char_press(e, 'd') => self.pending_jump = Some(CellJump::PrevDiff),
+            _ => return false,
+        }
+        true
+    }
 }
 
 impl TranscriptOverlay {
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
         match event {
             TuiEvent::Key(key_event) => match key_event {
-                e if KEY_Q.is_press(e) || KEY_CTRL_C.is_press(e) || KEY_CTRL_T.is_press(e) => {
+                e if KEY_CTRL_C.is_press(e) || KEY_CTRL_T.is_press(e) => {
+                    self.is_done = true;
+                    Ok(())
+                }
+                e if self.handle_search_key(e) => {
+                    tui.frame_requester()
+                        .schedule_frame_in(crate::tui::TARGET_FRAME_INTERVAL);
+                    Ok(())
+                }
+                e if KEY_Q.is_press(e) => {
                     self.is_done = true;
                     Ok(())
                 }
@@ -707,6 +954,206 @@ impl TranscriptOverlay {
     }
 }
 
+/// Cell-level navigation shortcuts in the transcript overlay.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CellJump {
+    NextUserMessage,
+    PrevUserMessage,
+    NextDiff,
+    PrevDiff,
+}
+
+/// A single search hit, located by content row and display columns within that row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct SearchMatch {
+    row: usize,
+    start_col: usize,
+    end_col: usize,
+}
+
+/// Inputs the current match list was computed from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct SearchKey {
+    query: String,
+    case_sensitive: bool,
+    regex: bool,
+    width: u16,
+}
+
+/// Incremental search state for the transcript overlay.
+///
+/// Matching is case-insensitive and literal by default; `alt+c` and `alt+r` toggle case
+/// sensitivity and regex syntax.
+#[derive(Default)]
+struct TranscriptSearch {
+    query: String,
+    /// Whether the `/` prompt is open and receiving keystrokes.
+    editing: bool,
+    case_sensitive: bool,
+    regex: bool,
+    invalid_regex: bool,
+    matches: Vec<SearchMatch>,
+    current: Option<usize>,
+    /// `None` forces the matches to be recomputed on the next render.
+    matches_key: Option<SearchKey>,
+    /// Pick a new current match (relative to the viewport) after recomputing.
+    retarget: bool,
+    /// Scroll the current match into view on the next render.
+    pending_scroll: bool,
+}
+
+impl TranscriptSearch {
+    fn is_active(&self) -> bool {
+        !self.query.is_empty()
+    }
+
+    fn invalidate(&mut self) {
+        self.matches_key = None;
+    }
+
+    fn start(&mut self) {
+        self.editing = true;
+        self.query.clear();
+        self.retarget = true;
+    }
+
+    fn clear(&mut self) {
+        *self = Self {
+            case_sensitive: self.case_sensitive,
+            regex: self.regex,
+            ..Self::default()
+        };
+    }
+
+    fn toggle_case_sensitive(&mut self) {
+        self.case_sensitive = !self.case_sensitive;
+        self.retarget = true;
+    }
+
+    fn toggle_regex(&mut self) {
+        self.regex = !self.regex;
+        self.retarget = true;
+    }
+
+    fn step(&mut self, forward: bool) {
+        let len = self.matches.len();
+        if len == 0 {
+            return;
+        }
+        self.current = Some(match (self.current, forward) {
+            (Some(idx), true) => (idx + 1) % len,
+            (Some(idx), false) => (idx + len - 1) % len,
+            (None, true) => 0,
+            (None, false) => len - 1,
+        });
+        self.pending_scroll = true;
+    }
+
+    fn current_match(&self) -> Option<&SearchMatch> {
+        self.current.and_then(|idx| self.matches.get(idx))
+    }
+
+    /// Returns `None` when there is nothing to search for.
+    fn build_regex(&self) -> Option<std::result::Result<Regex, regex_lite::Error>> {
+        if self.query.is_empty() {
+            return None;
+        }
+        let pattern = if self.regex {
+            self.query.clone()
+        } else {
+            regex_lite::escape(&self.query)
+        };
+        Some(
+            RegexBuilder::new(&pattern)
+                .case_insensitive(!self.case_sensitive)
+                .build(),
+        )
+    }
+
+    fn handle_prompt_key(&mut self, key_event: KeyEvent) -> bool {
+        match key_event {
+            e if KEY_ENTER.is_press(e) => {
+                self.editing = false;
+                self.pending_scroll = true;
+            }
+            e if KEY_ESC.is_press(e) => self.clear(),
+            e if KEY_BACKSPACE.is_press(e) => {
+                if self.query.pop().is_none() {
+                    self.clear();
+                } else {
+                    self.retarget = true;
+                }
+            }
+            e if KEY_ALT_C.is_press(e) => self.toggle_case_sensitive(),
+            e if KEY_ALT_R.is_press(e) => self.toggle_regex(),
+            KeyEvent {
+                code: KeyCode::Char(c),
+                modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+                kind: crossterm::event::KeyEventKind::Press | crossterm::event::KeyEventKind::Repeat,
+                ..
+            } => {
+                self.query.push(c);
+                self.retarget = true;
+            }
+            _ => {}
+        }
+        // The prompt swallows every key so typing never scrolls or quits the pager.
+        true
+    }
+
+    fn render_prompt(&self, area: Rect, buf: &mut Buffer) {
+        let mut spans: Vec<Span<'static>> = vec![" /".cyan(), self.query.clone().into()];
+        if self.editing {
+            spans.push("▌".dim());
+        }
+        if self.invalid_regex {
+            spans.push("  invalid regex".red());
+        } else if self.is_active() {
+            let status = match self.current {
+                Some(idx) => format!("  {} of {}", idx + 1, self.matches.len()),
+                None if self.matches.is_empty() => "  no matches".to_string(),
+                None => format!("  {} matches", self.matches.len()),
+            };
+            spans.push(status.dim());
+        }
+        if self.case_sensitive {
+            spans.push("  [case]".cyan());
+        }
+        if self.regex {
+            spans.push("  [regex]".cyan());
+        }
+        Paragraph::new(Line::from(spans)).render_ref(area, buf);
+    }
+
+    /// Paint match highlights over already-rendered content.
+    fn render_highlights(&self, area: Rect, scroll_offset: usize, buf: &mut Buffer) {
+        let visible = scroll_offset..scroll_offset.saturating_add(area.height as usize);
+        for (idx, m) in self.matches.iter().enumerate() {
+            if !visible.contains(&m.row) {
+                continue;
+            }
+            let style = if self.current == Some(idx) {
+                Style::default().cyan().reversed()
+            } else {
+                Style::default().reversed()
+            };
+            let y = area.y + (m.row - scroll_offset) as u16;
+            let end_col = m.end_col.min(area.width as usize);
+            for col in m.start_col..end_col {
+                buf[(area.x + col as u16, y)].set_style(style);
+            }
+        }
+    }
+}
+
+/// Matches `c` typed with shift held, which terminals report either as an uppercase char with
+/// `SHIFT` or as a bare uppercase char.
+fn is_shifted_char_press(key_event: KeyEvent, c: char) -> bool {
+    let upper = c.to_ascii_uppercase();
+    key_hint::shift(KeyCode::Char(upper)).is_press(key_event)
+        || key_hint::plain(KeyCode::Char(upper)).is_press(key_event)
+}
+
 pub(crate) struct StaticOverlay {
     view: PagerView,
     is_done: bool,
@@ -813,6 +1260,8 @@ mod tests {
     use codex_protocol::parse_command::ParsedCommand;
     use ratatui::Terminal;
     use ratatui::backend::TestBackend;
+    use ratatui::style::Color;
+    use ratatui::style::Modifier;
     use ratatui::text::Text;
 
     #[derive(Debug)]
@@ -1024,6 +1473,153 @@ mod tests {
         assert_snapshot!("transcript_overlay_apply_patch_scroll_vt100", snapshot);
     }
 
+    fn press(overlay: &mut TranscriptOverlay, code: KeyCode, modifiers: KeyModifiers) {
+        overlay.handle_search_key(KeyEvent::new(code, modifiers));
+    }
+
+    fn type_search(overlay: &mut TranscriptOverlay, query: &str) {
+        press(overlay, KeyCode::Char('/'), KeyModifiers::NONE);
+        for c in query.chars() {
+            press(overlay, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        press(overlay, KeyCode::Enter, KeyModifiers::NONE);
+    }
+
+    #[test]
+    fn transcript_search_highlights_and_steps_through_matches() {
+        let mut overlay = TranscriptOverlay::new(vec![
+            Arc::new(TestCell {
+                lines: vec![Line::from("alpha one")],
+            }),
+            Arc::new(TestCell {
+                lines: vec![Line::from("beta")],
+            }),
+            Arc::new(TestCell {
+                lines: vec![Line::from("Alpha two")],
+            }),
+        ]);
+        let area = Rect::new(0, 0, 40, 12);
+        let mut buf = Buffer::empty(area);
+
+        type_search(&mut overlay, "alpha");
+        overlay.render(area, &mut buf);
+        assert_eq!(overlay.search.matches.len(), 2);
+        assert_eq!(overlay.search.current, Some(0));
+        // Content starts below the header row; the current match is cyan + reversed.
+        assert_eq!(buf[(0, 1)].fg, Color::Cyan);
+        assert!(buf[(0, 1)].modifier.contains(Modifier::REVERSED));
+        assert_ne!(buf[(0, 5)].fg, Color::Cyan);
+        assert!(buf[(0, 5)].modifier.contains(Modifier::REVERSED));
+        assert!(buffer_to_text(&buf, area).contains("/alpha  1 of 2"));
+
+        press(&mut overlay, KeyCode::Char('n'), KeyModifiers::NONE);
+        overlay.render(area, &mut buf);
+        assert_eq!(overlay.search.current, Some(1));
+        assert_eq!(buf[(0, 5)].fg, Color::Cyan);
+
+        press(&mut overlay, KeyCode::Char('N'), KeyModifiers::SHIFT);
+        overlay.render(area, &mut buf);
+        assert_eq!(overlay.search.current, Some(0));
+
+        press(&mut overlay, KeyCode::Char('c'), KeyModifiers::ALT);
+        overlay.render(area, &mut buf);
+        assert_eq!(overlay.search.matches.len(), 1);
+        assert!(buffer_to_text(&buf, area).contains("[case]"));
+    }
+
+    #[test]
+    fn transcript_search_supports_regex_and_reports_invalid_patterns() {
+        let mut overlay = TranscriptOverlay::new(vec![Arc::new(TestCell {
+            lines: vec![Line::from("exit 0"), Line::from("exit 17")],
+        })]);
+        let area = Rect::new(0, 0, 40, 12);
+        let mut buf = Buffer::empty(area);
+
+        press(&mut overlay, KeyCode::Char('r'), KeyModifiers::ALT);
+        type_search(&mut overlay, "exit [1-9]");
+        overlay.render(area, &mut buf);
+        assert_eq!(
+            overlay.search.matches,
+            vec![SearchMatch {
+                row: 1,
+                start_col: 0,
+                end_col: 6,
+            }]
+        );
+
+        type_search(&mut overlay, "exit (");
+        overlay.render(area, &mut buf);
+        assert!(overlay.search.matches.is_empty());
+        assert!(buffer_to_text(&buf, area).contains("invalid regex"));
+    }
+
+    #[test]
+    fn transcript_search_prompt_captures_backtrack_keys() {
+        let mut overlay = TranscriptOverlay::new(vec![Arc::new(TestCell {
+            lines: vec![Line::from("hello")],
+        })]);
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let esc = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(!overlay.captures_key(esc));
+
+        press(&mut overlay, KeyCode::Char('/'), KeyModifiers::NONE);
+        assert!(overlay.captures_key(enter));
+        press(&mut overlay, KeyCode::Char('q'), KeyModifiers::NONE);
+        assert_eq!(overlay.search.query, "q");
+
+        press(&mut overlay, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(!overlay.captures_key(enter));
+        assert!(overlay.captures_key(esc));
+
+        press(&mut overlay, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(!overlay.search.is_active());
+        assert!(!overlay.captures_key(esc));
+    }
+
+    #[test]
+    fn transcript_jumps_to_next_and_previous_diff() {
+        let mut cells: Vec<Arc<dyn HistoryCell>> = (0..20)
+            .map(|i| {
+                Arc::new(TestCell {
+                    lines: vec![Line::from(format!("line{i}"))],
+                }) as Arc<dyn HistoryCell>
+            })
+            .collect();
+        let mut changes = HashMap::new();
+        changes.insert(
+            PathBuf::from("foo.txt"),
+            FileChange::Add {
+                content: "hello\n".to_string(),
+            },
+        );
+        cells.push(Arc::new(new_patch_event(changes, &PathBuf::from("/repo"))));
+        cells.extend((0..20).map(|i| {
+            Arc::new(TestCell {
+                lines: vec![Line::from(format!("tail{i}"))],
+            }) as Arc<dyn HistoryCell>
+        }));
+        let mut overlay = TranscriptOverlay::new(cells);
+        let area = Rect::new(0, 0, 40, 12);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+        overlay.view.scroll_offset = 0;
+
+        press(&mut overlay, KeyCode::Char('d'), KeyModifiers::NONE);
+        overlay.render(area, &mut buf);
+        let diff_row = overlay.cell_content_rows(area.width)[20];
+        assert_eq!(overlay.view.scroll_offset, diff_row);
+
+        // No further diff below: the position is unchanged.
+        press(&mut overlay, KeyCode::Char('d'), KeyModifiers::NONE);
+        overlay.render(area, &mut buf);
+        assert_eq!(overlay.view.scroll_offset, diff_row);
+
+        overlay.view.scroll_offset = diff_row + 10;
+        press(&mut overlay, KeyCode::Char('D'), KeyModifiers::SHIFT);
+        overlay.render(area, &mut buf);
+        assert_eq!(overlay.view.scroll_offset, diff_row);
+    }
+
     #[test]
     fn transcript_overlay_keeps_scroll_pinned_at_bottom() {
         let mut overlay = TranscriptOverlay::new(
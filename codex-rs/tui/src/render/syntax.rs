@@ -0,0 +1,976 @@
+//! Language-aware highlighting for fenced code blocks, file reads and diffs.
+//!
+//! Bash goes through the tree-sitter grammar in [`super::highlight`]. Other
+//! languages use a small lexer that recognizes comments, strings, numbers,
+//! keywords and a few identifier classes, which is enough to make code
+//! readable without shipping a grammar per language.
+//!
+//! Styles only use the ANSI colors from `tui/styles.md`, so they inherit the
+//! terminal theme and degrade to plain modifiers when color is unavailable.
+
+use std::path::Path;
+
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+use super::highlight::highlight_bash_to_lines;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Language {
+    Bash,
+    C,
+    Cpp,
+    Go,
+    Java,
+    JavaScript,
+    Json,
+    Python,
+    Ruby,
+    Rust,
+    Sql,
+    Toml,
+    TypeScript,
+    Yaml,
+}
+
+impl Language {
+    /// Resolve the language from a fenced code block info string such as
+    /// `rust`, `rust,ignore` or `ts {title="x"}`.
+    pub(crate) fn from_fence_info(info: &str) -> Option<Self> {
+        let tag = info
+            .split(|c: char| c.is_whitespace() || c == ',' || c == '{')
+            .next()?;
+        Self::from_name(&tag.to_ascii_lowercase())
+    }
+
+    /// Resolve the language from a file name or extension.
+    pub(crate) fn from_path(path: &Path) -> Option<Self> {
+        let file_name = path.file_name()?.to_str()?;
+        match file_name {
+            "Cargo.lock" => return Some(Self::Toml),
+            ".bashrc" | ".zshrc" | ".profile" | ".bash_profile" => return Some(Self::Bash),
+            _ => {}
+        }
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        Self::from_name(&extension)
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        let language = match name {
+            "bash" | "sh" | "shell" | "zsh" => Self::Bash,
+            "c" | "h" => Self::C,
+            "cpp" | "c++" | "cc" | "cxx" | "hpp" | "hh" | "hxx" => Self::Cpp,
+            "go" | "golang" => Self::Go,
+            "java" | "kt" | "kotlin" | "scala" => Self::Java,
+            "js" | "jsx" | "mjs" | "cjs" | "javascript" => Self::JavaScript,
+            "json" | "jsonc" | "json5" | "jsonl" => Self::Json,
+            "py" | "pyi" | "python" | "python3" => Self::Python,
+            "rb" | "ruby" => Self::Ruby,
+            "rs" | "rust" => Self::Rust,
+            "sql" => Self::Sql,
+            "toml" => Self::Toml,
+            "ts" | "tsx" | "mts" | "cts" | "typescript" => Self::TypeScript,
+            "yaml" | "yml" => Self::Yaml,
+            _ => return None,
+        };
+        Some(language)
+    }
+
+    fn spec(self) -> &'static LexSpec {
+        match self {
+            // Bash is highlighted with tree-sitter; the spec is only a fallback.
+            Self::Bash => &BASH,
+            Self::C => &C,
+            Self::Cpp => &CPP,
+            Self::Go => &GO,
+            Self::Java => &JAVA,
+            Self::JavaScript => &JAVASCRIPT,
+            Self::Json => &JSON,
+            Self::Python => &PYTHON,
+            Self::Ruby => &RUBY,
+            Self::Rust => &RUST,
+            Self::Sql => &SQL,
+            Self::Toml => &TOML,
+            Self::TypeScript => &TYPESCRIPT,
+            Self::Yaml => &YAML,
+        }
+    }
+}
+
+/// Per-token styles. Only ANSI colors and modifiers are used so themes follow
+/// the user's terminal palette.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct SyntaxTheme {
+    keyword: Style,
+    literal: Style,
+    string: Style,
+    comment: Style,
+    number: Style,
+    type_name: Style,
+    property: Style,
+}
+
+impl SyntaxTheme {
+    /// Theme for dark (or unknown) terminal backgrounds.
+    pub(crate) fn dark() -> Self {
+        Self {
+            keyword: Style::default().magenta(),
+            literal: Style::default().cyan(),
+            string: Style::default().green(),
+            comment: Style::default().dim().italic(),
+            number: Style::default().cyan(),
+            type_name: Style::default().cyan(),
+            property: Style::default().cyan(),
+        }
+    }
+
+    /// Theme for light backgrounds. Default ANSI green and cyan tend to wash
+    /// out on light palettes, so those tokens are bolded, and comments drop
+    /// the italic to stay legible when dimmed.
+    pub(crate) fn light() -> Self {
+        Self {
+            keyword: Style::default().magenta(),
+            literal: Style::default().cyan().bold(),
+            string: Style::default().green().bold(),
+            comment: Style::default().dim(),
+            number: Style::default().cyan().bold(),
+            type_name: Style::default().cyan().bold(),
+            property: Style::default().cyan(),
+        }
+    }
+
+    /// Modifier-only theme for terminals without color, and for content that
+    /// already carries a foreground color (e.g. added/removed diff lines).
+    pub(crate) fn monochrome() -> Self {
+        Self {
+            keyword: Style::default().bold(),
+            literal: Style::default(),
+            string: Style::default().italic(),
+            comment: Style::default().dim(),
+            number: Style::default(),
+            type_name: Style::default(),
+            property: Style::default(),
+        }
+    }
+
+    /// Pick a theme from the terminal's color support and background.
+    pub(crate) fn for_terminal(has_color: bool, terminal_bg: Option<(u8, u8, u8)>) -> Self {
+        if !has_color {
+            return Self::monochrome();
+        }
+        match terminal_bg {
+            Some(bg) if crate::color::is_light(bg) => Self::light(),
+            _ => Self::dark(),
+        }
+    }
+
+    /// Theme for the current terminal.
+    #[cfg(not(test))]
+    pub(crate) fn current() -> Self {
+        let has_color = supports_color::on_cached(supports_color::Stream::Stdout).is_some();
+        Self::for_terminal(has_color, crate::terminal_palette::default_bg())
+    }
+
+    /// Tests render with a fixed theme so styled output is deterministic.
+    #[cfg(test)]
+    pub(crate) fn current() -> Self {
+        Self::dark()
+    }
+
+    fn style_for(&self, kind: TokenKind) -> Style {
+        match kind {
+            TokenKind::Plain => Style::default(),
+            TokenKind::Keyword => self.keyword,
+            TokenKind::Literal => self.literal,
+            TokenKind::String => self.string,
+            TokenKind::Comment => self.comment,
+            TokenKind::Number => self.number,
+            TokenKind::TypeName => self.type_name,
+            TokenKind::Property => self.property,
+        }
+    }
+}
+
+/// Highlight `code` with the current terminal theme, returning one `Line` per
+/// source line.
+pub(crate) fn highlight_code_to_lines(code: &str, language: Language) -> Vec<Line<'static>> {
+    highlight_code_with_theme(code, language, &SyntaxTheme::current())
+}
+
+pub(crate) fn highlight_code_with_theme(
+    code: &str,
+    language: Language,
+    theme: &SyntaxTheme,
+) -> Vec<Line<'static>> {
+    if language == Language::Bash {
+        return highlight_bash_to_lines(code);
+    }
+
+    let mut lines: Vec<Line<'static>> = vec![Line::default()];
+    for (kind, text) in lex(code, language.spec()) {
+        let style = theme.style_for(kind);
+        for (i, part) in text.split('\n').enumerate() {
+            if i > 0 {
+                lines.push(Line::default());
+            }
+            if part.is_empty() {
+                continue;
+            }
+            let Some(line) = lines.last_mut() else {
+                continue;
+            };
+            match line.spans.last_mut() {
+                Some(last) if last.style == style => last.content.to_mut().push_str(part),
+                _ => line.spans.push(Span::styled(part.to_string(), style)),
+            }
+        }
+    }
+    lines
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TokenKind {
+    Plain,
+    Keyword,
+    Literal,
+    String,
+    Comment,
+    Number,
+    TypeName,
+    Property,
+}
+
+struct LexSpec {
+    keywords: &'static [&'static str],
+    literals: &'static [&'static str],
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+    /// Python-style `"""` / `'''` strings.
+    triple_quotes: bool,
+    /// Rust-style `'c'` char literals; a lone `'` (lifetimes) stays plain.
+    char_literals: bool,
+    /// Keywords are matched case-insensitively (SQL).
+    case_insensitive_keywords: bool,
+    /// Capitalized identifiers are treated as type names.
+    capitalized_types: bool,
+    /// Identifiers or strings followed by this character are keys (JSON/YAML
+    /// `:`, TOML `=`).
+    key_separator: Option<char>,
+}
+
+const DEFAULT_SPEC: LexSpec = LexSpec {
+    keywords: &[],
+    literals: &[],
+    line_comments: &[],
+    block_comment: None,
+    quotes: &['"', '\''],
+    triple_quotes: false,
+    char_literals: false,
+    case_insensitive_keywords: false,
+    capitalized_types: false,
+    key_separator: None,
+};
+
+const C_KEYWORDS: &[&str] = &[
+    "auto", "break", "case", "char", "const", "continue", "default", "do", "double", "else",
+    "enum", "extern", "float", "for", "goto", "if", "inline", "int", "long", "register", "return",
+    "short", "signed", "sizeof", "static", "struct", "switch", "typedef", "union", "unsigned",
+    "void", "volatile", "while", "#include", "#define", "#ifdef", "#ifndef", "#endif", "#if",
+    "#else", "#pragma",
+];
+
+const BASH: LexSpec = LexSpec {
+    keywords: &[
+        "if", "then", "else", "elif", "fi", "for", "while", "until", "do", "done", "case", "esac",
+        "in", "function", "return", "local", "export",
+    ],
+    line_comments: &["#"],
+    ..DEFAULT_SPEC
+};
+
+const C: LexSpec = LexSpec {
+    keywords: C_KEYWORDS,
+    literals: &["NULL", "true", "false"],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    ..DEFAULT_SPEC
+};
+
+const CPP: LexSpec = LexSpec {
+    keywords: &[
+        "auto",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "const",
+        "constexpr",
+        "continue",
+        "default",
+        "delete",
+        "do",
+        "else",
+        "enum",
+        "explicit",
+        "for",
+        "friend",
+        "if",
+        "inline",
+        "namespace",
+        "new",
+        "noexcept",
+        "operator",
+        "override",
+        "private",
+        "protected",
+        "public",
+        "return",
+        "sizeof",
+        "static",
+        "struct",
+        "switch",
+        "template",
+        "this",
+        "throw",
+        "try",
+        "typedef",
+        "typename",
+        "union",
+        "using",
+        "virtual",
+        "void",
+        "while",
+        "int",
+        "char",
+        "bool",
+        "double",
+        "float",
+        "long",
+        "unsigned",
+        "#include",
+        "#define",
+        "#ifdef",
+        "#ifndef",
+        "#endif",
+        "#pragma",
+    ],
+    literals: &["nullptr", "NULL", "true", "false"],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    capitalized_types: true,
+    ..DEFAULT_SPEC
+};
+
+const GO: LexSpec = LexSpec {
+    keywords: &[
+        "break",
+        "case",
+        "chan",
+        "const",
+        "continue",
+        "default",
+        "defer",
+        "else",
+        "fallthrough",
+        "for",
+        "func",
+        "go",
+        "goto",
+        "if",
+        "import",
+        "interface",
+        "map",
+        "package",
+        "range",
+        "return",
+        "select",
+        "struct",
+        "switch",
+        "type",
+        "var",
+    ],
+    literals: &["nil", "true", "false", "iota"],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\'', '`'],
+    capitalized_types: true,
+    ..DEFAULT_SPEC
+};
+
+const JAVA: LexSpec = LexSpec {
+    keywords: &[
+        "abstract",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "continue",
+        "default",
+        "do",
+        "else",
+        "enum",
+        "extends",
+        "final",
+        "finally",
+        "for",
+        "fun",
+        "if",
+        "implements",
+        "import",
+        "instanceof",
+        "interface",
+        "new",
+        "object",
+        "package",
+        "private",
+        "protected",
+        "public",
+        "return",
+        "static",
+        "super",
+        "switch",
+        "synchronized",
+        "this",
+        "throw",
+        "throws",
+        "try",
+        "val",
+        "var",
+        "void",
+        "when",
+        "while",
+    ],
+    literals: &["null", "true", "false"],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    capitalized_types: true,
+    ..DEFAULT_SPEC
+};
+
+const JS_KEYWORDS: &[&str] = &[
+    "async",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "export",
+    "extends",
+    "finally",
+    "for",
+    "from",
+    "function",
+    "if",
+    "import",
+    "in",
+    "instanceof",
+    "let",
+    "new",
+    "of",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "yield",
+];
+
+const JAVASCRIPT: LexSpec = LexSpec {
+    keywords: JS_KEYWORDS,
+    literals: &["null", "undefined", "true", "false", "NaN"],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\'', '`'],
+    capitalized_types: true,
+    ..DEFAULT_SPEC
+};
+
+const TYPESCRIPT: LexSpec = LexSpec {
+    keywords: &[
+        "abstract",
+        "as",
+        "async",
+        "await",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "const",
+        "continue",
+        "declare",
+        "default",
+        "delete",
+        "do",
+        "else",
+        "enum",
+        "export",
+        "extends",
+        "finally",
+        "for",
+        "from",
+        "function",
+        "if",
+        "implements",
+        "import",
+        "in",
+        "instanceof",
+        "interface",
+        "keyof",
+        "let",
+        "namespace",
+        "new",
+        "of",
+        "private",
+        "protected",
+        "public",
+        "readonly",
+        "return",
+        "static",
+        "super",
+        "switch",
+        "this",
+        "throw",
+        "try",
+        "type",
+        "typeof",
+        "var",
+        "void",
+        "while",
+        "yield",
+    ],
+    ..JAVASCRIPT
+};
+
+const JSON: LexSpec = LexSpec {
+    literals: &["null", "true", "false"],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"'],
+    key_separator: Some(':'),
+    ..DEFAULT_SPEC
+};
+
+const PYTHON: LexSpec = LexSpec {
+    keywords: &[
+        "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+        "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in", "is",
+        "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while", "with",
+        "yield", "match", "case",
+    ],
+    literals: &["None", "True", "False", "self", "cls"],
+    line_comments: &["#"],
+    triple_quotes: true,
+    capitalized_types: true,
+    ..DEFAULT_SPEC
+};
+
+const RUBY: LexSpec = LexSpec {
+    keywords: &[
+        "alias", "and", "begin", "break", "case", "class", "def", "do", "else", "elsif", "end",
+        "ensure", "for", "if", "in", "module", "next", "not", "or", "redo", "rescue", "retry",
+        "return", "self", "super", "then", "unless", "until", "when", "while", "yield", "require",
+    ],
+    literals: &["nil", "true", "false"],
+    line_comments: &["#"],
+    capitalized_types: true,
+    ..DEFAULT_SPEC
+};
+
+const RUST: LexSpec = LexSpec {
+    keywords: &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+        "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "type",
+        "unsafe", "use", "where", "while",
+    ],
+    literals: &["true", "false", "None", "Some", "Ok", "Err"],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"'],
+    char_literals: true,
+    capitalized_types: true,
+    ..DEFAULT_SPEC
+};
+
+const SQL: LexSpec = LexSpec {
+    keywords: &[
+        "select",
+        "from",
+        "where",
+        "and",
+        "or",
+        "not",
+        "insert",
+        "into",
+        "values",
+        "update",
+        "set",
+        "delete",
+        "create",
+        "table",
+        "drop",
+        "alter",
+        "index",
+        "join",
+        "left",
+        "right",
+        "inner",
+        "outer",
+        "on",
+        "group",
+        "by",
+        "order",
+        "having",
+        "limit",
+        "offset",
+        "as",
+        "distinct",
+        "union",
+        "all",
+        "case",
+        "when",
+        "then",
+        "else",
+        "end",
+        "like",
+        "in",
+        "is",
+        "primary",
+        "key",
+        "foreign",
+        "references",
+        "with",
+    ],
+    literals: &["null", "true", "false"],
+    line_comments: &["--"],
+    block_comment: Some(("/*", "*/")),
+    case_insensitive_keywords: true,
+    ..DEFAULT_SPEC
+};
+
+const TOML: LexSpec = LexSpec {
+    literals: &["true", "false"],
+    line_comments: &["#"],
+    triple_quotes: true,
+    key_separator: Some('='),
+    ..DEFAULT_SPEC
+};
+
+const YAML: LexSpec = LexSpec {
+    literals: &["null", "true", "false", "yes", "no", "~"],
+    line_comments: &["#"],
+    key_separator: Some(':'),
+    ..DEFAULT_SPEC
+};
+
+/// Split `code` into `(kind, text)` tokens. Concatenating the texts always
+/// reproduces `code` exactly.
+fn lex<'a>(code: &'a str, spec: &LexSpec) -> Vec<(TokenKind, &'a str)> {
+    let mut tokens: Vec<(TokenKind, &'a str)> = Vec::new();
+    let mut push = |kind: TokenKind, text: &'a str| tokens.push((kind, text));
+
+    let bytes = code.as_bytes();
+    let mut i = 0;
+    while i < code.len() {
+        let rest = &code[i..];
+
+        if let Some((open, close)) = spec.block_comment
+            && rest.starts_with(open)
+        {
+            let end = rest[open.len()..]
+                .find(close)
+                .map(|idx| open.len() + idx + close.len())
+                .unwrap_or(rest.len());
+            push(TokenKind::Comment, &rest[..end]);
+            i += end;
+            continue;
+        }
+
+        if spec
+            .line_comments
+            .iter()
+            .any(|prefix| rest.starts_with(prefix))
+        {
+            let end = rest.find('\n').unwrap_or(rest.len());
+            push(TokenKind::Comment, &rest[..end]);
+            i += end;
+            continue;
+        }
+
+        if spec.triple_quotes
+            && let Some(delim) = ["\"\"\"", "'''"]
+                .into_iter()
+                .find(|delim| rest.starts_with(delim))
+        {
+            let end = rest[3..]
+                .find(delim)
+                .map(|idx| 3 + idx + 3)
+                .unwrap_or(rest.len());
+            push(TokenKind::String, &rest[..end]);
+            i += end;
+            continue;
+        }
+
+        let Some(ch) = rest.chars().next() else {
+            break;
+        };
+
+        if spec.quotes.contains(&ch) {
+            let end = scan_string(rest, ch);
+            let kind = if is_key(code, i + end, spec) {
+                TokenKind::Property
+            } else {
+                TokenKind::String
+            };
+            push(kind, &rest[..end]);
+            i += end;
+            continue;
+        }
+
+        if spec.char_literals
+            && ch == '\''
+            && let Some(end) = scan_char_literal(rest)
+        {
+            push(TokenKind::String, &rest[..end]);
+            i += end;
+            continue;
+        }
+
+        let prev_is_word = i > 0 && is_word_byte(bytes[i - 1]);
+        if ch.is_ascii_digit() && !prev_is_word {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '.'))
+                .unwrap_or(rest.len());
+            push(TokenKind::Number, &rest[..end]);
+            i += end;
+            continue;
+        }
+
+        if (ch.is_alphabetic() || ch == '_' || ch == '#') && !prev_is_word {
+            let end = rest
+                .char_indices()
+                .skip(1)
+                .find(|(_, c)| !(c.is_alphanumeric() || *c == '_' || (*c == '-' && is_kebab(spec))))
+                .map(|(idx, _)| idx)
+                .unwrap_or(rest.len());
+            let word = &rest[..end];
+            push(classify_word(word, code, i + end, spec), word);
+            i += end;
+            continue;
+        }
+
+        push(TokenKind::Plain, &rest[..ch.len_utf8()]);
+        i += ch.len_utf8();
+    }
+    tokens
+}
+
+/// YAML and TOML keys commonly contain dashes.
+fn is_kebab(spec: &LexSpec) -> bool {
+    spec.key_separator.is_some()
+}
+
+fn is_word_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
+
+/// Returns the byte length of the string starting at the beginning of `rest`
+/// (including both quotes). Unterminated strings end at the end of the line,
+/// except for backtick strings which may span lines.
+fn scan_string(rest: &str, quote: char) -> usize {
+    let mut escaped = false;
+    for (idx, c) in rest.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' => escaped = true,
+            '\n' if quote != '`' => return idx,
+            c if c == quote => return idx + c.len_utf8(),
+            _ => {}
+        }
+    }
+    rest.len()
+}
+
+/// Matches `'x'` and `'\n'`-style char literals.
+fn scan_char_literal(rest: &str) -> Option<usize> {
+    let mut chars = rest.char_indices().skip(1);
+    let (_, first) = chars.next()?;
+    if first == '\\' {
+        let (idx, _) = chars.find(|(_, c)| *c == '\'')?;
+        // Escapes are short (`\n`, `\u{1F600}`); anything longer is not a char.
+        return (idx <= 12).then_some(idx + 1);
+    }
+    let (idx, closing) = chars.next()?;
+    (closing == '\'').then_some(idx + 1)
+}
+
+fn is_key(code: &str, end: usize, spec: &LexSpec) -> bool {
+    let Some(separator) = spec.key_separator else {
+        return false;
+    };
+    code[end..]
+        .chars()
+        .find(|c| *c != ' ' && *c != '\t')
+        .is_some_and(|c| c == separator)
+}
+
+fn classify_word(word: &str, code: &str, end: usize, spec: &LexSpec) -> TokenKind {
+    let is_keyword = if spec.case_insensitive_keywords {
+        spec.keywords
+            .iter()
+            .any(|keyword| keyword.eq_ignore_ascii_case(word))
+    } else {
+        spec.keywords.contains(&word)
+    };
+    if is_keyword {
+        return TokenKind::Keyword;
+    }
+    if spec.literals.contains(&word) {
+        return TokenKind::Literal;
+    }
+    if is_key(code, end, spec) {
+        return TokenKind::Property;
+    }
+    if spec.capitalized_types && word.chars().next().is_some_and(char::is_uppercase) {
+        return TokenKind::TypeName;
+    }
+    TokenKind::Plain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn reconstructed(lines: &[Line<'static>]) -> String {
+        lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn spans_with_style(lines: &[Line<'static>], style: Style) -> Vec<String> {
+        lines
+            .iter()
+            .flat_map(|line| line.spans.iter())
+            .filter(|span| span.style == style)
+            .map(|span| span.content.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn detects_language_from_fence_info_and_path() {
+        assert_eq!(Language::from_fence_info("rust"), Some(Language::Rust));
+        assert_eq!(
+            Language::from_fence_info("Rust,ignore"),
+            Some(Language::Rust)
+        );
+        assert_eq!(
+            Language::from_fence_info("ts {title=\"x\"}"),
+            Some(Language::TypeScript)
+        );
+        assert_eq!(Language::from_fence_info("text"), None);
+        assert_eq!(Language::from_fence_info(""), None);
+        assert_eq!(
+            Language::from_path(Path::new("src/main.py")),
+            Some(Language::Python)
+        );
+        assert_eq!(
+            Language::from_path(Path::new("Cargo.lock")),
+            Some(Language::Toml)
+        );
+        assert_eq!(Language::from_path(Path::new("README")), None);
+    }
+
+    #[test]
+    fn highlights_rust_tokens_with_dark_theme() {
+        let code = "// hi\nfn main() {\n    let s = \"x\"; let n = 42;\n}";
+        let theme = SyntaxTheme::dark();
+        let lines = highlight_code_with_theme(code, Language::Rust, &theme);
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(reconstructed(&lines), code);
+        assert_eq!(spans_with_style(&lines, theme.comment), vec!["// hi"]);
+        assert_eq!(
+            spans_with_style(&lines, theme.keyword),
+            vec!["fn", "let", "let"]
+        );
+        assert_eq!(spans_with_style(&lines, theme.string), vec!["\"x\""]);
+        assert_eq!(spans_with_style(&lines, theme.number), vec!["42"]);
+    }
+
+    #[test]
+    fn rust_lifetimes_are_not_char_literals() {
+        let code = "fn f<'a>(c: char) -> bool { c == 'x' }";
+        let theme = SyntaxTheme::dark();
+        let lines = highlight_code_with_theme(code, Language::Rust, &theme);
+
+        assert_eq!(reconstructed(&lines), code);
+        assert_eq!(spans_with_style(&lines, theme.string), vec!["'x'"]);
+    }
+
+    #[test]
+    fn json_keys_are_properties() {
+        let code = "{\"name\": \"codex\", \"ok\": true}";
+        let theme = SyntaxTheme::dark();
+        let lines = highlight_code_with_theme(code, Language::Json, &theme);
+
+        // Properties and literals share cyan in the dark theme.
+        assert_eq!(
+            spans_with_style(&lines, theme.property),
+            vec!["\"name\"", "\"ok\"", "true"]
+        );
+        assert_eq!(spans_with_style(&lines, theme.string), vec!["\"codex\""]);
+    }
+
+    #[test]
+    fn multiline_strings_and_comments_keep_line_structure() {
+        let code = "x = \"\"\"a\nb\"\"\"\n/* not a comment */";
+        let theme = SyntaxTheme::dark();
+        let lines = highlight_code_with_theme(code, Language::Python, &theme);
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(reconstructed(&lines), code);
+        assert_eq!(
+            spans_with_style(&lines, theme.string),
+            vec!["\"\"\"a", "b\"\"\""]
+        );
+    }
+
+    #[test]
+    fn theme_follows_terminal_capabilities() {
+        assert_eq!(
+            SyntaxTheme::for_terminal(false, Some((0, 0, 0))),
+            SyntaxTheme::monochrome()
+        );
+        assert_eq!(
+            SyntaxTheme::for_terminal(true, Some((255, 255, 255))),
+            SyntaxTheme::light()
+        );
+        assert_eq!(SyntaxTheme::for_terminal(true, None), SyntaxTheme::dark());
+    }
+}
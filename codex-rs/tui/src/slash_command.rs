@@ -20,6 +20,7 @@ pub enum SlashCommand {
     Experimental,
     Skills,
     Review,
+    Findings,
     Rename,
     New,
     Resume,
@@ -31,7 +32,8 @@ pub enum SlashCommand {
     Agent,
     AgentConfig,
     RoleModels,
-    // Undo,
+    Undo,
+    Redo,
     Diff,
     Mention,
     Context,
@@ -65,10 +67,12 @@ impl SlashCommand {
             SlashCommand::Init => "create an AGENTS.md file with instructions for Codex",
             SlashCommand::Compact => "summarize conversation to prevent hitting the context limit",
             SlashCommand::Review => "review my current changes and find issues",
+            SlashCommand::Findings => "go through the findings of the last review",
             SlashCommand::Rename => "rename the current thread",
             SlashCommand::Resume => "resume a saved chat",
             SlashCommand::Fork => "fork the current chat",
-            // SlashCommand::Undo => "ask Codex to undo a turn",
+            SlashCommand::Undo => "browse snapshots of earlier turns and restore one",
+            SlashCommand::Redo => "reapply what the last undo or restore replaced",
             SlashCommand::Quit | SlashCommand::Exit => "exit Codex",
             SlashCommand::Diff => "show git diff (including untracked files)",
             SlashCommand::Mention => "mention a file",
@@ -89,7 +93,9 @@ impl SlashCommand {
             SlashCommand::AgentConfig => "configure agent spawn permissions",
             SlashCommand::RoleModels => "configure role-series model overrides",
             SlashCommand::Approvals => "choose what Codex can do without approval",
-            SlashCommand::Permissions => "choose what Codex is allowed to do",
+            SlashCommand::Permissions => {
+                "review or revoke approvals remembered for this repository"
+            }
             SlashCommand::ElevateSandbox => "set up default agent sandbox",
             SlashCommand::Experimental => "toggle experimental features",
             SlashCommand::Mcp => "list configured MCP tools",
@@ -125,7 +131,8 @@ impl SlashCommand {
             | SlashCommand::Fork
             | SlashCommand::Init
             | SlashCommand::Compact
-            // | SlashCommand::Undo
+            | SlashCommand::Undo
+            | SlashCommand::Redo
             | SlashCommand::Model
             | SlashCommand::Personality
             | SlashCommand::Approvals
@@ -138,6 +145,7 @@ impl SlashCommand {
             | SlashCommand::MemoryDrop
             | SlashCommand::MemoryUpdate => false,
             SlashCommand::Diff
+            | SlashCommand::Findings
             | SlashCommand::Rename
             | SlashCommand::Mention
             | SlashCommand::Context
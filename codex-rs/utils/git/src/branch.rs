@@ -47,6 +47,32 @@ pub fn merge_base_with_head(
     Ok(Some(merge_base))
 }
 
+/// Resolves `rev` to the commit it names, mirroring `git rev-parse --verify <rev>^{commit}`.
+///
+/// Returns `Ok(None)` when `rev` does not name a commit, including revisions that git would
+/// parse as an option.
+pub fn resolve_commit(repo_path: &Path, rev: &str) -> Result<Option<String>, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    if rev.is_empty() || rev.starts_with('-') {
+        return Ok(None);
+    }
+
+    match run_git_for_stdout(
+        repo_path,
+        vec![
+            OsString::from("rev-parse"),
+            OsString::from("--verify"),
+            OsString::from("--quiet"),
+            OsString::from(format!("{rev}^{{commit}}")),
+        ],
+        None,
+    ) {
+        Ok(sha) => Ok(Some(sha)),
+        Err(GitToolingError::GitCommand { .. }) => Ok(None),
+        Err(other) => Err(other),
+    }
+}
+
 fn resolve_branch_ref(repo_root: &Path, branch: &str) -> Result<Option<String>, GitToolingError> {
     let rev = run_git_for_stdout(
         repo_root,
@@ -119,6 +145,7 @@ fn resolve_upstream_if_remote_ahead(
 #[cfg(test)]
 mod tests {
     use super::merge_base_with_head;
+    use super::resolve_commit;
     use crate::GitToolingError;
     use pretty_assertions::assert_eq;
     use std::path::Path;
@@ -253,4 +280,24 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn resolve_commit_accepts_only_commits() -> Result<(), GitToolingError> {
+        let temp = tempdir()?;
+        let repo = temp.path();
+        init_test_repo(repo);
+
+        std::fs::write(repo.join("tracked.txt"), "tracked\n")?;
+        run_git_in(repo, &["add", "tracked.txt"]);
+        commit(repo, "initial");
+
+        let head = run_git_stdout(repo, &["rev-parse", "HEAD"]);
+        assert_eq!(resolve_commit(repo, "main")?, Some(head.clone()));
+        assert_eq!(resolve_commit(repo, &head[..7])?, Some(head));
+        assert_eq!(resolve_commit(repo, "missing-branch")?, None);
+        assert_eq!(resolve_commit(repo, "HEAD:tracked.txt")?, None);
+        assert_eq!(resolve_commit(repo, "--output=x")?, None);
+
+        Ok(())
+    }
 }
@@ -26,6 +26,8 @@ pub enum GitToolingError {
     NonRelativePath { path: PathBuf },
     #[error("path {path:?} escapes the repository root")]
     PathEscapesRepository { path: PathBuf },
+    #[error("cannot restore {path:?} from a snapshot: {reason}")]
+    UnrestorablePath { path: PathBuf, reason: &'static str },
     #[error("failed to process path inside worktree")]
     PathPrefix(#[from] std::path::StripPrefixError),
     #[error(transparent)]
@@ -453,6 +453,218 @@ pub fn restore_ghost_commit_with_options(
     )
 }
 
+/// Restore only `paths` (relative to `options.repo_path`) to their state in the ghost commit.
+///
+/// Paths that are not in the snapshot are deleted only when they are untracked files the
+/// snapshot did not record, i.e. files created after it was captured. Paths inside `.git`
+/// and paths git ignores are rejected, since snapshots never capture them.
+pub fn restore_ghost_commit_paths(
+    options: &RestoreGhostCommitOptions<'_>,
+    commit: &GhostCommit,
+    paths: &[PathBuf],
+) -> Result<(), GitToolingError> {
+    ensure_git_repository(options.repo_path)?;
+
+    let repo_root = resolve_repository_root(options.repo_path)?;
+    let repo_prefix = repo_subdir(repo_root.as_path(), options.repo_path);
+    let mut requested = Vec::with_capacity(paths.len());
+    for path in paths {
+        let relative = normalize_relative_path(path)?;
+        let repo_relative = match repo_prefix.as_deref() {
+            Some(prefix) => prefix.join(&relative),
+            None => relative,
+        };
+        if is_inside_git_dir(&repo_relative) {
+            return Err(GitToolingError::UnrestorablePath {
+                path: path.clone(),
+                reason: "it is inside the .git directory",
+            });
+        }
+        if should_ignore_for_snapshot(&repo_relative) {
+            return Err(GitToolingError::UnrestorablePath {
+                path: path.clone(),
+                reason: "snapshots never capture it",
+            });
+        }
+        requested.push((path, repo_relative));
+    }
+    let ignored = git_ignored_paths(
+        repo_root.as_path(),
+        requested.iter().map(|(_, repo_relative)| repo_relative),
+    )?;
+    if let Some((path, _)) = requested
+        .iter()
+        .find(|(_, repo_relative)| ignored.contains(repo_relative))
+    {
+        return Err(GitToolingError::UnrestorablePath {
+            path: (*path).clone(),
+            reason: "it is ignored by git",
+        });
+    }
+
+    let current_untracked = capture_existing_untracked(
+        repo_root.as_path(),
+        repo_prefix.as_deref(),
+        options.ghost_snapshot.ignore_large_untracked_files,
+        options.ghost_snapshot.ignore_large_untracked_dirs,
+        &[],
+    )?;
+    let mut in_snapshot = Vec::new();
+    let mut not_in_snapshot = Vec::new();
+    for (_, repo_relative) in requested {
+        let listed = run_git_for_stdout(
+            repo_root.as_path(),
+            [
+                OsString::from("ls-tree"),
+                OsString::from("--name-only"),
+                OsString::from(commit.id()),
+                OsString::from("--"),
+                repo_relative.as_os_str().to_os_string(),
+            ],
+            None,
+        )?;
+        if listed.is_empty() {
+            not_in_snapshot.push(repo_relative);
+        } else {
+            in_snapshot.push(repo_relative);
+        }
+    }
+
+    // Only the untracked entries under the requested paths are candidates for removal;
+    // `remove_new_untracked` then keeps the ones the snapshot recorded as pre-existing.
+    let is_requested = |path: &PathBuf| {
+        not_in_snapshot
+            .iter()
+            .any(|requested| path.starts_with(requested))
+    };
+    let new_untracked = UntrackedSnapshot {
+        files: current_untracked
+            .files
+            .into_iter()
+            .filter(is_requested)
+            .collect(),
+        dirs: current_untracked
+            .dirs
+            .into_iter()
+            .filter(is_requested)
+            .collect(),
+        ..Default::default()
+    };
+    remove_new_untracked(
+        repo_root.as_path(),
+        commit.preexisting_untracked_files(),
+        commit.preexisting_untracked_dirs(),
+        new_untracked,
+    )?;
+    if in_snapshot.is_empty() {
+        return Ok(());
+    }
+
+    // Same as a full restore: only the working tree changes, the index is left alone.
+    let mut restore_args = vec![
+        OsString::from("restore"),
+        OsString::from("--source"),
+        OsString::from(commit.id()),
+        OsString::from("--worktree"),
+        OsString::from("--"),
+    ];
+    restore_args.extend(in_snapshot.into_iter().map(PathBuf::into_os_string));
+    run_git_for_status(repo_root.as_path(), restore_args, None)
+}
+
+/// Whether `path` is the repository's `.git` directory or something inside it.
+fn is_inside_git_dir(path: &Path) -> bool {
+    path.components()
+        .any(|component| component == Component::Normal(".git".as_ref()))
+}
+
+/// Returns the untracked entries of `paths` that git ignores. Tracked files are never
+/// reported, even when they match an ignore pattern.
+fn git_ignored_paths<'a>(
+    repo_root: &Path,
+    paths: impl IntoIterator<Item = &'a PathBuf>,
+) -> Result<HashSet<PathBuf>, GitToolingError> {
+    let mut args = vec![
+        OsString::from("check-ignore"),
+        OsString::from("-z"),
+        OsString::from("--"),
+    ];
+    let base_len = args.len();
+    args.extend(
+        paths
+            .into_iter()
+            .map(|path| path.as_os_str().to_os_string()),
+    );
+    if args.len() == base_len {
+        return Ok(HashSet::new());
+    }
+
+    match run_git_for_stdout_all(repo_root, args, None) {
+        Ok(output) => Ok(output
+            .split('\0')
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+            .collect()),
+        // `git check-ignore` exits with 1 when none of the paths is ignored.
+        Err(GitToolingError::GitCommand { status, .. }) if status.code() == Some(1) => {
+            Ok(HashSet::new())
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Lines added and removed in one file between two ghost commits. The counts are `None`
+/// for binary files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GhostDiffStat {
+    pub path: PathBuf,
+    pub added: Option<u64>,
+    pub removed: Option<u64>,
+}
+
+/// Per-file line counts of the changes from `from` to `to`, limited to `repo_path` and with
+/// paths relative to it.
+pub fn ghost_commit_diff_stats(
+    repo_path: &Path,
+    from: &GhostCommit,
+    to: &GhostCommit,
+) -> Result<Vec<GhostDiffStat>, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+
+    let output = run_git_for_stdout_all(
+        repo_path,
+        [
+            "diff",
+            "--numstat",
+            "-z",
+            "--relative",
+            "--no-renames",
+            from.id(),
+            to.id(),
+        ],
+        None,
+    )?;
+    Ok(parse_numstat(&output))
+}
+
+/// Parses `git diff --numstat -z` output, where every record is `added\tremoved\tpath\0`.
+fn parse_numstat(output: &str) -> Vec<GhostDiffStat> {
+    output
+        .split('\0')
+        .filter_map(|record| {
+            let mut fields = record.splitn(3, '\t');
+            let added = fields.next()?;
+            let removed = fields.next()?;
+            let path = fields.next().filter(|path| !path.is_empty())?;
+            Some(GhostDiffStat {
+                path: PathBuf::from(path),
+                added: added.parse().ok(),
+                removed: removed.parse().ok(),
+            })
+        })
+        .collect()
+}
+
 /// Restore the working tree to match the given commit ID.
 pub fn restore_to_commit(repo_path: &Path, commit_id: &str) -> Result<(), GitToolingError> {
     ensure_git_repository(repo_path)?;
@@ -1535,6 +1747,163 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    /// Restoring selected paths leaves every other file alone.
+    fn restore_paths_only_touches_selected_files() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        let repo = temp.path();
+        init_test_repo(repo);
+        std::fs::write(repo.join("a.txt"), "a before\n")?;
+        std::fs::write(repo.join("b.txt"), "b before\n")?;
+        run_git_in(repo, &["add", "."]);
+        run_git_in(
+            repo,
+            &[
+                "-c",
+                "user.name=Tester",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-m",
+                "initial",
+            ],
+        );
+
+        let ghost = create_ghost_commit(&CreateGhostCommitOptions::new(repo))?;
+
+        std::fs::write(repo.join("a.txt"), "a after\n")?;
+        std::fs::write(repo.join("b.txt"), "b after\n")?;
+        std::fs::write(repo.join("new.txt"), "new\n")?;
+
+        restore_ghost_commit_paths(
+            &RestoreGhostCommitOptions::new(repo),
+            &ghost,
+            &[PathBuf::from("a.txt"), PathBuf::from("new.txt")],
+        )?;
+
+        assert_eq!(std::fs::read_to_string(repo.join("a.txt"))?, "a before\n");
+        assert_eq!(std::fs::read_to_string(repo.join("b.txt"))?, "b after\n");
+        assert!(!repo.join("new.txt").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    /// Restoring selected paths refuses to touch the `.git` directory.
+    fn restore_paths_rejects_git_directory() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        let repo = temp.path();
+        init_test_repo(repo);
+        std::fs::write(repo.join("a.txt"), "a\n")?;
+        let ghost = create_ghost_commit(&CreateGhostCommitOptions::new(repo))?;
+
+        for path in [".git", ".git/config", "nested/.git"] {
+            let err = restore_ghost_commit_paths(
+                &RestoreGhostCommitOptions::new(repo),
+                &ghost,
+                &[PathBuf::from(path)],
+            )
+            .expect_err("restoring inside .git should fail");
+            assert_matches!(err, GitToolingError::UnrestorablePath { .. });
+        }
+        assert!(repo.join(".git").join("config").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    /// Ignored paths are never captured, so restoring them is rejected instead of deleting
+    /// them.
+    fn restore_paths_rejects_ignored_paths() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        let repo = temp.path();
+        init_test_repo(repo);
+        std::fs::write(repo.join(".gitignore"), "target/\nsecrets.txt\n")?;
+        std::fs::write(repo.join("a.txt"), "a before\n")?;
+        let ghost = create_ghost_commit(&CreateGhostCommitOptions::new(repo))?;
+
+        std::fs::create_dir_all(repo.join("target"))?;
+        std::fs::write(repo.join("target").join("app"), "binary\n")?;
+        std::fs::write(repo.join("secrets.txt"), "token\n")?;
+        std::fs::write(repo.join(".env"), "KEY=value\n")?;
+        std::fs::write(repo.join("a.txt"), "a after\n")?;
+
+        for path in ["target", "secrets.txt", ".env"] {
+            let err = restore_ghost_commit_paths(
+                &RestoreGhostCommitOptions::new(repo),
+                &ghost,
+                &[PathBuf::from("a.txt"), PathBuf::from(path)],
+            )
+            .expect_err("restoring an ignored path should fail");
+            assert_matches!(err, GitToolingError::UnrestorablePath { .. });
+        }
+
+        // Nothing is restored or removed when any requested path is rejected.
+        assert_eq!(std::fs::read_to_string(repo.join("a.txt"))?, "a after\n");
+        assert!(repo.join("target").join("app").exists());
+        assert!(repo.join("secrets.txt").exists());
+        assert!(repo.join(".env").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    /// Paths missing from the snapshot are removed only if they are new untracked files.
+    fn restore_paths_keeps_tracked_files_missing_from_snapshot() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        let repo = temp.path();
+        init_test_repo(repo);
+        std::fs::write(repo.join("a.txt"), "a\n")?;
+        let ghost = create_ghost_commit(&CreateGhostCommitOptions::new(repo))?;
+
+        std::fs::write(repo.join("added.txt"), "staged\n")?;
+        run_git_in(repo, &["add", "added.txt"]);
+
+        restore_ghost_commit_paths(
+            &RestoreGhostCommitOptions::new(repo),
+            &ghost,
+            &[PathBuf::from("added.txt"), PathBuf::from("missing.txt")],
+        )?;
+
+        assert!(repo.join("added.txt").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    /// Diff stats between two snapshots report per-file line counts.
+    fn diff_stats_between_ghost_commits() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        let repo = temp.path();
+        init_test_repo(repo);
+        std::fs::write(repo.join("lib.rs"), "one\ntwo\n")?;
+
+        let before = create_ghost_commit(&CreateGhostCommitOptions::new(repo))?;
+        std::fs::write(repo.join("lib.rs"), "one\nthree\nfour\n")?;
+        std::fs::write(repo.join("data.bin"), [0u8, 1, 2, 0])?;
+        let after = create_ghost_commit(&CreateGhostCommitOptions::new(repo))?;
+
+        let mut stats = ghost_commit_diff_stats(repo, &before, &after)?;
+        stats.sort_by(|a, b| a.path.cmp(&b.path));
+        assert_eq!(
+            stats,
+            vec![
+                GhostDiffStat {
+                    path: PathBuf::from("data.bin"),
+                    added: None,
+                    removed: None,
+                },
+                GhostDiffStat {
+                    path: PathBuf::from("lib.rs"),
+                    added: Some(2),
+                    removed: Some(1),
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
     #[test]
     /// Restoring from a subdirectory preserves ignored files in parent folders.
     fn restore_from_subdirectory_preserves_parent_vscode() -> Result<(), GitToolingError> {
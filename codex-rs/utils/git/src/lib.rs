@@ -15,8 +15,10 @@ pub use apply::extract_paths_from_patch;
 pub use apply::parse_git_apply_output;
 pub use apply::stage_paths;
 pub use branch::merge_base_with_head;
+pub use branch::resolve_commit;
 pub use errors::GitToolingError;
 pub use ghost_commits::CreateGhostCommitOptions;
+pub use ghost_commits::GhostDiffStat;
 pub use ghost_commits::GhostSnapshotConfig;
 pub use ghost_commits::GhostSnapshotReport;
 pub use ghost_commits::IgnoredUntrackedFile;
@@ -25,7 +27,9 @@ pub use ghost_commits::RestoreGhostCommitOptions;
 pub use ghost_commits::capture_ghost_snapshot_report;
 pub use ghost_commits::create_ghost_commit;
 pub use ghost_commits::create_ghost_commit_with_report;
+pub use ghost_commits::ghost_commit_diff_stats;
 pub use ghost_commits::restore_ghost_commit;
+pub use ghost_commits::restore_ghost_commit_paths;
 pub use ghost_commits::restore_ghost_commit_with_options;
 pub use ghost_commits::restore_to_commit;
 pub use platform::create_symlink;
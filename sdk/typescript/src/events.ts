@@ -68,6 +68,19 @@ export type ThreadErrorEvent = {
   message: string;
 };
 
+/** Which `codex exec` budget flag was exceeded. */
+export type BudgetKind = "max_turns" | "max_tokens" | "max_tool_calls" | "timeout";
+
+/** Emitted as the final event when a run is aborted because a budget was exceeded. */
+export type BudgetExceededEvent = {
+  type: "budget.exceeded";
+  budget: BudgetKind;
+  /** The configured limit (seconds for `timeout`). */
+  limit: number;
+  /** Consumption at the time the budget tripped. */
+  used: number;
+};
+
 /** Top-level JSONL events emitted by codex exec. */
 export type ThreadEvent =
   | ThreadStartedEvent
@@ -77,4 +90,5 @@ export type ThreadEvent =
   | ItemStartedEvent
   | ItemUpdatedEvent
   | ItemCompletedEvent
-  | ThreadErrorEvent;
+  | ThreadErrorEvent
+  | BudgetExceededEvent;
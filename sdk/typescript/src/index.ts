@@ -9,6 +9,8 @@ export type {
   ItemCompletedEvent,
   ThreadError,
   ThreadErrorEvent,
+  BudgetExceededEvent,
+  BudgetKind,
   Usage,
 } from "./events";
 export type {
@@ -16,6 +18,7 @@ export type {
   AgentMessageItem,
   ReasoningItem,
   CommandExecutionItem,
+  DecisionProvenance,
   FileChangeItem,
   McpToolCallItem,
   WebSearchItem,
@@ -2,9 +2,28 @@
 
 import type { ContentBlock as McpContentBlock } from "@modelcontextprotocol/sdk/types.js";
 
+import type { ApprovalMode } from "./threadOptions";
+
 /** The status of a command execution. */
 export type CommandExecutionStatus = "in_progress" | "completed" | "failed";
 
+/** What allowed a command to run, or rejected it. */
+export type DecisionProvenance =
+  | { type: "known_safe_command" }
+  | { type: "dangerous_command" }
+  | {
+      type: "exec_policy_rule";
+      matched_prefix: string[];
+      justification?: string;
+      /** Rules file that declared the matching rule. */
+      file?: string;
+      /** 1-based line of the `prefix_rule(...)` call in `file`. */
+      line?: number;
+    }
+  | { type: "session_approval" }
+  | { type: "sandbox_denial_retry" }
+  | { type: "approval_policy"; approval_policy: ApprovalMode };
+
 /** A command executed by the agent. */
 export type CommandExecutionItem = {
   id: string;
@@ -17,6 +36,8 @@ export type CommandExecutionItem = {
   exit_code?: number;
   /** Current status of the command execution. */
   status: CommandExecutionStatus;
+  /** What allowed the command to run or rejected it. Set once the command finishes. */
+  provenance?: DecisionProvenance;
 };
 
 /** Indicates the type of the file change. */